@@ -1,6 +1,18 @@
-pub const ANTHROPIC_VAR_NAME: &str = "ANTHROPIC_API_KEY";
-pub const GEMINI_VAR_NAME: &str = "GOOGLE_GEMINI_API_KEY";
-pub const LLAMA_VAR_NAME: &str = "META_LLAMA_API_KEY";
-pub const MISTRAL_VAR_NAME: &str = "MISTRAL_API_KEY";
-pub const OPENAI_VAR_NAME: &str = "OPENAI_API_KEY";
-pub const PERPLEXITY_VAR_NAME: &str = "PERPLEXITY_API_KEY";
+// Each provider reads `<PREFIX>_API_KEY`, `<PREFIX>_BASE_URL`, `<PREFIX>_ORG` and `<PREFIX>_MODEL`.
+pub const ANTHROPIC_ENV_PREFIX: &str = "ANTHROPIC";
+pub const GEMINI_ENV_PREFIX: &str = "GOOGLE_GEMINI";
+pub const LLAMA_ENV_PREFIX: &str = "META_LLAMA";
+pub const MISTRAL_ENV_PREFIX: &str = "MISTRAL";
+pub const OPENAI_ENV_PREFIX: &str = "OPENAI";
+pub const OPENAI_COMPATIBLE_ENV_PREFIX: &str = "OPENAI_COMPATIBLE";
+pub const PERPLEXITY_ENV_PREFIX: &str = "PERPLEXITY";
+
+pub const ANTHROPIC_BASE_URL: &str = "https://api.anthropic.com/v1";
+pub const GEMINI_BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
+pub const LLAMA_BASE_URL: &str = "https://api.llama-api.com";
+pub const MISTRAL_BASE_URL: &str = "https://api.mistral.ai/v1";
+pub const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+pub const PERPLEXITY_BASE_URL: &str = "https://api.perplexity.ai";
+
+// llama.cpp serves whichever model it was started with and ignores the name.
+pub const OPENAI_COMPATIBLE_DEFAULT_MODEL: &str = "default";
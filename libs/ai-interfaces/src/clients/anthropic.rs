@@ -1,6 +1,13 @@
-use crate::ai_constants::ANTHROPIC_VAR_NAME;
 use crate::clients::bulk::Role as SuperRole;
-use crate::clients::bulk::{ChatCompletionExt, Message};
+use crate::clients::bulk::{
+    split_system_prompt, ChatCompletionExt, ClientKind, Completion, CompletionOptions, Message,
+    TokenUsage,
+};
+use crate::clients::config::ProviderConfig;
+#[cfg(test)]
+use crate::clients::sse::collect_fixture;
+use crate::clients::sse::{delta_stream, CompletionStream, SseEvent, StreamEvent};
+use crate::error::AiInterfaceError;
 use crate::models::anthropic::AnthropicModels;
 use crate::models::base::ModelName;
 use anyhow::{anyhow, Context};
@@ -8,30 +15,19 @@ use async_trait::async_trait;
 use dotenv::dotenv;
 use reqwest::Client;
 use serde::{Deserialize, Serialize, Serializer};
-use std::env::VarError;
 use std::fmt::{Display, Formatter};
 
+const DEFAULT_MAX_TOKENS: u32 = 1024;
+
 #[async_trait]
 impl ChatCompletionExt for AnthropicClient {
     async fn completion(
         &self,
         model_name: ModelName,
         messages: Vec<Message>,
-    ) -> anyhow::Result<Message> {
-        let request = ChatRequest {
-            model: model_name.to_string(),
-            max_tokens: 1024,
-            messages: messages
-                .into_iter()
-                .map(|msg| {
-                    if matches!(msg.role, SuperRole::User) {
-                        ChatMessage::user(msg.content)
-                    } else {
-                        ChatMessage::assistant(msg.content)
-                    }
-                })
-                .collect(),
-        };
+        options: &CompletionOptions,
+    ) -> anyhow::Result<Completion> {
+        let request = ChatRequest::from_messages(model_name, messages, options);
         let mut result = self.chat_completion(&request).await?;
         let role = match result.role {
             Role::User => SuperRole::User,
@@ -42,57 +38,78 @@ impl ChatCompletionExt for AnthropicClient {
             .pop()
             .context("Anthropic returned no completion message")?
             .text;
-        Ok(Message { role, content })
+        let usage = TokenUsage {
+            input_tokens: result.usage.input_tokens,
+            output_tokens: result.usage.output_tokens,
+        };
+        Ok(Completion {
+            message: Message { role, content },
+            usage,
+        })
+    }
+
+    async fn completion_stream(
+        &self,
+        model_name: ModelName,
+        messages: Vec<Message>,
+        options: &CompletionOptions,
+    ) -> anyhow::Result<CompletionStream> {
+        let mut request = ChatRequest::from_messages(model_name, messages, options);
+        request.stream = Some(true);
+        let response = self.send(&request).await?;
+        Ok(delta_stream(response.bytes_stream(), stream_event))
     }
 }
 pub struct AnthropicClient {
     client: Client,
-    api_key: String,
+    config: ProviderConfig,
 }
 
 impl AnthropicClient {
-    pub fn new(client: Client, api_key: String) -> Self {
-        Self { client, api_key }
+    pub fn new(client: Client, config: ProviderConfig) -> Self {
+        Self { client, config }
     }
-    pub fn from_env(client: Client, env_var_name: &str) -> Result<Self, VarError> {
-        let api_key = std::env::var(env_var_name)?;
-        Ok(Self::new(client, api_key))
+
+    pub fn from_env(client: Client) -> Result<Self, AiInterfaceError> {
+        Ok(Self::new(
+            client,
+            ProviderConfig::from_env(ClientKind::Anthropic)?,
+        ))
     }
 
     async fn chat_completion(&self, chat_request: &ChatRequest) -> anyhow::Result<ChatResponse> {
-        let url = "https://api.anthropic.com/v1/messages";
+        Ok(self.send(chat_request).await?.json().await?)
+    }
+
+    async fn send(&self, chat_request: &ChatRequest) -> anyhow::Result<reqwest::Response> {
+        let url = self.config.url("messages");
         let response = self
             .client
             .post(url)
-            .header("x-api-key", &self.api_key)
+            .header("x-api-key", self.config.api_key(ClientKind::Anthropic)?)
             .header("anthropic-version", "2023-06-01")
             .json(chat_request)
             .send()
             .await?;
         if response.status().is_success() {
-            return Ok(response.json().await?);
+            return Ok(response);
         }
-        if response.status().is_client_error() {
-            let error: Error = response.json().await?;
-            return Err(anyhow!(error));
-        }
-        Err(anyhow!(
-            "Unexpected response status code {} for Anthorpic chat completion request",
-            response.status()
-        ))
+        Err(
+            AiInterfaceError::from_response(ClientKind::Anthropic, response)
+                .await
+                .into(),
+        )
     }
 }
 
-#[derive(Deserialize, Debug)]
-struct Error {
-    #[serde(rename = "type")]
-    kind: String,
-    error: InnerError,
-}
-
-impl Display for Error {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:#?}", self.error)
+fn stream_event(event: &SseEvent) -> anyhow::Result<StreamEvent> {
+    match serde_json::from_str(&event.data)? {
+        StreamChunk::ContentBlockDelta {
+            delta: Delta::TextDelta { text },
+        } => Ok(StreamEvent::Delta(text)),
+        StreamChunk::MessageStop => Ok(StreamEvent::Done),
+        StreamChunk::Error { error } => Err(anyhow!("{:#?}", error)),
+        _ => Ok(StreamEvent::Skip),
     }
 }
 
@@ -115,6 +132,42 @@ struct ChatRequest {
     model: String,
     max_tokens: u32,
     messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
+}
+
+impl ChatRequest {
+    fn from_messages(
+        model_name: ModelName,
+        messages: Vec<Message>,
+        options: &CompletionOptions,
+    ) -> Self {
+        let (system, messages) = split_system_prompt(messages);
+        Self {
+            model: model_name.to_string(),
+            max_tokens: options.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
+            messages: messages
+                .into_iter()
+                .map(|msg| {
+                    if matches!(msg.role, SuperRole::User) {
+                        ChatMessage::user(msg.content)
+                    } else {
+                        ChatMessage::assistant(msg.content)
+                    }
+                })
+                .collect(),
+            system,
+            temperature: options.temperature,
+            top_p: options.top_p,
+            stream: None,
+        }
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -143,6 +196,30 @@ struct Usage {
     output_tokens: u32,
 }
 
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamChunk {
+    ContentBlockDelta {
+        delta: Delta,
+    },
+    MessageStop,
+    Error {
+        error: InnerError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Delta {
+    TextDelta {
+        text: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 struct ChatMessage {
     role: Role,
@@ -169,13 +246,47 @@ impl ChatMessage {
 #[tokio::test]
 async fn anthropic() {
     dotenv().ok();
-    let client = AnthropicClient::from_env(Client::new(), ANTHROPIC_VAR_NAME).unwrap();
+    let client = AnthropicClient::from_env(Client::new()).unwrap();
     let result = client
-        .chat_completion(&ChatRequest {
-            model: ModelName::Anthropic(AnthropicModels::default()).to_string(),
-            max_tokens: 1024,
-            messages: vec![ChatMessage::user(String::from("Introduce yourself"))],
-        })
+        .chat_completion(&ChatRequest::from_messages(
+            ModelName::Anthropic(AnthropicModels::default()),
+            vec![
+                Message::system("Answer in one sentence"),
+                Message::user("Introduce yourself"),
+            ],
+            &CompletionOptions::default(),
+        ))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn anthropic_stream_fixture() {
+    let text = collect_fixture(include_str!("../../fixtures/anthropic.sse"), stream_event)
         .await
         .unwrap();
+    assert_eq!(text, "Hello! I'm Claude, an AI assistant.");
+    let error = collect_fixture(
+        include_str!("../../fixtures/anthropic_error.sse"),
+        stream_event,
+    )
+    .await
+    .unwrap_err();
+    assert!(error.to_string().contains("Overloaded"));
+}
+
+#[test]
+fn anthropic_system_prompt_is_top_level() {
+    let request = ChatRequest::from_messages(
+        ModelName::Anthropic(AnthropicModels::default()),
+        vec![Message::system("Be brief"), Message::user("Hi")],
+        &CompletionOptions {
+            max_tokens: Some(64),
+            ..CompletionOptions::default()
+        },
+    );
+    let value = serde_json::to_value(&request).unwrap();
+    assert_eq!(value["system"], "Be brief");
+    assert_eq!(value["max_tokens"], 64);
+    assert_eq!(value["messages"].as_array().unwrap().len(), 1);
 }
@@ -1,14 +1,15 @@
-use crate::ai_constants::{
-    ANTHROPIC_VAR_NAME, GEMINI_VAR_NAME, LLAMA_VAR_NAME, MISTRAL_VAR_NAME, OPENAI_VAR_NAME,
-    PERPLEXITY_VAR_NAME,
-};
+use crate::ai_constants::OPENAI_COMPATIBLE_DEFAULT_MODEL;
 use crate::clients::anthropic::AnthropicClient;
-use crate::clients::bulk::ClientKind::Perplexity;
+use crate::clients::compare::CompareOptions;
+use crate::clients::config::{AIClientConfig, ProviderConfig};
 use crate::clients::google::GeminiClient;
 use crate::clients::meta::LlamaClient;
 use crate::clients::mistral::MistralClient;
 use crate::clients::openai::OpenAiClient;
+use crate::clients::openai_compatible::OpenAiCompatibleClient;
 use crate::clients::perplexity::PerplexityClient;
+use crate::clients::sse::CompletionStream;
+use crate::error::AiInterfaceError;
 use crate::models::anthropic::AnthropicModels;
 use crate::models::base::ModelName;
 use crate::models::google::GoogleModels;
@@ -16,149 +17,235 @@ use crate::models::meta::MetaModels;
 use crate::models::mistral::MistralModels;
 use crate::models::open_ai::OpenAiModels;
 use crate::models::perplexity::PerplexityModels;
+use crate::models::pricing::cost_usd;
 use async_trait::async_trait;
-use dotenv::dotenv;
-use reqwest::Client;
+use enum_iterator::{all, Sequence};
+use futures::future::join_all;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use std::env::VarError;
-use std::fs::File;
-use std::io::Write;
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
 
 pub struct AIClient {
-    anthropic: AnthropicClient,
-    google: GeminiClient,
-    meta: LlamaClient,
-    mistral: MistralClient,
-    openai: OpenAiClient,
-    perplexity: PerplexityClient,
+    anthropic: Option<AnthropicClient>,
+    google: Option<GeminiClient>,
+    meta: Option<LlamaClient>,
+    mistral: Option<MistralClient>,
+    openai: Option<OpenAiClient>,
+    openai_compatible: Option<OpenAiCompatibleClient>,
+    perplexity: Option<PerplexityClient>,
+    default_models: HashMap<ClientKind, ModelName>,
 }
 
-#[derive(Hash, PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+#[derive(
+    Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Clone, Copy, Debug, Sequence,
+)]
 pub enum ClientKind {
     Anthropic,
     Google,
     Meta,
     Mistral,
     OpenAi,
+    OpenAiCompatible,
     Perplexity,
 }
 
+impl ClientKind {
+    pub fn default_model(&self) -> ModelName {
+        match self {
+            ClientKind::Anthropic => ModelName::Anthropic(AnthropicModels::default()),
+            ClientKind::Google => ModelName::Google(GoogleModels::default()),
+            ClientKind::Meta => ModelName::Meta(MetaModels::default()),
+            ClientKind::Mistral => ModelName::Mistral(MistralModels::default()),
+            ClientKind::OpenAi => ModelName::OpenAi(OpenAiModels::default()),
+            ClientKind::OpenAiCompatible => {
+                ModelName::OpenAiCompatible(String::from(OPENAI_COMPATIBLE_DEFAULT_MODEL))
+            }
+            ClientKind::Perplexity => ModelName::Perplexity(PerplexityModels::default()),
+        }
+    }
+}
+
+impl Display for ClientKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientKind::Anthropic => write!(f, "Anthropic"),
+            ClientKind::Google => write!(f, "Google"),
+            ClientKind::Meta => write!(f, "Meta"),
+            ClientKind::Mistral => write!(f, "Mistral"),
+            ClientKind::OpenAi => write!(f, "OpenAi"),
+            ClientKind::OpenAiCompatible => write!(f, "OpenAiCompatible"),
+            ClientKind::Perplexity => write!(f, "Perplexity"),
+        }
+    }
+}
+
+impl From<&ModelName> for ClientKind {
+    fn from(model_name: &ModelName) -> Self {
+        match model_name {
+            ModelName::OpenAi(_) => ClientKind::OpenAi,
+            ModelName::Anthropic(_) => ClientKind::Anthropic,
+            ModelName::Google(_) => ClientKind::Google,
+            ModelName::Perplexity(_) => ClientKind::Perplexity,
+            ModelName::Meta(_) => ClientKind::Meta,
+            ModelName::Mistral(_) => ClientKind::Mistral,
+            ModelName::OpenAiCompatible(_) => ClientKind::OpenAiCompatible,
+        }
+    }
+}
+
 impl AIClient {
-    pub fn new() -> Result<Self, VarError> {
+    /// Providers missing from the environment are skipped and fail with `NotConfigured`.
+    pub fn new() -> Self {
+        Self::from_config(AIClientConfig::from_env())
+    }
+
+    pub fn from_config(config: AIClientConfig) -> Self {
         let client = reqwest::Client::new();
-        let anthropic = AnthropicClient::from_env(client.clone(), ANTHROPIC_VAR_NAME)?;
-        let google = GeminiClient::from_env(client.clone(), GEMINI_VAR_NAME)?;
-        let meta = LlamaClient::from_env(client.clone(), LLAMA_VAR_NAME)?;
-        let mistral = MistralClient::from_env(client.clone(), MISTRAL_VAR_NAME)?;
-        let openai = OpenAiClient::from_env(client.clone(), OPENAI_VAR_NAME)?;
-        let perplexity = PerplexityClient::from_env(client, PERPLEXITY_VAR_NAME)?;
-        Ok(Self {
-            anthropic,
-            google,
-            meta,
-            mistral,
-            openai,
-            perplexity,
-        })
+        let provider = |kind: ClientKind| config.providers.get(&kind).cloned();
+        Self {
+            anthropic: provider(ClientKind::Anthropic)
+                .map(|c| AnthropicClient::new(client.clone(), c)),
+            google: provider(ClientKind::Google).map(|c| GeminiClient::new(client.clone(), c)),
+            meta: provider(ClientKind::Meta).map(|c| LlamaClient::new(client.clone(), c)),
+            mistral: provider(ClientKind::Mistral).map(|c| MistralClient::new(client.clone(), c)),
+            openai: provider(ClientKind::OpenAi).map(|c| OpenAiClient::new(client.clone(), c)),
+            openai_compatible: provider(ClientKind::OpenAiCompatible)
+                .map(|c| OpenAiCompatibleClient::new(client.clone(), c)),
+            perplexity: provider(ClientKind::Perplexity)
+                .map(|c| PerplexityClient::new(client.clone(), c)),
+            default_models: config
+                .providers
+                .iter()
+                .filter_map(|(kind, c)| Some((*kind, c.default_model.clone()?)))
+                .collect(),
+        }
+    }
+
+    pub fn default_model(&self, kind: ClientKind) -> ModelName {
+        self.default_models
+            .get(&kind)
+            .cloned()
+            .unwrap_or_else(|| kind.default_model())
     }
+
+    fn client(
+        &self,
+        kind: ClientKind,
+    ) -> Result<&(dyn ChatCompletionExt + Sync), AiInterfaceError> {
+        let client: Option<&(dyn ChatCompletionExt + Sync)> = match kind {
+            ClientKind::Anthropic => self.anthropic.as_ref().map(|c| c as _),
+            ClientKind::Google => self.google.as_ref().map(|c| c as _),
+            ClientKind::Meta => self.meta.as_ref().map(|c| c as _),
+            ClientKind::Mistral => self.mistral.as_ref().map(|c| c as _),
+            ClientKind::OpenAi => self.openai.as_ref().map(|c| c as _),
+            ClientKind::OpenAiCompatible => self.openai_compatible.as_ref().map(|c| c as _),
+            ClientKind::Perplexity => self.perplexity.as_ref().map(|c| c as _),
+        };
+        client.ok_or(AiInterfaceError::NotConfigured(kind))
+    }
+
+    pub async fn completion(
+        &self,
+        model_name: ModelName,
+        messages: Vec<Message>,
+        options: &CompletionOptions,
+    ) -> anyhow::Result<Completion> {
+        self.client(ClientKind::from(&model_name))?
+            .completion(model_name, messages, options)
+            .await
+    }
+
+    pub async fn completion_stream(
+        &self,
+        model_name: ModelName,
+        messages: Vec<Message>,
+        options: &CompletionOptions,
+    ) -> anyhow::Result<CompletionStream> {
+        self.client(ClientKind::from(&model_name))?
+            .completion_stream(model_name, messages, options)
+            .await
+    }
+
+    pub fn configured_kinds(&self) -> Vec<ClientKind> {
+        all::<ClientKind>()
+            .filter(|kind| self.client(*kind).is_ok())
+            .collect()
+    }
+
     pub async fn completions(
         &self,
         client_kinds: impl Into<HashSet<ClientKind>>,
         messages: Vec<Message>,
-    ) -> AIClientResponses {
-        let mut responses = AIClientResponses::default();
-        for kind in client_kinds.into().into_iter() {
-            match kind {
-                ClientKind::Perplexity => {
-                    responses.insert(
-                        ClientKind::Perplexity,
-                        Some(
-                            self.perplexity
-                                .completion(
-                                    ModelName::Perplexity(PerplexityModels::default()),
-                                    messages.clone(),
-                                )
-                                .await,
-                        ),
-                    );
-                }
-                ClientKind::Anthropic => {
-                    responses.insert(
-                        ClientKind::Anthropic,
-                        Some(
-                            self.anthropic
-                                .completion(
-                                    ModelName::Anthropic(AnthropicModels::default()),
-                                    messages.clone(),
-                                )
-                                .await,
-                        ),
-                    );
-                }
-                ClientKind::Google => {
-                    responses.insert(
-                        ClientKind::Google,
-                        Some(
-                            self.google
-                                .completion(
-                                    ModelName::Google(GoogleModels::default()),
-                                    messages.clone(),
-                                )
-                                .await,
-                        ),
-                    );
-                }
-                ClientKind::Meta => {
-                    responses.insert(
-                        ClientKind::Meta,
-                        Some(
-                            self.meta
-                                .completion(
-                                    ModelName::Meta(MetaModels::default()),
-                                    messages.clone(),
-                                )
-                                .await,
-                        ),
-                    );
-                }
-                ClientKind::Mistral => {
-                    responses.insert(
-                        ClientKind::Mistral,
-                        Some(
-                            self.mistral
-                                .completion(
-                                    ModelName::Mistral(MistralModels::default()),
-                                    messages.clone(),
-                                )
-                                .await,
-                        ),
-                    );
-                }
-                ClientKind::OpenAi => {
-                    responses.insert(
-                        ClientKind::OpenAi,
-                        Some(
-                            self.openai
-                                .completion(
-                                    ModelName::OpenAi(OpenAiModels::default()),
-                                    messages.clone(),
-                                )
-                                .await,
-                        ),
-                    );
-                }
-            };
-        }
-        println!("Question: {:?}, responses: {:?}", messages, responses);
+        options: &CompareOptions,
+    ) -> Vec<Response> {
+        let mut responses = join_all(client_kinds.into().into_iter().map(|kind| {
+            self.timed_completion(
+                self.default_model(kind),
+                messages.clone(),
+                &options.completion,
+                options.timeout_for(kind),
+            )
+        }))
+        .await;
+        responses.sort_by_key(|response| response.client);
         responses
     }
+
+    pub(crate) async fn timed_completion(
+        &self,
+        model_name: ModelName,
+        messages: Vec<Message>,
+        options: &CompletionOptions,
+        timeout: Duration,
+    ) -> Response {
+        let client = ClientKind::from(&model_name);
+        let question = messages
+            .last()
+            .map(|msg| msg.content.clone())
+            .unwrap_or_default();
+        let started = Instant::now();
+        let result = tokio::time::timeout(
+            timeout,
+            self.completion(model_name.clone(), messages, options),
+        )
+        .await
+        .unwrap_or_else(|_| Err(AiInterfaceError::Timeout(client).into()));
+        let latency_ms = started.elapsed().as_millis() as u64;
+        match result {
+            Ok(completion) => Response {
+                client,
+                model: model_name.to_string(),
+                question,
+                answer: Some(completion.message.content),
+                error: None,
+                latency_ms,
+                usage: completion.usage,
+                cost_usd: cost_usd(&model_name, &completion.usage),
+            },
+            Err(e) => Response {
+                client,
+                model: model_name.to_string(),
+                question,
+                answer: None,
+                error: Some(e.to_string()),
+                latency_ms,
+                usage: TokenUsage::default(),
+                cost_usd: None,
+            },
+        }
+    }
 }
 
-pub type AIClientResponses = HashMap<ClientKind, Option<anyhow::Result<Message>>>;
+impl Default for AIClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Role {
+    System,
     User,
     Assistant,
 }
@@ -169,18 +256,119 @@ pub struct Message {
     pub role: Role,
 }
 
+impl Message {
+    pub fn system(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            role: Role::System,
+        }
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            role: Role::User,
+        }
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            role: Role::Assistant,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Completion {
+    pub message: Message,
+    pub usage: TokenUsage,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompletionOptions {
+    pub temperature: Option<f32>,
+    pub max_tokens: Option<u32>,
+    pub top_p: Option<f32>,
+}
+
+/// Providers without a system role take the prompt as a separate field.
+pub(crate) fn split_system_prompt(messages: Vec<Message>) -> (Option<String>, Vec<Message>) {
+    let (system, messages): (Vec<Message>, Vec<Message>) = messages
+        .into_iter()
+        .partition(|msg| msg.role == Role::System);
+    let system = system
+        .into_iter()
+        .map(|msg| msg.content)
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    ((!system.is_empty()).then_some(system), messages)
+}
+
 #[async_trait]
 pub trait ChatCompletionExt {
     async fn completion(
         &self,
         model_name: ModelName,
         messages: Vec<Message>,
-    ) -> anyhow::Result<Message>;
+        options: &CompletionOptions,
+    ) -> anyhow::Result<Completion>;
+
+    async fn completion_stream(
+        &self,
+        model_name: ModelName,
+        messages: Vec<Message>,
+        options: &CompletionOptions,
+    ) -> anyhow::Result<CompletionStream>;
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize)]
 pub struct Response {
-    model: ClientKind,
-    question: String,
-    answer: String,
+    pub client: ClientKind,
+    pub model: String,
+    pub question: String,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub latency_ms: u64,
+    pub usage: TokenUsage,
+    pub cost_usd: Option<f64>,
+}
+
+#[tokio::test]
+async fn test_completions_against_local_server() {
+    use crate::clients::openai_compatible::mock_server;
+
+    let (base_url, _) = mock_server(
+        200,
+        r#"{"choices":[{"message":{"role":"assistant","content":"4"}}]}"#,
+    )
+    .await;
+    let client = AIClient::from_config(AIClientConfig::default().with_provider(
+        ClientKind::OpenAiCompatible,
+        ProviderConfig {
+            default_model: Some(ModelName::OpenAiCompatible(String::from("qwen2.5"))),
+            ..ProviderConfig::new(base_url, None)
+        },
+    ));
+    assert_eq!(
+        client.configured_kinds(),
+        vec![ClientKind::OpenAiCompatible]
+    );
+    let responses = client
+        .completions(
+            [ClientKind::OpenAiCompatible, ClientKind::OpenAi],
+            vec![Message::user("2+2?")],
+            &CompareOptions::default(),
+        )
+        .await;
+    assert_eq!(responses[0].client, ClientKind::OpenAi);
+    assert!(responses[0].error.is_some());
+    assert_eq!(responses[1].model, "openai-compatible/qwen2.5");
+    assert_eq!(responses[1].answer.as_deref(), Some("4"));
 }
@@ -0,0 +1,328 @@
+use crate::clients::bulk::{AIClient, ClientKind, CompletionOptions, Message, Response};
+use crate::models::base::ModelName;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::time::Duration;
+
+const SCORE_PROMPT: &str = r#"You are judging answers to the same question from different AI models.
+Reply only with JSON of the form {"scores":[{"answer":1,"score":7,"reason":"..."}]},
+scoring every answer from 0 to 10 for correctness, completeness and clarity."#;
+
+const MERGE_PROMPT: &str = r#"You are given answers to the same question from different AI models.
+Combine them into a single best answer, correcting any mistakes you find.
+Reply with the merged answer only."#;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JudgeMode {
+    Score,
+    Merge,
+}
+
+impl From<&str> for JudgeMode {
+    fn from(value: &str) -> Self {
+        match value {
+            "merge" => JudgeMode::Merge,
+            _ => JudgeMode::Score,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Judge {
+    pub model_name: ModelName,
+    pub mode: JudgeMode,
+}
+
+#[derive(Debug, Clone)]
+pub struct CompareOptions {
+    pub completion: CompletionOptions,
+    pub timeout: Duration,
+    pub timeouts: HashMap<ClientKind, Duration>,
+    pub judge: Option<Judge>,
+}
+
+impl Default for CompareOptions {
+    fn default() -> Self {
+        Self {
+            completion: CompletionOptions::default(),
+            timeout: Duration::from_secs(60),
+            timeouts: HashMap::new(),
+            judge: None,
+        }
+    }
+}
+
+impl CompareOptions {
+    pub fn timeout_for(&self, kind: ClientKind) -> Duration {
+        self.timeouts.get(&kind).copied().unwrap_or(self.timeout)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Score {
+    pub client: ClientKind,
+    pub score: f32,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Verdict {
+    pub mode: JudgeMode,
+    pub judge: Response,
+    pub scores: Vec<Score>,
+}
+
+impl Verdict {
+    pub fn merged(&self) -> Option<&str> {
+        match self.mode {
+            JudgeMode::Merge => self.judge.answer.as_deref(),
+            JudgeMode::Score => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comparison {
+    pub created_at: DateTime<Utc>,
+    pub question: String,
+    pub responses: Vec<Response>,
+    pub verdict: Option<Verdict>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+enum RecordKind {
+    Answer,
+    Judge,
+}
+
+#[derive(Serialize)]
+struct ComparisonRecord<'a> {
+    kind: RecordKind,
+    created_at: DateTime<Utc>,
+    #[serde(flatten)]
+    response: &'a Response,
+    judge_mode: Option<JudgeMode>,
+    score: Option<f32>,
+    reason: Option<&'a str>,
+}
+
+impl Comparison {
+    pub fn score(&self, client: ClientKind) -> Option<&Score> {
+        self.verdict
+            .as_ref()?
+            .scores
+            .iter()
+            .find(|score| score.client == client)
+    }
+
+    /// One line per answer plus one for the judge, ready for offline evaluation.
+    pub fn write_jsonl(&self, mut writer: impl Write) -> anyhow::Result<()> {
+        let judge_mode = self.verdict.as_ref().map(|verdict| verdict.mode);
+        for response in &self.responses {
+            let score = self.score(response.client);
+            let record = ComparisonRecord {
+                kind: RecordKind::Answer,
+                created_at: self.created_at,
+                response,
+                judge_mode,
+                score: score.map(|score| score.score),
+                reason: score.map(|score| score.reason.as_str()),
+            };
+            serde_json::to_writer(&mut writer, &record)?;
+            writer.write_all(b"\n")?;
+        }
+        if let Some(verdict) = &self.verdict {
+            let record = ComparisonRecord {
+                kind: RecordKind::Judge,
+                created_at: self.created_at,
+                response: &verdict.judge,
+                judge_mode,
+                score: None,
+                reason: None,
+            };
+            serde_json::to_writer(&mut writer, &record)?;
+            writer.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    pub fn to_jsonl(&self) -> anyhow::Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        self.write_jsonl(&mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+impl AIClient {
+    pub async fn compare(
+        &self,
+        client_kinds: impl Into<HashSet<ClientKind>>,
+        messages: Vec<Message>,
+        options: &CompareOptions,
+    ) -> Comparison {
+        let created_at = Utc::now();
+        let question = messages
+            .last()
+            .map(|msg| msg.content.clone())
+            .unwrap_or_default();
+        let responses = self.completions(client_kinds, messages, options).await;
+        let verdict = match &options.judge {
+            Some(judge) if responses.iter().any(|response| response.answer.is_some()) => {
+                Some(self.judge(judge, &question, &responses, options).await)
+            }
+            _ => None,
+        };
+        Comparison {
+            created_at,
+            question,
+            responses,
+            verdict,
+        }
+    }
+
+    async fn judge(
+        &self,
+        judge: &Judge,
+        question: &str,
+        responses: &[Response],
+        options: &CompareOptions,
+    ) -> Verdict {
+        // Answers are numbered rather than named so the judge can't favour a provider.
+        let answered: Vec<(ClientKind, &str)> = responses
+            .iter()
+            .filter_map(|response| Some((response.client, response.answer.as_deref()?)))
+            .collect();
+        let mut prompt = format!("Question:\n{}\n", question);
+        for (index, (_, answer)) in answered.iter().enumerate() {
+            prompt.push_str(&format!("\nAnswer {}:\n{}\n", index + 1, answer));
+        }
+        let system = match judge.mode {
+            JudgeMode::Score => SCORE_PROMPT,
+            JudgeMode::Merge => MERGE_PROMPT,
+        };
+        let response = self
+            .timed_completion(
+                judge.model_name.clone(),
+                vec![Message::system(system), Message::user(prompt)],
+                &options.completion,
+                options.timeout_for(ClientKind::from(&judge.model_name)),
+            )
+            .await;
+        let scores = match (judge.mode, &response.answer) {
+            (JudgeMode::Score, Some(raw)) => {
+                let clients: Vec<ClientKind> = answered.iter().map(|(client, _)| *client).collect();
+                parse_scores(raw, &clients)
+            }
+            _ => Vec::new(),
+        };
+        Verdict {
+            mode: judge.mode,
+            judge: response,
+            scores,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct JudgeScores {
+    scores: Vec<JudgeScore>,
+}
+
+#[derive(Deserialize)]
+struct JudgeScore {
+    answer: usize,
+    score: f32,
+    #[serde(default)]
+    reason: String,
+}
+
+fn parse_scores(raw: &str, clients: &[ClientKind]) -> Vec<Score> {
+    // Models like to wrap JSON in prose or code fences, so only the outermost object is parsed.
+    let json = match (raw.find('{'), raw.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &raw[start..=end],
+        _ => return Vec::new(),
+    };
+    let Ok(judge_scores) = serde_json::from_str::<JudgeScores>(json) else {
+        return Vec::new();
+    };
+    judge_scores
+        .scores
+        .into_iter()
+        .filter_map(|score| {
+            let client = *clients.get(score.answer.checked_sub(1)?)?;
+            Some(Score {
+                client,
+                score: score.score.clamp(0.0, 10.0),
+                reason: score.reason,
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_parse_scores() {
+    let raw = r#"Here you go:
+```json
+{"scores":[{"answer":1,"score":8,"reason":"Correct"},{"answer":2,"score":14},{"answer":3,"score":5}]}
+```"#;
+    let scores = parse_scores(raw, &[ClientKind::Anthropic, ClientKind::OpenAi]);
+    assert_eq!(scores.len(), 2);
+    assert_eq!(scores[0].client, ClientKind::Anthropic);
+    assert_eq!(scores[0].reason, "Correct");
+    assert_eq!(scores[1].client, ClientKind::OpenAi);
+    assert_eq!(scores[1].score, 10.0);
+    assert!(parse_scores("no json here", &[ClientKind::OpenAi]).is_empty());
+}
+
+#[test]
+fn test_comparison_jsonl() {
+    use crate::clients::bulk::TokenUsage;
+
+    let response = |client: ClientKind, answer: Option<&str>| Response {
+        client,
+        model: client.default_model().to_string(),
+        question: String::from("2+2?"),
+        answer: answer.map(String::from),
+        error: answer.is_none().then(|| String::from("timed out")),
+        latency_ms: 120,
+        usage: TokenUsage {
+            input_tokens: 5,
+            output_tokens: 1,
+        },
+        cost_usd: None,
+    };
+    let comparison = Comparison {
+        created_at: Utc::now(),
+        question: String::from("2+2?"),
+        responses: vec![
+            response(ClientKind::Anthropic, Some("4")),
+            response(ClientKind::Google, None),
+        ],
+        verdict: Some(Verdict {
+            mode: JudgeMode::Score,
+            judge: response(ClientKind::OpenAi, Some(r#"{"scores":[]}"#)),
+            scores: vec![Score {
+                client: ClientKind::Anthropic,
+                score: 9.0,
+                reason: String::from("Correct"),
+            }],
+        }),
+    };
+    let jsonl = String::from_utf8(comparison.to_jsonl().unwrap()).unwrap();
+    let lines: Vec<serde_json::Value> = jsonl
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["kind"], "answer");
+    assert_eq!(lines[0]["client"], "Anthropic");
+    assert_eq!(lines[0]["score"], 9.0);
+    assert_eq!(lines[1]["error"], "timed out");
+    assert!(lines[1]["score"].is_null());
+    assert_eq!(lines[2]["kind"], "judge");
+    assert_eq!(lines[2]["judge_mode"], "score");
+}
@@ -0,0 +1,131 @@
+use crate::ai_constants::{
+    ANTHROPIC_BASE_URL, ANTHROPIC_ENV_PREFIX, GEMINI_BASE_URL, GEMINI_ENV_PREFIX, LLAMA_BASE_URL,
+    LLAMA_ENV_PREFIX, MISTRAL_BASE_URL, MISTRAL_ENV_PREFIX, OPENAI_BASE_URL,
+    OPENAI_COMPATIBLE_ENV_PREFIX, OPENAI_ENV_PREFIX, PERPLEXITY_BASE_URL, PERPLEXITY_ENV_PREFIX,
+};
+use crate::clients::bulk::ClientKind;
+use crate::error::AiInterfaceError;
+use crate::models::base::ModelName;
+use enum_iterator::all;
+use std::collections::HashMap;
+use std::env;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderConfig {
+    pub base_url: String,
+    pub api_key: Option<String>,
+    pub organization: Option<String>,
+    pub default_model: Option<ModelName>,
+}
+
+impl ProviderConfig {
+    pub fn new(base_url: impl Into<String>, api_key: Option<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            api_key,
+            organization: None,
+            default_model: None,
+        }
+    }
+
+    /// Vendors need an API key, self-hosted servers need a base URL.
+    pub fn from_env(kind: ClientKind) -> Result<Self, AiInterfaceError> {
+        let prefix = env_prefix(kind);
+        let var = |suffix: &str| {
+            env::var(format!("{}_{}", prefix, suffix))
+                .ok()
+                .filter(|value| !value.is_empty())
+        };
+        let api_key = var("API_KEY");
+        if api_key.is_none() && kind != ClientKind::OpenAiCompatible {
+            return Err(AiInterfaceError::NotConfigured(kind));
+        }
+        let base_url = var("BASE_URL")
+            .or_else(|| default_base_url(kind).map(String::from))
+            .ok_or(AiInterfaceError::NotConfigured(kind))?;
+        Ok(Self {
+            base_url,
+            api_key,
+            organization: var("ORG"),
+            default_model: var("MODEL").and_then(|model| parse_model(kind, model)),
+        })
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url.trim_end_matches('/'), path)
+    }
+
+    pub(crate) fn api_key(&self, kind: ClientKind) -> Result<&str, AiInterfaceError> {
+        self.api_key
+            .as_deref()
+            .ok_or(AiInterfaceError::NotConfigured(kind))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AIClientConfig {
+    pub providers: HashMap<ClientKind, ProviderConfig>,
+}
+
+impl AIClientConfig {
+    pub fn from_env() -> Self {
+        Self {
+            providers: all::<ClientKind>()
+                .filter_map(|kind| Some((kind, ProviderConfig::from_env(kind).ok()?)))
+                .collect(),
+        }
+    }
+
+    pub fn with_provider(mut self, kind: ClientKind, config: ProviderConfig) -> Self {
+        self.providers.insert(kind, config);
+        self
+    }
+}
+
+fn env_prefix(kind: ClientKind) -> &'static str {
+    match kind {
+        ClientKind::Anthropic => ANTHROPIC_ENV_PREFIX,
+        ClientKind::Google => GEMINI_ENV_PREFIX,
+        ClientKind::Meta => LLAMA_ENV_PREFIX,
+        ClientKind::Mistral => MISTRAL_ENV_PREFIX,
+        ClientKind::OpenAi => OPENAI_ENV_PREFIX,
+        ClientKind::OpenAiCompatible => OPENAI_COMPATIBLE_ENV_PREFIX,
+        ClientKind::Perplexity => PERPLEXITY_ENV_PREFIX,
+    }
+}
+
+fn default_base_url(kind: ClientKind) -> Option<&'static str> {
+    match kind {
+        ClientKind::Anthropic => Some(ANTHROPIC_BASE_URL),
+        ClientKind::Google => Some(GEMINI_BASE_URL),
+        ClientKind::Meta => Some(LLAMA_BASE_URL),
+        ClientKind::Mistral => Some(MISTRAL_BASE_URL),
+        ClientKind::OpenAi => Some(OPENAI_BASE_URL),
+        ClientKind::OpenAiCompatible => None,
+        ClientKind::Perplexity => Some(PERPLEXITY_BASE_URL),
+    }
+}
+
+fn parse_model(kind: ClientKind, model: String) -> Option<ModelName> {
+    if kind == ClientKind::OpenAiCompatible {
+        return Some(ModelName::OpenAiCompatible(model));
+    }
+    ModelName::try_from_string(model)
+        .ok()
+        .filter(|model_name| ClientKind::from(model_name) == kind)
+}
+
+#[test]
+fn test_parse_model() {
+    assert_eq!(
+        parse_model(ClientKind::OpenAiCompatible, String::from("llama3.1")),
+        Some(ModelName::OpenAiCompatible(String::from("llama3.1")))
+    );
+    assert!(parse_model(ClientKind::OpenAi, String::from("gpt-4o")).is_some());
+    assert!(parse_model(ClientKind::Anthropic, String::from("gpt-4o")).is_none());
+    let config = ProviderConfig::new("http://localhost:11434/v1/", None);
+    assert_eq!(
+        config.url("chat/completions"),
+        "http://localhost:11434/v1/chat/completions"
+    );
+}
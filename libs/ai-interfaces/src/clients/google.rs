@@ -1,7 +1,14 @@
-use crate::ai_constants::GEMINI_VAR_NAME;
 use crate::clients::bulk::Role as SuperRole;
-use crate::clients::bulk::{ChatCompletionExt, Message};
+use crate::clients::bulk::{
+    split_system_prompt, ChatCompletionExt, ClientKind, Completion, CompletionOptions, Message,
+    TokenUsage,
+};
+use crate::clients::config::ProviderConfig;
 use crate::clients::google::Role::Model;
+#[cfg(test)]
+use crate::clients::sse::collect_fixture;
+use crate::clients::sse::{delta_stream, CompletionStream, SseEvent, StreamEvent};
+use crate::error::AiInterfaceError;
 use crate::models::base::ModelName;
 use crate::models::google::GoogleModels;
 use anyhow::{anyhow, Context};
@@ -10,7 +17,6 @@ use dotenv::dotenv;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::env::VarError;
 
 #[async_trait]
 impl ChatCompletionExt for GeminiClient {
@@ -18,19 +24,9 @@ impl ChatCompletionExt for GeminiClient {
         &self,
         model_name: ModelName,
         messages: Vec<Message>,
-    ) -> anyhow::Result<Message> {
-        let request = ChatRequest::new(
-            messages
-                .into_iter()
-                .map(|msg| {
-                    if matches!(msg.role, SuperRole::User) {
-                        ChatMessage::user(vec![Part::Text(msg.content)])
-                    } else {
-                        ChatMessage::model(vec![Part::Text(msg.content)])
-                    }
-                })
-                .collect(),
-        );
+        options: &CompletionOptions,
+    ) -> anyhow::Result<Completion> {
+        let request = ChatRequest::from_messages(messages, options);
         let mut result = self.chat_completion(model_name, &request).await?;
         let part = result
             .candidates
@@ -48,24 +44,48 @@ impl ChatCompletionExt for GeminiClient {
                 ))
             }
         };
-        let role = SuperRole::User;
-        Ok(Message { content, role })
-        // Err(anyhow!("aaaa"))
+        let role = SuperRole::Assistant;
+        let usage = TokenUsage {
+            input_tokens: result.usage_metadata.prompt_token_count,
+            output_tokens: result.usage_metadata.candidates_token_count,
+        };
+        Ok(Completion {
+            message: Message { content, role },
+            usage,
+        })
+    }
+
+    async fn completion_stream(
+        &self,
+        model_name: ModelName,
+        messages: Vec<Message>,
+        options: &CompletionOptions,
+    ) -> anyhow::Result<CompletionStream> {
+        let request = ChatRequest::from_messages(messages, options);
+        let url = self.config.url(&format!(
+            "models/{}:streamGenerateContent?alt=sse&key={}",
+            model_name,
+            self.config.api_key(ClientKind::Google)?
+        ));
+        let response = self.send(url, &request).await?;
+        Ok(delta_stream(response.bytes_stream(), stream_event))
     }
 }
 pub struct GeminiClient {
     client: Client,
-    api_key: String,
+    config: ProviderConfig,
 }
 
 impl GeminiClient {
-    pub fn new(client: Client, api_key: String) -> Self {
-        Self { client, api_key }
+    pub fn new(client: Client, config: ProviderConfig) -> Self {
+        Self { client, config }
     }
 
-    pub fn from_env(client: Client, env_var_name: &str) -> Result<Self, VarError> {
-        let api_key = std::env::var(env_var_name)?;
-        Ok(Self::new(client, api_key))
+    pub fn from_env(client: Client) -> Result<Self, AiInterfaceError> {
+        Ok(Self::new(
+            client,
+            ProviderConfig::from_env(ClientKind::Google)?,
+        ))
     }
 
     async fn chat_completion(
@@ -73,36 +93,128 @@ impl GeminiClient {
         model_name: ModelName,
         chat_request: &ChatRequest,
     ) -> anyhow::Result<ChatResponse> {
-        let url = format!(
-            "https://generativelanguage.googleapis.com/v1beta/models/{}?key={}",
-            model_name, self.api_key
-        );
+        let url = self.config.url(&format!(
+            "models/{}:generateContent?key={}",
+            model_name,
+            self.config.api_key(ClientKind::Google)?
+        ));
+        Ok(self.send(url, chat_request).await?.json().await?)
+    }
+
+    async fn send(
+        &self,
+        url: String,
+        chat_request: &ChatRequest,
+    ) -> anyhow::Result<reqwest::Response> {
         let response = self.client.post(url).json(chat_request).send().await?;
         if response.status().is_success() {
-            return Ok(response.json().await?);
-        }
-        if response.status().is_client_error() {
-            let error: Value = response.json().await?;
-            return Err(anyhow!(error));
+            return Ok(response);
         }
-        Err(anyhow!(
-            "Unexpected response status code {} for Google Gemini chat completion request",
-            response.status()
-        ))
+        Err(
+            AiInterfaceError::from_response(ClientKind::Google, response)
+                .await
+                .into(),
+        )
+    }
+}
+
+fn stream_event(event: &SseEvent) -> anyhow::Result<StreamEvent> {
+    let chunk: StreamChunk = serde_json::from_str(&event.data)?;
+    if let Some(error) = chunk.error {
+        return Err(anyhow!(error));
     }
+    Ok(StreamEvent::Delta(
+        chunk
+            .candidates
+            .into_iter()
+            .filter_map(|candidate| candidate.content)
+            .flat_map(|content| content.parts)
+            .filter_map(|part| match part {
+                Part::Text(text) => Some(text),
+                Part::InlineData { .. } => None,
+            })
+            .collect(),
+    ))
 }
 
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 struct ChatRequest {
     contents: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_instruction: Option<SystemInstruction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    generation_config: Option<GenerationConfig>,
 }
 
 impl ChatRequest {
     fn new(messages: Vec<ChatMessage>) -> Self {
-        Self { contents: messages }
+        Self {
+            contents: messages,
+            system_instruction: None,
+            generation_config: None,
+        }
+    }
+
+    fn from_messages(messages: Vec<Message>, options: &CompletionOptions) -> Self {
+        let (system, messages) = split_system_prompt(messages);
+        let generation_config = GenerationConfig {
+            temperature: options.temperature,
+            max_output_tokens: options.max_tokens,
+            top_p: options.top_p,
+        };
+        Self {
+            system_instruction: system.map(|text| SystemInstruction {
+                parts: vec![Part::Text(text)],
+            }),
+            generation_config: (options.temperature.is_some()
+                || options.max_tokens.is_some()
+                || options.top_p.is_some())
+            .then_some(generation_config),
+            ..Self::new(
+                messages
+                    .into_iter()
+                    .map(|msg| {
+                        if matches!(msg.role, SuperRole::User) {
+                            ChatMessage::user(vec![Part::Text(msg.content)])
+                        } else {
+                            ChatMessage::model(vec![Part::Text(msg.content)])
+                        }
+                    })
+                    .collect(),
+            )
+        }
     }
 }
+
+#[derive(Serialize, Debug)]
+struct SystemInstruction {
+    parts: Vec<Part>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct GenerationConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_output_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StreamChunk {
+    #[serde(default)]
+    candidates: Vec<StreamCandidate>,
+    error: Option<Value>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StreamCandidate {
+    content: Option<ChatMessage>,
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 struct ChatResponse {
@@ -129,8 +241,11 @@ struct SafetyRating {
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 struct UsageMetadata {
+    #[serde(default)]
     prompt_token_count: u32,
+    #[serde(default)]
     candidates_token_count: u32,
+    #[serde(default)]
     total_token_count: u32,
 }
 #[derive(Serialize, Deserialize, Debug)]
@@ -171,7 +286,7 @@ enum Role {
 #[tokio::test]
 async fn google_gemini() {
     dotenv().ok();
-    let client = GeminiClient::from_env(Client::new(), GEMINI_VAR_NAME).unwrap();
+    let client = GeminiClient::from_env(Client::new()).unwrap();
     let response = client
         .chat_completion(
             ModelName::Google(GoogleModels::default()),
@@ -196,3 +311,29 @@ fn test_parts_parsing() {
     let s = serde_json::to_string(&p2).unwrap();
     println!("{s}");
 }
+
+#[tokio::test]
+async fn google_gemini_stream_fixture() {
+    let text = collect_fixture(include_str!("../../fixtures/gemini.sse"), stream_event)
+        .await
+        .unwrap();
+    assert_eq!(
+        text,
+        "I am Gemini, a large language model built by Google.\n"
+    );
+}
+
+#[test]
+fn google_gemini_system_instruction() {
+    let request = ChatRequest::from_messages(
+        vec![Message::system("Be brief"), Message::user("Hi")],
+        &CompletionOptions {
+            temperature: Some(0.5),
+            ..CompletionOptions::default()
+        },
+    );
+    let value = serde_json::to_value(&request).unwrap();
+    assert_eq!(value["systemInstruction"]["parts"][0]["text"], "Be brief");
+    assert_eq!(value["generationConfig"]["temperature"], 0.5);
+    assert_eq!(value["contents"].as_array().unwrap().len(), 1);
+}
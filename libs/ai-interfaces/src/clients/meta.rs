@@ -1,6 +1,12 @@
-use crate::ai_constants::LLAMA_VAR_NAME;
 use crate::clients::bulk::Role as SuperRole;
-use crate::clients::bulk::{ChatCompletionExt, Message};
+use crate::clients::bulk::{
+    ChatCompletionExt, ClientKind, Completion, CompletionOptions, Message, TokenUsage,
+};
+use crate::clients::config::ProviderConfig;
+#[cfg(test)]
+use crate::clients::sse::collect_fixture;
+use crate::clients::sse::{delta_stream, CompletionStream, SseEvent, StreamEvent};
+use crate::error::AiInterfaceError;
 use crate::models::base::ModelName;
 use anyhow::{anyhow, Context};
 use async_trait::async_trait;
@@ -8,8 +14,6 @@ use dotenv::dotenv;
 use reqwest::header::AUTHORIZATION;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
-use std::env::VarError;
 
 #[async_trait]
 impl ChatCompletionExt for LlamaClient {
@@ -17,68 +21,93 @@ impl ChatCompletionExt for LlamaClient {
         &self,
         model_name: ModelName,
         messages: Vec<Message>,
-    ) -> anyhow::Result<Message> {
-        let request = ChatRequest::new(
-            model_name.to_string(),
-            messages
-                .into_iter()
-                .map(|msg| {
-                    if matches!(msg.role, SuperRole::User) {
-                        ChatMessage::user(msg.content)
-                    } else {
-                        ChatMessage::assistant(msg.content)
-                    }
-                })
-                .collect(),
-        );
+        options: &CompletionOptions,
+    ) -> anyhow::Result<Completion> {
+        let request = ChatRequest::from_messages(model_name, messages, options);
         let mut result = self.chat_completion(&request).await?;
         let choice = result
             .choices
             .pop()
             .context("Llama returned no completion messages")?;
         let role = match choice.message.role {
+            Role::System => SuperRole::System,
             Role::User => SuperRole::User,
             Role::Assistant => SuperRole::Assistant,
         };
         let content = choice.message.content;
-        Ok(Message { content, role })
+        let usage = result.usage.map(TokenUsage::from).unwrap_or_default();
+        Ok(Completion {
+            message: Message { content, role },
+            usage,
+        })
+    }
+
+    async fn completion_stream(
+        &self,
+        model_name: ModelName,
+        messages: Vec<Message>,
+        options: &CompletionOptions,
+    ) -> anyhow::Result<CompletionStream> {
+        let mut request = ChatRequest::from_messages(model_name, messages, options);
+        request.stream = true;
+        let response = self.send(&request).await?;
+        Ok(delta_stream(response.bytes_stream(), stream_event))
     }
 }
 pub struct LlamaClient {
     client: Client,
-    api_key: String,
+    config: ProviderConfig,
 }
 
 impl LlamaClient {
-    pub fn new(client: Client, api_key: String) -> Self {
-        Self { client, api_key }
+    pub fn new(client: Client, config: ProviderConfig) -> Self {
+        Self { client, config }
     }
-    pub fn from_env(client: Client, env_var_name: &str) -> Result<Self, VarError> {
-        let api_key = std::env::var(env_var_name)?;
-        Ok(Self::new(client, api_key))
+
+    pub fn from_env(client: Client) -> Result<Self, AiInterfaceError> {
+        Ok(Self::new(
+            client,
+            ProviderConfig::from_env(ClientKind::Meta)?,
+        ))
     }
 
     async fn chat_completion(&self, chat_request: &ChatRequest) -> anyhow::Result<ChatResponse> {
-        let url = "https://api.llama-api.com/chat/completions";
+        Ok(self.send(chat_request).await?.json().await?)
+    }
+
+    async fn send(&self, chat_request: &ChatRequest) -> anyhow::Result<reqwest::Response> {
+        let url = self.config.url("chat/completions");
         let response = self
             .client
             .post(url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.api_key.as_str()))
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.config.api_key(ClientKind::Meta)?),
+            )
             .json(&chat_request)
             .send()
             .await?;
         if response.status().is_success() {
-            return Ok(response.json().await?);
+            return Ok(response);
         }
-        if response.status().is_client_error() {
-            let error: Value = response.json().await?;
-            return Err(anyhow!(error));
-        }
-        Err(anyhow!(
-            "Unexpected response status code {} for Meta Llama chat completion request",
-            response.status()
-        ))
+        Err(AiInterfaceError::from_response(ClientKind::Meta, response)
+            .await
+            .into())
+    }
+}
+
+fn stream_event(event: &SseEvent) -> anyhow::Result<StreamEvent> {
+    if event.data == "[DONE]" {
+        return Ok(StreamEvent::Done);
     }
+    let chunk: StreamChunk = serde_json::from_str(&event.data)?;
+    Ok(StreamEvent::Delta(
+        chunk
+            .choices
+            .into_iter()
+            .filter_map(|choice| choice.delta.content)
+            .collect(),
+    ))
 }
 
 #[derive(Serialize, Debug)]
@@ -88,6 +117,12 @@ struct ChatRequest {
     // functions: Vec<Function>,
     stream: bool,
     function_call: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
 }
 
 impl ChatRequest {
@@ -97,6 +132,32 @@ impl ChatRequest {
             messages,
             stream: false,
             function_call: String::from("none"),
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
+        }
+    }
+
+    fn from_messages(
+        model_name: ModelName,
+        messages: Vec<Message>,
+        options: &CompletionOptions,
+    ) -> Self {
+        Self {
+            temperature: options.temperature,
+            max_tokens: options.max_tokens,
+            top_p: options.top_p,
+            ..Self::new(
+                model_name.to_string(),
+                messages
+                    .into_iter()
+                    .map(|msg| match msg.role {
+                        SuperRole::System => ChatMessage::system(msg.content),
+                        SuperRole::User => ChatMessage::user(msg.content),
+                        SuperRole::Assistant => ChatMessage::assistant(msg.content),
+                    })
+                    .collect(),
+            )
         }
     }
 }
@@ -104,6 +165,22 @@ impl ChatRequest {
 #[derive(Deserialize, Debug)]
 struct ChatResponse {
     choices: Vec<Choice>,
+    usage: Option<Usage>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Usage {
+    prompt_tokens: u32,
+    completion_tokens: u32,
+}
+
+impl From<Usage> for TokenUsage {
+    fn from(usage: Usage) -> Self {
+        Self {
+            input_tokens: usage.prompt_tokens,
+            output_tokens: usage.completion_tokens,
+        }
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -120,6 +197,12 @@ struct ChatMessage {
 }
 
 impl ChatMessage {
+    fn system(content: String) -> Self {
+        Self {
+            role: Role::System,
+            content,
+        }
+    }
     fn user(content: String) -> Self {
         Self {
             role: Role::User,
@@ -134,9 +217,25 @@ impl ChatMessage {
     }
 }
 
+#[derive(Deserialize, Debug)]
+struct StreamChunk {
+    choices: Vec<StreamChoice>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StreamChoice {
+    delta: StreamDelta,
+}
+
+#[derive(Deserialize, Debug)]
+struct StreamDelta {
+    content: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
-#[serde(rename = "lowercase")]
+#[serde(rename_all = "lowercase")]
 enum Role {
+    System,
     User,
     Assistant,
 }
@@ -145,7 +244,7 @@ enum Role {
 #[tokio::test]
 async fn meta() {
     dotenv().ok();
-    let client = LlamaClient::from_env(Client::new(), LLAMA_VAR_NAME).unwrap();
+    let client = LlamaClient::from_env(Client::new()).unwrap();
     let result = client
         .chat_completion(&ChatRequest::new(
             String::from("llama3.1-405b"),
@@ -154,3 +253,11 @@ async fn meta() {
         .await
         .unwrap();
 }
+
+#[tokio::test]
+async fn meta_stream_fixture() {
+    let text = collect_fixture(include_str!("../../fixtures/llama.sse"), stream_event)
+        .await
+        .unwrap();
+    assert_eq!(text, "Hi, I'm Llama 3.1.");
+}
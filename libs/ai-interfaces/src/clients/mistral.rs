@@ -1,15 +1,19 @@
-use crate::ai_constants::MISTRAL_VAR_NAME;
 use crate::clients::bulk::Role as SuperRole;
-use crate::clients::bulk::{ChatCompletionExt, Message};
+use crate::clients::bulk::{
+    ChatCompletionExt, ClientKind, Completion, CompletionOptions, Message, TokenUsage,
+};
+use crate::clients::config::ProviderConfig;
+#[cfg(test)]
+use crate::clients::sse::collect_fixture;
+use crate::clients::sse::{delta_stream, CompletionStream, SseEvent, StreamEvent};
+use crate::error::AiInterfaceError;
 use crate::models::base::ModelName;
 use anyhow::{anyhow, Context};
 use async_trait::async_trait;
 use dotenv::dotenv;
 use reqwest::header::AUTHORIZATION;
 use reqwest::Client;
-use serde::__private::de::Content;
 use serde::{Deserialize, Serialize};
-use std::env::VarError;
 use std::fmt::{Display, Formatter};
 
 #[async_trait]
@@ -18,20 +22,9 @@ impl ChatCompletionExt for MistralClient {
         &self,
         model_name: ModelName,
         messages: Vec<Message>,
-    ) -> anyhow::Result<Message> {
-        let request = ChatRequest::new(
-            model_name.to_string(),
-            messages
-                .into_iter()
-                .map(|msg| {
-                    if matches!(msg.role, SuperRole::User) {
-                        ChatMessage::user(msg.content)
-                    } else {
-                        ChatMessage::assistant(msg.content, false)
-                    }
-                })
-                .collect(),
-        );
+        options: &CompletionOptions,
+    ) -> anyhow::Result<Completion> {
+        let request = ChatRequest::from_messages(model_name, messages, options);
         let mut result = self.chat_completion(&request).await?;
         let message = result
             .choices
@@ -42,47 +35,88 @@ impl ChatCompletionExt for MistralClient {
             .content
             .context("Mistral should have included a non-empty string in the response")?;
         let role = match message.role {
+            Role::System => SuperRole::System,
             Role::User => SuperRole::User,
             Role::Assistant => SuperRole::Assistant,
         };
-        Ok(Message { content, role })
+        let usage = TokenUsage {
+            input_tokens: result.usage.prompt_tokens as u32,
+            output_tokens: result.usage.completion_tokens as u32,
+        };
+        Ok(Completion {
+            message: Message { content, role },
+            usage,
+        })
+    }
+
+    async fn completion_stream(
+        &self,
+        model_name: ModelName,
+        messages: Vec<Message>,
+        options: &CompletionOptions,
+    ) -> anyhow::Result<CompletionStream> {
+        let mut request = ChatRequest::from_messages(model_name, messages, options);
+        request.stream = Some(true);
+        let response = self.send(&request).await?;
+        Ok(delta_stream(response.bytes_stream(), stream_event))
     }
 }
 pub struct MistralClient {
     client: Client,
-    api_key: String,
+    config: ProviderConfig,
 }
 
 impl MistralClient {
-    pub fn new(client: Client, api_key: String) -> Self {
-        Self { client, api_key }
+    pub fn new(client: Client, config: ProviderConfig) -> Self {
+        Self { client, config }
     }
-    pub fn from_env(client: Client, env_var_name: &str) -> Result<Self, VarError> {
-        let api_key = std::env::var(env_var_name)?;
-        Ok(Self::new(client, api_key))
+
+    pub fn from_env(client: Client) -> Result<Self, AiInterfaceError> {
+        Ok(Self::new(
+            client,
+            ProviderConfig::from_env(ClientKind::Mistral)?,
+        ))
     }
     async fn chat_completion(&self, chat_request: &ChatRequest) -> anyhow::Result<ChatResponse> {
-        let url = "https://api.mistral.ai/v1/chat/completions";
+        Ok(self.send(chat_request).await?.json().await?)
+    }
+    async fn send(&self, chat_request: &ChatRequest) -> anyhow::Result<reqwest::Response> {
+        let url = self.config.url("chat/completions");
         let response = self
             .client
             .post(url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.api_key.as_str()))
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.config.api_key(ClientKind::Mistral)?),
+            )
             .json(chat_request)
             .send()
             .await?;
         if response.status().is_success() {
-            return Ok(response.json().await?);
-        }
-        if response.status() == 422 {
-            let error: Error = response.json().await?;
-            return Err(anyhow!(error));
+            return Ok(response);
         }
-        Err(anyhow!(
-            "Unexpected response status code {} for Mistral chat completion request",
-            response.status()
-        ))
+        Err(
+            AiInterfaceError::from_response(ClientKind::Mistral, response)
+                .await
+                .into(),
+        )
     }
 }
+
+fn stream_event(event: &SseEvent) -> anyhow::Result<StreamEvent> {
+    if event.data == "[DONE]" {
+        return Ok(StreamEvent::Done);
+    }
+    let chunk: StreamChunk = serde_json::from_str(&event.data)?;
+    Ok(StreamEvent::Delta(
+        chunk
+            .choices
+            .into_iter()
+            .filter_map(|choice| choice.delta.content)
+            .collect(),
+    ))
+}
+
 #[derive(Deserialize, Debug)]
 struct ChatResponse {
     id: String,
@@ -117,32 +151,67 @@ struct UsageInfo {
     total_tokens: u64,
 }
 #[derive(Deserialize, Debug)]
-struct Error {
-    detail: Vec<Detail>,
+struct StreamChunk {
+    choices: Vec<StreamChoice>,
 }
 
-impl Display for Error {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:#?}", self.detail)
-    }
+#[derive(Deserialize, Debug)]
+struct StreamChoice {
+    delta: StreamDelta,
 }
 
 #[derive(Deserialize, Debug)]
-struct Detail {
-    loc: Vec<String>,
-    msg: String,
-    #[serde(rename = "type")]
-    kind: String,
+struct StreamDelta {
+    content: Option<String>,
 }
+
 #[derive(Serialize)]
 struct ChatRequest {
     model: String,
     messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
 }
 
 impl ChatRequest {
     fn new(model: String, messages: Vec<ChatMessage>) -> Self {
-        Self { model, messages }
+        Self {
+            model,
+            messages,
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
+            stream: None,
+        }
+    }
+
+    fn from_messages(
+        model_name: ModelName,
+        messages: Vec<Message>,
+        options: &CompletionOptions,
+    ) -> Self {
+        Self {
+            temperature: options.temperature,
+            max_tokens: options.max_tokens,
+            top_p: options.top_p,
+            ..Self::new(
+                model_name.to_string(),
+                messages
+                    .into_iter()
+                    .map(|msg| match msg.role {
+                        SuperRole::System => ChatMessage::system(msg.content),
+                        SuperRole::User => ChatMessage::user(msg.content),
+                        SuperRole::Assistant => ChatMessage::assistant(msg.content, false),
+                    })
+                    .collect(),
+            )
+        }
     }
 }
 
@@ -156,6 +225,13 @@ struct ChatMessage {
 }
 
 impl ChatMessage {
+    fn system(content: String) -> Self {
+        Self {
+            role: Role::System,
+            content: Some(content),
+            prefix: None,
+        }
+    }
     fn user(content: String) -> Self {
         Self {
             role: Role::User,
@@ -175,6 +251,7 @@ impl ChatMessage {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "lowercase")]
 enum Role {
+    System,
     User,
     Assistant,
 }
@@ -183,7 +260,7 @@ enum Role {
 #[tokio::test]
 async fn mistral() {
     dotenv().ok();
-    let client = MistralClient::from_env(Client::new(), MISTRAL_VAR_NAME).unwrap();
+    let client = MistralClient::from_env(Client::new()).unwrap();
     let request = ChatRequest::new(
         String::from("mistral-small-latest"),
         vec![ChatMessage::user(String::from("Introduce yourself"))],
@@ -191,3 +268,11 @@ async fn mistral() {
     let result = client.chat_completion(&request).await.unwrap();
     println!("{result:#?}")
 }
+
+#[tokio::test]
+async fn mistral_stream_fixture() {
+    let text = collect_fixture(include_str!("../../fixtures/mistral.sse"), stream_event)
+        .await
+        .unwrap();
+    assert_eq!(text, "Bonjour, je suis Mistral.");
+}
@@ -1,7 +1,11 @@
 pub mod anthropic;
 pub mod bulk;
+pub mod compare;
+pub mod config;
 pub mod google;
 pub mod meta;
 pub mod mistral;
 pub mod openai;
+pub mod openai_compatible;
 pub mod perplexity;
+pub mod sse;
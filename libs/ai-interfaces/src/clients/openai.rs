@@ -1,6 +1,12 @@
-use crate::ai_constants::OPENAI_VAR_NAME;
 use crate::clients::bulk::Role as SuperRole;
-use crate::clients::bulk::{ChatCompletionExt, Message};
+use crate::clients::bulk::{
+    ChatCompletionExt, ClientKind, Completion, CompletionOptions, Message, TokenUsage,
+};
+use crate::clients::config::ProviderConfig;
+#[cfg(test)]
+use crate::clients::sse::collect_fixture;
+use crate::clients::sse::{delta_stream, CompletionStream, SseEvent, StreamEvent};
+use crate::error::AiInterfaceError;
 use crate::models::base::ModelName;
 use anyhow::{anyhow, Context};
 use async_trait::async_trait;
@@ -8,7 +14,7 @@ use dotenv::dotenv;
 use reqwest::header::AUTHORIZATION;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::env::VarError;
+use serde_json::Value;
 use std::fmt::{Display, Formatter};
 
 #[async_trait]
@@ -17,20 +23,9 @@ impl ChatCompletionExt for OpenAiClient {
         &self,
         model_name: ModelName,
         messages: Vec<Message>,
-    ) -> anyhow::Result<Message> {
-        let request = ChatRequest::new(
-            model_name.to_string(),
-            messages
-                .into_iter()
-                .map(|msg| {
-                    if matches!(msg.role, SuperRole::User) {
-                        ChatMessage::user(msg.content)
-                    } else {
-                        ChatMessage::assistant(msg.content)
-                    }
-                })
-                .collect(),
-        );
+        options: &CompletionOptions,
+    ) -> anyhow::Result<Completion> {
+        let request = ChatRequest::from_messages(model_name, messages, options);
         let mut response = self.chat_completion(&request).await?;
         let message = response
             .choices
@@ -40,64 +35,158 @@ impl ChatCompletionExt for OpenAiClient {
         let role = match message.message.role {
             Role::User => SuperRole::User,
             Role::Assistant => SuperRole::Assistant,
+            Role::System => SuperRole::System,
             other => return Err(anyhow!("Unimplemented GPT role {other}")),
         };
-        Ok(Message { content, role })
+        let usage = response.usage.map(TokenUsage::from).unwrap_or_default();
+        Ok(Completion {
+            message: Message { content, role },
+            usage,
+        })
+    }
+
+    async fn completion_stream(
+        &self,
+        model_name: ModelName,
+        messages: Vec<Message>,
+        options: &CompletionOptions,
+    ) -> anyhow::Result<CompletionStream> {
+        let mut request = ChatRequest::from_messages(model_name, messages, options);
+        request.stream = Some(true);
+        let response = self.send(&request).await?;
+        Ok(delta_stream(response.bytes_stream(), stream_event))
     }
 }
 pub struct OpenAiClient {
     client: Client,
-    api_key: String,
+    config: ProviderConfig,
+    kind: ClientKind,
 }
 
 impl OpenAiClient {
-    pub fn new(client: Client, api_key: String) -> Self {
-        Self { client, api_key }
+    pub fn new(client: Client, config: ProviderConfig) -> Self {
+        Self::with_kind(client, config, ClientKind::OpenAi)
     }
 
-    pub fn from_env(client: Client, env_var_name: &str) -> Result<Self, VarError> {
-        let api_key = std::env::var(env_var_name)?;
-        Ok(Self::new(client, api_key))
+    pub(crate) fn with_kind(client: Client, config: ProviderConfig, kind: ClientKind) -> Self {
+        Self {
+            client,
+            config,
+            kind,
+        }
+    }
+
+    pub fn from_env(client: Client) -> Result<Self, AiInterfaceError> {
+        Ok(Self::new(
+            client,
+            ProviderConfig::from_env(ClientKind::OpenAi)?,
+        ))
     }
 
     async fn chat_completion(&self, request: &ChatRequest) -> anyhow::Result<ChatResponse> {
-        let url = "https://api.openai.com/v1/chat/completions";
-        let response = self
-            .client
-            .post(url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.api_key.as_str()))
-            .json(&request)
-            .send()
-            .await?;
-        if response.status().is_success() {
-            return Ok(response.json().await?);
+        Ok(self.send(request).await?.json().await?)
+    }
+
+    async fn send(&self, request: &ChatRequest) -> anyhow::Result<reqwest::Response> {
+        let mut builder = self.client.post(self.config.url("chat/completions"));
+        // Local servers usually run without a key.
+        if let Some(api_key) = &self.config.api_key {
+            builder = builder.header(AUTHORIZATION, format!("Bearer {}", api_key));
         }
-        if response.status().is_client_error() {
-            let error: Error = response.json().await?;
-            return Err(anyhow!(error));
+        if let Some(organization) = &self.config.organization {
+            builder = builder.header(ORGANIZATION_HEADER, organization);
         }
-        Err(anyhow!(
-            "Unexpected response status code {} for OpenAI chat completion request",
-            response.status()
-        ))
+        let response = builder.json(&request).send().await?;
+        if response.status().is_success() {
+            return Ok(response);
+        }
+        Err(AiInterfaceError::from_response(self.kind, response)
+            .await
+            .into())
     }
 }
 
+const ORGANIZATION_HEADER: &str = "OpenAI-Organization";
+
+fn stream_event(event: &SseEvent) -> anyhow::Result<StreamEvent> {
+    if event.data == "[DONE]" {
+        return Ok(StreamEvent::Done);
+    }
+    let chunk: StreamChunk = serde_json::from_str(&event.data)?;
+    if let Some(error) = chunk.error {
+        return Err(anyhow!(error));
+    }
+    Ok(StreamEvent::Delta(
+        chunk
+            .choices
+            .into_iter()
+            .filter_map(|choice| choice.delta.content)
+            .collect(),
+    ))
+}
+
 #[derive(Serialize)]
 struct ChatRequest {
     model: String,
     messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
 }
 
 impl ChatRequest {
     fn new(model: String, messages: Vec<ChatMessage>) -> Self {
-        Self { model, messages }
+        Self {
+            model,
+            messages,
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
+            stream: None,
+        }
+    }
+
+    fn from_messages(
+        model_name: ModelName,
+        messages: Vec<Message>,
+        options: &CompletionOptions,
+    ) -> Self {
+        Self {
+            temperature: options.temperature,
+            max_tokens: options.max_tokens,
+            top_p: options.top_p,
+            ..Self::new(
+                model_name.api_name(),
+                messages.into_iter().map(ChatMessage::from).collect(),
+            )
+        }
     }
 }
 
 #[derive(Deserialize)]
 struct ChatResponse {
     pub(crate) choices: Vec<Choice>,
+    usage: Option<Usage>,
+}
+
+#[derive(Deserialize)]
+struct Usage {
+    prompt_tokens: u32,
+    completion_tokens: u32,
+}
+
+impl From<Usage> for TokenUsage {
+    fn from(usage: Usage) -> Self {
+        Self {
+            input_tokens: usage.prompt_tokens,
+            output_tokens: usage.completion_tokens,
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -105,6 +194,23 @@ struct Choice {
     pub(crate) message: ChatMessage,
 }
 
+#[derive(Deserialize)]
+struct StreamChunk {
+    #[serde(default)]
+    choices: Vec<StreamChoice>,
+    error: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct StreamChoice {
+    delta: StreamDelta,
+}
+
+#[derive(Deserialize)]
+struct StreamDelta {
+    content: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "lowercase")]
 enum Role {
@@ -126,6 +232,20 @@ struct ChatMessage {
     pub(crate) content: String,
 }
 
+impl From<Message> for ChatMessage {
+    fn from(msg: Message) -> Self {
+        let role = match msg.role {
+            SuperRole::System => Role::System,
+            SuperRole::User => Role::User,
+            SuperRole::Assistant => Role::Assistant,
+        };
+        Self {
+            role,
+            content: msg.content,
+        }
+    }
+}
+
 impl ChatMessage {
     fn user(content: String) -> Self {
         Self {
@@ -141,26 +261,6 @@ impl ChatMessage {
     }
 }
 
-#[derive(Deserialize, Debug)]
-struct Error {
-    error: InnerError,
-}
-
-impl Display for Error {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:#?}", self.error)
-    }
-}
-
-#[derive(Deserialize, Debug)]
-struct InnerError {
-    message: String,
-    #[serde(rename = "type")]
-    kind: String,
-    param: Option<String>,
-    code: String,
-}
-
 struct Metadata {}
 enum RateLimitHeader {
     LimitRequests,
@@ -192,7 +292,7 @@ impl Display for RateLimitHeader {
 #[tokio::test]
 async fn openai() {
     dotenv().ok();
-    let client = OpenAiClient::from_env(Client::new(), OPENAI_VAR_NAME).unwrap();
+    let client = OpenAiClient::from_env(Client::new()).unwrap();
     client
         .chat_completion(&ChatRequest::new(
             String::from("gpt-4o"),
@@ -201,3 +301,11 @@ async fn openai() {
         .await
         .unwrap();
 }
+
+#[tokio::test]
+async fn openai_stream_fixture() {
+    let text = collect_fixture(include_str!("../../fixtures/openai.sse"), stream_event)
+        .await
+        .unwrap();
+    assert_eq!(text, "Hello! How can I help you today?");
+}
@@ -0,0 +1,140 @@
+use crate::clients::bulk::{ChatCompletionExt, ClientKind, Completion, CompletionOptions, Message};
+use crate::clients::config::ProviderConfig;
+use crate::clients::openai::OpenAiClient;
+use crate::clients::sse::CompletionStream;
+use crate::error::AiInterfaceError;
+use crate::models::base::ModelName;
+use async_trait::async_trait;
+use reqwest::Client;
+
+// vLLM, llama.cpp and Ollama all speak the OpenAI chat completions protocol.
+pub struct OpenAiCompatibleClient {
+    inner: OpenAiClient,
+}
+
+impl OpenAiCompatibleClient {
+    pub fn new(client: Client, config: ProviderConfig) -> Self {
+        Self {
+            inner: OpenAiClient::with_kind(client, config, ClientKind::OpenAiCompatible),
+        }
+    }
+
+    pub fn from_env(client: Client) -> Result<Self, AiInterfaceError> {
+        Ok(Self::new(
+            client,
+            ProviderConfig::from_env(ClientKind::OpenAiCompatible)?,
+        ))
+    }
+}
+
+#[async_trait]
+impl ChatCompletionExt for OpenAiCompatibleClient {
+    async fn completion(
+        &self,
+        model_name: ModelName,
+        messages: Vec<Message>,
+        options: &CompletionOptions,
+    ) -> anyhow::Result<Completion> {
+        self.inner.completion(model_name, messages, options).await
+    }
+
+    async fn completion_stream(
+        &self,
+        model_name: ModelName,
+        messages: Vec<Message>,
+        options: &CompletionOptions,
+    ) -> anyhow::Result<CompletionStream> {
+        self.inner
+            .completion_stream(model_name, messages, options)
+            .await
+    }
+}
+
+#[cfg(test)]
+pub(crate) async fn mock_server(
+    status: u16,
+    body: &'static str,
+) -> (String, tokio::task::JoinHandle<String>) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}/v1", listener.local_addr().unwrap());
+    let handle = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buffer = [0u8; 4096];
+        // Read the headers, then as much body as Content-Length announces.
+        loop {
+            let read = socket.read(&mut buffer).await.unwrap();
+            request.extend_from_slice(&buffer[..read]);
+            let text = String::from_utf8_lossy(&request).to_string();
+            if let Some(end) = text.find("\r\n\r\n") {
+                let length = text[..end]
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse::<usize>().ok())?
+                    })
+                    .unwrap_or(0);
+                if request.len() >= end + 4 + length || read == 0 {
+                    break;
+                }
+            }
+        }
+        let response = format!(
+            "HTTP/1.1 {} OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+        String::from_utf8_lossy(&request).to_string()
+    });
+    (base_url, handle)
+}
+
+#[tokio::test]
+async fn openai_compatible_mock_server() {
+    let (base_url, request) = mock_server(
+        200,
+        r#"{"choices":[{"message":{"role":"assistant","content":"Hi from llama"}}],"usage":{"prompt_tokens":3,"completion_tokens":4}}"#,
+    )
+    .await;
+    let client = OpenAiCompatibleClient::new(Client::new(), ProviderConfig::new(base_url, None));
+    let completion = client
+        .completion(
+            ModelName::OpenAiCompatible(String::from("llama3.1:8b")),
+            vec![Message::user("Introduce yourself")],
+            &CompletionOptions::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(completion.message.content, "Hi from llama");
+    assert_eq!(completion.usage.output_tokens, 4);
+    let request = request.await.unwrap();
+    assert!(request.starts_with("POST /v1/chat/completions"));
+    assert!(request.contains(r#""model":"llama3.1:8b""#));
+    assert!(!request.to_lowercase().contains("authorization"));
+}
+
+#[tokio::test]
+async fn openai_compatible_errors_name_the_client() {
+    let (base_url, _) = mock_server(503, r#"{"error":"model is loading"}"#).await;
+    let client = OpenAiCompatibleClient::new(Client::new(), ProviderConfig::new(base_url, None));
+    let error = client
+        .completion(
+            ModelName::OpenAiCompatible(String::from("llama3.1:8b")),
+            vec![Message::user("Introduce yourself")],
+            &CompletionOptions::default(),
+        )
+        .await
+        .unwrap_err();
+    match error.downcast_ref::<AiInterfaceError>() {
+        Some(AiInterfaceError::Provider { client, status, .. }) => {
+            assert_eq!(*client, ClientKind::OpenAiCompatible);
+            assert_eq!(*status, 503);
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+}
@@ -1,76 +1,79 @@
-use crate::ai_constants::{OPENAI_VAR_NAME, PERPLEXITY_VAR_NAME};
 use crate::clients::bulk::Role as SuperRole;
-use crate::clients::bulk::{ChatCompletionExt, Message};
-use crate::clients::openai::OpenAiClient;
+use crate::clients::bulk::{
+    ChatCompletionExt, ClientKind, Completion, CompletionOptions, Message, TokenUsage,
+};
+use crate::clients::config::ProviderConfig;
+#[cfg(test)]
+use crate::clients::sse::collect_fixture;
+use crate::clients::sse::{delta_stream, CompletionStream, SseEvent, StreamEvent};
+use crate::error::AiInterfaceError;
 use crate::models::base::ModelName;
 use anyhow::{anyhow, Context};
 use async_trait::async_trait;
 use dotenv::dotenv;
-use reqwest::header::{HeaderName, AUTHORIZATION};
+use reqwest::header::AUTHORIZATION;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
-use std::env::VarError;
 use std::fmt::{Display, Formatter};
 
 pub struct PerplexityClient {
     client: Client,
-    api_key: String,
+    config: ProviderConfig,
 }
 
 impl PerplexityClient {
-    pub fn new(client: Client, api_key: String) -> Self {
-        Self { client, api_key }
+    pub fn new(client: Client, config: ProviderConfig) -> Self {
+        Self { client, config }
     }
 
-    pub fn from_env(client: Client, env_var_name: &str) -> Result<Self, VarError> {
-        let api_key = std::env::var(env_var_name)?;
-        Ok(Self::new(client, api_key))
+    pub fn from_env(client: Client) -> Result<Self, AiInterfaceError> {
+        Ok(Self::new(
+            client,
+            ProviderConfig::from_env(ClientKind::Perplexity)?,
+        ))
     }
     async fn chat_completion(
         &self,
         request: &ChatCompletionRequest,
     ) -> anyhow::Result<ChatCompletionResponse> {
-        let url = "https://api.perplexity.ai/chat/completions";
+        Ok(self.send(request).await?.json().await?)
+    }
+
+    async fn send(&self, request: &ChatCompletionRequest) -> anyhow::Result<reqwest::Response> {
+        let url = self.config.url("chat/completions");
         let response = self
             .client
             .post(url)
-            .header(AUTHORIZATION, format!("Bearer {}", self.api_key.as_str()))
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.config.api_key(ClientKind::Perplexity)?),
+            )
             .json(&request)
             .send()
             .await?;
         if response.status().is_success() {
-            return Ok(response.json().await?);
-        }
-        if response.status().is_client_error() {
-            let error: Error = response.json().await?;
-            return Err(anyhow!(error));
+            return Ok(response);
         }
-        Err(anyhow!(
-            "Unexpected response status code {} for OpenAI chat completion request",
-            response.status()
-        ))
+        Err(
+            AiInterfaceError::from_response(ClientKind::Perplexity, response)
+                .await
+                .into(),
+        )
     }
 }
 
-#[derive(Deserialize, Debug)]
-struct InnerError {
-    message: String,
-    #[serde(rename = "type")]
-    kind: String,
-    param: Option<String>,
-    code: String,
-}
-
-#[derive(Deserialize, Debug)]
-struct Error {
-    error: InnerError,
-}
-
-impl Display for Error {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:#?}", self.error)
+fn stream_event(event: &SseEvent) -> anyhow::Result<StreamEvent> {
+    if event.data == "[DONE]" {
+        return Ok(StreamEvent::Done);
     }
+    let chunk: StreamChunk = serde_json::from_str(&event.data)?;
+    Ok(StreamEvent::Delta(
+        chunk
+            .choices
+            .into_iter()
+            .filter_map(|choice| choice.delta.content)
+            .collect(),
+    ))
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -95,6 +98,13 @@ struct ChatMessage {
 }
 
 impl ChatMessage {
+    fn system(content: String) -> Self {
+        Self {
+            role: Role::System,
+            content,
+        }
+    }
+
     fn user(content: String) -> Self {
         Self {
             role: Role::User,
@@ -114,11 +124,49 @@ impl ChatMessage {
 pub struct ChatCompletionRequest {
     model: String,
     messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
 }
 
 impl ChatCompletionRequest {
     fn new(model: String, messages: Vec<ChatMessage>) -> Self {
-        Self { model, messages }
+        Self {
+            model,
+            messages,
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
+            stream: None,
+        }
+    }
+
+    fn from_messages(
+        model_name: ModelName,
+        messages: Vec<Message>,
+        options: &CompletionOptions,
+    ) -> Self {
+        Self {
+            temperature: options.temperature,
+            max_tokens: options.max_tokens,
+            top_p: options.top_p,
+            ..Self::new(
+                model_name.to_string(),
+                messages
+                    .into_iter()
+                    .map(|msg| match msg.role {
+                        SuperRole::System => ChatMessage::system(msg.content),
+                        SuperRole::User => ChatMessage::user(msg.content),
+                        SuperRole::Assistant => ChatMessage::assistant(msg.content),
+                    })
+                    .collect(),
+            )
+        }
     }
 }
 
@@ -130,6 +178,37 @@ pub struct Choice {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ChatCompletionResponse {
     choices: Vec<Choice>,
+    usage: Option<Usage>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Usage {
+    prompt_tokens: u32,
+    completion_tokens: u32,
+}
+
+impl From<Usage> for TokenUsage {
+    fn from(usage: Usage) -> Self {
+        Self {
+            input_tokens: usage.prompt_tokens,
+            output_tokens: usage.completion_tokens,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct StreamChunk {
+    choices: Vec<StreamChoice>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StreamChoice {
+    delta: StreamDelta,
+}
+
+#[derive(Deserialize, Debug)]
+struct StreamDelta {
+    content: Option<String>,
 }
 
 #[async_trait]
@@ -138,20 +217,9 @@ impl ChatCompletionExt for PerplexityClient {
         &self,
         model_name: ModelName,
         messages: Vec<Message>,
-    ) -> anyhow::Result<Message> {
-        let request = ChatCompletionRequest::new(
-            model_name.to_string(),
-            messages
-                .into_iter()
-                .map(|msg| {
-                    if matches!(msg.role, SuperRole::User) {
-                        ChatMessage::user(msg.content)
-                    } else {
-                        ChatMessage::assistant(msg.content)
-                    }
-                })
-                .collect(),
-        );
+        options: &CompletionOptions,
+    ) -> anyhow::Result<Completion> {
+        let request = ChatCompletionRequest::from_messages(model_name, messages, options);
         let mut response = self.chat_completion(&request).await?;
         let message = response
             .choices
@@ -161,9 +229,26 @@ impl ChatCompletionExt for PerplexityClient {
         let role = match message.message.role {
             Role::User => SuperRole::User,
             Role::Assistant => SuperRole::Assistant,
+            Role::System => SuperRole::System,
             other => return Err(anyhow!("Unimplemented GPT role {other}")),
         };
-        Ok(Message { content, role })
+        let usage = response.usage.map(TokenUsage::from).unwrap_or_default();
+        Ok(Completion {
+            message: Message { content, role },
+            usage,
+        })
+    }
+
+    async fn completion_stream(
+        &self,
+        model_name: ModelName,
+        messages: Vec<Message>,
+        options: &CompletionOptions,
+    ) -> anyhow::Result<CompletionStream> {
+        let mut request = ChatCompletionRequest::from_messages(model_name, messages, options);
+        request.stream = Some(true);
+        let response = self.send(&request).await?;
+        Ok(delta_stream(response.bytes_stream(), stream_event))
     }
 }
 
@@ -171,7 +256,7 @@ impl ChatCompletionExt for PerplexityClient {
 #[tokio::test]
 async fn perplexity() {
     dotenv().ok();
-    let client = PerplexityClient::from_env(Client::new(), PERPLEXITY_VAR_NAME).unwrap();
+    let client = PerplexityClient::from_env(Client::new()).unwrap();
     let resp = client
         .chat_completion(&ChatCompletionRequest::new(
             String::from("llama-3.1-sonar-small-128k-online"),
@@ -181,3 +266,11 @@ async fn perplexity() {
         .unwrap();
     println!("resp = {:#?}", resp);
 }
+
+#[tokio::test]
+async fn perplexity_stream_fixture() {
+    let text = collect_fixture(include_str!("../../fixtures/perplexity.sse"), stream_event)
+        .await
+        .unwrap();
+    assert_eq!(text, "I am an AI assistant by Perplexity.");
+}
@@ -0,0 +1,193 @@
+use bytes::Bytes;
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use std::collections::VecDeque;
+
+pub type CompletionStream = BoxStream<'static, anyhow::Result<String>>;
+
+pub type EventParser = fn(&SseEvent) -> anyhow::Result<StreamEvent>;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct SseEvent {
+    pub event: Option<String>,
+    pub data: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum StreamEvent {
+    Delta(String),
+    Skip,
+    Done,
+}
+
+#[derive(Default)]
+pub struct SseParser {
+    buffer: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(position) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=position).collect();
+            let line = String::from_utf8_lossy(&line);
+            if let Some(event) = self.line(line.trim_end_matches(['\n', '\r'])) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    pub fn finish(&mut self) -> Option<SseEvent> {
+        let line = String::from_utf8_lossy(&std::mem::take(&mut self.buffer)).to_string();
+        if let Some(event) = self.line(line.trim_end_matches('\r')) {
+            return Some(event);
+        }
+        self.dispatch()
+    }
+
+    fn line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => self.data.push(value.to_string()),
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        if self.data.is_empty() {
+            self.event = None;
+            return None;
+        }
+        Some(SseEvent {
+            event: self.event.take(),
+            data: std::mem::take(&mut self.data).join("\n"),
+        })
+    }
+}
+
+struct DeltaState {
+    bytes: BoxStream<'static, anyhow::Result<Bytes>>,
+    parser: SseParser,
+    pending: VecDeque<anyhow::Result<String>>,
+    done: bool,
+}
+
+pub fn delta_stream<S, E>(bytes: S, parse: EventParser) -> CompletionStream
+where
+    S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let state = DeltaState {
+        bytes: bytes
+            .map(|chunk| chunk.map_err(anyhow::Error::from))
+            .boxed(),
+        parser: SseParser::default(),
+        pending: VecDeque::new(),
+        done: false,
+    };
+    stream::unfold(state, move |mut state| async move {
+        loop {
+            if let Some(item) = state.pending.pop_front() {
+                return Some((item, state));
+            }
+            if state.done {
+                return None;
+            }
+            let events = match state.bytes.next().await {
+                Some(Ok(chunk)) => state.parser.push(&chunk),
+                Some(Err(error)) => {
+                    state.done = true;
+                    state.pending.push_back(Err(error));
+                    continue;
+                }
+                None => {
+                    state.done = true;
+                    state.parser.finish().into_iter().collect()
+                }
+            };
+            for event in events {
+                match parse(&event) {
+                    Ok(StreamEvent::Delta(text)) if !text.is_empty() => {
+                        state.pending.push_back(Ok(text))
+                    }
+                    Ok(StreamEvent::Delta(_)) | Ok(StreamEvent::Skip) => {}
+                    Ok(StreamEvent::Done) => {
+                        state.done = true;
+                        break;
+                    }
+                    Err(error) => {
+                        state.done = true;
+                        state.pending.push_back(Err(error));
+                        break;
+                    }
+                }
+            }
+        }
+    })
+    .boxed()
+}
+
+#[cfg(test)]
+pub(crate) async fn collect_fixture(fixture: &str, parse: EventParser) -> anyhow::Result<String> {
+    // Replay the recording in small uneven chunks so events straddle reads.
+    let chunks: Vec<Result<Bytes, std::io::Error>> = fixture
+        .as_bytes()
+        .chunks(7)
+        .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+        .collect();
+    let mut stream = delta_stream(stream::iter(chunks), parse);
+    let mut text = String::new();
+    while let Some(delta) = stream.next().await {
+        text.push_str(&delta?);
+    }
+    Ok(text)
+}
+
+#[test]
+fn test_sse_parser() {
+    let mut parser = SseParser::default();
+    let mut events = parser.push(b": keep-alive\r\nevent: ping\r\ndata: {}\r\n\r\ndata: a");
+    events.extend(parser.push(b"\ndata: b\n\nretry: 10\n\n"));
+    events.extend(parser.finish());
+    assert_eq!(
+        events,
+        vec![
+            SseEvent {
+                event: Some(String::from("ping")),
+                data: String::from("{}"),
+            },
+            SseEvent {
+                event: None,
+                data: String::from("a\nb"),
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_delta_stream_stops_at_done() {
+    fn parse(event: &SseEvent) -> anyhow::Result<StreamEvent> {
+        Ok(match event.data.as_str() {
+            "[DONE]" => StreamEvent::Done,
+            data => StreamEvent::Delta(data.to_string()),
+        })
+    }
+    let text = collect_fixture("data: a\n\ndata: b\n\ndata: [DONE]\n\ndata: c\n\n", parse)
+        .await
+        .unwrap();
+    assert_eq!(text, "ab");
+}
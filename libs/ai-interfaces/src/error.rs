@@ -1,5 +1,29 @@
+use crate::clients::bulk::ClientKind;
+
 #[derive(Debug, thiserror::Error)]
 pub enum AiInterfaceError {
     #[error("DB Error: {0}")]
     DBError(String),
+    #[error("{0} client is not configured")]
+    NotConfigured(ClientKind),
+    #[error("{0} did not answer in time")]
+    Timeout(ClientKind),
+    #[error("{client} request failed with status {status}: {message}")]
+    Provider {
+        client: ClientKind,
+        status: u16,
+        message: String,
+    },
+}
+
+impl AiInterfaceError {
+    pub(crate) async fn from_response(client: ClientKind, response: reqwest::Response) -> Self {
+        let status = response.status().as_u16();
+        let message = response.text().await.unwrap_or_default();
+        Self::Provider {
+            client,
+            status,
+            message,
+        }
+    }
 }
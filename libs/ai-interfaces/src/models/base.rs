@@ -6,14 +6,16 @@ use crate::models::mistral::MistralModels;
 use crate::models::open_ai::OpenAiModels;
 use crate::models::perplexity::PerplexityModels;
 use anyhow::anyhow;
-use enum_iterator::{all, Sequence};
+use enum_iterator::all;
 use serde::{Deserialize, Serialize};
 use sqlx::postgres::PgSslMode::Prefer;
 use sqlx::{Decode, Postgres};
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Sequence)]
+pub const OPENAI_COMPATIBLE_PREFIX: &str = "openai-compatible/";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum ModelName {
     OpenAi(OpenAiModels),
     Anthropic(AnthropicModels),
@@ -21,6 +23,8 @@ pub enum ModelName {
     Perplexity(PerplexityModels),
     Meta(MetaModels),
     Mistral(MistralModels),
+    // Whatever the self-hosted server calls its model, stored with a prefix so it round-trips.
+    OpenAiCompatible(String),
 }
 
 impl From<String> for ModelName {
@@ -32,7 +36,28 @@ impl From<String> for ModelName {
 // impl TryFrom<String> for ModelName {
 // type Error = AiInterfaceError;
 impl ModelName {
-    fn try_from_string(value: String) -> Result<Self, AiInterfaceError> {
+    pub fn known() -> Vec<Self> {
+        all::<OpenAiModels>()
+            .map(Self::OpenAi)
+            .chain(all::<AnthropicModels>().map(Self::Anthropic))
+            .chain(all::<GoogleModels>().map(Self::Google))
+            .chain(all::<PerplexityModels>().map(Self::Perplexity))
+            .chain(all::<MetaModels>().map(Self::Meta))
+            .chain(all::<MistralModels>().map(Self::Mistral))
+            .collect()
+    }
+
+    pub fn api_name(&self) -> String {
+        match self {
+            Self::OpenAiCompatible(model) => model.clone(),
+            other => other.to_string(),
+        }
+    }
+
+    pub(crate) fn try_from_string(value: String) -> Result<Self, AiInterfaceError> {
+        if let Some(model) = value.strip_prefix(OPENAI_COMPATIBLE_PREFIX) {
+            return Ok(Self::OpenAiCompatible(model.to_string()));
+        }
         if let Ok(open_ai) = OpenAiModels::try_from(value.as_str()) {
             return Ok(Self::OpenAi(open_ai));
         }
@@ -66,7 +91,9 @@ impl Display for ModelName {
             Self::Anthropic(x) => write!(f, "{}", x),
             Self::Google(x) => write!(f, "{}", x),
             Self::Perplexity(x) => write!(f, "{}", x),
-            _ => write!(f, "{}", Self::default()),
+            Self::Meta(x) => write!(f, "{}", x),
+            Self::Mistral(x) => write!(f, "{}", x),
+            Self::OpenAiCompatible(x) => write!(f, "{}{}", OPENAI_COMPATIBLE_PREFIX, x),
         }
     }
 }
@@ -104,3 +131,13 @@ impl sqlx::Decode<'_, Postgres> for ModelName {
         }
     }
 }
+
+#[test]
+fn test_model_name_round_trip() {
+    for model_name in ModelName::known() {
+        assert_eq!(ModelName::from(model_name.to_string()), model_name);
+    }
+    let local = ModelName::OpenAiCompatible(String::from("meta-llama/Llama-3.1-8B-Instruct"));
+    assert_eq!(ModelName::from(local.to_string()), local);
+    assert_eq!(local.api_name(), "meta-llama/Llama-3.1-8B-Instruct");
+}
@@ -0,0 +1,49 @@
+use crate::clients::bulk::TokenUsage;
+use crate::models::anthropic::AnthropicModels;
+use crate::models::base::ModelName;
+use crate::models::google::GoogleModels;
+use crate::models::mistral::MistralModels;
+use crate::models::open_ai::OpenAiModels;
+use crate::models::perplexity::PerplexityModels;
+
+// USD per million input and output tokens, taken from the providers' public price lists.
+pub fn price_per_million(model_name: &ModelName) -> Option<(f64, f64)> {
+    let price = match model_name {
+        ModelName::OpenAi(model) => match model {
+            OpenAiModels::Gpt4o => (2.5, 10.0),
+            OpenAiModels::Gpt4oLatest => (5.0, 15.0),
+            OpenAiModels::Gpt4oMini => (0.15, 0.6),
+            OpenAiModels::Gpt4Turbo => (10.0, 30.0),
+            OpenAiModels::Gpt4 => (30.0, 60.0),
+            OpenAiModels::Gpt35Turbo => (0.5, 1.5),
+        },
+        ModelName::Anthropic(model) => match model {
+            AnthropicModels::Claude35HaikuLatest => (0.8, 4.0),
+            AnthropicModels::Claude35SonnetLatest => (3.0, 15.0),
+            AnthropicModels::Claude3OpusLatest => (15.0, 75.0),
+        },
+        ModelName::Google(model) => match model {
+            GoogleModels::Gemini15FlashLatest => (0.075, 0.3),
+            GoogleModels::Gemini15ProLatest => (1.25, 5.0),
+        },
+        ModelName::Perplexity(model) => match model {
+            PerplexityModels::Llama31SonarSmall128KOnline
+            | PerplexityModels::Llama31SonarSmall128KChat
+            | PerplexityModels::Llama318BInstruct => (0.2, 0.2),
+            PerplexityModels::Llama31SonarLarge128KOnline
+            | PerplexityModels::Llama31SonarLarge128KChat
+            | PerplexityModels::Llama3170BInstruct => (1.0, 1.0),
+            PerplexityModels::Llama31SonarHuge128KOnline => (5.0, 5.0),
+        },
+        ModelName::Mistral(model) => match model {
+            MistralModels::MistralSmallLatest => (0.2, 0.6),
+        },
+        ModelName::Meta(_) | ModelName::OpenAiCompatible(_) => return None,
+    };
+    Some(price)
+}
+
+pub fn cost_usd(model_name: &ModelName, usage: &TokenUsage) -> Option<f64> {
+    let (input, output) = price_per_million(model_name)?;
+    Some((usage.input_tokens as f64 * input + usage.output_tokens as f64 * output) / 1_000_000.0)
+}
@@ -0,0 +1,149 @@
+use crate::local::LocalBlobStore;
+use crate::s3::S3BlobStore;
+use anyhow::anyhow;
+use sha2::{Digest, Sha256};
+use std::env;
+use tokio::sync::OnceCell;
+
+static BLOB_STORE: OnceCell<BlobStore> = OnceCell::const_new();
+
+pub enum BlobStore {
+    Local(LocalBlobStore),
+    S3(S3BlobStore),
+}
+
+#[tracing::instrument(name = "get_blob_store", skip_all, err)]
+pub async fn get_blob_store() -> anyhow::Result<&'static BlobStore> {
+    BLOB_STORE
+        .get_or_try_init(|| async { BlobStore::from_env().await })
+        .await
+}
+
+pub fn blob_key(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+pub fn blob_path(key: &str) -> anyhow::Result<String> {
+    if key.len() != 64 || !key.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(anyhow!("Invalid blob key {}", key));
+    }
+    Ok(format!("{}/{}/{}.zst", &key[0..2], &key[2..4], key))
+}
+
+impl BlobStore {
+    /// Errors instead of guessing a store, blobs written to a temp dir would be lost on restart.
+    pub async fn from_env() -> anyhow::Result<Self> {
+        match env::var("BLOB_STORE").unwrap_or_default().as_str() {
+            // S3 futures are boxed here and below so callers' async state machines stay shallow.
+            "s3" => Ok(Self::S3(Box::pin(S3BlobStore::from_env()).await)),
+            "local" => Ok(Self::Local(LocalBlobStore::new(
+                env::var("BLOB_STORE_PATH")
+                    .map_err(|_| anyhow!("BLOB_STORE_PATH is required for the local blob store"))?,
+            ))),
+            other => Err(anyhow!(
+                "BLOB_STORE must be \"local\" or \"s3\", got {:?}",
+                other
+            )),
+        }
+    }
+
+    #[tracing::instrument(name = "blob_store_put", skip_all, err)]
+    pub async fn put(&self, bytes: &[u8]) -> anyhow::Result<String> {
+        let key = blob_key(bytes);
+        let path = blob_path(&key)?;
+        if self.exists_path(&path).await? {
+            return Ok(key);
+        }
+        let level = env::var("BLOB_STORE_ZSTD_LEVEL")
+            .unwrap_or("3".to_string())
+            .parse()
+            .unwrap_or(3);
+        let compressed = zstd::encode_all(bytes, level)?;
+        match self {
+            Self::Local(store) => store.put(&path, &compressed).await?,
+            Self::S3(store) => Box::pin(store.put(&path, compressed)).await?,
+        }
+        Ok(key)
+    }
+
+    #[tracing::instrument(name = "blob_store_get", skip(self), err)]
+    pub async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        let path = blob_path(key)?;
+        let compressed = match self {
+            Self::Local(store) => store.get(&path).await?,
+            Self::S3(store) => Box::pin(store.get(&path)).await?,
+        };
+        let compressed = match compressed {
+            Some(compressed) => compressed,
+            None => return Ok(None),
+        };
+        let bytes = zstd::decode_all(compressed.as_slice())?;
+        if blob_key(&bytes) != key {
+            return Err(anyhow!("Blob {} failed integrity check", key));
+        }
+        Ok(Some(bytes))
+    }
+
+    #[tracing::instrument(name = "blob_store_exists", skip(self), err)]
+    pub async fn exists(&self, key: &str) -> anyhow::Result<bool> {
+        self.exists_path(&blob_path(key)?).await
+    }
+
+    #[tracing::instrument(name = "blob_store_delete", skip(self), err)]
+    pub async fn delete(&self, key: &str) -> anyhow::Result<()> {
+        let path = blob_path(key)?;
+        match self {
+            Self::Local(store) => store.delete(&path).await,
+            Self::S3(store) => Box::pin(store.delete(&path)).await,
+        }
+    }
+
+    async fn exists_path(&self, path: &str) -> anyhow::Result<bool> {
+        match self {
+            Self::Local(store) => store.exists(path).await,
+            Self::S3(store) => Box::pin(store.exists(path)).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn local_store() -> BlobStore {
+        let root = env::temp_dir().join(format!("blob-store-{}", Uuid::new_v4()));
+        BlobStore::Local(LocalBlobStore::new(root.to_string_lossy().to_string()))
+    }
+
+    #[tokio::test]
+    async fn local_round_trip() {
+        let store = local_store();
+        let body = b"{\"jsonrpc\":\"2.0\",\"result\":{\"value\":1}}".repeat(100);
+        let key = store.put(&body).await.unwrap();
+        assert_eq!(key, blob_key(&body));
+        assert!(store.exists(&key).await.unwrap());
+        assert_eq!(store.get(&key).await.unwrap(), Some(body));
+        store.delete(&key).await.unwrap();
+        assert!(!store.exists(&key).await.unwrap());
+        assert_eq!(store.get(&key).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn identical_content_shares_a_key() {
+        let store = local_store();
+        let first = store.put(b"same body").await.unwrap();
+        let second = store.put(b"same body").await.unwrap();
+        let other = store.put(b"other body").await.unwrap();
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_keys() {
+        let store = local_store();
+        assert!(store.get("../../etc/passwd").await.is_err());
+        assert!(blob_path(&"a".repeat(63)).is_err());
+        assert!(blob_path(&"a".repeat(64)).is_ok());
+    }
+}
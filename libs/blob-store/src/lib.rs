@@ -0,0 +1,3 @@
+pub mod blob_store;
+pub mod local;
+pub mod s3;
@@ -0,0 +1,46 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use tokio::fs;
+
+pub struct LocalBlobStore {
+    root: PathBuf,
+}
+
+impl LocalBlobStore {
+    pub fn new(root: String) -> Self {
+        Self {
+            root: PathBuf::from(root),
+        }
+    }
+
+    pub async fn put(&self, path: &str, bytes: &[u8]) -> anyhow::Result<()> {
+        let path = self.root.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, bytes).await?;
+        fs::rename(&tmp, &path).await?;
+        Ok(())
+    }
+
+    pub async fn get(&self, path: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        match fs::read(self.root.join(path)).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn exists(&self, path: &str) -> anyhow::Result<bool> {
+        Ok(fs::try_exists(self.root.join(path)).await?)
+    }
+
+    pub async fn delete(&self, path: &str) -> anyhow::Result<()> {
+        match fs::remove_file(self.root.join(path)).await {
+            Ok(_) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
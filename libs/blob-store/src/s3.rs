@@ -0,0 +1,217 @@
+use anyhow::anyhow;
+use aws_sdk_s3::primitives::ByteStream;
+use std::env;
+
+pub struct S3BlobStore {
+    client: aws_sdk_s3::Client,
+    bucket: String,
+}
+
+impl S3BlobStore {
+    pub fn new(client: aws_sdk_s3::Client, bucket: String) -> Self {
+        Self { client, bucket }
+    }
+
+    pub async fn from_env() -> Self {
+        let shared_config = aws_config::from_env().load().await;
+        let mut builder = aws_sdk_s3::config::Builder::from(&shared_config);
+        if let Ok(endpoint) = env::var("BLOB_STORE_ENDPOINT") {
+            builder = builder.endpoint_url(endpoint).force_path_style(true);
+        }
+        Self::new(
+            aws_sdk_s3::Client::from_conf(builder.build()),
+            env::var("BLOB_STORE_BUCKET").unwrap_or("blockmesh-tasks".to_string()),
+        )
+    }
+
+    pub async fn put(&self, path: &str, bytes: Vec<u8>) -> anyhow::Result<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(path)
+            .content_type("application/zstd")
+            .body(ByteStream::from(bytes))
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to put {}: {}", path, e))?;
+        Ok(())
+    }
+
+    pub async fn get(&self, path: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        match self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(path)
+            .send()
+            .await
+        {
+            Ok(output) => Ok(Some(output.body.collect().await?.into_bytes().to_vec())),
+            Err(e) if e.as_service_error().is_some_and(|e| e.is_no_such_key()) => Ok(None),
+            Err(e) => Err(anyhow!("Failed to get {}: {}", path, e)),
+        }
+    }
+
+    pub async fn exists(&self, path: &str) -> anyhow::Result<bool> {
+        match self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(path)
+            .send()
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(e) if e.as_service_error().is_some_and(|e| e.is_not_found()) => Ok(false),
+            Err(e) => Err(anyhow!("Failed to head {}: {}", path, e)),
+        }
+    }
+
+    pub async fn delete(&self, path: &str) -> anyhow::Result<()> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(path)
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to delete {}: {}", path, e))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blob_store::{blob_key, BlobStore};
+    use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region};
+    use axum::body::Bytes;
+    use axum::extract::{Path, State};
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::response::IntoResponse;
+    use axum::routing::put;
+    use axum::Router;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use tokio::net::TcpListener;
+
+    type Objects = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+    const NO_SUCH_KEY: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?><Error><Code>NoSuchKey</Code><Message>missing</Message></Error>";
+
+    // Minimal in-memory stand-in for an S3-compatible server such as MinIO.
+    async fn spawn_stand_in() -> String {
+        let objects: Objects = Arc::default();
+        let app = Router::new()
+            .route(
+                "/:bucket/*key",
+                put(put_object)
+                    .get(get_object)
+                    .head(head_object)
+                    .delete(delete_object),
+            )
+            .with_state(objects);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    fn decode_aws_chunked(mut body: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        loop {
+            let line_end = body.windows(2).position(|w| w == b"\r\n").unwrap();
+            let header = std::str::from_utf8(&body[..line_end]).unwrap();
+            let size = usize::from_str_radix(header.split(';').next().unwrap(), 16).unwrap();
+            body = &body[line_end + 2..];
+            if size == 0 {
+                return out;
+            }
+            out.extend_from_slice(&body[..size]);
+            body = &body[size + 2..];
+        }
+    }
+
+    async fn put_object(
+        State(objects): State<Objects>,
+        Path((bucket, key)): Path<(String, String)>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> impl IntoResponse {
+        let chunked = headers
+            .get("content-encoding")
+            .is_some_and(|v| v.to_str().unwrap_or_default().contains("aws-chunked"));
+        let body = if chunked {
+            decode_aws_chunked(&body)
+        } else {
+            body.to_vec()
+        };
+        objects
+            .lock()
+            .unwrap()
+            .insert(format!("{}/{}", bucket, key), body);
+        StatusCode::OK
+    }
+
+    async fn get_object(
+        State(objects): State<Objects>,
+        Path((bucket, key)): Path<(String, String)>,
+    ) -> impl IntoResponse {
+        match objects.lock().unwrap().get(&format!("{}/{}", bucket, key)) {
+            Some(body) => (StatusCode::OK, body.clone()).into_response(),
+            None => (StatusCode::NOT_FOUND, NO_SUCH_KEY).into_response(),
+        }
+    }
+
+    async fn head_object(
+        State(objects): State<Objects>,
+        Path((bucket, key)): Path<(String, String)>,
+    ) -> impl IntoResponse {
+        match objects
+            .lock()
+            .unwrap()
+            .contains_key(&format!("{}/{}", bucket, key))
+        {
+            true => StatusCode::OK,
+            false => StatusCode::NOT_FOUND,
+        }
+    }
+
+    async fn delete_object(
+        State(objects): State<Objects>,
+        Path((bucket, key)): Path<(String, String)>,
+    ) -> impl IntoResponse {
+        objects
+            .lock()
+            .unwrap()
+            .remove(&format!("{}/{}", bucket, key));
+        StatusCode::NO_CONTENT
+    }
+
+    async fn s3_store() -> BlobStore {
+        let endpoint = spawn_stand_in().await;
+        let config = aws_sdk_s3::config::Builder::new()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("us-east-1"))
+            .credentials_provider(Credentials::new("minio", "minio123", None, None, "test"))
+            .endpoint_url(endpoint)
+            .force_path_style(true)
+            .build();
+        BlobStore::S3(S3BlobStore::new(
+            aws_sdk_s3::Client::from_conf(config),
+            "tasks".to_string(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn s3_round_trip() {
+        let store = s3_store().await;
+        let body = b"{\"jsonrpc\":\"2.0\",\"result\":{\"value\":1}}".repeat(100);
+        let key = store.put(&body).await.unwrap();
+        assert_eq!(key, blob_key(&body));
+        assert!(store.exists(&key).await.unwrap());
+        assert_eq!(store.get(&key).await.unwrap(), Some(body));
+        store.delete(&key).await.unwrap();
+        assert!(!store.exists(&key).await.unwrap());
+        assert_eq!(store.get(&key).await.unwrap(), None);
+    }
+}
@@ -9,6 +9,7 @@ pub const BLOCKMESH_FOUNDER_TWITTER_USER_ID: u64 = 1778711300127821824;
 pub const BLOCKMESH_PG_NOTIFY_WORKER: &str = "pgchannel";
 pub const BLOCKMESH_PG_NOTIFY_API: &str = "pgchannel_api";
 pub const BLOCKMESH_PG_NOTIFY_EMAIL: &str = "pgchannel_email";
+pub const BLOCKMESH_PG_NOTIFY_FLAGS: &str = "pgchannel_flags";
 pub const BLOCKMESH_VPS: &str = "https://vps.blockmesh.xyz";
 
 pub const BLOCKMESH_WS_REDIS_COUNT_KEY: &str = "BLOCKMESH_WS_REDIS_COUNT_KEY";
@@ -44,6 +45,8 @@ pub static BLOCK_MESH_CHROME_EXTENSION_LINK: &str =
 
 pub static BLOCK_MESH_APP_SERVER: &str = "https://app.blockmesh.xyz";
 pub static BLOCK_MESH_API_SERVER: &str = "https://api.blockmesh.xyz";
+pub static BLOCK_MESH_DATA_SINK: &str = "https://data-sink.blockmesh.xyz";
+pub static BLOCK_MESH_TG_PRIVACY_BOT: &str = "https://tg-privacy-bot.blockmesh.xyz";
 
 pub static BLOCK_MESH_GITHUB: &str = "https://github.com/block-mesh/block-mesh-monorepo";
 
@@ -1,72 +1,40 @@
-use crate::email_client::client::{EmailClient, CONFIRM_SUBJECT, EMAIL, REPLY_TO, RESET_SUBJECT};
-use crate::email_client::confirm_email::CONFIRM_EMAIL;
-use crate::email_client::reset_email::RESET_EMAIL;
-use aws_sdk_sesv2::operation::send_email::SendEmailOutput;
-use aws_sdk_sesv2::types::{Body, Content, Destination, EmailContent, Message};
+use crate::email_client::client::{EmailClient, EMAIL, REPLY_TO};
+use crate::email_client::template::RenderedEmail;
+use aws_sdk_sesv2::types::{Body, Content, Destination, EmailContent, Message, MessageHeader};
 
 impl EmailClient {
-    #[tracing::instrument(name = "send_confirmation_email_aws", skip_all, ret, err)]
-    pub async fn send_confirmation_email_aws(
-        &self,
-        to: &str,
-        token: &str,
-    ) -> anyhow::Result<SendEmailOutput> {
+    #[tracing::instrument(name = "send_aws", skip_all, err)]
+    pub async fn send_aws(&self, to: &str, email: &RenderedEmail) -> anyhow::Result<String> {
         let mut dest: Destination = Destination::builder().build();
         dest.to_addresses = Some(vec![to.to_string()]);
         let subject_content = Content::builder()
-            .data(CONFIRM_SUBJECT)
+            .data(&email.subject)
             .charset("UTF-8")
             .build()?;
-        let body_content = Content::builder()
-            .data(CONFIRM_EMAIL.replace(
-                "{{action_url}}",
-                &format!("{}/email_confirm?token={}", self.base_url, token),
-            ))
+        let html_content = Content::builder()
+            .data(&email.html)
             .charset("UTF-8")
             .build()?;
-        let body = Body::builder().html(body_content).build();
-        let msg = Message::builder()
-            .subject(subject_content)
-            .body(body)
-            .build();
-        let email_content = EmailContent::builder().simple(msg).build();
-        Ok(self
-            .aws_client
-            .send_email()
-            .from_email_address(EMAIL)
-            .reply_to_addresses(REPLY_TO)
-            .destination(dest)
-            .content(email_content)
-            .send()
-            .await?)
-    }
-
-    #[tracing::instrument(name = "send_reset_password_email_aws", skip_all, ret, err)]
-    pub async fn send_reset_password_email_aws(
-        &self,
-        to: &str,
-        token: &str,
-    ) -> anyhow::Result<SendEmailOutput> {
-        let mut dest: Destination = Destination::builder().build();
-        dest.to_addresses = Some(vec![to.to_string()]);
-        let subject_content = Content::builder()
-            .data(RESET_SUBJECT)
+        let text_content = Content::builder()
+            .data(&email.text)
             .charset("UTF-8")
             .build()?;
-        let body_content = Content::builder()
-            .data(RESET_EMAIL.replace(
-                "{{action_url}}",
-                &format!("{}/new_password?token={}", self.base_url, token),
-            ))
-            .charset("UTF-8")
-            .build()?;
-        let body = Body::builder().html(body_content).build();
+        let body = Body::builder()
+            .html(html_content)
+            .text(text_content)
+            .build();
+        let headers = email
+            .headers
+            .iter()
+            .map(|(name, value)| MessageHeader::builder().name(*name).value(value).build())
+            .collect::<Result<Vec<_>, _>>()?;
         let msg = Message::builder()
             .subject(subject_content)
             .body(body)
+            .set_headers(Some(headers))
             .build();
         let email_content = EmailContent::builder().simple(msg).build();
-        Ok(self
+        let output = self
             .aws_client
             .send_email()
             .from_email_address(EMAIL)
@@ -74,6 +42,7 @@ impl EmailClient {
             .destination(dest)
             .content(email_content)
             .send()
-            .await?)
+            .await?;
+        Ok(output.message_id.unwrap_or_default())
     }
 }
@@ -1,20 +1,58 @@
+use crate::email_client::template::RenderedEmail;
 use aws_config::meta::region::RegionProviderChain;
 use aws_sdk_sesv2::config::Region;
-use reqwest::{Client, ClientBuilder};
-use std::time::Duration;
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-pub const _BASE_URL: &str = "https://api.mailgun.net/v3/blockmesh.xyz/messages";
-pub const _CONFIRM_TEMPLATE_ID: &str = "confirmation email";
-pub const _RESET_TEMPLATE_ID: &str = "reset password";
 pub const EMAIL: &str = "support@blockmesh.xyz";
 pub const SMTP_FROM: &str = "support@blockmesh.xyz";
-pub const _SUBJECT: &str = "BlockMesh Network";
 pub const REPLY_TO: &str = "support@blockmesh.xyz";
-pub const CONFIRM_SUBJECT: &str = "Confirmation Email from BlockMesh Network";
-pub const RESET_SUBJECT: &str = "Reset Password from BlockMesh Network";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailProvider {
+    Aws,
+    Smtp,
+}
+
+impl EmailProvider {
+    /// Providers in the order they are tried, from the comma separated `EMAIL_PROVIDERS`.
+    pub fn from_env() -> Vec<Self> {
+        let providers: Vec<Self> = env::var("EMAIL_PROVIDERS")
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|provider| provider.trim().parse().ok())
+            .collect();
+        if providers.is_empty() {
+            vec![EmailProvider::Aws, EmailProvider::Smtp]
+        } else {
+            providers
+        }
+    }
+}
+
+impl Display for EmailProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmailProvider::Aws => write!(f, "aws"),
+            EmailProvider::Smtp => write!(f, "smtp"),
+        }
+    }
+}
+
+impl FromStr for EmailProvider {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "aws" => Ok(EmailProvider::Aws),
+            "smtp" => Ok(EmailProvider::Smtp),
+            _ => Err(anyhow::anyhow!("Unknown email provider {}", s)),
+        }
+    }
+}
 
 pub struct EmailClient {
-    pub client: Client,
     pub base_url: String,
     pub aws_client: aws_sdk_sesv2::Client,
 }
@@ -29,11 +67,20 @@ impl EmailClient {
 
         Self {
             base_url,
-            client: ClientBuilder::new()
-                .timeout(Duration::from_secs(3))
-                .build()
-                .unwrap_or_default(),
             aws_client,
         }
     }
+
+    /// Sends through a single provider and returns its message id.
+    pub async fn send(
+        &self,
+        provider: EmailProvider,
+        to: &str,
+        email: &RenderedEmail,
+    ) -> anyhow::Result<String> {
+        match provider {
+            EmailProvider::Aws => self.send_aws(to, email).await,
+            EmailProvider::Smtp => self.send_smtp(to, email).await,
+        }
+    }
 }
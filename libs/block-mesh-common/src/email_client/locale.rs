@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmailLocale {
+    #[default]
+    En,
+    Es,
+    Fr,
+}
+
+impl EmailLocale {
+    pub const ALL: [EmailLocale; 3] = [EmailLocale::En, EmailLocale::Es, EmailLocale::Fr];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            EmailLocale::En => "English",
+            EmailLocale::Es => "Español",
+            EmailLocale::Fr => "Français",
+        }
+    }
+
+    /// Picks the first supported language of an `Accept-Language` header.
+    pub fn from_accept_language(header: &str) -> Option<Self> {
+        header
+            .split(',')
+            .filter_map(|part| part.split(';').next())
+            .find_map(|tag| tag.trim().parse().ok())
+    }
+}
+
+impl Display for EmailLocale {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmailLocale::En => write!(f, "en"),
+            EmailLocale::Es => write!(f, "es"),
+            EmailLocale::Fr => write!(f, "fr"),
+        }
+    }
+}
+
+impl FromStr for EmailLocale {
+    type Err = anyhow::Error;
+
+    /// Accepts bare languages and regional tags such as `es-MX`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s.split(['-', '_']).next().unwrap_or_default();
+        match language.to_ascii_lowercase().as_str() {
+            "en" => Ok(EmailLocale::En),
+            "es" => Ok(EmailLocale::Es),
+            "fr" => Ok(EmailLocale::Fr),
+            _ => Err(anyhow::anyhow!("Unsupported locale {}", s)),
+        }
+    }
+}
@@ -1,5 +1,9 @@
+#[cfg(feature = "email-client")]
 pub mod aws;
+#[cfg(feature = "email-client")]
 pub mod client;
-pub mod confirm_email;
-pub mod reset_email;
+pub mod locale;
+#[cfg(feature = "email-client")]
 pub mod smtp;
+#[cfg(feature = "email-client")]
+pub mod template;
@@ -0,0 +1,307 @@
+use crate::email_client::locale::EmailLocale;
+use askama::Template;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// An email waiting in the outbox, serialized into its `payload` column.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "template", rename_all = "snake_case")]
+pub enum EmailTemplate {
+    ConfirmEmail { token: String },
+    ResetPassword { token: String },
+    ProbeAlert { probe_name: String, details: String },
+}
+
+/// Account emails are always sent, every other category can be turned off by the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmailCategory {
+    Account,
+    ProbeAlerts,
+}
+
+impl Display for EmailCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmailCategory::Account => write!(f, "account"),
+            EmailCategory::ProbeAlerts => write!(f, "probe_alerts"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderedEmail {
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+    /// Extra headers, such as `List-Unsubscribe` for optional emails.
+    pub headers: Vec<(&'static str, String)>,
+}
+
+struct EmailCopy {
+    subject: String,
+    heading: String,
+    greeting: &'static str,
+    paragraphs: Vec<String>,
+    action: &'static str,
+    support: &'static str,
+    unsubscribe: &'static str,
+}
+
+#[derive(Template)]
+#[template(path = "emails/email.html")]
+struct HtmlEmail<'a> {
+    locale: EmailLocale,
+    copy: &'a EmailCopy,
+    action_url: &'a str,
+    unsubscribe_url: Option<&'a str>,
+}
+
+#[derive(Template)]
+#[template(path = "emails/email.txt")]
+struct TextEmail<'a> {
+    copy: &'a EmailCopy,
+    action_url: &'a str,
+    unsubscribe_url: Option<&'a str>,
+}
+
+impl EmailTemplate {
+    pub fn name(&self) -> &'static str {
+        match self {
+            EmailTemplate::ConfirmEmail { .. } => "confirm_email",
+            EmailTemplate::ResetPassword { .. } => "reset_password",
+            EmailTemplate::ProbeAlert { .. } => "probe_alert",
+        }
+    }
+
+    pub fn category(&self) -> EmailCategory {
+        match self {
+            EmailTemplate::ConfirmEmail { .. } | EmailTemplate::ResetPassword { .. } => {
+                EmailCategory::Account
+            }
+            EmailTemplate::ProbeAlert { .. } => EmailCategory::ProbeAlerts,
+        }
+    }
+
+    fn action_url(&self, base_url: &str) -> String {
+        match self {
+            EmailTemplate::ConfirmEmail { token } => {
+                format!("{}/email_confirm?token={}", base_url, token)
+            }
+            EmailTemplate::ResetPassword { token } => {
+                format!("{}/new_password?token={}", base_url, token)
+            }
+            EmailTemplate::ProbeAlert { .. } => format!("{}/ui/dashboard", base_url),
+        }
+    }
+
+    pub fn render(
+        &self,
+        locale: EmailLocale,
+        base_url: &str,
+        unsubscribe_url: Option<&str>,
+    ) -> anyhow::Result<RenderedEmail> {
+        let copy = self.copy(locale);
+        let action_url = self.action_url(base_url);
+        let html = HtmlEmail {
+            locale,
+            copy: &copy,
+            action_url: &action_url,
+            unsubscribe_url,
+        }
+        .render()?;
+        let text = TextEmail {
+            copy: &copy,
+            action_url: &action_url,
+            unsubscribe_url,
+        }
+        .render()?;
+        let headers = match unsubscribe_url {
+            Some(url) => vec![
+                ("List-Unsubscribe", format!("<{}>", url)),
+                (
+                    "List-Unsubscribe-Post",
+                    "List-Unsubscribe=One-Click".to_string(),
+                ),
+            ],
+            None => Vec::new(),
+        };
+        Ok(RenderedEmail {
+            subject: copy.subject,
+            html,
+            text,
+            headers,
+        })
+    }
+
+    fn copy(&self, locale: EmailLocale) -> EmailCopy {
+        let (greeting, action, support, unsubscribe) = match locale {
+            EmailLocale::En => ("Hi,", "Click Here", "Support", "Unsubscribe"),
+            EmailLocale::Es => ("Hola,", "Haz clic aquí", "Soporte", "Darse de baja"),
+            EmailLocale::Fr => ("Bonjour,", "Cliquez ici", "Assistance", "Se désabonner"),
+        };
+        let (subject, heading, paragraphs) = match (self, locale) {
+            (EmailTemplate::ConfirmEmail { .. }, EmailLocale::En) => (
+                "Confirmation Email from BlockMesh Network".to_string(),
+                "BlockMesh - Confirmation Email",
+                vec![
+                    "Thank you for registering.".to_string(),
+                    "Please confirm your email by clicking the following link:".to_string(),
+                ],
+            ),
+            (EmailTemplate::ConfirmEmail { .. }, EmailLocale::Es) => (
+                "Correo de confirmación de BlockMesh Network".to_string(),
+                "BlockMesh - Confirmación de correo",
+                vec![
+                    "Gracias por registrarte.".to_string(),
+                    "Confirma tu correo haciendo clic en el siguiente enlace:".to_string(),
+                ],
+            ),
+            (EmailTemplate::ConfirmEmail { .. }, EmailLocale::Fr) => (
+                "E-mail de confirmation de BlockMesh Network".to_string(),
+                "BlockMesh - Confirmation de l'e-mail",
+                vec![
+                    "Merci pour votre inscription.".to_string(),
+                    "Veuillez confirmer votre e-mail en cliquant sur le lien suivant :".to_string(),
+                ],
+            ),
+            (EmailTemplate::ResetPassword { .. }, EmailLocale::En) => (
+                "Reset Password from BlockMesh Network".to_string(),
+                "BlockMesh - Reset Password",
+                vec![
+                    "You have requested to reset your password.".to_string(),
+                    "Please click the following link to continue:".to_string(),
+                ],
+            ),
+            (EmailTemplate::ResetPassword { .. }, EmailLocale::Es) => (
+                "Restablecer contraseña de BlockMesh Network".to_string(),
+                "BlockMesh - Restablecer contraseña",
+                vec![
+                    "Has solicitado restablecer tu contraseña.".to_string(),
+                    "Haz clic en el siguiente enlace para continuar:".to_string(),
+                ],
+            ),
+            (EmailTemplate::ResetPassword { .. }, EmailLocale::Fr) => (
+                "Réinitialisation du mot de passe BlockMesh Network".to_string(),
+                "BlockMesh - Réinitialiser le mot de passe",
+                vec![
+                    "Vous avez demandé la réinitialisation de votre mot de passe.".to_string(),
+                    "Veuillez cliquer sur le lien suivant pour continuer :".to_string(),
+                ],
+            ),
+            (
+                EmailTemplate::ProbeAlert {
+                    probe_name,
+                    details,
+                },
+                EmailLocale::En,
+            ) => (
+                "Probe Alert from BlockMesh Network".to_string(),
+                "BlockMesh - Probe Alert",
+                vec![
+                    format!("Your probe {} breached its alert threshold.", probe_name),
+                    details.clone(),
+                    "Please click the following link to view your dashboard:".to_string(),
+                ],
+            ),
+            (
+                EmailTemplate::ProbeAlert {
+                    probe_name,
+                    details,
+                },
+                EmailLocale::Es,
+            ) => (
+                "Alerta de sonda de BlockMesh Network".to_string(),
+                "BlockMesh - Alerta de sonda",
+                vec![
+                    format!("Tu sonda {} superó su umbral de alerta.", probe_name),
+                    details.clone(),
+                    "Haz clic en el siguiente enlace para ver tu panel:".to_string(),
+                ],
+            ),
+            (
+                EmailTemplate::ProbeAlert {
+                    probe_name,
+                    details,
+                },
+                EmailLocale::Fr,
+            ) => (
+                "Alerte de sonde de BlockMesh Network".to_string(),
+                "BlockMesh - Alerte de sonde",
+                vec![
+                    format!("Votre sonde {} a dépassé son seuil d'alerte.", probe_name),
+                    details.clone(),
+                    "Veuillez cliquer sur le lien suivant pour voir votre tableau de bord :"
+                        .to_string(),
+                ],
+            ),
+        };
+        EmailCopy {
+            subject,
+            heading: heading.to_string(),
+            greeting,
+            paragraphs,
+            action,
+            support,
+            unsubscribe,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_localised_copy_and_escapes_values() {
+        let email = EmailTemplate::ProbeAlert {
+            probe_name: "<script>".to_string(),
+            details: "99% down".to_string(),
+        }
+        .render(
+            EmailLocale::Es,
+            "https://app.blockmesh.xyz",
+            Some("https://app.blockmesh.xyz/unsubscribe?token=abc"),
+        )
+        .unwrap();
+        assert_eq!(email.subject, "Alerta de sonda de BlockMesh Network");
+        assert!(email.html.contains("&lt;script&gt;"));
+        assert!(!email.html.contains("<script>"));
+        assert!(email.html.contains("Darse de baja"));
+        assert_eq!(
+            email.headers[0],
+            (
+                "List-Unsubscribe",
+                "<https://app.blockmesh.xyz/unsubscribe?token=abc>".to_string()
+            )
+        );
+        assert!(email
+            .text
+            .contains("https://app.blockmesh.xyz/ui/dashboard"));
+    }
+
+    #[test]
+    fn account_emails_skip_the_unsubscribe_link() {
+        let email = EmailTemplate::ConfirmEmail {
+            token: "t".to_string(),
+        }
+        .render(EmailLocale::En, "https://app.blockmesh.xyz", None)
+        .unwrap();
+        assert!(email.html.contains("/email_confirm?token=t"));
+        assert!(!email.html.contains("Unsubscribe"));
+        assert!(email.headers.is_empty());
+    }
+
+    #[test]
+    fn payload_round_trips() {
+        let template = EmailTemplate::ResetPassword {
+            token: "t".to_string(),
+        };
+        let value = serde_json::to_value(&template).unwrap();
+        assert_eq!(value["template"], "reset_password");
+        assert_eq!(
+            serde_json::from_value::<EmailTemplate>(value).unwrap(),
+            template
+        );
+    }
+}
@@ -1,11 +1,18 @@
 use crate::constants::{DeviceType, BLOCK_MESH_FEATURE_FLAGS};
+use crate::feature_flags::{FlagContext, FlagRuleset};
 use dashmap::try_result::TryResult::Present;
 use dashmap::DashMap;
-use reqwest::Client;
+use reqwest::header::{ACCEPT, ETAG, IF_NONE_MATCH};
+use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::sync::{Arc, RwLock};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
 
-const FLAGS: [&str; 1] = ["polling_interval"];
+// Overrides the short timeout of `http_client`, the server keeps the stream alive with comments.
+#[cfg(not(target_arch = "wasm32"))]
+const STREAM_TIMEOUT: Duration = Duration::from_secs(3600);
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum FlagValue {
@@ -36,37 +43,146 @@ impl TryInto<f64> for FlagValue {
     }
 }
 
+impl TryFrom<&Value> for FlagValue {
+    type Error = ();
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Bool(b) => Ok(FlagValue::Boolean(*b)),
+            Value::Number(n) => n.as_f64().map(FlagValue::Number).ok_or(()),
+            Value::String(s) => Ok(FlagValue::String(s.clone())),
+            _ => Err(()),
+        }
+    }
+}
+
 #[tracing::instrument(name = "get_all_flags", skip_all, ret, err)]
 pub async fn get_all_flags(
     client: &Client,
     device_type: DeviceType,
 ) -> anyhow::Result<DashMap<String, FlagValue>> {
-    let flags: DashMap<String, FlagValue> = DashMap::new();
-    for flag in FLAGS {
-        tracing::info!("Fetching flag {:?}", flag);
-        let value = match get_flag_value(flag, client, device_type).await {
-            Ok(v) => v,
-            Err(_e) => continue,
-        };
-        let value = match value {
-            Some(v) => v,
-            None => continue,
-        };
-
-        tracing::info!("Fetching flag {:?} from http , value = {:?}", flag, value);
-        if value.is_boolean() {
-            flags.insert(
-                flag.to_string(),
-                FlagValue::Boolean(value.as_bool().unwrap()),
-            );
-        } else if value.is_string() {
-            flags.insert(flag.to_string(), FlagValue::String(value.to_string()));
-        } else if value.is_number() {
-            flags.insert(flag.to_string(), FlagValue::Number(value.as_f64().unwrap()));
+    let ruleset = match get_ruleset(client, None).await? {
+        Some((ruleset, _)) => ruleset,
+        None => return Err(anyhow::anyhow!("Empty ruleset response")),
+    };
+    Ok(evaluate_all(&ruleset, &FlagContext::new(device_type)))
+}
+
+pub fn evaluate_all(ruleset: &FlagRuleset, context: &FlagContext) -> DashMap<String, FlagValue> {
+    ruleset
+        .flags
+        .iter()
+        .filter_map(|flag| {
+            let value = FlagValue::try_from(flag.evaluate(context)).ok()?;
+            Some((flag.name.clone(), value))
+        })
+        .collect()
+}
+
+/// `Ok(None)` when the server answered 304 for `etag`.
+#[tracing::instrument(name = "get_ruleset", skip_all, err)]
+pub async fn get_ruleset(
+    client: &Client,
+    etag: Option<&str>,
+) -> anyhow::Result<Option<(FlagRuleset, Option<String>)>> {
+    let mut request = client.get(format!("{}/flags", BLOCK_MESH_FEATURE_FLAGS));
+    if let Some(etag) = etag {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    let response = request.send().await?;
+    if response.status() == StatusCode::NOT_MODIFIED {
+        return Ok(None);
+    }
+    if response.status() != StatusCode::OK {
+        return Err(anyhow::anyhow!(response.text().await?));
+    }
+    let etag = response
+        .headers()
+        .get(ETAG)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+    let ruleset: FlagRuleset = response.json().await?;
+    Ok(Some((ruleset, etag)))
+}
+
+/// Keeps the full ruleset in memory so flags are evaluated without a request per lookup.
+#[derive(Clone)]
+pub struct FlagsClient {
+    client: Client,
+    state: Arc<RwLock<(Arc<FlagRuleset>, Option<String>)>>,
+}
+
+impl FlagsClient {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            state: Arc::new(RwLock::new((Arc::new(FlagRuleset::default()), None))),
+        }
+    }
+
+    pub fn ruleset(&self) -> Arc<FlagRuleset> {
+        self.state.read().unwrap().0.clone()
+    }
+
+    pub fn evaluate(&self, name: &str, context: &FlagContext) -> Option<Value> {
+        self.ruleset().evaluate(name, context)
+    }
+
+    fn replace(&self, ruleset: FlagRuleset, etag: Option<String>) {
+        let mut state = self.state.write().unwrap();
+        *state = (Arc::new(ruleset), etag);
+    }
+
+    /// Returns whether the ruleset changed.
+    pub async fn refresh(&self) -> anyhow::Result<bool> {
+        let etag = self.state.read().unwrap().1.clone();
+        match get_ruleset(&self.client, etag.as_deref()).await? {
+            Some((ruleset, etag)) => {
+                let changed = ruleset.revision != self.ruleset().revision;
+                self.replace(ruleset, etag);
+                Ok(changed)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Applies every ruleset the server pushes until the stream ends.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tracing::instrument(name = "FlagsClient::stream", skip_all, err)]
+    pub async fn stream(&self) -> anyhow::Result<()> {
+        let mut response = self
+            .client
+            .get(format!("{}/flags/stream", BLOCK_MESH_FEATURE_FLAGS))
+            .header(ACCEPT, "text/event-stream")
+            .timeout(STREAM_TIMEOUT)
+            .send()
+            .await?
+            .error_for_status()?;
+        // Bytes until a full event arrived, a chunk can end inside a multi-byte character.
+        let mut buffer: Vec<u8> = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            buffer.extend_from_slice(&chunk);
+            while let Some(end) = buffer.windows(2).position(|w| w == b"\n\n") {
+                let event: Vec<u8> = buffer.drain(..end + 2).collect();
+                let data: String = String::from_utf8_lossy(&event)
+                    .lines()
+                    .filter_map(|line| line.strip_prefix("data:"))
+                    .map(str::trim_start)
+                    .collect();
+                if data.is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<FlagRuleset>(&data) {
+                    Ok(ruleset) => {
+                        let etag = format!("\"{}\"", ruleset.revision);
+                        self.replace(ruleset, Some(etag));
+                    }
+                    Err(e) => tracing::warn!("Invalid ruleset event: {}", e),
+                }
+            }
         }
-        tracing::info!("Finished fetching flag {:?} , value = {:?}", flag, value);
+        Ok(())
     }
-    Ok(flags)
 }
 
 pub fn get_flag_value_from_map(
@@ -102,7 +218,6 @@ pub async fn get_flag_value(
 mod tests {
     use super::*;
     use crate::reqwest::http_client;
-    use std::sync::Arc;
     use tracing_test::traced_test;
     use uuid::Uuid;
 
@@ -0,0 +1,287 @@
+use crate::constants::DeviceType;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagKind {
+    Boolean,
+    Number,
+    String,
+    Json,
+}
+
+impl FlagKind {
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::Bool(_) => FlagKind::Boolean,
+            Value::Number(_) => FlagKind::Number,
+            Value::String(_) => FlagKind::String,
+            _ => FlagKind::Json,
+        }
+    }
+
+    pub fn accepts(&self, value: &Value) -> bool {
+        *self == FlagKind::Json || *self == FlagKind::of(value)
+    }
+}
+
+impl Display for FlagKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FlagKind::Boolean => write!(f, "boolean"),
+            FlagKind::Number => write!(f, "number"),
+            FlagKind::String => write!(f, "string"),
+            FlagKind::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl FromStr for FlagKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "boolean" => Ok(FlagKind::Boolean),
+            "number" => Ok(FlagKind::Number),
+            "string" => Ok(FlagKind::String),
+            "json" => Ok(FlagKind::Json),
+            _ => Err(anyhow::anyhow!("Unknown flag kind {}", s)),
+        }
+    }
+}
+
+/// Every condition that is set has to match, empty lists match anything.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct FlagRule {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub device_types: Vec<DeviceType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub countries: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollout_percent: Option<u8>,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FlagDefinition {
+    pub name: String,
+    pub kind: FlagKind,
+    pub default_value: Value,
+    #[serde(default)]
+    pub rules: Vec<FlagRule>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct FlagRuleset {
+    pub revision: String,
+    pub flags: Vec<FlagDefinition>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FlagContext {
+    pub device_type: DeviceType,
+    pub version: Option<String>,
+    pub user_id: Option<Uuid>,
+    pub country: Option<String>,
+}
+
+impl FlagContext {
+    pub fn new(device_type: DeviceType) -> Self {
+        Self {
+            device_type,
+            version: None,
+            user_id: None,
+            country: None,
+        }
+    }
+}
+
+impl FlagRule {
+    pub fn matches(&self, flag: &str, context: &FlagContext) -> bool {
+        if !self.device_types.is_empty() && !self.device_types.contains(&context.device_type) {
+            return false;
+        }
+        if self.min_version.is_some() || self.max_version.is_some() {
+            let Some(version) = &context.version else {
+                return false;
+            };
+            if let Some(min) = &self.min_version {
+                if compare_versions(version, min) == Ordering::Less {
+                    return false;
+                }
+            }
+            if let Some(max) = &self.max_version {
+                if compare_versions(version, max) == Ordering::Greater {
+                    return false;
+                }
+            }
+        }
+        if !self.countries.is_empty() {
+            let Some(country) = &context.country else {
+                return false;
+            };
+            if !self
+                .countries
+                .iter()
+                .any(|c| c.eq_ignore_ascii_case(country))
+            {
+                return false;
+            }
+        }
+        if let Some(percent) = self.rollout_percent {
+            let Some(user_id) = &context.user_id else {
+                return false;
+            };
+            if rollout_bucket(flag, user_id) >= u64::from(percent) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl FlagDefinition {
+    /// First matching rule wins, rule values of the wrong kind are skipped.
+    pub fn evaluate(&self, context: &FlagContext) -> &Value {
+        self.rules
+            .iter()
+            .filter(|rule| self.kind.accepts(&rule.value))
+            .find(|rule| rule.matches(&self.name, context))
+            .map(|rule| &rule.value)
+            .unwrap_or(&self.default_value)
+    }
+}
+
+impl FlagRuleset {
+    pub fn get(&self, name: &str) -> Option<&FlagDefinition> {
+        self.flags.iter().find(|flag| flag.name == name)
+    }
+
+    pub fn evaluate(&self, name: &str, context: &FlagContext) -> Option<Value> {
+        self.get(name).map(|flag| flag.evaluate(context).clone())
+    }
+}
+
+/// Stable across processes and releases, unlike `DefaultHasher`, so a user keeps their bucket.
+pub fn fingerprint(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Salted with the flag name so the same users are not always first in line.
+pub fn rollout_bucket(flag: &str, user_id: &Uuid) -> u64 {
+    let key = format!("{}:{}", flag, user_id);
+    fingerprint(key.as_bytes()) % 100
+}
+
+/// Dotted versions compared numerically, missing parts count as zero.
+pub fn compare_versions(left: &str, right: &str) -> Ordering {
+    let parse = |version: &str| -> Vec<u64> {
+        version
+            .trim_start_matches('v')
+            .split('.')
+            .map(|part| {
+                part.chars()
+                    .take_while(|c| c.is_ascii_digit())
+                    .collect::<String>()
+                    .parse()
+                    .unwrap_or_default()
+            })
+            .collect()
+    };
+    let (left, right) = (parse(left), parse(right));
+    for index in 0..left.len().max(right.len()) {
+        let ordering = left
+            .get(index)
+            .unwrap_or(&0)
+            .cmp(right.get(index).unwrap_or(&0));
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flag(rules: Vec<FlagRule>) -> FlagDefinition {
+        FlagDefinition {
+            name: "use_websocket".to_string(),
+            kind: FlagKind::Boolean,
+            default_value: Value::Bool(false),
+            rules,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("0.0.412", "0.0.41"), Ordering::Greater);
+        assert_eq!(compare_versions("v1.2", "1.2.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.2.3-beta", "1.10"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_rules_match_in_order() {
+        let flag = flag(vec![
+            FlagRule {
+                device_types: vec![DeviceType::Cli],
+                min_version: Some("0.0.400".to_string()),
+                value: Value::Bool(true),
+                ..FlagRule::default()
+            },
+            FlagRule {
+                countries: vec!["US".to_string()],
+                value: Value::String("wrong kind".to_string()),
+                ..FlagRule::default()
+            },
+        ]);
+        let mut context = FlagContext::new(DeviceType::Cli);
+        assert_eq!(flag.evaluate(&context), &Value::Bool(false));
+        context.version = Some("0.0.412".to_string());
+        assert_eq!(flag.evaluate(&context), &Value::Bool(true));
+        context.device_type = DeviceType::Extension;
+        context.country = Some("us".to_string());
+        assert_eq!(flag.evaluate(&context), &Value::Bool(false));
+    }
+
+    #[test]
+    fn test_rollout_percent() {
+        let flag = flag(vec![FlagRule {
+            rollout_percent: Some(30),
+            value: Value::Bool(true),
+            ..FlagRule::default()
+        }]);
+        let enabled = (0..10_000)
+            .map(|_| FlagContext {
+                user_id: Some(Uuid::new_v4()),
+                ..FlagContext::new(DeviceType::Extension)
+            })
+            .filter(|context| flag.evaluate(context) == &Value::Bool(true))
+            .count();
+        assert!((2_500..3_500).contains(&enabled), "{}", enabled);
+        let user_id = Uuid::new_v4();
+        assert_eq!(
+            rollout_bucket("use_websocket", &user_id),
+            rollout_bucket("use_websocket", &user_id)
+        );
+        assert_eq!(
+            flag.evaluate(&FlagContext::new(DeviceType::Extension)),
+            &Value::Bool(false)
+        );
+    }
+}
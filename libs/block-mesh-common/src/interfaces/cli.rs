@@ -1,15 +1,19 @@
 use clap::{Parser, ValueEnum};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 #[derive(Parser, Debug, Clone, PartialEq, Default)]
 #[command(author = "BlockMesh Network", version, about)]
 pub struct CliOpts {
-    /// Email
-    #[arg(long)]
-    pub email: String,
+    /// Email, leave out together with the password to log in with a device code
+    #[arg(long, requires = "password")]
+    pub email: Option<String>,
     /// Password
-    #[arg(long)]
-    pub password: String,
+    #[arg(long, requires = "email")]
+    pub password: Option<String>,
+    /// Code from the authenticator app, for accounts with two factor enabled
+    #[arg(long, requires = "password")]
+    pub totp_code: Option<String>,
     #[arg(value_enum, default_value_t = CliOptMod::Login)]
     /// Mode
     pub mode: CliOptMod,
@@ -21,6 +25,16 @@ pub struct CliOpts {
     /// DePIN aggregator name
     #[arg(long)]
     pub depin_aggregator: Option<String>,
+    /// Name shown when approving the device code, defaults to $HOSTNAME
+    #[arg(long)]
+    pub device_name: Option<String>,
+    /// Where the device token is kept between runs, so a fleet only approves each node once
+    #[arg(long)]
+    pub credentials_file: Option<PathBuf>,
+    /// Where the node key for sealed tasks is kept, defaults to blockmesh/blockmesh-node.key
+    /// in the user's config dir
+    #[arg(long)]
+    pub node_key_file: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, ValueEnum, PartialEq, Default)]
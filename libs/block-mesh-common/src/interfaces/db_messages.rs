@@ -11,6 +11,7 @@ pub enum DBMessageTypes {
     AnalyticsMessage,
     DailyStatMessage,
     AggregateSetToMessage,
+    AggregateSampleMessage,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -21,6 +22,7 @@ pub enum DBMessage {
     AnalyticsMessage(AnalyticsMessage),
     DailyStatMessage(DailyStatMessage),
     AggregateSetToMessage(AggregateSetToMessage),
+    AggregateSampleMessage(AggregateSampleMessage),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -43,6 +45,8 @@ pub struct AggregateAddToMessage {
     pub name: String,
     pub value: Value,
     pub msg_type: DBMessageTypes,
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -51,6 +55,19 @@ pub struct AggregateSetToMessage {
     pub name: String,
     pub value: Value,
     pub msg_type: DBMessageTypes,
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+}
+
+/// A measurement the aggregate is a running average of, instead of a value to add or set.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AggregateSampleMessage {
+    pub user_id: Uuid,
+    pub name: String,
+    pub value: Value,
+    pub msg_type: DBMessageTypes,
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -1,5 +1,7 @@
 use crate::constants::DeviceType;
+use crate::email_client::locale::EmailLocale;
 use crate::interfaces::ws_api::WsServerMessage;
+use crate::siws::SiwsPurpose;
 use chrono::{DateTime, NaiveDate, Utc};
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
@@ -22,21 +24,41 @@ pub struct GetTaskResponse {
     pub headers: Option<Value>,
     #[typeshare(serialized_as = "object")]
     pub body: Option<Value>,
+    #[serde(default)]
+    pub encrypted_payload: Option<String>,
+    #[serde(default)]
+    pub reply_key: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct EncryptedTaskPayload {
+    pub url: String,
+    pub method: String,
+    pub headers: Option<Value>,
+    pub body: Option<Value>,
 }
 
 #[typeshare]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct GetTaskRequest {
+    #[serde(default)]
     pub email: String,
     #[typeshare(serialized_as = "string")]
+    #[serde(default)]
     pub api_token: Uuid,
+    /// Sealed tasks are only handed to the node holding this key.
+    #[typeshare(serialized_as = "Option<string>")]
+    #[serde(default)]
+    pub node_key_id: Option<Uuid>,
 }
 
 #[typeshare]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SubmitTaskRequest {
+    #[serde(default)]
     pub email: String,
     #[typeshare(serialized_as = "string")]
+    #[serde(default)]
     pub api_token: Uuid,
     #[typeshare(serialized_as = "string")]
     pub task_id: Uuid,
@@ -64,20 +86,30 @@ pub struct ClientsMetadata {
     pub version: Option<String>,
 }
 
+/// `email` and `api_token` can be left out when the token is sent as a bearer header.
 #[typeshare]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ReportUptimeRequest {
+    #[serde(default)]
     pub email: String,
     #[typeshare(serialized_as = "string")]
+    #[serde(default)]
     pub api_token: Uuid,
     pub ip: Option<String>,
+    /// Generated by the client for every report, so the manager can tell a retried report
+    /// from a new one.
+    #[typeshare(serialized_as = "Option<string>")]
+    #[serde(default)]
+    pub report_id: Option<Uuid>,
 }
 
 #[typeshare]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DashboardRequest {
+    #[serde(default)]
     pub email: String,
     #[typeshare(serialized_as = "string")]
+    #[serde(default)]
     pub api_token: Uuid,
 }
 
@@ -98,6 +130,9 @@ pub struct SubmitTaskResponse {
 pub struct GetTokenRequest {
     pub email: String,
     pub password: String,
+    /// Required when the account has two factor enabled, only the app server checks it.
+    #[serde(default)]
+    pub totp_code: Option<String>,
 }
 
 #[typeshare]
@@ -126,6 +161,9 @@ pub struct GetTokenResponse {
     #[typeshare(serialized_as = "string")]
     pub api_token: Option<Uuid>,
     pub message: Option<String>,
+    #[serde(default)]
+    #[typeshare(serialized_as = "string")]
+    pub user_id: Option<Uuid>,
 }
 
 #[typeshare]
@@ -158,8 +196,8 @@ pub struct SigArray(pub Vec<u8>);
 pub struct LoginWalletForm {
     pub pubkey: String,
     pub signature: String,
-    pub nonce: String,
-    pub password: String,
+    /// The Sign-In-With-Solana message issued by the server, exactly as signed.
+    pub message: String,
 }
 
 #[typeshare]
@@ -194,6 +232,8 @@ pub struct NewPasswordForm {
     pub token: String,
     pub password: String,
     pub password_confirm: String,
+    #[serde(default)]
+    pub totp_code: Option<String>,
 }
 
 #[typeshare]
@@ -211,9 +251,7 @@ pub struct RegisterForm {
 pub struct RegisterWalletForm {
     pub pubkey: String,
     pub signature: String,
-    pub nonce: String,
-    pub password: String,
-    pub password_confirm: String,
+    pub message: String,
     pub invite_code: String,
     pub cftoken: Option<String>,
 }
@@ -263,8 +301,10 @@ pub struct GetLatestInviteCodeResponse {
 #[typeshare]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ReportBandwidthRequest {
+    #[serde(default)]
     pub email: String,
     #[typeshare(serialized_as = "string")]
+    #[serde(default)]
     pub api_token: Uuid,
     pub download_speed: f64,
     pub upload_speed: f64,
@@ -303,6 +343,11 @@ pub struct DashboardResponse {
     pub verified_email: bool,
     pub user_ips: Vec<UserIpInfo>,
     pub wallet_address: Option<String>,
+    /// Points credited from referees, already part of `points`.
+    #[serde(default)]
+    pub referral_points: f64,
+    #[serde(default)]
+    pub referral_earnings: Vec<ReferralEarningForDashboard>,
 }
 
 #[typeshare]
@@ -313,6 +358,9 @@ pub struct PerkUI {
     pub name: String,
     pub multiplier: f64,
     pub one_time_bonus: f64,
+    #[serde(default)]
+    #[typeshare(serialized_as = "Date")]
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 #[typeshare]
@@ -333,6 +381,20 @@ pub struct DailyStatForDashboard {
     #[typeshare(serialized_as = "Date")]
     pub day: NaiveDate,
     pub points: f64,
+    /// Combined multiplier of the perks that counted on the day.
+    #[serde(default)]
+    pub perk_multiplier: f64,
+    #[serde(default)]
+    pub perks: Vec<String>,
+}
+
+#[typeshare]
+#[derive(Default, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReferralEarningForDashboard {
+    #[typeshare(serialized_as = "Date")]
+    pub day: NaiveDate,
+    pub level_1_points: f64,
+    pub level_2_points: f64,
 }
 
 #[typeshare]
@@ -357,6 +419,8 @@ pub struct ConnectWalletRequest {
     pub pubkey: String,
     pub message: String,
     pub signature: Vec<u8>,
+    #[serde(default)]
+    pub totp_code: Option<String>,
 }
 
 #[typeshare]
@@ -365,6 +429,54 @@ pub struct ConnectWalletResponse {
     pub status: i32,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SiwsMessageQuery {
+    pub address: String,
+    pub purpose: SiwsPurpose,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SiwsMessageResponse {
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WalletChangeAction {
+    Linked,
+    Unlinked,
+    /// Before wallets could be linked side by side, connecting one replaced the previous wallet.
+    Replaced,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WalletChangeEntry {
+    pub action: WalletChangeAction,
+    pub address: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UserWalletInfo {
+    pub address: String,
+    pub linked_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UserWalletsResponse {
+    /// The wallet perks and the extension use, the most recently linked one.
+    pub primary: Option<String>,
+    pub wallets: Vec<UserWalletInfo>,
+    pub history: Vec<WalletChangeEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UnlinkWalletRequest {
+    pub address: String,
+    #[serde(default)]
+    pub totp_code: Option<String>,
+}
+
 #[typeshare]
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Referral {
@@ -378,6 +490,8 @@ pub struct Referral {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct EditInviteCodeForm {
     pub new_invite_code: String,
+    #[serde(default)]
+    pub totp_code: Option<String>,
 }
 
 #[typeshare]
@@ -519,6 +633,207 @@ impl CronReportAggregateEntry {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RebuildAggregatesRequest {
+    pub user_id: Option<Uuid>,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RebuildAggregatesResponse {
+    pub rows_affected: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum CronJobAction {
+    Trigger,
+    Pause,
+    Resume,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CronJobActionRequest {
+    pub name: String,
+    pub action: CronJobAction,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PerkDefinitionRequest {
+    pub name: String,
+    pub multiplier: f64,
+    #[serde(default)]
+    pub one_time_bonus: f64,
+    pub stacking_group: Option<String>,
+    /// `Multiply` or `Max`.
+    pub stacking: String,
+    pub max_multiplier: Option<f64>,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_until: Option<DateTime<Utc>>,
+    pub duration_days: Option<i32>,
+    /// `ConsecutiveNodeDays` or `CompletedTasks`, perks without one are granted by code.
+    pub condition: Option<String>,
+    pub condition_threshold: Option<i64>,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreateInviteCampaignRequest {
+    pub name: String,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub max_uses: Option<i32>,
+    /// Perk multiplier granted to each signup, for `perk_days` days.
+    pub perk_multiplier: Option<f64>,
+    pub perk_days: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreatePartnerInviteCodeRequest {
+    pub campaign: String,
+    pub invite_code: String,
+    /// The partner account signups are attributed to.
+    pub owner_email: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InviteCampaignStats {
+    pub id: Uuid,
+    pub name: String,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub max_uses: Option<i32>,
+    pub uses: i32,
+    pub perk_multiplier: Option<f64>,
+    pub perk_days: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub codes: Vec<String>,
+    pub signups: i64,
+    /// Signups that reported uptime at least once.
+    pub activated: i64,
+    pub retained_7d: i64,
+    pub retained_30d: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProbeAssertion {
+    pub pointer: String,
+    pub equals: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreateProbeRequest {
+    pub email: String,
+    pub api_token: Uuid,
+    pub name: String,
+    pub url: String,
+    pub method: String,
+    pub headers: Option<Value>,
+    pub body: Option<Value>,
+    pub expected_status: Option<i32>,
+    pub assertion: Option<ProbeAssertion>,
+    pub interval_secs: i32,
+    #[serde(default)]
+    pub regions: Vec<String>,
+    pub latency_threshold_ms: Option<f64>,
+    pub availability_threshold: Option<f64>,
+    pub alert_email: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreateProbeResponse {
+    pub id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProbeRequest {
+    pub email: String,
+    pub api_token: Uuid,
+    pub probe_id: Uuid,
+    pub window_secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProbeSlo {
+    pub country: String,
+    pub asn: String,
+    pub total: i64,
+    pub availability: f64,
+    pub avg_latency: f64,
+    pub p95_latency: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProbeSloResponse {
+    pub probe_id: Uuid,
+    pub window_secs: u64,
+    pub by_region: Vec<ProbeSlo>,
+    pub by_asn: Vec<ProbeSlo>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaskRetentionRequest {
+    pub email: String,
+    pub api_token: Uuid,
+    pub retention_days: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaskRetentionResponse {
+    pub retention_days: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RegisterNodeKeyRequest {
+    #[serde(default)]
+    pub email: String,
+    #[serde(default)]
+    pub api_token: Uuid,
+    pub public_key: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NodeKeysRequest {
+    pub email: String,
+    pub api_token: Uuid,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NodeKey {
+    pub id: Uuid,
+    pub public_key: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreateEncryptedTaskRequest {
+    pub email: String,
+    pub api_token: Uuid,
+    pub node_key_id: Uuid,
+    pub encrypted_payload: String,
+    pub reply_key: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreateEncryptedTaskResponse {
+    pub task_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaskResultRequest {
+    pub email: String,
+    pub api_token: Uuid,
+    pub task_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaskResultResponse {
+    pub task_id: Uuid,
+    pub status: String,
+    pub response_code: Option<i32>,
+    pub response_body: Option<String>,
+    pub encrypted: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CaptchaResp {
     pub status: u16,
@@ -541,7 +856,7 @@ pub struct OptCreds {
     pub api_token: Option<Uuid>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct FeedElement {
     pub origin: String,
     pub user_name: String,
@@ -588,6 +903,118 @@ pub struct DigestDataResponse {
     pub status_code: u16,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DigestDataBatchRequest {
+    pub email: String,
+    pub api_token: Uuid,
+    pub data: Vec<FeedElement>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestDataStatus {
+    Inserted,
+    Duplicate,
+    Invalid,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DigestDataResult {
+    pub id: String,
+    pub status: DigestDataStatus,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DigestDataBatchResponse {
+    pub results: Vec<DigestDataResult>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedDimension {
+    #[default]
+    Source,
+    Author,
+    Hashtag,
+    Topic,
+}
+
+impl FeedDimension {
+    pub const ALL: [FeedDimension; 4] = [
+        FeedDimension::Source,
+        FeedDimension::Author,
+        FeedDimension::Hashtag,
+        FeedDimension::Topic,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FeedDimension::Source => "source",
+            FeedDimension::Author => "author",
+            FeedDimension::Hashtag => "hashtag",
+            FeedDimension::Topic => "topic",
+        }
+    }
+}
+
+impl std::fmt::Display for FeedDimension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for FeedDimension {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|dimension| dimension.as_str() == s)
+            .ok_or_else(|| format!("Unknown feed dimension {}", s))
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FeedAnalyticsRequest {
+    pub email: String,
+    pub api_token: Uuid,
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+    pub timeline: Option<FeedDimension>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct FeedShare {
+    pub key: String,
+    pub count: i64,
+    pub share: f64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct FeedComposition {
+    pub dimension: FeedDimension,
+    pub total: i64,
+    pub top: Vec<FeedShare>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct FeedTimelinePoint {
+    pub day: NaiveDate,
+    pub key: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct FeedAnalyticsResponse {
+    pub since: NaiveDate,
+    pub until: NaiveDate,
+    pub user: Vec<FeedComposition>,
+    pub global: Vec<FeedComposition>,
+    pub timeline_dimension: FeedDimension,
+    pub timeline: Vec<FeedTimelinePoint>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SendEmail {
     pub code: String,
@@ -602,3 +1029,202 @@ pub struct TurnStile {
     pub secret: String,
     pub response: String,
 }
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenScope {
+    NodeReporting,
+    TaskSubmission,
+    DashboardRead,
+}
+
+impl TokenScope {
+    pub const ALL: [TokenScope; 3] = [
+        TokenScope::NodeReporting,
+        TokenScope::TaskSubmission,
+        TokenScope::DashboardRead,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenScope::NodeReporting => "node_reporting",
+            TokenScope::TaskSubmission => "task_submission",
+            TokenScope::DashboardRead => "dashboard_read",
+        }
+    }
+}
+
+impl std::fmt::Display for TokenScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for TokenScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|scope| scope.as_str() == s)
+            .ok_or_else(|| format!("Unknown token scope {}", s))
+    }
+}
+
+/// Never carries the token itself, that is only shown once on create and rotate.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ApiTokenInfo {
+    pub id: Uuid,
+    pub name: String,
+    pub scopes: Vec<TokenScope>,
+    pub is_primary: bool,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub last_used_ip: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CreateApiTokenRequest {
+    pub name: String,
+    pub scopes: Vec<TokenScope>,
+    pub expires_in_days: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ApiTokenIdRequest {
+    pub id: Uuid,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ApiTokenSecretResponse {
+    pub info: ApiTokenInfo,
+    pub token: Uuid,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DeviceCodeRequest {
+    pub device_name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DeviceCodeResponse {
+    pub device_code: Uuid,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: String,
+    pub expires_in: i64,
+    pub interval: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DeviceTokenRequest {
+    pub device_code: Uuid,
+}
+
+/// Same meaning as the error codes of RFC 8628, section 3.5.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceTokenError {
+    AuthorizationPending,
+    SlowDown,
+    AccessDenied,
+    ExpiredToken,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DeviceTokenResponse {
+    pub email: Option<String>,
+    pub api_token: Option<Uuid>,
+    pub error: Option<DeviceTokenError>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DeviceAuthorizationInfo {
+    pub user_code: String,
+    pub device_name: String,
+    pub scopes: Vec<TokenScope>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DeviceApprovalRequest {
+    pub user_code: String,
+    pub approve: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TwoFactorStatus {
+    pub enabled: bool,
+    pub recovery_codes_left: i64,
+    pub remembered_devices: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TwoFactorEnrollResponse {
+    pub secret: String,
+    pub otpauth_url: String,
+    pub qr_svg: String,
+}
+
+/// Takes either a code from the authenticator app or an unused recovery code.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TwoFactorCodeRequest {
+    pub code: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TwoFactorRecoveryCodesResponse {
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TwoFactorLoginForm {
+    pub code: String,
+    #[serde(default)]
+    pub remember_device: Option<String>,
+}
+
+/// Returned by and posted to the notification preferences route, account emails cannot be turned off.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct NotificationPreferencesRequest {
+    pub probe_alerts: bool,
+    pub locale: EmailLocale,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AccountExportInfo {
+    pub id: Uuid,
+    pub status: String,
+    pub size_bytes: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AccountDeletionInfo {
+    pub requested_at: DateTime<Utc>,
+    pub scheduled_for: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AccountDataResponse {
+    pub exports: Vec<AccountExportInfo>,
+    /// Set while a deletion is scheduled and can still be cancelled.
+    pub deletion: Option<AccountDeletionInfo>,
+    /// Opens the privacy bot with this account's link code, unset when no bot is configured.
+    pub telegram_link: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AccountExportDownloadQuery {
+    pub id: Uuid,
+}
+
+/// The email has to be typed again, a bit of friction for an action that cannot be undone.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AccountDeletionRequest {
+    pub email: String,
+    #[serde(default)]
+    pub totp_code: Option<String>,
+}
@@ -4,17 +4,20 @@ pub mod chrome_storage;
 #[cfg(feature = "cli")]
 pub mod cli;
 pub mod constants;
+pub mod email_client;
 #[cfg(feature = "env")]
 pub mod env;
 #[cfg(feature = "feature-flag")]
 pub mod feature_flag_client;
+pub mod feature_flags;
 #[cfg(feature = "http")]
 pub mod http;
 pub mod interfaces;
+pub mod public_address;
 #[cfg(feature = "reqwest")]
 pub mod reqwest;
 pub mod routes_enum;
+pub mod siws;
+#[cfg(feature = "task-crypto")]
+pub mod task_crypto;
 pub mod tauri_message_channel;
-
-#[cfg(feature = "email-client")]
-pub mod email_client;
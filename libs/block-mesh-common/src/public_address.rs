@@ -0,0 +1,86 @@
+use anyhow::anyhow;
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+
+/// Whether `ip` is reachable on the public internet, i.e. not loopback, private, link-local,
+/// unspecified or otherwise reserved for local use.
+pub fn is_public_ip(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || a == 0
+                || (a == 100 && (b & 0xc0) == 64))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_ip(&IpAddr::V4(ip)),
+            None => {
+                let first = ip.segments()[0];
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || (first & 0xfe00) == 0xfc00
+                    || (first & 0xffc0) == 0xfe80)
+            }
+        },
+    }
+}
+
+/// Resolves `host` and returns its addresses, failing when the host is local or any of the
+/// addresses is not public. This blocks on DNS, async callers should use `spawn_blocking`.
+pub fn resolve_public_host(host: &str, port: u16) -> anyhow::Result<Vec<SocketAddr>> {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let lowercase = host.to_ascii_lowercase();
+    if lowercase == "localhost" || lowercase.ends_with(".localhost") {
+        return Err(anyhow!("Host {} is not public", host));
+    }
+    let addrs: Vec<SocketAddr> = (host, port).to_socket_addrs()?.collect();
+    if addrs.is_empty() || addrs.iter().any(|addr| !is_public_ip(&addr.ip())) {
+        return Err(anyhow!("Host {} is not public", host));
+    }
+    Ok(addrs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn public(ip: &str) -> bool {
+        is_public_ip(&ip.parse().unwrap())
+    }
+
+    #[test]
+    fn test_is_public_ip() {
+        assert!(public("1.1.1.1"));
+        assert!(public("2606:4700:4700::1111"));
+        for ip in [
+            "127.0.0.1",
+            "10.0.0.1",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "0.0.0.0",
+            "100.64.0.1",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!public(ip), "{}", ip);
+        }
+    }
+
+    #[test]
+    fn test_resolve_public_host_rejects_local_hosts() {
+        assert!(resolve_public_host("localhost", 80).is_err());
+        assert!(resolve_public_host("api.localhost", 80).is_err());
+        assert!(resolve_public_host("127.0.0.1", 80).is_err());
+        assert!(resolve_public_host("[::1]", 80).is_err());
+        assert!(resolve_public_host("169.254.169.254", 80).is_err());
+        assert!(resolve_public_host("1.1.1.1", 80).is_ok());
+    }
+}
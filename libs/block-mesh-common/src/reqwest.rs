@@ -1,4 +1,12 @@
 use crate::constants::DeviceType;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::public_address::resolve_public_host;
+#[cfg(not(target_arch = "wasm32"))]
+use anyhow::anyhow;
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::redirect::Policy;
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::Url;
 use reqwest::{Client, ClientBuilder};
 #[allow(unused_imports)]
 use std::time::Duration;
@@ -16,7 +24,7 @@ pub fn http_client(device_type: DeviceType) -> Client {
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-pub fn http_client(device_type: DeviceType) -> Client {
+fn http_client_builder(device_type: DeviceType) -> ClientBuilder {
     ClientBuilder::new()
         .timeout(Duration::from_secs(3))
         .cookie_store(true)
@@ -27,6 +35,25 @@ pub fn http_client(device_type: DeviceType) -> Client {
         ))
         .no_hickory_dns()
         .use_rustls_tls()
-        .build()
-        .unwrap_or_default()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn http_client(device_type: DeviceType) -> Client {
+    http_client_builder(device_type).build().unwrap_or_default()
+}
+
+/// A client for a URL someone else picked: its host has to resolve to public addresses only,
+/// the connection is pinned to those so the name cannot be rebound in between, and redirects
+/// are not followed. Blocks on DNS.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn public_http_client(device_type: DeviceType, url: &Url) -> anyhow::Result<Client> {
+    let host = url.host_str().ok_or_else(|| anyhow!("URL has no host"))?;
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| anyhow!("URL has no port"))?;
+    let addrs = resolve_public_host(host, port)?;
+    Ok(http_client_builder(device_type)
+        .resolve_to_addrs(host, &addrs)
+        .redirect(Policy::none())
+        .build()?)
 }
@@ -19,6 +19,8 @@ pub enum RoutesEnum {
     Static_UnAuth_Unsubscribe,
     Static_UnAuth_Login,
     Static_UnAuth_Login_Wallet,
+    Static_UnAuth_Login_Two_Factor,
+    Static_UnAuth_Siws_Message,
     Static_UnAuth_DbHealth,
     Static_UnAuth_ServerHealth,
     Static_Auth_ResendConfirmationEmail,
@@ -28,6 +30,27 @@ pub enum RoutesEnum {
     Static_Auth_Logout,
     Static_Auth_Dashboard,
     Static_Auth_Daily_Leaderboard,
+    Static_Auth_Feed_Analytics,
+    Static_Auth_Api_Tokens,
+    Static_Auth_Api_Tokens_Revoke,
+    Static_Auth_Api_Tokens_Rotate,
+    Static_Auth_Device,
+    Static_Auth_Two_Factor,
+    Static_Auth_Two_Factor_Enroll,
+    Static_Auth_Two_Factor_Enable,
+    Static_Auth_Two_Factor_Disable,
+    Static_Auth_Two_Factor_Recovery_Codes,
+    Static_Auth_Two_Factor_Forget_Devices,
+    Static_Auth_Two_Factor_Verify,
+    Static_Auth_Wallets,
+    Static_Auth_Wallets_Unlink,
+    Static_Auth_Notification_Preferences,
+    Static_Auth_Account_Data,
+    Static_Auth_Account_Export,
+    Static_Auth_Account_Export_Download,
+    Static_Auth_Account_Deletion,
+    Static_Auth_Account_Deletion_Cancel,
+    Static_UnAuth_Email_Events,
     Static_UnAuth_Twitter_Callback,
     Api_ConnectWallet,
     Api_ReportUptime,
@@ -42,6 +65,22 @@ pub enum RoutesEnum {
     Api_EMailViaToken,
     Api_Dashboard,
     Api_ReportsQueue,
+    Api_RebuildAggregates,
+    Api_CronJobs,
+    Api_InviteCampaigns,
+    Api_InviteCampaignCodes,
+    Api_PerkDefinitions,
+    Api_Probes,
+    Api_CreateProbe,
+    Api_DeleteProbe,
+    Api_ProbeSlo,
+    Api_TaskRetention,
+    Api_RegisterNodeKey,
+    Api_NodeKeys,
+    Api_CreateEncryptedTask,
+    Api_TaskResult,
+    Api_DeviceCode,
+    Api_DeviceToken,
 }
 
 impl Display for RoutesEnum {
@@ -56,6 +95,37 @@ impl Display for RoutesEnum {
             RoutesEnum::Static_UnAuth_RpcApi => write!(f, "/rpc_api"),
             RoutesEnum::Static_UnAuth_Notification => write!(f, "/notification"),
             RoutesEnum::Static_Auth_Daily_Leaderboard => write!(f, "/daily_leaderboard"),
+            RoutesEnum::Static_Auth_Feed_Analytics => write!(f, "/feed_analytics"),
+            RoutesEnum::Static_Auth_Api_Tokens => write!(f, "/api_tokens"),
+            RoutesEnum::Static_Auth_Api_Tokens_Revoke => write!(f, "/api_tokens/revoke"),
+            RoutesEnum::Static_Auth_Api_Tokens_Rotate => write!(f, "/api_tokens/rotate"),
+            RoutesEnum::Static_Auth_Device => write!(f, "/device"),
+            RoutesEnum::Static_Auth_Two_Factor => write!(f, "/two_factor"),
+            RoutesEnum::Static_Auth_Two_Factor_Enroll => write!(f, "/two_factor/enroll"),
+            RoutesEnum::Static_Auth_Two_Factor_Enable => write!(f, "/two_factor/enable"),
+            RoutesEnum::Static_Auth_Two_Factor_Disable => write!(f, "/two_factor/disable"),
+            RoutesEnum::Static_Auth_Two_Factor_Recovery_Codes => {
+                write!(f, "/two_factor/recovery_codes")
+            }
+            RoutesEnum::Static_Auth_Two_Factor_Forget_Devices => {
+                write!(f, "/two_factor/forget_devices")
+            }
+            RoutesEnum::Static_Auth_Two_Factor_Verify => write!(f, "/two_factor/verify"),
+            RoutesEnum::Static_Auth_Wallets => write!(f, "/wallets"),
+            RoutesEnum::Static_Auth_Wallets_Unlink => write!(f, "/wallets/unlink"),
+            RoutesEnum::Static_Auth_Notification_Preferences => {
+                write!(f, "/notification_preferences")
+            }
+            RoutesEnum::Static_Auth_Account_Data => write!(f, "/account_data"),
+            RoutesEnum::Static_Auth_Account_Export => write!(f, "/account_data/export"),
+            RoutesEnum::Static_Auth_Account_Export_Download => {
+                write!(f, "/account_data/export/download")
+            }
+            RoutesEnum::Static_Auth_Account_Deletion => write!(f, "/account_data/deletion"),
+            RoutesEnum::Static_Auth_Account_Deletion_Cancel => {
+                write!(f, "/account_data/deletion/cancel")
+            }
+            RoutesEnum::Static_UnAuth_Email_Events => write!(f, "/email_events"),
             RoutesEnum::Static_UnAuth_EmailConfirm => write!(f, "/email_confirm"),
             RoutesEnum::Static_UnAuth_ResetPassword => write!(f, "/reset_password"),
             RoutesEnum::Static_UnAuth_NewPassword => write!(f, "/new_password"),
@@ -66,6 +136,8 @@ impl Display for RoutesEnum {
             RoutesEnum::Static_UnAuth_Register_Wallet => write!(f, "/register_wallet"),
             RoutesEnum::Static_UnAuth_Login => write!(f, "/login"),
             RoutesEnum::Static_UnAuth_Login_Wallet => write!(f, "/login_wallet"),
+            RoutesEnum::Static_UnAuth_Login_Two_Factor => write!(f, "/login/two_factor"),
+            RoutesEnum::Static_UnAuth_Siws_Message => write!(f, "/siws/message"),
             RoutesEnum::Static_UnAuth_DbHealth => write!(f, "/db_health"),
             RoutesEnum::Static_UnAuth_ServerHealth => write!(f, "/server_health"),
             RoutesEnum::Static_Auth_ResendConfirmationEmail => {
@@ -88,6 +160,22 @@ impl Display for RoutesEnum {
             RoutesEnum::Api_EMailViaToken => write!(f, "/get_email_via_token"),
             RoutesEnum::Api_Dashboard => write!(f, "/dashboard"),
             RoutesEnum::Api_ReportsQueue => write!(f, "/admin/reports_queue"),
+            RoutesEnum::Api_RebuildAggregates => write!(f, "/admin/rebuild_aggregates"),
+            RoutesEnum::Api_CronJobs => write!(f, "/admin/cron_jobs"),
+            RoutesEnum::Api_InviteCampaigns => write!(f, "/admin/invite_campaigns"),
+            RoutesEnum::Api_InviteCampaignCodes => write!(f, "/admin/invite_campaigns/codes"),
+            RoutesEnum::Api_PerkDefinitions => write!(f, "/admin/perk_definitions"),
+            RoutesEnum::Api_Probes => write!(f, "/probes"),
+            RoutesEnum::Api_CreateProbe => write!(f, "/create_probe"),
+            RoutesEnum::Api_DeleteProbe => write!(f, "/delete_probe"),
+            RoutesEnum::Api_ProbeSlo => write!(f, "/probe_slo"),
+            RoutesEnum::Api_TaskRetention => write!(f, "/task_retention"),
+            RoutesEnum::Api_RegisterNodeKey => write!(f, "/register_node_key"),
+            RoutesEnum::Api_NodeKeys => write!(f, "/node_keys"),
+            RoutesEnum::Api_CreateEncryptedTask => write!(f, "/create_encrypted_task"),
+            RoutesEnum::Api_TaskResult => write!(f, "/task_result"),
+            RoutesEnum::Api_DeviceCode => write!(f, "/device/code"),
+            RoutesEnum::Api_DeviceToken => write!(f, "/device/token"),
             RoutesEnum::Static_UnAuth_Unsubscribe => write!(f, "/unsubscribe"),
         }
     }
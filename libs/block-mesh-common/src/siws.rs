@@ -0,0 +1,319 @@
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+pub const SIWS_VERSION: &str = "1";
+pub const SIWS_CHAIN_ID: &str = "mainnet";
+/// How long a signed message stays valid after the server issued it.
+pub const SIWS_TTL_SECS: i64 = 600;
+
+const HEADER_SUFFIX: &str = " wants you to sign in with your Solana account:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SiwsPurpose {
+    Register,
+    Login,
+    Link,
+}
+
+impl SiwsPurpose {
+    pub fn statement(&self) -> &'static str {
+        match self {
+            SiwsPurpose::Register => "Create a BlockMesh account with this wallet.",
+            SiwsPurpose::Login => "Sign in to BlockMesh with this wallet.",
+            SiwsPurpose::Link => "Link this wallet to your BlockMesh account.",
+        }
+    }
+
+    fn from_statement(statement: &str) -> Option<Self> {
+        [SiwsPurpose::Register, SiwsPurpose::Login, SiwsPurpose::Link]
+            .into_iter()
+            .find(|purpose| purpose.statement() == statement)
+    }
+}
+
+impl Display for SiwsPurpose {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SiwsPurpose::Register => write!(f, "register"),
+            SiwsPurpose::Login => write!(f, "login"),
+            SiwsPurpose::Link => write!(f, "link"),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum SiwsError {
+    #[error("Malformed message: {0}")]
+    Malformed(&'static str),
+    #[error("Message was issued for another domain")]
+    DomainMismatch,
+    #[error("Message was issued for another site")]
+    UriMismatch,
+    #[error("Message was signed for another wallet")]
+    AddressMismatch,
+    #[error("Message was issued for another action")]
+    PurposeMismatch,
+    #[error("Message was issued for another chain")]
+    ChainMismatch,
+    #[error("Message expired")]
+    Expired,
+    #[error("Message is not valid yet")]
+    NotYetValid,
+}
+
+/// A Sign-In-With-Solana message, the text the wallet signs is its `Display` output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiwsMessage {
+    pub domain: String,
+    pub address: String,
+    pub statement: String,
+    pub uri: String,
+    pub version: String,
+    pub chain_id: String,
+    pub nonce: String,
+    pub issued_at: DateTime<Utc>,
+    pub expiration_time: DateTime<Utc>,
+}
+
+impl SiwsMessage {
+    pub fn new(
+        domain: &str,
+        uri: &str,
+        address: &str,
+        purpose: SiwsPurpose,
+        nonce: &str,
+        issued_at: DateTime<Utc>,
+    ) -> Self {
+        // Whole seconds, so the message survives a round trip through its text form.
+        let issued_at = DateTime::from_timestamp(issued_at.timestamp(), 0).unwrap_or(issued_at);
+        Self {
+            domain: domain.to_string(),
+            address: address.to_string(),
+            statement: purpose.statement().to_string(),
+            uri: uri.to_string(),
+            version: SIWS_VERSION.to_string(),
+            chain_id: SIWS_CHAIN_ID.to_string(),
+            nonce: nonce.to_string(),
+            issued_at,
+            expiration_time: issued_at + Duration::seconds(SIWS_TTL_SECS),
+        }
+    }
+
+    pub fn purpose(&self) -> Option<SiwsPurpose> {
+        SiwsPurpose::from_statement(&self.statement)
+    }
+
+    /// Checks everything but the signature and the nonce, which the issuer has to look up.
+    pub fn validate(
+        &self,
+        domain: &str,
+        uri: &str,
+        address: &str,
+        purpose: SiwsPurpose,
+        now: DateTime<Utc>,
+    ) -> Result<(), SiwsError> {
+        if self.domain != domain {
+            return Err(SiwsError::DomainMismatch);
+        }
+        if self.uri != uri {
+            return Err(SiwsError::UriMismatch);
+        }
+        if self.address != address {
+            return Err(SiwsError::AddressMismatch);
+        }
+        if self.purpose() != Some(purpose) {
+            return Err(SiwsError::PurposeMismatch);
+        }
+        if self.chain_id != SIWS_CHAIN_ID {
+            return Err(SiwsError::ChainMismatch);
+        }
+        if self.version != SIWS_VERSION {
+            return Err(SiwsError::Malformed("unsupported version"));
+        }
+        if now < self.issued_at - Duration::seconds(60) {
+            return Err(SiwsError::NotYetValid);
+        }
+        if now >= self.expiration_time {
+            return Err(SiwsError::Expired);
+        }
+        Ok(())
+    }
+}
+
+impl Display for SiwsMessage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}\n{}\n\n{}\n\nURI: {}\nVersion: {}\nChain ID: {}\nNonce: {}\nIssued At: {}\nExpiration Time: {}",
+            self.domain,
+            HEADER_SUFFIX,
+            self.address,
+            self.statement,
+            self.uri,
+            self.version,
+            self.chain_id,
+            self.nonce,
+            self.issued_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            self.expiration_time
+                .to_rfc3339_opts(SecondsFormat::Secs, true),
+        )
+    }
+}
+
+impl FromStr for SiwsMessage {
+    type Err = SiwsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.split('\n');
+        let mut next = |what: &'static str| lines.next().ok_or(SiwsError::Malformed(what));
+        let domain = next("header")?
+            .strip_suffix(HEADER_SUFFIX)
+            .ok_or(SiwsError::Malformed("header"))?
+            .to_string();
+        let address = next("address")?.to_string();
+        if !next("blank line")?.is_empty() {
+            return Err(SiwsError::Malformed("blank line"));
+        }
+        let statement = next("statement")?.to_string();
+        if !next("blank line")?.is_empty() {
+            return Err(SiwsError::Malformed("blank line"));
+        }
+        let mut field = |name: &'static str| -> Result<String, SiwsError> {
+            next(name)?
+                .strip_prefix(name)
+                .and_then(|rest| rest.strip_prefix(": "))
+                .map(str::to_string)
+                .ok_or(SiwsError::Malformed(name))
+        };
+        let uri = field("URI")?;
+        let version = field("Version")?;
+        let chain_id = field("Chain ID")?;
+        let nonce = field("Nonce")?;
+        let issued_at = field("Issued At")?;
+        let expiration_time = field("Expiration Time")?;
+        if lines.next().is_some() {
+            return Err(SiwsError::Malformed("trailing lines"));
+        }
+        let parse_time = |value: &str, what: &'static str| {
+            DateTime::parse_from_rfc3339(value)
+                .map(|time| time.with_timezone(&Utc))
+                .map_err(|_| SiwsError::Malformed(what))
+        };
+        Ok(Self {
+            domain,
+            address,
+            statement,
+            uri,
+            version,
+            chain_id,
+            nonce,
+            issued_at: parse_time(&issued_at, "Issued At")?,
+            expiration_time: parse_time(&expiration_time, "Expiration Time")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(purpose: SiwsPurpose) -> SiwsMessage {
+        SiwsMessage::new(
+            "app.blockmesh.xyz",
+            "https://app.blockmesh.xyz",
+            "4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T",
+            purpose,
+            "k3j4h5g6",
+            Utc::now(),
+        )
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let message = message(SiwsPurpose::Login);
+        let parsed: SiwsMessage = message.to_string().parse().unwrap();
+        assert_eq!(parsed, message);
+        assert_eq!(parsed.purpose(), Some(SiwsPurpose::Login));
+    }
+
+    #[test]
+    fn validates_domain_uri_address_and_purpose() {
+        let message = message(SiwsPurpose::Link);
+        let now = Utc::now();
+        assert_eq!(
+            message.validate(
+                "app.blockmesh.xyz",
+                "https://app.blockmesh.xyz",
+                &message.address,
+                SiwsPurpose::Link,
+                now
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            message.validate(
+                "evil.example",
+                "https://app.blockmesh.xyz",
+                &message.address,
+                SiwsPurpose::Link,
+                now
+            ),
+            Err(SiwsError::DomainMismatch)
+        );
+        assert_eq!(
+            message.validate(
+                "app.blockmesh.xyz",
+                "https://evil.example",
+                &message.address,
+                SiwsPurpose::Link,
+                now
+            ),
+            Err(SiwsError::UriMismatch)
+        );
+        assert_eq!(
+            message.validate(
+                "app.blockmesh.xyz",
+                "https://app.blockmesh.xyz",
+                "other",
+                SiwsPurpose::Link,
+                now
+            ),
+            Err(SiwsError::AddressMismatch)
+        );
+        assert_eq!(
+            message.validate(
+                "app.blockmesh.xyz",
+                "https://app.blockmesh.xyz",
+                &message.address,
+                SiwsPurpose::Login,
+                now
+            ),
+            Err(SiwsError::PurposeMismatch)
+        );
+    }
+
+    #[test]
+    fn rejects_expired_messages() {
+        let message = message(SiwsPurpose::Login);
+        let later = Utc::now() + Duration::seconds(SIWS_TTL_SECS + 1);
+        assert_eq!(
+            message.validate(
+                "app.blockmesh.xyz",
+                "https://app.blockmesh.xyz",
+                &message.address,
+                SiwsPurpose::Login,
+                later
+            ),
+            Err(SiwsError::Expired)
+        );
+    }
+
+    #[test]
+    fn rejects_extra_lines() {
+        let text = format!("{}\nResources:", message(SiwsPurpose::Login));
+        assert!(text.parse::<SiwsMessage>().is_err());
+    }
+}
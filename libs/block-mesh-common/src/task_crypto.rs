@@ -0,0 +1,154 @@
+use anyhow::anyhow;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use hkdf::Hkdf;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::Sha256;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+const INFO: &[u8] = b"blockmesh-task-v1";
+
+#[derive(Clone)]
+pub struct TaskKeypair {
+    secret: StaticSecret,
+    public: PublicKey,
+}
+
+impl TaskKeypair {
+    pub fn generate() -> Self {
+        let secret = StaticSecret::random_from_rng(OsRng);
+        let public = PublicKey::from(&secret);
+        Self { secret, public }
+    }
+
+    pub fn from_secret_key(secret_key: &str) -> anyhow::Result<Self> {
+        let bytes = STANDARD.decode(secret_key)?;
+        let bytes = <[u8; KEY_LEN]>::try_from(bytes.as_slice())
+            .map_err(|_| anyhow!("Secret key must be {} bytes", KEY_LEN))?;
+        let secret = StaticSecret::from(bytes);
+        let public = PublicKey::from(&secret);
+        Ok(Self { secret, public })
+    }
+
+    pub fn public_key(&self) -> String {
+        STANDARD.encode(self.public.as_bytes())
+    }
+
+    pub fn secret_key(&self) -> String {
+        STANDARD.encode(self.secret.to_bytes())
+    }
+
+    pub fn open(&self, envelope: &str) -> anyhow::Result<Vec<u8>> {
+        let envelope = STANDARD.decode(envelope)?;
+        if envelope.len() < KEY_LEN + NONCE_LEN + TAG_LEN {
+            return Err(anyhow!("Envelope too short"));
+        }
+        let (ephemeral, rest) = envelope.split_at(KEY_LEN);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let ephemeral = PublicKey::from(<[u8; KEY_LEN]>::try_from(ephemeral)?);
+        let shared = self.secret.diffie_hellman(&ephemeral);
+        let nonce = Nonce::from(<[u8; NONCE_LEN]>::try_from(nonce)?);
+        let cipher = cipher(shared.as_bytes(), &ephemeral, &self.public)?;
+        cipher
+            .decrypt(&nonce, ciphertext)
+            .map_err(|_| anyhow!("Failed to decrypt envelope"))
+    }
+}
+
+pub fn parse_public_key(public_key: &str) -> anyhow::Result<PublicKey> {
+    let bytes = STANDARD.decode(public_key)?;
+    let bytes = <[u8; KEY_LEN]>::try_from(bytes.as_slice())
+        .map_err(|_| anyhow!("Public key must be {} bytes", KEY_LEN))?;
+    Ok(PublicKey::from(bytes))
+}
+
+pub fn is_envelope(envelope: &str) -> bool {
+    STANDARD
+        .decode(envelope)
+        .is_ok_and(|bytes| bytes.len() >= KEY_LEN + NONCE_LEN + TAG_LEN)
+}
+
+pub fn seal(recipient: &str, plaintext: &[u8]) -> anyhow::Result<String> {
+    let recipient = parse_public_key(recipient)?;
+    let ephemeral_secret = StaticSecret::random_from_rng(OsRng);
+    let ephemeral = PublicKey::from(&ephemeral_secret);
+    let shared = ephemeral_secret.diffie_hellman(&recipient);
+    let cipher = cipher(shared.as_bytes(), &ephemeral, &recipient)?;
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    let ciphertext = cipher
+        .encrypt(&Nonce::from(nonce), plaintext)
+        .map_err(|_| anyhow!("Failed to encrypt envelope"))?;
+    let mut envelope = Vec::with_capacity(KEY_LEN + NONCE_LEN + ciphertext.len());
+    envelope.extend_from_slice(ephemeral.as_bytes());
+    envelope.extend_from_slice(&nonce);
+    envelope.extend_from_slice(&ciphertext);
+    Ok(STANDARD.encode(envelope))
+}
+
+fn cipher(
+    shared: &[u8; KEY_LEN],
+    ephemeral: &PublicKey,
+    recipient: &PublicKey,
+) -> anyhow::Result<ChaCha20Poly1305> {
+    let mut salt = [0u8; KEY_LEN * 2];
+    salt[..KEY_LEN].copy_from_slice(ephemeral.as_bytes());
+    salt[KEY_LEN..].copy_from_slice(recipient.as_bytes());
+    let mut key = [0u8; KEY_LEN];
+    Hkdf::<Sha256>::new(Some(&salt), shared)
+        .expand(INFO, &mut key)
+        .map_err(|_| anyhow!("Failed to derive key"))?;
+    Ok(ChaCha20Poly1305::new(&Key::from(key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seal_and_open_round_trip() {
+        let node = TaskKeypair::generate();
+        let envelope = seal(&node.public_key(), b"{\"url\":\"https://example.com\"}").unwrap();
+        assert!(is_envelope(&envelope));
+        assert_eq!(
+            node.open(&envelope).unwrap(),
+            b"{\"url\":\"https://example.com\"}"
+        );
+    }
+
+    #[test]
+    fn restored_keypairs_open_old_envelopes() {
+        let node = TaskKeypair::generate();
+        let envelope = seal(&node.public_key(), b"sealed before restart").unwrap();
+        let restored = TaskKeypair::from_secret_key(&node.secret_key()).unwrap();
+        assert_eq!(restored.public_key(), node.public_key());
+        assert_eq!(restored.open(&envelope).unwrap(), b"sealed before restart");
+        assert!(TaskKeypair::from_secret_key(&STANDARD.encode([0u8; 16])).is_err());
+    }
+
+    #[test]
+    fn other_keys_cannot_open() {
+        let node = TaskKeypair::generate();
+        let other = TaskKeypair::generate();
+        let envelope = seal(&node.public_key(), b"secret header").unwrap();
+        assert!(other.open(&envelope).is_err());
+    }
+
+    #[test]
+    fn tampered_envelopes_are_rejected() {
+        let node = TaskKeypair::generate();
+        let envelope = seal(&node.public_key(), b"secret header").unwrap();
+        let mut bytes = STANDARD.decode(&envelope).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        assert!(node.open(&STANDARD.encode(bytes)).is_err());
+        assert!(node.open("not base64").is_err());
+        assert!(parse_public_key(&STANDARD.encode([0u8; 16])).is_err());
+    }
+}
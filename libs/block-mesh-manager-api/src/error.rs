@@ -16,6 +16,8 @@ pub enum Error {
     ApiTokenMismatch,
     #[error("Password Mismatch")]
     PasswordMismatch,
+    #[error("Two factor code required")]
+    TwoFactorRequired,
 }
 
 impl IntoResponse for Error {
@@ -34,6 +36,11 @@ impl IntoResponse for Error {
             Error::ApiTokenMismatch => {
                 (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error.").into_response()
             }
+            Error::TwoFactorRequired => (
+                StatusCode::FORBIDDEN,
+                "Two factor code required, log in through the app server",
+            )
+                .into_response(),
             Error::Anyhow(_) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error.").into_response()
             }
@@ -52,6 +59,7 @@ impl From<Error> for StatusCode {
             Error::ApiTokenMismatch => StatusCode::INTERNAL_SERVER_ERROR,
             Error::UserNotFound => StatusCode::INTERNAL_SERVER_ERROR,
             Error::ApiTokenNotFound => StatusCode::INTERNAL_SERVER_ERROR,
+            Error::TwoFactorRequired => StatusCode::FORBIDDEN,
             Error::Sql(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Error::Anyhow(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
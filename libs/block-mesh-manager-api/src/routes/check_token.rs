@@ -51,6 +51,7 @@ pub async fn check_token(
     let response = GetTokenResponse {
         api_token: Some(*user.token.as_ref()),
         message: None,
+        user_id: Some(user.user_id),
     };
     commit_txn(transaction).await?;
     check_token_map.insert(
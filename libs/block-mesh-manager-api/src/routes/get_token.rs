@@ -5,6 +5,7 @@ use block_mesh_common::interfaces::server_api::{
     GetTokenRequest, GetTokenResponse, GetTokenResponseEnum, GetTokenResponseMap,
 };
 use block_mesh_manager_database_domain::domain::get_user_and_api_token::get_user_and_api_token_by_email;
+use block_mesh_manager_database_domain::domain::is_two_factor_enabled::is_two_factor_enabled;
 use dashmap::try_result::TryResult::Present;
 use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
 use sqlx::PgPool;
@@ -49,9 +50,15 @@ pub async fn get_token(
         get_token_map.insert(key, GetTokenResponseEnum::PasswordMismatch);
         return Err(Error::PasswordMismatch);
     }
+    // This service only reads a follower, codes are checked and counted by the app server.
+    if is_two_factor_enabled(&mut transaction, &user.user_id).await? {
+        commit_txn(transaction).await?;
+        return Err(Error::TwoFactorRequired);
+    }
     let response = GetTokenResponse {
         api_token: Some(*user.token.as_ref()),
         message: None,
+        user_id: Some(user.user_id),
     };
     commit_txn(transaction).await?;
     get_token_map.insert(
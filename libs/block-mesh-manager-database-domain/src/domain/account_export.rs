@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, Clone)]
+pub struct AccountExport {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub status: String,
+    pub blob_key: Option<String>,
+    pub size_bytes: Option<i64>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum AccountExportStatus {
+    Pending,
+    /// Claimed by a worker, which builds the archive outside of the claiming transaction.
+    Processing,
+    Ready,
+    Failed,
+    /// The archive was removed from the blob store after `expires_at`.
+    Expired,
+}
+
+impl Display for AccountExportStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pending => write!(f, "Pending"),
+            Self::Processing => write!(f, "Processing"),
+            Self::Ready => write!(f, "Ready"),
+            Self::Failed => write!(f, "Failed"),
+            Self::Expired => write!(f, "Expired"),
+        }
+    }
+}
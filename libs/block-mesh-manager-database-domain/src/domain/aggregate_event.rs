@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum AggregateEventKind {
+    Add,
+    Set,
+    /// Moves the value halfway towards the sample.
+    Sample,
+}
+
+impl Display for AggregateEventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Add => write!(f, "Add"),
+            Self::Set => write!(f, "Set"),
+            Self::Sample => write!(f, "Sample"),
+        }
+    }
+}
+
+impl From<String> for AggregateEventKind {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Set" => Self::Set,
+            "Sample" => Self::Sample,
+            _ => Self::Add,
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use block_mesh_common::interfaces::server_api::TokenScope;
+use http::header::AUTHORIZATION;
+use http::HeaderMap;
+use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// How a client proved who it is, the email pair is kept for clients that predate bearer tokens.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Credentials {
+    Bearer(Uuid),
+    Legacy { email: String, api_token: Uuid },
+}
+
+impl Credentials {
+    /// Prefers the `Authorization: Bearer` header over the email pair of the request.
+    pub fn new(headers: &HeaderMap, email: &str, api_token: &Uuid) -> Self {
+        match bearer_token(headers) {
+            Some(token) => Self::Bearer(token),
+            None => Self::Legacy {
+                email: email.to_string(),
+                api_token: *api_token,
+            },
+        }
+    }
+
+    pub fn token(&self) -> &Uuid {
+        match self {
+            Self::Bearer(token) => token,
+            Self::Legacy { api_token, .. } => api_token,
+        }
+    }
+}
+
+pub fn bearer_token(headers: &HeaderMap) -> Option<Uuid> {
+    let value = headers.get(AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+    if !scheme.eq_ignore_ascii_case("bearer") {
+        return None;
+    }
+    Uuid::parse_str(token.trim()).ok()
+}
+
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, Clone)]
+pub struct AuthenticatedToken {
+    pub token_id: Uuid,
+    pub user_id: Uuid,
+    pub email: String,
+    pub wallet_address: Option<String>,
+    pub verified_email: bool,
+}
+
+#[derive(sqlx::FromRow)]
+struct TokenRow {
+    token_id: Uuid,
+    user_id: Uuid,
+    email: String,
+    wallet_address: Option<String>,
+    verified_email: bool,
+    scopes: Vec<String>,
+}
+
+fn grants_scope(scopes: &[String], scope: TokenScope) -> bool {
+    scopes.iter().any(|granted| granted == scope.as_str())
+}
+
+/// Returns `None` unless the token is active, not expired and carries `scope`.
+#[tracing::instrument(name = "authenticate_token", skip_all, fields(scope = %scope))]
+pub async fn authenticate_token(
+    transaction: &mut Transaction<'_, Postgres>,
+    credentials: &Credentials,
+    scope: TokenScope,
+) -> anyhow::Result<Option<AuthenticatedToken>> {
+    let email = match credentials {
+        Credentials::Bearer(_) => None,
+        Credentials::Legacy { email, .. } => Some(email.as_str()),
+    };
+    let row = sqlx::query_as!(
+        TokenRow,
+        r#"SELECT
+        api_tokens.id as token_id,
+        users.id as user_id,
+        users.email as email,
+        users.wallet_address as wallet_address,
+        users.verified_email as verified_email,
+        api_tokens.scopes as scopes
+        FROM api_tokens
+        JOIN users ON users.id = api_tokens.user_id
+        WHERE api_tokens.token = $1
+        AND ($2::TEXT IS NULL OR users.email = $2)
+        AND api_tokens.status = 'Active'
+        AND (api_tokens.expires_at IS NULL OR api_tokens.expires_at > now())
+        LIMIT 1"#,
+        credentials.token(),
+        email
+    )
+    .fetch_optional(&mut **transaction)
+    .await?;
+    Ok(row
+        .filter(|row| grants_scope(&row.scopes, scope))
+        .map(|row| AuthenticatedToken {
+            token_id: row.token_id,
+            user_id: row.user_id,
+            email: row.email,
+            wallet_address: row.wallet_address,
+            verified_email: row.verified_email,
+        }))
+}
+
+/// Written at most every few minutes per token unless the IP changes, to spare the hot path.
+#[tracing::instrument(name = "touch_api_token", skip_all)]
+pub async fn touch_api_token(
+    transaction: &mut Transaction<'_, Postgres>,
+    token_id: &Uuid,
+    ip: Option<&str>,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE api_tokens
+        SET last_used_at = now(), last_used_ip = COALESCE($2, last_used_ip)
+        WHERE id = $1
+        AND (
+            last_used_at IS NULL
+            OR last_used_at < now() - interval '5 minutes'
+            OR ($2::TEXT IS NOT NULL AND last_used_ip IS DISTINCT FROM $2)
+        )
+        "#,
+        token_id,
+        ip
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+    use std::str::FromStr;
+
+    fn headers(authorization: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_str(authorization).unwrap());
+        headers
+    }
+
+    #[test]
+    fn bearer_token_reads_the_authorization_header() {
+        let token = Uuid::new_v4();
+        assert_eq!(
+            bearer_token(&headers(&format!("Bearer {token}"))),
+            Some(token)
+        );
+        assert_eq!(
+            bearer_token(&headers(&format!("bearer  {token} "))),
+            Some(token)
+        );
+        assert_eq!(bearer_token(&headers(&format!("Basic {token}"))), None);
+        assert_eq!(bearer_token(&headers("Bearer not-a-uuid")), None);
+        assert_eq!(bearer_token(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn credentials_prefer_the_bearer_header() {
+        let token = Uuid::new_v4();
+        let legacy = Uuid::new_v4();
+        let credentials = Credentials::new(&headers(&format!("Bearer {token}")), "a@b.c", &legacy);
+        assert_eq!(credentials, Credentials::Bearer(token));
+        let credentials = Credentials::new(&HeaderMap::new(), "a@b.c", &legacy);
+        assert_eq!(credentials.token(), &legacy);
+    }
+
+    #[test]
+    fn token_scopes_round_trip() {
+        for scope in TokenScope::ALL {
+            assert_eq!(TokenScope::from_str(scope.as_str()), Ok(scope));
+        }
+        assert!(TokenScope::from_str("admin").is_err());
+        assert!(TokenScope::from_str("NodeReporting").is_err());
+    }
+
+    #[test]
+    fn tokens_only_grant_their_scopes() {
+        let scopes = vec![TokenScope::NodeReporting.to_string()];
+        assert!(grants_scope(&scopes, TokenScope::NodeReporting));
+        assert!(!grants_scope(&scopes, TokenScope::TaskSubmission));
+        assert!(!grants_scope(&scopes, TokenScope::DashboardRead));
+        assert!(!grants_scope(&[], TokenScope::NodeReporting));
+    }
+}
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::Display;
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, Clone)]
+pub struct EmailOutbox {
+    pub id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub email_address: String,
+    pub template: String,
+    pub payload: Value,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub provider: Option<String>,
+    pub message_id: Option<String>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum EmailOutboxStatus {
+    Pending,
+    /// Claimed by a worker, reclaimed once `next_attempt_at` passes in case the worker died.
+    Sending,
+    Sent,
+    Failed,
+    /// Not sent because the address bounced, complained or the user unsubscribed.
+    Suppressed,
+}
+
+impl Display for EmailOutboxStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pending => write!(f, "Pending"),
+            Self::Sending => write!(f, "Sending"),
+            Self::Sent => write!(f, "Sent"),
+            Self::Failed => write!(f, "Failed"),
+            Self::Suppressed => write!(f, "Suppressed"),
+        }
+    }
+}
@@ -15,7 +15,10 @@ pub async fn find_pending_tasks_with_limit(
         url,
         method,
         headers,
-        body
+        body,
+        body_ref,
+        encrypted,
+        reply_key
         FROM tasks
         WHERE status = $1
         LIMIT $2
@@ -17,10 +17,12 @@ pub async fn find_task_by_task_id_and_status(
         method,
         headers,
         body,
+        body_ref,
         assigned_user_id,
         status,
         response_code,
         response_raw,
+        response_ref,
         created_at,
         retries_count,
         country,
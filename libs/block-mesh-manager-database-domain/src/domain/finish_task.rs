@@ -8,6 +8,7 @@ pub async fn finish_task(
     task_id: Uuid,
     response_code: Option<i32>,
     response_raw: Option<String>,
+    response_ref: Option<String>,
     status: TaskStatus,
     country: &str,
     ip: &str,
@@ -22,15 +23,17 @@ pub async fn finish_task(
         SET
         response_code = $1,
         response_raw = $2,
-        status = $3,
-        country = $4,
-        ip = $5,
-        asn = $6,
-        colo = $7,
-        response_time = $8
-        WHERE id = $9"#,
+        response_ref = $3,
+        status = $4,
+        country = $5,
+        ip = $6,
+        asn = $7,
+        colo = $8,
+        response_time = $9
+        WHERE id = $10"#,
         response_code,
         response_raw,
+        response_ref,
         status.to_string(),
         country,
         ip,
@@ -0,0 +1,18 @@
+use crate::domain::cron_job::CronJob;
+use sqlx::{Postgres, Transaction};
+
+#[tracing::instrument(name = "get_cron_jobs", skip_all)]
+pub async fn get_cron_jobs(
+    transaction: &mut Transaction<'_, Postgres>,
+) -> anyhow::Result<Vec<CronJob>> {
+    Ok(sqlx::query_as!(
+        CronJob,
+        r#"
+        SELECT name, schedule, jitter_ms, paused, triggered_at, created_at, updated_at
+        FROM cron_jobs
+        ORDER BY name
+        "#
+    )
+    .fetch_all(&mut **transaction)
+    .await?)
+}
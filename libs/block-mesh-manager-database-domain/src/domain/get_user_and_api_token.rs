@@ -3,6 +3,7 @@ use secret::Secret;
 use sqlx::{Postgres, Transaction};
 use uuid::Uuid;
 
+/// Joins the primary token, the one created with the account and shown to legacy clients.
 #[tracing::instrument(name = "get_user_and_api_token_by_email", skip_all)]
 pub async fn get_user_and_api_token_by_email(
     transaction: &mut Transaction<'_, Postgres>,
@@ -19,7 +20,7 @@ pub async fn get_user_and_api_token_by_email(
         users.verified_email as verified_email
         FROM users
         JOIN api_tokens ON users.id = api_tokens.user_id
-        WHERE users.email = $1
+        WHERE users.email = $1 AND api_tokens.is_primary
         LIMIT 1"#,
         email,
     )
@@ -0,0 +1,17 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "is_two_factor_enabled", skip_all)]
+pub async fn is_two_factor_enabled(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<bool> {
+    Ok(sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+        SELECT 1 FROM two_factor_secrets WHERE user_id = $1 AND enabled_at IS NOT NULL
+        ) AS "enabled!""#,
+        user_id
+    )
+    .fetch_one(&mut **transaction)
+    .await?)
+}
@@ -1,13 +1,20 @@
+pub mod account_deletion;
+pub mod account_export;
 pub mod aggregate;
+pub mod aggregate_event;
 pub mod api_token;
+pub mod authenticate_token;
 pub mod bulk_get_or_create_aggregate_by_user_and_name;
 pub mod create_daily_stat;
+pub mod cron_job;
 pub mod daily_stat;
+pub mod email_outbox;
 pub mod fetch_latest_cron_settings;
 pub mod find_pending_tasks_with_limit;
 pub mod find_task_by_task_id_and_status;
 pub mod find_token;
 pub mod finish_task;
+pub mod get_cron_jobs;
 pub mod get_daily_stat_of_user;
 pub mod get_or_create_aggregate_by_user_and_name;
 pub mod get_user_and_api_token;
@@ -15,15 +22,24 @@ pub mod get_user_opt_by_email;
 pub mod get_user_opt_by_id;
 pub mod increment_tasks_count;
 pub mod increment_uptime;
+pub mod is_two_factor_enabled;
 pub mod nonce;
+pub mod notification_preferences;
 pub mod notify_api;
 pub mod notify_worker;
 pub mod option_uuid;
+pub mod perk_definition;
+pub mod points;
 pub mod prep_user;
+pub mod probe;
+pub mod rebuild_aggregates;
+pub mod record_probe_result;
+pub mod referral_earning;
 pub mod report_uptime_content;
 pub mod submit_bandwidth_content;
 pub mod submit_task_content;
 pub mod task;
+pub mod task_blob;
 pub mod task_limit;
 pub mod update_aggregate;
 pub mod update_task_assigned;
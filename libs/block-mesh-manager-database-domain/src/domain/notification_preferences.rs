@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, Clone)]
+pub struct NotificationPreferences {
+    pub user_id: Uuid,
+    pub probe_alerts: bool,
+    pub locale: String,
+    pub unsubscribe_token: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// `locale` is only used when the row does not exist yet.
+#[tracing::instrument(name = "get_or_create_notification_preferences", skip_all, err)]
+pub async fn get_or_create_notification_preferences(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    locale: &str,
+) -> anyhow::Result<NotificationPreferences> {
+    let preferences = sqlx::query_as!(
+        NotificationPreferences,
+        r#"
+        WITH inserted AS (
+            INSERT INTO notification_preferences (user_id, locale)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO NOTHING
+            RETURNING user_id, probe_alerts, locale, unsubscribe_token, created_at, updated_at
+        )
+        SELECT
+        user_id AS "user_id!",
+        probe_alerts AS "probe_alerts!",
+        locale AS "locale!",
+        unsubscribe_token AS "unsubscribe_token!",
+        created_at AS "created_at!",
+        updated_at AS "updated_at!"
+        FROM inserted
+        UNION ALL
+        SELECT user_id, probe_alerts, locale, unsubscribe_token, created_at, updated_at
+        FROM notification_preferences
+        WHERE user_id = $1
+        "#,
+        user_id,
+        locale
+    )
+    .fetch_one(&mut **transaction)
+    .await?;
+    Ok(preferences)
+}
@@ -0,0 +1,82 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Catalog entry perks are granted from, the perk's `name` references it.
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, Clone)]
+pub struct PerkDefinition {
+    pub name: String,
+    pub multiplier: f64,
+    pub one_time_bonus: f64,
+    /// Perks of one group are combined with `stacking`, a perk without a group stands alone.
+    pub stacking_group: Option<String>,
+    pub stacking: String,
+    /// Upper bound of the group's combined multiplier.
+    pub max_multiplier: Option<f64>,
+    /// Outside this window the perk is neither granted nor counted.
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_until: Option<DateTime<Utc>>,
+    /// Granted perks expire after this many days, never when empty.
+    pub duration_days: Option<i32>,
+    /// Rule the worker grants the perk by, see `PerkCondition`.
+    pub condition: Option<String>,
+    pub condition_threshold: Option<i64>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum PerkStacking {
+    /// Every perk of the group counts.
+    Multiply,
+    /// Only the group's largest multiplier counts.
+    Max,
+}
+
+impl Display for PerkStacking {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Multiply => write!(f, "Multiply"),
+            Self::Max => write!(f, "Max"),
+        }
+    }
+}
+
+impl From<&str> for PerkStacking {
+    fn from(s: &str) -> Self {
+        match s {
+            "Max" => Self::Max,
+            _ => Self::Multiply,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum PerkCondition {
+    /// Reported uptime on `condition_threshold` days in a row.
+    ConsecutiveNodeDays,
+    /// Completed at least `condition_threshold` tasks overall.
+    CompletedTasks,
+}
+
+impl Display for PerkCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ConsecutiveNodeDays => write!(f, "ConsecutiveNodeDays"),
+            Self::CompletedTasks => write!(f, "CompletedTasks"),
+        }
+    }
+}
+
+impl TryFrom<&str> for PerkCondition {
+    type Error = anyhow::Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "ConsecutiveNodeDays" => Ok(Self::ConsecutiveNodeDays),
+            "CompletedTasks" => Ok(Self::CompletedTasks),
+            _ => Err(anyhow::anyhow!("Unknown perk condition {}", s)),
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use crate::domain::perk_definition::PerkStacking;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+pub const UPTIME_FACTOR: f64 = 100.0 / (24.0 * 60.0 * 60.0);
+pub const TASKS_FACTOR: f64 = 10.0;
+
+pub fn raw_points(uptime: f64, tasks_count: i64) -> f64 {
+    uptime * UPTIME_FACTOR + tasks_count as f64 * TASKS_FACTOR
+}
+
+/// A user's perk together with the catalog rules it is combined by.
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, Clone)]
+pub struct PerkEffect {
+    pub user_id: Uuid,
+    pub name: String,
+    pub multiplier: f64,
+    pub stacking_group: Option<String>,
+    pub stacking: String,
+    pub max_multiplier: Option<f64>,
+    /// Perks granted before windows existed have neither and count on every day.
+    pub starts_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_until: Option<DateTime<Utc>>,
+}
+
+impl PerkEffect {
+    /// Whether the perk's windows overlap `day` (UTC).
+    pub fn is_active_on(&self, day: NaiveDate) -> bool {
+        let Some(day_start) = day.and_hms_opt(0, 0, 0).map(|t| t.and_utc()) else {
+            return false;
+        };
+        let day_end = day_start + chrono::Duration::days(1);
+        let started = [self.starts_at, self.valid_from]
+            .into_iter()
+            .flatten()
+            .all(|start| start < day_end);
+        let not_ended = [self.expires_at, self.valid_until]
+            .into_iter()
+            .flatten()
+            .all(|end| end > day_start);
+        started && not_ended
+    }
+}
+
+/// Combined multiplier of the perks active on `day` and the names of those that counted.
+/// Groups are multiplied together; within a group `Max` keeps the largest multiplier,
+/// `Multiply` keeps all of them, and the group's smallest `max_multiplier` caps the result.
+pub fn combine_perks(effects: &[PerkEffect], day: NaiveDate) -> (f64, Vec<String>) {
+    let mut groups: BTreeMap<&str, Vec<&PerkEffect>> = BTreeMap::new();
+    for effect in effects.iter().filter(|effect| effect.is_active_on(day)) {
+        let key = effect.stacking_group.as_deref().unwrap_or(&effect.name);
+        groups.entry(key).or_default().push(effect);
+    }
+    let mut multiplier = 1.0;
+    let mut applied = Vec::new();
+    for group in groups.values() {
+        let stacking = if group
+            .iter()
+            .any(|effect| PerkStacking::from(effect.stacking.as_str()) == PerkStacking::Max)
+        {
+            PerkStacking::Max
+        } else {
+            PerkStacking::Multiply
+        };
+        let mut group_multiplier = match stacking {
+            PerkStacking::Multiply => {
+                applied.extend(group.iter().map(|effect| effect.name.clone()));
+                group.iter().map(|effect| effect.multiplier).product()
+            }
+            PerkStacking::Max => {
+                let best = group
+                    .iter()
+                    .max_by(|a, b| a.multiplier.total_cmp(&b.multiplier))
+                    .map(|effect| (effect.name.clone(), effect.multiplier));
+                match best {
+                    Some((name, best)) => {
+                        applied.push(name);
+                        best
+                    }
+                    None => 1.0,
+                }
+            }
+        };
+        if let Some(cap) = group
+            .iter()
+            .filter_map(|effect| effect.max_multiplier)
+            .min_by(f64::total_cmp)
+        {
+            group_multiplier = group_multiplier.min(cap);
+        }
+        multiplier *= group_multiplier;
+    }
+    (multiplier, applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn day() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 12, 10).unwrap()
+    }
+
+    fn midnight() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 12, 10, 0, 0, 0).unwrap()
+    }
+
+    fn effect(name: &str, multiplier: f64) -> PerkEffect {
+        PerkEffect {
+            user_id: Uuid::nil(),
+            name: name.to_string(),
+            multiplier,
+            stacking_group: None,
+            stacking: "Multiply".to_string(),
+            max_multiplier: None,
+            starts_at: None,
+            expires_at: None,
+            valid_from: None,
+            valid_until: None,
+        }
+    }
+
+    fn grouped(name: &str, multiplier: f64, stacking: &str, cap: Option<f64>) -> PerkEffect {
+        PerkEffect {
+            stacking_group: Some("activity".to_string()),
+            stacking: stacking.to_string(),
+            max_multiplier: cap,
+            ..effect(name, multiplier)
+        }
+    }
+
+    #[test]
+    fn perk_without_windows_is_always_active() {
+        assert!(effect("wallet", 1.1).is_active_on(day()));
+    }
+
+    #[test]
+    fn perk_starting_during_the_day_counts_for_it() {
+        let starting = |at| PerkEffect {
+            starts_at: Some(at),
+            ..effect("campaign", 1.5)
+        };
+        let end = midnight() + Duration::days(1);
+        assert!(starting(midnight()).is_active_on(day()));
+        assert!(starting(end - Duration::seconds(1)).is_active_on(day()));
+        assert!(!starting(end).is_active_on(day()));
+    }
+
+    #[test]
+    fn perk_ending_during_the_day_counts_for_it() {
+        let expiring = |at| PerkEffect {
+            expires_at: Some(at),
+            ..effect("campaign", 1.5)
+        };
+        assert!(expiring(midnight() + Duration::seconds(1)).is_active_on(day()));
+        assert!(!expiring(midnight()).is_active_on(day()));
+    }
+
+    #[test]
+    fn catalog_window_limits_the_perk_too() {
+        let windowed = |from, until| PerkEffect {
+            valid_from: from,
+            valid_until: until,
+            ..effect("wallet", 1.1)
+        };
+        let end = midnight() + Duration::days(1);
+        assert!(!windowed(Some(end), None).is_active_on(day()));
+        assert!(!windowed(None, Some(midnight())).is_active_on(day()));
+        assert!(windowed(Some(midnight()), Some(end)).is_active_on(day()));
+        let perk_open_catalog_closed = PerkEffect {
+            starts_at: Some(midnight() - Duration::days(10)),
+            ..windowed(None, Some(midnight() - Duration::days(1)))
+        };
+        assert!(!perk_open_catalog_closed.is_active_on(day()));
+    }
+
+    #[test]
+    fn no_perks_multiply_by_one() {
+        assert_eq!(combine_perks(&[], day()), (1.0, vec![]));
+    }
+
+    #[test]
+    fn perks_without_a_group_multiply() {
+        let (multiplier, applied) =
+            combine_perks(&[effect("wallet", 1.1), effect("campaign", 2.0)], day());
+        assert!((multiplier - 2.2).abs() < 1e-9);
+        assert_eq!(applied.len(), 2);
+    }
+
+    #[test]
+    fn max_group_keeps_its_largest_perk() {
+        let (multiplier, applied) = combine_perks(
+            &[
+                grouped("node_streak_7", 1.1, "Max", None),
+                grouped("tasks_100", 1.05, "Max", None),
+                effect("wallet", 1.1),
+            ],
+            day(),
+        );
+        assert!((multiplier - 1.21).abs() < 1e-9);
+        assert_eq!(applied, vec!["node_streak_7", "wallet"]);
+    }
+
+    #[test]
+    fn multiply_group_keeps_every_perk_up_to_its_cap() {
+        let (multiplier, applied) = combine_perks(
+            &[
+                grouped("node_streak_7", 1.1, "Multiply", Some(1.2)),
+                grouped("tasks_100", 1.1, "Multiply", None),
+            ],
+            day(),
+        );
+        assert!((multiplier - 1.2).abs() < 1e-9);
+        assert_eq!(applied, vec!["node_streak_7", "tasks_100"]);
+        let (multiplier, _) = combine_perks(
+            &[
+                grouped("node_streak_7", 1.1, "Multiply", Some(1.5)),
+                grouped("tasks_100", 1.1, "Multiply", None),
+            ],
+            day(),
+        );
+        assert!((multiplier - 1.21).abs() < 1e-9);
+    }
+
+    #[test]
+    fn smallest_cap_of_a_group_wins() {
+        let (multiplier, _) = combine_perks(
+            &[
+                grouped("node_streak_7", 2.0, "Max", Some(1.5)),
+                grouped("tasks_100", 1.05, "Max", Some(1.2)),
+            ],
+            day(),
+        );
+        assert!((multiplier - 1.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn inactive_perks_do_not_count() {
+        let expired = PerkEffect {
+            expires_at: Some(midnight()),
+            ..effect("campaign", 2.0)
+        };
+        assert_eq!(
+            combine_perks(&[expired, effect("wallet", 1.1)], day()),
+            (1.1, vec!["wallet".to_string()])
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use block_mesh_common::interfaces::server_api::ProbeAssertion;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, Clone)]
+pub struct Probe {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub url: String,
+    pub method: String,
+    pub headers: Option<Value>,
+    pub body: Option<Value>,
+    pub expected_status: Option<i32>,
+    pub assertion: Option<Value>,
+    pub interval_secs: i32,
+    pub regions: Vec<String>,
+    pub latency_threshold_ms: Option<f64>,
+    pub availability_threshold: Option<f64>,
+    pub alert_email: Option<String>,
+    pub enabled: bool,
+    pub last_scheduled_at: Option<DateTime<Utc>>,
+    pub last_alerted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Probe {
+    pub fn is_success(&self, response_code: Option<i32>, response_raw: &str) -> bool {
+        let response_code = response_code.unwrap_or(520);
+        let status_ok = match self.expected_status {
+            Some(expected) => response_code == expected,
+            None => (200..300).contains(&response_code),
+        };
+        if !status_ok {
+            return false;
+        }
+        let assertion = match &self.assertion {
+            Some(assertion) => assertion,
+            None => return true,
+        };
+        let assertion: ProbeAssertion = match serde_json::from_value(assertion.clone()) {
+            Ok(assertion) => assertion,
+            Err(_) => return false,
+        };
+        let response: Value = match serde_json::from_str(response_raw) {
+            Ok(response) => response,
+            Err(_) => return false,
+        };
+        match (response.pointer(&assertion.pointer), assertion.equals) {
+            (Some(value), Some(expected)) => *value == expected,
+            (Some(value), None) => !value.is_null(),
+            (None, _) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn probe(expected_status: Option<i32>, assertion: Option<Value>) -> Probe {
+        Probe {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            name: "rpc".to_string(),
+            url: "https://example.com".to_string(),
+            method: "POST".to_string(),
+            headers: None,
+            body: None,
+            expected_status,
+            assertion,
+            interval_secs: 60,
+            regions: Vec::new(),
+            latency_threshold_ms: None,
+            availability_threshold: None,
+            alert_email: None,
+            enabled: true,
+            last_scheduled_at: None,
+            last_alerted_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn status_defaults_to_any_2xx() {
+        let probe = probe(None, None);
+        assert!(probe.is_success(Some(200), ""));
+        assert!(probe.is_success(Some(204), ""));
+        assert!(!probe.is_success(Some(301), ""));
+        assert!(!probe.is_success(None, ""));
+    }
+
+    #[test]
+    fn expected_status_must_match_exactly() {
+        let probe = probe(Some(404), None);
+        assert!(probe.is_success(Some(404), ""));
+        assert!(!probe.is_success(Some(200), ""));
+    }
+
+    #[test]
+    fn pointer_must_exist_and_not_be_null() {
+        let probe = probe(Some(200), Some(json!({"pointer": "/result"})));
+        assert!(probe.is_success(Some(200), r#"{"result": {"slot": 1}}"#));
+        assert!(!probe.is_success(Some(200), r#"{"result": null}"#));
+        assert!(!probe.is_success(Some(200), r#"{"error": "down"}"#));
+        assert!(!probe.is_success(Some(200), "not json"));
+        assert!(!probe.is_success(Some(500), r#"{"result": {"slot": 1}}"#));
+    }
+
+    #[test]
+    fn pointer_value_must_equal_when_given() {
+        let probe = probe(
+            Some(200),
+            Some(json!({"pointer": "/status", "equals": "ok"})),
+        );
+        assert!(probe.is_success(Some(200), r#"{"status": "ok"}"#));
+        assert!(!probe.is_success(Some(200), r#"{"status": "degraded"}"#));
+    }
+
+    #[test]
+    fn invalid_assertions_fail() {
+        let probe = probe(Some(200), Some(json!({"equals": 1})));
+        assert!(!probe.is_success(Some(200), r#"{"result": 1}"#));
+    }
+}
@@ -0,0 +1,73 @@
+use crate::domain::aggregate::AggregateName;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "rebuild_aggregates", skip_all)]
+pub async fn rebuild_aggregates(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Option<Uuid>,
+    name: Option<AggregateName>,
+) -> anyhow::Result<u64> {
+    let r = sqlx::query!(
+        r#"
+        WITH
+        events AS (
+            SELECT id, user_id, name, kind, value, created_at
+            FROM aggregate_events
+            WHERE
+                ($1::uuid IS NULL OR user_id = $1)
+                AND ($2::text IS NULL OR name = $2)
+        ),
+        last_set AS (
+            SELECT DISTINCT ON (user_id, name) user_id, name, value, created_at
+            FROM events
+            WHERE kind = 'Set'
+            ORDER BY user_id, name, created_at DESC
+        ),
+        -- Samples are folded oldest first, each one halving the weight of what came before
+        since_set AS (
+            SELECT
+                events.user_id,
+                events.name,
+                events.kind,
+                events.value,
+                ROW_NUMBER() OVER (
+                    PARTITION BY events.user_id, events.name, events.kind
+                    ORDER BY events.created_at DESC, events.id DESC
+                ) AS age
+            FROM events
+            LEFT JOIN last_set ON last_set.user_id = events.user_id AND last_set.name = events.name
+            WHERE
+                events.kind <> 'Set'
+                AND (last_set.created_at IS NULL OR events.created_at >= last_set.created_at)
+        ),
+        folded AS (
+            SELECT
+                keys.user_id,
+                keys.name,
+                COALESCE(MAX(last_set.value), 0)
+                    * power(0.5, COUNT(since_set.age) FILTER (WHERE since_set.kind = 'Sample')::double precision)
+                + COALESCE(SUM(since_set.value) FILTER (WHERE since_set.kind = 'Add'), 0)
+                + COALESCE(SUM(since_set.value * power(0.5, since_set.age::double precision)) FILTER (
+                    WHERE since_set.kind = 'Sample'
+                ), 0) AS value
+            FROM (SELECT DISTINCT user_id, name FROM events) keys
+            LEFT JOIN last_set ON last_set.user_id = keys.user_id AND last_set.name = keys.name
+            LEFT JOIN since_set ON since_set.user_id = keys.user_id AND since_set.name = keys.name
+            GROUP BY keys.user_id, keys.name
+        )
+        -- updated_at is left untouched, report_uptime relies on it
+        UPDATE aggregates
+            SET value = to_jsonb(folded.value)
+        FROM folded
+        WHERE
+            aggregates.user_id = folded.user_id
+            AND aggregates.name = folded.name
+        "#,
+        user_id,
+        name.map(|n| n.to_string())
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(r.rows_affected())
+}
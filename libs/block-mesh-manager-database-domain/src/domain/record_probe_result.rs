@@ -0,0 +1,68 @@
+use crate::domain::probe::Probe;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "record_probe_result", skip_all)]
+pub async fn record_probe_result(
+    transaction: &mut Transaction<'_, Postgres>,
+    task_id: &Uuid,
+    response_code: Option<i32>,
+    response_raw: &str,
+    country: &str,
+    asn: &str,
+    latency: f64,
+) -> anyhow::Result<()> {
+    let probe = sqlx::query_as!(
+        Probe,
+        r#"
+        SELECT
+        probes.id,
+        probes.user_id,
+        probes.name,
+        probes.url,
+        probes.method,
+        probes.headers,
+        probes.body,
+        probes.expected_status,
+        probes.assertion,
+        probes.interval_secs,
+        probes.regions,
+        probes.latency_threshold_ms,
+        probes.availability_threshold,
+        probes.alert_email,
+        probes.enabled,
+        probes.last_scheduled_at,
+        probes.last_alerted_at,
+        probes.created_at,
+        probes.updated_at
+        FROM tasks
+        JOIN probes ON probes.id = tasks.probe_id
+        WHERE tasks.id = $1
+        LIMIT 1
+        "#,
+        task_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await?;
+    let probe = match probe {
+        Some(probe) => probe,
+        None => return Ok(()),
+    };
+    let success = probe.is_success(response_code, response_raw);
+    sqlx::query!(
+        r#"
+        INSERT INTO probe_results (probe_id, task_id, country, asn, response_code, latency, success)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+        probe.id,
+        task_id,
+        country,
+        asn,
+        response_code,
+        latency,
+        success
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, Clone)]
+pub struct ReferralEarning {
+    pub id: Uuid,
+    /// The inviter that is credited.
+    pub user_id: Uuid,
+    pub referee_id: Uuid,
+    pub day: NaiveDate,
+    /// 1 for direct referees, 2 for the referees of a referee.
+    pub level: i32,
+    pub referee_points: f64,
+    pub percentage: f64,
+    pub points: f64,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum ReferralEarningStatus {
+    Credited,
+    /// The inviter, the referee or an account between them look like the same person,
+    /// recorded with zero points.
+    SelfReferral,
+}
+
+impl Display for ReferralEarningStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Credited => write!(f, "Credited"),
+            Self::SelfReferral => write!(f, "SelfReferral"),
+        }
+    }
+}
@@ -1,16 +1,17 @@
 use crate::domain::aggregate::AggregateName;
+use crate::domain::authenticate_token::{authenticate_token, touch_api_token, Credentials};
 use crate::domain::create_daily_stat::get_or_create_daily_stat;
 use crate::domain::get_or_create_aggregate_by_user_and_name::get_or_create_aggregate_by_user_and_name;
-use crate::domain::get_user_and_api_token::get_user_and_api_token_by_email;
 use crate::domain::notify_worker::notify_worker;
 use anyhow::{anyhow, Error};
 use axum::extract::Request;
 use axum::Json;
 use block_mesh_common::interfaces::db_messages::{
-    AggregateMessage, AnalyticsMessage, DBMessage, DBMessageTypes, DailyStatMessage, UsersIpMessage,
+    AggregateAddToMessage, AnalyticsMessage, DBMessage, DBMessageTypes, DailyStatMessage,
+    UsersIpMessage,
 };
 use block_mesh_common::interfaces::server_api::{
-    ClientsMetadata, HandlerMode, ReportUptimeRequest, ReportUptimeResponse,
+    ClientsMetadata, HandlerMode, ReportUptimeResponse, TokenScope,
 };
 use chrono::Utc;
 use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
@@ -19,6 +20,7 @@ use http_body_util::BodyExt;
 use num_traits::abs;
 use sqlx::PgPool;
 use std::env;
+use uuid::Uuid;
 
 pub fn resolve_ip(
     query_ip: &Option<String>,
@@ -41,23 +43,25 @@ pub async fn report_uptime_content(
     follower_pool: &PgPool,
     channel_pool: &PgPool,
     ip: String,
-    query: ReportUptimeRequest,
+    credentials: &Credentials,
+    report_id: Option<Uuid>,
     request: Option<Request>,
     mode: HandlerMode,
     polling_interval: f64,
     interval_factor: f64,
 ) -> Result<Json<ReportUptimeResponse>, Error> {
     let mut follower_transaction = create_txn(follower_pool).await?;
-    let user = get_user_and_api_token_by_email(&mut follower_transaction, &query.email)
-        .await?
-        .ok_or_else(|| anyhow!("User Not Found"))?;
-
-    if user.token.as_ref() != &query.api_token {
-        return Err(anyhow!("Api Token mismatch"));
-    }
+    let user = authenticate_token(
+        &mut follower_transaction,
+        credentials,
+        TokenScope::NodeReporting,
+    )
+    .await?
+    .ok_or_else(|| anyhow!("Api Token Not Found"))?;
     commit_txn(follower_transaction).await?;
     let mut messages: Vec<DBMessage> = Vec::with_capacity(10);
     let mut transaction = create_txn(pool).await?;
+    touch_api_token(&mut transaction, &user.token_id, Some(&ip)).await?;
     let daily_stat = get_or_create_daily_stat(&mut transaction, &user.user_id, None).await?;
     if let Some(request) = request {
         let (_parts, body) = request.into_parts();
@@ -104,19 +108,16 @@ pub async fn report_uptime_content(
         .parse()
         .unwrap_or(1);
 
-    let (extra, abs) = if (sec_diff
+    let (extra, delta) = if (sec_diff
         < connected_buffer
             * ((polling_interval * interval_factor) as i64)
                 .checked_div(1_000)
                 .unwrap_or(240))
         || mode == HandlerMode::WebSocket
     {
-        (
-            (uptime_bonus * sec_diff) as f64,
-            uptime.value.as_f64().unwrap_or_default() + sec_diff as f64,
-        )
+        ((uptime_bonus * sec_diff) as f64, sec_diff as f64)
     } else {
-        (0.0, uptime.value.as_f64().unwrap_or_default())
+        (0.0, 0.0)
     };
 
     if extra > 0.0 {
@@ -126,10 +127,17 @@ pub async fn report_uptime_content(
             uptime: extra,
         }));
     }
-    messages.push(DBMessage::AggregateMessage(AggregateMessage {
-        msg_type: DBMessageTypes::AggregateMessage,
-        id: uptime.id,
-        value: serde_json::Value::from(abs),
+    // A retried report keeps its id, reports of old clients without one are all applied
+    messages.push(DBMessage::AggregateAddToMessage(AggregateAddToMessage {
+        msg_type: DBMessageTypes::AggregateAddToMessage,
+        user_id: user.user_id,
+        name: AggregateName::Uptime.to_string(),
+        value: serde_json::Value::from(delta),
+        idempotency_key: Some(format!(
+            "uptime:{}:{}",
+            user.user_id,
+            report_id.unwrap_or_else(Uuid::new_v4)
+        )),
     }));
     let _ = notify_worker(channel_pool, &messages).await;
     Ok(Json(ReportUptimeResponse {
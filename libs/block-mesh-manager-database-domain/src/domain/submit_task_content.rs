@@ -1,18 +1,22 @@
 use crate::domain::aggregate::AggregateName;
+use crate::domain::authenticate_token::{authenticate_token, touch_api_token, Credentials};
 use crate::domain::create_daily_stat::get_or_create_daily_stat;
 use crate::domain::find_task_by_task_id_and_status::find_task_by_task_id_and_status;
 use crate::domain::finish_task::finish_task;
 use crate::domain::get_or_create_aggregate_by_user_and_name::get_or_create_aggregate_by_user_and_name;
-use crate::domain::get_user_and_api_token::get_user_and_api_token_by_email;
 use crate::domain::increment_tasks_count::increment_tasks_count;
 use crate::domain::notify_worker::notify_worker;
+use crate::domain::record_probe_result::record_probe_result;
 use crate::domain::task::TaskStatus;
+use crate::domain::task_blob::offload_task_response;
 use anyhow::{anyhow, Error};
 use axum::extract::Request;
 use axum::Json;
-use block_mesh_common::interfaces::db_messages::{AggregateMessage, DBMessage, DBMessageTypes};
+use block_mesh_common::interfaces::db_messages::{
+    AggregateAddToMessage, DBMessage, DBMessageTypes,
+};
 use block_mesh_common::interfaces::server_api::{
-    HandlerMode, SubmitTaskRequest, SubmitTaskResponse,
+    HandlerMode, SubmitTaskRequest, SubmitTaskResponse, TokenScope,
 };
 use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
 use http::StatusCode;
@@ -34,24 +38,27 @@ pub async fn extract_body(request: Request) -> anyhow::Result<String> {
     Ok(String::from_utf8(bytes.to_vec()).unwrap_or_else(|_| String::from("")))
 }
 
+#[allow(clippy::too_many_arguments)]
 #[tracing::instrument(name = "submit_task_content", skip_all)]
 pub async fn submit_task_content(
     pool: &PgPool,
     follower_pool: &PgPool,
     channel_pool: &PgPool,
+    ip: Option<&str>,
+    credentials: &Credentials,
     query: SubmitTaskRequest,
     request: Option<Request>,
     mode: HandlerMode,
 ) -> Result<Json<SubmitTaskResponse>, Error> {
     let mut follower_transaction = create_txn(follower_pool).await?;
 
-    let user = get_user_and_api_token_by_email(&mut follower_transaction, &query.email)
-        .await?
-        .ok_or_else(|| anyhow!("User Not Found"))?;
-    if user.token.as_ref() != &query.api_token {
-        commit_txn(follower_transaction).await?;
-        return Err(anyhow!("Api Token Mismatch"));
-    }
+    let user = authenticate_token(
+        &mut follower_transaction,
+        credentials,
+        TokenScope::TaskSubmission,
+    )
+    .await?
+    .ok_or_else(|| anyhow!("Api Token Not Found"))?;
     let task = find_task_by_task_id_and_status(
         &mut follower_transaction,
         &query.task_id,
@@ -81,21 +88,38 @@ pub async fn submit_task_content(
         },
     };
     commit_txn(follower_transaction).await?;
+    let country = query.country.unwrap_or_default();
+    let asn = query.asn.unwrap_or_default();
+    let response_time = query.response_time.unwrap_or_default();
     let mut transaction = create_txn(pool).await?;
+    touch_api_token(&mut transaction, &user.token_id, ip).await?;
+    record_probe_result(
+        &mut transaction,
+        &query.task_id,
+        query.response_code,
+        &response_raw,
+        &country,
+        &asn,
+        response_time,
+    )
+    .await?;
+    let (response_raw, response_ref) =
+        offload_task_response(&mut transaction, response_raw).await?;
     finish_task(
         &mut transaction,
         query.task_id,
         query.response_code,
-        Option::from(response_raw),
+        response_raw,
+        response_ref,
         match query.response_code.unwrap_or(520) {
             520 => TaskStatus::Failed,
             _ => TaskStatus::Completed,
         },
-        &query.country.unwrap_or_default(),
+        &country,
         &query.ip.unwrap_or_default(),
-        &query.asn.unwrap_or_default(),
+        &asn,
         &query.colo.unwrap_or_default(),
-        query.response_time.unwrap_or_default(),
+        response_time,
     )
     .await?;
     let daily_stat = get_or_create_daily_stat(&mut transaction, &user.user_id, None).await?;
@@ -108,7 +132,7 @@ pub async fn submit_task_content(
 
     if query.response_code.unwrap_or(520) == 200 {
         let mut transaction = create_txn(pool).await?;
-        let tasks = get_or_create_aggregate_by_user_and_name(
+        get_or_create_aggregate_by_user_and_name(
             &mut transaction,
             AggregateName::Tasks,
             &user.user_id,
@@ -117,10 +141,12 @@ pub async fn submit_task_content(
         commit_txn(transaction).await?;
         let _ = notify_worker(
             channel_pool,
-            &vec![DBMessage::AggregateMessage(AggregateMessage {
-                msg_type: DBMessageTypes::AggregateMessage,
-                id: tasks.id,
-                value: serde_json::Value::from(tasks.value.as_i64().unwrap_or_default() + 1),
+            &vec![DBMessage::AggregateAddToMessage(AggregateAddToMessage {
+                msg_type: DBMessageTypes::AggregateAddToMessage,
+                user_id: user.user_id,
+                name: AggregateName::Tasks.to_string(),
+                value: serde_json::Value::from(1),
+                idempotency_key: Some(format!("task:{}", query.task_id)),
             })],
         )
         .await;
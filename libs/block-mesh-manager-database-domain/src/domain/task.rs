@@ -120,10 +120,12 @@ pub struct Task {
     pub method: TaskMethod,
     pub headers: Option<Value>,
     pub body: Option<Value>,
+    pub body_ref: Option<String>,
     pub assigned_user_id: Option<Uuid>,
     pub status: TaskStatus,
     pub response_code: Option<i32>,
     pub response_raw: Option<String>,
+    pub response_ref: Option<String>,
     pub created_at: DateTime<Utc>,
     pub retries_count: i32,
     pub country: String,
@@ -140,4 +142,7 @@ pub struct GetTask {
     pub method: TaskMethod,
     pub headers: Option<Value>,
     pub body: Option<Value>,
+    pub body_ref: Option<String>,
+    pub encrypted: bool,
+    pub reply_key: Option<String>,
 }
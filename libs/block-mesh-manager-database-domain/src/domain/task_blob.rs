@@ -0,0 +1,100 @@
+use anyhow::anyhow;
+use blob_store::blob_store::{blob_key, get_blob_store};
+use serde_json::Value;
+use sqlx::{Postgres, Transaction};
+use std::env;
+
+/// Bodies up to this many bytes stay inline in the row.
+fn offload_threshold() -> usize {
+    env::var("BLOB_OFFLOAD_THRESHOLD")
+        .unwrap_or("65536".to_string())
+        .parse()
+        .unwrap_or(65536)
+}
+
+/// Every object put in the store needs a `blobs` row, the orphan sweep only knows those.
+#[tracing::instrument(name = "store_blob", skip_all, err)]
+pub async fn store_blob(
+    transaction: &mut Transaction<'_, Postgres>,
+    bytes: &[u8],
+) -> anyhow::Result<String> {
+    let key = blob_key(bytes);
+    // Touch the row before writing the object so a concurrent orphan sweep
+    // either skips it or finishes deleting before the object is written again.
+    sqlx::query!(
+        r#"
+        INSERT INTO blobs (key, size)
+        VALUES ($1, $2)
+        ON CONFLICT (key) DO UPDATE SET last_referenced_at = now()
+        "#,
+        key,
+        bytes.len() as i64
+    )
+    .execute(&mut **transaction)
+    .await?;
+    get_blob_store().await?.put(bytes).await?;
+    Ok(key)
+}
+
+#[tracing::instrument(name = "offload_task_body", skip_all, err)]
+pub async fn offload_task_body(
+    transaction: &mut Transaction<'_, Postgres>,
+    body: Option<Value>,
+) -> anyhow::Result<(Option<Value>, Option<String>)> {
+    let bytes = match &body {
+        Some(body) => serde_json::to_vec(body)?,
+        None => return Ok((None, None)),
+    };
+    if bytes.len() <= offload_threshold() {
+        return Ok((body, None));
+    }
+    Ok((None, Some(store_blob(transaction, &bytes).await?)))
+}
+
+#[tracing::instrument(name = "offload_task_response", skip_all, err)]
+pub async fn offload_task_response(
+    transaction: &mut Transaction<'_, Postgres>,
+    response_raw: String,
+) -> anyhow::Result<(Option<String>, Option<String>)> {
+    if response_raw.is_empty() || response_raw.len() <= offload_threshold() {
+        return Ok((Some(response_raw), None));
+    }
+    Ok((
+        None,
+        Some(store_blob(transaction, response_raw.as_bytes()).await?),
+    ))
+}
+
+#[tracing::instrument(name = "resolve_task_body", skip(body), err)]
+pub async fn resolve_task_body(
+    body: Option<Value>,
+    body_ref: Option<String>,
+) -> anyhow::Result<Option<Value>> {
+    let key = match body_ref {
+        Some(key) => key,
+        None => return Ok(body),
+    };
+    let bytes = get_blob_store()
+        .await?
+        .get(&key)
+        .await?
+        .ok_or_else(|| anyhow!("Missing blob {}", key))?;
+    Ok(Some(serde_json::from_slice(&bytes)?))
+}
+
+#[tracing::instrument(name = "resolve_task_response", skip(response_raw), err)]
+pub async fn resolve_task_response(
+    response_raw: Option<String>,
+    response_ref: Option<String>,
+) -> anyhow::Result<Option<String>> {
+    let key = match response_ref {
+        Some(key) => key,
+        None => return Ok(response_raw),
+    };
+    let bytes = get_blob_store()
+        .await?
+        .get(&key)
+        .await?
+        .ok_or_else(|| anyhow!("Missing blob {}", key))?;
+    Ok(Some(String::from_utf8_lossy(&bytes).to_string()))
+}
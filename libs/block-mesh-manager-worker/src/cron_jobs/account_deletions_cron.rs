@@ -0,0 +1,130 @@
+use crate::db_calls::claim_due_deletion::{claim_due_deletion, mark_deletion_failed};
+use crate::db_calls::delete_user_data::delete_user_data;
+use crate::db_calls::get_telegram_link_code::get_telegram_link_code;
+use crate::domain::account_data::{cached_session_pattern, data_sink_url, tg_privacy_bot_url};
+use blob_store::blob_store::get_blob_store;
+use block_mesh_common::interfaces::db_messages::InvalidateApiCache;
+use block_mesh_manager_database_domain::domain::get_user_opt_by_id::get_user_opt_by_id;
+use block_mesh_manager_database_domain::domain::notify_api::notify_api;
+use chrono::{Duration, Utc};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use redis::AsyncCommands;
+use reqwest::Client;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::env;
+use uuid::Uuid;
+
+/// Deletes the accounts whose grace period ended, each one in its own transaction.
+#[tracing::instrument(name = "account_deletions_cron", level = "trace", skip(pool))]
+pub async fn account_deletions_cron(pool: &PgPool) -> anyhow::Result<()> {
+    let limit = env::var("ACCOUNT_DELETION_BATCH")
+        .unwrap_or("10".to_string())
+        .parse()
+        .unwrap_or(10);
+    let client = Client::new();
+    for _ in 0..limit {
+        let mut transaction = create_txn(pool).await?;
+        let Some(deletion) = claim_due_deletion(&mut transaction).await? else {
+            return commit_txn(transaction).await;
+        };
+        let user_id = deletion.user_id;
+        let email = get_user_opt_by_id(&mut transaction, &user_id)
+            .await?
+            .map(|user| user.email);
+        match delete_account(&mut transaction, &client, &user_id).await {
+            Ok(blob_keys) => {
+                commit_txn(transaction).await?;
+                forget_account(pool, &user_id, email.as_deref(), &blob_keys).await;
+                tracing::info!("Deleted account {}", user_id);
+            }
+            Err(e) => {
+                tracing::error!("Failed to delete account {}: {}", user_id, e);
+                drop(transaction);
+                let mut transaction = create_txn(pool).await?;
+                let retry_at = Utc::now() + Duration::hours(1);
+                mark_deletion_failed(&mut transaction, &user_id, &e.to_string(), retry_at).await?;
+                commit_txn(transaction).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The data sink and privacy bot rows live in other databases, they go first so a failure
+/// there keeps the account scheduled.
+async fn delete_account(
+    transaction: &mut Transaction<'_, Postgres>,
+    client: &Client,
+    user_id: &Uuid,
+) -> anyhow::Result<Vec<String>> {
+    client
+        .delete(format!("{}/data_sinks/user", data_sink_url()))
+        .query(&[
+            ("code", env::var("ADMIN_PARAM").unwrap_or_default()),
+            ("user_id", user_id.to_string()),
+        ])
+        .send()
+        .await?
+        .error_for_status()?;
+    if let Some(link_code) = get_telegram_link_code(transaction, user_id).await? {
+        client
+            .delete(format!("{}/users", tg_privacy_bot_url()))
+            .query(&[
+                ("code", env::var("ADMIN_PARAM").unwrap_or_default()),
+                ("link_code", link_code),
+            ])
+            .send()
+            .await?
+            .error_for_status()?;
+    }
+    delete_user_data(transaction, user_id).await
+}
+
+/// Removes the export archives, the cached sessions of the manager and the tokens cached by
+/// the api and ws services. Nothing here can bring the account back, so failures are only logged.
+async fn forget_account(pool: &PgPool, user_id: &Uuid, email: Option<&str>, blob_keys: &[String]) {
+    match get_blob_store().await {
+        Ok(store) => {
+            for key in blob_keys {
+                if let Err(e) = store.delete(key).await {
+                    tracing::warn!("Failed to delete export {} of {}: {}", key, user_id, e);
+                }
+            }
+        }
+        Err(e) => tracing::warn!("Failed to delete exports of {}: {}", user_id, e),
+    }
+    if let Some(email) = email {
+        let message = InvalidateApiCache {
+            email: email.to_string(),
+        };
+        if let Err(e) = notify_api(pool, message).await {
+            tracing::warn!("Failed to invalidate api caches of {}: {}", user_id, e);
+        }
+    }
+    let Ok(redis_url) = env::var("REDIS_URL") else {
+        return;
+    };
+    let result: anyhow::Result<()> = async {
+        let client = redis::Client::open(redis_url)?;
+        let mut con = client.get_multiplexed_async_connection().await?;
+        con.del::<_, ()>(user_id.to_string()).await?;
+        if let Some(email) = email {
+            // SCAN instead of KEYS, which blocks Redis while it walks every key.
+            let mut keys = Vec::new();
+            let mut iter = con
+                .scan_match::<_, String>(cached_session_pattern(email))
+                .await?;
+            while let Some(key) = iter.next_item().await {
+                keys.push(key);
+            }
+            for key in keys {
+                con.del::<_, ()>(key).await?;
+            }
+        }
+        Ok(())
+    }
+    .await;
+    if let Err(e) = result {
+        tracing::warn!("Failed to clear cached sessions of {}: {}", user_id, e);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::db_calls::claim_pending_exports::claim_pending_exports;
+use crate::db_calls::export_user_rows::export_user_rows;
+use crate::db_calls::get_telegram_link_code::get_telegram_link_code;
+use crate::db_calls::update_account_export::{
+    expire_account_exports, mark_export_failed, mark_export_ready,
+};
+use crate::domain::account_data::{data_sink_url, tg_privacy_bot_url, EXPORT_SECTIONS};
+use block_mesh_manager_database_domain::domain::account_export::AccountExport;
+use block_mesh_manager_database_domain::domain::task_blob::store_blob;
+use chrono::{Duration, Utc};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use reqwest::Client;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::env;
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Claims a batch and commits, then builds and stores every archive on its own so a slow
+/// data sink or a failed export does not hold locks or roll back the others.
+#[tracing::instrument(name = "account_exports_cron", level = "trace", skip(pool))]
+pub async fn account_exports_cron(pool: &PgPool) -> anyhow::Result<()> {
+    let limit = env::var("ACCOUNT_EXPORT_BATCH")
+        .unwrap_or("5".to_string())
+        .parse()
+        .unwrap_or(5);
+    let ttl_hours = env::var("ACCOUNT_EXPORT_TTL_HOURS")
+        .unwrap_or("168".to_string())
+        .parse()
+        .unwrap_or(168);
+    let stale_minutes = env::var("ACCOUNT_EXPORT_STALE_MINUTES")
+        .unwrap_or("30".to_string())
+        .parse()
+        .unwrap_or(30);
+    let mut transaction = create_txn(pool).await?;
+    expire_account_exports(&mut transaction, 100).await?;
+    let exports = claim_pending_exports(&mut transaction, limit, stale_minutes).await?;
+    commit_txn(transaction).await?;
+    let client = Client::new();
+    for export in exports {
+        let result = match build_archive(pool, &client, &export).await {
+            Ok(archive) => save_archive(pool, &export, &archive, ttl_hours).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            tracing::error!("Failed to export account data {}: {}", export.id, e);
+            let mut transaction = create_txn(pool).await?;
+            mark_export_failed(&mut transaction, &export.id, &e.to_string()).await?;
+            commit_txn(transaction).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn save_archive(
+    pool: &PgPool,
+    export: &AccountExport,
+    archive: &[u8],
+    ttl_hours: i64,
+) -> anyhow::Result<()> {
+    let mut transaction = create_txn(pool).await?;
+    let key = store_blob(&mut transaction, archive).await?;
+    let expires_at = Utc::now() + Duration::hours(ttl_hours);
+    mark_export_ready(
+        &mut transaction,
+        &export.id,
+        &key,
+        archive.len() as i64,
+        expires_at,
+    )
+    .await?;
+    commit_txn(transaction).await
+}
+
+/// A zip with a JSON file per table, the data sink records as NDJSON and the privacy bot
+/// rows of linked Telegram users.
+async fn build_archive(
+    pool: &PgPool,
+    client: &Client,
+    export: &AccountExport,
+) -> anyhow::Result<Vec<u8>> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let manifest = json!({
+        "export_id": export.id,
+        "user_id": export.user_id,
+        "requested_at": export.created_at,
+        "generated_at": Utc::now(),
+        "files": EXPORT_SECTIONS
+            .iter()
+            .map(|(name, _)| format!("{}.json", name))
+            .chain(["data_sinks.ndjson".to_string(), "telegram.json".to_string()])
+            .collect::<Vec<_>>(),
+    });
+    zip.start_file("manifest.json", options)?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+    let mut transaction = create_txn(pool).await?;
+    for (name, query) in EXPORT_SECTIONS {
+        let rows = export_user_rows(&mut transaction, query, &export.user_id).await?;
+        zip.start_file(format!("{}.json", name), options)?;
+        zip.write_all(&serde_json::to_vec_pretty(&rows)?)?;
+    }
+    let link_code = get_telegram_link_code(&mut transaction, &export.user_id).await?;
+    commit_txn(transaction).await?;
+    let data_sinks = client
+        .get(format!("{}/data_sinks/export", data_sink_url()))
+        .query(&[
+            ("code", env::var("ADMIN_PARAM").unwrap_or_default()),
+            ("user_id", export.user_id.to_string()),
+            ("format", "ndjson".to_string()),
+            ("include_raw", "true".to_string()),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    zip.start_file("data_sinks.ndjson", options)?;
+    zip.write_all(&data_sinks)?;
+    let telegram = match link_code {
+        Some(link_code) => {
+            client
+                .get(format!("{}/users/export", tg_privacy_bot_url()))
+                .query(&[
+                    ("code", env::var("ADMIN_PARAM").unwrap_or_default()),
+                    ("link_code", link_code),
+                ])
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<Value>()
+                .await?
+        }
+        None => json!({}),
+    };
+    zip.start_file("telegram.json", options)?;
+    zip.write_all(&serde_json::to_vec_pretty(&telegram)?)?;
+    Ok(zip.finish()?.into_inner())
+}
@@ -0,0 +1,19 @@
+use crate::db_calls::delete_old_probe_results::delete_old_probe_results;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::PgPool;
+use std::env;
+
+#[tracing::instrument(name = "clean_old_probe_results", level = "trace", skip(pool))]
+pub async fn clean_old_probe_results(pool: &PgPool) -> anyhow::Result<()> {
+    let retention_days = env::var("PROBE_RESULTS_RETENTION_DAYS")
+        .unwrap_or("30".to_string())
+        .parse()
+        .unwrap_or(30);
+    let limit = env::var("BULK_DELETE_LIMIT")
+        .unwrap_or("300".to_string())
+        .parse()
+        .unwrap_or(300);
+    let mut transaction = create_txn(pool).await?;
+    delete_old_probe_results(&mut transaction, retention_days, limit).await?;
+    commit_txn(transaction).await
+}
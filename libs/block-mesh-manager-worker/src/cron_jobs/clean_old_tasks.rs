@@ -1,14 +1,12 @@
 use crate::db_calls::bulk_delete_old_tasks::bulk_delete_old_tasks;
+use crate::db_calls::fail_stale_encrypted_tasks::fail_stale_encrypted_tasks;
 use sqlx::PgPool;
-use std::time::Duration;
 
 #[tracing::instrument(name = "clean_old_tasks", level = "trace", skip(pool))]
-pub async fn clean_old_tasks(pool: PgPool) -> Result<(), anyhow::Error> {
-    loop {
-        if let Ok(mut transaction) = pool.begin().await {
-            let _ = bulk_delete_old_tasks(&mut transaction).await;
-            let _ = transaction.commit().await;
-        }
-        tokio::time::sleep(Duration::from_secs(60)).await;
-    }
+pub async fn clean_old_tasks(pool: &PgPool) -> Result<(), anyhow::Error> {
+    let mut transaction = pool.begin().await?;
+    bulk_delete_old_tasks(&mut transaction).await?;
+    fail_stale_encrypted_tasks(&mut transaction).await?;
+    transaction.commit().await?;
+    Ok(())
 }
@@ -0,0 +1,24 @@
+use crate::db_calls::delete_orphan_blobs::delete_orphan_blobs;
+use blob_store::blob_store::get_blob_store;
+use sqlx::PgPool;
+use std::env;
+
+#[tracing::instrument(name = "clean_orphan_blobs", level = "trace", skip(pool))]
+pub async fn clean_orphan_blobs(pool: &PgPool) -> Result<(), anyhow::Error> {
+    let grace_secs = env::var("BLOB_ORPHAN_GRACE")
+        .unwrap_or("3600".to_string())
+        .parse()
+        .unwrap_or(3600);
+    let limit = env::var("BULK_DELETE_LIMIT")
+        .unwrap_or("300".to_string())
+        .parse()
+        .unwrap_or(300);
+    let mut transaction = pool.begin().await?;
+    let keys = delete_orphan_blobs(&mut transaction, grace_secs, limit).await?;
+    let store = get_blob_store().await?;
+    for key in &keys {
+        store.delete(key).await?;
+    }
+    transaction.commit().await?;
+    Ok(())
+}
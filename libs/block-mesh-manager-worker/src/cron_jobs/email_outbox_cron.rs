@@ -0,0 +1,148 @@
+use crate::db_calls::claim_pending_emails::claim_pending_emails;
+use crate::db_calls::get_email_suppression::get_email_suppression;
+use crate::db_calls::update_email_outbox::{
+    mark_email_failed, mark_email_sent, mark_email_suppressed,
+};
+use block_mesh_common::constants::BLOCK_MESH_APP_SERVER;
+use block_mesh_common::email_client::client::{EmailClient, EmailProvider};
+use block_mesh_common::email_client::locale::EmailLocale;
+use block_mesh_common::email_client::template::{EmailCategory, EmailTemplate, RenderedEmail};
+use block_mesh_manager_database_domain::domain::email_outbox::EmailOutbox;
+use block_mesh_manager_database_domain::domain::notification_preferences::get_or_create_notification_preferences;
+use chrono::{Duration, Utc};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::env;
+
+/// Backoff before the next attempt, doubling from 30 seconds up to 6 hours.
+fn retry_delay(attempts: i32) -> Duration {
+    Duration::seconds((30i64 << attempts.clamp(0, 10)).min(6 * 60 * 60))
+}
+
+#[tracing::instrument(name = "email_outbox_cron", level = "trace", skip(pool))]
+pub async fn email_outbox_cron(pool: &PgPool) -> anyhow::Result<()> {
+    let limit = env::var("EMAIL_OUTBOX_BATCH")
+        .unwrap_or("50".to_string())
+        .parse()
+        .unwrap_or(50);
+    let max_attempts = env::var("EMAIL_MAX_ATTEMPTS")
+        .unwrap_or("5".to_string())
+        .parse()
+        .unwrap_or(5);
+    let lease = Duration::seconds(
+        env::var("EMAIL_CLAIM_LEASE_SECONDS")
+            .unwrap_or("600".to_string())
+            .parse()
+            .unwrap_or(600),
+    );
+    let mut transaction = create_txn(pool).await?;
+    let emails = claim_pending_emails(&mut transaction, limit, lease).await?;
+    commit_txn(transaction).await?;
+    if emails.is_empty() {
+        return Ok(());
+    }
+    let email_client = EmailClient::new(BLOCK_MESH_APP_SERVER.to_string()).await;
+    let providers = EmailProvider::from_env();
+    for email in emails {
+        if email.attempts > max_attempts {
+            // Claimed again after its last lease ran out without an outcome.
+            let mut transaction = create_txn(pool).await?;
+            mark_email_failed(&mut transaction, &email.id, "Out of attempts", None).await?;
+            commit_txn(transaction).await?;
+            continue;
+        }
+        if let Err(e) = deliver(pool, &email_client, &providers, &email).await {
+            tracing::error!("Failed to deliver email {}: {}", email.id, e);
+            let next_attempt_at = (email.attempts < max_attempts)
+                .then(|| Utc::now() + retry_delay(email.attempts - 1));
+            let mut transaction = create_txn(pool).await?;
+            mark_email_failed(&mut transaction, &email.id, &e.to_string(), next_attempt_at).await?;
+            commit_txn(transaction).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Sends through the first provider that accepts the email, an error schedules a retry.
+/// No transaction is held while the providers are called, each outcome is its own write.
+async fn deliver(
+    pool: &PgPool,
+    email_client: &EmailClient,
+    providers: &[EmailProvider],
+    email: &EmailOutbox,
+) -> anyhow::Result<()> {
+    let mut transaction = create_txn(pool).await?;
+    let rendered = render(&mut transaction, email_client, email).await?;
+    commit_txn(transaction).await?;
+    let Some(rendered) = rendered else {
+        return Ok(());
+    };
+    let mut errors = Vec::new();
+    for provider in providers {
+        match email_client
+            .send(*provider, &email.email_address, &rendered)
+            .await
+        {
+            Ok(message_id) => {
+                let mut transaction = create_txn(pool).await?;
+                mark_email_sent(
+                    &mut transaction,
+                    &email.id,
+                    &provider.to_string(),
+                    &message_id,
+                )
+                .await?;
+                return commit_txn(transaction).await;
+            }
+            Err(e) => {
+                tracing::warn!("Email {} failed through {}: {}", email.id, provider, e);
+                errors.push(format!("{}: {}", provider, e));
+            }
+        }
+    }
+    Err(anyhow::anyhow!(errors.join("; ")))
+}
+
+/// Returns `None` when the email is suppressed or can never render, the row is updated already.
+async fn render(
+    transaction: &mut Transaction<'_, Postgres>,
+    email_client: &EmailClient,
+    email: &EmailOutbox,
+) -> anyhow::Result<Option<RenderedEmail>> {
+    if let Some(reason) = get_email_suppression(transaction, &email.email_address).await? {
+        mark_email_suppressed(transaction, &email.id, &reason).await?;
+        return Ok(None);
+    }
+    let template: EmailTemplate = match serde_json::from_value(email.payload.clone()) {
+        Ok(template) => template,
+        Err(e) => {
+            // A payload that does not parse will not parse on the next attempt either.
+            mark_email_failed(transaction, &email.id, &e.to_string(), None).await?;
+            return Ok(None);
+        }
+    };
+    let mut locale = EmailLocale::default();
+    let mut unsubscribe_url = None;
+    if let Some(user_id) = &email.user_id {
+        let preferences =
+            get_or_create_notification_preferences(transaction, user_id, &locale.to_string())
+                .await?;
+        locale = preferences.locale.parse().unwrap_or_default();
+        match template.category() {
+            EmailCategory::Account => {}
+            EmailCategory::ProbeAlerts if !preferences.probe_alerts => {
+                mark_email_suppressed(transaction, &email.id, "Unsubscribed").await?;
+                return Ok(None);
+            }
+            category => {
+                unsubscribe_url = Some(format!(
+                    "{}/unsubscribe?token={}&category={}",
+                    email_client.base_url, preferences.unsubscribe_token, category
+                ));
+            }
+        }
+    }
+    template
+        .render(locale, &email_client.base_url, unsubscribe_url.as_deref())
+        .map(Some)
+}
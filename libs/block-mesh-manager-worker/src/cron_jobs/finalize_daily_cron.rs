@@ -1,15 +1,120 @@
 use crate::db_calls::bulk_finalize::bulk_finalize;
+use crate::db_calls::create_referral_earnings::{create_referral_earnings, ReferralEarningRows};
+use crate::db_calls::get_perk_effects::get_perk_effects;
+use crate::db_calls::get_referral_candidates::get_referral_candidates;
+use crate::db_calls::snapshot_daily_perks::{
+    create_perk_history, get_daily_stats_without_perks, update_daily_stat_perk_multipliers,
+    PerkHistoryRows,
+};
+use crate::domain::referral_program::ReferralProgram;
+use block_mesh_manager_database_domain::domain::points::{combine_perks, raw_points};
+use chrono::{Duration, Utc};
 use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
-use sqlx::PgPool;
-use std::time::Duration;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
+use std::env;
 
 #[tracing::instrument(name = "finalize_daily_cron", level = "trace", skip(pool))]
-pub async fn finalize_daily_cron(pool: PgPool) -> Result<(), anyhow::Error> {
-    loop {
-        if let Ok(mut transaction) = create_txn(&pool).await {
-            let _ = bulk_finalize(&mut transaction).await;
-            let _ = commit_txn(transaction).await;
+pub async fn finalize_daily_cron(pool: &PgPool) -> Result<(), anyhow::Error> {
+    let mut transaction = create_txn(pool).await?;
+    bulk_finalize(&mut transaction).await?;
+    commit_txn(transaction).await?;
+    let mut transaction = create_txn(pool).await?;
+    snapshot_daily_perks(&mut transaction).await?;
+    commit_txn(transaction).await?;
+    let mut transaction = create_txn(pool).await?;
+    pay_referral_earnings(&mut transaction, &ReferralProgram::from_env()).await?;
+    commit_txn(transaction).await?;
+    Ok(())
+}
+
+/// Records which perks counted on each finalized day and their combined multiplier,
+/// so later perk changes do not rewrite past points.
+#[tracing::instrument(name = "snapshot_daily_perks", level = "trace", skip(transaction))]
+async fn snapshot_daily_perks(transaction: &mut Transaction<'_, Postgres>) -> anyhow::Result<()> {
+    let limit = env::var("PERK_SNAPSHOT_BATCH")
+        .unwrap_or("10000".to_string())
+        .parse()
+        .unwrap_or(10000);
+    let days = env::var("PERK_SNAPSHOT_DAYS")
+        .unwrap_or("7".to_string())
+        .parse()
+        .unwrap_or(7);
+    let since = (Utc::now() - Duration::days(days)).date_naive();
+    let daily_stats = get_daily_stats_without_perks(transaction, since, limit).await?;
+    if daily_stats.is_empty() {
+        return Ok(());
+    }
+    let mut user_ids: Vec<_> = daily_stats.iter().map(|i| i.user_id).collect();
+    user_ids.sort();
+    user_ids.dedup();
+    let mut effects: HashMap<_, Vec<_>> = HashMap::new();
+    for effect in get_perk_effects(transaction, &user_ids).await? {
+        effects.entry(effect.user_id).or_default().push(effect);
+    }
+    let mut history = PerkHistoryRows::default();
+    let mut ids = Vec::with_capacity(daily_stats.len());
+    let mut multipliers = Vec::with_capacity(daily_stats.len());
+    for daily_stat in daily_stats {
+        let user_effects = effects
+            .get(&daily_stat.user_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let (multiplier, applied) = combine_perks(user_effects, daily_stat.day);
+        for effect in user_effects
+            .iter()
+            .filter(|e| e.is_active_on(daily_stat.day))
+        {
+            history.user_ids.push(daily_stat.user_id);
+            history.days.push(daily_stat.day);
+            history.perk_names.push(effect.name.clone());
+            history.multipliers.push(effect.multiplier);
+            history.applied.push(applied.contains(&effect.name));
+        }
+        ids.push(daily_stat.id);
+        multipliers.push(multiplier);
+    }
+    create_perk_history(transaction, &history).await?;
+    update_daily_stat_perk_multipliers(transaction, &ids, &multipliers).await?;
+    Ok(())
+}
+
+/// Credits inviters with a share of their referees' finalized days.
+/// Runs after every finalize so days finalized in later batches are paid too.
+#[tracing::instrument(name = "pay_referral_earnings", level = "trace", skip(transaction))]
+async fn pay_referral_earnings(
+    transaction: &mut Transaction<'_, Postgres>,
+    program: &ReferralProgram,
+) -> anyhow::Result<()> {
+    let since = (Utc::now() - Duration::days(program.window_days)).date_naive();
+    for (level, percentage) in program.levels() {
+        let candidates = get_referral_candidates(
+            transaction,
+            level,
+            since,
+            program.min_active_days,
+            program.min_daily_uptime,
+            program.batch,
+        )
+        .await?;
+        let mut rows = ReferralEarningRows::default();
+        for candidate in candidates {
+            let referee_points =
+                raw_points(candidate.uptime, candidate.tasks_count) * candidate.perk_multiplier;
+            let (points, status) =
+                ReferralProgram::earning(percentage, referee_points, candidate.overlap);
+            rows.user_ids.push(candidate.user_id);
+            rows.referee_ids.push(candidate.referee_id);
+            rows.days.push(candidate.day);
+            rows.referee_points.push(referee_points);
+            rows.points.push(points);
+            rows.statuses.push(status.to_string());
+        }
+        if rows.user_ids.is_empty() {
+            continue;
         }
-        tokio::time::sleep(Duration::from_secs(60 * 60)).await;
+        let inserted = create_referral_earnings(transaction, level, percentage, &rows).await?;
+        tracing::info!("Credited {} level {} referral earnings", inserted, level);
     }
+    Ok(())
 }
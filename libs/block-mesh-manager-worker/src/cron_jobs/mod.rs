@@ -1,6 +1,14 @@
+pub mod account_deletions_cron;
+pub mod account_exports_cron;
 pub mod bulk_task_bonus_cron;
 pub mod bulk_uptime_bonus_cron;
+pub mod clean_old_probe_results;
 pub mod clean_old_tasks;
+pub mod clean_orphan_blobs;
+pub mod email_outbox_cron;
 pub mod finalize_daily_cron;
-pub mod rpc_cron;
+pub mod perk_rules_cron;
+pub mod probe_alerts_cron;
+pub mod probe_cron;
+pub mod prune_aggregate_events_cron;
 pub mod special_task_cron;
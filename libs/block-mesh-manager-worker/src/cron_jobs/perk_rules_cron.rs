@@ -0,0 +1,46 @@
+use crate::db_calls::perk_rules::{
+    get_rule_perk_definitions, get_users_with_completed_tasks, get_users_with_node_streak,
+    grant_perk,
+};
+use block_mesh_manager_database_domain::domain::perk_definition::PerkCondition;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::PgPool;
+use std::env;
+
+/// Grants catalog perks to users that meet their condition.
+#[tracing::instrument(name = "perk_rules_cron", level = "trace", skip(pool))]
+pub async fn perk_rules_cron(pool: &PgPool) -> anyhow::Result<()> {
+    let limit = env::var("PERK_RULES_BATCH")
+        .unwrap_or("1000".to_string())
+        .parse()
+        .unwrap_or(1000);
+    let mut transaction = create_txn(pool).await?;
+    for definition in get_rule_perk_definitions(&mut transaction).await? {
+        let (Some(condition), Some(threshold)) = (
+            definition.condition.as_deref(),
+            definition.condition_threshold,
+        ) else {
+            continue;
+        };
+        let user_ids = match PerkCondition::try_from(condition) {
+            Ok(PerkCondition::ConsecutiveNodeDays) => {
+                get_users_with_node_streak(&mut transaction, &definition.name, threshold, limit)
+                    .await?
+            }
+            Ok(PerkCondition::CompletedTasks) => {
+                get_users_with_completed_tasks(&mut transaction, &definition.name, threshold, limit)
+                    .await?
+            }
+            Err(e) => {
+                tracing::error!("Skipping perk {}: {}", definition.name, e);
+                continue;
+            }
+        };
+        if user_ids.is_empty() {
+            continue;
+        }
+        let granted = grant_perk(&mut transaction, &definition, &user_ids).await?;
+        tracing::info!("Granted perk {} to {} users", definition.name, granted);
+    }
+    commit_txn(transaction).await
+}
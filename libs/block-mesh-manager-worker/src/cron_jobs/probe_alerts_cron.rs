@@ -0,0 +1,55 @@
+use crate::db_calls::enqueue_email::enqueue_email;
+use crate::db_calls::get_probe_breaches::get_probe_breaches;
+use crate::db_calls::touch_probe::touch_probe_alerted;
+use block_mesh_common::email_client::template::EmailTemplate;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::PgPool;
+use std::env;
+use std::time::Duration;
+
+#[tracing::instrument(name = "probe_alerts_cron", level = "trace", skip(pool))]
+pub async fn probe_alerts_cron(pool: &PgPool) -> anyhow::Result<()> {
+    let window = env::var("PROBE_SLO_WINDOW")
+        .unwrap_or("900".to_string())
+        .parse()
+        .unwrap_or(900);
+    let cooldown = env::var("PROBE_ALERT_COOLDOWN")
+        .unwrap_or("3600".to_string())
+        .parse()
+        .unwrap_or(3600);
+    // Used for probes that only set an alert email.
+    let default_availability = env::var("PROBE_DEFAULT_AVAILABILITY")
+        .unwrap_or("0.95".to_string())
+        .parse()
+        .unwrap_or(0.95);
+    let mut transaction = create_txn(pool).await?;
+    let breaches = get_probe_breaches(
+        &mut transaction,
+        Duration::from_secs(window),
+        Duration::from_secs(cooldown),
+        default_availability,
+    )
+    .await?;
+    for breach in breaches {
+        let details = format!(
+            "{} samples in the last {} seconds: availability {:.2}%, average latency {:.0}ms",
+            breach.total,
+            window,
+            breach.availability * 100.0,
+            breach.latency
+        );
+        let template = EmailTemplate::ProbeAlert {
+            probe_name: breach.name,
+            details,
+        };
+        enqueue_email(
+            &mut transaction,
+            &breach.user_id,
+            &breach.alert_email,
+            &template,
+        )
+        .await?;
+        touch_probe_alerted(&mut transaction, &breach.id).await?;
+    }
+    commit_txn(transaction).await
+}
@@ -0,0 +1,48 @@
+use crate::db_calls::create_probe_task::create_probe_task;
+use crate::db_calls::get_due_probes::get_due_probes;
+use crate::db_calls::touch_probe::touch_probe_scheduled;
+use anyhow::anyhow;
+use block_mesh_common::public_address::resolve_public_host;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::PgPool;
+use std::env;
+use url::Url;
+
+/// The address a probe points at is checked again when it is scheduled, a name that resolved
+/// to a public address when the probe was created may not anymore.
+async fn check_probe_target(url: &str) -> anyhow::Result<()> {
+    let url = Url::parse(url)?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow!("URL has no host"))?
+        .to_string();
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| anyhow!("URL has no port"))?;
+    tokio::task::spawn_blocking(move || resolve_public_host(&host, port)).await??;
+    Ok(())
+}
+
+#[tracing::instrument(name = "create_probe_tasks", level = "trace", skip(pool))]
+pub async fn create_probe_tasks(pool: &PgPool) -> anyhow::Result<()> {
+    let limit = env::var("PROBE_CRON_LIMIT")
+        .unwrap_or("500".to_string())
+        .parse()
+        .unwrap_or(500);
+    let mut transaction = create_txn(pool).await?;
+    for probe in get_due_probes(&mut transaction, limit).await? {
+        if let Err(e) = check_probe_target(&probe.url).await {
+            tracing::warn!("Skipping probe {}: {}", probe.id, e);
+            touch_probe_scheduled(&mut transaction, &probe.id).await?;
+            continue;
+        }
+        if probe.regions.is_empty() {
+            create_probe_task(&mut transaction, &probe, None).await?;
+        }
+        for region in &probe.regions {
+            create_probe_task(&mut transaction, &probe, Some(region)).await?;
+        }
+        touch_probe_scheduled(&mut transaction, &probe.id).await?;
+    }
+    commit_txn(transaction).await
+}
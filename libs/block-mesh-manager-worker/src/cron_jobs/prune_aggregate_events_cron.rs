@@ -0,0 +1,24 @@
+use crate::db_calls::prune_aggregate_events::{
+    delete_old_aggregate_events, snapshot_aggregate_events,
+};
+use chrono::{Duration, Utc};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::PgPool;
+use std::env;
+
+#[tracing::instrument(name = "prune_aggregate_events_cron", level = "trace", skip(pool))]
+pub async fn prune_aggregate_events_cron(pool: &PgPool) -> anyhow::Result<()> {
+    let retention_days = env::var("AGGREGATE_EVENTS_RETENTION_DAYS")
+        .unwrap_or("30".to_string())
+        .parse()
+        .unwrap_or(30);
+    let limit = env::var("BULK_DELETE_LIMIT")
+        .unwrap_or("300".to_string())
+        .parse()
+        .unwrap_or(300);
+    let before = Utc::now() - Duration::days(retention_days);
+    let mut transaction = create_txn(pool).await?;
+    snapshot_aggregate_events(&mut transaction, before, limit).await?;
+    delete_old_aggregate_events(&mut transaction, before, limit).await?;
+    commit_txn(transaction).await
+}
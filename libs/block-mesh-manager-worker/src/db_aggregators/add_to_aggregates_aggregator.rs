@@ -1,5 +1,6 @@
 use anyhow::anyhow;
 use block_mesh_common::interfaces::db_messages::DBMessage;
+use block_mesh_manager_database_domain::domain::aggregate_event::AggregateEventKind;
 use chrono::Utc;
 use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
 use flume::Sender;
@@ -12,48 +13,49 @@ use tokio::task::JoinHandle;
 use uuid::Uuid;
 
 #[tracing::instrument(name = "add_to_aggregates_create_bulk_query", skip_all)]
-pub fn add_to_aggregates_create_bulk_query(calls: HashMap<Uuid, (String, Value)>) -> String {
+pub fn add_to_aggregates_create_bulk_query(calls: HashMap<String, (Uuid, String, f64)>) -> String {
     let now = Utc::now();
-    let lock_values: Vec<String> = calls
+    let event_values: Vec<String> = calls
         .iter()
-        .map(|(user_id, value)| format!("('{}'::uuid, '{}')", user_id, value.0))
-        .collect();
-    let lock_values_str = lock_values.join(",");
-
-    let update_values: Vec<String> = calls
-        .iter()
-        .map(|(user_id, value)| {
+        .map(|(idempotency_key, (user_id, name, value))| {
             format!(
-                "('{}'::uuid, '{}'::jsonb, '{}'::timestamptz, '{}')",
+                "('{}'::uuid, '{}', {}::double precision, '{}', '{}'::timestamptz)",
                 user_id,
-                value.1,
-                now.to_rfc3339(),
-                value.0
+                name,
+                value,
+                idempotency_key.replace('\'', "''"),
+                now.to_rfc3339()
             )
         })
         .collect();
 
-    let update_values_str = update_values.join(",");
+    let event_values_str = event_values.join(",");
     format!(
         r#"
         WITH
-        locked_rows (user_id, name) AS (
-            SELECT user_id, name
-            FROM aggregates
-            WHERE (user_id, name) IN ( {lock_values_str} )
-            FOR UPDATE SKIP LOCKED
+        events (user_id, name, value, idempotency_key, created_at) AS ( VALUES {event_values_str} ),
+        inserted AS (
+            INSERT INTO aggregate_events (user_id, name, kind, value, idempotency_key, created_at)
+            SELECT user_id, name, '{kind}', value, idempotency_key, created_at
+            FROM events
+            ON CONFLICT (idempotency_key) DO NOTHING
+            RETURNING user_id, name, value, created_at
         ),
-        updates (user_id, value, updated_at, name) AS ( VALUES {update_values_str} )
+        folded AS (
+            SELECT user_id, name, SUM(value) AS value, MAX(created_at) AS updated_at
+            FROM inserted
+            GROUP BY user_id, name
+        )
         UPDATE aggregates
             SET
-                value =  to_jsonb((COALESCE(NULLIF(aggregates.value, 'null'), '0')::text)::double precision + updates.value::double precision),
-                updated_at = updates.updated_at
-        FROM updates
-        JOIN locked_rows ON locked_rows.user_id = updates.user_id AND locked_rows.name = updates.name
+                value =  to_jsonb((COALESCE(NULLIF(aggregates.value, 'null'), '0')::text)::double precision + folded.value),
+                updated_at = folded.updated_at
+        FROM folded
         WHERE
-            aggregates.user_id = updates.user_id
-            AND aggregates.name = updates.name
-        "#
+            aggregates.user_id = folded.user_id
+            AND aggregates.name = folded.name
+        "#,
+        kind = AggregateEventKind::Add
     )
 }
 
@@ -65,7 +67,7 @@ pub async fn add_to_aggregates_aggregator(
     agg_size: i32,
     time_limit: i64,
 ) -> Result<(), anyhow::Error> {
-    let mut calls: HashMap<Uuid, (String, Value)> = HashMap::new();
+    let mut calls: HashMap<String, (Uuid, String, f64)> = HashMap::new();
     let mut count = 0;
     let mut prev = Utc::now();
     loop {
@@ -74,7 +76,16 @@ pub async fn add_to_aggregates_aggregator(
                 if let Ok(DBMessage::AggregateAddToMessage(message)) =
                     serde_json::from_value::<DBMessage>(message)
                 {
-                    calls.insert(message.user_id, (message.name, message.value));
+                    calls.insert(
+                        message
+                            .idempotency_key
+                            .unwrap_or_else(|| Uuid::new_v4().to_string()),
+                        (
+                            message.user_id,
+                            message.name,
+                            message.value.as_f64().unwrap_or_default(),
+                        ),
+                    );
                     count += 1;
                     let now = Utc::now();
                     let diff = now - prev;
@@ -4,5 +4,6 @@ pub mod analytics_aggregator;
 pub mod channel;
 pub mod daily_stats_aggregator;
 pub mod joiner_loop;
+pub mod sample_aggregates_aggregator;
 pub mod set_to_aggregates_aggregator;
 pub mod users_ip_aggregator;
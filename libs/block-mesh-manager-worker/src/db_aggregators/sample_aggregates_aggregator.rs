@@ -0,0 +1,152 @@
+use anyhow::anyhow;
+use block_mesh_common::interfaces::db_messages::DBMessage;
+use block_mesh_manager_database_domain::domain::aggregate_event::AggregateEventKind;
+use chrono::Utc;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use flume::Sender;
+use serde_json::Value;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+#[tracing::instrument(name = "sample_aggregates_create_bulk_query", skip_all)]
+pub fn sample_aggregates_create_bulk_query(calls: HashMap<String, (Uuid, String, f64)>) -> String {
+    let now = Utc::now();
+    let event_values: Vec<String> = calls
+        .iter()
+        .map(|(idempotency_key, (user_id, name, value))| {
+            format!(
+                "('{}'::uuid, '{}', {}::double precision, '{}', '{}'::timestamptz)",
+                user_id,
+                name,
+                value,
+                idempotency_key.replace('\'', "''"),
+                now.to_rfc3339()
+            )
+        })
+        .collect();
+
+    let event_values_str = event_values.join(",");
+    format!(
+        r#"
+        WITH
+        events (user_id, name, value, idempotency_key, created_at) AS ( VALUES {event_values_str} ),
+        inserted AS (
+            INSERT INTO aggregate_events (user_id, name, kind, value, idempotency_key, created_at)
+            SELECT user_id, name, '{kind}', value, idempotency_key, created_at
+            FROM events
+            ON CONFLICT (idempotency_key) DO NOTHING
+            RETURNING id, user_id, name, value, created_at
+        ),
+        -- The same order rebuild_aggregates folds samples in, the newest sample has age 1
+        ranked AS (
+            SELECT user_id, name, value, created_at,
+                ROW_NUMBER() OVER (PARTITION BY user_id, name ORDER BY created_at DESC, id DESC) AS age
+            FROM inserted
+        ),
+        folded AS (
+            SELECT
+                user_id,
+                name,
+                COUNT(*)::double precision AS samples,
+                SUM(value * power(0.5, age::double precision)) AS value,
+                MAX(created_at) AS updated_at
+            FROM ranked
+            GROUP BY user_id, name
+        )
+        UPDATE aggregates
+            SET
+                value = to_jsonb(
+                    (COALESCE(NULLIF(aggregates.value, 'null'), '0')::text)::double precision * power(0.5, folded.samples)
+                    + folded.value
+                ),
+                updated_at = folded.updated_at
+        FROM folded
+        WHERE
+            aggregates.user_id = folded.user_id
+            AND aggregates.name = folded.name
+        "#,
+        kind = AggregateEventKind::Sample
+    )
+}
+
+#[tracing::instrument(name = "sample_aggregates_aggregator", skip_all, err)]
+pub async fn sample_aggregates_aggregator(
+    joiner_tx: Sender<JoinHandle<()>>,
+    pool: PgPool,
+    mut rx: Receiver<Value>,
+    agg_size: i32,
+    time_limit: i64,
+) -> Result<(), anyhow::Error> {
+    let mut calls: HashMap<String, (Uuid, String, f64)> = HashMap::new();
+    let mut count = 0;
+    let mut prev = Utc::now();
+    loop {
+        match rx.recv().await {
+            Ok(message) => {
+                if let Ok(DBMessage::AggregateSampleMessage(message)) =
+                    serde_json::from_value::<DBMessage>(message)
+                {
+                    calls.insert(
+                        message
+                            .idempotency_key
+                            .unwrap_or_else(|| Uuid::new_v4().to_string()),
+                        (
+                            message.user_id,
+                            message.name,
+                            message.value.as_f64().unwrap_or_default(),
+                        ),
+                    );
+                    count += 1;
+                    let now = Utc::now();
+                    let diff = now - prev;
+                    let run = diff.num_seconds() > time_limit || count >= agg_size;
+                    prev = Utc::now();
+                    if run {
+                        let calls_clone = calls.clone();
+                        let poll_clone = pool.clone();
+                        let handle = tokio::spawn(async move {
+                            tracing::info!("sample_aggregates_create_bulk_query starting txn");
+                            if let Ok(mut transaction) = create_txn(&poll_clone).await {
+                                let query = sample_aggregates_create_bulk_query(calls_clone);
+                                let r = sqlx::query(&query)
+                                            .execute(&mut *transaction)
+                                            .await
+                                            .map_err(|e| {
+                                                tracing::error!(
+                                                    "sample_aggregates_create_bulk_query failed to execute query size: {} , with error {:?}",
+                                                    count,
+                                                    e
+                                                );
+                                            });
+                                if let Ok(r) = r {
+                                    tracing::info!(
+                                        "sample_aggregates_create_bulk_query rows_affected : {}",
+                                        r.rows_affected()
+                                    );
+                                }
+                                let _ = commit_txn(transaction).await;
+                                tracing::info!("sample_aggregates_create_bulk_query finished txn");
+                            }
+                        });
+                        let _ = joiner_tx.send_async(handle).await;
+                        count = 0;
+                        calls.clear();
+                    }
+                }
+            }
+            Err(e) => match e {
+                RecvError::Closed => {
+                    tracing::error!("sample_aggregates_aggregator error recv: {:?}", e);
+                    return Err(anyhow!("sample_aggregates_aggregator error recv: {:?}", e));
+                }
+                RecvError::Lagged(_) => {
+                    tracing::error!("sample_aggregates_aggregator error recv: {:?}", e);
+                }
+            },
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use chrono::{Duration, Utc};
+use chrono::Utc;
 use sqlx::{Postgres, Transaction};
 use std::env;
 
@@ -12,16 +12,28 @@ use std::env;
 pub async fn bulk_delete_old_tasks(
     transaction: &mut Transaction<'_, Postgres>,
 ) -> anyhow::Result<()> {
-    let date = Utc::now() - Duration::days(1);
+    let now = Utc::now();
+    let default_retention_days = env::var("TASK_RETENTION_DAYS")
+        .unwrap_or("1".to_string())
+        .parse()
+        .unwrap_or(1);
     let bulk_delete_limit = env::var("BULK_DELETE_LIMIT")
         .unwrap_or("300".to_string())
         .parse()
         .unwrap_or(300);
     sqlx::query!(
         r#"
-        DELETE FROM tasks WHERE id IN (SELECT id from tasks WHERE created_at < $1 LIMIT $2 FOR UPDATE SKIP LOCKED)
+        DELETE FROM tasks WHERE id IN (
+            SELECT tasks.id
+            FROM tasks
+            LEFT JOIN task_retention ON task_retention.user_id = tasks.user_id
+            WHERE tasks.created_at < $1::timestamptz - make_interval(days => COALESCE(task_retention.retention_days, $2))
+            LIMIT $3
+            FOR UPDATE OF tasks SKIP LOCKED
+        )
         "#,
-        date,
+        now,
+        default_retention_days,
         bulk_delete_limit
     )
     .execute(&mut **transaction)
@@ -0,0 +1,24 @@
+use sqlx::{Postgres, Transaction};
+
+#[tracing::instrument(
+    name = "claim_cron_job_trigger",
+    skip(transaction),
+    err,
+    level = "trace"
+)]
+pub async fn claim_cron_job_trigger(
+    transaction: &mut Transaction<'_, Postgres>,
+    name: &str,
+) -> anyhow::Result<bool> {
+    let r = sqlx::query!(
+        r#"
+        UPDATE cron_jobs
+        SET triggered_at = NULL
+        WHERE name = $1 AND triggered_at IS NOT NULL
+        "#,
+        name
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(r.rows_affected() > 0)
+}
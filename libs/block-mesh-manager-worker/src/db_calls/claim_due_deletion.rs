@@ -0,0 +1,59 @@
+use block_mesh_manager_database_domain::domain::account_deletion::{
+    AccountDeletion, AccountDeletionStatus,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// A deletion whose grace period is over, locked so a cancel cannot race the delete.
+#[tracing::instrument(
+    name = "claim_due_deletion",
+    skip(transaction),
+    level = "trace",
+    ret,
+    err
+)]
+pub(crate) async fn claim_due_deletion(
+    transaction: &mut Transaction<'_, Postgres>,
+) -> anyhow::Result<Option<AccountDeletion>> {
+    let deletion = sqlx::query_as!(
+        AccountDeletion,
+        r#"
+        SELECT user_id, status, requested_at, scheduled_for, completed_at, last_error
+        FROM account_deletions
+        WHERE status = $1 AND scheduled_for <= now()
+        ORDER BY scheduled_for
+        LIMIT 1
+        FOR UPDATE SKIP LOCKED
+        "#,
+        AccountDeletionStatus::Scheduled.to_string()
+    )
+    .fetch_optional(&mut **transaction)
+    .await?;
+    Ok(deletion)
+}
+
+/// Keeps the deletion scheduled and moves it back, so one failing account does not block the rest.
+#[tracing::instrument(
+    name = "mark_deletion_failed",
+    skip(transaction),
+    level = "trace",
+    ret,
+    err
+)]
+pub(crate) async fn mark_deletion_failed(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    error: &str,
+    retry_at: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        "UPDATE account_deletions SET last_error = $2, scheduled_for = $3 WHERE user_id = $1",
+        user_id,
+        error,
+        retry_at
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
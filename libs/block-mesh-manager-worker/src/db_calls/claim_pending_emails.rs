@@ -0,0 +1,41 @@
+use block_mesh_manager_database_domain::domain::email_outbox::{EmailOutbox, EmailOutboxStatus};
+use chrono::{Duration, Utc};
+use sqlx::{Postgres, Transaction};
+
+/// Marks the rows as `Sending` for `lease` and counts the attempt, commit right away and send
+/// outside the transaction. A lease that runs out is a crashed attempt, it is not free.
+#[tracing::instrument(name = "claim_pending_emails", skip(transaction), level = "trace", err)]
+pub(crate) async fn claim_pending_emails(
+    transaction: &mut Transaction<'_, Postgres>,
+    limit: i64,
+    lease: Duration,
+) -> anyhow::Result<Vec<EmailOutbox>> {
+    let emails = sqlx::query_as!(
+        EmailOutbox,
+        r#"
+        UPDATE email_outbox
+        SET status = $2, attempts = attempts + 1, next_attempt_at = $4, updated_at = now()
+        WHERE id IN (
+            SELECT id
+            FROM email_outbox
+            WHERE
+                status IN ($1, $2)
+            AND
+                next_attempt_at <= now()
+            ORDER BY next_attempt_at
+            LIMIT $3
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING
+        id, user_id, email_address, template, payload, status, attempts, next_attempt_at,
+        provider, message_id, last_error, created_at, updated_at, sent_at
+        "#,
+        EmailOutboxStatus::Pending.to_string(),
+        EmailOutboxStatus::Sending.to_string(),
+        limit,
+        Utc::now() + lease
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(emails)
+}
@@ -0,0 +1,47 @@
+use block_mesh_manager_database_domain::domain::account_export::{
+    AccountExport, AccountExportStatus,
+};
+use sqlx::{Postgres, Transaction};
+
+/// Moves pending exports to `Processing`, along with the ones a crashed worker left behind
+/// for longer than `stale_minutes`.
+#[tracing::instrument(
+    name = "claim_pending_exports",
+    skip(transaction),
+    level = "trace",
+    err
+)]
+pub(crate) async fn claim_pending_exports(
+    transaction: &mut Transaction<'_, Postgres>,
+    limit: i64,
+    stale_minutes: i64,
+) -> anyhow::Result<Vec<AccountExport>> {
+    let exports = sqlx::query_as!(
+        AccountExport,
+        r#"
+        WITH claimed AS (
+            SELECT id
+            FROM account_exports
+            WHERE status = $1
+            OR (status = $2 AND claimed_at < now() - make_interval(mins => $4))
+            ORDER BY created_at
+            LIMIT $3
+            FOR UPDATE SKIP LOCKED
+        )
+        UPDATE account_exports
+        SET status = $2, claimed_at = now()
+        FROM claimed
+        WHERE account_exports.id = claimed.id
+        RETURNING
+        account_exports.id, user_id, status, blob_key, size_bytes, last_error, created_at,
+        completed_at, expires_at
+        "#,
+        AccountExportStatus::Pending.to_string(),
+        AccountExportStatus::Processing.to_string(),
+        limit,
+        stale_minutes as i32
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(exports)
+}
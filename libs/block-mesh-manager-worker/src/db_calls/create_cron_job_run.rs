@@ -0,0 +1,30 @@
+use block_mesh_manager_database_domain::domain::cron_job::{CronJobRunStatus, CronJobTrigger};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[tracing::instrument(name = "create_cron_job_run", skip(pool), err, level = "trace")]
+pub async fn create_cron_job_run(
+    pool: &PgPool,
+    name: &str,
+    trigger: CronJobTrigger,
+    instance: &str,
+    scheduled_for: DateTime<Utc>,
+) -> anyhow::Result<Option<Uuid>> {
+    let id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO cron_job_runs (name, trigger, status, instance, scheduled_for)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (name, scheduled_for) DO NOTHING
+        RETURNING id
+        "#,
+        name,
+        trigger.to_string(),
+        CronJobRunStatus::Running.to_string(),
+        instance,
+        scheduled_for
+    )
+    .fetch_optional(pool)
+    .await?;
+    Ok(id)
+}
@@ -0,0 +1,43 @@
+use block_mesh_manager_database_domain::domain::probe::Probe;
+use block_mesh_manager_database_domain::domain::task_blob::offload_task_body;
+use chrono::Utc;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(
+    name = "create_probe_task",
+    level = "trace",
+    skip(transaction, probe),
+    ret,
+    err
+)]
+pub(crate) async fn create_probe_task(
+    transaction: &mut Transaction<'_, Postgres>,
+    probe: &Probe,
+    region: Option<&str>,
+) -> anyhow::Result<Uuid> {
+    let now = Utc::now();
+    let id = Uuid::new_v4();
+    let (body, body_ref) = offload_task_body(transaction, probe.body.clone()).await?;
+    sqlx::query!(
+        r#"INSERT
+           INTO tasks
+           (id, created_at, url, method, headers, body, body_ref, status, user_id, probe_id, region)
+           VALUES
+           ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)"#,
+        id,
+        now,
+        probe.url,
+        probe.method,
+        probe.headers,
+        body,
+        body_ref,
+        "Pending".to_string(),
+        probe.user_id,
+        probe.id,
+        region
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(id)
+}
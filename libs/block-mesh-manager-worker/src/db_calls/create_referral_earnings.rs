@@ -0,0 +1,49 @@
+use chrono::NaiveDate;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[derive(Debug, Default)]
+pub(crate) struct ReferralEarningRows {
+    pub user_ids: Vec<Uuid>,
+    pub referee_ids: Vec<Uuid>,
+    pub days: Vec<NaiveDate>,
+    pub referee_points: Vec<f64>,
+    pub points: Vec<f64>,
+    pub statuses: Vec<String>,
+}
+
+#[tracing::instrument(
+    name = "create_referral_earnings",
+    skip(transaction, rows),
+    level = "trace",
+    ret,
+    err
+)]
+pub(crate) async fn create_referral_earnings(
+    transaction: &mut Transaction<'_, Postgres>,
+    level: i32,
+    percentage: f64,
+    rows: &ReferralEarningRows,
+) -> anyhow::Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO referral_earnings
+        (user_id, referee_id, day, level, referee_points, percentage, points, status)
+        SELECT user_id, referee_id, day, $1, referee_points, $2, points, status
+        FROM UNNEST($3::UUID[], $4::UUID[], $5::DATE[], $6::FLOAT8[], $7::FLOAT8[], $8::TEXT[])
+        AS rows(user_id, referee_id, day, referee_points, points, status)
+        ON CONFLICT (referee_id, level, day) DO NOTHING
+        "#,
+        level,
+        percentage,
+        &rows.user_ids,
+        &rows.referee_ids,
+        &rows.days,
+        &rows.referee_points,
+        &rows.points,
+        &rows.statuses
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(result.rows_affected())
+}
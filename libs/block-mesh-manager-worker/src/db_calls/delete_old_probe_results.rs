@@ -0,0 +1,32 @@
+use chrono::{Duration, Utc};
+use sqlx::{Postgres, Transaction};
+
+#[tracing::instrument(
+    name = "delete_old_probe_results",
+    skip(transaction),
+    ret,
+    err,
+    level = "trace"
+)]
+pub(crate) async fn delete_old_probe_results(
+    transaction: &mut Transaction<'_, Postgres>,
+    retention_days: i64,
+    limit: i64,
+) -> anyhow::Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM probe_results WHERE id IN (
+            SELECT id
+            FROM probe_results
+            WHERE created_at < $1
+            LIMIT $2
+            FOR UPDATE SKIP LOCKED
+        )
+        "#,
+        Utc::now() - Duration::days(retention_days),
+        limit
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(result.rows_affected())
+}
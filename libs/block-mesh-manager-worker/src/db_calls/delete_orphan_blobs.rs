@@ -0,0 +1,41 @@
+use chrono::{Duration, Utc};
+use sqlx::{Postgres, Transaction};
+
+#[tracing::instrument(
+    name = "delete_orphan_blobs",
+    skip(transaction),
+    ret,
+    err,
+    level = "trace"
+)]
+pub(crate) async fn delete_orphan_blobs(
+    transaction: &mut Transaction<'_, Postgres>,
+    grace_secs: i64,
+    limit: i64,
+) -> anyhow::Result<Vec<String>> {
+    let cutoff = Utc::now() - Duration::seconds(grace_secs);
+    let keys = sqlx::query_scalar!(
+        r#"
+        DELETE FROM blobs WHERE key IN (
+            SELECT key
+            FROM blobs
+            WHERE
+                last_referenced_at < $1
+            AND
+                NOT EXISTS (SELECT 1 FROM tasks WHERE tasks.body_ref = blobs.key)
+            AND
+                NOT EXISTS (SELECT 1 FROM tasks WHERE tasks.response_ref = blobs.key)
+            AND
+                NOT EXISTS (SELECT 1 FROM account_exports WHERE account_exports.blob_key = blobs.key)
+            LIMIT $2
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING key
+        "#,
+        cutoff,
+        limit
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(keys)
+}
@@ -0,0 +1,101 @@
+use crate::domain::account_data::{anonymised_email, DELETED_USER_TABLES};
+use block_mesh_manager_database_domain::domain::account_deletion::AccountDeletionStatus;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Removes or anonymises every row tied to the user and returns the blob keys of their
+/// export archives, which the caller deletes from the blob store.
+#[tracing::instrument(name = "delete_user_data", skip(transaction), level = "trace", err)]
+pub(crate) async fn delete_user_data(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<Vec<String>> {
+    let blob_keys = sqlx::query_scalar!(
+        r#"
+        DELETE FROM account_exports
+        WHERE user_id = $1
+        RETURNING blob_key
+        "#,
+        user_id
+    )
+    .fetch_all(&mut **transaction)
+    .await?
+    .into_iter()
+    .flatten()
+    .collect();
+    // Tasks the user ran for others belong to their creators, only the runner is forgotten.
+    sqlx::query!(
+        r#"
+        UPDATE tasks
+        SET assigned_user_id = NULL, ip = ''
+        WHERE assigned_user_id = $1 AND user_id <> $1
+        "#,
+        user_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+    sqlx::query!("DELETE FROM tasks WHERE user_id = $1", user_id)
+        .execute(&mut **transaction)
+        .await?;
+    for table in DELETED_USER_TABLES {
+        sqlx::query(&format!("DELETE FROM {} WHERE user_id = $1", table))
+            .bind(user_id)
+            .execute(&mut **transaction)
+            .await?;
+    }
+    sqlx::query!("DELETE FROM nonces WHERE user_id = $1", user_id)
+        .execute(&mut **transaction)
+        .await?;
+    // The nonces trigger keeps a copy of every version, including the one just deleted.
+    sqlx::query!(
+        r#"
+        DELETE FROM archives
+        WHERE
+            table_name = 'nonces'
+        AND
+            (old_values ->> 'user_id' = $1::text OR new_values ->> 'user_id' = $1::text)
+        "#,
+        user_id.to_string()
+    )
+    .execute(&mut **transaction)
+    .await?;
+    sqlx::query!(
+        r#"
+        DELETE FROM email_suppressions
+        WHERE email_address = (SELECT email FROM users WHERE id = $1)
+        "#,
+        user_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET
+            email = $2,
+            password = '',
+            wallet_address = NULL,
+            invited_by = NULL,
+            verified_email = FALSE,
+            status = 'deleted',
+            comments = NULL
+        WHERE id = $1
+        "#,
+        user_id,
+        anonymised_email(user_id)
+    )
+    .execute(&mut **transaction)
+    .await?;
+    sqlx::query!(
+        r#"
+        UPDATE account_deletions
+        SET status = $2, completed_at = now(), last_error = NULL
+        WHERE user_id = $1
+        "#,
+        user_id,
+        AccountDeletionStatus::Completed.to_string()
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(blob_keys)
+}
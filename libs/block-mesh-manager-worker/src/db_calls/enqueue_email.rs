@@ -0,0 +1,29 @@
+use block_mesh_common::email_client::template::EmailTemplate;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(
+    name = "enqueue_email",
+    skip(transaction, template),
+    level = "trace",
+    ret,
+    err
+)]
+pub(crate) async fn enqueue_email(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    email_address: &str,
+    template: &EmailTemplate,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"INSERT INTO email_outbox (user_id, email_address, template, payload)
+        VALUES ($1, $2, $3, $4)"#,
+        user_id,
+        email_address,
+        template.name(),
+        serde_json::to_value(template)?
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use serde_json::Value;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Runs one of the `EXPORT_SECTIONS` queries and returns its rows as a JSON array.
+#[tracing::instrument(
+    name = "export_user_rows",
+    skip(transaction, query),
+    level = "trace",
+    err
+)]
+pub(crate) async fn export_user_rows(
+    transaction: &mut Transaction<'_, Postgres>,
+    query: &str,
+    user_id: &Uuid,
+) -> anyhow::Result<Value> {
+    let query = format!(
+        "SELECT COALESCE(json_agg(t), '[]'::json) FROM ({}) t",
+        query
+    );
+    let rows: Value = sqlx::query_scalar(&query)
+        .bind(user_id)
+        .fetch_one(&mut **transaction)
+        .await?;
+    Ok(rows)
+}
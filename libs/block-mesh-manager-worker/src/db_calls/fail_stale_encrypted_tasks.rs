@@ -0,0 +1,48 @@
+use block_mesh_manager_database_domain::domain::task::TaskStatus;
+use chrono::{Duration, Utc};
+use sqlx::{Postgres, Transaction};
+use std::env;
+
+/// Sealed tasks can only be opened by the node key they were pinned to, once that node stops
+/// registering its key nobody else can run them.
+#[tracing::instrument(
+    name = "fail_stale_encrypted_tasks",
+    skip(transaction),
+    ret,
+    err,
+    level = "trace"
+)]
+pub async fn fail_stale_encrypted_tasks(
+    transaction: &mut Transaction<'_, Postgres>,
+) -> anyhow::Result<u64> {
+    let max_age_secs = env::var("NODE_KEY_MAX_AGE")
+        .unwrap_or("86400".to_string())
+        .parse()
+        .unwrap_or(86_400);
+    let bulk_limit = env::var("BULK_DELETE_LIMIT")
+        .unwrap_or("300".to_string())
+        .parse()
+        .unwrap_or(300);
+    let cutoff = Utc::now() - Duration::seconds(max_age_secs);
+    let r = sqlx::query!(
+        r#"
+        UPDATE tasks
+        SET status = $2
+        WHERE id IN (
+            SELECT tasks.id
+            FROM tasks
+            JOIN node_keys ON node_keys.id = tasks.node_key_id
+            WHERE tasks.status = $1 AND node_keys.updated_at < $3
+            LIMIT $4
+            FOR UPDATE OF tasks SKIP LOCKED
+        )
+        "#,
+        TaskStatus::Assigned.to_string(),
+        TaskStatus::Failed.to_string(),
+        cutoff,
+        bulk_limit
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(r.rows_affected())
+}
@@ -0,0 +1,41 @@
+use block_mesh_manager_database_domain::domain::cron_job::CronJobRunStatus;
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+use std::env;
+use uuid::Uuid;
+
+#[tracing::instrument(name = "finish_cron_job_run", skip(pool), err, level = "trace")]
+pub async fn finish_cron_job_run(
+    pool: &PgPool,
+    id: &Uuid,
+    name: &str,
+    status: CronJobRunStatus,
+    error: Option<String>,
+) -> anyhow::Result<()> {
+    let now = Utc::now();
+    let retention_days = env::var("CRON_JOB_RUNS_RETENTION_DAYS")
+        .unwrap_or("7".to_string())
+        .parse()
+        .unwrap_or(7);
+    sqlx::query!(
+        r#"
+        UPDATE cron_job_runs
+        SET status = $1, error = $2, finished_at = $3
+        WHERE id = $4
+        "#,
+        status.to_string(),
+        error,
+        now,
+        id
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query!(
+        r#"DELETE FROM cron_job_runs WHERE name = $1 AND started_at < $2"#,
+        name,
+        now - Duration::days(retention_days)
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
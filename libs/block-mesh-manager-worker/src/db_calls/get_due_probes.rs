@@ -0,0 +1,49 @@
+use block_mesh_manager_database_domain::domain::probe::Probe;
+use chrono::Utc;
+use sqlx::{Postgres, Transaction};
+
+#[tracing::instrument(name = "get_due_probes", skip(transaction), level = "trace", ret, err)]
+pub(crate) async fn get_due_probes(
+    transaction: &mut Transaction<'_, Postgres>,
+    limit: i64,
+) -> anyhow::Result<Vec<Probe>> {
+    let now = Utc::now();
+    let probes = sqlx::query_as!(
+        Probe,
+        r#"
+        SELECT
+        id,
+        user_id,
+        name,
+        url,
+        method,
+        headers,
+        body,
+        expected_status,
+        assertion,
+        interval_secs,
+        regions,
+        latency_threshold_ms,
+        availability_threshold,
+        alert_email,
+        enabled,
+        last_scheduled_at,
+        last_alerted_at,
+        created_at,
+        updated_at
+        FROM probes
+        WHERE
+            enabled = TRUE
+        AND
+            (last_scheduled_at IS NULL OR last_scheduled_at + make_interval(secs => interval_secs) <= $1)
+        ORDER BY last_scheduled_at NULLS FIRST
+        LIMIT $2
+        FOR UPDATE SKIP LOCKED
+        "#,
+        now,
+        limit
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(probes)
+}
@@ -0,0 +1,22 @@
+use sqlx::{Postgres, Transaction};
+
+/// The reason the address is suppressed, if it is.
+#[tracing::instrument(
+    name = "get_email_suppression",
+    skip(transaction),
+    level = "trace",
+    ret,
+    err
+)]
+pub(crate) async fn get_email_suppression(
+    transaction: &mut Transaction<'_, Postgres>,
+    email_address: &str,
+) -> anyhow::Result<Option<String>> {
+    let reason = sqlx::query_scalar!(
+        r#"SELECT reason FROM email_suppressions WHERE email_address = lower($1)"#,
+        email_address
+    )
+    .fetch_optional(&mut **transaction)
+    .await?;
+    Ok(reason)
+}
@@ -0,0 +1,34 @@
+use block_mesh_manager_database_domain::domain::points::PerkEffect;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Every perk of the users, perks missing from the catalog stack on their own.
+#[tracing::instrument(name = "get_perk_effects", skip_all, level = "trace", err)]
+pub(crate) async fn get_perk_effects(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_ids: &[Uuid],
+) -> anyhow::Result<Vec<PerkEffect>> {
+    let effects = sqlx::query_as!(
+        PerkEffect,
+        r#"
+        SELECT
+        perks.user_id,
+        perks.name,
+        perks.multiplier,
+        perk_definitions.stacking_group AS "stacking_group?",
+        COALESCE(perk_definitions.stacking, 'Multiply') AS "stacking!",
+        perk_definitions.max_multiplier AS "max_multiplier?",
+        perks.starts_at,
+        perks.expires_at,
+        perk_definitions.valid_from AS "valid_from?",
+        perk_definitions.valid_until AS "valid_until?"
+        FROM perks
+        LEFT JOIN perk_definitions ON perk_definitions.name = perks.name
+        WHERE perks.user_id = ANY($1)
+        "#,
+        user_ids
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(effects)
+}
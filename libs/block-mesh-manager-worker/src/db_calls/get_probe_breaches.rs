@@ -0,0 +1,63 @@
+use chrono::Utc;
+use sqlx::{Postgres, Transaction};
+use std::time::Duration;
+use uuid::Uuid;
+
+#[derive(Debug)]
+pub(crate) struct ProbeBreach {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub alert_email: String,
+    pub total: i64,
+    pub availability: f64,
+    pub latency: f64,
+}
+
+#[tracing::instrument(
+    name = "get_probe_breaches",
+    skip(transaction),
+    level = "trace",
+    ret,
+    err
+)]
+pub(crate) async fn get_probe_breaches(
+    transaction: &mut Transaction<'_, Postgres>,
+    window: Duration,
+    cooldown: Duration,
+    default_availability: f64,
+) -> anyhow::Result<Vec<ProbeBreach>> {
+    let now = Utc::now();
+    let breaches = sqlx::query_as!(
+        ProbeBreach,
+        r#"
+        SELECT
+        probes.id,
+        probes.user_id,
+        probes.name,
+        probes.alert_email AS "alert_email!",
+        COUNT(*) AS "total!",
+        AVG(probe_results.success::int)::float8 AS "availability!",
+        AVG(probe_results.latency) AS "latency!"
+        FROM probes
+        JOIN probe_results ON probe_results.probe_id = probes.id AND probe_results.created_at > $1
+        WHERE
+            probes.enabled = TRUE
+        AND
+            probes.alert_email IS NOT NULL
+        AND
+            (probes.last_alerted_at IS NULL OR probes.last_alerted_at < $2)
+        GROUP BY probes.id
+        HAVING
+            AVG(probe_results.success::int) < COALESCE(probes.availability_threshold, $3)
+        OR
+            AVG(probe_results.latency) > COALESCE(probes.latency_threshold_ms, 'Infinity'::float8)
+        "#,
+        now - window,
+        now - cooldown,
+        default_availability
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(breaches)
+}
@@ -0,0 +1,214 @@
+use chrono::NaiveDate;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// A finalized day of a referee that has not been paid out at `level` yet.
+#[derive(Debug, Clone)]
+pub(crate) struct ReferralCandidate {
+    pub user_id: Uuid,
+    pub referee_id: Uuid,
+    pub day: NaiveDate,
+    pub uptime: f64,
+    pub tasks_count: i64,
+    /// Combined perk multiplier recorded when the day was finalized.
+    pub perk_multiplier: f64,
+    /// Two accounts of the chain from inviter to referee look like the same person, see
+    /// `referral_overlap` in the migrations.
+    pub overlap: bool,
+}
+
+#[tracing::instrument(
+    name = "get_referral_candidates",
+    skip(transaction),
+    level = "trace",
+    err
+)]
+pub(crate) async fn get_referral_candidates(
+    transaction: &mut Transaction<'_, Postgres>,
+    level: i32,
+    since: NaiveDate,
+    min_active_days: i64,
+    min_daily_uptime: f64,
+    limit: i64,
+) -> anyhow::Result<Vec<ReferralCandidate>> {
+    let candidates = sqlx::query_as!(
+        ReferralCandidate,
+        r#"
+        SELECT
+        inviter.id AS "user_id!",
+        referee.id AS "referee_id!",
+        daily_stats.day AS "day!",
+        daily_stats.uptime AS "uptime!",
+        daily_stats.tasks_count AS "tasks_count!",
+        daily_stats.perk_multiplier AS "perk_multiplier!",
+        (
+            referral_overlap(inviter.id, referee.id)
+            OR ($1 = 2 AND (referral_overlap(inviter.id, parent.id) OR referral_overlap(parent.id, referee.id)))
+        ) AS "overlap!"
+        FROM daily_stats
+        JOIN users referee ON referee.id = daily_stats.user_id
+        JOIN users parent ON parent.id = referee.invited_by
+        JOIN users inviter ON inviter.id = CASE WHEN $1 = 1 THEN parent.id ELSE parent.invited_by END
+        WHERE daily_stats.status = 'Finalized'
+        AND daily_stats.perk_multiplier IS NOT NULL
+        AND daily_stats.day >= $2
+        AND daily_stats.uptime >= $4
+        AND inviter.id <> referee.id
+        AND inviter.status IS DISTINCT FROM 'deleted'
+        AND NOT EXISTS (
+            SELECT 1 FROM referral_earnings
+            WHERE referee_id = referee.id AND level = $1 AND day = daily_stats.day
+        )
+        AND (
+            SELECT COUNT(*) FROM daily_stats active
+            WHERE active.user_id = referee.id AND active.day <= daily_stats.day AND active.uptime >= $4
+        ) >= $3
+        ORDER BY daily_stats.day
+        LIMIT $5
+        "#,
+        level,
+        since,
+        min_active_days,
+        min_daily_uptime,
+        limit
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use block_mesh_common::env::load_dotenv::load_dotenv;
+    use chrono::{Duration, Utc};
+    use database_utils::utils::connection::write_pool::write_pool;
+
+    async fn create_user(
+        transaction: &mut Transaction<'_, Postgres>,
+        email: &str,
+        invited_by: Option<Uuid>,
+    ) -> Uuid {
+        let id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO users (id, email, password, created_at, invited_by)
+            VALUES ($1, $2, 'password', now(), $3)",
+        )
+        .bind(id)
+        .bind(email)
+        .bind(invited_by)
+        .execute(&mut **transaction)
+        .await
+        .unwrap();
+        id
+    }
+
+    async fn finalize_day(
+        transaction: &mut Transaction<'_, Postgres>,
+        user_id: &Uuid,
+        day: NaiveDate,
+        uptime: f64,
+    ) {
+        sqlx::query(
+            "INSERT INTO daily_stats (id, user_id, tasks_count, status, day, created_at, uptime, perk_multiplier)
+            VALUES ($1, $2, 0, 'Finalized', $3, now(), $4, 1.0)",
+        )
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(day)
+        .bind(uptime)
+        .execute(&mut **transaction)
+        .await
+        .unwrap();
+    }
+
+    async fn candidates(
+        transaction: &mut Transaction<'_, Postgres>,
+        level: i32,
+        referee_id: &Uuid,
+    ) -> Vec<ReferralCandidate> {
+        let since = Utc::now().date_naive() - Duration::days(7);
+        get_referral_candidates(transaction, level, since, 2, 100.0, i64::MAX)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|candidate| candidate.referee_id == *referee_id)
+            .collect()
+    }
+
+    fn email() -> String {
+        format!("{}@example.com", Uuid::new_v4().simple())
+    }
+
+    #[tokio::test]
+    async fn days_are_paid_once_the_referee_is_active_enough() {
+        load_dotenv();
+        let pool = write_pool(None).await;
+        let mut transaction = pool.begin().await.unwrap();
+        let today = Utc::now().date_naive();
+        let inviter = create_user(&mut transaction, &email(), None).await;
+        let referee = create_user(&mut transaction, &email(), Some(inviter)).await;
+        finalize_day(&mut transaction, &referee, today - Duration::days(3), 200.0).await;
+        finalize_day(&mut transaction, &referee, today - Duration::days(2), 50.0).await;
+        finalize_day(&mut transaction, &referee, today - Duration::days(1), 200.0).await;
+
+        let paid = candidates(&mut transaction, 1, &referee).await;
+        assert_eq!(
+            paid.iter().map(|c| c.day).collect::<Vec<_>>(),
+            vec![today - Duration::days(1)]
+        );
+        assert_eq!(paid[0].user_id, inviter);
+        assert!(!paid[0].overlap);
+        assert!(candidates(&mut transaction, 2, &referee).await.is_empty());
+
+        sqlx::query(
+            "INSERT INTO referral_earnings
+            (user_id, referee_id, day, level, referee_points, percentage, points)
+            VALUES ($1, $2, $3, 1, 0, 10, 0)",
+        )
+        .bind(inviter)
+        .bind(referee)
+        .bind(today - Duration::days(1))
+        .execute(&mut *transaction)
+        .await
+        .unwrap();
+        assert!(candidates(&mut transaction, 1, &referee).await.is_empty());
+        transaction.rollback().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn overlap_anywhere_in_the_chain_is_a_self_referral() {
+        load_dotenv();
+        let pool = write_pool(None).await;
+        let mut transaction = pool.begin().await.unwrap();
+        let today = Utc::now().date_naive();
+        let mailbox = Uuid::new_v4().simple().to_string();
+        let inviter = create_user(&mut transaction, &format!("{}@gmail.com", mailbox), None).await;
+        let parent = create_user(
+            &mut transaction,
+            &format!("{}.{}+alt@Gmail.com", &mailbox[..4], &mailbox[4..]),
+            Some(inviter),
+        )
+        .await;
+        let referee = create_user(&mut transaction, &email(), Some(parent)).await;
+        for days in 1..=2 {
+            finalize_day(
+                &mut transaction,
+                &referee,
+                today - Duration::days(days),
+                200.0,
+            )
+            .await;
+        }
+
+        let level_1 = candidates(&mut transaction, 1, &referee).await;
+        assert_eq!(level_1.len(), 1);
+        assert_eq!(level_1[0].user_id, parent);
+        assert!(!level_1[0].overlap);
+        let level_2 = candidates(&mut transaction, 2, &referee).await;
+        assert_eq!(level_2.len(), 1);
+        assert_eq!(level_2[0].user_id, inviter);
+        assert!(level_2[0].overlap);
+        transaction.rollback().await.unwrap();
+    }
+}
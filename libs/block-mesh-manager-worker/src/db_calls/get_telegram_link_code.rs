@@ -0,0 +1,20 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(
+    name = "get_telegram_link_code",
+    skip(transaction),
+    level = "trace",
+    err
+)]
+pub(crate) async fn get_telegram_link_code(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<Option<String>> {
+    Ok(sqlx::query_scalar!(
+        r#"SELECT link_code FROM telegram_links WHERE user_id = $1"#,
+        user_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await?)
+}
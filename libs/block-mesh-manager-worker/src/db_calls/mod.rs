@@ -2,8 +2,34 @@ pub mod bulk_delete_old_tasks;
 pub mod bulk_finalize;
 pub mod bulk_task_bonus;
 pub mod bulk_uptime_bonus;
+pub mod claim_cron_job_trigger;
+pub mod claim_due_deletion;
+pub mod claim_pending_emails;
+pub mod claim_pending_exports;
+pub mod create_cron_job_run;
+pub mod create_probe_task;
+pub mod create_referral_earnings;
 pub mod create_server_user;
 pub mod create_task;
-pub mod get_all_rpcs;
+pub mod delete_old_probe_results;
+pub mod delete_orphan_blobs;
+pub mod delete_user_data;
+pub mod enqueue_email;
+pub mod export_user_rows;
+pub mod fail_stale_encrypted_tasks;
+pub mod finish_cron_job_run;
+pub mod get_due_probes;
+pub mod get_email_suppression;
 pub mod get_or_create_analytics;
+pub mod get_perk_effects;
+pub mod get_probe_breaches;
+pub mod get_referral_candidates;
+pub mod get_telegram_link_code;
+pub mod perk_rules;
+pub mod prune_aggregate_events;
+pub mod snapshot_daily_perks;
+pub mod touch_probe;
 pub mod touch_users_ip;
+pub mod try_cron_job_lock;
+pub mod update_account_export;
+pub mod update_email_outbox;
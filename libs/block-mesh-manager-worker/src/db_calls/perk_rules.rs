@@ -0,0 +1,239 @@
+use block_mesh_manager_database_domain::domain::perk_definition::PerkDefinition;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Enabled catalog perks the worker grants by rule, inside their validity window.
+#[tracing::instrument(name = "get_rule_perk_definitions", skip_all, level = "trace", err)]
+pub(crate) async fn get_rule_perk_definitions(
+    transaction: &mut Transaction<'_, Postgres>,
+) -> anyhow::Result<Vec<PerkDefinition>> {
+    let definitions = sqlx::query_as!(
+        PerkDefinition,
+        r#"
+        SELECT
+        name, multiplier, one_time_bonus, stacking_group, stacking, max_multiplier, valid_from,
+        valid_until, duration_days, condition, condition_threshold, enabled, created_at, updated_at
+        FROM perk_definitions
+        WHERE enabled
+        AND condition IS NOT NULL
+        AND (valid_from IS NULL OR valid_from <= now())
+        AND (valid_until IS NULL OR valid_until > now())
+        "#
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(definitions)
+}
+
+/// Users without an active perk that reported uptime on `days` consecutive days recently.
+#[tracing::instrument(
+    name = "get_users_with_node_streak",
+    skip(transaction),
+    level = "trace",
+    err
+)]
+pub(crate) async fn get_users_with_node_streak(
+    transaction: &mut Transaction<'_, Postgres>,
+    perk_name: &str,
+    days: i64,
+    limit: i64,
+) -> anyhow::Result<Vec<Uuid>> {
+    let users = sqlx::query_scalar!(
+        r#"
+        SELECT streaks.user_id AS "user_id!"
+        FROM (
+            SELECT
+            user_id,
+            day - (ROW_NUMBER() OVER (PARTITION BY user_id ORDER BY day))::INTEGER AS streak
+            FROM daily_stats
+            WHERE uptime > 0 AND day >= CURRENT_DATE - ($2::BIGINT * 2)::INTEGER
+        ) streaks
+        WHERE NOT EXISTS (
+            SELECT 1 FROM perks WHERE perks.user_id = streaks.user_id AND perks.name = $1
+            AND (perks.expires_at IS NULL OR perks.expires_at > now())
+        )
+        GROUP BY streaks.user_id, streaks.streak
+        HAVING COUNT(*) >= $2
+        LIMIT $3
+        "#,
+        perk_name,
+        days,
+        limit
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(users)
+}
+
+/// Users without an active perk whose task aggregate reached `tasks`.
+#[tracing::instrument(
+    name = "get_users_with_completed_tasks",
+    skip(transaction),
+    level = "trace",
+    err
+)]
+pub(crate) async fn get_users_with_completed_tasks(
+    transaction: &mut Transaction<'_, Postgres>,
+    perk_name: &str,
+    tasks: i64,
+    limit: i64,
+) -> anyhow::Result<Vec<Uuid>> {
+    let users = sqlx::query_scalar!(
+        r#"
+        SELECT aggregates.user_id
+        FROM aggregates
+        WHERE aggregates.name = 'Tasks'
+        AND (aggregates.value #>> '{}')::FLOAT8 >= $2
+        AND NOT EXISTS (
+            SELECT 1 FROM perks WHERE perks.user_id = aggregates.user_id AND perks.name = $1
+            AND (perks.expires_at IS NULL OR perks.expires_at > now())
+        )
+        LIMIT $3
+        "#,
+        perk_name,
+        tasks as f64,
+        limit
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(users)
+}
+
+/// Grants the catalog perk, or renews it when the user's one has expired. It expires after the
+/// definition's `duration_days`.
+#[tracing::instrument(
+    name = "grant_perk",
+    skip(transaction, definition, user_ids),
+    level = "trace",
+    ret,
+    err
+)]
+pub(crate) async fn grant_perk(
+    transaction: &mut Transaction<'_, Postgres>,
+    definition: &PerkDefinition,
+    user_ids: &[Uuid],
+) -> anyhow::Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO perks
+        (id, user_id, created_at, name, multiplier, one_time_bonus, data, updated_at, starts_at, expires_at)
+        SELECT
+        gen_random_uuid(), user_id, now(), $2, $3, $4, '{}', now(), now(),
+        now() + make_interval(days => $5)
+        FROM UNNEST($1::UUID[]) AS user_id
+        ON CONFLICT (user_id, name) DO UPDATE SET
+        multiplier = EXCLUDED.multiplier,
+        one_time_bonus = EXCLUDED.one_time_bonus,
+        updated_at = EXCLUDED.updated_at,
+        starts_at = EXCLUDED.starts_at,
+        expires_at = EXCLUDED.expires_at
+        WHERE perks.expires_at <= now()
+        "#,
+        user_ids,
+        definition.name,
+        definition.multiplier,
+        definition.one_time_bonus,
+        definition.duration_days
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use block_mesh_common::env::load_dotenv::load_dotenv;
+    use chrono::{DateTime, Duration, Utc};
+    use database_utils::utils::connection::write_pool::write_pool;
+
+    fn definition(name: &str) -> PerkDefinition {
+        PerkDefinition {
+            name: name.to_string(),
+            multiplier: 1.5,
+            one_time_bonus: 0.0,
+            stacking_group: None,
+            stacking: "Multiply".to_string(),
+            max_multiplier: None,
+            valid_from: None,
+            valid_until: None,
+            duration_days: Some(7),
+            condition: Some("CompletedTasks".to_string()),
+            condition_threshold: Some(10),
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    async fn expires_at(
+        transaction: &mut Transaction<'_, Postgres>,
+        user_id: &Uuid,
+        name: &str,
+    ) -> DateTime<Utc> {
+        sqlx::query_scalar("SELECT expires_at FROM perks WHERE user_id = $1 AND name = $2")
+            .bind(user_id)
+            .bind(name)
+            .fetch_one(&mut **transaction)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn expired_perks_are_granted_again() {
+        load_dotenv();
+        let pool = write_pool(None).await;
+        let mut transaction = pool.begin().await.unwrap();
+        let user_id = Uuid::new_v4();
+        let name = format!("perk-{}", user_id.simple());
+        sqlx::query(
+            "INSERT INTO users (id, email, password, created_at) VALUES ($1, $2, 'password', now())",
+        )
+        .bind(user_id)
+        .bind(format!("{}@example.com", user_id.simple()))
+        .execute(&mut *transaction)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO aggregates (id, user_id, name, value, created_at, updated_at)
+            VALUES ($1, $2, 'Tasks', '20', now(), now())",
+        )
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .execute(&mut *transaction)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO perks (id, user_id, created_at, name, multiplier, one_time_bonus, data, updated_at, starts_at, expires_at)
+            VALUES ($1, $2, now(), $3, 1.5, 0, '{}', now(), now() - interval '8 days', now() - interval '1 day')",
+        )
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(&name)
+        .execute(&mut *transaction)
+        .await
+        .unwrap();
+
+        let users = get_users_with_completed_tasks(&mut transaction, &name, 10, i64::MAX)
+            .await
+            .unwrap();
+        assert!(users.contains(&user_id));
+        let granted = grant_perk(&mut transaction, &definition(&name), &[user_id])
+            .await
+            .unwrap();
+        assert_eq!(granted, 1);
+        assert!(
+            expires_at(&mut transaction, &user_id, &name).await > Utc::now() + Duration::days(6)
+        );
+
+        let users = get_users_with_completed_tasks(&mut transaction, &name, 10, i64::MAX)
+            .await
+            .unwrap();
+        assert!(!users.contains(&user_id));
+        let granted = grant_perk(&mut transaction, &definition(&name), &[user_id])
+            .await
+            .unwrap();
+        assert_eq!(granted, 0);
+        transaction.rollback().await.unwrap();
+    }
+}
@@ -0,0 +1,212 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+
+/// Folds the events before `before` into a `Set` at `before`, the same way
+/// `rebuild_aggregates` does, for up to `limit` aggregates that still have older adds or samples.
+#[tracing::instrument(
+    name = "snapshot_aggregate_events",
+    skip(transaction),
+    ret,
+    err,
+    level = "trace"
+)]
+pub(crate) async fn snapshot_aggregate_events(
+    transaction: &mut Transaction<'_, Postgres>,
+    before: DateTime<Utc>,
+    limit: i64,
+) -> anyhow::Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        WITH
+        stale AS (
+            SELECT DISTINCT user_id, name
+            FROM aggregate_events
+            WHERE created_at < $1 AND kind <> 'Set'
+            LIMIT $2
+        ),
+        events AS (
+            SELECT aggregate_events.id, aggregate_events.user_id, aggregate_events.name,
+                aggregate_events.kind, aggregate_events.value, aggregate_events.created_at
+            FROM aggregate_events
+            JOIN stale ON stale.user_id = aggregate_events.user_id AND stale.name = aggregate_events.name
+            WHERE aggregate_events.created_at < $1
+        ),
+        last_set AS (
+            SELECT DISTINCT ON (user_id, name) user_id, name, value, created_at
+            FROM events
+            WHERE kind = 'Set'
+            ORDER BY user_id, name, created_at DESC
+        ),
+        since_set AS (
+            SELECT
+                events.user_id,
+                events.name,
+                events.kind,
+                events.value,
+                ROW_NUMBER() OVER (
+                    PARTITION BY events.user_id, events.name, events.kind
+                    ORDER BY events.created_at DESC, events.id DESC
+                ) AS age
+            FROM events
+            LEFT JOIN last_set ON last_set.user_id = events.user_id AND last_set.name = events.name
+            WHERE
+                events.kind <> 'Set'
+                AND (last_set.created_at IS NULL OR events.created_at >= last_set.created_at)
+        ),
+        folded AS (
+            SELECT
+                stale.user_id,
+                stale.name,
+                COALESCE(MAX(last_set.value), 0)
+                    * power(0.5, COUNT(since_set.age) FILTER (WHERE since_set.kind = 'Sample')::double precision)
+                + COALESCE(SUM(since_set.value) FILTER (WHERE since_set.kind = 'Add'), 0)
+                + COALESCE(SUM(since_set.value * power(0.5, since_set.age::double precision)) FILTER (
+                    WHERE since_set.kind = 'Sample'
+                ), 0) AS value
+            FROM stale
+            LEFT JOIN last_set ON last_set.user_id = stale.user_id AND last_set.name = stale.name
+            LEFT JOIN since_set ON since_set.user_id = stale.user_id AND since_set.name = stale.name
+            GROUP BY stale.user_id, stale.name
+        )
+        INSERT INTO aggregate_events (user_id, name, kind, value, idempotency_key, created_at)
+        SELECT
+            user_id,
+            name,
+            'Set',
+            value,
+            'snapshot:' || user_id::text || ':' || name || ':' || (extract(epoch FROM $1::timestamptz) * 1000000)::bigint::text,
+            $1
+        FROM folded
+        ON CONFLICT (idempotency_key) DO NOTHING
+        "#,
+        before,
+        limit
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Only events a later `Set` overrides are deleted, so folding what is left gives the same value.
+#[tracing::instrument(
+    name = "delete_old_aggregate_events",
+    skip(transaction),
+    ret,
+    err,
+    level = "trace"
+)]
+pub(crate) async fn delete_old_aggregate_events(
+    transaction: &mut Transaction<'_, Postgres>,
+    before: DateTime<Utc>,
+    limit: i64,
+) -> anyhow::Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM aggregate_events WHERE id IN (
+            SELECT id
+            FROM aggregate_events
+            WHERE
+                created_at < $1
+                AND EXISTS (
+                    SELECT 1
+                    FROM aggregate_events later
+                    WHERE
+                        later.user_id = aggregate_events.user_id
+                        AND later.name = aggregate_events.name
+                        AND later.kind = 'Set'
+                        AND later.created_at > aggregate_events.created_at
+                )
+            LIMIT $2
+            FOR UPDATE SKIP LOCKED
+        )
+        "#,
+        before,
+        limit
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use block_mesh_common::env::load_dotenv::load_dotenv;
+    use block_mesh_manager_database_domain::domain::bulk_get_or_create_aggregate_by_user_and_name::bulk_get_or_create_aggregate_by_user_and_name;
+    use block_mesh_manager_database_domain::domain::rebuild_aggregates::rebuild_aggregates;
+    use chrono::Duration;
+    use database_utils::utils::connection::write_pool::write_pool;
+    use uuid::Uuid;
+
+    async fn insert_event(
+        transaction: &mut Transaction<'_, Postgres>,
+        user_id: &Uuid,
+        kind: &str,
+        value: f64,
+        days_ago: i64,
+    ) {
+        sqlx::query(
+            "INSERT INTO aggregate_events (user_id, name, kind, value, idempotency_key, created_at)
+            VALUES ($1, 'Uptime', $2, $3, $4, $5)",
+        )
+        .bind(user_id)
+        .bind(kind)
+        .bind(value)
+        .bind(Uuid::new_v4().to_string())
+        .bind(Utc::now() - Duration::days(days_ago))
+        .execute(&mut **transaction)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn pruning_keeps_the_folded_value() {
+        load_dotenv();
+        let pool = write_pool(None).await;
+        let mut transaction = pool.begin().await.unwrap();
+        let user_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO users (id, email, password, created_at) VALUES ($1, $2, 'password', now())",
+        )
+        .bind(user_id)
+        .bind(format!("{}@example.com", user_id.simple()))
+        .execute(&mut *transaction)
+        .await
+        .unwrap();
+        bulk_get_or_create_aggregate_by_user_and_name(&mut transaction, &user_id)
+            .await
+            .unwrap();
+        insert_event(&mut transaction, &user_id, "Set", 10.0, 50).await;
+        insert_event(&mut transaction, &user_id, "Add", 5.0, 40).await;
+        insert_event(&mut transaction, &user_id, "Add", 7.0, 35).await;
+        insert_event(&mut transaction, &user_id, "Add", 1.0, 1).await;
+
+        let before = Utc::now() - Duration::days(30);
+        snapshot_aggregate_events(&mut transaction, before, i64::MAX)
+            .await
+            .unwrap();
+        delete_old_aggregate_events(&mut transaction, before, i64::MAX)
+            .await
+            .unwrap();
+        let left: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM aggregate_events WHERE user_id = $1")
+                .bind(user_id)
+                .fetch_one(&mut *transaction)
+                .await
+                .unwrap();
+        assert_eq!(left, 2);
+
+        rebuild_aggregates(&mut transaction, Some(user_id), None)
+            .await
+            .unwrap();
+        let value: serde_json::Value = sqlx::query_scalar(
+            "SELECT value FROM aggregates WHERE user_id = $1 AND name = 'Uptime'",
+        )
+        .bind(user_id)
+        .fetch_one(&mut *transaction)
+        .await
+        .unwrap();
+        assert_eq!(value.as_f64(), Some(23.0));
+        transaction.rollback().await.unwrap();
+    }
+}
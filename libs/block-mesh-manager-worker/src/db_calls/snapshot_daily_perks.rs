@@ -0,0 +1,102 @@
+use chrono::NaiveDate;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub(crate) struct DailyStatWithoutPerks {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub day: NaiveDate,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct PerkHistoryRows {
+    pub user_ids: Vec<Uuid>,
+    pub days: Vec<NaiveDate>,
+    pub perk_names: Vec<String>,
+    pub multipliers: Vec<f64>,
+    pub applied: Vec<bool>,
+}
+
+/// Finalized days whose perks have not been recorded yet.
+#[tracing::instrument(
+    name = "get_daily_stats_without_perks",
+    skip(transaction),
+    level = "trace",
+    err
+)]
+pub(crate) async fn get_daily_stats_without_perks(
+    transaction: &mut Transaction<'_, Postgres>,
+    since: NaiveDate,
+    limit: i64,
+) -> anyhow::Result<Vec<DailyStatWithoutPerks>> {
+    let daily_stats = sqlx::query_as!(
+        DailyStatWithoutPerks,
+        r#"
+        SELECT id, user_id, day
+        FROM daily_stats
+        WHERE status = 'Finalized' AND perk_multiplier IS NULL AND day >= $1
+        LIMIT $2
+        "#,
+        since,
+        limit
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(daily_stats)
+}
+
+#[tracing::instrument(
+    name = "create_perk_history",
+    skip(transaction, rows),
+    level = "trace",
+    ret,
+    err
+)]
+pub(crate) async fn create_perk_history(
+    transaction: &mut Transaction<'_, Postgres>,
+    rows: &PerkHistoryRows,
+) -> anyhow::Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO perk_history (user_id, day, perk_name, multiplier, applied)
+        SELECT * FROM UNNEST($1::UUID[], $2::DATE[], $3::TEXT[], $4::FLOAT8[], $5::BOOL[])
+        ON CONFLICT (user_id, day, perk_name) DO NOTHING
+        "#,
+        &rows.user_ids,
+        &rows.days,
+        &rows.perk_names,
+        &rows.multipliers,
+        &rows.applied
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(
+    name = "update_daily_stat_perk_multipliers",
+    skip_all,
+    level = "trace",
+    ret,
+    err
+)]
+pub(crate) async fn update_daily_stat_perk_multipliers(
+    transaction: &mut Transaction<'_, Postgres>,
+    ids: &[Uuid],
+    multipliers: &[f64],
+) -> anyhow::Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE daily_stats
+        SET perk_multiplier = rows.perk_multiplier
+        FROM UNNEST($1::UUID[], $2::FLOAT8[]) AS rows(id, perk_multiplier)
+        WHERE daily_stats.id = rows.id
+        "#,
+        ids,
+        multipliers
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(result.rows_affected())
+}
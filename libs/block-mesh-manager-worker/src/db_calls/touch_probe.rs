@@ -0,0 +1,47 @@
+use chrono::Utc;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(
+    name = "touch_probe_scheduled",
+    skip(transaction),
+    level = "trace",
+    ret,
+    err
+)]
+pub(crate) async fn touch_probe_scheduled(
+    transaction: &mut Transaction<'_, Postgres>,
+    probe_id: &Uuid,
+) -> anyhow::Result<()> {
+    let now = Utc::now();
+    sqlx::query!(
+        r#"UPDATE probes SET last_scheduled_at = $1 WHERE id = $2"#,
+        now,
+        probe_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
+
+#[tracing::instrument(
+    name = "touch_probe_alerted",
+    skip(transaction),
+    level = "trace",
+    ret,
+    err
+)]
+pub(crate) async fn touch_probe_alerted(
+    transaction: &mut Transaction<'_, Postgres>,
+    probe_id: &Uuid,
+) -> anyhow::Result<()> {
+    let now = Utc::now();
+    sqlx::query!(
+        r#"UPDATE probes SET last_alerted_at = $1 WHERE id = $2"#,
+        now,
+        probe_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,15 @@
+use sqlx::{Postgres, Transaction};
+
+#[tracing::instrument(name = "try_cron_job_lock", skip(transaction), err, level = "trace")]
+pub async fn try_cron_job_lock(
+    transaction: &mut Transaction<'_, Postgres>,
+    name: &str,
+) -> anyhow::Result<bool> {
+    let locked = sqlx::query_scalar!(
+        r#"SELECT pg_try_advisory_xact_lock(hashtext('cron_jobs:' || $1)) AS "locked!""#,
+        name
+    )
+    .fetch_one(&mut **transaction)
+    .await?;
+    Ok(locked)
+}
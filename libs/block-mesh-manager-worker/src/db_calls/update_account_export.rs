@@ -0,0 +1,93 @@
+use block_mesh_manager_database_domain::domain::account_export::AccountExportStatus;
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(
+    name = "mark_export_ready",
+    skip(transaction),
+    level = "trace",
+    ret,
+    err
+)]
+pub(crate) async fn mark_export_ready(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: &Uuid,
+    blob_key: &str,
+    size_bytes: i64,
+    expires_at: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"UPDATE account_exports
+        SET status = $2, blob_key = $3, size_bytes = $4, expires_at = $5, completed_at = now()
+        WHERE id = $1"#,
+        id,
+        AccountExportStatus::Ready.to_string(),
+        blob_key,
+        size_bytes,
+        expires_at
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
+
+#[tracing::instrument(
+    name = "mark_export_failed",
+    skip(transaction),
+    level = "trace",
+    ret,
+    err
+)]
+pub(crate) async fn mark_export_failed(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: &Uuid,
+    error: &str,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"UPDATE account_exports
+        SET status = $2, last_error = $3, completed_at = now()
+        WHERE id = $1"#,
+        id,
+        AccountExportStatus::Failed.to_string(),
+        error
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
+
+/// Drops the blob references of expired archives, the orphan sweep then removes the blobs.
+#[tracing::instrument(
+    name = "expire_account_exports",
+    skip(transaction),
+    level = "trace",
+    ret,
+    err
+)]
+pub(crate) async fn expire_account_exports(
+    transaction: &mut Transaction<'_, Postgres>,
+    limit: i64,
+) -> anyhow::Result<u64> {
+    let r = sqlx::query!(
+        r#"
+        WITH expired AS (
+            SELECT id
+            FROM account_exports
+            WHERE status = $1 AND expires_at < now()
+            LIMIT $3
+            FOR UPDATE SKIP LOCKED
+        )
+        UPDATE account_exports
+        SET status = $2, blob_key = NULL
+        FROM expired
+        WHERE account_exports.id = expired.id
+        "#,
+        AccountExportStatus::Ready.to_string(),
+        AccountExportStatus::Expired.to_string(),
+        limit
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(r.rows_affected())
+}
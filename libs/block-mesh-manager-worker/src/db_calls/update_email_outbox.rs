@@ -0,0 +1,85 @@
+use block_mesh_manager_database_domain::domain::email_outbox::EmailOutboxStatus;
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "mark_email_sent", skip(transaction), level = "trace", ret, err)]
+pub(crate) async fn mark_email_sent(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: &Uuid,
+    provider: &str,
+    message_id: &str,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"UPDATE email_outbox
+        SET status = $2, provider = $3, message_id = $4,
+            last_error = NULL, sent_at = now(), updated_at = now()
+        WHERE id = $1"#,
+        id,
+        EmailOutboxStatus::Sent.to_string(),
+        provider,
+        message_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
+
+/// Records why the claimed attempt failed, `next_attempt_at` is `None` once the email is
+/// given up on.
+#[tracing::instrument(
+    name = "mark_email_failed",
+    skip(transaction),
+    level = "trace",
+    ret,
+    err
+)]
+pub(crate) async fn mark_email_failed(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: &Uuid,
+    error: &str,
+    next_attempt_at: Option<DateTime<Utc>>,
+) -> anyhow::Result<()> {
+    let status = match next_attempt_at {
+        Some(_) => EmailOutboxStatus::Pending,
+        None => EmailOutboxStatus::Failed,
+    };
+    sqlx::query!(
+        r#"UPDATE email_outbox
+        SET status = $2, last_error = $3,
+            next_attempt_at = COALESCE($4, next_attempt_at), updated_at = now()
+        WHERE id = $1"#,
+        id,
+        status.to_string(),
+        error,
+        next_attempt_at
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
+
+#[tracing::instrument(
+    name = "mark_email_suppressed",
+    skip(transaction),
+    level = "trace",
+    ret,
+    err
+)]
+pub(crate) async fn mark_email_suppressed(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: &Uuid,
+    reason: &str,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"UPDATE email_outbox
+        SET status = $2, last_error = $3, updated_at = now()
+        WHERE id = $1"#,
+        id,
+        EmailOutboxStatus::Suppressed.to_string(),
+        reason
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
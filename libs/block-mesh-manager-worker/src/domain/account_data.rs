@@ -0,0 +1,284 @@
+use block_mesh_common::constants::{BLOCK_MESH_DATA_SINK, BLOCK_MESH_TG_PRIVACY_BOT};
+use std::env;
+use uuid::Uuid;
+
+/// One JSON file per entry of the export archive, every query takes the user id as `$1`.
+/// Secrets such as password hashes, token values and 2FA seeds are left out.
+pub const EXPORT_SECTIONS: &[(&str, &str)] = &[
+    (
+        "user",
+        "SELECT id, email, wallet_address, created_at, role, invited_by, verified_email, status
+        FROM users WHERE id = $1",
+    ),
+    (
+        "api_tokens",
+        "SELECT id, name, status, scopes, created_at, expires_at, last_used_at, last_used_ip, is_primary
+        FROM api_tokens WHERE user_id = $1",
+    ),
+    (
+        "user_wallets",
+        "SELECT * FROM user_wallets WHERE user_id = $1",
+    ),
+    (
+        "wallet_perk_grants",
+        "SELECT * FROM wallet_perk_grants WHERE user_id = $1",
+    ),
+    (
+        "invite_codes",
+        "SELECT * FROM invite_codes WHERE user_id = $1",
+    ),
+    (
+        "invite_code_signups",
+        "SELECT invite_codes.invite_code, invite_campaigns.name AS campaign, invite_code_signups.created_at
+        FROM invite_code_signups
+        LEFT JOIN invite_codes ON invite_codes.id = invite_code_signups.invite_code_id
+        LEFT JOIN invite_campaigns ON invite_campaigns.id = invite_code_signups.campaign_id
+        WHERE invite_code_signups.user_id = $1",
+    ),
+    ("perks", "SELECT * FROM perks WHERE user_id = $1"),
+    (
+        "perk_history",
+        "SELECT day, perk_name, multiplier, applied FROM perk_history WHERE user_id = $1",
+    ),
+    (
+        "call_to_actions",
+        "SELECT * FROM call_to_actions WHERE user_id = $1",
+    ),
+    (
+        "daily_stats",
+        "SELECT daily_stats.*, COALESCE((
+            SELECT SUM(referral_earnings.points) FROM referral_earnings
+            WHERE referral_earnings.user_id = daily_stats.user_id
+            AND referral_earnings.day = daily_stats.day
+            AND referral_earnings.status = 'Credited'
+        ), 0) AS referral_points
+        FROM daily_stats WHERE user_id = $1",
+    ),
+    (
+        "referral_earnings",
+        "SELECT day, level, referee_points, percentage, points, status, created_at
+        FROM referral_earnings WHERE user_id = $1",
+    ),
+    ("aggregates", "SELECT * FROM aggregates WHERE user_id = $1"),
+    (
+        "aggregate_events",
+        "SELECT * FROM aggregate_events WHERE user_id = $1",
+    ),
+    ("analytics", "SELECT * FROM analytics WHERE user_id = $1"),
+    (
+        "users_ip",
+        "SELECT users_ip.created_at, users_ip.updated_at, ip_addresses.ip, ip_addresses.country,
+        ip_addresses.city, ip_addresses.region, ip_addresses.timezone, ip_addresses.isp
+        FROM users_ip JOIN ip_addresses ON ip_addresses.id = users_ip.ip_id
+        WHERE users_ip.user_id = $1",
+    ),
+    (
+        "uptime_reports",
+        "SELECT id, created_at, ip, latitude, longitude, country, city, region, timezone, isp
+        FROM uptime_reports WHERE user_id = $1",
+    ),
+    (
+        "bandwidth_reports",
+        "SELECT * FROM bandwidth_reports WHERE user_id = $1",
+    ),
+    (
+        "tasks",
+        "SELECT id, user_id, assigned_user_id, url, method, status, response_code, response_time,
+        created_at, country, region, asn, colo, ip, probe_id
+        FROM tasks WHERE user_id = $1 OR assigned_user_id = $1",
+    ),
+    (
+        "task_retention",
+        "SELECT * FROM task_retention WHERE user_id = $1",
+    ),
+    ("probes", "SELECT * FROM probes WHERE user_id = $1"),
+    (
+        "probe_results",
+        "SELECT probe_results.* FROM probe_results
+        JOIN probes ON probes.id = probe_results.probe_id
+        WHERE probes.user_id = $1",
+    ),
+    (
+        "node_keys",
+        "SELECT * FROM node_keys WHERE user_id = $1",
+    ),
+    (
+        "device_authorizations",
+        "SELECT id, device_name, scopes, status, expires_at, created_at
+        FROM device_authorizations WHERE user_id = $1",
+    ),
+    (
+        "two_factor",
+        "SELECT enabled_at, created_at FROM two_factor_secrets WHERE user_id = $1",
+    ),
+    (
+        "notification_preferences",
+        "SELECT probe_alerts, locale, created_at, updated_at
+        FROM notification_preferences WHERE user_id = $1",
+    ),
+    (
+        "emails",
+        "SELECT id, email_address, template, status, created_at, sent_at
+        FROM email_outbox WHERE user_id = $1",
+    ),
+];
+
+/// Rows removed when an account is deleted, in an order that satisfies the foreign keys.
+/// `tasks`, `nonces`, `account_exports` and `users` need more than a plain delete.
+pub const DELETED_USER_TABLES: &[&str] = &[
+    "device_authorizations",
+    "api_tokens",
+    "two_factor_recovery_codes",
+    "two_factor_remembered_devices",
+    "two_factor_secrets",
+    "user_wallets",
+    "wallet_perk_grants",
+    "node_keys",
+    "notification_preferences",
+    "email_outbox",
+    "probes",
+    "task_retention",
+    "aggregate_events",
+    "aggregates",
+    "analytics",
+    "daily_stats",
+    "referral_earnings",
+    "uptime_reports",
+    "bandwidth_reports",
+    "users_ip",
+    "perk_history",
+    "perks",
+    "invite_code_signups",
+    "invite_codes",
+    "call_to_actions",
+    "telegram_links",
+];
+
+/// The `users` row is kept so ids other users point at, like `invited_by`, stay valid.
+pub fn anonymised_email(user_id: &Uuid) -> String {
+    format!("deleted-{}@deleted.blockmesh.xyz", user_id)
+}
+
+/// Matches the manager's session keys, `{email}-{password or api token}`, with the glob
+/// characters of the email escaped so the pattern never reaches other accounts.
+pub fn cached_session_pattern(email: &str) -> String {
+    let mut pattern = String::with_capacity(email.len() + 2);
+    for c in email.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push_str("-*");
+    pattern
+}
+
+pub fn data_sink_url() -> String {
+    env::var("DATA_SINK_URL").unwrap_or_else(|_| BLOCK_MESH_DATA_SINK.to_string())
+}
+
+pub fn tg_privacy_bot_url() -> String {
+    env::var("TG_PRIVACY_BOT_URL").unwrap_or_else(|_| BLOCK_MESH_TG_PRIVACY_BOT.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use std::fs;
+
+    /// Cleared by `delete_user_data` with more than a plain delete.
+    const SPECIALLY_DELETED: &[&str] = &["tasks", "nonces", "account_exports", "account_deletions"];
+    /// Secrets, or the bookkeeping of exports and deletions themselves.
+    const NOT_EXPORTED: &[&str] = &[
+        "nonces",
+        "telegram_links",
+        "two_factor_recovery_codes",
+        "two_factor_remembered_devices",
+        "account_exports",
+        "account_deletions",
+    ];
+
+    fn table_name<'a>(words: &[&'a str]) -> Option<&'a str> {
+        words
+            .iter()
+            .find(|word| !matches!(**word, "if" | "not" | "exists"))
+            .map(|word| word.trim_end_matches(['(', ';']))
+    }
+
+    /// Every table the migrations create with a `user_id` foreign key to `users`.
+    fn tables_with_user_fk() -> BTreeSet<String> {
+        let dir = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../block-mesh-manager/migrations"
+        );
+        let mut files: Vec<_> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        files.sort();
+        let mut tables = BTreeSet::new();
+        for file in files {
+            let sql = fs::read_to_string(file).unwrap().to_lowercase();
+            let mut current = None;
+            for line in sql.lines() {
+                let words: Vec<&str> = line.split_whitespace().collect();
+                match words.as_slice() {
+                    ["create", "table", rest @ ..] => current = table_name(rest),
+                    ["drop", "table", rest @ ..] => {
+                        if let Some(table) = table_name(rest) {
+                            tables.remove(table);
+                        }
+                    }
+                    _ => {}
+                }
+                if line.contains("foreign key (user_id) references users") {
+                    tables.insert(current.expect("foreign key outside of a table").to_string());
+                }
+            }
+        }
+        tables
+    }
+
+    #[test]
+    fn cached_session_pattern_escapes_globs() {
+        assert_eq!(cached_session_pattern("a@b.xyz"), "a@b.xyz-*");
+        assert_eq!(
+            cached_session_pattern("a*[x]?\\@b.xyz"),
+            "a\\*\\[x\\]\\?\\\\@b.xyz-*"
+        );
+    }
+
+    #[test]
+    fn deleted_user_tables_cover_every_user_table() {
+        let tables = tables_with_user_fk();
+        assert!(tables.contains("api_tokens"));
+        let missing: Vec<_> = tables
+            .iter()
+            .filter(|table| {
+                !DELETED_USER_TABLES.contains(&table.as_str())
+                    && !SPECIALLY_DELETED.contains(&table.as_str())
+            })
+            .collect();
+        assert!(missing.is_empty(), "not deleted: {:?}", missing);
+    }
+
+    #[test]
+    fn export_sections_cover_every_user_table() {
+        let queries: Vec<String> = EXPORT_SECTIONS
+            .iter()
+            .map(|(_, query)| query.split_whitespace().collect::<Vec<_>>().join(" "))
+            .collect();
+        let missing: Vec<_> = tables_with_user_fk()
+            .into_iter()
+            .filter(|table| !NOT_EXPORTED.contains(&table.as_str()))
+            .filter(|table| {
+                !queries.iter().any(|query| {
+                    query.contains(&format!("FROM {} ", table))
+                        || query.contains(&format!("JOIN {} ", table))
+                })
+            })
+            .collect();
+        assert!(missing.is_empty(), "not exported: {:?}", missing);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::cron_jobs::account_deletions_cron::account_deletions_cron;
+use crate::cron_jobs::account_exports_cron::account_exports_cron;
+use crate::cron_jobs::bulk_task_bonus_cron::bulk_task_bonus_cron;
+use crate::cron_jobs::bulk_uptime_bonus_cron::bulk_uptime_bonus_cron;
+use crate::cron_jobs::clean_old_probe_results::clean_old_probe_results;
+use crate::cron_jobs::clean_old_tasks::clean_old_tasks;
+use crate::cron_jobs::clean_orphan_blobs::clean_orphan_blobs;
+use crate::cron_jobs::email_outbox_cron::email_outbox_cron;
+use crate::cron_jobs::finalize_daily_cron::finalize_daily_cron;
+use crate::cron_jobs::perk_rules_cron::perk_rules_cron;
+use crate::cron_jobs::probe_alerts_cron::probe_alerts_cron;
+use crate::cron_jobs::probe_cron::create_probe_tasks;
+use crate::cron_jobs::prune_aggregate_events_cron::prune_aggregate_events_cron;
+use crate::cron_jobs::special_task_cron::create_special_task_cron;
+use sqlx::PgPool;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CronJobName {
+    FinalizeDaily,
+    Probes,
+    ProbeAlerts,
+    SpecialTasks,
+    BulkUptimeBonus,
+    BulkTaskBonus,
+    CleanOldTasks,
+    CleanOldProbeResults,
+    CleanOrphanBlobs,
+    EmailOutbox,
+    AccountExports,
+    AccountDeletions,
+    PerkRules,
+    PruneAggregateEvents,
+    Invalid,
+}
+
+impl CronJobName {
+    pub async fn run(&self, pool: &PgPool) -> anyhow::Result<()> {
+        match self {
+            Self::FinalizeDaily => finalize_daily_cron(pool).await,
+            Self::Probes => create_probe_tasks(pool).await,
+            Self::ProbeAlerts => probe_alerts_cron(pool).await,
+            Self::SpecialTasks => create_special_task_cron(pool).await,
+            Self::BulkUptimeBonus => bulk_uptime_bonus_cron(pool).await,
+            Self::BulkTaskBonus => bulk_task_bonus_cron(pool).await,
+            Self::CleanOldTasks => clean_old_tasks(pool).await,
+            Self::CleanOldProbeResults => clean_old_probe_results(pool).await,
+            Self::CleanOrphanBlobs => clean_orphan_blobs(pool).await,
+            Self::EmailOutbox => email_outbox_cron(pool).await,
+            Self::AccountExports => account_exports_cron(pool).await,
+            Self::AccountDeletions => account_deletions_cron(pool).await,
+            Self::PerkRules => perk_rules_cron(pool).await,
+            Self::PruneAggregateEvents => prune_aggregate_events_cron(pool).await,
+            Self::Invalid => Err(anyhow::anyhow!("Invalid cron job")),
+        }
+    }
+}
+
+impl Display for CronJobName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FinalizeDaily => write!(f, "FinalizeDaily"),
+            Self::Probes => write!(f, "Probes"),
+            Self::ProbeAlerts => write!(f, "ProbeAlerts"),
+            Self::SpecialTasks => write!(f, "SpecialTasks"),
+            Self::BulkUptimeBonus => write!(f, "BulkUptimeBonus"),
+            Self::BulkTaskBonus => write!(f, "BulkTaskBonus"),
+            Self::CleanOldTasks => write!(f, "CleanOldTasks"),
+            Self::CleanOldProbeResults => write!(f, "CleanOldProbeResults"),
+            Self::CleanOrphanBlobs => write!(f, "CleanOrphanBlobs"),
+            Self::EmailOutbox => write!(f, "EmailOutbox"),
+            Self::AccountExports => write!(f, "AccountExports"),
+            Self::AccountDeletions => write!(f, "AccountDeletions"),
+            Self::PerkRules => write!(f, "PerkRules"),
+            Self::PruneAggregateEvents => write!(f, "PruneAggregateEvents"),
+            Self::Invalid => write!(f, "Invalid"),
+        }
+    }
+}
+
+impl From<&str> for CronJobName {
+    fn from(s: &str) -> Self {
+        match s {
+            "FinalizeDaily" => Self::FinalizeDaily,
+            "Probes" => Self::Probes,
+            "ProbeAlerts" => Self::ProbeAlerts,
+            "SpecialTasks" => Self::SpecialTasks,
+            "BulkUptimeBonus" => Self::BulkUptimeBonus,
+            "BulkTaskBonus" => Self::BulkTaskBonus,
+            "CleanOldTasks" => Self::CleanOldTasks,
+            "CleanOldProbeResults" => Self::CleanOldProbeResults,
+            "CleanOrphanBlobs" => Self::CleanOrphanBlobs,
+            "EmailOutbox" => Self::EmailOutbox,
+            "AccountExports" => Self::AccountExports,
+            "AccountDeletions" => Self::AccountDeletions,
+            "PerkRules" => Self::PerkRules,
+            "PruneAggregateEvents" => Self::PruneAggregateEvents,
+            _ => Self::Invalid,
+        }
+    }
+}
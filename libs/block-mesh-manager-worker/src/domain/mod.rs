@@ -1 +1,3 @@
-pub mod rpc;
+pub mod account_data;
+pub mod cron_job_name;
+pub mod referral_program;
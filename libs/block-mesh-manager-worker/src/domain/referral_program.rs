@@ -0,0 +1,110 @@
+use block_mesh_manager_database_domain::domain::referral_earning::ReferralEarningStatus;
+use std::env;
+
+/// Referral payout settings, a level with a percentage of 0 is not paid.
+#[derive(Debug, Clone)]
+pub struct ReferralProgram {
+    /// Percentage of the referee's daily points per level, starting at level 1.
+    pub percentages: [f64; 2],
+    /// Days the referee must have reached `min_daily_uptime` on before anything is paid.
+    pub min_active_days: i64,
+    pub min_daily_uptime: f64,
+    /// How many finalized days back are looked at, days already paid are skipped.
+    pub window_days: i64,
+    pub batch: i64,
+}
+
+impl ReferralProgram {
+    pub fn from_env() -> Self {
+        Self {
+            percentages: [
+                env::var("REFERRAL_LEVEL_1_PERCENT")
+                    .unwrap_or("10".to_string())
+                    .parse()
+                    .unwrap_or(10.0),
+                env::var("REFERRAL_LEVEL_2_PERCENT")
+                    .unwrap_or("0".to_string())
+                    .parse()
+                    .unwrap_or(0.0),
+            ],
+            min_active_days: env::var("REFERRAL_MIN_ACTIVE_DAYS")
+                .unwrap_or("3".to_string())
+                .parse()
+                .unwrap_or(3),
+            min_daily_uptime: env::var("REFERRAL_MIN_DAILY_UPTIME")
+                .unwrap_or("3600".to_string())
+                .parse()
+                .unwrap_or(3600.0),
+            window_days: env::var("REFERRAL_WINDOW_DAYS")
+                .unwrap_or("7".to_string())
+                .parse()
+                .unwrap_or(7),
+            batch: env::var("REFERRAL_EARNINGS_BATCH")
+                .unwrap_or("10000".to_string())
+                .parse()
+                .unwrap_or(10000),
+        }
+    }
+
+    /// Enabled levels with their percentage.
+    pub fn levels(&self) -> impl Iterator<Item = (i32, f64)> + '_ {
+        self.percentages
+            .iter()
+            .enumerate()
+            .filter(|(_, percentage)| **percentage > 0.0)
+            .map(|(index, percentage)| (index as i32 + 1, *percentage))
+    }
+
+    /// What the inviter is credited at `percentage` of `referee_points`, nothing when the
+    /// pair looks like the same person.
+    pub fn earning(
+        percentage: f64,
+        referee_points: f64,
+        overlap: bool,
+    ) -> (f64, ReferralEarningStatus) {
+        if overlap {
+            (0.0, ReferralEarningStatus::SelfReferral)
+        } else {
+            (
+                referee_points * percentage / 100.0,
+                ReferralEarningStatus::Credited,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program(percentages: [f64; 2]) -> ReferralProgram {
+        ReferralProgram {
+            percentages,
+            min_active_days: 3,
+            min_daily_uptime: 3600.0,
+            window_days: 7,
+            batch: 100,
+        }
+    }
+
+    #[test]
+    fn levels_skip_unpaid_levels() {
+        let levels = |percentages| program(percentages).levels().collect::<Vec<_>>();
+        assert_eq!(levels([10.0, 0.0]), vec![(1, 10.0)]);
+        assert_eq!(levels([10.0, 5.0]), vec![(1, 10.0), (2, 5.0)]);
+        assert_eq!(levels([0.0, 5.0]), vec![(2, 5.0)]);
+        assert_eq!(levels([-1.0, 0.0]), vec![]);
+    }
+
+    #[test]
+    fn earning_is_a_share_of_the_referee_points() {
+        assert_eq!(
+            ReferralProgram::earning(10.0, 250.0, false),
+            (25.0, ReferralEarningStatus::Credited)
+        );
+        assert_eq!(
+            ReferralProgram::earning(10.0, 250.0, true),
+            (0.0, ReferralEarningStatus::SelfReferral)
+        );
+    }
+}
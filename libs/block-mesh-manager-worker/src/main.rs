@@ -6,6 +6,7 @@ use block_mesh_common::env::load_dotenv::load_dotenv;
 use database_utils::utils::connection::channel_pool::channel_pool;
 use database_utils::utils::connection::unlimited_pool::unlimited_pool;
 use database_utils::utils::connection::write_pool::write_pool;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
 use logger_general::tracing::setup_tracing_stdout_only_with_sentry;
 use serde_json::Value;
 use std::net::SocketAddr;
@@ -22,22 +23,20 @@ mod domain;
 mod errors;
 mod pg_listener;
 mod routes;
+mod scheduler;
 mod utils;
 
 use crate::call_backs::send_to_rx::send_to_rx;
-use crate::cron_jobs::bulk_task_bonus_cron::bulk_task_bonus_cron;
-use crate::cron_jobs::bulk_uptime_bonus_cron::bulk_uptime_bonus_cron;
-use crate::cron_jobs::clean_old_tasks::clean_old_tasks;
-use crate::cron_jobs::finalize_daily_cron::finalize_daily_cron;
-use crate::cron_jobs::rpc_cron::rpc_worker_loop;
-use crate::cron_jobs::special_task_cron::special_worker_loop;
 use crate::db_aggregators::add_to_aggregates_aggregator::add_to_aggregates_aggregator;
 use crate::db_aggregators::aggregates_aggregator::aggregates_aggregator;
 use crate::db_aggregators::analytics_aggregator::analytics_aggregator;
 use crate::db_aggregators::daily_stats_aggregator::daily_stats_aggregator;
 use crate::db_aggregators::joiner_loop::joiner_loop;
+use crate::db_aggregators::sample_aggregates_aggregator::sample_aggregates_aggregator;
 use crate::db_aggregators::set_to_aggregates_aggregator::set_to_aggregates_aggregator;
+use crate::db_calls::create_server_user::create_server_user;
 use crate::routes::get_router;
+use crate::scheduler::scheduler_loop::scheduler_loop;
 
 pub async fn run_server(listener: TcpListener, app: Router<()>) -> std::io::Result<()> {
     axum::serve(
@@ -97,12 +96,12 @@ async fn run() -> anyhow::Result<()> {
             .unwrap_or(5000),
     );
 
-    let bulk_task_bonus_task = tokio::spawn(bulk_task_bonus_cron(un_limited_db_pool.clone()));
-    let bulk_uptime_bonus_task = tokio::spawn(bulk_uptime_bonus_cron(un_limited_db_pool));
+    if let Ok(mut transaction) = create_txn(&db_pool).await {
+        _ = create_server_user(&mut transaction).await;
+        _ = commit_txn(transaction).await;
+    }
+    let scheduler_task = tokio::spawn(scheduler_loop(un_limited_db_pool));
     let joiner_task = tokio::spawn(joiner_loop(joiner_rx));
-    let rpc_worker_task = tokio::spawn(rpc_worker_loop(db_pool.clone()));
-    let finalize_daily_stats_task = tokio::spawn(finalize_daily_cron(db_pool.clone()));
-    let delete_old_tasks_task = tokio::spawn(clean_old_tasks(db_pool.clone()));
     let channel_pool = channel_pool(Some("CHANNEL_DATABASE_URL".to_string())).await;
 
     let db_listen_task = tokio::spawn(start_listening(
@@ -131,6 +130,16 @@ async fn run() -> anyhow::Result<()> {
             .unwrap_or(300),
         5,
     ));
+    let db_aggregator_sample = tokio::spawn(sample_aggregates_aggregator(
+        joiner_tx.clone(),
+        db_pool.clone(),
+        tx.subscribe(),
+        env::var("SAMPLE_AGG_SIZE")
+            .unwrap_or("300".to_string())
+            .parse()
+            .unwrap_or(300),
+        5,
+    ));
     let db_aggregator_users_ip_task = tokio::spawn(users_ip_aggregator(
         joiner_tx.clone(),
         db_pool.clone(),
@@ -170,7 +179,6 @@ async fn run() -> anyhow::Result<()> {
             .unwrap_or(300),
         5,
     ));
-    let db_special_task = tokio::spawn(special_worker_loop(db_pool.clone()));
 
     let router = get_router();
     let cors = CorsLayer::permissive();
@@ -186,14 +194,10 @@ async fn run() -> anyhow::Result<()> {
     tokio::select! {
         o = db_aggregator_set => panic!("db_aggregator_set exit {:?}", o),
         o = db_aggregator_add => panic!("db_aggregator_add exit {:?}", o),
-        o = bulk_uptime_bonus_task => panic!("bulk_uptime_bonus_task exit {:?}", o),
-        o = bulk_task_bonus_task => panic!("bulk_task_bonus_task exit {:?}", o),
-        o = db_special_task => panic!("db_special_task exit {:?}", o),
-        o = delete_old_tasks_task => panic!("delete_old_tasks_task exit {:?}", o),
+        o = db_aggregator_sample => panic!("db_aggregator_sample exit {:?}", o),
+        o = scheduler_task => panic!("scheduler_task exit {:?}", o),
         o = joiner_task => panic!("joiner_task exit {:?}", o),
         o = server_task => panic!("server task exit {:?}", o),
-        o = finalize_daily_stats_task => panic!("finalize_daily_stats_task exit {:?}", o),
-        o = rpc_worker_task => panic!("rpc_worker_task exit {:?}", o),
         o = db_listen_task => panic!("db_listen_task exit {:?}", o),
         o = db_aggregator_users_ip_task => panic!("db_aggregator_users_ip_task exit {:?}", o),
         o = db_aggregates_aggregator_task => panic!("db_aggregates_aggregator_task exit {:?}", o),
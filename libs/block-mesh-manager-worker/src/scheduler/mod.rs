@@ -0,0 +1,2 @@
+pub mod run_cron_job;
+pub mod scheduler_loop;
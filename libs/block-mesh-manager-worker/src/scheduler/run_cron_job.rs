@@ -0,0 +1,102 @@
+use crate::db_calls::create_cron_job_run::create_cron_job_run;
+use crate::db_calls::finish_cron_job_run::finish_cron_job_run;
+use crate::db_calls::try_cron_job_lock::try_cron_job_lock;
+use crate::domain::cron_job_name::CronJobName;
+use block_mesh_manager_database_domain::domain::cron_job::{CronJobRunStatus, CronJobTrigger};
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::time::Instant;
+
+#[tracing::instrument(name = "run_cron_job", skip(pool), err)]
+pub async fn run_cron_job(
+    pool: PgPool,
+    name: CronJobName,
+    trigger: CronJobTrigger,
+    scheduled_for: DateTime<Utc>,
+    jitter_ms: i64,
+    instance: String,
+) -> anyhow::Result<()> {
+    if jitter_ms > 0 {
+        let jitter = rand::thread_rng().gen_range(0..jitter_ms as u64);
+        tokio::time::sleep(Duration::from_millis(jitter)).await;
+    }
+    let job_name = name.to_string();
+    // The advisory lock lives as long as this transaction, so a slow run can't overlap the next one
+    let mut lock_transaction = pool.begin().await?;
+    if !try_cron_job_lock(&mut lock_transaction, &job_name).await? {
+        tracing::info!("run_cron_job {} is already running", job_name);
+        lock_transaction.rollback().await?;
+        return Ok(());
+    }
+    // Every replica fires the same `scheduled_for`, only the first one to record it runs the job
+    let id = match create_cron_job_run(&pool, &job_name, trigger, &instance, scheduled_for).await? {
+        Some(id) => id,
+        None => {
+            lock_transaction.rollback().await?;
+            return Ok(());
+        }
+    };
+    let now = Instant::now();
+    let (status, error) = match name.run(&pool).await {
+        Ok(_) => (CronJobRunStatus::Completed, None),
+        Err(e) => {
+            tracing::error!("run_cron_job {} failed with error {}", job_name, e);
+            (CronJobRunStatus::Failed, Some(e.to_string()))
+        }
+    };
+    tracing::info!(
+        "run_cron_job {} finished status = {} elapsed = {:?}",
+        job_name,
+        status,
+        now.elapsed()
+    );
+    let r = finish_cron_job_run(&pool, &id, &job_name, status, error).await;
+    lock_transaction.commit().await?;
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use block_mesh_common::env::load_dotenv::load_dotenv;
+    use database_utils::utils::connection::write_pool::write_pool;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn advisory_lock_is_exclusive_until_the_transaction_ends() {
+        load_dotenv();
+        let pool = write_pool(None).await;
+        let name = Uuid::new_v4().to_string();
+        let mut first = pool.begin().await.unwrap();
+        let mut second = pool.begin().await.unwrap();
+        assert!(try_cron_job_lock(&mut first, &name).await.unwrap());
+        assert!(!try_cron_job_lock(&mut second, &name).await.unwrap());
+        first.rollback().await.unwrap();
+        assert!(try_cron_job_lock(&mut second, &name).await.unwrap());
+        second.rollback().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn only_one_replica_records_a_scheduled_run() {
+        load_dotenv();
+        let pool = write_pool(None).await;
+        let name = CronJobName::FinalizeDaily.to_string();
+        let scheduled_for = Utc::now();
+        let first = create_cron_job_run(&pool, &name, CronJobTrigger::Schedule, "a", scheduled_for)
+            .await
+            .unwrap();
+        let second =
+            create_cron_job_run(&pool, &name, CronJobTrigger::Schedule, "b", scheduled_for)
+                .await
+                .unwrap();
+        assert!(first.is_some());
+        assert!(second.is_none());
+        sqlx::query("DELETE FROM cron_job_runs WHERE id = $1")
+            .bind(first)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+}
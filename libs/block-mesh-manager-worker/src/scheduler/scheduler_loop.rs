@@ -0,0 +1,162 @@
+use crate::db_calls::claim_cron_job_trigger::claim_cron_job_trigger;
+use crate::domain::cron_job_name::CronJobName;
+use crate::scheduler::run_cron_job::run_cron_job;
+use block_mesh_manager_database_domain::domain::cron_job::CronJobTrigger;
+use block_mesh_manager_database_domain::domain::get_cron_jobs::get_cron_jobs;
+use chrono::{DateTime, Utc};
+use cron::Schedule;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::env;
+use std::str::FromStr;
+use std::time::Duration;
+use uuid::Uuid;
+
+type NextRuns = HashMap<String, (String, DateTime<Utc>)>;
+
+/// Returns the run that is due now, if any, and the next run to remember for the job.
+/// A remembered run only counts while the job keeps the schedule it was computed from.
+fn plan_run(
+    expression: &str,
+    schedule: &Schedule,
+    previous: Option<&(String, DateTime<Utc>)>,
+    now: DateTime<Utc>,
+) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    let next = match previous {
+        Some((previous_expression, next)) if previous_expression == expression => Some(*next),
+        _ => schedule.after(&now).next(),
+    };
+    match next {
+        Some(next) if next <= now => (Some(next), schedule.after(&now).next()),
+        next => (None, next),
+    }
+}
+
+#[tracing::instrument(name = "schedule_due_jobs", skip_all, err)]
+pub async fn schedule_due_jobs(
+    pool: &PgPool,
+    instance: &str,
+    next_runs: &mut NextRuns,
+) -> anyhow::Result<()> {
+    let now = Utc::now();
+    let mut due: Vec<(CronJobName, CronJobTrigger, DateTime<Utc>, i64)> = Vec::new();
+    let mut transaction = create_txn(pool).await?;
+    for job in get_cron_jobs(&mut transaction).await? {
+        let name = CronJobName::from(job.name.as_str());
+        if name == CronJobName::Invalid {
+            tracing::warn!("schedule_due_jobs unknown job {}", job.name);
+            continue;
+        }
+        if job.triggered_at.is_some() && claim_cron_job_trigger(&mut transaction, &job.name).await?
+        {
+            due.push((name, CronJobTrigger::Manual, now, 0));
+        }
+        if job.paused {
+            next_runs.remove(&job.name);
+            continue;
+        }
+        let schedule = match Schedule::from_str(&job.schedule) {
+            Ok(schedule) => schedule,
+            Err(e) => {
+                tracing::error!("schedule_due_jobs invalid schedule for {}: {}", job.name, e);
+                continue;
+            }
+        };
+        let (scheduled_for, next) =
+            plan_run(&job.schedule, &schedule, next_runs.get(&job.name), now);
+        if let Some(scheduled_for) = scheduled_for {
+            due.push((name, CronJobTrigger::Schedule, scheduled_for, job.jitter_ms));
+        }
+        match next {
+            Some(next) => next_runs.insert(job.name, (job.schedule, next)),
+            None => next_runs.remove(&job.name),
+        };
+    }
+    commit_txn(transaction).await?;
+    for (name, trigger, scheduled_for, jitter_ms) in due {
+        tokio::spawn(run_cron_job(
+            pool.clone(),
+            name,
+            trigger,
+            scheduled_for,
+            jitter_ms,
+            instance.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[tracing::instrument(name = "scheduler_loop", skip_all)]
+pub async fn scheduler_loop(pool: PgPool) -> Result<(), anyhow::Error> {
+    let tick = env::var("SCHEDULER_TICK")
+        .unwrap_or("1000".to_string())
+        .parse()
+        .unwrap_or(1000);
+    let instance = env::var("HOSTNAME").unwrap_or_else(|_| Uuid::new_v4().to_string());
+    let mut next_runs: NextRuns = HashMap::new();
+    loop {
+        let _ = schedule_due_jobs(&pool, &instance, &mut next_runs).await;
+        tokio::time::sleep(Duration::from_millis(tick)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    const EVERY_MINUTE: &str = "0 * * * * *";
+
+    fn at(hour: u32, minute: u32, second: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 11, 22, hour, minute, second)
+            .unwrap()
+    }
+
+    #[test]
+    fn new_job_waits_for_its_first_run() {
+        let schedule = Schedule::from_str(EVERY_MINUTE).unwrap();
+        let (due, next) = plan_run(EVERY_MINUTE, &schedule, None, at(10, 0, 30));
+        assert_eq!(due, None);
+        assert_eq!(next, Some(at(10, 1, 0)));
+    }
+
+    #[test]
+    fn remembered_run_fires_once_and_moves_on() {
+        let schedule = Schedule::from_str(EVERY_MINUTE).unwrap();
+        let previous = (EVERY_MINUTE.to_string(), at(10, 1, 0));
+        let (due, next) = plan_run(EVERY_MINUTE, &schedule, Some(&previous), at(10, 1, 0));
+        assert_eq!(due, Some(at(10, 1, 0)));
+        assert_eq!(next, Some(at(10, 2, 0)));
+    }
+
+    #[test]
+    fn missed_runs_collapse_into_one() {
+        let schedule = Schedule::from_str(EVERY_MINUTE).unwrap();
+        let previous = (EVERY_MINUTE.to_string(), at(10, 1, 0));
+        let now = at(10, 5, 30);
+        let (due, next) = plan_run(EVERY_MINUTE, &schedule, Some(&previous), now);
+        assert_eq!(due, Some(at(10, 1, 0)));
+        assert_eq!(next, Some(at(10, 6, 0)));
+    }
+
+    #[test]
+    fn future_run_is_kept() {
+        let schedule = Schedule::from_str(EVERY_MINUTE).unwrap();
+        let previous = (EVERY_MINUTE.to_string(), at(10, 1, 0));
+        let (due, next) = plan_run(EVERY_MINUTE, &schedule, Some(&previous), at(10, 0, 59));
+        assert_eq!(due, None);
+        assert_eq!(next, Some(at(10, 1, 0)));
+    }
+
+    #[test]
+    fn changed_schedule_drops_the_remembered_run() {
+        let hourly = "0 0 * * * *";
+        let schedule = Schedule::from_str(hourly).unwrap();
+        let previous = (EVERY_MINUTE.to_string(), at(10, 1, 0));
+        let now = at(10, 1, 0) + Duration::seconds(1);
+        let (due, next) = plan_run(hourly, &schedule, Some(&previous), now);
+        assert_eq!(due, None);
+        assert_eq!(next, Some(at(11, 0, 0)));
+    }
+}
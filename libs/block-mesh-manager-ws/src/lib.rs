@@ -2,5 +2,6 @@ pub mod app;
 pub mod errors;
 pub mod joiner_loop;
 pub mod message_aggregator;
+pub mod pg_listener;
 pub mod state;
 pub mod websocket;
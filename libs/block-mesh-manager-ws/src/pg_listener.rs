@@ -0,0 +1,46 @@
+use crate::state::{WsAppState, WsCredsCache};
+use block_mesh_common::constants::BLOCKMESH_PG_NOTIFY_API;
+use block_mesh_common::interfaces::db_messages::InvalidateApiCache;
+use block_mesh_manager_database_domain::domain::authenticate_token::Credentials;
+use sqlx::postgres::PgListener;
+use std::cmp;
+use std::sync::Arc;
+use std::time::Duration;
+
+const MIN_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Drops cached credentials of a user whose tokens were rotated or revoked.
+#[tracing::instrument(name = "creds_listener", skip_all, err)]
+pub async fn creds_listener(state: Arc<WsAppState>) -> anyhow::Result<()> {
+    let mut listener = PgListener::connect_with(&state.channel_pool).await?;
+    listener.listen(BLOCKMESH_PG_NOTIFY_API).await?;
+    let mut backoff = MIN_BACKOFF;
+    loop {
+        let notification = match listener.recv().await {
+            Ok(notification) => {
+                backoff = MIN_BACKOFF;
+                notification
+            }
+            Err(e) => {
+                tracing::error!("creds_listener failed to receive, retrying in {backoff:?}: {e}");
+                tokio::time::sleep(backoff).await;
+                backoff = cmp::min(backoff * 2, MAX_BACKOFF);
+                continue;
+            }
+        };
+        let Ok(payload) = serde_json::from_str::<InvalidateApiCache>(notification.payload()) else {
+            tracing::error!("Failed to deserialize {:?}", notification.payload());
+            continue;
+        };
+        state
+            .creds_cache
+            .lock()
+            .await
+            .retain(|credentials, cached| match (credentials, cached) {
+                (_, WsCredsCache::Found(user)) => user.email != payload.email,
+                (Credentials::Legacy { email, .. }, _) => *email != payload.email,
+                _ => true,
+            });
+    }
+}
@@ -1,7 +1,9 @@
 use block_mesh_common::constants::BLOCKMESH_WS_REDIS_COUNT_KEY;
 use block_mesh_common::env::environment::Environment;
 use block_mesh_common::interfaces::db_messages::DBMessage;
-use block_mesh_manager_database_domain::domain::user::UserAndApiToken;
+use block_mesh_manager_database_domain::domain::authenticate_token::{
+    AuthenticatedToken, Credentials,
+};
 use database_utils::utils::connection::channel_pool::channel_pool;
 use database_utils::utils::connection::follower_pool::follower_pool;
 use database_utils::utils::connection::write_pool::write_pool;
@@ -19,9 +21,8 @@ use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum WsCredsCache {
-    UserNotFound,
-    TokenMismatch,
-    Found(UserAndApiToken),
+    TokenNotFound,
+    Found(AuthenticatedToken),
 }
 
 #[derive(Clone)]
@@ -34,7 +35,7 @@ pub struct WsAppState {
     pub tx: Sender<DBMessage>,
     pub emails: Arc<Mutex<HashSet<String>>>,
     pub user_ids: Arc<Mutex<HashSet<Uuid>>>,
-    pub creds_cache: Arc<Mutex<HashMap<(String, Uuid), WsCredsCache>>>,
+    pub creds_cache: Arc<Mutex<HashMap<Credentials, WsCredsCache>>>,
 }
 
 impl WsAppState {
@@ -52,6 +52,11 @@ pub async fn handle_socket_light(
                     user_id,
                     value: serde_json::Value::from(delta),
                     name: AggregateName::Uptime.to_string(),
+                    idempotency_key: Some(format!(
+                        "ws-uptime:{}:{}",
+                        user_id,
+                        prev.timestamp_micros()
+                    )),
                 }))
                 .await;
             prev = Utc::now();
@@ -76,6 +81,7 @@ pub async fn handle_socket_light(
                                         user_id,
                                         value: serde_json::Value::from(report.download_speed),
                                         name: AggregateName::Download.to_string(),
+                                        idempotency_key: None,
                                     },
                                 ));
                                 messages.push(DBMessage::AggregateSetToMessage(
@@ -84,6 +90,7 @@ pub async fn handle_socket_light(
                                         user_id,
                                         value: serde_json::Value::from(report.upload_speed),
                                         name: AggregateName::Upload.to_string(),
+                                        idempotency_key: None,
                                     },
                                 ));
                                 messages.push(DBMessage::AggregateSetToMessage(
@@ -92,6 +99,7 @@ pub async fn handle_socket_light(
                                         user_id,
                                         value: serde_json::Value::from(report.latency),
                                         name: AggregateName::Latency.to_string(),
+                                        idempotency_key: None,
                                     },
                                 ));
                                 for message in messages {
@@ -2,6 +2,7 @@ use crate::state::WsAppState;
 use axum::extract::ws::Message;
 use block_mesh_common::interfaces::server_api::HandlerMode;
 use block_mesh_common::interfaces::ws_api::WsClientMessage;
+use block_mesh_manager_database_domain::domain::authenticate_token::Credentials;
 use block_mesh_manager_database_domain::domain::report_uptime_content::report_uptime_content;
 use block_mesh_manager_database_domain::domain::submit_bandwidth_content::submit_bandwidth_content;
 use block_mesh_manager_database_domain::domain::submit_task_content::submit_task_content;
@@ -9,15 +10,17 @@ use std::env;
 use std::ops::ControlFlow;
 use std::sync::Arc;
 
+/// `credentials` are the ones checked at the handshake, so bearer clients keep working.
 #[tracing::instrument(name = "process_message", skip_all)]
 pub async fn process_message(
     msg: Message,
     ip: String,
+    credentials: &Credentials,
     state: Arc<WsAppState>,
 ) -> ControlFlow<(), Option<WsClientMessage>> {
     match msg {
         Message::Text(text) => {
-            let ws_client_message = process_client_message(&text, ip, state).await;
+            let ws_client_message = process_client_message(&text, ip, credentials, state).await;
             return ControlFlow::Continue(ws_client_message);
         }
         Message::Binary(bytes) => {
@@ -53,6 +56,7 @@ pub async fn process_message(
 async fn process_client_message(
     text: &str,
     ip: String,
+    credentials: &Credentials,
     state: Arc<WsAppState>,
 ) -> Option<WsClientMessage> {
     if text == "pong" {
@@ -66,6 +70,8 @@ async fn process_client_message(
                         &state.pool,
                         &state.follower_pool,
                         &state.channel_pool,
+                        Some(&ip),
+                        credentials,
                         query.clone(),
                         None,
                         HandlerMode::WebSocket,
@@ -77,17 +83,20 @@ async fn process_client_message(
                         &state.pool,
                         &state.follower_pool,
                         &state.channel_pool,
+                        Some(&ip),
+                        credentials,
                         body.clone(),
                     )
                     .await;
                 }
-                WsClientMessage::ReportUptime(query) => {
+                WsClientMessage::ReportUptime(body) => {
                     let _ = report_uptime_content(
                         &state.pool,
                         &state.follower_pool,
                         &state.channel_pool,
                         ip.clone(),
-                        query.clone(),
+                        credentials,
+                        body.report_id,
                         None,
                         HandlerMode::WebSocket,
                         env::var("POLLING_INTERVAL")
@@ -2,6 +2,7 @@ use crate::state::WsAppState;
 use crate::websocket::process_message::process_message;
 use axum::extract::ws::WebSocket;
 use block_mesh_common::interfaces::ws_api::WsClientMessage;
+use block_mesh_manager_database_domain::domain::authenticate_token::Credentials;
 use futures::stream::SplitStream;
 use futures::StreamExt;
 use std::ops::ControlFlow;
@@ -14,12 +15,13 @@ pub async fn receiver(
     mut ws_stream: SplitStream<WebSocket>,
     is_cls: Arc<AtomicBool>,
     ip: String,
+    credentials: Credentials,
     task_scheduler_notifier: Arc<Notify>,
     state: Arc<WsAppState>,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
         while let Some(Ok(msg)) = ws_stream.next().await {
-            match process_message(msg.clone(), ip.clone(), state.clone()).await {
+            match process_message(msg.clone(), ip.clone(), &credentials, state.clone()).await {
                 ControlFlow::Continue(ws_client_message) => {
                     if let Some(ws_client_message) = ws_client_message {
                         if matches!(ws_client_message, WsClientMessage::CompleteTask(_)) {
@@ -4,8 +4,10 @@ use crate::websocket::handle_socket_light::handle_socket_light;
 use anyhow::{anyhow, Context};
 use axum::extract::{Query, State, WebSocketUpgrade};
 use axum::response::IntoResponse;
-use block_mesh_manager_database_domain::domain::get_user_and_api_token::get_user_and_api_token_by_email;
-use block_mesh_manager_database_domain::domain::user::UserAndApiToken;
+use block_mesh_common::interfaces::server_api::TokenScope;
+use block_mesh_manager_database_domain::domain::authenticate_token::{
+    authenticate_token, bearer_token, AuthenticatedToken, Credentials,
+};
 use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
 use http::{HeaderMap, StatusCode};
 use std::collections::HashMap;
@@ -32,52 +34,52 @@ pub async fn ws_handler(
         "127.0.0.1"
     }
     .to_string();
-    let email = query
-        .get("email")
-        .ok_or(anyhow!("Missing email".to_string()))?
-        .clone();
-    let api_token = query
-        .get("api_token")
-        .ok_or(anyhow!("Missing token".to_string()))?;
-    let api_token = Uuid::from_str(api_token).context("Cannot deserialize UUID")?;
-    if state.emails.lock().await.contains(&email) {
-        return Ok((StatusCode::ALREADY_REPORTED, "Already connected").into_response());
-    }
-    let creds_key = (email.clone(), api_token);
+    let credentials = match bearer_token(&headers) {
+        Some(token) => Credentials::Bearer(token),
+        None => {
+            let email = query
+                .get("email")
+                .ok_or(anyhow!("Missing email".to_string()))?
+                .clone();
+            let api_token = query
+                .get("api_token")
+                .ok_or(anyhow!("Missing token".to_string()))?;
+            let api_token = Uuid::from_str(api_token).context("Cannot deserialize UUID")?;
+            Credentials::Legacy { email, api_token }
+        }
+    };
     let mut creds_cache = state.creds_cache.lock().await;
-    let cached_value = creds_cache.get(&creds_key);
-    let user: UserAndApiToken = match cached_value {
+    let cached_value = creds_cache.get(&credentials);
+    let user: AuthenticatedToken = match cached_value {
         None => {
             let follower_pool = &state.follower_pool;
             let mut transaction = create_txn(follower_pool).await?;
-            let user = get_user_and_api_token_by_email(&mut transaction, &email)
-                .await?
-                .ok_or({
-                    creds_cache.insert(creds_key.clone(), WsCredsCache::UserNotFound);
-                    anyhow!(String::from("User email is not present in DB"))
-                })?;
+            let user =
+                authenticate_token(&mut transaction, &credentials, TokenScope::NodeReporting)
+                    .await?;
             commit_txn(transaction).await?;
-            if user.token.as_ref() != &api_token {
-                creds_cache.insert(creds_key, WsCredsCache::TokenMismatch);
-                return Err(Error::from(anyhow!("Api Token Mismatch")));
-            }
-            creds_cache.insert(creds_key, WsCredsCache::Found(user.clone()));
+            let user = match user {
+                Some(user) => user,
+                None => {
+                    creds_cache.insert(credentials, WsCredsCache::TokenNotFound);
+                    return Err(Error::from(anyhow!("Api Token Not Found")));
+                }
+            };
+            creds_cache.insert(credentials, WsCredsCache::Found(user.clone()));
             user
         }
         Some(v) => match v {
-            WsCredsCache::UserNotFound => {
-                return Err(Error::from(anyhow!(String::from(
-                    "User email is not present in DB"
-                ))));
-            }
-            WsCredsCache::TokenMismatch => {
-                return Err(Error::from(anyhow!("Api Token Mismatch")));
+            WsCredsCache::TokenNotFound => {
+                return Err(Error::from(anyhow!("Api Token Not Found")));
             }
             WsCredsCache::Found(u) => u.clone(),
         },
     };
     drop(creds_cache);
+    if state.emails.lock().await.contains(&user.email) {
+        return Ok((StatusCode::ALREADY_REPORTED, "Already connected").into_response());
+    }
     Ok(ws.on_upgrade(move |socket| {
-        handle_socket_light(email, socket, header_ip, state, user.user_id)
+        handle_socket_light(user.email, socket, header_ip, state, user.user_id)
     }))
 }
@@ -0,0 +1,26 @@
+CREATE TABLE aggregate_events
+(
+    id              uuid             NOT NULL DEFAULT gen_random_uuid(),
+    user_id         uuid             NOT NULL,
+    name            TEXT             NOT NULL,
+    kind            TEXT             NOT NULL,
+    value           DOUBLE PRECISION NOT NULL,
+    idempotency_key TEXT             NOT NULL,
+    created_at      timestamptz      NOT NULL DEFAULT now(),
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id),
+    PRIMARY KEY (id)
+);
+-- -- -----
+CREATE UNIQUE INDEX aggregate_events_idempotency_key ON aggregate_events (idempotency_key);
+CREATE INDEX aggregate_events_user_id_name_created_at ON aggregate_events (user_id, name, created_at);
+-- -- -----
+INSERT INTO aggregate_events (user_id, name, kind, value, idempotency_key, created_at)
+SELECT user_id,
+       name,
+       'Set',
+       (value #>> '{}')::double precision,
+       'baseline:' || id::text,
+       updated_at
+FROM aggregates
+WHERE name IN ('Uptime', 'Tasks', 'Download', 'Upload', 'Latency')
+  AND jsonb_typeof(value) = 'number';
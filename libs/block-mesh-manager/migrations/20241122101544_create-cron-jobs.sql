@@ -0,0 +1,37 @@
+CREATE TABLE cron_jobs
+(
+    name         TEXT        NOT NULL,
+    schedule     TEXT        NOT NULL,
+    jitter_ms    BIGINT      NOT NULL DEFAULT 0,
+    paused       BOOLEAN     NOT NULL DEFAULT FALSE,
+    triggered_at timestamptz NULL,
+    created_at   timestamptz NOT NULL DEFAULT now(),
+    updated_at   timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (name)
+);
+-- -- -----
+CREATE TABLE cron_job_runs
+(
+    id            uuid        NOT NULL DEFAULT gen_random_uuid(),
+    name          TEXT        NOT NULL,
+    trigger       TEXT        NOT NULL,
+    status        TEXT        NOT NULL,
+    instance      TEXT        NOT NULL,
+    error         TEXT        NULL,
+    scheduled_for timestamptz NOT NULL,
+    started_at    timestamptz NOT NULL DEFAULT now(),
+    finished_at   timestamptz NULL,
+    CONSTRAINT fk_cron_job FOREIGN KEY (name) REFERENCES cron_jobs (name),
+    PRIMARY KEY (id)
+);
+-- -- -----
+CREATE UNIQUE INDEX cron_job_runs_name_scheduled_for ON cron_job_runs (name, scheduled_for);
+CREATE INDEX cron_job_runs_started_at ON cron_job_runs (started_at);
+-- -- -----
+INSERT INTO cron_jobs (name, schedule, jitter_ms)
+VALUES ('FinalizeDaily', '0 0 * * * *', 0),
+       ('RpcTasks', '*/30 * * * * *', 0),
+       ('SpecialTasks', '*/30 * * * * *', 0),
+       ('BulkUptimeBonus', '0 * * * * *', 5000),
+       ('BulkTaskBonus', '0 */10 * * * *', 5000),
+       ('CleanOldTasks', '0 * * * * *', 5000);
@@ -0,0 +1,89 @@
+CREATE TABLE probes
+(
+    id                     uuid             NOT NULL DEFAULT gen_random_uuid(),
+    user_id                uuid             NOT NULL,
+    name                   TEXT             NOT NULL,
+    url                    TEXT             NOT NULL,
+    method                 TEXT             NOT NULL,
+    headers                JSONB            NULL,
+    body                   JSONB            NULL,
+    expected_status        INTEGER          NULL,
+    assertion              JSONB            NULL,
+    interval_secs          INTEGER          NOT NULL DEFAULT 60,
+    regions                TEXT[]           NOT NULL DEFAULT '{}',
+    latency_threshold_ms   DOUBLE PRECISION NULL,
+    availability_threshold DOUBLE PRECISION NULL,
+    alert_email            TEXT             NULL,
+    enabled                BOOLEAN          NOT NULL DEFAULT TRUE,
+    last_scheduled_at      timestamptz      NULL,
+    last_alerted_at        timestamptz      NULL,
+    created_at             timestamptz      NOT NULL DEFAULT now(),
+    updated_at             timestamptz      NOT NULL DEFAULT now(),
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id),
+    PRIMARY KEY (id)
+);
+-- -- -----
+CREATE INDEX probes_user_id ON probes (user_id);
+CREATE INDEX probes_enabled_last_scheduled_at ON probes (enabled, last_scheduled_at);
+-- -- -----
+CREATE TABLE probe_results
+(
+    id            uuid             NOT NULL DEFAULT gen_random_uuid(),
+    probe_id      uuid             NOT NULL,
+    task_id       uuid             NOT NULL,
+    country       TEXT             NOT NULL DEFAULT '',
+    asn           TEXT             NOT NULL DEFAULT '',
+    response_code INTEGER          NULL,
+    latency       DOUBLE PRECISION NOT NULL DEFAULT 0.0,
+    success       BOOLEAN          NOT NULL,
+    created_at    timestamptz      NOT NULL DEFAULT now(),
+    CONSTRAINT fk_probe FOREIGN KEY (probe_id) REFERENCES probes (id) ON DELETE CASCADE,
+    PRIMARY KEY (id)
+);
+-- -- -----
+CREATE INDEX probe_results_probe_id_created_at ON probe_results (probe_id, created_at);
+-- -- -----
+ALTER TABLE tasks
+    ADD COLUMN probe_id uuid NULL;
+ALTER TABLE tasks
+    ADD COLUMN region TEXT NULL;
+-- -- -----
+DO
+$$
+    BEGIN
+        IF EXISTS (SELECT 1 FROM rpcs)
+            AND NOT EXISTS (SELECT 1 FROM users WHERE email = 'support@blockmesh.xyz') THEN
+            RAISE EXCEPTION 'support@blockmesh.xyz must exist to take over the rpcs probes before rpcs is dropped';
+        END IF;
+    END
+$$;
+-- -- -----
+INSERT INTO probes (user_id, name, url, method, headers, body, expected_status, assertion, interval_secs)
+SELECT users.id,
+       rpcs.name,
+       CASE
+           WHEN rpcs.name = 'Helius' THEN rpcs.host || '?api-key=' || rpcs.token
+           WHEN rpcs.name = 'Shyft' THEN rpcs.host || '?api_key=' || rpcs.token
+           WHEN rpcs.name = 'SolanaLabs' THEN rpcs.host
+           ELSE rpcs.host || '/' || rpcs.token
+           END,
+       'POST',
+       '{"Content-Type": "application/json"}'::jsonb,
+       '{"id": 1, "jsonrpc": "2.0", "method": "getLatestBlockhash", "params": [{"commitment": "processed"}]}'::jsonb,
+       200,
+       '{"pointer": "/result"}'::jsonb,
+       30
+FROM rpcs
+         JOIN users ON users.email = 'support@blockmesh.xyz';
+-- -- -----
+DROP TABLE rpcs;
+-- -- -----
+DELETE
+FROM cron_job_runs
+WHERE name = 'RpcTasks';
+UPDATE cron_jobs
+SET name     = 'Probes',
+    schedule = '*/5 * * * * *'
+WHERE name = 'RpcTasks';
+INSERT INTO cron_jobs (name, schedule, jitter_ms)
+VALUES ('ProbeAlerts', '0 * * * * *', 5000);
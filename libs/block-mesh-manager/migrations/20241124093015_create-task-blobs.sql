@@ -0,0 +1,31 @@
+ALTER TABLE tasks
+    ADD COLUMN body_ref TEXT NULL;
+ALTER TABLE tasks
+    ADD COLUMN response_ref TEXT NULL;
+-- -- -----
+CREATE INDEX tasks_body_ref ON tasks (body_ref) WHERE body_ref IS NOT NULL;
+CREATE INDEX tasks_response_ref ON tasks (response_ref) WHERE response_ref IS NOT NULL;
+-- -- -----
+CREATE TABLE blobs
+(
+    key                TEXT        NOT NULL,
+    size               BIGINT      NOT NULL,
+    created_at         timestamptz NOT NULL DEFAULT now(),
+    last_referenced_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (key)
+);
+-- -- -----
+CREATE INDEX blobs_last_referenced_at ON blobs (last_referenced_at);
+-- -- -----
+CREATE TABLE task_retention
+(
+    user_id        uuid        NOT NULL,
+    retention_days INTEGER     NOT NULL,
+    created_at     timestamptz NOT NULL DEFAULT now(),
+    updated_at     timestamptz NOT NULL DEFAULT now(),
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id),
+    PRIMARY KEY (user_id)
+);
+-- -- -----
+INSERT INTO cron_jobs (name, schedule, jitter_ms)
+VALUES ('CleanOrphanBlobs', '0 */10 * * * *', 5000);
@@ -0,0 +1,18 @@
+CREATE TABLE node_keys
+(
+    id         uuid        NOT NULL DEFAULT gen_random_uuid(),
+    user_id    uuid        NOT NULL,
+    public_key TEXT        NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz NOT NULL DEFAULT now(),
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id),
+    PRIMARY KEY (id)
+);
+-- -- -----
+CREATE UNIQUE INDEX node_keys_user_id ON node_keys (user_id);
+CREATE INDEX node_keys_updated_at ON node_keys (updated_at);
+-- -- -----
+ALTER TABLE tasks
+    ADD COLUMN encrypted BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE tasks
+    ADD COLUMN reply_key TEXT NULL;
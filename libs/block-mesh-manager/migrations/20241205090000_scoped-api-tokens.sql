@@ -0,0 +1,13 @@
+ALTER TABLE api_tokens ADD COLUMN name TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE api_tokens ADD COLUMN scopes TEXT[] NOT NULL DEFAULT ARRAY ['node_reporting', 'task_submission', 'dashboard_read'];
+ALTER TABLE api_tokens ADD COLUMN expires_at timestamptz;
+ALTER TABLE api_tokens ADD COLUMN last_used_at timestamptz;
+ALTER TABLE api_tokens ADD COLUMN last_used_ip TEXT;
+ALTER TABLE api_tokens ADD COLUMN is_primary BOOLEAN NOT NULL DEFAULT false;
+
+-- The token every existing client was given stays usable with the email + api_token pair
+UPDATE api_tokens
+SET is_primary = true
+WHERE id IN (SELECT DISTINCT ON (user_id) id FROM api_tokens ORDER BY user_id, created_at);
+
+CREATE UNIQUE INDEX api_tokens_primary ON api_tokens (user_id) WHERE is_primary;
@@ -0,0 +1,22 @@
+CREATE TABLE device_authorizations
+(
+    id             uuid        NOT NULL DEFAULT gen_random_uuid(),
+    device_code    uuid        NOT NULL,
+    user_code      TEXT        NOT NULL,
+    device_name    TEXT        NOT NULL,
+    scopes         TEXT[]      NOT NULL,
+    status         TEXT        NOT NULL DEFAULT 'pending',
+    user_id        uuid        NULL,
+    api_token_id   uuid        NULL,
+    expires_at     timestamptz NOT NULL,
+    last_polled_at timestamptz NULL,
+    created_at     timestamptz NOT NULL DEFAULT now(),
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id),
+    CONSTRAINT fk_api_token FOREIGN KEY (api_token_id) REFERENCES api_tokens (id),
+    PRIMARY KEY (id)
+);
+-- -- -----
+CREATE UNIQUE INDEX device_authorizations_device_code ON device_authorizations (device_code);
+-- Codes are short, so they only have to be unique among the ones still pending
+CREATE UNIQUE INDEX device_authorizations_user_code ON device_authorizations (user_code) WHERE status = 'pending';
+CREATE INDEX device_authorizations_expires_at ON device_authorizations (expires_at);
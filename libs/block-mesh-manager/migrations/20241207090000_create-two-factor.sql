@@ -0,0 +1,35 @@
+CREATE TABLE two_factor_secrets
+(
+    user_id        uuid        NOT NULL,
+    secret         TEXT        NOT NULL,
+    enabled_at     timestamptz NULL,
+    last_used_step BIGINT      NULL,
+    created_at     timestamptz NOT NULL DEFAULT now(),
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    PRIMARY KEY (user_id)
+);
+-- -- -----
+CREATE TABLE two_factor_recovery_codes
+(
+    id         uuid        NOT NULL DEFAULT gen_random_uuid(),
+    user_id    uuid        NOT NULL,
+    code_hash  TEXT        NOT NULL,
+    used_at    timestamptz NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    PRIMARY KEY (id)
+);
+CREATE INDEX two_factor_recovery_codes_user_id ON two_factor_recovery_codes (user_id);
+-- -- -----
+CREATE TABLE two_factor_remembered_devices
+(
+    id         uuid        NOT NULL DEFAULT gen_random_uuid(),
+    user_id    uuid        NOT NULL,
+    token_hash TEXT        NOT NULL,
+    expires_at timestamptz NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    PRIMARY KEY (id)
+);
+CREATE UNIQUE INDEX two_factor_remembered_devices_token_hash ON two_factor_remembered_devices (token_hash);
+CREATE INDEX two_factor_remembered_devices_user_id ON two_factor_remembered_devices (user_id);
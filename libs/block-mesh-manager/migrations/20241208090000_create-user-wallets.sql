@@ -0,0 +1,18 @@
+CREATE TABLE user_wallets
+(
+    id         uuid        NOT NULL DEFAULT gen_random_uuid(),
+    user_id    uuid        NOT NULL,
+    address    TEXT        NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    PRIMARY KEY (id)
+);
+CREATE UNIQUE INDEX user_wallets_address ON user_wallets (address);
+CREATE INDEX user_wallets_user_id ON user_wallets (user_id);
+-- -- -----
+INSERT INTO user_wallets (user_id, address, created_at)
+SELECT DISTINCT ON (wallet_address) id, wallet_address, created_at
+FROM users
+WHERE wallet_address IS NOT NULL AND wallet_address <> ''
+ORDER BY wallet_address, created_at
+ON CONFLICT (address) DO NOTHING;
@@ -0,0 +1,47 @@
+CREATE TABLE email_outbox
+(
+    id              uuid        NOT NULL DEFAULT gen_random_uuid(),
+    user_id         uuid        NULL,
+    email_address   TEXT        NOT NULL,
+    template        TEXT        NOT NULL,
+    payload         JSONB       NOT NULL,
+    status          TEXT        NOT NULL DEFAULT 'Pending',
+    attempts        INTEGER     NOT NULL DEFAULT 0,
+    next_attempt_at timestamptz NOT NULL DEFAULT now(),
+    provider        TEXT        NULL,
+    message_id      TEXT        NULL,
+    last_error      TEXT        NULL,
+    created_at      timestamptz NOT NULL DEFAULT now(),
+    updated_at      timestamptz NOT NULL DEFAULT now(),
+    sent_at         timestamptz NULL,
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE SET NULL,
+    PRIMARY KEY (id)
+);
+-- -- -----
+CREATE INDEX email_outbox_status_next_attempt_at ON email_outbox (status, next_attempt_at);
+CREATE INDEX email_outbox_message_id ON email_outbox (message_id) WHERE message_id IS NOT NULL;
+-- -- -----
+CREATE TABLE notification_preferences
+(
+    user_id           uuid        NOT NULL,
+    probe_alerts      BOOLEAN     NOT NULL DEFAULT TRUE,
+    locale            TEXT        NOT NULL DEFAULT 'en',
+    unsubscribe_token uuid        NOT NULL DEFAULT gen_random_uuid(),
+    created_at        timestamptz NOT NULL DEFAULT now(),
+    updated_at        timestamptz NOT NULL DEFAULT now(),
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    PRIMARY KEY (user_id)
+);
+-- -- -----
+CREATE UNIQUE INDEX notification_preferences_unsubscribe_token ON notification_preferences (unsubscribe_token);
+-- -- -----
+CREATE TABLE email_suppressions
+(
+    email_address TEXT        NOT NULL,
+    reason        TEXT        NOT NULL,
+    created_at    timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (email_address)
+);
+-- -- -----
+INSERT INTO cron_jobs (name, schedule, jitter_ms)
+VALUES ('EmailOutbox', '*/5 * * * * *', 0);
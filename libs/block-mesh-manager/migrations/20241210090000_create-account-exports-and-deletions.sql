@@ -0,0 +1,35 @@
+CREATE TABLE account_exports
+(
+    id           uuid        NOT NULL DEFAULT gen_random_uuid(),
+    user_id      uuid        NOT NULL,
+    status       TEXT        NOT NULL DEFAULT 'Pending',
+    blob_key     TEXT        NULL,
+    size_bytes   BIGINT      NULL,
+    last_error   TEXT        NULL,
+    created_at   timestamptz NOT NULL DEFAULT now(),
+    completed_at timestamptz NULL,
+    expires_at   timestamptz NULL,
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    PRIMARY KEY (id)
+);
+-- -- -----
+CREATE INDEX account_exports_user_id_created_at ON account_exports (user_id, created_at DESC);
+CREATE INDEX account_exports_status ON account_exports (status);
+-- -- -----
+CREATE TABLE account_deletions
+(
+    user_id       uuid        NOT NULL,
+    status        TEXT        NOT NULL DEFAULT 'Scheduled',
+    requested_at  timestamptz NOT NULL DEFAULT now(),
+    scheduled_for timestamptz NOT NULL,
+    completed_at  timestamptz NULL,
+    last_error    TEXT        NULL,
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    PRIMARY KEY (user_id)
+);
+-- -- -----
+CREATE INDEX account_deletions_status_scheduled_for ON account_deletions (status, scheduled_for);
+-- -- -----
+INSERT INTO cron_jobs (name, schedule, jitter_ms)
+VALUES ('AccountExports', '*/30 * * * * *', 0),
+       ('AccountDeletions', '0 */10 * * * *', 0);
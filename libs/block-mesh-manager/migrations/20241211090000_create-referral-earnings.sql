@@ -0,0 +1,21 @@
+CREATE TABLE referral_earnings
+(
+    id             uuid             NOT NULL DEFAULT gen_random_uuid(),
+    user_id        uuid             NOT NULL,
+    referee_id     uuid             NOT NULL,
+    day            DATE             NOT NULL,
+    level          INTEGER          NOT NULL,
+    referee_points DOUBLE PRECISION NOT NULL,
+    percentage     DOUBLE PRECISION NOT NULL,
+    points         DOUBLE PRECISION NOT NULL,
+    status         TEXT             NOT NULL DEFAULT 'Credited',
+    created_at     timestamptz      NOT NULL DEFAULT now(),
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    CONSTRAINT fk_referee FOREIGN KEY (referee_id) REFERENCES users (id) ON DELETE CASCADE,
+    PRIMARY KEY (id)
+);
+-- -- -----
+CREATE UNIQUE INDEX referral_earnings_referee_id_level_day ON referral_earnings (referee_id, level, day);
+CREATE INDEX referral_earnings_user_id_day ON referral_earnings (user_id, day);
+-- -- -----
+CREATE INDEX node_keys_public_key ON node_keys (public_key);
@@ -0,0 +1,36 @@
+CREATE TABLE invite_campaigns
+(
+    id              uuid             NOT NULL DEFAULT gen_random_uuid(),
+    name            TEXT             NOT NULL,
+    starts_at       timestamptz      NULL,
+    ends_at         timestamptz      NULL,
+    max_uses        INTEGER          NULL,
+    uses            INTEGER          NOT NULL DEFAULT 0,
+    perk_multiplier DOUBLE PRECISION NULL,
+    perk_days       INTEGER          NULL,
+    created_by      uuid             NOT NULL,
+    created_at      timestamptz      NOT NULL DEFAULT now(),
+    CONSTRAINT fk_created_by FOREIGN KEY (created_by) REFERENCES users (id),
+    PRIMARY KEY (id)
+);
+-- -- -----
+CREATE UNIQUE INDEX invite_campaigns_name ON invite_campaigns (name);
+-- -- -----
+ALTER TABLE invite_codes ADD COLUMN campaign_id uuid NULL REFERENCES invite_campaigns (id);
+CREATE INDEX invite_codes_campaign_id ON invite_codes (campaign_id);
+-- -- -----
+CREATE TABLE invite_code_signups
+(
+    user_id        uuid        NOT NULL,
+    invite_code_id uuid        NULL,
+    campaign_id    uuid        NULL,
+    created_at     timestamptz NOT NULL DEFAULT now(),
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    CONSTRAINT fk_invite_code FOREIGN KEY (invite_code_id) REFERENCES invite_codes (id) ON DELETE SET NULL,
+    CONSTRAINT fk_campaign FOREIGN KEY (campaign_id) REFERENCES invite_campaigns (id),
+    PRIMARY KEY (user_id)
+);
+-- -- -----
+CREATE INDEX invite_code_signups_campaign_id ON invite_code_signups (campaign_id);
+-- -- -----
+ALTER TABLE perks ADD COLUMN expires_at timestamptz NULL;
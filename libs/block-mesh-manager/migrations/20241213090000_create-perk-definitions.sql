@@ -0,0 +1,47 @@
+CREATE TABLE perk_definitions
+(
+    name                TEXT             NOT NULL,
+    multiplier          DOUBLE PRECISION NOT NULL DEFAULT 1,
+    one_time_bonus      DOUBLE PRECISION NOT NULL DEFAULT 0,
+    stacking_group      TEXT             NULL,
+    stacking            TEXT             NOT NULL DEFAULT 'Multiply',
+    max_multiplier      DOUBLE PRECISION NULL,
+    valid_from          timestamptz      NULL,
+    valid_until         timestamptz      NULL,
+    duration_days       INTEGER          NULL,
+    condition           TEXT             NULL,
+    condition_threshold BIGINT           NULL,
+    enabled             BOOLEAN          NOT NULL DEFAULT TRUE,
+    created_at          timestamptz      NOT NULL DEFAULT now(),
+    updated_at          timestamptz      NOT NULL DEFAULT now(),
+    PRIMARY KEY (name)
+);
+-- -- -----
+INSERT INTO perk_definitions (name, multiplier, one_time_bonus)
+VALUES ('wallet', 1.1, 0),
+       ('twitter', 1, 500),
+       ('founder_twitter', 1, 500),
+       ('campaign', 1, 0);
+INSERT INTO perk_definitions
+(name, multiplier, stacking_group, stacking, max_multiplier, duration_days, condition, condition_threshold)
+VALUES ('node_streak_7', 1.1, 'activity', 'Max', 1.2, 30, 'ConsecutiveNodeDays', 7),
+       ('tasks_100', 1.05, 'activity', 'Max', 1.2, NULL, 'CompletedTasks', 100);
+-- -- -----
+ALTER TABLE perks ADD COLUMN starts_at timestamptz NULL;
+-- -- -----
+ALTER TABLE daily_stats ADD COLUMN perk_multiplier DOUBLE PRECISION NULL;
+-- -- -----
+CREATE TABLE perk_history
+(
+    user_id    uuid             NOT NULL,
+    day        DATE             NOT NULL,
+    perk_name  TEXT             NOT NULL,
+    multiplier DOUBLE PRECISION NOT NULL,
+    applied    BOOLEAN          NOT NULL,
+    created_at timestamptz      NOT NULL DEFAULT now(),
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    PRIMARY KEY (user_id, day, perk_name)
+);
+-- -- -----
+INSERT INTO cron_jobs (name, schedule, jitter_ms)
+VALUES ('PerkRules', '0 15 * * * *', 0);
@@ -0,0 +1,5 @@
+DROP INDEX node_keys_user_id;
+CREATE UNIQUE INDEX node_keys_user_id_public_key ON node_keys (user_id, public_key);
+-- -- -----
+ALTER TABLE tasks
+    ADD COLUMN node_key_id uuid NULL;
@@ -0,0 +1,4 @@
+CREATE INDEX probe_results_created_at ON probe_results (created_at);
+-- -- -----
+INSERT INTO cron_jobs (name, schedule, jitter_ms)
+VALUES ('CleanOldProbeResults', '30 * * * * *', 5000);
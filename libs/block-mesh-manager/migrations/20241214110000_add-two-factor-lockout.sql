@@ -0,0 +1,5 @@
+ALTER TABLE two_factor_secrets
+    ADD COLUMN failed_attempts INTEGER NOT NULL DEFAULT 0;
+-- -- -----
+ALTER TABLE two_factor_secrets
+    ADD COLUMN locked_until timestamptz NULL;
@@ -0,0 +1,2 @@
+ALTER TABLE device_authorizations
+    ADD COLUMN consumed_at timestamptz NULL;
@@ -0,0 +1,14 @@
+CREATE TABLE wallet_perk_grants
+(
+    address    TEXT        NOT NULL,
+    user_id    uuid        NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    PRIMARY KEY (address)
+);
+-- -- -----
+INSERT INTO wallet_perk_grants (address, user_id, created_at)
+SELECT user_wallets.address, user_wallets.user_id, user_wallets.created_at
+FROM user_wallets
+JOIN perks ON perks.user_id = user_wallets.user_id AND perks.name = 'wallet'
+ON CONFLICT (address) DO NOTHING;
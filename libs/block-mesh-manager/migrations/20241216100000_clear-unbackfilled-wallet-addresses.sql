@@ -0,0 +1,25 @@
+-- Two accounts could share a wallet address before user_wallets enforced one owner, the
+-- backfill kept the oldest account. The others lose the address, with an unlinked entry in
+-- their wallet history so support can tell why.
+WITH conflicts AS (
+    SELECT users.id, users.wallet_address, gen_random_uuid() AS change_id
+    FROM users
+    WHERE users.wallet_address IS NOT NULL
+      AND users.wallet_address <> ''
+      AND NOT EXISTS (
+        SELECT 1
+        FROM user_wallets
+        WHERE user_wallets.user_id = users.id
+          AND user_wallets.address = users.wallet_address
+      )
+),
+history AS (
+    INSERT INTO aggregates (id, created_at, user_id, name, value, updated_at)
+    SELECT change_id, now(), id, 'WalletChange_' || change_id,
+           jsonb_build_object('action', 'unlinked', 'address', wallet_address), now()
+    FROM conflicts
+)
+UPDATE users
+SET wallet_address = NULL
+FROM conflicts
+WHERE users.id = conflicts.id;
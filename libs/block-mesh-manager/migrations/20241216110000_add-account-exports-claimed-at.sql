@@ -0,0 +1 @@
+ALTER TABLE account_exports ADD COLUMN claimed_at timestamptz NULL;
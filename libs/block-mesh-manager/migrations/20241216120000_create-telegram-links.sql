@@ -0,0 +1,9 @@
+CREATE TABLE telegram_links
+(
+    user_id    uuid        NOT NULL,
+    link_code  TEXT        NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    PRIMARY KEY (user_id)
+);
+CREATE UNIQUE INDEX telegram_links_link_code ON telegram_links (link_code);
@@ -0,0 +1,39 @@
+-- The mailbox an address delivers to, `+tags` dropped and Gmail's dots ignored.
+CREATE FUNCTION normalized_email(email TEXT) RETURNS TEXT
+    LANGUAGE sql
+    IMMUTABLE
+AS
+$$
+SELECT CASE
+           WHEN domain IN ('gmail.com', 'googlemail.com') THEN replace(local, '.', '') || '@gmail.com'
+           ELSE local || '@' || domain
+           END
+FROM (SELECT split_part(split_part(lower(email), '@', 1), '+', 1) AS local,
+             split_part(lower(email), '@', 2)                     AS domain) parts
+$$;
+-- -- -----
+-- Two accounts that look like the same person: a shared IP, node key or mailbox,
+-- or a wallet that moved from one to the other.
+CREATE FUNCTION referral_overlap(a uuid, b uuid) RETURNS BOOLEAN
+    LANGUAGE sql
+    STABLE
+AS
+$$
+SELECT EXISTS (SELECT 1
+               FROM users_ip a_ip
+                        JOIN users_ip b_ip ON b_ip.ip_id = a_ip.ip_id
+               WHERE a_ip.user_id = a
+                 AND b_ip.user_id = b)
+           OR EXISTS (SELECT 1
+                      FROM node_keys a_key
+                               JOIN node_keys b_key ON b_key.public_key = a_key.public_key
+                      WHERE a_key.user_id = a
+                        AND b_key.user_id = b)
+           OR EXISTS (SELECT 1
+                      FROM wallet_perk_grants
+                               JOIN user_wallets ON user_wallets.address = wallet_perk_grants.address
+                      WHERE (wallet_perk_grants.user_id = a AND user_wallets.user_id = b)
+                         OR (wallet_perk_grants.user_id = b AND user_wallets.user_id = a))
+           OR (SELECT normalized_email(email) FROM users WHERE id = a) =
+              (SELECT normalized_email(email) FROM users WHERE id = b)
+$$;
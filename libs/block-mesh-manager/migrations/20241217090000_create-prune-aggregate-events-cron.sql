@@ -0,0 +1,4 @@
+CREATE INDEX aggregate_events_created_at ON aggregate_events (created_at);
+-- -- -----
+INSERT INTO cron_jobs (name, schedule, jitter_ms)
+VALUES ('PruneAggregateEvents', '0 */10 * * * *', 5000);
@@ -49,6 +49,19 @@ pub fn get_configuration() -> Result<Settings, config::ConfigError> {
         )
         .add_source(config::Environment::with_prefix("APP").separator("_"))
         .set_override_option("application.port", env::var("PORT").ok())?
+        // The switches the node endpoints were rate limited by before the policies existed.
+        .set_override_option(
+            "rate_limits.policies.get_task.enabled",
+            env::var("APP_RATE_LIMIT").ok(),
+        )?
+        .set_override_option(
+            "rate_limits.policies.report_uptime.enabled",
+            env::var("APP_RATE_LIMIT").ok(),
+        )?
+        .set_override_option(
+            "rate_limits.policies.submit_bandwidth.enabled",
+            env::var("APP_BW_LIMIT").ok(),
+        )?
         .build()?
         .try_deserialize()
 }
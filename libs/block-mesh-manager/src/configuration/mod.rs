@@ -1,4 +1,5 @@
 pub mod application_settings;
 pub mod database_settings;
 pub mod get_configuration;
+pub mod rate_limit_settings;
 pub mod settings;
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Deserialize, Debug, Serialize, Clone)]
+pub struct RateLimitSettings {
+    /// Redis calls slower than this fall back to the process local limiter.
+    #[serde(default = "default_redis_timeout_ms")]
+    pub redis_timeout_ms: u64,
+    /// Keys `ip` buckets by `cf-connecting-ip`, only safe when every request comes through
+    /// Cloudflare, otherwise a client can pick its own bucket.
+    #[serde(default)]
+    pub trust_cloudflare_ip: bool,
+    #[serde(default)]
+    pub policies: HashMap<String, RateLimitPolicy>,
+}
+
+impl Default for RateLimitSettings {
+    fn default() -> Self {
+        Self {
+            redis_timeout_ms: default_redis_timeout_ms(),
+            trust_cloudflare_ip: false,
+            policies: HashMap::new(),
+        }
+    }
+}
+
+fn default_redis_timeout_ms() -> u64 {
+    100
+}
+
+#[derive(Deserialize, Debug, Serialize, Clone)]
+pub struct RateLimitPolicy {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Every key gets its own bucket, a request has to pass all of them.
+    pub keys: Vec<RateLimitKey>,
+    #[serde(flatten)]
+    pub algorithm: RateLimitAlgorithm,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Deserialize, Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(tag = "algorithm", rename_all = "snake_case")]
+pub enum RateLimitAlgorithm {
+    TokenBucket { capacity: u64, refill_per_sec: f64 },
+    SlidingWindow { limit: u64, window_secs: u64 },
+}
+
+impl RateLimitAlgorithm {
+    pub fn limit(&self) -> u64 {
+        match self {
+            Self::TokenBucket { capacity, .. } => *capacity,
+            Self::SlidingWindow { limit, .. } => *limit,
+        }
+    }
+
+    /// The window advertised in `RateLimit-Policy`, for a bucket the time it takes to refill.
+    pub fn window_secs(&self) -> u64 {
+        match self {
+            Self::TokenBucket {
+                capacity,
+                refill_per_sec,
+            } => (*capacity as f64 / refill_per_sec.max(f64::EPSILON)).ceil() as u64,
+            Self::SlidingWindow { window_secs, .. } => *window_secs,
+        }
+    }
+}
+
+/// What a bucket is keyed by. `Email` is only known to the handler after it parsed the form,
+/// so the middleware skips it and the handler enforces it.
+#[derive(Deserialize, Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitKey {
+    Ip,
+    User,
+    ApiToken,
+    Email,
+}
+
+impl RateLimitKey {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ip => "ip",
+            Self::User => "user",
+            Self::ApiToken => "api_token",
+            Self::Email => "email",
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use crate::configuration::application_settings::ApplicationSettings;
 use crate::configuration::database_settings::DatabaseSettings;
+use crate::configuration::rate_limit_settings::RateLimitSettings;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Settings {
     pub database: DatabaseSettings,
     pub application: ApplicationSettings,
+    #[serde(default)]
+    pub rate_limits: RateLimitSettings,
 }
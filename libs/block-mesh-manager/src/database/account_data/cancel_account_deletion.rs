@@ -0,0 +1,19 @@
+use block_mesh_manager_database_domain::domain::account_deletion::AccountDeletionStatus;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Returns false when there was nothing left to cancel.
+#[tracing::instrument(name = "cancel_account_deletion", skip_all)]
+pub async fn cancel_account_deletion(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<bool> {
+    let result = sqlx::query!(
+        r#"DELETE FROM account_deletions WHERE user_id = $1 AND status = $2"#,
+        user_id,
+        AccountDeletionStatus::Scheduled.to_string()
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
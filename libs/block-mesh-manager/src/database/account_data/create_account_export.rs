@@ -0,0 +1,16 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// The worker picks the export up and builds the archive.
+#[tracing::instrument(name = "create_account_export", skip_all)]
+pub async fn create_account_export(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<Uuid> {
+    Ok(sqlx::query_scalar!(
+        r#"INSERT INTO account_exports (user_id) VALUES ($1) RETURNING id"#,
+        user_id
+    )
+    .fetch_one(&mut **transaction)
+    .await?)
+}
@@ -0,0 +1,19 @@
+use block_mesh_manager_database_domain::domain::account_deletion::AccountDeletion;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "get_account_deletion", skip_all)]
+pub async fn get_account_deletion(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<Option<AccountDeletion>> {
+    Ok(sqlx::query_as!(
+        AccountDeletion,
+        r#"SELECT user_id, status, requested_at, scheduled_for, completed_at, last_error
+        FROM account_deletions
+        WHERE user_id = $1"#,
+        user_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await?)
+}
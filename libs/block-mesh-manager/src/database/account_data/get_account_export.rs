@@ -0,0 +1,22 @@
+use block_mesh_manager_database_domain::domain::account_export::AccountExport;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "get_account_export", skip_all)]
+pub async fn get_account_export(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    id: &Uuid,
+) -> anyhow::Result<Option<AccountExport>> {
+    Ok(sqlx::query_as!(
+        AccountExport,
+        r#"SELECT
+        id, user_id, status, blob_key, size_bytes, last_error, created_at, completed_at, expires_at
+        FROM account_exports
+        WHERE id = $1 AND user_id = $2"#,
+        id,
+        user_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await?)
+}
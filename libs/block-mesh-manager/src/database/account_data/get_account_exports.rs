@@ -0,0 +1,25 @@
+use block_mesh_manager_database_domain::domain::account_export::AccountExport;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Newest first.
+#[tracing::instrument(name = "get_account_exports", skip_all)]
+pub async fn get_account_exports(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    limit: i64,
+) -> anyhow::Result<Vec<AccountExport>> {
+    Ok(sqlx::query_as!(
+        AccountExport,
+        r#"SELECT
+        id, user_id, status, blob_key, size_bytes, last_error, created_at, completed_at, expires_at
+        FROM account_exports
+        WHERE user_id = $1
+        ORDER BY created_at DESC
+        LIMIT $2"#,
+        user_id,
+        limit
+    )
+    .fetch_all(&mut **transaction)
+    .await?)
+}
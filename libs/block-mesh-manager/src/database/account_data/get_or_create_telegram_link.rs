@@ -0,0 +1,24 @@
+use block_mesh_manager_database_domain::domain::nonce::Nonce;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// The privacy bot stores the code of every Telegram user that opened it with the link, so
+/// the worker can export and delete those users with the account.
+#[tracing::instrument(name = "get_or_create_telegram_link", skip_all)]
+pub async fn get_or_create_telegram_link(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<String> {
+    Ok(sqlx::query_scalar!(
+        r#"
+        INSERT INTO telegram_links (user_id, link_code)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id) DO UPDATE SET user_id = EXCLUDED.user_id
+        RETURNING link_code
+        "#,
+        user_id,
+        Nonce::generate_nonce(32)
+    )
+    .fetch_one(&mut **transaction)
+    .await?)
+}
@@ -0,0 +1,7 @@
+pub mod cancel_account_deletion;
+pub mod create_account_export;
+pub mod get_account_deletion;
+pub mod get_account_export;
+pub mod get_account_exports;
+pub mod get_or_create_telegram_link;
+pub mod schedule_account_deletion;
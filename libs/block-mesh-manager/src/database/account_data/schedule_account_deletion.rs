@@ -0,0 +1,24 @@
+use block_mesh_manager_database_domain::domain::account_deletion::AccountDeletionStatus;
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Asking again while a deletion is scheduled keeps the original date.
+#[tracing::instrument(name = "schedule_account_deletion", skip_all)]
+pub async fn schedule_account_deletion(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    scheduled_for: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"INSERT INTO account_deletions (user_id, status, scheduled_for)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (user_id) DO NOTHING"#,
+        user_id,
+        AccountDeletionStatus::Scheduled.to_string(),
+        scheduled_for
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use block_mesh_manager_database_domain::domain::api_token::ApiTokenStatus;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "count_active_api_tokens", skip_all)]
+pub async fn count_active_api_tokens(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<i64> {
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM api_tokens
+        WHERE user_id = $1 AND status = $2
+        AND (expires_at IS NULL OR expires_at > now())"#,
+        user_id,
+        ApiTokenStatus::Active.to_string()
+    )
+    .fetch_one(&mut **transaction)
+    .await?;
+    Ok(count)
+}
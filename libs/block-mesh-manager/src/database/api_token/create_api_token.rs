@@ -11,7 +11,7 @@ pub(crate) async fn create_api_token(
     let id = Uuid::new_v4();
     let token = Uuid::new_v4();
     sqlx::query!(
-        r#"INSERT INTO api_tokens (id, created_at, token, status, user_id) VALUES ($1, $2, $3, $4, $5)"#,
+        r#"INSERT INTO api_tokens (id, created_at, token, status, user_id, is_primary) VALUES ($1, $2, $3, $4, $5, true)"#,
         id,
         now,
         token,
@@ -0,0 +1,35 @@
+use crate::database::api_token::get_api_tokens_by_user_id::ApiTokenRow;
+use block_mesh_common::interfaces::server_api::TokenScope;
+use block_mesh_manager_database_domain::domain::api_token::ApiTokenStatus;
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Returns the new row and the token, which is not stored anywhere the user can read it again.
+#[tracing::instrument(name = "create_named_api_token", skip_all)]
+pub async fn create_named_api_token(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    name: &str,
+    scopes: &[TokenScope],
+    expires_at: Option<DateTime<Utc>>,
+) -> anyhow::Result<(ApiTokenRow, Uuid)> {
+    let token = Uuid::new_v4();
+    let scopes: Vec<String> = scopes.iter().map(|scope| scope.to_string()).collect();
+    let row = sqlx::query_as!(
+        ApiTokenRow,
+        r#"INSERT INTO api_tokens (id, created_at, token, status, user_id, name, scopes, expires_at)
+        VALUES ($1, now(), $2, $3, $4, $5, $6, $7)
+        RETURNING id, name, scopes, is_primary, created_at, expires_at, last_used_at, last_used_ip"#,
+        Uuid::new_v4(),
+        token,
+        ApiTokenStatus::Active.to_string(),
+        user_id,
+        name,
+        &scopes,
+        expires_at
+    )
+    .fetch_one(&mut **transaction)
+    .await?;
+    Ok((row, token))
+}
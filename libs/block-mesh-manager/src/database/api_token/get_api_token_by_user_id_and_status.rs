@@ -17,7 +17,7 @@ pub async fn get_api_token_by_usr_and_status(
         user_id,
         token as "token: Secret<Uuid>",
         status as "status: ApiTokenStatus"
-        FROM api_tokens WHERE user_id = $1 and status = $2 LIMIT 1"#,
+        FROM api_tokens WHERE user_id = $1 and status = $2 and is_primary LIMIT 1"#,
         user_id,
         status.to_string()
     )
@@ -0,0 +1,61 @@
+use block_mesh_common::interfaces::server_api::ApiTokenInfo;
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+use std::str::FromStr;
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct ApiTokenRow {
+    pub id: Uuid,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub is_primary: bool,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub last_used_ip: Option<String>,
+}
+
+impl From<ApiTokenRow> for ApiTokenInfo {
+    fn from(row: ApiTokenRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            scopes: row
+                .scopes
+                .iter()
+                .filter_map(|scope| FromStr::from_str(scope).ok())
+                .collect(),
+            is_primary: row.is_primary,
+            created_at: row.created_at,
+            expires_at: row.expires_at,
+            last_used_at: row.last_used_at,
+            last_used_ip: row.last_used_ip,
+        }
+    }
+}
+
+#[tracing::instrument(name = "get_api_tokens_by_user_id", skip_all)]
+pub async fn get_api_tokens_by_user_id(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<Vec<ApiTokenRow>> {
+    Ok(sqlx::query_as!(
+        ApiTokenRow,
+        r#"SELECT
+        id,
+        name,
+        scopes,
+        is_primary,
+        created_at,
+        expires_at,
+        last_used_at,
+        last_used_ip
+        FROM api_tokens
+        WHERE user_id = $1 AND status = 'Active'
+        ORDER BY is_primary DESC, created_at"#,
+        user_id
+    )
+    .fetch_all(&mut **transaction)
+    .await?)
+}
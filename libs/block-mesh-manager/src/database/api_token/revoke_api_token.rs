@@ -0,0 +1,23 @@
+use block_mesh_manager_database_domain::domain::api_token::ApiTokenStatus;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// The primary token can only be rotated, legacy clients have no other token to fall back to.
+#[tracing::instrument(name = "revoke_api_token", skip_all)]
+pub async fn revoke_api_token(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    id: &Uuid,
+) -> anyhow::Result<bool> {
+    let result = sqlx::query!(
+        r#"UPDATE api_tokens SET status = $1
+        WHERE id = $2 AND user_id = $3 AND status = $4 AND NOT is_primary"#,
+        ApiTokenStatus::Inactive.to_string(),
+        id,
+        user_id,
+        ApiTokenStatus::Active.to_string()
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,26 @@
+use crate::database::api_token::get_api_tokens_by_user_id::ApiTokenRow;
+use block_mesh_manager_database_domain::domain::api_token::ApiTokenStatus;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "rotate_api_token", skip_all)]
+pub async fn rotate_api_token(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    id: &Uuid,
+) -> anyhow::Result<Option<(ApiTokenRow, Uuid)>> {
+    let token = Uuid::new_v4();
+    let row = sqlx::query_as!(
+        ApiTokenRow,
+        r#"UPDATE api_tokens SET token = $1, last_used_at = NULL, last_used_ip = NULL
+        WHERE id = $2 AND user_id = $3 AND status = $4
+        RETURNING id, name, scopes, is_primary, created_at, expires_at, last_used_at, last_used_ip"#,
+        token,
+        id,
+        user_id,
+        ApiTokenStatus::Active.to_string()
+    )
+    .fetch_optional(&mut **transaction)
+    .await?;
+    Ok(row.map(|row| (row, token)))
+}
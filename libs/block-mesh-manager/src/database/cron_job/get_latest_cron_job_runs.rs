@@ -0,0 +1,21 @@
+use block_mesh_manager_database_domain::domain::cron_job::CronJobRun;
+use sqlx::{Postgres, Transaction};
+
+#[tracing::instrument(name = "get_latest_cron_job_runs", skip_all)]
+pub async fn get_latest_cron_job_runs(
+    transaction: &mut Transaction<'_, Postgres>,
+    limit: i64,
+) -> anyhow::Result<Vec<CronJobRun>> {
+    Ok(sqlx::query_as!(
+        CronJobRun,
+        r#"
+        SELECT id, name, trigger, status, instance, error, scheduled_for, started_at, finished_at
+        FROM cron_job_runs
+        ORDER BY started_at DESC
+        LIMIT $1
+        "#,
+        limit
+    )
+    .fetch_all(&mut **transaction)
+    .await?)
+}
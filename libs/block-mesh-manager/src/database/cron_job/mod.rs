@@ -0,0 +1,3 @@
+pub mod get_latest_cron_job_runs;
+pub mod trigger_cron_job;
+pub mod update_cron_job_paused;
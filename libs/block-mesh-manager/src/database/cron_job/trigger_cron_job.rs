@@ -0,0 +1,18 @@
+use chrono::Utc;
+use sqlx::{Postgres, Transaction};
+
+#[tracing::instrument(name = "trigger_cron_job", skip_all)]
+pub async fn trigger_cron_job(
+    transaction: &mut Transaction<'_, Postgres>,
+    name: &str,
+) -> anyhow::Result<u64> {
+    let now = Utc::now();
+    let r = sqlx::query!(
+        r#"UPDATE cron_jobs SET triggered_at = $1, updated_at = $1 WHERE name = $2"#,
+        now,
+        name
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(r.rows_affected())
+}
@@ -0,0 +1,20 @@
+use chrono::Utc;
+use sqlx::{Postgres, Transaction};
+
+#[tracing::instrument(name = "update_cron_job_paused", skip_all)]
+pub async fn update_cron_job_paused(
+    transaction: &mut Transaction<'_, Postgres>,
+    name: &str,
+    paused: bool,
+) -> anyhow::Result<u64> {
+    let now = Utc::now();
+    let r = sqlx::query!(
+        r#"UPDATE cron_jobs SET paused = $1, updated_at = $2 WHERE name = $3"#,
+        paused,
+        now,
+        name
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(r.rows_affected())
+}
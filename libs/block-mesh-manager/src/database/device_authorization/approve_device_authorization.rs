@@ -0,0 +1,24 @@
+use crate::domain::device_authorization::DeviceAuthorizationStatus;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "approve_device_authorization", skip_all)]
+pub async fn approve_device_authorization(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: &Uuid,
+    user_id: &Uuid,
+    api_token_id: &Uuid,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"UPDATE device_authorizations
+        SET status = $4, user_id = $2, api_token_id = $3
+        WHERE id = $1"#,
+        id,
+        user_id,
+        api_token_id,
+        DeviceAuthorizationStatus::Approved.to_string()
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
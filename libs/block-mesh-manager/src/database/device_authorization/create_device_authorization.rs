@@ -0,0 +1,30 @@
+use block_mesh_common::interfaces::server_api::TokenScope;
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "create_device_authorization", skip_all)]
+pub async fn create_device_authorization(
+    transaction: &mut Transaction<'_, Postgres>,
+    device_code: &Uuid,
+    user_code: &str,
+    device_name: &str,
+    scopes: &[TokenScope],
+    expires_at: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    let scopes: Vec<String> = scopes.iter().map(|scope| scope.to_string()).collect();
+    sqlx::query!(
+        r#"INSERT INTO device_authorizations
+        (id, device_code, user_code, device_name, scopes, expires_at, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, now())"#,
+        Uuid::new_v4(),
+        device_code,
+        user_code,
+        device_name,
+        &scopes,
+        expires_at
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use crate::domain::device_authorization::DeviceAuthorizationStatus;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "deny_device_authorization", skip_all)]
+pub async fn deny_device_authorization(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: &Uuid,
+    user_id: &Uuid,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"UPDATE device_authorizations SET status = $3, user_id = $2 WHERE id = $1"#,
+        id,
+        user_id,
+        DeviceAuthorizationStatus::Denied.to_string()
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::domain::device_authorization::DeviceAuthorizationStatus;
+use chrono::{DateTime, Utc};
+use secret::Secret;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct DeviceAuthorization {
+    pub id: Uuid,
+    pub status: DeviceAuthorizationStatus,
+    pub expires_at: DateTime<Utc>,
+    pub last_polled_at: Option<DateTime<Utc>>,
+    pub consumed_at: Option<DateTime<Utc>>,
+    pub email: Option<String>,
+    pub token: Option<Secret<Uuid>>,
+}
+
+#[tracing::instrument(name = "get_device_authorization_by_device_code", skip_all)]
+pub async fn get_device_authorization_by_device_code(
+    transaction: &mut Transaction<'_, Postgres>,
+    device_code: &Uuid,
+) -> anyhow::Result<Option<DeviceAuthorization>> {
+    Ok(sqlx::query_as!(
+        DeviceAuthorization,
+        r#"SELECT
+        device_authorizations.id,
+        device_authorizations.status as "status: DeviceAuthorizationStatus",
+        device_authorizations.expires_at,
+        device_authorizations.last_polled_at,
+        device_authorizations.consumed_at,
+        users.email as "email?",
+        api_tokens.token as "token?: Secret<Uuid>"
+        FROM device_authorizations
+        LEFT JOIN users ON users.id = device_authorizations.user_id
+        LEFT JOIN api_tokens ON api_tokens.id = device_authorizations.api_token_id
+        WHERE device_authorizations.device_code = $1
+        FOR UPDATE OF device_authorizations"#,
+        device_code
+    )
+    .fetch_optional(&mut **transaction)
+    .await?)
+}
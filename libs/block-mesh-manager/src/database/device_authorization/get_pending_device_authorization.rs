@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct PendingDeviceAuthorization {
+    pub id: Uuid,
+    pub user_code: String,
+    pub device_name: String,
+    pub scopes: Vec<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Locks the row, so two tabs approving the same code cannot both issue a token.
+#[tracing::instrument(name = "get_pending_device_authorization", skip_all)]
+pub async fn get_pending_device_authorization(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_code: &str,
+) -> anyhow::Result<Option<PendingDeviceAuthorization>> {
+    Ok(sqlx::query_as!(
+        PendingDeviceAuthorization,
+        r#"SELECT id, user_code, device_name, scopes, expires_at
+        FROM device_authorizations
+        WHERE user_code = $1 AND status = 'pending' AND expires_at > now()
+        FOR UPDATE"#,
+        user_code
+    )
+    .fetch_optional(&mut **transaction)
+    .await?)
+}
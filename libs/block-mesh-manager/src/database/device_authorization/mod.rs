@@ -0,0 +1,6 @@
+pub mod approve_device_authorization;
+pub mod create_device_authorization;
+pub mod deny_device_authorization;
+pub mod get_device_authorization_by_device_code;
+pub mod get_pending_device_authorization;
+pub mod update_device_authorization_status;
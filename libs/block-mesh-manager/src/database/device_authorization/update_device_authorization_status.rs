@@ -0,0 +1,25 @@
+use crate::domain::device_authorization::DeviceAuthorizationStatus;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Also records the poll, which is what `slow_down` is measured against, and when the token
+/// was first picked up.
+#[tracing::instrument(name = "update_device_authorization_status", skip_all)]
+pub async fn update_device_authorization_status(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: &Uuid,
+    status: DeviceAuthorizationStatus,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"UPDATE device_authorizations
+        SET status = $2,
+        last_polled_at = now(),
+        consumed_at = CASE WHEN $2 = 'consumed' THEN COALESCE(consumed_at, now()) ELSE consumed_at END
+        WHERE id = $1"#,
+        id,
+        status.to_string()
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use sqlx::{Postgres, Transaction};
+
+#[tracing::instrument(name = "create_email_suppression", skip_all)]
+pub async fn create_email_suppression(
+    transaction: &mut Transaction<'_, Postgres>,
+    email_address: &str,
+    reason: &str,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"INSERT INTO email_suppressions (email_address, reason)
+        VALUES (lower($1), $2)
+        ON CONFLICT (email_address) DO NOTHING"#,
+        email_address,
+        reason
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use block_mesh_common::email_client::template::EmailTemplate;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Queues the email for the worker, it is only sent if the transaction commits.
+#[tracing::instrument(name = "enqueue_email", skip_all)]
+pub async fn enqueue_email(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    email_address: &str,
+    template: &EmailTemplate,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"INSERT INTO email_outbox (user_id, email_address, template, payload)
+        VALUES ($1, $2, $3, $4)"#,
+        user_id,
+        email_address,
+        template.name(),
+        serde_json::to_value(template)?
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
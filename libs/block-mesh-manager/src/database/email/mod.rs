@@ -0,0 +1,4 @@
+pub mod create_email_suppression;
+pub mod enqueue_email;
+pub mod unsubscribe_by_token;
+pub mod update_notification_preferences;
@@ -0,0 +1,26 @@
+use block_mesh_common::email_client::template::EmailCategory;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Turns the category off for the owner of the token, returns false for an unknown token.
+#[tracing::instrument(name = "unsubscribe_by_token", skip_all)]
+pub async fn unsubscribe_by_token(
+    transaction: &mut Transaction<'_, Postgres>,
+    unsubscribe_token: &Uuid,
+    category: EmailCategory,
+) -> anyhow::Result<bool> {
+    let result = match category {
+        EmailCategory::Account => return Ok(false),
+        EmailCategory::ProbeAlerts => {
+            sqlx::query!(
+                r#"UPDATE notification_preferences
+                SET probe_alerts = FALSE, updated_at = now()
+                WHERE unsubscribe_token = $1"#,
+                unsubscribe_token
+            )
+            .execute(&mut **transaction)
+            .await?
+        }
+    };
+    Ok(result.rows_affected() > 0)
+}
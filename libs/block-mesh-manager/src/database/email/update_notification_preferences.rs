@@ -0,0 +1,22 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "update_notification_preferences", skip_all)]
+pub async fn update_notification_preferences(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    probe_alerts: bool,
+    locale: &str,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"UPDATE notification_preferences
+        SET probe_alerts = $2, locale = $3, updated_at = now()
+        WHERE user_id = $1"#,
+        user_id,
+        probe_alerts,
+        locale
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use crate::domain::invite_campaign::InviteCampaign;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Counts a signup against the campaign, `None` when it is outside its dates or out of uses.
+#[tracing::instrument(name = "claim_invite_campaign_use", skip_all)]
+pub async fn claim_invite_campaign_use(
+    transaction: &mut Transaction<'_, Postgres>,
+    campaign_id: &Uuid,
+) -> anyhow::Result<Option<InviteCampaign>> {
+    Ok(sqlx::query_as!(
+        InviteCampaign,
+        r#"UPDATE invite_campaigns
+        SET uses = uses + 1
+        WHERE id = $1
+        AND (starts_at IS NULL OR starts_at <= now())
+        AND (ends_at IS NULL OR ends_at > now())
+        AND (max_uses IS NULL OR uses < max_uses)
+        RETURNING
+        id, name, starts_at, ends_at, max_uses, uses, perk_multiplier, perk_days, created_by, created_at"#,
+        campaign_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await?)
+}
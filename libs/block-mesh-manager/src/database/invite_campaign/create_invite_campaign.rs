@@ -0,0 +1,29 @@
+use crate::domain::invite_campaign::InviteCampaign;
+use block_mesh_common::interfaces::server_api::CreateInviteCampaignRequest;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "create_invite_campaign", skip_all)]
+pub async fn create_invite_campaign(
+    transaction: &mut Transaction<'_, Postgres>,
+    created_by: &Uuid,
+    request: &CreateInviteCampaignRequest,
+) -> anyhow::Result<InviteCampaign> {
+    Ok(sqlx::query_as!(
+        InviteCampaign,
+        r#"INSERT INTO invite_campaigns
+        (name, starts_at, ends_at, max_uses, perk_multiplier, perk_days, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING
+        id, name, starts_at, ends_at, max_uses, uses, perk_multiplier, perk_days, created_by, created_at"#,
+        request.name,
+        request.starts_at,
+        request.ends_at,
+        request.max_uses,
+        request.perk_multiplier,
+        request.perk_days,
+        created_by
+    )
+    .fetch_one(&mut **transaction)
+    .await?)
+}
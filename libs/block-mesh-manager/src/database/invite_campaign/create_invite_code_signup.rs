@@ -0,0 +1,22 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "create_invite_code_signup", skip_all)]
+pub async fn create_invite_code_signup(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    invite_code_id: &Uuid,
+    campaign_id: Option<Uuid>,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"INSERT INTO invite_code_signups (user_id, invite_code_id, campaign_id)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (user_id) DO NOTHING"#,
+        user_id,
+        invite_code_id,
+        campaign_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use crate::domain::invite_campaign::InviteCampaign;
+use sqlx::{Postgres, Transaction};
+
+#[tracing::instrument(name = "get_invite_campaign_by_name", skip_all)]
+pub async fn get_invite_campaign_by_name(
+    transaction: &mut Transaction<'_, Postgres>,
+    name: &str,
+) -> anyhow::Result<Option<InviteCampaign>> {
+    Ok(sqlx::query_as!(
+        InviteCampaign,
+        r#"SELECT
+        id, name, starts_at, ends_at, max_uses, uses, perk_multiplier, perk_days, created_by, created_at
+        FROM invite_campaigns
+        WHERE name = $1"#,
+        name
+    )
+    .fetch_optional(&mut **transaction)
+    .await?)
+}
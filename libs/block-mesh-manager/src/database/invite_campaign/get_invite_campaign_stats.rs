@@ -0,0 +1,48 @@
+use block_mesh_common::interfaces::server_api::InviteCampaignStats;
+use sqlx::{Postgres, Transaction};
+
+/// Signups of every campaign, with how many ran a node and how many still did 7 and 30 days later.
+#[tracing::instrument(name = "get_invite_campaign_stats", skip_all)]
+pub async fn get_invite_campaign_stats(
+    transaction: &mut Transaction<'_, Postgres>,
+) -> anyhow::Result<Vec<InviteCampaignStats>> {
+    Ok(sqlx::query_as!(
+        InviteCampaignStats,
+        r#"SELECT
+        invite_campaigns.id,
+        invite_campaigns.name,
+        invite_campaigns.starts_at,
+        invite_campaigns.ends_at,
+        invite_campaigns.max_uses,
+        invite_campaigns.uses,
+        invite_campaigns.perk_multiplier,
+        invite_campaigns.perk_days,
+        invite_campaigns.created_at,
+        COALESCE(
+            (SELECT array_agg(invite_code ORDER BY created_at) FROM invite_codes
+            WHERE campaign_id = invite_campaigns.id),
+            '{}'
+        ) AS "codes!",
+        COUNT(invite_code_signups.user_id) AS "signups!",
+        COUNT(invite_code_signups.user_id) FILTER (WHERE EXISTS (
+            SELECT 1 FROM daily_stats
+            WHERE daily_stats.user_id = invite_code_signups.user_id AND daily_stats.uptime > 0
+        )) AS "activated!",
+        COUNT(invite_code_signups.user_id) FILTER (WHERE EXISTS (
+            SELECT 1 FROM daily_stats
+            WHERE daily_stats.user_id = invite_code_signups.user_id AND daily_stats.uptime > 0
+            AND daily_stats.day >= (invite_code_signups.created_at + INTERVAL '7 days')::DATE
+        )) AS "retained_7d!",
+        COUNT(invite_code_signups.user_id) FILTER (WHERE EXISTS (
+            SELECT 1 FROM daily_stats
+            WHERE daily_stats.user_id = invite_code_signups.user_id AND daily_stats.uptime > 0
+            AND daily_stats.day >= (invite_code_signups.created_at + INTERVAL '30 days')::DATE
+        )) AS "retained_30d!"
+        FROM invite_campaigns
+        LEFT JOIN invite_code_signups ON invite_code_signups.campaign_id = invite_campaigns.id
+        GROUP BY invite_campaigns.id
+        ORDER BY invite_campaigns.created_at DESC"#
+    )
+    .fetch_all(&mut **transaction)
+    .await?)
+}
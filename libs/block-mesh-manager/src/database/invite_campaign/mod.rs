@@ -0,0 +1,5 @@
+pub mod claim_invite_campaign_use;
+pub mod create_invite_campaign;
+pub mod create_invite_code_signup;
+pub mod get_invite_campaign_by_name;
+pub mod get_invite_campaign_stats;
@@ -0,0 +1,23 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "create_campaign_invite_code", skip_all)]
+pub async fn create_campaign_invite_code(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    invite_code: &str,
+    campaign_id: &Uuid,
+) -> anyhow::Result<Uuid> {
+    let id = Uuid::new_v4();
+    sqlx::query!(
+        r#"INSERT INTO invite_codes (id, created_at, invite_code, user_id, campaign_id)
+        VALUES ($1, now(), $2, $3, $4)"#,
+        id,
+        invite_code,
+        user_id,
+        campaign_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(id)
+}
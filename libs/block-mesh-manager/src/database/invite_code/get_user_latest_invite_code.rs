@@ -2,6 +2,7 @@ use crate::domain::invite_code::InviteCode;
 use sqlx::{Postgres, Transaction};
 use uuid::Uuid;
 
+/// The user's personal code, campaign codes they own are left out.
 pub async fn get_user_latest_invite_code(
     transaction: &mut Transaction<'_, Postgres>,
     user_id: &Uuid,
@@ -12,8 +13,9 @@ pub async fn get_user_latest_invite_code(
         id,
         invite_code,
         user_id,
-        created_at
-        FROM invite_codes WHERE user_id = $1 ORDER BY created_at DESC LIMIT 1"#,
+        created_at,
+        campaign_id
+        FROM invite_codes WHERE user_id = $1 AND campaign_id IS NULL ORDER BY created_at DESC LIMIT 1"#,
         user_id
     )
     .fetch_one(&mut **transaction)
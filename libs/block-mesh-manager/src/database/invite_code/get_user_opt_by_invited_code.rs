@@ -11,7 +11,8 @@ pub async fn get_user_opt_by_invited_code(
         id,
         invite_code,
         user_id,
-        created_at
+        created_at,
+        campaign_id
         FROM invite_codes WHERE invite_code = $1 LIMIT 1"#,
         invite_code
     )
@@ -1,3 +1,4 @@
+pub mod create_campaign_invite_code;
 pub mod create_invite_code;
 pub mod get_number_of_users_invited;
 pub mod get_user_latest_invite_code;
@@ -14,7 +14,13 @@ pub async fn get_daily_leaderboard(
         r#"
         SELECT
             users.email AS email,
-            (uptime * $1 + CAST(tasks_count as DOUBLE PRECISION) * $2) AS points
+            (uptime * $1 + CAST(tasks_count as DOUBLE PRECISION) * $2) + COALESCE((
+                SELECT SUM(referral_earnings.points)
+                FROM referral_earnings
+                WHERE referral_earnings.user_id = daily_stats.user_id
+                AND referral_earnings.day = daily_stats.day
+                AND referral_earnings.status = 'Credited'
+            ), 0) AS points
         FROM
 	        daily_stats
 	        JOIN users ON users.id = daily_stats.user_id
@@ -1,16 +1,26 @@
+pub mod account_data;
 pub mod aggregate;
 pub mod analytics;
 pub mod api_token;
 pub mod bandwidth;
 pub mod call_to_action;
+pub mod cron_job;
 pub mod daily_stat;
+pub mod device_authorization;
+pub mod email;
+pub mod invite_campaign;
 pub mod invite_code;
 pub mod ip_address;
 pub mod leaderboard;
+pub mod node_key;
 pub mod nonce;
 pub mod perks;
+pub mod probe;
 pub mod proxy_master;
+pub mod referral_earning;
 pub mod task;
+pub mod two_factor;
 pub mod uptime_report;
 pub mod user;
 pub mod users_ip;
+pub mod wallet;
@@ -0,0 +1,30 @@
+use chrono::{Duration, Utc};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+pub struct NodeKeyOwner {
+    pub user_id: Uuid,
+    pub public_key: String,
+}
+
+/// Only keys of `user_id`'s own nodes can be targeted, the same ones `get_node_keys` lists.
+/// Keys the node hasn't registered again within `max_age_secs` are treated as gone.
+#[tracing::instrument(name = "get_node_key_by_id", skip_all)]
+pub async fn get_node_key_by_id(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: &Uuid,
+    user_id: &Uuid,
+    max_age_secs: i64,
+) -> anyhow::Result<Option<NodeKeyOwner>> {
+    let since = Utc::now() - Duration::seconds(max_age_secs);
+    let key = sqlx::query_as!(
+        NodeKeyOwner,
+        r#"SELECT user_id, public_key FROM node_keys WHERE id = $1 AND user_id = $2 AND updated_at > $3"#,
+        id,
+        user_id,
+        since
+    )
+    .fetch_optional(&mut **transaction)
+    .await?;
+    Ok(key)
+}
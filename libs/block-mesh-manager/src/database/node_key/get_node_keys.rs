@@ -0,0 +1,30 @@
+use block_mesh_common::interfaces::server_api::NodeKey;
+use chrono::{Duration, Utc};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "get_node_keys", skip_all)]
+pub async fn get_node_keys(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    max_age_secs: i64,
+    limit: i64,
+) -> anyhow::Result<Vec<NodeKey>> {
+    let since = Utc::now() - Duration::seconds(max_age_secs);
+    let keys = sqlx::query_as!(
+        NodeKey,
+        r#"
+        SELECT id, public_key
+        FROM node_keys
+        WHERE user_id = $1 AND updated_at > $2
+        ORDER BY updated_at DESC
+        LIMIT $3
+        "#,
+        user_id,
+        since,
+        limit
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(keys)
+}
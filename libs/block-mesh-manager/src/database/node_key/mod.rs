@@ -0,0 +1,3 @@
+pub mod get_node_key_by_id;
+pub mod get_node_keys;
+pub mod upsert_node_key;
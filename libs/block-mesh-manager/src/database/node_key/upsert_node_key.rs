@@ -0,0 +1,23 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "upsert_node_key", skip_all)]
+pub async fn upsert_node_key(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    public_key: &str,
+) -> anyhow::Result<Uuid> {
+    let id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO node_keys (user_id, public_key)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id, public_key) DO UPDATE SET updated_at = now()
+        RETURNING id
+        "#,
+        user_id,
+        public_key
+    )
+    .fetch_one(&mut **transaction)
+    .await?;
+    Ok(id)
+}
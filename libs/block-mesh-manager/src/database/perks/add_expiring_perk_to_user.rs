@@ -0,0 +1,35 @@
+use crate::domain::perk::PerkName;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Like `add_perk_to_user`, but the perk stops counting at `expires_at`.
+#[tracing::instrument(name = "add_expiring_perk_to_user", skip_all)]
+pub(crate) async fn add_expiring_perk_to_user(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    name: PerkName,
+    multiplier: f64,
+    data: Value,
+    expires_at: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO perks
+        (id, user_id, created_at, name, multiplier, one_time_bonus, data, updated_at, starts_at, expires_at)
+        VALUES
+        (gen_random_uuid(), $1, now(), $2, $3, 0, $4, now(), now(), $5)
+        ON CONFLICT (user_id, name) DO UPDATE
+        SET multiplier = $3, data = $4, updated_at = now(), starts_at = now(), expires_at = $5
+        "#,
+        user_id,
+        name.to_string(),
+        multiplier,
+        data,
+        expires_at
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
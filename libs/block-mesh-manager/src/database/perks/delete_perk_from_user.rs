@@ -0,0 +1,19 @@
+use crate::domain::perk::PerkName;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "delete_perk_from_user", skip_all)]
+pub(crate) async fn delete_perk_from_user(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    name: PerkName,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"DELETE FROM perks WHERE user_id = $1 AND name = $2"#,
+        user_id,
+        name.to_string()
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
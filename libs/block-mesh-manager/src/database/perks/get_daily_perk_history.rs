@@ -0,0 +1,38 @@
+use chrono::NaiveDate;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct DailyPerkHistory {
+    pub day: NaiveDate,
+    pub perk_multiplier: f64,
+    /// Perks that counted, the ones a `Max` group outranked are left out.
+    pub perks: Vec<String>,
+}
+
+/// Perks recorded when each of the user's days was finalized.
+#[tracing::instrument(name = "get_daily_perk_history", skip_all)]
+pub async fn get_daily_perk_history(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<Vec<DailyPerkHistory>> {
+    Ok(sqlx::query_as!(
+        DailyPerkHistory,
+        r#"SELECT
+        daily_stats.day,
+        daily_stats.perk_multiplier AS "perk_multiplier!",
+        COALESCE(
+            array_agg(perk_history.perk_name ORDER BY perk_history.perk_name)
+            FILTER (WHERE perk_history.applied),
+            '{}'
+        ) AS "perks!"
+        FROM daily_stats
+        LEFT JOIN perk_history
+        ON perk_history.user_id = daily_stats.user_id AND perk_history.day = daily_stats.day
+        WHERE daily_stats.user_id = $1 AND daily_stats.perk_multiplier IS NOT NULL
+        GROUP BY daily_stats.day, daily_stats.perk_multiplier"#,
+        user_id
+    )
+    .fetch_all(&mut **transaction)
+    .await?)
+}
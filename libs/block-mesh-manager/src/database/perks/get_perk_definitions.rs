@@ -0,0 +1,18 @@
+use block_mesh_manager_database_domain::domain::perk_definition::PerkDefinition;
+use sqlx::{Postgres, Transaction};
+
+#[tracing::instrument(name = "get_perk_definitions", skip_all)]
+pub async fn get_perk_definitions(
+    transaction: &mut Transaction<'_, Postgres>,
+) -> anyhow::Result<Vec<PerkDefinition>> {
+    Ok(sqlx::query_as!(
+        PerkDefinition,
+        r#"SELECT
+        name, multiplier, one_time_bonus, stacking_group, stacking, max_multiplier, valid_from,
+        valid_until, duration_days, condition, condition_threshold, enabled, created_at, updated_at
+        FROM perk_definitions
+        ORDER BY name"#
+    )
+    .fetch_all(&mut **transaction)
+    .await?)
+}
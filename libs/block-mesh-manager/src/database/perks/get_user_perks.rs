@@ -7,6 +7,7 @@ struct Id {
     id: Uuid,
 }
 
+/// Perks that count right now, outside the catalog's window a perk counts for nothing.
 pub async fn get_user_perks(
     transaction: &mut Transaction<'_, Postgres>,
     user_id: &Uuid,
@@ -15,9 +16,14 @@ pub async fn get_user_perks(
         Perk,
         r#"
         SELECT
-        id, user_id, name, created_at, multiplier, one_time_bonus, data
+        perks.id, perks.user_id, perks.name, perks.created_at, perks.multiplier,
+        perks.one_time_bonus, perks.data, perks.starts_at, perks.expires_at
         FROM perks
-        WHERE user_id = $1
+        LEFT JOIN perk_definitions ON perk_definitions.name = perks.name
+        WHERE perks.user_id = $1
+        AND (perks.expires_at IS NULL OR perks.expires_at > now())
+        AND (perk_definitions.valid_from IS NULL OR perk_definitions.valid_from <= now())
+        AND (perk_definitions.valid_until IS NULL OR perk_definitions.valid_until > now())
         "#,
         user_id
     )
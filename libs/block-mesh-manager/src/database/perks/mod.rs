@@ -1,2 +1,8 @@
+pub mod add_expiring_perk_to_user;
 pub mod add_perk_to_user;
+pub mod delete_perk_from_user;
+pub mod get_daily_perk_history;
+pub mod get_perk_definitions;
+pub mod get_user_perk_effects;
 pub mod get_user_perks;
+pub mod upsert_perk_definition;
@@ -0,0 +1,39 @@
+use block_mesh_common::interfaces::server_api::PerkDefinitionRequest;
+use block_mesh_manager_database_domain::domain::perk_definition::PerkDefinition;
+use sqlx::{Postgres, Transaction};
+
+/// Already granted perks keep their multiplier, the catalog only changes how they stack.
+#[tracing::instrument(name = "upsert_perk_definition", skip_all)]
+pub async fn upsert_perk_definition(
+    transaction: &mut Transaction<'_, Postgres>,
+    request: &PerkDefinitionRequest,
+) -> anyhow::Result<PerkDefinition> {
+    Ok(sqlx::query_as!(
+        PerkDefinition,
+        r#"INSERT INTO perk_definitions
+        (name, multiplier, one_time_bonus, stacking_group, stacking, max_multiplier, valid_from,
+        valid_until, duration_days, condition, condition_threshold, enabled)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        ON CONFLICT (name) DO UPDATE SET
+        multiplier = $2, one_time_bonus = $3, stacking_group = $4, stacking = $5,
+        max_multiplier = $6, valid_from = $7, valid_until = $8, duration_days = $9,
+        condition = $10, condition_threshold = $11, enabled = $12, updated_at = now()
+        RETURNING
+        name, multiplier, one_time_bonus, stacking_group, stacking, max_multiplier, valid_from,
+        valid_until, duration_days, condition, condition_threshold, enabled, created_at, updated_at"#,
+        request.name,
+        request.multiplier,
+        request.one_time_bonus,
+        request.stacking_group,
+        request.stacking,
+        request.max_multiplier,
+        request.valid_from,
+        request.valid_until,
+        request.duration_days,
+        request.condition,
+        request.condition_threshold,
+        request.enabled
+    )
+    .fetch_one(&mut **transaction)
+    .await?)
+}
@@ -0,0 +1,16 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "count_user_probes", skip_all)]
+pub async fn count_user_probes(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<i64> {
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM probes WHERE user_id = $1"#,
+        user_id
+    )
+    .fetch_one(&mut **transaction)
+    .await?;
+    Ok(count)
+}
@@ -0,0 +1,40 @@
+use block_mesh_common::interfaces::server_api::CreateProbeRequest;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "create_probe", skip_all)]
+pub async fn create_probe(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    request: &CreateProbeRequest,
+) -> anyhow::Result<Uuid> {
+    let assertion = match &request.assertion {
+        Some(assertion) => Some(serde_json::to_value(assertion)?),
+        None => None,
+    };
+    let id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO probes
+        (user_id, name, url, method, headers, body, expected_status, assertion, interval_secs,
+         regions, latency_threshold_ms, availability_threshold, alert_email)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        RETURNING id
+        "#,
+        user_id,
+        request.name,
+        request.url,
+        request.method,
+        request.headers,
+        request.body,
+        request.expected_status,
+        assertion,
+        request.interval_secs,
+        &request.regions,
+        request.latency_threshold_ms,
+        request.availability_threshold,
+        request.alert_email
+    )
+    .fetch_one(&mut **transaction)
+    .await?;
+    Ok(id)
+}
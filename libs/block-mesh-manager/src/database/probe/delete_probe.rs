@@ -0,0 +1,18 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "delete_probe", skip_all)]
+pub async fn delete_probe(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    probe_id: &Uuid,
+) -> anyhow::Result<u64> {
+    let r = sqlx::query!(
+        r#"DELETE FROM probes WHERE id = $1 AND user_id = $2"#,
+        probe_id,
+        user_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(r.rows_affected())
+}
@@ -0,0 +1,65 @@
+use block_mesh_common::interfaces::server_api::ProbeSlo;
+use chrono::Utc;
+use sqlx::{Postgres, Transaction};
+use std::time::Duration;
+use uuid::Uuid;
+
+#[tracing::instrument(name = "get_probe_slo_by_region", skip_all)]
+pub async fn get_probe_slo_by_region(
+    transaction: &mut Transaction<'_, Postgres>,
+    probe_id: &Uuid,
+    window_secs: u64,
+) -> anyhow::Result<Vec<ProbeSlo>> {
+    let since = Utc::now() - Duration::from_secs(window_secs);
+    let slo = sqlx::query_as!(
+        ProbeSlo,
+        r#"
+        SELECT
+        country,
+        '' AS "asn!",
+        COUNT(*) AS "total!",
+        AVG(success::int)::float8 AS "availability!",
+        AVG(latency) AS "avg_latency!",
+        percentile_cont(0.95) WITHIN GROUP (ORDER BY latency) AS "p95_latency!"
+        FROM probe_results
+        WHERE probe_id = $1 AND created_at > $2
+        GROUP BY country
+        ORDER BY country
+        "#,
+        probe_id,
+        since
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(slo)
+}
+
+#[tracing::instrument(name = "get_probe_slo_by_asn", skip_all)]
+pub async fn get_probe_slo_by_asn(
+    transaction: &mut Transaction<'_, Postgres>,
+    probe_id: &Uuid,
+    window_secs: u64,
+) -> anyhow::Result<Vec<ProbeSlo>> {
+    let since = Utc::now() - Duration::from_secs(window_secs);
+    let slo = sqlx::query_as!(
+        ProbeSlo,
+        r#"
+        SELECT
+        '' AS "country!",
+        asn,
+        COUNT(*) AS "total!",
+        AVG(success::int)::float8 AS "availability!",
+        AVG(latency) AS "avg_latency!",
+        percentile_cont(0.95) WITHIN GROUP (ORDER BY latency) AS "p95_latency!"
+        FROM probe_results
+        WHERE probe_id = $1 AND created_at > $2
+        GROUP BY asn
+        ORDER BY asn
+        "#,
+        probe_id,
+        since
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(slo)
+}
@@ -0,0 +1,42 @@
+use block_mesh_manager_database_domain::domain::probe::Probe;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "get_user_probes", skip_all)]
+pub async fn get_user_probes(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<Vec<Probe>> {
+    let probes = sqlx::query_as!(
+        Probe,
+        r#"
+        SELECT
+        id,
+        user_id,
+        name,
+        url,
+        method,
+        headers,
+        body,
+        expected_status,
+        assertion,
+        interval_secs,
+        regions,
+        latency_threshold_ms,
+        availability_threshold,
+        alert_email,
+        enabled,
+        last_scheduled_at,
+        last_alerted_at,
+        created_at,
+        updated_at
+        FROM probes
+        WHERE user_id = $1
+        ORDER BY created_at
+        "#,
+        user_id
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(probes)
+}
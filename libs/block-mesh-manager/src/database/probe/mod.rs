@@ -0,0 +1,5 @@
+pub mod count_user_probes;
+pub mod create_probe;
+pub mod delete_probe;
+pub mod get_probe_slo;
+pub mod get_user_probes;
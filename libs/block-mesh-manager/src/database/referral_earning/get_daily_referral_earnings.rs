@@ -0,0 +1,25 @@
+use block_mesh_common::interfaces::server_api::ReferralEarningForDashboard;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Credited referral points summed per day, newest first.
+#[tracing::instrument(name = "get_daily_referral_earnings", skip_all)]
+pub async fn get_daily_referral_earnings(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<Vec<ReferralEarningForDashboard>> {
+    Ok(sqlx::query_as!(
+        ReferralEarningForDashboard,
+        r#"SELECT
+        day,
+        COALESCE(SUM(points) FILTER (WHERE level = 1), 0) AS "level_1_points!",
+        COALESCE(SUM(points) FILTER (WHERE level = 2), 0) AS "level_2_points!"
+        FROM referral_earnings
+        WHERE user_id = $1 AND status = 'Credited'
+        GROUP BY day
+        ORDER BY day DESC"#,
+        user_id
+    )
+    .fetch_all(&mut **transaction)
+    .await?)
+}
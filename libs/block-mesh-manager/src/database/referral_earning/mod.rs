@@ -0,0 +1 @@
+pub mod get_daily_referral_earnings;
@@ -0,0 +1,40 @@
+use block_mesh_manager_database_domain::domain::task::TaskStatus;
+use block_mesh_manager_database_domain::domain::task_blob::offload_task_body;
+use chrono::Utc;
+use serde_json::Value;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "create_encrypted_task", skip_all)]
+pub async fn create_encrypted_task(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    assigned_user_id: &Uuid,
+    node_key_id: &Uuid,
+    encrypted_payload: String,
+    reply_key: &str,
+) -> anyhow::Result<Uuid> {
+    let now = Utc::now();
+    let id = Uuid::new_v4();
+    let (body, body_ref) =
+        offload_task_body(transaction, Some(Value::String(encrypted_payload))).await?;
+    sqlx::query!(
+        r#"INSERT
+           INTO tasks
+           (id, created_at, url, method, body, body_ref, status, user_id, assigned_user_id, node_key_id, encrypted, reply_key)
+           VALUES
+           ($1, $2, '', '', $3, $4, $5, $6, $7, $8, TRUE, $9)"#,
+        id,
+        now,
+        body,
+        body_ref,
+        TaskStatus::Assigned.to_string(),
+        user_id,
+        assigned_user_id,
+        node_key_id,
+        reply_key
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(id)
+}
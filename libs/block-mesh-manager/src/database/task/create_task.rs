@@ -1,5 +1,6 @@
 use block_mesh_manager_database_domain::domain::task::TaskMethod;
 use block_mesh_manager_database_domain::domain::task::TaskStatus;
+use block_mesh_manager_database_domain::domain::task_blob::offload_task_body;
 use chrono::Utc;
 use sqlx::types::JsonValue;
 use sqlx::{Postgres, Transaction};
@@ -15,18 +16,20 @@ pub async fn create_task(
 ) -> anyhow::Result<Uuid> {
     let now = Utc::now();
     let id = Uuid::new_v4();
+    let (body, body_ref) = offload_task_body(transaction, body).await?;
     sqlx::query!(
         r#"INSERT
            INTO tasks
-           (id, created_at, url, method, headers, body, status, user_id)
+           (id, created_at, url, method, headers, body, body_ref, status, user_id)
            VALUES
-           ($1, $2, $3, $4, $5, $6, $7, $8)"#,
+           ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#,
         id,
         now,
         url,
         method.to_string(),
         headers,
         body,
+        body_ref,
         TaskStatus::Pending.to_string(),
         user_id
     )
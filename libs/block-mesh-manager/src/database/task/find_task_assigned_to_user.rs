@@ -6,6 +6,7 @@ use uuid::Uuid;
 pub async fn find_task_assigned_to_user(
     transaction: &mut Transaction<'_, Postgres>,
     user_id: &Uuid,
+    node_key_id: Option<Uuid>,
 ) -> anyhow::Result<Option<GetTask>> {
     let task = sqlx::query_as!(
         GetTask,
@@ -15,13 +16,18 @@ pub async fn find_task_assigned_to_user(
         url,
         method,
         headers,
-        body
+        body,
+        body_ref,
+        encrypted,
+        reply_key
         FROM tasks
         WHERE status = $1 AND assigned_user_id = $2
+        AND (node_key_id IS NULL OR node_key_id = $3)
         LIMIT 1
         "#,
         TaskStatus::Assigned.to_string(),
-        user_id
+        user_id,
+        node_key_id
     )
     .fetch_optional(&mut **transaction)
     .await?;
@@ -5,6 +5,7 @@ use sqlx::{Postgres, Transaction};
 pub async fn find_task_by_status(
     transaction: &mut Transaction<'_, Postgres>,
     status: TaskStatus,
+    country: &str,
 ) -> anyhow::Result<Option<GetTask>> {
     let task = sqlx::query_as!(
         GetTask,
@@ -14,12 +15,17 @@ pub async fn find_task_by_status(
         url,
         method,
         headers,
-        body
+        body,
+        body_ref,
+        encrypted,
+        reply_key
         FROM tasks
         WHERE status = $1
+        AND (region IS NULL OR region = $2)
         LIMIT 1
         "#,
-        status.to_string()
+        status.to_string(),
+        country
     )
     .fetch_optional(&mut **transaction)
     .await?;
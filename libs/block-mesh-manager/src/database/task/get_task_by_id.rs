@@ -16,10 +16,12 @@ pub async fn get_task_by_user_id(
         method,
         headers,
         body,
+        body_ref,
         assigned_user_id,
         status,
         response_code,
         response_raw,
+        response_ref,
         created_at,
         retries_count,
         country,
@@ -0,0 +1,33 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+pub struct TaskResult {
+    pub id: Uuid,
+    pub status: String,
+    pub response_code: Option<i32>,
+    pub response_raw: Option<String>,
+    pub response_ref: Option<String>,
+    pub encrypted: bool,
+}
+
+#[tracing::instrument(name = "get_task_result", skip_all)]
+pub async fn get_task_result(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    task_id: &Uuid,
+) -> anyhow::Result<Option<TaskResult>> {
+    let task = sqlx::query_as!(
+        TaskResult,
+        r#"
+        SELECT id, status, response_code, response_raw, response_ref, encrypted
+        FROM tasks
+        WHERE id = $1 AND user_id = $2
+        LIMIT 1
+        "#,
+        task_id,
+        user_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await?;
+    Ok(task)
+}
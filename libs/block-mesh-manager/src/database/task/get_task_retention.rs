@@ -0,0 +1,16 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "get_task_retention", skip_all)]
+pub async fn get_task_retention(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<Option<i32>> {
+    let retention_days = sqlx::query_scalar!(
+        r#"SELECT retention_days FROM task_retention WHERE user_id = $1"#,
+        user_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await?;
+    Ok(retention_days)
+}
@@ -1,17 +1,8 @@
-use block_mesh_manager_database_domain::domain::task::TaskStatus;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use sqlx::{Postgres, Transaction};
 use std::time::Duration;
-
-#[derive(sqlx::FromRow, Debug, Serialize, Deserialize)]
-pub struct TmpRpcResults {
-    pub url: Option<String>,
-    pub country: String,
-    pub response_code: Option<i32>,
-    pub latency: Option<f64>,
-    pub count: Option<i64>,
-}
+use uuid::Uuid;
 
 #[derive(sqlx::FromRow, Debug, Serialize, Deserialize)]
 pub struct RpcResults {
@@ -25,54 +16,33 @@ pub struct RpcResults {
 
 pub async fn get_tasks_rpc_results(
     transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
     duration: u64,
 ) -> anyhow::Result<Vec<RpcResults>> {
     let now = Utc::now();
     let duration = now - Duration::from_secs(duration);
-    let status = [TaskStatus::Failed, TaskStatus::Completed]
-        .iter()
-        .map(|s| s.to_string())
-        .collect::<Vec<String>>();
-
     let rpc_results = sqlx::query_as!(
-        TmpRpcResults,
+        RpcResults,
         r#"
         SELECT
-        	regexp_replace(url, '\?.*$', '') as url,
-        	country,
-        	response_code,
-        	AVG(response_time) as latency,
-        	COALESCE(COUNT(*), 0) as count
-        FROM tasks
+        	COALESCE(substring(probes.url FROM '^[a-z]+://[^/?]+'), '') AS "url!",
+        	probe_results.country,
+        	COALESCE(probe_results.response_code, 0) AS "response_code!",
+        	COALESCE(AVG(probe_results.latency), 0) AS "latency!",
+        	COUNT(*) AS "count!",
+        	probes.name AS provider
+        FROM probe_results
+        JOIN probes ON probes.id = probe_results.probe_id
         WHERE
-            created_at > $1
+            probes.user_id = $1
         AND
-        	status = ANY($2::text[])
-        GROUP BY url, country, response_code
+            probe_results.created_at > $2
+        GROUP BY probes.name, probes.url, probe_results.country, probe_results.response_code
         "#,
-        duration,
-        &status
+        user_id,
+        duration
     )
     .fetch_all(&mut **transaction)
     .await?;
-    Ok(rpc_results
-        .into_iter()
-        .filter_map(|i| {
-            let rpc_results = RpcResults {
-                url: i.url.clone().unwrap_or_default(),
-                country: i.country,
-                response_code: i.response_code.unwrap_or_default(),
-                latency: i.latency.unwrap_or_default(),
-                count: i.count.unwrap_or_default(),
-                provider: "N/A".to_string(), // RpcName::from_url(&i.url.clone().unwrap_or_default()).to_string(),
-            };
-            if rpc_results.provider == *"Invalid" // RpcName::Invalid.to_string()
-                || rpc_results.provider.is_empty()
-            {
-                None
-            } else {
-                Some(rpc_results)
-            }
-        })
-        .collect())
+    Ok(rpc_results)
 }
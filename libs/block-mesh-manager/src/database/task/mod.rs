@@ -1,11 +1,15 @@
 pub mod count_user_tasks_by_status;
 pub mod count_user_tasks_in_period;
 pub mod count_user_tasks_in_period_with_status;
+pub mod create_encrypted_task;
 pub mod create_task;
 pub mod find_task_assigned_to_user;
 pub mod find_task_by_excluded_user_id_and_status;
 pub mod find_task_by_status;
 pub mod get_task_by_id;
+pub mod get_task_result;
+pub mod get_task_retention;
 pub mod get_tasks_by_user_id;
 pub mod get_tasks_rpc_results;
 pub mod update_task_assigned;
+pub mod upsert_task_retention;
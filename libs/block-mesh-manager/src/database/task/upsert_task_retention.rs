@@ -0,0 +1,22 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "upsert_task_retention", skip_all)]
+pub async fn upsert_task_retention(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    retention_days: i32,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO task_retention (user_id, retention_days)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id) DO UPDATE SET retention_days = $2, updated_at = now()
+        "#,
+        user_id,
+        retention_days
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "create_remembered_device", skip_all)]
+pub async fn create_remembered_device(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    token_hash: &str,
+    expires_at: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"INSERT INTO two_factor_remembered_devices (user_id, token_hash, expires_at)
+        VALUES ($1, $2, $3)"#,
+        user_id,
+        token_hash,
+        expires_at
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
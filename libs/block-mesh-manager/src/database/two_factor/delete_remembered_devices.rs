@@ -0,0 +1,16 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "delete_remembered_devices", skip_all)]
+pub async fn delete_remembered_devices(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"DELETE FROM two_factor_remembered_devices WHERE user_id = $1"#,
+        user_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
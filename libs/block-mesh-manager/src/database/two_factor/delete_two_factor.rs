@@ -0,0 +1,29 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Removes the secret together with its recovery codes and remembered devices.
+#[tracing::instrument(name = "delete_two_factor", skip_all)]
+pub async fn delete_two_factor(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"DELETE FROM two_factor_recovery_codes WHERE user_id = $1"#,
+        user_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+    sqlx::query!(
+        r#"DELETE FROM two_factor_remembered_devices WHERE user_id = $1"#,
+        user_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+    sqlx::query!(
+        r#"DELETE FROM two_factor_secrets WHERE user_id = $1"#,
+        user_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
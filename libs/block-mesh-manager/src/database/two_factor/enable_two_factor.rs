@@ -0,0 +1,20 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "enable_two_factor", skip_all)]
+pub async fn enable_two_factor(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    step: i64,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"UPDATE two_factor_secrets
+        SET enabled_at = now(), last_used_step = $2
+        WHERE user_id = $1"#,
+        user_id,
+        step
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct TwoFactorSecret {
+    pub secret: String,
+    pub enabled_at: Option<DateTime<Utc>>,
+    pub last_used_step: Option<i64>,
+    pub failed_attempts: i32,
+    pub locked_until: Option<DateTime<Utc>>,
+}
+
+/// Locks the row, so the same code cannot be accepted by two concurrent requests.
+#[tracing::instrument(name = "get_two_factor_secret", skip_all)]
+pub async fn get_two_factor_secret(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<Option<TwoFactorSecret>> {
+    Ok(sqlx::query_as!(
+        TwoFactorSecret,
+        r#"SELECT secret, enabled_at, last_used_step, failed_attempts, locked_until
+        FROM two_factor_secrets
+        WHERE user_id = $1
+        FOR UPDATE"#,
+        user_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await?)
+}
@@ -0,0 +1,27 @@
+use block_mesh_common::interfaces::server_api::TwoFactorStatus;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "get_two_factor_status", skip_all)]
+pub async fn get_two_factor_status(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<TwoFactorStatus> {
+    Ok(sqlx::query_as!(
+        TwoFactorStatus,
+        r#"SELECT
+        EXISTS (
+            SELECT 1 FROM two_factor_secrets WHERE user_id = $1 AND enabled_at IS NOT NULL
+        ) AS "enabled!",
+        (
+            SELECT COUNT(*) FROM two_factor_recovery_codes WHERE user_id = $1 AND used_at IS NULL
+        ) AS "recovery_codes_left!",
+        (
+            SELECT COUNT(*) FROM two_factor_remembered_devices WHERE user_id = $1 AND expires_at > now()
+        ) AS "remembered_devices!"
+        "#,
+        user_id
+    )
+    .fetch_one(&mut **transaction)
+    .await?)
+}
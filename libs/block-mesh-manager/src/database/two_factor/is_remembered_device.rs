@@ -0,0 +1,20 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "is_remembered_device", skip_all)]
+pub async fn is_remembered_device(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    token_hash: &str,
+) -> anyhow::Result<bool> {
+    Ok(sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+            SELECT 1 FROM two_factor_remembered_devices
+            WHERE user_id = $1 AND token_hash = $2 AND expires_at > now()
+        ) AS "exists!""#,
+        user_id,
+        token_hash
+    )
+    .fetch_one(&mut **transaction)
+    .await?)
+}
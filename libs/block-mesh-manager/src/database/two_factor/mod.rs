@@ -0,0 +1,13 @@
+pub mod create_remembered_device;
+pub mod delete_remembered_devices;
+pub mod delete_two_factor;
+pub mod enable_two_factor;
+pub mod get_two_factor_secret;
+pub mod get_two_factor_status;
+pub mod is_remembered_device;
+pub mod record_two_factor_failure;
+pub mod replace_recovery_codes;
+pub mod reset_two_factor_failures;
+pub mod update_two_factor_last_used_step;
+pub mod upsert_two_factor_secret;
+pub mod use_recovery_code;
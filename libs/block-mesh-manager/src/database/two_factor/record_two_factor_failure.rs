@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Counts a wrong code, the `max_attempts`-th one locks 2FA until `locked_until` and starts over.
+#[tracing::instrument(name = "record_two_factor_failure", skip_all)]
+pub async fn record_two_factor_failure(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    max_attempts: i32,
+    locked_until: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"UPDATE two_factor_secrets
+        SET
+            failed_attempts = CASE WHEN failed_attempts + 1 >= $2 THEN 0 ELSE failed_attempts + 1 END,
+            locked_until = CASE WHEN failed_attempts + 1 >= $2 THEN $3 ELSE locked_until END
+        WHERE user_id = $1"#,
+        user_id,
+        max_attempts,
+        locked_until
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "replace_recovery_codes", skip_all)]
+pub async fn replace_recovery_codes(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    code_hashes: &[String],
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"DELETE FROM two_factor_recovery_codes WHERE user_id = $1"#,
+        user_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+    sqlx::query!(
+        r#"INSERT INTO two_factor_recovery_codes (user_id, code_hash)
+        SELECT $1, code_hash FROM UNNEST($2::TEXT[]) AS code_hash"#,
+        user_id,
+        code_hashes
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "reset_two_factor_failures", skip_all)]
+pub async fn reset_two_factor_failures(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"UPDATE two_factor_secrets SET failed_attempts = 0, locked_until = NULL WHERE user_id = $1"#,
+        user_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Returns `false` if the step was already used, so a code is accepted once even across requests.
+#[tracing::instrument(name = "update_two_factor_last_used_step", skip_all)]
+pub async fn update_two_factor_last_used_step(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    step: i64,
+) -> anyhow::Result<bool> {
+    let result = sqlx::query!(
+        r#"UPDATE two_factor_secrets
+        SET last_used_step = $2, failed_attempts = 0, locked_until = NULL
+        WHERE user_id = $1 AND (last_used_step IS NULL OR last_used_step < $2)"#,
+        user_id,
+        step
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,22 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Starts or restarts enrolment, returns `false` when two factor is already enabled.
+#[tracing::instrument(name = "upsert_two_factor_secret", skip_all)]
+pub async fn upsert_two_factor_secret(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    secret: &str,
+) -> anyhow::Result<bool> {
+    let result = sqlx::query!(
+        r#"INSERT INTO two_factor_secrets (user_id, secret)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id) DO UPDATE SET secret = $2, last_used_step = NULL, created_at = now()
+        WHERE two_factor_secrets.enabled_at IS NULL"#,
+        user_id,
+        secret
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,21 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Marks the code as used, returns `false` if it does not exist or was used before.
+#[tracing::instrument(name = "use_recovery_code", skip_all)]
+pub async fn use_recovery_code(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    code_hash: &str,
+) -> anyhow::Result<bool> {
+    let result = sqlx::query!(
+        r#"UPDATE two_factor_recovery_codes
+        SET used_at = now()
+        WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL"#,
+        user_id,
+        code_hash
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
@@ -48,7 +48,7 @@ pub async fn create_test_user(pool: &PgPool) -> anyhow::Result<()> {
         .fetch_one(pool)
         .await?;
     sqlx::query!(
-        r#"INSERT INTO api_tokens (id, created_at, token, status, user_id) VALUES ($1, $2, $3, $4, $5)"#,
+        r#"INSERT INTO api_tokens (id, created_at, token, status, user_id, is_primary) VALUES ($1, $2, $3, $4, $5, true)"#,
         user_id,
         now,
         token,
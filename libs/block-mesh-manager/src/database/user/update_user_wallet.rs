@@ -4,7 +4,7 @@ use uuid::Uuid;
 pub(crate) async fn update_user_wallet(
     transaction: &mut Transaction<'_, Postgres>,
     user_id: Uuid,
-    wallet: &str,
+    wallet: Option<&str>,
 ) -> Result<(), sqlx::Error> {
     sqlx::query!(
         r#"UPDATE users SET wallet_address = $1 WHERE id = $2"#,
@@ -0,0 +1,30 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// The first account a wallet is linked to keeps its perk grant, relinking there grants it again.
+#[tracing::instrument(name = "claim_wallet_perk", skip_all)]
+pub async fn claim_wallet_perk(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    address: &str,
+) -> anyhow::Result<bool> {
+    let owner = sqlx::query_scalar!(
+        r#"
+        WITH inserted AS (
+            INSERT INTO wallet_perk_grants (address, user_id)
+            VALUES ($1, $2)
+            ON CONFLICT (address) DO NOTHING
+            RETURNING user_id
+        )
+        SELECT user_id AS "user_id!" FROM inserted
+        UNION ALL
+        SELECT user_id FROM wallet_perk_grants WHERE address = $1
+        LIMIT 1
+        "#,
+        address,
+        user_id
+    )
+    .fetch_one(&mut **transaction)
+    .await?;
+    Ok(owner == *user_id)
+}
@@ -0,0 +1,20 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Returns `false` when the address is already linked, to this or another user.
+#[tracing::instrument(name = "create_user_wallet", skip_all)]
+pub async fn create_user_wallet(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    address: &str,
+) -> anyhow::Result<bool> {
+    let result = sqlx::query!(
+        r#"INSERT INTO user_wallets (user_id, address) VALUES ($1, $2)
+        ON CONFLICT (address) DO NOTHING"#,
+        user_id,
+        address
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,25 @@
+use block_mesh_manager_database_domain::domain::aggregate::AggregateName;
+use serde_json::Value;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Every change gets its own `WalletChange_<id>` aggregate, so the rows form the history.
+#[tracing::instrument(name = "create_wallet_change", skip_all)]
+pub async fn create_wallet_change(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    value: &Value,
+) -> anyhow::Result<()> {
+    let id = Uuid::new_v4();
+    sqlx::query!(
+        r#"INSERT INTO aggregates (id, created_at, user_id, name, value, updated_at)
+        VALUES ($1, now(), $2, $3, $4, now())"#,
+        id,
+        user_id,
+        format!("{}_{}", AggregateName::WalletChange, id),
+        value
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
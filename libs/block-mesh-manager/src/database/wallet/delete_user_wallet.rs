@@ -0,0 +1,18 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "delete_user_wallet", skip_all)]
+pub async fn delete_user_wallet(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    address: &str,
+) -> anyhow::Result<bool> {
+    let result = sqlx::query!(
+        r#"DELETE FROM user_wallets WHERE user_id = $1 AND address = $2"#,
+        user_id,
+        address
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
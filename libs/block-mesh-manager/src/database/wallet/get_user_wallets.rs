@@ -0,0 +1,22 @@
+use block_mesh_common::interfaces::server_api::UserWalletInfo;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Newest first, locked so concurrent unlinks see the same list.
+#[tracing::instrument(name = "get_user_wallets", skip_all)]
+pub async fn get_user_wallets(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<Vec<UserWalletInfo>> {
+    Ok(sqlx::query_as!(
+        UserWalletInfo,
+        r#"SELECT address, created_at AS linked_at
+        FROM user_wallets
+        WHERE user_id = $1
+        ORDER BY created_at DESC
+        FOR UPDATE"#,
+        user_id
+    )
+    .fetch_all(&mut **transaction)
+    .await?)
+}
@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+pub struct WalletChangeRow {
+    pub value: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[tracing::instrument(name = "get_wallet_changes", skip_all)]
+pub async fn get_wallet_changes(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    limit: i64,
+) -> anyhow::Result<Vec<WalletChangeRow>> {
+    Ok(sqlx::query_as!(
+        WalletChangeRow,
+        r#"SELECT value, created_at
+        FROM aggregates
+        WHERE user_id = $1 AND name LIKE 'WalletChange\_%'
+        ORDER BY created_at DESC
+        LIMIT $2"#,
+        user_id,
+        limit
+    )
+    .fetch_all(&mut **transaction)
+    .await?)
+}
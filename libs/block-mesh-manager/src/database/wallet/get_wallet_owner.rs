@@ -0,0 +1,15 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[tracing::instrument(name = "get_wallet_owner", skip_all)]
+pub async fn get_wallet_owner(
+    transaction: &mut Transaction<'_, Postgres>,
+    address: &str,
+) -> anyhow::Result<Option<Uuid>> {
+    Ok(sqlx::query_scalar!(
+        r#"SELECT user_id FROM user_wallets WHERE address = $1"#,
+        address
+    )
+    .fetch_optional(&mut **transaction)
+    .await?)
+}
@@ -0,0 +1,7 @@
+pub mod claim_wallet_perk;
+pub mod create_user_wallet;
+pub mod create_wallet_change;
+pub mod delete_user_wallet;
+pub mod get_user_wallets;
+pub mod get_wallet_changes;
+pub mod get_wallet_owner;
@@ -0,0 +1,125 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Postgres};
+use std::error::Error;
+use std::fmt::Display;
+
+/// `Consumed` once the device picked up its token, a device code is only good for one token.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+pub enum DeviceAuthorizationStatus {
+    Pending,
+    Approved,
+    Denied,
+    Consumed,
+}
+
+impl sqlx::Type<Postgres> for DeviceAuthorizationStatus {
+    fn type_info() -> sqlx::postgres::PgTypeInfo {
+        <String as sqlx::Type<Postgres>>::type_info()
+    }
+}
+
+impl sqlx::Encode<'_, Postgres> for DeviceAuthorizationStatus {
+    fn encode_by_ref(
+        &self,
+        buf: &mut <Postgres as sqlx::database::HasArguments<'_>>::ArgumentBuffer,
+    ) -> sqlx::encode::IsNull {
+        <String as sqlx::Encode<Postgres>>::encode(self.to_string(), buf)
+    }
+}
+
+impl sqlx::Decode<'_, Postgres> for DeviceAuthorizationStatus {
+    fn decode(
+        value: <Postgres as sqlx::database::HasValueRef<'_>>::ValueRef,
+    ) -> Result<Self, Box<dyn Error + 'static + Send + Sync>> {
+        let value = <&str as Decode<Postgres>>::decode(value)?;
+        let value = value.to_string();
+        Ok(Self::from(value))
+    }
+}
+
+impl Display for DeviceAuthorizationStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeviceAuthorizationStatus::Pending => write!(f, "pending"),
+            DeviceAuthorizationStatus::Approved => write!(f, "approved"),
+            DeviceAuthorizationStatus::Denied => write!(f, "denied"),
+            DeviceAuthorizationStatus::Consumed => write!(f, "consumed"),
+        }
+    }
+}
+
+impl From<String> for DeviceAuthorizationStatus {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "pending" => DeviceAuthorizationStatus::Pending,
+            "approved" => DeviceAuthorizationStatus::Approved,
+            "denied" => DeviceAuthorizationStatus::Denied,
+            _ => DeviceAuthorizationStatus::Consumed,
+        }
+    }
+}
+
+const USER_CODE_CHARSET: &[u8] = b"BCDFGHJKLMNPQRSTVWXZ";
+
+/// Eight consonants as `XXXX-XXXX`, easy to read out and never spelling a word.
+pub fn generate_user_code() -> String {
+    let mut rng = rand::thread_rng();
+    let mut code: String = (0..8)
+        .map(|_| USER_CODE_CHARSET[rng.gen_range(0..USER_CODE_CHARSET.len())] as char)
+        .collect();
+    code.insert(4, '-');
+    code
+}
+
+/// Accepts what users type, lower case and with or without the dash.
+pub fn normalize_user_code(input: &str) -> String {
+    let mut code: String = input
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    if code.len() == 8 {
+        code.insert(4, '-');
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_codes_are_two_groups_of_consonants() {
+        for _ in 0..100 {
+            let code = generate_user_code();
+            assert_eq!(code.len(), 9, "{code}");
+            assert_eq!(code.find('-'), Some(4), "{code}");
+            assert!(code
+                .bytes()
+                .filter(|c| *c != b'-')
+                .all(|c| USER_CODE_CHARSET.contains(&c)));
+            assert_eq!(normalize_user_code(&code), code);
+        }
+    }
+
+    #[test]
+    fn normalize_user_code_accepts_what_users_type() {
+        for input in [
+            "BCDF-GHJK",
+            "bcdf-ghjk",
+            "bcdfghjk",
+            " bcdf ghjk ",
+            "BCDF_GHJK",
+        ] {
+            assert_eq!(normalize_user_code(input), "BCDF-GHJK", "{input}");
+        }
+    }
+
+    #[test]
+    fn normalize_user_code_leaves_wrong_lengths_without_a_dash() {
+        assert_eq!(normalize_user_code("bcd-fgh"), "BCDFGH");
+        assert_eq!(normalize_user_code("bcdf-ghjkl"), "BCDFGHJKL");
+        assert_eq!(normalize_user_code(""), "");
+    }
+}
@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, Clone)]
+pub struct InviteCampaign {
+    pub id: Uuid,
+    pub name: String,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    /// Signups allowed across all of the campaign's codes, unlimited when empty.
+    pub max_uses: Option<i32>,
+    pub uses: i32,
+    /// Multiplier granted to every signup as a campaign perk, for `perk_days` days.
+    pub perk_multiplier: Option<f64>,
+    pub perk_days: Option<i32>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
@@ -8,4 +8,28 @@ pub struct InviteCode {
     pub invite_code: String,
     pub user_id: Uuid,
     pub created_at: DateTime<Utc>,
+    pub campaign_id: Option<Uuid>,
+}
+
+/// Codes users and partners pick themselves, generated codes are UUIDs.
+pub fn is_valid_vanity_code(invite_code: &str) -> bool {
+    (3..=32).contains(&invite_code.len()) && invite_code.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_vanity_code() {
+        assert!(is_valid_vanity_code("abc"));
+        assert!(is_valid_vanity_code("Partner2024"));
+        assert!(is_valid_vanity_code(&"a".repeat(32)));
+        assert!(!is_valid_vanity_code("ab"));
+        assert!(!is_valid_vanity_code(&"a".repeat(33)));
+        assert!(!is_valid_vanity_code("with-dash"));
+        assert!(!is_valid_vanity_code("with space"));
+        assert!(!is_valid_vanity_code("ünï"));
+        assert!(!is_valid_vanity_code(""));
+    }
 }
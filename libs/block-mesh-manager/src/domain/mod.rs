@@ -1,10 +1,14 @@
 pub mod bandwidth_report;
 pub mod call_to_action;
+pub mod device_authorization;
+pub mod invite_campaign;
 pub mod invite_code;
 pub mod ip_address;
 pub mod password;
 pub mod perk;
 pub mod provider_master_status;
 pub mod proxy_master;
+pub mod two_factor;
 pub mod uptime_report;
 pub mod users_ip;
+pub mod wallet;
@@ -11,6 +11,9 @@ pub enum PerkName {
     Wallet,
     Twitter,
     FounderTwitter,
+    Campaign,
+    /// Any other perk of the catalog, by name.
+    Catalog(String),
     Invalid,
 }
 
@@ -20,6 +23,8 @@ impl Display for PerkName {
             Self::Wallet => write!(f, "wallet"),
             Self::Twitter => write!(f, "twitter"),
             Self::FounderTwitter => write!(f, "founder_twitter"),
+            Self::Campaign => write!(f, "campaign"),
+            Self::Catalog(name) => write!(f, "{}", name),
             Self::Invalid => write!(f, "invalid"),
         }
     }
@@ -31,7 +36,9 @@ impl From<String> for PerkName {
             "wallet" => Self::Wallet,
             "twitter" => Self::Twitter,
             "founder_twitter" => Self::FounderTwitter,
-            _ => Self::Invalid,
+            "campaign" => Self::Campaign,
+            "invalid" | "" => Self::Invalid,
+            _ => Self::Catalog(s),
         }
     }
 }
@@ -79,4 +86,6 @@ pub struct Perk {
     pub one_time_bonus: f64,
     pub name: PerkName,
     pub data: Value,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
 }
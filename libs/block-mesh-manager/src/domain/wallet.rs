@@ -0,0 +1,45 @@
+use block_mesh_common::interfaces::server_api::{WalletChangeAction, WalletChangeEntry};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Accounts registered with a wallet get a placeholder email that never receives mail.
+pub fn wallet_account_email(address: &str) -> String {
+    format!("wallet_{address}@blockmesh.xyz").to_ascii_lowercase()
+}
+
+pub fn is_wallet_account(email: &str) -> bool {
+    email.starts_with("wallet_") && email.ends_with("@blockmesh.xyz")
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WalletChange {
+    pub action: WalletChangeAction,
+    pub address: String,
+}
+
+impl WalletChange {
+    pub fn new(action: WalletChangeAction, address: &str) -> Self {
+        Self {
+            action,
+            address: address.to_string(),
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    /// Older `WalletChange` aggregates only hold the address that was replaced.
+    pub fn entry(value: Value, created_at: DateTime<Utc>) -> Option<WalletChangeEntry> {
+        let change = match value {
+            Value::String(address) => Self::new(WalletChangeAction::Replaced, &address),
+            value => serde_json::from_value(value).ok()?,
+        };
+        Some(WalletChangeEntry {
+            action: change.action,
+            address: change.address,
+            created_at,
+        })
+    }
+}
@@ -1,3 +1,4 @@
+use crate::middlewares::rate_limit::RateLimitDecision;
 use anyhow::Error as AnyhowError;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Redirect};
@@ -7,8 +8,8 @@ use url::Url;
 pub enum Error {
     #[error("Task limit")]
     TaskLimit,
-    #[error("Not allowed rate limit")]
-    NotAllowedRateLimit,
+    #[error("Rate limited, retry after {}s", .0.retry_after_secs)]
+    RateLimited(RateLimitDecision),
     #[error("Internal server error")]
     InternalServer,
     #[error("Authentication error: {0}")]
@@ -57,6 +58,12 @@ pub enum Error {
     TokenMismatch,
     #[error("Signature mismatch")]
     SignatureMismatch,
+    #[error("Bad request: {0}")]
+    BadRequest(String),
+    #[error("Two factor code required")]
+    TwoFactorRequired,
+    #[error("Too many two factor attempts")]
+    TwoFactorLocked,
 }
 
 impl Error {
@@ -82,9 +89,12 @@ impl IntoResponse for Error {
             Error::PleaseLogout => {
                 (StatusCode::INTERNAL_SERVER_ERROR, "Please Logout").into_response()
             }
-            Error::NotAllowedRateLimit => {
-                (StatusCode::TOO_MANY_REQUESTS, "Not Allowed Rate Limit").into_response()
-            }
+            Error::RateLimited(decision) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                decision.headers(),
+                "Rate limit exceeded",
+            )
+                .into_response(),
             Error::SignatureMismatch => {
                 (StatusCode::INTERNAL_SERVER_ERROR, "Signature Mismatch").into_response()
             }
@@ -115,6 +125,15 @@ impl IntoResponse for Error {
                 (StatusCode::INTERNAL_SERVER_ERROR, "Failed Reading Body").into_response()
             }
             Error::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized").into_response(),
+            Error::BadRequest(message) => (StatusCode::BAD_REQUEST, message).into_response(),
+            Error::TwoFactorRequired => {
+                (StatusCode::FORBIDDEN, "Two factor code required").into_response()
+            }
+            Error::TwoFactorLocked => (
+                StatusCode::TOO_MANY_REQUESTS,
+                "Too many two factor attempts, try again later",
+            )
+                .into_response(),
             Error::TaskNotFound => {
                 (StatusCode::INTERNAL_SERVER_ERROR, "Task Not Found").into_response()
             }
@@ -157,7 +176,7 @@ impl From<Error> for StatusCode {
         match error {
             Error::TaskLimit => StatusCode::NOT_MODIFIED,
             Error::PleaseLogout => StatusCode::INTERNAL_SERVER_ERROR,
-            Error::NotAllowedRateLimit => StatusCode::TOO_MANY_REQUESTS,
+            Error::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
             Error::SignatureMismatch => StatusCode::INTERNAL_SERVER_ERROR,
             Error::TokenMismatch => StatusCode::INTERNAL_SERVER_ERROR,
             Error::NotYourTask => StatusCode::INTERNAL_SERVER_ERROR,
@@ -168,6 +187,9 @@ impl From<Error> for StatusCode {
             Error::TaskAssignedToAnotherUser => StatusCode::INTERNAL_SERVER_ERROR,
             Error::FailedReadingBody => StatusCode::INTERNAL_SERVER_ERROR,
             Error::Unauthorized => StatusCode::UNAUTHORIZED,
+            Error::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Error::TwoFactorRequired => StatusCode::FORBIDDEN,
+            Error::TwoFactorLocked => StatusCode::TOO_MANY_REQUESTS,
             Error::TaskNotFound => StatusCode::INTERNAL_SERVER_ERROR,
             Error::ApiTokenNotFound => StatusCode::INTERNAL_SERVER_ERROR,
             Error::NonceNotFound => StatusCode::INTERNAL_SERVER_ERROR,
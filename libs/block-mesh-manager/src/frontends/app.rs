@@ -17,12 +17,18 @@ use crate::frontends::frontend_tauri::pages::logged_in::TauriLoggedIn;
 use crate::frontends::frontend_tauri::pages::login::TauriLogin;
 use crate::frontends::frontend_tauri::pages::register::TauriRegister;
 use crate::frontends::frontend_tauri::tauri_header::TauriHeader;
+use crate::frontends::frontend_webserver::app::account_data::AccountData;
 use crate::frontends::frontend_webserver::app::admin_dashboard::AdminDashboard;
+use crate::frontends::frontend_webserver::app::api_tokens::ApiTokens;
 use crate::frontends::frontend_webserver::app::application_layout::ApplicationLayout;
 use crate::frontends::frontend_webserver::app::daily_leaderboard::DailyLeaderboardDashboard;
+use crate::frontends::frontend_webserver::app::device::DeviceApproval;
+use crate::frontends::frontend_webserver::app::feed_analytics::FeedAnalytics;
 use crate::frontends::frontend_webserver::app::new_dashboard::NewDashboard;
+use crate::frontends::frontend_webserver::app::notifications::Notifications;
 use crate::frontends::frontend_webserver::app::perks::Perks;
 use crate::frontends::frontend_webserver::app::referrals::Referrals;
+use crate::frontends::frontend_webserver::app::security::Security;
 use crate::frontends::wrapper::Wrapper;
 use leptos::*;
 use leptos_meta::*;
@@ -64,6 +70,12 @@ pub fn App() -> impl IntoView {
                     <Route path="/dashboard" view=NewDashboard/>
                     <Route path="/referrals" view=Referrals/>
                     <Route path="/perks" view=Perks/>
+                    <Route path="/feed" view=FeedAnalytics/>
+                    <Route path="/api_tokens" view=ApiTokens/>
+                    <Route path="/device" view=DeviceApproval/>
+                    <Route path="/security" view=Security/>
+                    <Route path="/notifications" view=Notifications/>
+                    <Route path="/account_data" view=AccountData/>
                     <Route path="/admin_dashboard" view=AdminDashboard/>
                 </Route>
                 <Route
@@ -15,6 +15,7 @@ pub fn EditInviteCode() -> impl IntoView {
     }
 
     let new_invite_code = create_rw_signal(String::default());
+    let totp_code = create_rw_signal(String::default());
 
     let submit = create_action(move |_| async move {
         if invite_code.get_untracked().is_empty() {
@@ -29,13 +30,16 @@ pub fn EditInviteCode() -> impl IntoView {
             .post(format!("{}{}", origin, RoutesEnum::Static_Auth_Edit_Invite))
             .form(&EditInviteCodeForm {
                 new_invite_code: new_invite_code.get_untracked(),
+                totp_code: Some(totp_code.get_untracked()).filter(|code| !code.is_empty()),
             })
             .send()
             .await;
 
         match response {
             Ok(res) => {
-                if res.status().as_u16() != 200 {
+                if res.status().as_u16() == 403 {
+                    notifications.set_error("Please enter a valid two factor code");
+                } else if res.status().as_u16() != 200 {
                     notifications.set_error(
                         "Failed to update invite code, dont use spaces or special chars",
                     );
@@ -99,6 +103,23 @@ pub fn EditInviteCode() -> impl IntoView {
                             />
 
                         </div>
+                        <div class="mb-4">
+                            <label
+                                class="font-bebas-neue mb-2 block text-sm font-bold text-off-white"
+                                for="totp_code"
+                            >
+                                2FA Code (if enabled)
+                            </label>
+                            <input
+                                class="w-full appearance-none rounded border px-3 py-2 text-black shadow"
+                                id="totp_code"
+                                type="text"
+                                name="totp_code"
+                                autocomplete="one-time-code"
+                                placeholder="123456"
+                                on:input=move |ev| totp_code.set(event_target_value(&ev))
+                            />
+                        </div>
                         <button
                             class="hover:text-orange text-off-white py-2 px-4 border border-orange rounded font-bebas-neue focus:outline-none focus:shadow-outline"
                             type="submit"
@@ -0,0 +1,20 @@
+use leptos::*;
+
+#[component]
+pub fn ChartIcon() -> impl IntoView {
+    view! {
+        <svg
+            xmlns="http://www.w3.org/2000/svg"
+            height="24px"
+            viewBox="0 -960 960 960"
+            aria-hidden="true"
+            fill="currentColor"
+            data-slot="icon"
+        >
+            <path
+                fill-rule="evenodd"
+                d="M640-160v-280h160v280H640Zm-240 0v-640h160v640H400Zm-240 0v-440h160v440H160Z"
+            ></path>
+        </svg>
+    }
+}
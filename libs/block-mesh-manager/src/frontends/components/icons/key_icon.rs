@@ -0,0 +1,20 @@
+use leptos::*;
+
+#[component]
+pub fn KeyIcon() -> impl IntoView {
+    view! {
+        <svg
+            xmlns="http://www.w3.org/2000/svg"
+            height="24px"
+            viewBox="0 -960 960 960"
+            aria-hidden="true"
+            fill="currentColor"
+            data-slot="icon"
+        >
+            <path
+                fill-rule="evenodd"
+                d="M280-400q-33 0-56.5-23.5T200-480q0-33 23.5-56.5T280-560q33 0 56.5 23.5T360-480q0 33-23.5 56.5T280-400Zm0 160q-100 0-170-70T40-480q0-100 70-170t170-70q67 0 121.5 33t86.5 87h352l120 120-180 180-80-60-80 60-85-60h-47q-32 54-86.5 87T280-240Z"
+            ></path>
+        </svg>
+    }
+}
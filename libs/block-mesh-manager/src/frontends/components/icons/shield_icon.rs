@@ -0,0 +1,20 @@
+use leptos::*;
+
+#[component]
+pub fn ShieldIcon() -> impl IntoView {
+    view! {
+        <svg
+            xmlns="http://www.w3.org/2000/svg"
+            height="24px"
+            viewBox="0 -960 960 960"
+            aria-hidden="true"
+            fill="currentColor"
+            data-slot="icon"
+        >
+            <path
+                fill-rule="evenodd"
+                d="m438-338 226-226-57-57-169 169-84-84-57 57 141 141Zm42 258q-139-35-229.5-159.5T160-516v-244l320-120 320 120v244q0 152-90.5 276.5T480-80Z"
+            ></path>
+        </svg>
+    }
+}
@@ -0,0 +1,165 @@
+use crate::frontends::components::sub_heading::Subheading;
+use crate::frontends::components::tables::table::Table;
+use crate::frontends::components::tables::table_cell::TableCell;
+use crate::frontends::components::tables::table_head::TableHead;
+use crate::frontends::components::tables::table_header::TableHeader;
+use crate::frontends::context::notification_context::NotificationContext;
+use block_mesh_common::interfaces::server_api::{
+    UnlinkWalletRequest, UserWalletsResponse, WalletChangeAction,
+};
+use block_mesh_common::routes_enum::RoutesEnum;
+use leptos::logging::log;
+use leptos::*;
+use reqwest::Client;
+
+fn action_title(action: WalletChangeAction) -> &'static str {
+    match action {
+        WalletChangeAction::Linked => "Linked",
+        WalletChangeAction::Unlinked => "Unlinked",
+        WalletChangeAction::Replaced => "Replaced",
+    }
+}
+
+#[component]
+pub fn LinkedWallets(reload: RwSignal<u32>) -> impl IntoView {
+    let notifications = expect_context::<NotificationContext>();
+    let totp_code = RwSignal::new(String::default());
+
+    let wallets = create_local_resource(
+        move || reload.get(),
+        |_| async move {
+            let response = Client::new()
+                .get(format!(
+                    "{}{}",
+                    window().origin(),
+                    RoutesEnum::Static_Auth_Wallets
+                ))
+                .send()
+                .await
+                .ok()?;
+            match response.json::<UserWalletsResponse>().await {
+                Ok(json) => Some(json),
+                Err(e) => {
+                    log!("wallets json error {:#?}", e);
+                    None
+                }
+            }
+        },
+    );
+
+    let unlink = create_action(move |address: &String| {
+        let address = address.clone();
+        async move {
+            let response = Client::new()
+                .post(format!(
+                    "{}{}",
+                    window().origin(),
+                    RoutesEnum::Static_Auth_Wallets_Unlink
+                ))
+                .json(&UnlinkWalletRequest {
+                    address,
+                    totp_code: Some(totp_code.get_untracked()).filter(|code| !code.is_empty()),
+                })
+                .send()
+                .await;
+            match response {
+                Ok(res) if res.status().is_success() => {
+                    reload.update(|r| *r += 1);
+                    notifications.set_success("Wallet unlinked");
+                }
+                Ok(res) if res.status().as_u16() == 403 => {
+                    notifications.set_error("Please enter a valid two factor code")
+                }
+                Ok(res) => notifications.set_error(res.text().await.unwrap_or_default()),
+                Err(_) => notifications.set_error("Failed to unlink wallet"),
+            }
+        }
+    });
+
+    view! {
+        <Suspense fallback=|| view! { <Subheading class="mt-14">Loading...</Subheading> }>
+            {move || {
+                let Some(data) = wallets.get().flatten() else {
+                    return view! {}.into_view();
+                };
+                let primary = data.primary.clone();
+                let rows = data
+                    .wallets
+                    .into_iter()
+                    .map(|wallet| {
+                        let is_primary = primary.as_deref() == Some(wallet.address.as_str());
+                        let address = wallet.address.clone();
+                        view! {
+                            <tr>
+                                <TableCell>
+                                    {wallet.address} {is_primary.then_some(" (primary)")}
+                                </TableCell>
+                                <TableCell>
+                                    {wallet.linked_at.format("%Y-%m-%d %H:%M").to_string()}
+                                </TableCell>
+                                <TableCell class="text-right">
+                                    <button
+                                        class="rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+                                        on:click=move |_| unlink.dispatch(address.clone())
+                                    >
+                                        Unlink
+                                    </button>
+                                </TableCell>
+                            </tr>
+                        }
+                    })
+                    .collect_view();
+                let history = data
+                    .history
+                    .into_iter()
+                    .map(|change| {
+                        view! {
+                            <tr>
+                                <TableCell>{action_title(change.action)}</TableCell>
+                                <TableCell>{change.address}</TableCell>
+                                <TableCell class="text-right">
+                                    {change.created_at.format("%Y-%m-%d %H:%M").to_string()}
+                                </TableCell>
+                            </tr>
+                        }
+                    })
+                    .collect_view();
+                view! {
+                    <Subheading class="mt-14">Linked Wallets</Subheading>
+                    <div class="mt-4">
+                        <input
+                            class="rounded border px-3 py-2 text-black"
+                            type="text"
+                            autocomplete="one-time-code"
+                            placeholder="Two factor code (if enabled)"
+                            prop:value=move || totp_code.get()
+                            on:input=move |ev| totp_code.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <Table class="mt-4 [--gutter:theme(spacing.6)] lg:[--gutter:theme(spacing.10)]">
+                        <TableHead>
+                            <tr>
+                                <TableHeader>Wallet</TableHeader>
+                                <TableHeader>Linked</TableHeader>
+                                <TableHeader class="text-right">Action</TableHeader>
+                            </tr>
+                        </TableHead>
+                        <tbody>{rows}</tbody>
+                    </Table>
+                    <Subheading class="mt-14">Wallet History</Subheading>
+                    <Table class="mt-4 [--gutter:theme(spacing.6)] lg:[--gutter:theme(spacing.10)]">
+                        <TableHead>
+                            <tr>
+                                <TableHeader>Change</TableHeader>
+                                <TableHeader>Wallet</TableHeader>
+                                <TableHeader class="text-right">Date</TableHeader>
+                            </tr>
+                        </TableHead>
+                        <tbody>{history}</tbody>
+                    </Table>
+                }
+                    .into_view()
+            }}
+        </Suspense>
+    }
+}
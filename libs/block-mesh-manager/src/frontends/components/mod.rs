@@ -9,6 +9,7 @@ pub mod extension_button;
 pub mod extension_input;
 pub mod heading;
 pub mod icons;
+pub mod linked_wallets;
 pub mod modal;
 pub mod navbars;
 pub mod notification_popup;
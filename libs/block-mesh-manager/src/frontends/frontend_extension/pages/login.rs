@@ -4,7 +4,7 @@ use crate::frontends::frontend_extension::components::logo::Logo;
 use crate::frontends::utils::auth::login;
 use crate::frontends::utils::connectors::send_message_channel;
 use block_mesh_common::chrome_storage::{AuthStatus, MessageKey, MessageType, MessageValue};
-use block_mesh_common::interfaces::server_api::LoginForm;
+use block_mesh_common::interfaces::server_api::GetTokenRequest;
 use leptos::logging::log;
 use leptos::*;
 use uuid::Uuid;
@@ -16,6 +16,8 @@ pub fn ExtensionLogin() -> impl IntoView {
     let (password, set_password) = create_signal(String::new());
     let (email, set_email) = create_signal(String::new());
     let (wait, set_wait) = create_signal(false);
+    let (totp_code, set_totp_code) = create_signal(String::new());
+    let (needs_code, set_needs_code) = create_signal(false);
 
     let submit_action_resource = create_local_resource(
         move || (),
@@ -27,14 +29,23 @@ pub fn ExtensionLogin() -> impl IntoView {
                 return;
             }
             set_wait.set(true);
-            let credentials = LoginForm {
+            let credentials = GetTokenRequest {
                 email: email.get_untracked(),
                 password: password.get_untracked(),
+                totp_code: needs_code
+                    .get_untracked()
+                    .then(|| totp_code.get_untracked()),
             };
 
             let result = login(&state.blockmesh_url.get_untracked(), &credentials).await;
             match result {
-                Ok(res) => {
+                Ok(None) => {
+                    set_needs_code.set(true);
+                    notifications.set_error(
+                        "Please enter a code from your authenticator app or a recovery code",
+                    );
+                }
+                Ok(Some(res)) => {
                     if res.message.is_some() {
                         notifications.set_error(res.message.unwrap());
                         set_wait.set(false);
@@ -129,6 +140,34 @@ pub fn ExtensionLogin() -> impl IntoView {
 
                         <label class="font-bebas-neue text-off-white">Password</label>
                     </div>
+                    <Show when=move || needs_code.get()>
+                        <div class="auth-card-input-container">
+                            <input
+                                type="text"
+                                required=""
+                                name="totp_code"
+                                autocomplete="one-time-code"
+                                on:keyup=move |ev: ev::KeyboardEvent| {
+                                    match &*ev.key() {
+                                        "Enter" => {
+                                            submit_action_resource.refetch();
+                                        }
+                                        _ => {
+                                            let val = event_target_value(&ev);
+                                            set_totp_code.update(|v| *v = val);
+                                        }
+                                    }
+                                }
+
+                                on:change=move |ev| {
+                                    let val = event_target_value(&ev);
+                                    set_totp_code.update(|v| *v = val);
+                                }
+                            />
+
+                            <label class="font-bebas-neue text-off-white">Two Factor Code</label>
+                        </div>
+                    </Show>
                     <br/>
                     <button
                         class="auth-card-button font-bebas-neue text-off-white"
@@ -3,7 +3,7 @@ use crate::frontends::context::notification_context::NotificationContext;
 use crate::frontends::utils::auth::login;
 use crate::frontends::utils::connectors::send_message_channel;
 use block_mesh_common::chrome_storage::{AuthStatus, MessageKey, MessageType, MessageValue};
-use block_mesh_common::interfaces::server_api::LoginForm;
+use block_mesh_common::interfaces::server_api::GetTokenRequest;
 use leptos::leptos_dom::log;
 use leptos::*;
 use uuid::Uuid;
@@ -16,6 +16,8 @@ pub fn TauriLogin() -> impl IntoView {
     let (email, set_email) = create_signal(String::new());
     let (password, set_password) = create_signal(String::new());
     let (wait, set_wait) = create_signal(false);
+    let (totp_code, set_totp_code) = create_signal(String::new());
+    let (needs_code, set_needs_code) = create_signal(false);
 
     let submit_action_resource = create_local_resource(
         move || (),
@@ -34,15 +36,24 @@ pub fn TauriLogin() -> impl IntoView {
                 return;
             }
             set_wait.set(true);
-            let credentials = LoginForm {
+            let credentials = GetTokenRequest {
                 email: email.get_untracked(),
                 password: password.get_untracked(),
+                totp_code: needs_code
+                    .get_untracked()
+                    .then(|| totp_code.get_untracked()),
             };
 
             let result = login(&state.blockmesh_url.get_untracked(), &credentials).await;
             log!("result {:#?}", result);
             match result {
-                Ok(res) => {
+                Ok(None) => {
+                    set_needs_code.set(true);
+                    notifications.set_error(
+                        "Please enter a code from your authenticator app or a recovery code",
+                    );
+                }
+                Ok(Some(res)) => {
                     if res.message.is_some() {
                         notifications.set_error(res.message.unwrap());
                         set_wait.set(false);
@@ -183,6 +194,41 @@ pub fn TauriLogin() -> impl IntoView {
                         />
 
                     </div>
+                    <Show when=move || needs_code.get()>
+                        <div class="mb-4">
+                            <label
+                                class="font-bebas-neue block text-off-white text-sm font-bold mb-2"
+                                for="totp_code"
+                            >
+                                Two Factor Code
+                            </label>
+                            <input
+                                autocapitalize="off"
+                                autocomplete="one-time-code"
+                                class="shadow appearance-none border rounded w-full py-2 px-3 text-black mb-3 leading-tight focus:outline-none focus:shadow-outline"
+                                type="text"
+                                id="totp_code"
+                                name="totp_code"
+                                on:keyup=move |ev: ev::KeyboardEvent| {
+                                    match &*ev.key() {
+                                        "Enter" => {
+                                            submit_action_resource.refetch();
+                                        }
+                                        _ => {
+                                            let val = event_target_value(&ev);
+                                            set_totp_code.update(|v| *v = val);
+                                        }
+                                    }
+                                }
+
+                                on:change=move |ev| {
+                                    let val = event_target_value(&ev);
+                                    set_totp_code.update(|v| *v = val);
+                                }
+                            />
+
+                        </div>
+                    </Show>
                     <div class="flex items-center justify-between">
                         <button
                             class="hover:text-orange text-off-white py-2 px-4 border border-orange rounded font-bebas-neue focus:outline-none focus:shadow-outline"
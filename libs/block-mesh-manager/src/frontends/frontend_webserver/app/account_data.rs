@@ -0,0 +1,258 @@
+use crate::frontends::components::heading::Heading;
+use crate::frontends::components::sub_heading::Subheading;
+use crate::frontends::components::tables::table::Table;
+use crate::frontends::components::tables::table_cell::TableCell;
+use crate::frontends::components::tables::table_head::TableHead;
+use crate::frontends::components::tables::table_header::TableHeader;
+use crate::frontends::context::notification_context::NotificationContext;
+use block_mesh_common::interfaces::server_api::{AccountDataResponse, AccountDeletionRequest};
+use block_mesh_common::routes_enum::RoutesEnum;
+use chrono::{DateTime, Utc};
+use leptos::logging::log;
+use leptos::*;
+use reqwest::Client;
+
+fn format_time(time: Option<DateTime<Utc>>) -> String {
+    time.map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or("-".to_string())
+}
+
+fn format_size(size_bytes: Option<i64>) -> String {
+    size_bytes
+        .map(|size| format!("{:.1} MB", size as f64 / 1_000_000.0))
+        .unwrap_or("-".to_string())
+}
+
+async fn post_account_action(route: RoutesEnum) -> Option<reqwest::Response> {
+    Client::new()
+        .post(format!("{}{}", window().origin(), route))
+        .send()
+        .await
+        .ok()
+}
+
+#[component]
+pub fn AccountData() -> impl IntoView {
+    let notifications = expect_context::<NotificationContext>();
+    let reload = RwSignal::new(0u32);
+    let email = RwSignal::new(String::default());
+    let totp_code = RwSignal::new(String::default());
+
+    let account_data = create_local_resource(
+        move || reload.get(),
+        |_| async move {
+            let response = Client::new()
+                .get(format!(
+                    "{}{}",
+                    window().origin(),
+                    RoutesEnum::Static_Auth_Account_Data
+                ))
+                .send()
+                .await
+                .ok()?;
+            match response.json::<AccountDataResponse>().await {
+                Ok(json) => Some(json),
+                Err(e) => {
+                    log!("account data json error {:#?}", e);
+                    None
+                }
+            }
+        },
+    );
+
+    let request_export = create_action(move |_: &()| async move {
+        match post_account_action(RoutesEnum::Static_Auth_Account_Export).await {
+            Some(res) if res.status().is_success() => {
+                reload.update(|r| *r += 1);
+                notifications.set_success("Export requested, it will be ready in a few minutes");
+            }
+            Some(res) => notifications.set_error(res.text().await.unwrap_or_default()),
+            None => notifications.set_error("Failed to request export"),
+        }
+    });
+
+    let request_deletion = create_action(move |_: &()| async move {
+        let response = Client::new()
+            .post(format!(
+                "{}{}",
+                window().origin(),
+                RoutesEnum::Static_Auth_Account_Deletion
+            ))
+            .json(&AccountDeletionRequest {
+                email: email.get_untracked(),
+                totp_code: Some(totp_code.get_untracked()).filter(|code| !code.is_empty()),
+            })
+            .send()
+            .await;
+        match response {
+            Ok(res) if res.status().is_success() => {
+                email.set(String::default());
+                totp_code.set(String::default());
+                reload.update(|r| *r += 1);
+                notifications.set_success("Account deletion scheduled");
+            }
+            Ok(res) if res.status().as_u16() == 403 => {
+                notifications.set_error("Please enter a valid two factor code")
+            }
+            Ok(res) => notifications.set_error(res.text().await.unwrap_or_default()),
+            Err(_) => notifications.set_error("Failed to schedule account deletion"),
+        }
+    });
+
+    let cancel_deletion = create_action(move |_: &()| async move {
+        match post_account_action(RoutesEnum::Static_Auth_Account_Deletion_Cancel).await {
+            Some(res) if res.status().is_success() => {
+                reload.update(|r| *r += 1);
+                notifications.set_success("Account deletion cancelled");
+            }
+            Some(res) => notifications.set_error(res.text().await.unwrap_or_default()),
+            None => notifications.set_error("Failed to cancel account deletion"),
+        }
+    });
+
+    view! {
+        <div class="flex items-start justify-start gap-4">
+            <Heading>Account Data</Heading>
+        </div>
+        <Subheading class="mt-8">Export</Subheading>
+        <p class="mt-2 text-off-white">
+            "Download a zip with everything stored for your account. Archives can be downloaded until they expire."
+        </p>
+        <button
+            class="mt-4 rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+            on:click=move |_| request_export.dispatch(())
+        >
+            Request export
+        </button>
+        <Suspense fallback=|| view! { <Subheading class="mt-14">Loading...</Subheading> }>
+            {move || match account_data.get().flatten() {
+                None => view! { <Subheading class="mt-14">Loading...</Subheading> }.into_view(),
+                Some(data) => {
+                    let rows = data
+                        .exports
+                        .into_iter()
+                        .map(|export| {
+                            let download = format!(
+                                "{}?id={}",
+                                RoutesEnum::Static_Auth_Account_Export_Download,
+                                export.id,
+                            );
+                            let ready = export.status == "Ready";
+                            view! {
+                                <tr>
+                                    <TableCell>{format_time(Some(export.created_at))}</TableCell>
+                                    <TableCell>{export.status}</TableCell>
+                                    <TableCell>{format_size(export.size_bytes)}</TableCell>
+                                    <TableCell>{format_time(export.expires_at)}</TableCell>
+                                    <TableCell class="text-right">
+                                        <Show when=move || ready>
+                                            <a
+                                                class="rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+                                                href=download.clone()
+                                                rel="external"
+                                            >
+                                                Download
+                                            </a>
+                                        </Show>
+                                    </TableCell>
+                                </tr>
+                            }
+                        })
+                        .collect_view();
+                    let telegram = data
+                        .telegram_link
+                        .map(|link| {
+                            view! {
+                                <Subheading class="mt-14">Telegram</Subheading>
+                                <p class="mt-2 text-off-white">
+                                    "Open the privacy bot with this link so your chats with it are included in exports and deleted with your account."
+                                </p>
+                                <a
+                                    class="mt-4 inline-block rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+                                    href=link
+                                    target="_blank"
+                                    rel="external"
+                                >
+                                    Link Telegram
+                                </a>
+                            }
+                        });
+                    let deletion = match data.deletion {
+                        Some(deletion) => {
+                            view! {
+                                <p class="mt-2 text-off-white">
+                                    "Your account will be deleted on "
+                                    {format_time(Some(deletion.scheduled_for))}
+                                    ". You can still cancel until then."
+                                </p>
+                                <button
+                                    class="mt-4 rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+                                    on:click=move |_| cancel_deletion.dispatch(())
+                                >
+                                    Cancel deletion
+                                </button>
+                            }
+                                .into_view()
+                        }
+                        None => {
+                            view! {
+                                <p class="mt-2 text-off-white">
+                                    "Deleting your account removes your stats, tasks, perks, invite codes and collected data, and signs out every device. It happens after a grace period during which you can cancel."
+                                </p>
+                                <form
+                                    class="mt-4 flex flex-wrap items-end gap-4"
+                                    on:submit=move |ev| {
+                                        ev.prevent_default();
+                                        request_deletion.dispatch(());
+                                    }
+                                >
+                                    <input
+                                        class="rounded border px-3 py-2 text-black"
+                                        type="email"
+                                        placeholder="Type your email to confirm"
+                                        prop:value=move || email.get()
+                                        on:input=move |ev| email.set(event_target_value(&ev))
+                                    />
+                                    <input
+                                        class="rounded border px-3 py-2 text-black"
+                                        type="text"
+                                        inputmode="numeric"
+                                        autocomplete="one-time-code"
+                                        placeholder="Two factor code (if enabled)"
+                                        prop:value=move || totp_code.get()
+                                        on:input=move |ev| totp_code.set(event_target_value(&ev))
+                                    />
+                                    <button
+                                        type="submit"
+                                        class="rounded-lg border border-red-600 px-3 py-1 text-sm/6 text-red-600"
+                                    >
+                                        Delete account
+                                    </button>
+                                </form>
+                            }
+                                .into_view()
+                        }
+                    };
+                    view! {
+                        <Table class="mt-8 [--gutter:theme(spacing.6)] lg:[--gutter:theme(spacing.10)]">
+                            <TableHead>
+                                <tr>
+                                    <TableHeader>Requested</TableHeader>
+                                    <TableHeader>Status</TableHeader>
+                                    <TableHeader>Size</TableHeader>
+                                    <TableHeader>Expires</TableHeader>
+                                    <TableHeader class="text-right">Actions</TableHeader>
+                                </tr>
+                            </TableHead>
+                            <tbody>{rows}</tbody>
+                        </Table>
+                        {telegram}
+                        <Subheading class="mt-14">Delete account</Subheading>
+                        {deletion}
+                    }
+                        .into_view()
+                }
+            }}
+        </Suspense>
+    }
+}
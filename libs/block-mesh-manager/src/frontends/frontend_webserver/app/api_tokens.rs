@@ -0,0 +1,276 @@
+use crate::frontends::components::heading::Heading;
+use crate::frontends::components::sub_heading::Subheading;
+use crate::frontends::components::tables::table::Table;
+use crate::frontends::components::tables::table_cell::TableCell;
+use crate::frontends::components::tables::table_head::TableHead;
+use crate::frontends::components::tables::table_header::TableHeader;
+use crate::frontends::context::notification_context::NotificationContext;
+use block_mesh_common::interfaces::server_api::{
+    ApiTokenIdRequest, ApiTokenInfo, ApiTokenSecretResponse, CreateApiTokenRequest, TokenScope,
+};
+use block_mesh_common::routes_enum::RoutesEnum;
+use chrono::{DateTime, Utc};
+use leptos::logging::log;
+use leptos::*;
+use reqwest::Client;
+use uuid::Uuid;
+
+fn scope_title(scope: TokenScope) -> &'static str {
+    match scope {
+        TokenScope::NodeReporting => "Node reporting",
+        TokenScope::TaskSubmission => "Task submission",
+        TokenScope::DashboardRead => "Dashboard (read only)",
+    }
+}
+
+fn format_time(time: Option<DateTime<Utc>>) -> String {
+    time.map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or("-".to_string())
+}
+
+async fn post_token_action(route: RoutesEnum, id: Uuid) -> Option<reqwest::Response> {
+    Client::new()
+        .post(format!("{}{}", window().origin(), route))
+        .json(&ApiTokenIdRequest { id })
+        .send()
+        .await
+        .ok()
+}
+
+#[component]
+pub fn ApiTokens() -> impl IntoView {
+    let notifications = expect_context::<NotificationContext>();
+    let reload = RwSignal::new(0u32);
+    let issued = RwSignal::new(None::<ApiTokenSecretResponse>);
+    let name = RwSignal::new(String::default());
+    let expires_in_days = RwSignal::new(String::default());
+    let scopes = RwSignal::new(TokenScope::ALL.to_vec());
+
+    let tokens = create_local_resource(
+        move || reload.get(),
+        |_| async move {
+            let response = Client::new()
+                .get(format!(
+                    "{}{}",
+                    window().origin(),
+                    RoutesEnum::Static_Auth_Api_Tokens
+                ))
+                .send()
+                .await
+                .ok()?;
+            match response.json::<Vec<ApiTokenInfo>>().await {
+                Ok(json) => Some(json),
+                Err(e) => {
+                    log!("api tokens json error {:#?}", e);
+                    None
+                }
+            }
+        },
+    );
+
+    let create = create_action(move |_: &()| async move {
+        let expires_in_days = expires_in_days.get_untracked();
+        let expires_in_days = if expires_in_days.trim().is_empty() {
+            None
+        } else {
+            match expires_in_days.trim().parse() {
+                Ok(days) => Some(days),
+                Err(_) => {
+                    notifications.set_error("Expiry must be a number of days");
+                    return;
+                }
+            }
+        };
+        let response = Client::new()
+            .post(format!(
+                "{}{}",
+                window().origin(),
+                RoutesEnum::Static_Auth_Api_Tokens
+            ))
+            .json(&CreateApiTokenRequest {
+                name: name.get_untracked(),
+                scopes: scopes.get_untracked(),
+                expires_in_days,
+            })
+            .send()
+            .await;
+        match response {
+            Ok(res) if res.status().is_success() => {
+                if let Ok(secret) = res.json::<ApiTokenSecretResponse>().await {
+                    issued.set(Some(secret));
+                }
+                name.set(String::default());
+                reload.update(|r| *r += 1);
+                notifications.set_success("Token created");
+            }
+            Ok(res) => notifications.set_error(res.text().await.unwrap_or_default()),
+            Err(_) => notifications.set_error("Failed to create token"),
+        }
+    });
+
+    let revoke = create_action(move |id: &Uuid| {
+        let id = *id;
+        async move {
+            match post_token_action(RoutesEnum::Static_Auth_Api_Tokens_Revoke, id).await {
+                Some(res) if res.status().is_success() => {
+                    reload.update(|r| *r += 1);
+                    notifications.set_success("Token revoked");
+                }
+                _ => notifications.set_error("Failed to revoke token"),
+            }
+        }
+    });
+
+    let rotate = create_action(move |id: &Uuid| {
+        let id = *id;
+        async move {
+            match post_token_action(RoutesEnum::Static_Auth_Api_Tokens_Rotate, id).await {
+                Some(res) if res.status().is_success() => {
+                    if let Ok(secret) = res.json::<ApiTokenSecretResponse>().await {
+                        issued.set(Some(secret));
+                    }
+                    reload.update(|r| *r += 1);
+                    notifications.set_success("Token rotated");
+                }
+                _ => notifications.set_error("Failed to rotate token"),
+            }
+        }
+    });
+
+    view! {
+        <div class="flex items-start justify-start gap-4">
+            <Heading>API Tokens</Heading>
+        </div>
+        <Subheading class="mt-8">
+            Give every device its own token, send it as an Authorization: Bearer header
+        </Subheading>
+        {move || {
+            issued
+                .get()
+                .map(|secret| {
+                    view! {
+                        <div class="mt-4 rounded-lg border border-white/10 p-4 text-off-white">
+                            <p>
+                                "Copy the token for " {secret.info.name}
+                                " now, it will not be shown again:"
+                            </p>
+                            <code class="mt-2 block select-all">{secret.token.to_string()}</code>
+                        </div>
+                    }
+                })
+        }}
+        <form class="mt-8 flex flex-wrap items-end gap-4" on:submit=move |ev| {
+            ev.prevent_default();
+            create.dispatch(());
+        }>
+            <input
+                class="rounded border px-3 py-2 text-black"
+                type="text"
+                placeholder="Device name"
+                prop:value=move || name.get()
+                on:input=move |ev| name.set(event_target_value(&ev))
+            />
+            <input
+                class="w-32 rounded border px-3 py-2 text-black"
+                type="text"
+                placeholder="Expires in days"
+                prop:value=move || expires_in_days.get()
+                on:input=move |ev| expires_in_days.set(event_target_value(&ev))
+            />
+            {TokenScope::ALL
+                .into_iter()
+                .map(|scope| {
+                    view! {
+                        <label class="flex items-center gap-2 text-sm/6 text-off-white">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || scopes.get().contains(&scope)
+                                on:change=move |_| {
+                                    scopes
+                                        .update(|scopes| {
+                                            if let Some(index) = scopes.iter().position(|s| *s == scope) {
+                                                scopes.remove(index);
+                                            } else {
+                                                scopes.push(scope);
+                                            }
+                                        })
+                                }
+                            />
+                            {scope_title(scope)}
+                        </label>
+                    }
+                })
+                .collect_view()}
+            <button
+                type="submit"
+                class="rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+            >
+                Create token
+            </button>
+        </form>
+        <Suspense fallback=|| view! { <Subheading class="mt-14">Loading...</Subheading> }>
+            {move || {
+                let rows = tokens
+                    .get()
+                    .flatten()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|token| {
+                        let id = token.id;
+                        let scopes = token
+                            .scopes
+                            .iter()
+                            .map(|scope| scope_title(*scope))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        view! {
+                            <tr>
+                                <TableCell>
+                                    {token.name} {token.is_primary.then_some(" (primary)")}
+                                </TableCell>
+                                <TableCell>{scopes}</TableCell>
+                                <TableCell>{format_time(Some(token.created_at))}</TableCell>
+                                <TableCell>{format_time(token.expires_at)}</TableCell>
+                                <TableCell>
+                                    {format_time(token.last_used_at)} " "
+                                    {token.last_used_ip.unwrap_or_default()}
+                                </TableCell>
+                                <TableCell class="text-right">
+                                    <button
+                                        class="rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+                                        on:click=move |_| rotate.dispatch(id)
+                                    >
+                                        Rotate
+                                    </button>
+                                    <Show when=move || !token.is_primary>
+                                        <button
+                                            class="ml-2 rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+                                            on:click=move |_| revoke.dispatch(id)
+                                        >
+                                            Revoke
+                                        </button>
+                                    </Show>
+                                </TableCell>
+                            </tr>
+                        }
+                    })
+                    .collect_view();
+                view! {
+                    <Table class="mt-8 [--gutter:theme(spacing.6)] lg:[--gutter:theme(spacing.10)]">
+                        <TableHead>
+                            <tr>
+                                <TableHeader>Name</TableHeader>
+                                <TableHeader>Scopes</TableHeader>
+                                <TableHeader>Created</TableHeader>
+                                <TableHeader>Expires</TableHeader>
+                                <TableHeader>Last Used</TableHeader>
+                                <TableHeader class="text-right">Actions</TableHeader>
+                            </tr>
+                        </TableHead>
+                        <tbody>{rows}</tbody>
+                    </Table>
+                }
+            }}
+        </Suspense>
+    }
+}
@@ -1,11 +1,16 @@
 use crate::frontends::components::avatar::Avatar;
 use crate::frontends::components::conditionals::if_let_some::IfLetSome;
+use crate::frontends::components::icons::bell_icon::BellIcon;
+use crate::frontends::components::icons::chart_icon::ChartIcon;
+use crate::frontends::components::icons::database_icon::DatabaseIcon;
 use crate::frontends::components::icons::home_icon::HomeIcon;
+use crate::frontends::components::icons::key_icon::KeyIcon;
 use crate::frontends::components::icons::link_icon::LinkIcon;
 use crate::frontends::components::icons::logout_icon::LogoutIcon;
 #[allow(unused_imports)]
 use crate::frontends::components::icons::medal_icon::MedalIcon;
 use crate::frontends::components::icons::perk_icon::PerkIcon;
+use crate::frontends::components::icons::shield_icon::ShieldIcon;
 use crate::frontends::components::navbars::navbar::Navbar;
 use crate::frontends::components::navbars::navbar_section::NavbarSection;
 use crate::frontends::components::navbars::navbar_spacer::NavbarSpacer;
@@ -69,6 +74,26 @@ pub fn ApplicationSidebar() -> impl IntoView {
                         <PerkIcon/>
                         <SidebarLabel>Perks</SidebarLabel>
                     </SidebarItemLink>
+                    <SidebarItemLink href="/ui/feed">
+                        <ChartIcon/>
+                        <SidebarLabel>Feed</SidebarLabel>
+                    </SidebarItemLink>
+                    <SidebarItemLink href="/ui/api_tokens">
+                        <KeyIcon/>
+                        <SidebarLabel>API Tokens</SidebarLabel>
+                    </SidebarItemLink>
+                    <SidebarItemLink href="/ui/security">
+                        <ShieldIcon/>
+                        <SidebarLabel>Security</SidebarLabel>
+                    </SidebarItemLink>
+                    <SidebarItemLink href="/ui/notifications">
+                        <BellIcon/>
+                        <SidebarLabel>Notifications</SidebarLabel>
+                    </SidebarItemLink>
+                    <SidebarItemLink href="/ui/account_data">
+                        <DatabaseIcon/>
+                        <SidebarLabel>Account Data</SidebarLabel>
+                    </SidebarItemLink>
                 // <SidebarItemLink href="/ui/daily_leaderboard">
                 // <MedalIcon/>
                 // <SidebarLabel>Daily Leaderboard</SidebarLabel>
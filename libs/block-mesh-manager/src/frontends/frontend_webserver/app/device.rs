@@ -0,0 +1,157 @@
+use crate::frontends::components::heading::Heading;
+use crate::frontends::components::sub_heading::Subheading;
+use crate::frontends::context::notification_context::NotificationContext;
+use block_mesh_common::interfaces::server_api::{DeviceApprovalRequest, DeviceAuthorizationInfo};
+use block_mesh_common::routes_enum::RoutesEnum;
+use leptos::*;
+use leptos_router::use_query_map;
+use reqwest::Client;
+
+/// Where `blockmesh-cli` sends the user to approve a device code.
+#[component]
+pub fn DeviceApproval() -> impl IntoView {
+    let notifications = expect_context::<NotificationContext>();
+    let query = use_query_map();
+    let code = RwSignal::new(
+        query
+            .get_untracked()
+            .get("code")
+            .cloned()
+            .unwrap_or_default(),
+    );
+    let submitted_code = RwSignal::new(code.get_untracked());
+    let decided = RwSignal::new(None::<bool>);
+
+    let authorization = create_local_resource(
+        move || submitted_code.get(),
+        |code| async move {
+            if code.is_empty() {
+                return None;
+            }
+            let response = Client::new()
+                .get(format!(
+                    "{}{}",
+                    window().origin(),
+                    RoutesEnum::Static_Auth_Device
+                ))
+                .query(&[("code", code)])
+                .send()
+                .await
+                .ok()?;
+            if !response.status().is_success() {
+                return None;
+            }
+            response.json::<DeviceAuthorizationInfo>().await.ok()
+        },
+    );
+
+    let decide = create_action(move |approve: &bool| {
+        let approve = *approve;
+        async move {
+            let response = Client::new()
+                .post(format!(
+                    "{}{}",
+                    window().origin(),
+                    RoutesEnum::Static_Auth_Device
+                ))
+                .json(&DeviceApprovalRequest {
+                    user_code: submitted_code.get_untracked(),
+                    approve,
+                })
+                .send()
+                .await;
+            match response {
+                Ok(res) if res.status().is_success() => {
+                    decided.set(Some(approve));
+                    if approve {
+                        notifications.set_success("Device approved");
+                    } else {
+                        notifications.set_success("Device denied");
+                    }
+                }
+                _ => notifications.set_error("Code not found or expired"),
+            }
+        }
+    });
+
+    view! {
+        <div class="flex items-start justify-start gap-4">
+            <Heading>Connect a Device</Heading>
+        </div>
+        <form class="mt-8 flex items-end gap-4" on:submit=move |ev| {
+            ev.prevent_default();
+            decided.set(None);
+            submitted_code.set(code.get_untracked());
+        }>
+            <input
+                class="rounded border px-3 py-2 text-black uppercase"
+                type="text"
+                placeholder="XXXX-XXXX"
+                prop:value=move || code.get()
+                on:input=move |ev| code.set(event_target_value(&ev))
+            />
+            <button
+                type="submit"
+                class="rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+            >
+                Continue
+            </button>
+        </form>
+        <Suspense fallback=|| view! { <Subheading class="mt-14">Loading...</Subheading> }>
+            {move || match (authorization.get().flatten(), decided.get()) {
+                (_, Some(true)) => {
+                    view! {
+                        <Subheading class="mt-14">
+                            Approved, the device will connect within a few seconds
+                        </Subheading>
+                    }
+                        .into_view()
+                }
+                (_, Some(false)) => {
+                    view! { <Subheading class="mt-14">Denied, the device was not connected</Subheading> }
+                        .into_view()
+                }
+                (None, None) => {
+                    view! {
+                        <Subheading class="mt-14">
+                            Enter the code shown by blockmesh-cli, codes expire after 10 minutes
+                        </Subheading>
+                    }
+                        .into_view()
+                }
+                (Some(info), None) => {
+                    let scopes = info
+                        .scopes
+                        .iter()
+                        .map(|scope| scope.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    view! {
+                        <Subheading class="mt-14">
+                            {info.device_name} <span class="pr-2 pl-2">|</span> {info.user_code}
+                        </Subheading>
+                        <p class="mt-4 text-off-white">
+                            "This device will get its own token with: " {scopes}
+                            ". Only approve it if you just started the login yourself."
+                        </p>
+                        <div class="mt-4 flex gap-2">
+                            <button
+                                class="rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+                                on:click=move |_| decide.dispatch(true)
+                            >
+                                Approve
+                            </button>
+                            <button
+                                class="rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+                                on:click=move |_| decide.dispatch(false)
+                            >
+                                Deny
+                            </button>
+                        </div>
+                    }
+                        .into_view()
+                }
+            }}
+        </Suspense>
+    }
+}
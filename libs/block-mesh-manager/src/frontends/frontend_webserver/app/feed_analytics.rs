@@ -0,0 +1,219 @@
+use crate::frontends::components::heading::Heading;
+use crate::frontends::components::sub_heading::Subheading;
+use crate::frontends::components::tables::table::Table;
+use crate::frontends::components::tables::table_cell::TableCell;
+use crate::frontends::components::tables::table_head::TableHead;
+use crate::frontends::components::tables::table_header::TableHeader;
+use crate::frontends::context::size_context::SizeContext;
+use block_mesh_common::interfaces::server_api::{
+    FeedAnalyticsResponse, FeedComposition, FeedDimension, FeedTimelinePoint,
+};
+use block_mesh_common::routes_enum::RoutesEnum;
+use charming::component::{Grid, Legend};
+use charming::datatype::CompositeValue;
+use charming::element::{AxisLabel, AxisPointer, AxisPointerType, Tooltip, Trigger};
+use charming::{component::Axis, element::AxisType, series::Bar, Chart, HtmlRenderer};
+use leptos::logging::log;
+use leptos::*;
+use std::collections::BTreeSet;
+
+fn dimension_title(dimension: FeedDimension) -> &'static str {
+    match dimension {
+        FeedDimension::Source => "Sources",
+        FeedDimension::Author => "Authors",
+        FeedDimension::Hashtag => "Hashtags",
+        FeedDimension::Topic => "Topics",
+    }
+}
+
+fn percent(share: f64) -> String {
+    format!("{:.1}%", share * 100.0)
+}
+
+#[component]
+pub fn FeedAnalytics() -> impl IntoView {
+    let timeline_dimension = RwSignal::new(FeedDimension::Source);
+    let analytics = create_local_resource(
+        move || timeline_dimension.get(),
+        |dimension| async move {
+            let origin = window().origin();
+            let response = reqwest::Client::new()
+                .post(format!(
+                    "{}{}?timeline={}",
+                    origin,
+                    RoutesEnum::Static_Auth_Feed_Analytics,
+                    dimension
+                ))
+                .send()
+                .await
+                .ok()?;
+            match response.json::<FeedAnalyticsResponse>().await {
+                Ok(json) => Some(json),
+                Err(e) => {
+                    log!("feed analytics json error {:#?}", e);
+                    None
+                }
+            }
+        },
+    );
+
+    view! {
+        <div class="flex items-start justify-start gap-4">
+            <Heading>Feed Analytics</Heading>
+        </div>
+        <Suspense fallback=|| view! { <Subheading class="mt-14">Loading...</Subheading> }>
+            {move || match analytics.get().flatten() {
+                None => {
+                    view! {
+                        <Subheading class="mt-14">
+                            No feed data yet, browse with the extension enabled to collect some
+                        </Subheading>
+                    }
+                        .into_view()
+                }
+                Some(data) => {
+                    let range = format!("{} - {}", data.since, data.until);
+                    let timeline = data.timeline.clone();
+                    view! {
+                        <Subheading class="mt-14">
+                            Your feed compared to all users <span class="pr-2 pl-2">|</span> {range}
+                        </Subheading>
+                        <div class="flex gap-2 mt-4">
+                            {FeedDimension::ALL
+                                .into_iter()
+                                .map(|dimension| {
+                                    view! {
+                                        <button
+                                            class="rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+                                            class=("bg-zinc-600", move || timeline_dimension.get() == dimension)
+                                            on:click=move |_| timeline_dimension.set(dimension)
+                                        >
+                                            {dimension_title(dimension)}
+                                        </button>
+                                    }
+                                })
+                                .collect_view()}
+                        </div>
+                        <FeedTimelineChart timeline=timeline/>
+                        {data
+                            .user
+                            .iter()
+                            .map(|composition| {
+                                view! { <FeedCompositionTable composition=composition.clone() global=data.global.clone()/> }
+                            })
+                            .collect_view()}
+                    }
+                        .into_view()
+                }
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+fn FeedCompositionTable(
+    composition: FeedComposition,
+    global: Vec<FeedComposition>,
+) -> impl IntoView {
+    let global = global
+        .into_iter()
+        .find(|g| g.dimension == composition.dimension);
+    let rows = composition
+        .top
+        .into_iter()
+        .map(|share| {
+            let global_share = global
+                .as_ref()
+                .and_then(|g| g.top.iter().find(|g| g.key == share.key))
+                .map(|g| percent(g.share))
+                .unwrap_or("-".to_string());
+            view! {
+                <tr>
+                    <TableCell>{share.key}</TableCell>
+                    <TableCell class="text-right">{share.count}</TableCell>
+                    <TableCell class="text-right">{percent(share.share)}</TableCell>
+                    <TableCell class="text-right">{global_share}</TableCell>
+                </tr>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <Subheading class="mt-14">
+            {dimension_title(composition.dimension)} <span class="pr-2 pl-2">|</span>
+            {composition.total}
+        </Subheading>
+        <Table class="mt-4 [--gutter:theme(spacing.6)] lg:[--gutter:theme(spacing.10)]">
+            <TableHead>
+                <tr>
+                    <TableHeader>Name</TableHeader>
+                    <TableHeader class="text-right">Posts</TableHeader>
+                    <TableHeader class="text-right">Your Feed</TableHeader>
+                    <TableHeader class="text-right">All Users</TableHeader>
+                </tr>
+            </TableHead>
+            <tbody>{rows}</tbody>
+        </Table>
+    }
+}
+
+#[component]
+fn FeedTimelineChart(timeline: Vec<FeedTimelinePoint>) -> impl IntoView {
+    let size_context = use_context::<SizeContext>().unwrap();
+    let width = Signal::derive(move || size_context.width.get() * 0.5);
+    let days: Vec<_> = timeline
+        .iter()
+        .map(|point| point.day)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let keys: BTreeSet<String> = timeline.iter().map(|point| point.key.clone()).collect();
+
+    let html_chart = Signal::derive(move || {
+        let mut chart = Chart::new()
+            .grid(
+                Grid::new()
+                    .contain_label(true)
+                    .left(CompositeValue::String("3%".to_string()))
+                    .right(CompositeValue::String("4%".to_string()))
+                    .bottom(CompositeValue::String("3%".to_string())),
+            )
+            .legend(Legend::new())
+            .x_axis(
+                Axis::new()
+                    .type_(AxisType::Category)
+                    .axis_label(AxisLabel::new().show(true))
+                    .data(days.iter().map(|day| day.to_string()).collect()),
+            )
+            .y_axis(Axis::new().type_(AxisType::Value))
+            .axis_pointer(AxisPointer::new().type_(AxisPointerType::Shadow))
+            .tooltip(Tooltip::new().trigger(Trigger::Axis));
+        for key in &keys {
+            let data: Vec<i64> = days
+                .iter()
+                .map(|day| {
+                    timeline
+                        .iter()
+                        .find(|point| &point.day == day && &point.key == key)
+                        .map(|point| point.count)
+                        .unwrap_or_default()
+                })
+                .collect();
+            chart = chart.series(Bar::new().name(key.as_str()).stack("feed").data(data));
+        }
+        let html_renderer = HtmlRenderer::new("Feed Over Time", width.get() as u64, 400);
+        html_renderer.render(&chart).unwrap_or_default()
+    });
+
+    view! {
+        <div class="flex justify-center items-center mt-4 m-2 relative overflow-hidden rounded-[30px] pt-6 md:pt-[33px] pb-7 md:pb-[39px] pl-[11px] md:pl-[44px]">
+            <div class="m-2 grid grid-cols-1">
+                <iframe
+                    srcdoc=move || html_chart.get()
+                    width=move || width.get()
+                    height="450"
+                ></iframe>
+            </div>
+        </div>
+    }
+}
@@ -1,7 +1,13 @@
+pub mod account_data;
 pub mod admin_dashboard;
+pub mod api_tokens;
 pub mod application_layout;
 pub mod daily_leaderboard;
+pub mod device;
 pub mod extension;
+pub mod feed_analytics;
 pub mod new_dashboard;
+pub mod notifications;
 pub mod perks;
 pub mod referrals;
+pub mod security;
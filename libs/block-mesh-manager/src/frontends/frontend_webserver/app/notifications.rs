@@ -0,0 +1,128 @@
+use crate::frontends::components::heading::Heading;
+use crate::frontends::components::sub_heading::Subheading;
+use crate::frontends::context::notification_context::NotificationContext;
+use block_mesh_common::email_client::locale::EmailLocale;
+use block_mesh_common::interfaces::server_api::NotificationPreferencesRequest;
+use block_mesh_common::routes_enum::RoutesEnum;
+use leptos::logging::log;
+use leptos::*;
+use reqwest::Client;
+
+#[component]
+pub fn Notifications() -> impl IntoView {
+    let notifications = expect_context::<NotificationContext>();
+    let probe_alerts = RwSignal::new(true);
+    let locale = RwSignal::new(EmailLocale::default());
+
+    let preferences = create_local_resource(
+        || (),
+        move |_| async move {
+            let response = Client::new()
+                .get(format!(
+                    "{}{}",
+                    window().origin(),
+                    RoutesEnum::Static_Auth_Notification_Preferences
+                ))
+                .send()
+                .await
+                .ok()?;
+            match response.json::<NotificationPreferencesRequest>().await {
+                Ok(json) => {
+                    probe_alerts.set(json.probe_alerts);
+                    locale.set(json.locale);
+                    Some(json)
+                }
+                Err(e) => {
+                    log!("notification preferences json error {:#?}", e);
+                    None
+                }
+            }
+        },
+    );
+
+    let save = create_action(move |_: &()| async move {
+        let response = Client::new()
+            .post(format!(
+                "{}{}",
+                window().origin(),
+                RoutesEnum::Static_Auth_Notification_Preferences
+            ))
+            .json(&NotificationPreferencesRequest {
+                probe_alerts: probe_alerts.get_untracked(),
+                locale: locale.get_untracked(),
+            })
+            .send()
+            .await;
+        match response {
+            Ok(res) if res.status().is_success() => {
+                notifications.set_success("Notification preferences saved")
+            }
+            _ => notifications.set_error("Failed to save notification preferences"),
+        }
+    });
+
+    view! {
+        <div class="flex items-start justify-start gap-4">
+            <Heading>Notifications</Heading>
+        </div>
+        <Suspense fallback=|| view! { <Subheading class="mt-14">Loading...</Subheading> }>
+            {move || match preferences.get().flatten() {
+                None => view! { <Subheading class="mt-14">Loading...</Subheading> }.into_view(),
+                Some(_) => {
+                    view! {
+                        <p class="mt-8 text-off-white">
+                            "Account emails such as confirmations and password resets are always sent."
+                        </p>
+                        <form
+                            class="mt-4 flex flex-wrap items-end gap-4"
+                            on:submit=move |ev| {
+                                ev.prevent_default();
+                                save.dispatch(());
+                            }
+                        >
+                            <label class="flex items-center gap-2 text-sm/6 text-off-white">
+                                <input
+                                    type="checkbox"
+                                    prop:checked=move || probe_alerts.get()
+                                    on:change=move |ev| probe_alerts.set(event_target_checked(&ev))
+                                />
+                                Probe alerts
+                            </label>
+                            <label class="flex items-center gap-2 text-sm/6 text-off-white">
+                                Language
+                                <select
+                                    class="rounded border px-3 py-2 text-black"
+                                    prop:value=move || locale.get().to_string()
+                                    on:change=move |ev| {
+                                        locale.set(event_target_value(&ev).parse().unwrap_or_default())
+                                    }
+                                >
+                                    {EmailLocale::ALL
+                                        .into_iter()
+                                        .map(|option| {
+                                            view! {
+                                                <option
+                                                    value=option.to_string()
+                                                    selected=move || locale.get() == option
+                                                >
+                                                    {option.title()}
+                                                </option>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </select>
+                            </label>
+                            <button
+                                type="submit"
+                                class="rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+                            >
+                                Save
+                            </button>
+                        </form>
+                    }
+                        .into_view()
+                }
+            }}
+        </Suspense>
+    }
+}
@@ -1,5 +1,6 @@
 use crate::frontends::components::heading::Heading;
 use crate::frontends::components::icons::twitter_icon::TwitterIcon;
+use crate::frontends::components::linked_wallets::LinkedWallets;
 use crate::frontends::components::modal::Modal;
 use crate::frontends::components::sub_heading::Subheading;
 use crate::frontends::components::tables::table::Table;
@@ -22,8 +23,10 @@ pub fn Perks() -> impl IntoView {
     let show_wallet_modal = RwSignal::new(false);
     let wallet_name = RwSignal::new("".to_string());
     let perks = RwSignal::new(vec![]);
+    let daily_stats = RwSignal::new(vec![]);
     let button_enabled = RwSignal::new(true);
     let wallet_address = RwSignal::new("".to_string());
+    let wallets_reload = RwSignal::new(0u32);
 
     if let Some(a) = auth_status {
         let wallet = a.wallet_address.unwrap_or_default();
@@ -36,6 +39,7 @@ pub fn Perks() -> impl IntoView {
 
     if let Some(data) = async_data {
         perks.set(data.perks);
+        daily_stats.set(data.daily_stats);
         button_enabled.set(data.wallet_address.is_none());
     }
     let on_connect_button_click = move || {
@@ -54,6 +58,7 @@ pub fn Perks() -> impl IntoView {
             if connect_wallet_in_browser(w).await {
                 // button_enabled.set(false)
                 notifications.set_success("Wallet connected");
+                wallets_reload.update(|r| *r += 1);
             }
         }
     });
@@ -118,6 +123,7 @@ pub fn Perks() -> impl IntoView {
                 <tr>
                     <TableHeader>Perk</TableHeader>
                     <TableHeader>One Time Bonus</TableHeader>
+                    <TableHeader>Expires</TableHeader>
                     <TableHeader class="text-right">Multiplier</TableHeader>
                 </tr>
             </TableHead>
@@ -132,6 +138,12 @@ pub fn Perks() -> impl IntoView {
                                 <tr>
                                     <TableCell>{referral.name.to_uppercase()}</TableCell>
                                     <TableCell>{referral.one_time_bonus.to_string()}</TableCell>
+                                    <TableCell>
+                                        {referral
+                                            .expires_at
+                                            .map(|expires_at| expires_at.format("%Y-%m-%d").to_string())
+                                            .unwrap_or("-".to_string())}
+                                    </TableCell>
                                     <TableCell class="text-right">
                                         {referral.multiplier.to_string()}
                                     </TableCell>
@@ -143,5 +155,40 @@ pub fn Perks() -> impl IntoView {
 
             </tbody>
         </Table>
+        <Subheading class="mt-14">Perks By Day</Subheading>
+        <Table class="mt-4 [--gutter:theme(spacing.6)] lg:[--gutter:theme(spacing.10)]">
+            <TableHead>
+                <tr>
+                    <TableHeader>Day</TableHeader>
+                    <TableHeader>Perks</TableHeader>
+                    <TableHeader class="text-right">Multiplier</TableHeader>
+                </tr>
+            </TableHead>
+            <tbody>
+                {move || {
+                    daily_stats
+                        .get()
+                        .iter()
+                        .rev()
+                        .cloned()
+                        .map(|daily_stat| {
+                            view! {
+                                <tr>
+                                    <TableCell>{daily_stat.day.to_string()}</TableCell>
+                                    <TableCell>
+                                        {daily_stat.perks.join(", ").to_uppercase()}
+                                    </TableCell>
+                                    <TableCell class="text-right">
+                                        {format!("{:.2}", daily_stat.perk_multiplier)}
+                                    </TableCell>
+                                </tr>
+                            }
+                        })
+                        .collect_view()
+                }}
+
+            </tbody>
+        </Table>
+        <LinkedWallets reload=wallets_reload/>
     }
 }
@@ -16,10 +16,14 @@ use leptos::*;
 pub fn Referrals() -> impl IntoView {
     let async_data = use_context::<DashboardResponse>();
     let referrals = RwSignal::new(vec![]);
+    let referral_earnings = RwSignal::new(vec![]);
+    let referral_points = RwSignal::new(0.0);
     let invite_code = RwSignal::new("".to_string());
     let show_invite_code = RwSignal::new(false);
     if let Some(data) = async_data {
         referrals.set(data.referrals);
+        referral_earnings.set(data.referral_earnings);
+        referral_points.set(data.referral_points);
         invite_code.set(data.invite_code);
     }
 
@@ -138,6 +142,39 @@ pub fn Referrals() -> impl IntoView {
 
         </div>
 
+        <Subheading class="mt-14">
+            "Referral Earnings: " {move || format!("{:.2}", referral_points.get())} " points"
+        </Subheading>
+        <Table class="mt-4 [--gutter:theme(spacing.6)] lg:[--gutter:theme(spacing.10)]">
+            <TableHead>
+                <tr>
+                    <TableHeader>Day</TableHeader>
+                    <TableHeader>Direct Referrals</TableHeader>
+                    <TableHeader class="text-right">Second Level</TableHeader>
+                </tr>
+            </TableHead>
+            <tbody>
+                {referral_earnings
+                    .get()
+                    .iter()
+                    .rev()
+                    .cloned()
+                    .map(|earning| {
+                        view! {
+                            <tr>
+                                <TableCell>{earning.day.to_string()}</TableCell>
+                                <TableCell>{format!("{:.2}", earning.level_1_points)}</TableCell>
+                                <TableCell class="text-right">
+                                    {format!("{:.2}", earning.level_2_points)}
+                                </TableCell>
+                            </tr>
+                        }
+                    })
+                    .collect_view()}
+
+            </tbody>
+        </Table>
+
         <Subheading class="mt-14">Referrals List</Subheading>
         <Table class="mt-4 [--gutter:theme(spacing.6)] lg:[--gutter:theme(spacing.10)]">
             <TableHead>
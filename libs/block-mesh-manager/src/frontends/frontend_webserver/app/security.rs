@@ -0,0 +1,258 @@
+use crate::frontends::components::heading::Heading;
+use crate::frontends::components::sub_heading::Subheading;
+use crate::frontends::context::notification_context::NotificationContext;
+use block_mesh_common::interfaces::server_api::{
+    TwoFactorCodeRequest, TwoFactorEnrollResponse, TwoFactorRecoveryCodesResponse, TwoFactorStatus,
+};
+use block_mesh_common::routes_enum::RoutesEnum;
+use leptos::logging::log;
+use leptos::*;
+use reqwest::Client;
+
+#[derive(Clone, Copy, PartialEq)]
+enum CodeAction {
+    Enable,
+    Verify,
+    RecoveryCodes,
+    Disable,
+}
+
+impl CodeAction {
+    fn route(self) -> RoutesEnum {
+        match self {
+            CodeAction::Enable => RoutesEnum::Static_Auth_Two_Factor_Enable,
+            CodeAction::Verify => RoutesEnum::Static_Auth_Two_Factor_Verify,
+            CodeAction::RecoveryCodes => RoutesEnum::Static_Auth_Two_Factor_Recovery_Codes,
+            CodeAction::Disable => RoutesEnum::Static_Auth_Two_Factor_Disable,
+        }
+    }
+}
+
+async fn post_code(route: RoutesEnum, code: String) -> Option<reqwest::Response> {
+    Client::new()
+        .post(format!("{}{}", window().origin(), route))
+        .json(&TwoFactorCodeRequest { code })
+        .send()
+        .await
+        .ok()
+}
+
+#[component]
+pub fn Security() -> impl IntoView {
+    let notifications = expect_context::<NotificationContext>();
+    let reload = RwSignal::new(0u32);
+    let enrollment = RwSignal::new(None::<TwoFactorEnrollResponse>);
+    let recovery_codes = RwSignal::new(None::<Vec<String>>);
+    let code = RwSignal::new(String::default());
+
+    let status = create_local_resource(
+        move || reload.get(),
+        |_| async move {
+            let response = Client::new()
+                .get(format!(
+                    "{}{}",
+                    window().origin(),
+                    RoutesEnum::Static_Auth_Two_Factor
+                ))
+                .send()
+                .await
+                .ok()?;
+            match response.json::<TwoFactorStatus>().await {
+                Ok(json) => Some(json),
+                Err(e) => {
+                    log!("two factor status json error {:#?}", e);
+                    None
+                }
+            }
+        },
+    );
+
+    let enroll = create_action(move |_: &()| async move {
+        let response = Client::new()
+            .post(format!(
+                "{}{}",
+                window().origin(),
+                RoutesEnum::Static_Auth_Two_Factor_Enroll
+            ))
+            .send()
+            .await;
+        match response {
+            Ok(res) if res.status().is_success() => {
+                if let Ok(json) = res.json::<TwoFactorEnrollResponse>().await {
+                    enrollment.set(Some(json));
+                }
+            }
+            _ => notifications.set_error("Failed to start two factor setup"),
+        }
+    });
+
+    let submit_code = create_action(move |action: &CodeAction| {
+        let action = *action;
+        async move {
+            match post_code(action.route(), code.get_untracked()).await {
+                Some(res) if res.status().is_success() => {
+                    if matches!(action, CodeAction::Enable | CodeAction::RecoveryCodes) {
+                        if let Ok(json) = res.json::<TwoFactorRecoveryCodesResponse>().await {
+                            recovery_codes.set(Some(json.recovery_codes));
+                        }
+                    } else {
+                        recovery_codes.set(None);
+                    }
+                    if action == CodeAction::Disable {
+                        notifications.set_success("Two factor authentication disabled");
+                    } else {
+                        notifications.set_success("Code confirmed");
+                    }
+                    enrollment.set(None);
+                    code.set(String::default());
+                    reload.update(|r| *r += 1);
+                }
+                _ => notifications.set_error("Invalid two factor code"),
+            }
+        }
+    });
+
+    let forget_devices = create_action(move |_: &()| async move {
+        let response = Client::new()
+            .post(format!(
+                "{}{}",
+                window().origin(),
+                RoutesEnum::Static_Auth_Two_Factor_Forget_Devices
+            ))
+            .send()
+            .await;
+        match response {
+            Ok(res) if res.status().is_success() => {
+                reload.update(|r| *r += 1);
+                notifications.set_success("Remembered devices cleared");
+            }
+            _ => notifications.set_error("Failed to clear remembered devices"),
+        }
+    });
+
+    let code_input = move || {
+        view! {
+            <input
+                class="rounded border px-3 py-2 text-black"
+                type="text"
+                autocomplete="one-time-code"
+                placeholder="Code"
+                prop:value=move || code.get()
+                on:input=move |ev| code.set(event_target_value(&ev))
+            />
+        }
+    };
+
+    view! {
+        <div class="flex items-start justify-start gap-4">
+            <Heading>Security</Heading>
+        </div>
+        {move || {
+            recovery_codes
+                .get()
+                .map(|codes| {
+                    view! {
+                        <div class="mt-4 rounded-lg border border-white/10 p-4 text-off-white">
+                            <p>
+                                "Store these recovery codes somewhere safe, each works once and they will not be shown again:"
+                            </p>
+                            <code class="mt-2 block select-all whitespace-pre">
+                                {codes.join("\n")}
+                            </code>
+                        </div>
+                    }
+                })
+        }}
+        <Suspense fallback=|| view! { <Subheading class="mt-14">Loading...</Subheading> }>
+            {move || match (status.get().flatten(), enrollment.get()) {
+                (None, _) => view! { <Subheading class="mt-14">Loading...</Subheading> }.into_view(),
+                (Some(status), _) if status.enabled => {
+                    view! {
+                        <Subheading class="mt-8">
+                            Two factor authentication is on <span class="pr-2 pl-2">|</span>
+                            {status.recovery_codes_left} " recovery codes left"
+                            <span class="pr-2 pl-2">|</span> {status.remembered_devices}
+                            " remembered devices"
+                        </Subheading>
+                        <p class="mt-4 text-off-white">
+                            "Wallet changes, invite code changes and password resets ask for a code, confirming one here covers them for 10 minutes."
+                        </p>
+                        <div class="mt-4 flex flex-wrap items-end gap-2">
+                            {code_input()}
+                            <button
+                                class="rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+                                on:click=move |_| {
+                                    submit_code.dispatch(CodeAction::Verify)
+                                }
+                            >
+                                Confirm
+                            </button>
+                            <button
+                                class="rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+                                on:click=move |_| {
+                                    submit_code.dispatch(CodeAction::RecoveryCodes)
+                                }
+                            >
+                                New recovery codes
+                            </button>
+                            <button
+                                class="rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+                                on:click=move |_| {
+                                    submit_code.dispatch(CodeAction::Disable)
+                                }
+                            >
+                                Disable
+                            </button>
+                            <button
+                                class="rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+                                on:click=move |_| forget_devices.dispatch(())
+                            >
+                                Forget remembered devices
+                            </button>
+                        </div>
+                    }
+                        .into_view()
+                }
+                (Some(_), Some(enrollment)) => {
+                    view! {
+                        <Subheading class="mt-8">
+                            Scan the QR code with your authenticator app, then enter the code it shows
+                        </Subheading>
+                        <div class="mt-4 w-fit bg-white p-2" inner_html=enrollment.qr_svg></div>
+                        <p class="mt-4 text-off-white">
+                            "Or enter the key manually: "
+                            <code class="select-all">{enrollment.secret}</code>
+                        </p>
+                        <form class="mt-4 flex items-end gap-2" on:submit=move |ev| {
+                            ev.prevent_default();
+                            submit_code.dispatch(CodeAction::Enable);
+                        }>
+                            {code_input()}
+                            <button
+                                type="submit"
+                                class="rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+                            >
+                                Enable
+                            </button>
+                        </form>
+                    }
+                        .into_view()
+                }
+                (Some(_), None) => {
+                    view! {
+                        <Subheading class="mt-8">Two factor authentication is off</Subheading>
+                        <div class="mt-4">
+                            <button
+                                class="rounded-lg border border-white/10 px-3 py-1 text-sm/6 text-off-white"
+                                on:click=move |_| enroll.dispatch(())
+                            >
+                                Set up
+                            </button>
+                        </div>
+                    }
+                        .into_view()
+                }
+            }}
+        </Suspense>
+    }
+}
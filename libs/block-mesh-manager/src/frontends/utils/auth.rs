@@ -5,15 +5,16 @@ use anyhow::anyhow;
 use leptos::leptos_dom;
 #[allow(unused_imports)]
 use leptos_dom::tracing;
+use reqwest::StatusCode;
 
 use block_mesh_common::interfaces::server_api::{
-    ConnectWalletRequest, ConnectWalletResponse, GetTokenResponse, LoginForm, RegisterForm,
-    RegisterResponse,
+    ConnectWalletRequest, ConnectWalletResponse, GetTokenRequest, GetTokenResponse, RegisterForm,
+    RegisterResponse, SiwsMessageQuery, SiwsMessageResponse,
 };
 use block_mesh_common::routes_enum::RoutesEnum;
+use block_mesh_common::siws::SiwsPurpose;
 use js_sys::Uint8Array;
 use leptos::*;
-use uuid::Uuid;
 
 pub async fn register(blockmesh_url: &str, credentials: &RegisterForm) -> anyhow::Result<()> {
     let url = format!("{}{}", blockmesh_url, RoutesEnum::Static_UnAuth_RegisterApi);
@@ -30,11 +31,13 @@ pub async fn register(blockmesh_url: &str, credentials: &RegisterForm) -> anyhow
     }
 }
 
+/// `None` when the account has two factor enabled and `credentials` carries no valid code.
 pub async fn login(
     blockmesh_url: &str,
-    credentials: &LoginForm,
-) -> anyhow::Result<GetTokenResponse> {
-    let blockmesh_url = if blockmesh_url.contains("app") {
+    credentials: &GetTokenRequest,
+) -> anyhow::Result<Option<GetTokenResponse>> {
+    // Only the app server checks two factor codes, the api service reads from a follower.
+    let blockmesh_url = if blockmesh_url.contains("app") && credentials.totp_code.is_none() {
         blockmesh_url.replace("app", "api")
     } else {
         blockmesh_url.to_string()
@@ -46,64 +49,108 @@ pub async fn login(
         .header("Content-Type", "application/json")
         .json(&credentials)
         .send()
-        .await?
-        .json()
         .await?;
-    Ok(response)
+    if response.status() == StatusCode::FORBIDDEN {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(anyhow!(response.text().await?));
+    }
+    Ok(Some(response.json().await?))
 }
 
+/// `None` when the account has two factor enabled and no code was confirmed recently.
 pub async fn connect_wallet(
     origin: String,
     connect_wallet_request: ConnectWalletRequest,
-) -> anyhow::Result<ConnectWalletResponse> {
+) -> anyhow::Result<Option<ConnectWalletResponse>> {
     let client = reqwest::Client::new();
     let response = client
         .post(format!("{}/api/connect_wallet", origin))
         .header("Content-Type", "application/json")
         .json(&connect_wallet_request)
         .send()
+        .await?;
+    if response.status() == StatusCode::FORBIDDEN {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(anyhow!(response.text().await?));
+    }
+    Ok(Some(response.json().await?))
+}
+
+pub async fn siws_message(
+    origin: &str,
+    address: &str,
+    purpose: SiwsPurpose,
+) -> anyhow::Result<String> {
+    let response: SiwsMessageResponse = reqwest::Client::new()
+        .get(format!(
+            "{}{}",
+            origin,
+            RoutesEnum::Static_UnAuth_Siws_Message
+        ))
+        .query(&SiwsMessageQuery {
+            address: address.to_string(),
+            purpose,
+        })
+        .send()
         .await?
+        .error_for_status()?
         .json()
         .await?;
-    Ok(response)
+    Ok(response.message)
 }
 
 pub async fn connect_wallet_in_browser(wallet: String) -> bool {
     if wallet.is_empty() {
         return false;
     }
-    let msg = Uuid::new_v4().to_string();
+    let notifications = expect_context::<NotificationContext>();
+    let origin = window().origin();
     let key = pubkey(&wallet).await;
+    let Some(pubkey) = key.as_string() else {
+        notifications.set_error("Failed to read the wallet address");
+        return false;
+    };
+    let msg = match siws_message(&origin, &pubkey, SiwsPurpose::Link).await {
+        Ok(msg) => msg,
+        Err(_) => {
+            notifications.set_error("Failed to prepare the wallet message");
+            return false;
+        }
+    };
     let sign = sign_message(&msg, &wallet).await;
 
     let uint8_array = Uint8Array::new(&sign);
     let mut signature = vec![0; uint8_array.length() as usize];
     uint8_array.copy_to(&mut signature[..]);
 
-    let origin = window().origin();
-
-    let pubkey = key.as_string().unwrap();
-
-    let notifications = expect_context::<NotificationContext>();
-
     match connect_wallet(
         origin,
         ConnectWalletRequest {
             pubkey: pubkey.clone(),
-            message: msg.to_string(),
+            message: msg,
             signature,
+            totp_code: None,
         },
     )
     .await
     {
-        Ok(_) => {
+        Ok(Some(_)) => {
             let auth = expect_context::<AuthContext>();
             auth.wallet_address.set(Some(pubkey));
             notifications.set_success("Connected successfully");
             true
         }
-        Err(_) => {
-            notifications.set_error("Failed to connect");
+        Ok(None) => {
+            notifications
+                .set_error("Confirm a two factor code on the Security page, then connect again");
+            false
+        }
+        Err(e) => {
+            notifications.set_error(format!("Failed to connect: {e}"));
             false
         }
     }
@@ -5,7 +5,6 @@
 use cfg_if::cfg_if;
 
 cfg_if! { if #[cfg(feature = "ssr")] {
-    use block_mesh_common::email_client::client::EmailClient;
     use database_utils::utils::connection::channel_pool::channel_pool;
     use database_utils::utils::connection::follower_pool::follower_pool;
     use database_utils::utils::connection::unlimited_pool::unlimited_pool;
@@ -98,7 +97,6 @@ async fn run() -> anyhow::Result<()> {
         .await
         .expect("Failed to migrate database");
     tracing::info!("Database migration complete");
-    let email_client = Arc::new(EmailClient::new(configuration.application.base_url.clone()).await);
     let client = http_client(DeviceType::AppServer);
     tracing::info!("Starting to get feature flags");
     let flags = Arc::new(
@@ -121,14 +119,6 @@ async fn run() -> anyhow::Result<()> {
     let _ = create_test_user(&db_pool).await;
     let check_token_map: CheckTokenResponseMap = Arc::new(DashMap::new());
     let get_token_map: GetTokenResponseMap = Arc::new(DashMap::new());
-    let submit_bandwidth_limit = env::var("APP_BW_LIMIT")
-        .unwrap_or("false".to_string())
-        .parse()
-        .unwrap_or(false);
-    let rate_limit = env::var("APP_RATE_LIMIT")
-        .unwrap_or("false".to_string())
-        .parse()
-        .unwrap_or(false);
     let task_limit = env::var("APP_TASK_LIMIT")
         .unwrap_or("false".to_string())
         .parse()
@@ -148,12 +138,9 @@ async fn run() -> anyhow::Result<()> {
         cf_site_key,
         wallet_addresses,
         invite_codes,
-        submit_bandwidth_limit,
         task_limit,
-        rate_limit,
         check_token_map,
         get_token_map,
-        email_client,
         pool: db_pool.clone(),
         follower_pool,
         channel_pool,
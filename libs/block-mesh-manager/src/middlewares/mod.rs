@@ -1,3 +1,4 @@
 pub mod authentication;
 pub mod rate_limit;
+pub mod rate_limit_layer;
 // pub mod request_id;
@@ -1,74 +1,450 @@
-use crate::utils::cache_envar::get_envar;
+use crate::configuration::rate_limit_settings::{
+    RateLimitAlgorithm, RateLimitKey, RateLimitPolicy, RateLimitSettings,
+};
+use crate::errors::error::Error;
+use dashmap::DashMap;
+use http::{HeaderMap, HeaderName, HeaderValue};
 use redis::aio::MultiplexedConnection;
-use redis::{AsyncCommands, RedisResult};
+use redis::Script;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
-pub fn next_allowed_key(suffix: &str, namespace: &str) -> String {
-    format!("next-allowed-{}-{}", namespace, suffix)
+pub const REGISTER_POLICY: &str = "register";
+pub const RESET_PASSWORD_POLICY: &str = "reset_password";
+pub const RESEND_CONFIRM_EMAIL_POLICY: &str = "resend_confirm_email";
+pub const GET_TASK_POLICY: &str = "get_task";
+pub const SUBMIT_BANDWIDTH_POLICY: &str = "submit_bandwidth";
+pub const REPORT_UPTIME_POLICY: &str = "report_uptime";
+pub const SIWS_MESSAGE_POLICY: &str = "siws_message";
+
+/// Local buckets are swept once there are more than this many of them.
+const LOCAL_SWEEP_THRESHOLD: usize = 10_000;
+
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local capacity = tonumber(ARGV[1])
+local rate = tonumber(ARGV[2])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) + tonumber(time[2]) / 1000000
+local state = redis.call('HMGET', KEYS[1], 'tokens', 'updated')
+local tokens = tonumber(state[1]) or capacity
+local updated = tonumber(state[2]) or now
+tokens = math.min(capacity, tokens + math.max(0, now - updated) * rate)
+local allowed = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+end
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'updated', tostring(now))
+local reset = math.ceil((capacity - tokens) / rate)
+redis.call('EXPIRE', KEYS[1], reset + 1)
+local retry_after = 0
+if allowed == 0 then
+    retry_after = math.ceil((1 - tokens) / rate)
+end
+return {allowed, math.floor(tokens), reset, retry_after}
+"#;
+
+const SLIDING_WINDOW_SCRIPT: &str = r#"
+local limit = tonumber(ARGV[1])
+local window = tonumber(ARGV[2])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+redis.call('ZREMRANGEBYSCORE', KEYS[1], '-inf', now - window)
+local count = redis.call('ZCARD', KEYS[1])
+local allowed = 0
+if count < limit then
+    redis.call('ZADD', KEYS[1], now, ARGV[3])
+    count = count + 1
+    allowed = 1
+end
+redis.call('PEXPIRE', KEYS[1], window)
+local reset = window
+local oldest = redis.call('ZRANGE', KEYS[1], 0, 0, 'WITHSCORES')
+if oldest[2] then
+    reset = tonumber(oldest[2]) + window - now
+end
+reset = math.ceil(reset / 1000)
+local retry_after = 0
+if allowed == 0 then
+    retry_after = reset
+end
+return {allowed, limit - count, reset, retry_after}
+"#;
+
+#[derive(Debug, Clone)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u64,
+    pub remaining: u64,
+    pub reset_secs: u64,
+    pub retry_after_secs: u64,
+    pub window_secs: u64,
 }
 
-#[tracing::instrument(name = "get_next_allowed_request", skip_all)]
-pub async fn get_next_allowed_request(
-    con: &mut MultiplexedConnection,
-    user_id: &Uuid,
-    ip: &str,
-    namespace: &str,
-) -> anyhow::Result<bool> {
-    let r: RedisResult<String> = con
-        .get(next_allowed_key(&user_id.to_string(), namespace))
-        .await;
-    match r {
-        Ok(_) => return Ok(true),
-        Err(e) => {
-            tracing::trace!("e => {e}");
+impl RateLimitDecision {
+    /// Keeps whichever of the two decisions is more restrictive.
+    pub fn merge(self, other: Self) -> Self {
+        match (self.allowed, other.allowed) {
+            (true, false) => other,
+            (false, true) => self,
+            (false, false) if other.retry_after_secs > self.retry_after_secs => other,
+            (true, true) if other.remaining < self.remaining => other,
+            _ => self,
         }
-    };
-    let r: RedisResult<String> = con.get(next_allowed_key(ip, namespace)).await;
-    match r {
-        Ok(_) => return Ok(true),
-        Err(e) => {
-            tracing::trace!("e => {e}");
+    }
+
+    pub fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("ratelimit-limit"),
+            HeaderValue::from(self.limit),
+        );
+        headers.insert(
+            HeaderName::from_static("ratelimit-remaining"),
+            HeaderValue::from(self.remaining),
+        );
+        headers.insert(
+            HeaderName::from_static("ratelimit-reset"),
+            HeaderValue::from(self.reset_secs),
+        );
+        if let Ok(policy) = HeaderValue::from_str(&format!("{};w={}", self.limit, self.window_secs))
+        {
+            headers.insert(HeaderName::from_static("ratelimit-policy"), policy);
         }
-    };
-    Ok(false)
+        if !self.allowed {
+            headers.insert(
+                http::header::RETRY_AFTER,
+                HeaderValue::from(self.retry_after_secs.max(1)),
+            );
+        }
+        headers
+    }
+}
+
+struct LocalBucket {
+    tokens: f64,
+    updated: Instant,
+    expires_at: Instant,
+}
+
+struct LocalWindow {
+    log: VecDeque<Instant>,
+    expires_at: Instant,
+}
+
+/// The process local buckets used while Redis is unreachable or slow.
+#[derive(Default)]
+struct LocalLimiter {
+    buckets: DashMap<String, LocalBucket>,
+    windows: DashMap<String, LocalWindow>,
 }
 
-#[tracing::instrument(name = "create_next_allowed_request", skip_all)]
-pub async fn create_next_allowed_request(
-    con: &mut MultiplexedConnection,
-    user_id: &Uuid,
-    ip: &str,
-    expiry: u64,
-    namespace: &str,
-) {
-    let _: RedisResult<()> = con
-        .set_ex(
-            next_allowed_key(&user_id.to_string(), namespace),
-            "true",
-            expiry,
-        )
-        .await;
-    let _: RedisResult<()> = con
-        .set_ex(next_allowed_key(ip, namespace), "true", expiry)
-        .await;
+/// Redis backed limiter shared by every instance, each bucket falls back to a process local
+/// one while Redis is unreachable or slow.
+pub struct RateLimiter {
+    settings: RateLimitSettings,
+    redis: MultiplexedConnection,
+    local: LocalLimiter,
+    token_bucket: Script,
+    sliding_window: Script,
 }
 
-#[tracing::instrument(name = "filter_request", skip_all)]
-pub async fn filter_request(
-    con: &mut MultiplexedConnection,
-    user_id: &Uuid,
-    ip: &str,
-    namespace: &str,
-) -> anyhow::Result<bool> {
-    let expiry = get_envar("FILTER_REQUEST_EXPIRY_SECONDS")
-        .await
-        .parse()
-        .unwrap_or(3u64);
-    let exists = get_next_allowed_request(con, user_id, ip, namespace).await?;
-    if exists {
-        Ok(false)
-    } else {
-        create_next_allowed_request(con, user_id, ip, expiry, namespace).await;
-        Ok(true)
+impl RateLimiter {
+    pub fn new(settings: RateLimitSettings, redis: MultiplexedConnection) -> Self {
+        Self {
+            settings,
+            redis,
+            local: LocalLimiter::default(),
+            token_bucket: Script::new(TOKEN_BUCKET_SCRIPT),
+            sliding_window: Script::new(SLIDING_WINDOW_SCRIPT),
+        }
+    }
+
+    /// Returns the policy if it is configured and enabled.
+    pub fn policy(&self, name: &str) -> Option<&RateLimitPolicy> {
+        self.settings
+            .policies
+            .get(name)
+            .filter(|policy| policy.enabled)
+    }
+
+    pub fn trust_cloudflare_ip(&self) -> bool {
+        self.settings.trust_cloudflare_ip
+    }
+
+    /// Takes one request out of the `key` bucket for `value`, `None` when the policy does not limit by `key`.
+    #[tracing::instrument(name = "rate_limit_check", skip(self, value))]
+    pub async fn check(
+        &self,
+        name: &str,
+        key: RateLimitKey,
+        value: &str,
+    ) -> Option<RateLimitDecision> {
+        let policy = self.policy(name)?;
+        if !policy.keys.contains(&key) {
+            return None;
+        }
+        let bucket = format!("rate-limit-{}-{}-{}", name, key.as_str(), value);
+        let decision = match self.check_redis(&bucket, &policy.algorithm).await {
+            Ok(decision) => decision,
+            Err(e) => {
+                tracing::warn!("rate limit falling back to local bucket: {}", e);
+                self.local.check(&bucket, &policy.algorithm, Instant::now())
+            }
+        };
+        Some(decision)
+    }
+
+    /// Handler side check for keys the middleware cannot see, such as the email of a form.
+    pub async fn enforce(&self, name: &str, key: RateLimitKey, value: &str) -> Result<(), Error> {
+        match self.check(name, key, value).await {
+            Some(decision) if !decision.allowed => Err(Error::RateLimited(decision)),
+            _ => Ok(()),
+        }
+    }
+
+    async fn check_redis(
+        &self,
+        bucket: &str,
+        algorithm: &RateLimitAlgorithm,
+    ) -> anyhow::Result<RateLimitDecision> {
+        let mut redis = self.redis.clone();
+        let invocation = match algorithm {
+            RateLimitAlgorithm::TokenBucket {
+                capacity,
+                refill_per_sec,
+            } => {
+                let mut invocation = self.token_bucket.key(bucket);
+                invocation
+                    .arg(*capacity)
+                    .arg(refill_per_sec.max(f64::EPSILON));
+                invocation
+            }
+            RateLimitAlgorithm::SlidingWindow { limit, window_secs } => {
+                let mut invocation = self.sliding_window.key(bucket);
+                invocation
+                    .arg(*limit)
+                    .arg(window_secs * 1_000)
+                    .arg(Uuid::new_v4().to_string());
+                invocation
+            }
+        };
+        let (allowed, remaining, reset_secs, retry_after_secs): (i64, i64, i64, i64) =
+            tokio::time::timeout(
+                Duration::from_millis(self.settings.redis_timeout_ms),
+                invocation.invoke_async(&mut redis),
+            )
+            .await??;
+        Ok(RateLimitDecision {
+            allowed: allowed == 1,
+            limit: algorithm.limit(),
+            remaining: remaining.max(0) as u64,
+            reset_secs: reset_secs.max(0) as u64,
+            retry_after_secs: retry_after_secs.max(0) as u64,
+            window_secs: algorithm.window_secs(),
+        })
+    }
+}
+
+impl LocalLimiter {
+    fn check(
+        &self,
+        bucket: &str,
+        algorithm: &RateLimitAlgorithm,
+        now: Instant,
+    ) -> RateLimitDecision {
+        match algorithm {
+            RateLimitAlgorithm::TokenBucket {
+                capacity,
+                refill_per_sec,
+            } => {
+                if self.buckets.len() > LOCAL_SWEEP_THRESHOLD {
+                    self.buckets.retain(|_, entry| entry.expires_at > now);
+                }
+                let capacity = *capacity as f64;
+                let rate = refill_per_sec.max(f64::EPSILON);
+                let mut entry =
+                    self.buckets
+                        .entry(bucket.to_string())
+                        .or_insert_with(|| LocalBucket {
+                            tokens: capacity,
+                            updated: now,
+                            expires_at: now,
+                        });
+                let tokens = (entry.tokens
+                    + now.duration_since(entry.updated).as_secs_f64() * rate)
+                    .min(capacity);
+                let allowed = tokens >= 1.0;
+                let tokens = if allowed { tokens - 1.0 } else { tokens };
+                let reset = ((capacity - tokens) / rate).ceil();
+                entry.tokens = tokens;
+                entry.updated = now;
+                entry.expires_at = now + Duration::from_secs_f64(reset);
+                RateLimitDecision {
+                    allowed,
+                    limit: algorithm.limit(),
+                    remaining: tokens.floor() as u64,
+                    reset_secs: reset as u64,
+                    retry_after_secs: if allowed {
+                        0
+                    } else {
+                        ((1.0 - tokens) / rate).ceil() as u64
+                    },
+                    window_secs: algorithm.window_secs(),
+                }
+            }
+            RateLimitAlgorithm::SlidingWindow { limit, window_secs } => {
+                if self.windows.len() > LOCAL_SWEEP_THRESHOLD {
+                    self.windows.retain(|_, entry| entry.expires_at > now);
+                }
+                let window = Duration::from_secs(*window_secs);
+                let mut entry =
+                    self.windows
+                        .entry(bucket.to_string())
+                        .or_insert_with(|| LocalWindow {
+                            log: VecDeque::new(),
+                            expires_at: now,
+                        });
+                while entry
+                    .log
+                    .front()
+                    .is_some_and(|oldest| now.duration_since(*oldest) >= window)
+                {
+                    entry.log.pop_front();
+                }
+                let allowed = (entry.log.len() as u64) < *limit;
+                if allowed {
+                    entry.log.push_back(now);
+                }
+                entry.expires_at = now + window;
+                let reset_secs = entry
+                    .log
+                    .front()
+                    .map(|oldest| window.saturating_sub(now.duration_since(*oldest)))
+                    .unwrap_or(window)
+                    .as_secs_f64()
+                    .ceil() as u64;
+                RateLimitDecision {
+                    allowed,
+                    limit: *limit,
+                    remaining: limit.saturating_sub(entry.log.len() as u64),
+                    reset_secs,
+                    retry_after_secs: if allowed { 0 } else { reset_secs },
+                    window_secs: *window_secs,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUCKET: RateLimitAlgorithm = RateLimitAlgorithm::TokenBucket {
+        capacity: 2,
+        refill_per_sec: 0.5,
+    };
+    const WINDOW: RateLimitAlgorithm = RateLimitAlgorithm::SlidingWindow {
+        limit: 2,
+        window_secs: 10,
+    };
+
+    fn decision(allowed: bool, remaining: u64, retry_after_secs: u64) -> RateLimitDecision {
+        RateLimitDecision {
+            allowed,
+            limit: 5,
+            remaining,
+            reset_secs: 10,
+            retry_after_secs,
+            window_secs: 10,
+        }
+    }
+
+    #[test]
+    fn token_bucket_drains_and_refills() {
+        let limiter = LocalLimiter::default();
+        let now = Instant::now();
+        let first = limiter.check("a", &BUCKET, now);
+        assert!(first.allowed);
+        assert_eq!(first.remaining, 1);
+        assert_eq!(first.reset_secs, 2);
+        assert!(limiter.check("a", &BUCKET, now).allowed);
+        let denied = limiter.check("a", &BUCKET, now);
+        assert!(!denied.allowed);
+        assert_eq!(denied.remaining, 0);
+        assert_eq!(denied.retry_after_secs, 2);
+        assert!(limiter.check("b", &BUCKET, now).allowed);
+        assert!(
+            !limiter
+                .check("a", &BUCKET, now + Duration::from_secs(1))
+                .allowed
+        );
+        let refilled = limiter.check("a", &BUCKET, now + Duration::from_secs(2));
+        assert!(refilled.allowed);
+        assert_eq!(refilled.remaining, 0);
+    }
+
+    #[test]
+    fn token_bucket_never_exceeds_capacity() {
+        let limiter = LocalLimiter::default();
+        let now = Instant::now();
+        limiter.check("a", &BUCKET, now);
+        let later = now + Duration::from_secs(3600);
+        assert_eq!(limiter.check("a", &BUCKET, later).remaining, 1);
+        assert!(limiter.check("a", &BUCKET, later).allowed);
+        assert!(!limiter.check("a", &BUCKET, later).allowed);
+    }
+
+    #[test]
+    fn sliding_window_counts_requests_in_the_window() {
+        let limiter = LocalLimiter::default();
+        let now = Instant::now();
+        assert_eq!(limiter.check("a", &WINDOW, now).remaining, 1);
+        let second = limiter.check("a", &WINDOW, now + Duration::from_secs(4));
+        assert!(second.allowed);
+        assert_eq!(second.remaining, 0);
+        let denied = limiter.check("a", &WINDOW, now + Duration::from_secs(6));
+        assert!(!denied.allowed);
+        assert_eq!(denied.retry_after_secs, 4);
+        assert_eq!(denied.reset_secs, 4);
+        let slid = limiter.check("a", &WINDOW, now + Duration::from_secs(10));
+        assert!(slid.allowed);
+        assert_eq!(slid.remaining, 0);
+        assert!(
+            !limiter
+                .check("a", &WINDOW, now + Duration::from_secs(11))
+                .allowed
+        );
+        assert!(limiter.check("b", &WINDOW, now).allowed);
+    }
+
+    #[test]
+    fn merge_keeps_the_most_restrictive_decision() {
+        let merged = decision(true, 3, 0).merge(decision(false, 0, 5));
+        assert!(!merged.allowed);
+        let merged = decision(false, 0, 5).merge(decision(true, 3, 0));
+        assert!(!merged.allowed);
+        let merged = decision(false, 0, 5).merge(decision(false, 0, 9));
+        assert_eq!(merged.retry_after_secs, 9);
+        let merged = decision(false, 0, 9).merge(decision(false, 0, 5));
+        assert_eq!(merged.retry_after_secs, 9);
+        let merged = decision(true, 3, 0).merge(decision(true, 1, 0));
+        assert_eq!(merged.remaining, 1);
+        let merged = decision(true, 1, 0).merge(decision(true, 3, 0));
+        assert_eq!(merged.remaining, 1);
+    }
+
+    #[test]
+    fn headers_follow_the_ratelimit_draft() {
+        let headers = decision(true, 3, 0).headers();
+        assert_eq!(headers["ratelimit-limit"], "5");
+        assert_eq!(headers["ratelimit-remaining"], "3");
+        assert_eq!(headers["ratelimit-reset"], "10");
+        assert_eq!(headers["ratelimit-policy"], "5;w=10");
+        assert!(headers.get(http::header::RETRY_AFTER).is_none());
+        let headers = decision(false, 0, 0).headers();
+        assert_eq!(headers[http::header::RETRY_AFTER], "1");
     }
 }
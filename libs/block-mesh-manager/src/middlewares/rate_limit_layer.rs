@@ -0,0 +1,195 @@
+use crate::configuration::rate_limit_settings::RateLimitKey;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::AuthSession;
+use crate::middlewares::rate_limit::{RateLimitDecision, RateLimiter};
+use axum::body::Body;
+use axum::extract::{ConnectInfo, Query, Request};
+use axum::response::{IntoResponse, Response};
+use block_mesh_manager_database_domain::domain::authenticate_token::bearer_token;
+use futures::future::BoxFuture;
+use http::header::CONTENT_LENGTH;
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use uuid::Uuid;
+
+/// Bodies larger than this are not searched for an `api_token` field.
+const MAX_TOKEN_BODY_BYTES: usize = 256 * 1024;
+
+#[derive(Deserialize)]
+struct ApiTokenField {
+    api_token: Option<Uuid>,
+}
+
+/// Applies the named policy of the `RateLimiter` found in the request extensions.
+#[derive(Clone, Copy, Debug)]
+pub struct RateLimitLayer {
+    policy: &'static str,
+    limited: Option<fn() -> Response>,
+}
+
+impl RateLimitLayer {
+    pub fn new(policy: &'static str) -> Self {
+        Self {
+            policy,
+            limited: None,
+        }
+    }
+
+    /// Answers limited requests with `limited` instead of a 429, for clients that predate
+    /// the status code. The `RateLimit-*` headers are still added.
+    pub fn with_limited_response(mut self, limited: fn() -> Response) -> Self {
+        self.limited = Some(limited);
+        self
+    }
+}
+
+impl<S> tower::Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            policy: self.policy,
+            limited: self.limited,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RateLimitService<S> {
+    inner: S,
+    policy: &'static str,
+    limited: Option<fn() -> Response>,
+}
+
+impl<S> tower::Service<Request> for RateLimitService<S>
+where
+    S: tower::Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let policy = self.policy;
+        let limited = self.limited;
+        Box::pin(async move {
+            let Some(limiter) = request.extensions().get::<Arc<RateLimiter>>().cloned() else {
+                return inner.call(request).await;
+            };
+            let Some(keys) = limiter.policy(policy).map(|policy| policy.keys.clone()) else {
+                return inner.call(request).await;
+            };
+            let trust_cloudflare_ip = limiter.trust_cloudflare_ip();
+            let mut request = request;
+            let mut decision: Option<RateLimitDecision> = None;
+            for key in keys {
+                let value = match key {
+                    RateLimitKey::Ip => Some(client_ip(&request, trust_cloudflare_ip)),
+                    RateLimitKey::User => Some(
+                        user_id(&request)
+                            .unwrap_or_else(|| client_ip(&request, trust_cloudflare_ip)),
+                    ),
+                    RateLimitKey::ApiToken => {
+                        let (with_body, token) = api_token(request).await;
+                        request = with_body;
+                        Some(token.unwrap_or_else(|| client_ip(&request, trust_cloudflare_ip)))
+                    }
+                    RateLimitKey::Email => None,
+                };
+                let Some(value) = value else {
+                    continue;
+                };
+                if let Some(next) = limiter.check(policy, key, &value).await {
+                    decision = Some(match decision {
+                        Some(current) => current.merge(next),
+                        None => next,
+                    });
+                }
+            }
+            match decision {
+                Some(decision) if !decision.allowed => match limited {
+                    Some(limited) => {
+                        let mut response = limited();
+                        response.headers_mut().extend(decision.headers());
+                        Ok(response)
+                    }
+                    None => Ok(Error::RateLimited(decision).into_response()),
+                },
+                Some(decision) => {
+                    let mut response = inner.call(request).await?;
+                    response.headers_mut().extend(decision.headers());
+                    Ok(response)
+                }
+                None => inner.call(request).await,
+            }
+        })
+    }
+}
+
+/// Cloudflare's client address when it is trusted, otherwise the peer address.
+fn client_ip(request: &Request, trust_cloudflare_ip: bool) -> String {
+    trust_cloudflare_ip
+        .then(|| request.headers().get("cf-connecting-ip"))
+        .flatten()
+        .and_then(|ip| ip.to_str().ok())
+        .map(str::to_string)
+        .or_else(|| {
+            request
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(address)| address.ip().to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn user_id(request: &Request) -> Option<String> {
+    request
+        .extensions()
+        .get::<AuthSession>()
+        .and_then(|auth| auth.user.as_ref())
+        .map(|user| user.id.to_string())
+}
+
+/// Looks for the token in the bearer header, the query and finally a JSON body, which is
+/// buffered and put back for the handler.
+async fn api_token(request: Request) -> (Request, Option<String>) {
+    if let Some(token) = bearer_token(request.headers()) {
+        return (request, Some(token.to_string()));
+    }
+    if let Some(token) = Query::<ApiTokenField>::try_from_uri(request.uri())
+        .ok()
+        .and_then(|Query(field)| field.api_token)
+    {
+        return (request, Some(token.to_string()));
+    }
+    let small_body = request
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|length| length.to_str().ok()?.parse::<usize>().ok())
+        .is_some_and(|length| length <= MAX_TOKEN_BODY_BYTES);
+    if !small_body {
+        return (request, None);
+    }
+    let (parts, body) = request.into_parts();
+    match axum::body::to_bytes(body, MAX_TOKEN_BODY_BYTES).await {
+        Ok(bytes) => {
+            let token = serde_json::from_slice::<ApiTokenField>(&bytes)
+                .ok()
+                .and_then(|field| field.api_token)
+                .map(|token| token.to_string());
+            (Request::from_parts(parts, Body::from(bytes)), token)
+        }
+        Err(_) => (Request::from_parts(parts, Body::empty()), None),
+    }
+}
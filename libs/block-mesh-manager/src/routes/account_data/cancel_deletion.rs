@@ -0,0 +1,22 @@
+use crate::database::account_data::cancel_account_deletion::cancel_account_deletion;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use axum::Extension;
+use axum_login::AuthSession;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::StatusCode;
+use sqlx::PgPool;
+
+#[tracing::instrument(name = "cancel_account_deletion", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+) -> Result<StatusCode, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let mut transaction = create_txn(&pool).await?;
+    if !cancel_account_deletion(&mut transaction, &user.id).await? {
+        return Err(Error::BadRequest("No deletion is scheduled".to_string()));
+    }
+    commit_txn(transaction).await?;
+    Ok(StatusCode::OK)
+}
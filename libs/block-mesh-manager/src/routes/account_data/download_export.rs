@@ -0,0 +1,44 @@
+use crate::database::account_data::get_account_export::get_account_export;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use axum::extract::Query;
+use axum::response::IntoResponse;
+use axum::Extension;
+use axum_login::AuthSession;
+use blob_store::blob_store::get_blob_store;
+use block_mesh_common::interfaces::server_api::AccountExportDownloadQuery;
+use block_mesh_manager_database_domain::domain::account_export::AccountExportStatus;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use sqlx::PgPool;
+
+#[tracing::instrument(name = "download_account_export", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+    Query(query): Query<AccountExportDownloadQuery>,
+) -> Result<impl IntoResponse, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let mut transaction = create_txn(&pool).await?;
+    let export = get_account_export(&mut transaction, &user.id, &query.id).await?;
+    commit_txn(transaction).await?;
+    let blob_key = export
+        .filter(|export| export.status == AccountExportStatus::Ready.to_string())
+        .and_then(|export| export.blob_key)
+        .ok_or_else(|| Error::BadRequest("Export is not available".to_string()))?;
+    let archive = get_blob_store()
+        .await?
+        .get(&blob_key)
+        .await?
+        .ok_or_else(|| Error::BadRequest("Export is not available".to_string()))?;
+    Ok((
+        [
+            (CONTENT_TYPE, "application/zip".to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"blockmesh-export-{}.zip\"", query.id),
+            ),
+        ],
+        archive,
+    ))
+}
@@ -0,0 +1,52 @@
+use crate::database::account_data::get_account_deletion::get_account_deletion;
+use crate::database::account_data::get_account_exports::get_account_exports;
+use crate::database::account_data::get_or_create_telegram_link::get_or_create_telegram_link;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::{
+    AccountDataResponse, AccountDeletionInfo, AccountExportInfo,
+};
+use block_mesh_manager_database_domain::domain::account_deletion::AccountDeletionStatus;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::PgPool;
+use std::env;
+
+#[tracing::instrument(name = "account_data", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+) -> Result<Json<AccountDataResponse>, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let mut transaction = create_txn(&pool).await?;
+    let exports = get_account_exports(&mut transaction, &user.id, 10).await?;
+    let deletion = get_account_deletion(&mut transaction, &user.id).await?;
+    let telegram_link = match env::var("TG_PRIVACY_BOT_USERNAME") {
+        Ok(bot) if !bot.is_empty() => {
+            let link_code = get_or_create_telegram_link(&mut transaction, &user.id).await?;
+            Some(format!("https://t.me/{}?start={}", bot, link_code))
+        }
+        _ => None,
+    };
+    commit_txn(transaction).await?;
+    Ok(Json(AccountDataResponse {
+        exports: exports
+            .into_iter()
+            .map(|export| AccountExportInfo {
+                id: export.id,
+                status: export.status,
+                size_bytes: export.size_bytes,
+                created_at: export.created_at,
+                expires_at: export.expires_at,
+            })
+            .collect(),
+        deletion: deletion
+            .filter(|deletion| deletion.status == AccountDeletionStatus::Scheduled.to_string())
+            .map(|deletion| AccountDeletionInfo {
+                requested_at: deletion.requested_at,
+                scheduled_for: deletion.scheduled_for,
+            }),
+        telegram_link,
+    }))
+}
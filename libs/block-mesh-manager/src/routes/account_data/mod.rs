@@ -0,0 +1,5 @@
+pub mod cancel_deletion;
+pub mod download_export;
+pub mod get;
+pub mod request_deletion;
+pub mod request_export;
@@ -0,0 +1,53 @@
+use crate::database::account_data::schedule_account_deletion::schedule_account_deletion;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::routes::two_factor::guard::require_two_factor;
+use axum::{Extension, Json};
+use axum_login::tower_sessions::Session;
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::AccountDeletionRequest;
+use chrono::{Duration, Utc};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::StatusCode;
+use sqlx::PgPool;
+use std::env;
+
+#[tracing::instrument(name = "request_account_deletion", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+    session: Session,
+    Json(body): Json<AccountDeletionRequest>,
+) -> Result<StatusCode, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    if body.email.trim().to_ascii_lowercase() != user.email {
+        return Err(Error::BadRequest(
+            "Email does not match the account".to_string(),
+        ));
+    }
+    let grace_days = env::var("ACCOUNT_DELETION_GRACE_DAYS")
+        .unwrap_or("30".to_string())
+        .parse()
+        .unwrap_or(30);
+    let mut transaction = create_txn(&pool).await?;
+    if let Err(e) = require_two_factor(
+        &mut transaction,
+        &session,
+        &user.id,
+        &user.email,
+        body.totp_code.as_deref(),
+    )
+    .await
+    {
+        commit_txn(transaction).await?;
+        return Err(e);
+    }
+    schedule_account_deletion(
+        &mut transaction,
+        &user.id,
+        Utc::now() + Duration::days(grace_days),
+    )
+    .await?;
+    commit_txn(transaction).await?;
+    Ok(StatusCode::OK)
+}
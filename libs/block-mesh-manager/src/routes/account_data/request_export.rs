@@ -0,0 +1,43 @@
+use crate::database::account_data::create_account_export::create_account_export;
+use crate::database::account_data::get_account_exports::get_account_exports;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use axum::Extension;
+use axum_login::AuthSession;
+use block_mesh_manager_database_domain::domain::account_export::AccountExportStatus;
+use chrono::{Duration, Utc};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::StatusCode;
+use sqlx::PgPool;
+
+/// Building an archive reads every table, so users get one export per hour.
+const EXPORT_COOLDOWN_MINUTES: i64 = 60;
+
+#[tracing::instrument(name = "request_account_export", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+) -> Result<StatusCode, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let mut transaction = create_txn(&pool).await?;
+    if let Some(latest) = get_account_exports(&mut transaction, &user.id, 1)
+        .await?
+        .first()
+    {
+        if latest.status == AccountExportStatus::Pending.to_string()
+            || latest.status == AccountExportStatus::Processing.to_string()
+        {
+            return Err(Error::BadRequest(
+                "An export is already being prepared".to_string(),
+            ));
+        }
+        if latest.created_at > Utc::now() - Duration::minutes(EXPORT_COOLDOWN_MINUTES) {
+            return Err(Error::BadRequest(
+                "Exports can be requested once an hour".to_string(),
+            ));
+        }
+    }
+    create_account_export(&mut transaction, &user.id).await?;
+    commit_txn(transaction).await?;
+    Ok(StatusCode::OK)
+}
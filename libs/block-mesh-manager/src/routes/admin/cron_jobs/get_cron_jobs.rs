@@ -0,0 +1,39 @@
+use crate::database::cron_job::get_latest_cron_job_runs::get_latest_cron_job_runs;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::startup::application::AppState;
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_manager_database_domain::domain::cron_job::{CronJob, CronJobRun};
+use block_mesh_manager_database_domain::domain::get_cron_jobs::get_cron_jobs;
+use block_mesh_manager_database_domain::domain::get_user_opt_by_id::get_user_opt_by_id;
+use block_mesh_manager_database_domain::domain::user::UserRole;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Serialize)]
+pub struct CronJobsResponse {
+    pub jobs: Vec<CronJob>,
+    pub runs: Vec<CronJobRun>,
+}
+
+#[tracing::instrument(name = "get_cron_jobs", skip_all)]
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+) -> Result<Json<CronJobsResponse>, Error> {
+    let mut transaction = create_txn(&state.pool).await?;
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let user = get_user_opt_by_id(&mut transaction, &user.id)
+        .await?
+        .ok_or(Error::UserNotFound)?;
+    if !matches!(user.role, UserRole::Admin) {
+        return Err(Error::Unauthorized);
+    }
+    let jobs = get_cron_jobs(&mut transaction).await?;
+    let runs = get_latest_cron_job_runs(&mut transaction, 100).await?;
+    commit_txn(transaction).await?;
+    Ok(Json(CronJobsResponse { jobs, runs }))
+}
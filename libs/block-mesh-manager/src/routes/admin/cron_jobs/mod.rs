@@ -0,0 +1,2 @@
+pub mod get_cron_jobs;
+pub mod update_cron_job;
@@ -0,0 +1,44 @@
+use crate::database::cron_job::trigger_cron_job::trigger_cron_job;
+use crate::database::cron_job::update_cron_job_paused::update_cron_job_paused;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::startup::application::AppState;
+use anyhow::anyhow;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::{CronJobAction, CronJobActionRequest};
+use block_mesh_manager_database_domain::domain::get_user_opt_by_id::get_user_opt_by_id;
+use block_mesh_manager_database_domain::domain::user::UserRole;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::StatusCode;
+use std::sync::Arc;
+
+#[tracing::instrument(name = "update_cron_job", skip_all)]
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+    Json(body): Json<CronJobActionRequest>,
+) -> Result<impl IntoResponse, Error> {
+    let mut transaction = create_txn(&state.pool).await?;
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let user = get_user_opt_by_id(&mut transaction, &user.id)
+        .await?
+        .ok_or(Error::UserNotFound)?;
+    if !matches!(user.role, UserRole::Admin) {
+        return Err(Error::Unauthorized);
+    }
+    let rows_affected = match body.action {
+        CronJobAction::Trigger => trigger_cron_job(&mut transaction, &body.name).await?,
+        CronJobAction::Pause => update_cron_job_paused(&mut transaction, &body.name, true).await?,
+        CronJobAction::Resume => {
+            update_cron_job_paused(&mut transaction, &body.name, false).await?
+        }
+    };
+    if rows_affected == 0 {
+        return Err(Error::from(anyhow!("Cron job {} not found", body.name)));
+    }
+    commit_txn(transaction).await?;
+    Ok(StatusCode::OK.into_response())
+}
@@ -0,0 +1,66 @@
+use crate::database::invite_campaign::create_invite_campaign::create_invite_campaign;
+use crate::database::invite_campaign::get_invite_campaign_by_name::get_invite_campaign_by_name;
+use crate::domain::invite_campaign::InviteCampaign;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::startup::application::AppState;
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::CreateInviteCampaignRequest;
+use block_mesh_manager_database_domain::domain::get_user_opt_by_id::get_user_opt_by_id;
+use block_mesh_manager_database_domain::domain::user::UserRole;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use std::sync::Arc;
+
+fn validate(body: &CreateInviteCampaignRequest) -> Result<(), Error> {
+    if body.name.trim().is_empty() {
+        return Err(Error::BadRequest("Campaign name is required".to_string()));
+    }
+    if let (Some(starts_at), Some(ends_at)) = (body.starts_at, body.ends_at) {
+        if ends_at <= starts_at {
+            return Err(Error::BadRequest(
+                "Campaign must end after it starts".to_string(),
+            ));
+        }
+    }
+    if body.max_uses.is_some_and(|max_uses| max_uses <= 0) {
+        return Err(Error::BadRequest("max_uses must be positive".to_string()));
+    }
+    match (body.perk_multiplier, body.perk_days) {
+        (None, None) => Ok(()),
+        (Some(multiplier), Some(days)) if multiplier > 0.0 && days > 0 => Ok(()),
+        _ => Err(Error::BadRequest(
+            "perk_multiplier and perk_days must both be positive or both be empty".to_string(),
+        )),
+    }
+}
+
+#[tracing::instrument(name = "create_invite_campaign", skip_all)]
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+    Json(body): Json<CreateInviteCampaignRequest>,
+) -> Result<Json<InviteCampaign>, Error> {
+    let mut transaction = create_txn(&state.pool).await?;
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let user = get_user_opt_by_id(&mut transaction, &user.id)
+        .await?
+        .ok_or(Error::UserNotFound)?;
+    if !matches!(user.role, UserRole::Admin) {
+        return Err(Error::Unauthorized);
+    }
+    validate(&body)?;
+    if get_invite_campaign_by_name(&mut transaction, &body.name)
+        .await?
+        .is_some()
+    {
+        return Err(Error::BadRequest(format!(
+            "Campaign {} already exists",
+            body.name
+        )));
+    }
+    let campaign = create_invite_campaign(&mut transaction, &user.id, &body).await?;
+    commit_txn(transaction).await?;
+    Ok(Json(campaign))
+}
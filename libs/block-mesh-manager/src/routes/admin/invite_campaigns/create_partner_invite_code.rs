@@ -0,0 +1,66 @@
+use crate::database::invite_campaign::get_invite_campaign_by_name::get_invite_campaign_by_name;
+use crate::database::invite_code::create_campaign_invite_code::create_campaign_invite_code;
+use crate::database::invite_code::get_user_opt_by_invited_code::get_user_opt_by_invited_code;
+use crate::database::user::get_user_by_email::get_user_opt_by_email;
+use crate::domain::invite_code::is_valid_vanity_code;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::startup::application::AppState;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::CreatePartnerInviteCodeRequest;
+use block_mesh_manager_database_domain::domain::get_user_opt_by_id::get_user_opt_by_id;
+use block_mesh_manager_database_domain::domain::user::UserRole;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::StatusCode;
+use std::sync::Arc;
+
+/// Adds a code to a campaign, owned by the partner's account so signups count as their referrals.
+/// The partner's personal code stays the one they share, the campaign code is never their latest.
+#[tracing::instrument(name = "create_partner_invite_code", skip_all)]
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+    Json(body): Json<CreatePartnerInviteCodeRequest>,
+) -> Result<impl IntoResponse, Error> {
+    let mut transaction = create_txn(&state.pool).await?;
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let user = get_user_opt_by_id(&mut transaction, &user.id)
+        .await?
+        .ok_or(Error::UserNotFound)?;
+    if !matches!(user.role, UserRole::Admin) {
+        return Err(Error::Unauthorized);
+    }
+    if !is_valid_vanity_code(&body.invite_code) {
+        return Err(Error::BadRequest(
+            "Invite codes are 3 to 32 letters and digits".to_string(),
+        ));
+    }
+    let campaign = get_invite_campaign_by_name(&mut transaction, &body.campaign)
+        .await?
+        .ok_or(Error::BadRequest(format!(
+            "Campaign {} not found",
+            body.campaign
+        )))?;
+    let owner = get_user_opt_by_email(&mut transaction, &body.owner_email.to_ascii_lowercase())
+        .await?
+        .ok_or(Error::BadRequest(format!(
+            "User {} not found",
+            body.owner_email
+        )))?;
+    if get_user_opt_by_invited_code(&mut transaction, body.invite_code.clone())
+        .await?
+        .is_some()
+    {
+        return Err(Error::BadRequest(format!(
+            "Invite code {} is taken",
+            body.invite_code
+        )));
+    }
+    create_campaign_invite_code(&mut transaction, &owner.id, &body.invite_code, &campaign.id)
+        .await?;
+    commit_txn(transaction).await?;
+    Ok(StatusCode::OK.into_response())
+}
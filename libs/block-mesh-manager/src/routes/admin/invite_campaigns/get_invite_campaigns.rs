@@ -0,0 +1,30 @@
+use crate::database::invite_campaign::get_invite_campaign_stats::get_invite_campaign_stats;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::startup::application::AppState;
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::InviteCampaignStats;
+use block_mesh_manager_database_domain::domain::get_user_opt_by_id::get_user_opt_by_id;
+use block_mesh_manager_database_domain::domain::user::UserRole;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use std::sync::Arc;
+
+#[tracing::instrument(name = "get_invite_campaigns", skip_all)]
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+) -> Result<Json<Vec<InviteCampaignStats>>, Error> {
+    let mut transaction = create_txn(&state.pool).await?;
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let user = get_user_opt_by_id(&mut transaction, &user.id)
+        .await?
+        .ok_or(Error::UserNotFound)?;
+    if !matches!(user.role, UserRole::Admin) {
+        return Err(Error::Unauthorized);
+    }
+    let campaigns = get_invite_campaign_stats(&mut transaction).await?;
+    commit_txn(transaction).await?;
+    Ok(Json(campaigns))
+}
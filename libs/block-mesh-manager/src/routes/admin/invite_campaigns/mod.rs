@@ -0,0 +1,3 @@
+pub mod create_invite_campaign;
+pub mod create_partner_invite_code;
+pub mod get_invite_campaigns;
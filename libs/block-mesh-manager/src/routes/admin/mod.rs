@@ -1 +1,5 @@
+pub mod cron_jobs;
+pub mod invite_campaigns;
+pub mod perk_definitions;
+pub mod rebuild_aggregates;
 pub mod reports_queue;
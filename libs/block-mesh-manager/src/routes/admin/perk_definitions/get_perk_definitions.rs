@@ -0,0 +1,30 @@
+use crate::database::perks::get_perk_definitions::get_perk_definitions;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::startup::application::AppState;
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_manager_database_domain::domain::get_user_opt_by_id::get_user_opt_by_id;
+use block_mesh_manager_database_domain::domain::perk_definition::PerkDefinition;
+use block_mesh_manager_database_domain::domain::user::UserRole;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use std::sync::Arc;
+
+#[tracing::instrument(name = "get_perk_definitions", skip_all)]
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+) -> Result<Json<Vec<PerkDefinition>>, Error> {
+    let mut transaction = create_txn(&state.pool).await?;
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let user = get_user_opt_by_id(&mut transaction, &user.id)
+        .await?
+        .ok_or(Error::UserNotFound)?;
+    if !matches!(user.role, UserRole::Admin) {
+        return Err(Error::Unauthorized);
+    }
+    let definitions = get_perk_definitions(&mut transaction).await?;
+    commit_txn(transaction).await?;
+    Ok(Json(definitions))
+}
@@ -0,0 +1,2 @@
+pub mod get_perk_definitions;
+pub mod upsert_perk_definition;
@@ -0,0 +1,70 @@
+use crate::database::perks::upsert_perk_definition::upsert_perk_definition;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::startup::application::AppState;
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::PerkDefinitionRequest;
+use block_mesh_manager_database_domain::domain::get_user_opt_by_id::get_user_opt_by_id;
+use block_mesh_manager_database_domain::domain::perk_definition::{PerkCondition, PerkDefinition};
+use block_mesh_manager_database_domain::domain::user::UserRole;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use std::sync::Arc;
+
+fn validate(body: &PerkDefinitionRequest) -> Result<(), Error> {
+    if body.name.trim().is_empty() || body.name == "invalid" {
+        return Err(Error::BadRequest("Perk name is required".to_string()));
+    }
+    if body.multiplier <= 0.0 || body.max_multiplier.is_some_and(|cap| cap <= 0.0) {
+        return Err(Error::BadRequest(
+            "Multipliers must be positive".to_string(),
+        ));
+    }
+    if !matches!(body.stacking.as_str(), "Multiply" | "Max") {
+        return Err(Error::BadRequest(
+            "stacking must be Multiply or Max".to_string(),
+        ));
+    }
+    if let (Some(valid_from), Some(valid_until)) = (body.valid_from, body.valid_until) {
+        if valid_until <= valid_from {
+            return Err(Error::BadRequest(
+                "valid_until must be after valid_from".to_string(),
+            ));
+        }
+    }
+    if body.duration_days.is_some_and(|days| days <= 0) {
+        return Err(Error::BadRequest(
+            "duration_days must be positive".to_string(),
+        ));
+    }
+    match (body.condition.as_deref(), body.condition_threshold) {
+        (None, None) => Ok(()),
+        (Some(condition), Some(threshold)) if threshold > 0 => PerkCondition::try_from(condition)
+            .map(|_| ())
+            .map_err(|e| Error::BadRequest(e.to_string())),
+        _ => Err(Error::BadRequest(
+            "condition needs a positive condition_threshold".to_string(),
+        )),
+    }
+}
+
+#[tracing::instrument(name = "upsert_perk_definition", skip_all)]
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+    Json(body): Json<PerkDefinitionRequest>,
+) -> Result<Json<PerkDefinition>, Error> {
+    let mut transaction = create_txn(&state.pool).await?;
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let user = get_user_opt_by_id(&mut transaction, &user.id)
+        .await?
+        .ok_or(Error::UserNotFound)?;
+    if !matches!(user.role, UserRole::Admin) {
+        return Err(Error::Unauthorized);
+    }
+    validate(&body)?;
+    let definition = upsert_perk_definition(&mut transaction, &body).await?;
+    commit_txn(transaction).await?;
+    Ok(Json(definition))
+}
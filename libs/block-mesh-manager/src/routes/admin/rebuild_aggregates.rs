@@ -0,0 +1,39 @@
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::startup::application::AppState;
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::{
+    RebuildAggregatesRequest, RebuildAggregatesResponse,
+};
+use block_mesh_manager_database_domain::domain::aggregate::AggregateName;
+use block_mesh_manager_database_domain::domain::get_user_opt_by_id::get_user_opt_by_id;
+use block_mesh_manager_database_domain::domain::rebuild_aggregates::rebuild_aggregates;
+use block_mesh_manager_database_domain::domain::user::UserRole;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use std::sync::Arc;
+
+#[tracing::instrument(name = "rebuild_aggregates", skip_all)]
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+    Json(body): Json<RebuildAggregatesRequest>,
+) -> Result<Json<RebuildAggregatesResponse>, Error> {
+    let mut transaction = create_txn(&state.pool).await?;
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let user = get_user_opt_by_id(&mut transaction, &user.id)
+        .await?
+        .ok_or(Error::UserNotFound)?;
+    if !matches!(user.role, UserRole::Admin) {
+        return Err(Error::Unauthorized);
+    }
+    let rows_affected = rebuild_aggregates(
+        &mut transaction,
+        body.user_id,
+        body.name.map(AggregateName::from),
+    )
+    .await?;
+    commit_txn(transaction).await?;
+    Ok(Json(RebuildAggregatesResponse { rows_affected }))
+}
@@ -38,7 +38,7 @@ pub async fn handler(
             .context("Could not find SERVER_UUID env var")?
             .as_str(),
     )
-    .context("SERVER_UUID evn var contains invalid UUID value")?;
+    .context("SERVER_UUID env var contains invalid UUID value")?;
     let agg = get_or_create_aggregate_by_user_and_name(
         &mut transaction,
         AggregateName::CronReports,
@@ -52,6 +52,7 @@ pub async fn handler(
     let response = GetTokenResponse {
         api_token: Some(*user_and_api_token.token.as_ref()),
         message: None,
+        user_id: Some(user_and_api_token.user_id),
     };
     commit_txn(transaction).await?;
     check_token_map.insert(
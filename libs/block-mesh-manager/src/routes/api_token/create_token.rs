@@ -0,0 +1,67 @@
+use crate::database::api_token::count_active_api_tokens::count_active_api_tokens;
+use crate::database::api_token::create_named_api_token::create_named_api_token;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::{
+    ApiTokenInfo, ApiTokenSecretResponse, CreateApiTokenRequest,
+};
+use chrono::{Duration, Utc};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::PgPool;
+
+const MAX_TOKEN_NAME_LEN: usize = 64;
+const MAX_TOKEN_EXPIRY_DAYS: i64 = 3650;
+const MAX_ACTIVE_TOKENS: i64 = 20;
+
+#[tracing::instrument(name = "create_token", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+    Json(body): Json<CreateApiTokenRequest>,
+) -> Result<Json<ApiTokenSecretResponse>, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let name = body.name.trim();
+    if name.is_empty() || name.len() > MAX_TOKEN_NAME_LEN {
+        return Err(Error::BadRequest(format!(
+            "Token name must be 1 to {} characters",
+            MAX_TOKEN_NAME_LEN
+        )));
+    }
+    let mut scopes = body.scopes;
+    scopes.sort_by_key(|scope| scope.as_str());
+    scopes.dedup();
+    if scopes.is_empty() {
+        return Err(Error::BadRequest(
+            "At least one scope is required".to_string(),
+        ));
+    }
+    let expires_at = match body.expires_in_days {
+        None => None,
+        Some(days) if (1..=MAX_TOKEN_EXPIRY_DAYS).contains(&days) => {
+            Some(Utc::now() + Duration::days(days))
+        }
+        Some(_) => {
+            return Err(Error::BadRequest(format!(
+                "expires_in_days must be between 1 and {}",
+                MAX_TOKEN_EXPIRY_DAYS
+            )))
+        }
+    };
+    let mut transaction = create_txn(&pool).await?;
+    if count_active_api_tokens(&mut transaction, &user.id).await? >= MAX_ACTIVE_TOKENS {
+        commit_txn(transaction).await?;
+        return Err(Error::BadRequest(format!(
+            "At most {} active tokens are allowed, revoke one first",
+            MAX_ACTIVE_TOKENS
+        )));
+    }
+    let (row, token) =
+        create_named_api_token(&mut transaction, &user.id, name, &scopes, expires_at).await?;
+    commit_txn(transaction).await?;
+    Ok(Json(ApiTokenSecretResponse {
+        info: ApiTokenInfo::from(row),
+        token,
+    }))
+}
@@ -1,4 +1,5 @@
 use crate::errors::error::Error;
+use crate::routes::two_factor::guard::{check_two_factor_code, is_two_factor_enabled};
 use crate::startup::application::AppState;
 use crate::utils::verify_cache::verify_with_cache;
 use axum::extract::State;
@@ -12,7 +13,7 @@ use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
 use sqlx::PgPool;
 use std::sync::Arc;
 
-#[tracing::instrument(name = "get_token", skip(pool, state))]
+#[tracing::instrument(name = "get_token", skip_all)]
 pub async fn handler(
     Extension(pool): Extension<PgPool>,
     State(state): State<Arc<AppState>>,
@@ -50,14 +51,37 @@ pub async fn handler(
         get_token_map.insert(key, GetTokenResponseEnum::PasswordMismatch);
         return Err(Error::PasswordMismatch);
     }
+    let user_id = user_and_api_token.user_id;
+    let two_factor = is_two_factor_enabled(&mut transaction, &user_id).await?;
+    if two_factor {
+        let Some(code) = body
+            .totp_code
+            .as_deref()
+            .filter(|code| !code.trim().is_empty())
+        else {
+            commit_txn(transaction).await?;
+            return Err(Error::TwoFactorRequired);
+        };
+        let valid = check_two_factor_code(&mut transaction, &user_id, &email, code).await;
+        // Committed either way, a wrong code is counted towards the lockout.
+        commit_txn(transaction).await?;
+        if !valid? {
+            return Err(Error::TwoFactorRequired);
+        }
+    } else {
+        commit_txn(transaction).await?;
+    }
     let response = GetTokenResponse {
         api_token: Some(*user_and_api_token.token.as_ref()),
         message: None,
+        user_id: Some(user_id),
     };
-    commit_txn(transaction).await?;
-    get_token_map.insert(
-        key,
-        GetTokenResponseEnum::GetTokenResponse(response.clone()),
-    );
+    // A code is good once, so accounts with two factor are never answered from the cache.
+    if !two_factor {
+        get_token_map.insert(
+            key,
+            GetTokenResponseEnum::GetTokenResponse(response.clone()),
+        );
+    }
     Ok(Json(response))
 }
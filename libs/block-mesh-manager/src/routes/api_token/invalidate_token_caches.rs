@@ -0,0 +1,16 @@
+use crate::startup::application::AppState;
+use block_mesh_common::interfaces::db_messages::InvalidateApiCache;
+use block_mesh_manager_database_domain::domain::notify_api::notify_api;
+
+/// Tokens are cached by email in the api and ws services, drop them after a rotate or revoke.
+pub(crate) async fn invalidate_token_caches(state: &AppState, email: &str) {
+    state.get_token_map.retain(|key, _| key.0 != email);
+    state.check_token_map.retain(|key, _| key.0 != email);
+    let _ = notify_api(
+        &state.channel_pool,
+        InvalidateApiCache {
+            email: email.to_string(),
+        },
+    )
+    .await;
+}
@@ -0,0 +1,22 @@
+use crate::database::api_token::get_api_tokens_by_user_id::get_api_tokens_by_user_id;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::startup::application::AppState;
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::ApiTokenInfo;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use std::sync::Arc;
+
+#[tracing::instrument(name = "list_tokens", skip_all)]
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+) -> Result<Json<Vec<ApiTokenInfo>>, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let mut follower_transaction = create_txn(&state.follower_pool).await?;
+    let tokens = get_api_tokens_by_user_id(&mut follower_transaction, &user.id).await?;
+    commit_txn(follower_transaction).await?;
+    Ok(Json(tokens.into_iter().map(ApiTokenInfo::from).collect()))
+}
@@ -1,4 +1,9 @@
 pub mod check_token;
+pub mod create_token;
 pub mod get_email_via_token;
 pub mod get_stats;
 pub mod get_token;
+pub mod invalidate_token_caches;
+pub mod list_tokens;
+pub mod revoke_token;
+pub mod rotate_token;
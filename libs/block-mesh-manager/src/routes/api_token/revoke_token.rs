@@ -0,0 +1,33 @@
+use crate::database::api_token::revoke_api_token::revoke_api_token;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::routes::api_token::invalidate_token_caches::invalidate_token_caches;
+use crate::startup::application::AppState;
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::ApiTokenIdRequest;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::StatusCode;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+#[tracing::instrument(name = "revoke_token", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+    Json(body): Json<ApiTokenIdRequest>,
+) -> Result<StatusCode, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let mut transaction = create_txn(&pool).await?;
+    let revoked = revoke_api_token(&mut transaction, &user.id, &body.id).await?;
+    commit_txn(transaction).await?;
+    if !revoked {
+        return Err(Error::BadRequest(
+            "Token not found, the primary token can only be rotated".to_string(),
+        ));
+    }
+    invalidate_token_caches(&state, &user.email).await;
+    Ok(StatusCode::OK)
+}
@@ -1,15 +1,21 @@
 use crate::errors::error::Error;
-use crate::middlewares::rate_limit::filter_request;
 use crate::startup::application::AppState;
 use crate::utils::cache_envar::get_envar;
 use anyhow::Context;
 use axum::extract::State;
+use axum::response::{IntoResponse, Response};
 use axum::Json;
 use block_mesh_common::interfaces::server_api::{ReportBandwidthRequest, ReportBandwidthResponse};
+use block_mesh_manager_database_domain::domain::authenticate_token::Credentials;
 use block_mesh_manager_database_domain::domain::submit_bandwidth_content::submit_bandwidth_content;
 use http::HeaderMap;
 use std::sync::Arc;
 
+/// Nodes read the limit from the body and treat any other status as a failed report.
+pub fn rate_limited() -> Response {
+    Json(ReportBandwidthResponse { status_code: 429 }).into_response()
+}
+
 #[tracing::instrument(name = "submit_bandwidth", skip_all)]
 pub async fn handler(
     headers: HeaderMap,
@@ -27,15 +33,15 @@ pub async fn handler(
         "127.0.0.1"
     }
     .to_string();
-    let mut redis = state.redis.clone();
-    if state.submit_bandwidth_limit {
-        let filter =
-            filter_request(&mut redis, &body.api_token, &header_ip, "submit_bandwidth").await;
-        if filter.is_err() || !filter? {
-            return Ok(Json(ReportBandwidthResponse { status_code: 429 }));
-        }
-    }
-    submit_bandwidth_content(&state.pool, &state.follower_pool, &state.channel_pool, body)
-        .await
-        .map_err(Error::from)
+    let credentials = Credentials::new(&headers, &body.email, &body.api_token);
+    submit_bandwidth_content(
+        &state.pool,
+        &state.follower_pool,
+        &state.channel_pool,
+        Some(&header_ip),
+        &credentials,
+        body,
+    )
+    .await
+    .map_err(Error::from)
 }
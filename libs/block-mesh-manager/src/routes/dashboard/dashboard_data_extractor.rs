@@ -3,6 +3,7 @@ use chrono::Utc;
 use num_traits::abs;
 use sqlx::{PgPool, Postgres, Transaction};
 use std::cmp::max;
+use std::collections::HashMap;
 use std::sync::Arc;
 #[allow(unused_imports)]
 use tracing::Level;
@@ -16,7 +17,10 @@ use crate::database::daily_stat::get_daily_stats_by_user_id::get_daily_stats_by_
 use crate::database::invite_code::get_number_of_users_invited::get_number_of_users_invited;
 use crate::database::invite_code::get_user_latest_invite_code::get_user_latest_invite_code;
 use crate::database::invite_code::get_user_referrals::get_user_referrals;
+use crate::database::perks::get_daily_perk_history::get_daily_perk_history;
+use crate::database::perks::get_user_perk_effects::get_user_perk_effects;
 use crate::database::perks::get_user_perks::get_user_perks;
+use crate::database::referral_earning::get_daily_referral_earnings::get_daily_referral_earnings;
 use crate::database::users_ip::get_user_ips::get_user_ips;
 use crate::errors::error::Error;
 use crate::startup::application::AppState;
@@ -28,6 +32,7 @@ use block_mesh_manager_database_domain::domain::aggregate::AggregateName::{
 };
 use block_mesh_manager_database_domain::domain::bulk_get_or_create_aggregate_by_user_and_name::bulk_get_or_create_aggregate_by_user_and_name;
 use block_mesh_manager_database_domain::domain::create_daily_stat::get_or_create_daily_stat;
+use block_mesh_manager_database_domain::domain::points::combine_perks;
 use block_mesh_manager_database_domain::domain::user::UserAndApiToken;
 use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
 use regex::Regex;
@@ -51,6 +56,12 @@ pub async fn dashboard_data_extractor(
         .await
         .map_err(Error::from)?;
     let perks = get_user_perks(follower_transaction, &user.user_id).await?;
+    let perk_effects = get_user_perk_effects(follower_transaction, &user.user_id).await?;
+    let perk_history: HashMap<_, _> = get_daily_perk_history(follower_transaction, &user.user_id)
+        .await?
+        .into_iter()
+        .map(|i| (i.day, i))
+        .collect();
     let calls_to_action = get_user_call_to_action(follower_transaction, &user.user_id).await?;
     let number_of_users_invited = get_number_of_users_invited(follower_transaction, &user.user_id)
         .await
@@ -60,15 +71,27 @@ pub async fn dashboard_data_extractor(
             .await?
             .into_iter()
             .map(|i| {
-                let points = calc_points_daily(i.uptime, i.tasks_count, &perks);
+                let (perk_multiplier, applied_perks) = match perk_history.get(&i.day) {
+                    Some(history) => (history.perk_multiplier, history.perks.clone()),
+                    None => combine_perks(&perk_effects, i.day),
+                };
+                let points = calc_points_daily(i.uptime, i.tasks_count, perk_multiplier);
                 DailyStatForDashboard {
                     tasks_count: i.tasks_count,
                     uptime: i.uptime,
                     points,
                     day: i.day,
+                    perk_multiplier,
+                    perks: applied_perks,
                 }
             })
             .collect();
+    let referral_earnings =
+        get_daily_referral_earnings(follower_transaction, &user.user_id).await?;
+    let referral_points = referral_earnings
+        .iter()
+        .map(|i| i.level_1_points + i.level_2_points)
+        .sum::<f64>();
 
     let mut write_transaction = create_txn(write_pool).await?;
     let _ = get_or_create_daily_stat(&mut write_transaction, &user.user_id, None).await?;
@@ -118,10 +141,17 @@ pub async fn dashboard_data_extractor(
     );
     let one_time_bonus_points =
         calc_one_time_bonus_points(overall_uptime as f64, overall_task_count, &perks) as u64;
+    let (perk_multiplier, _) = combine_perks(&perk_effects, now.date_naive());
     let points = max(
-        calc_total_points(overall_uptime as f64, overall_task_count, &perks) as u64,
+        calc_total_points(
+            overall_uptime as f64,
+            overall_task_count,
+            perk_multiplier,
+            &perks,
+        ) as u64,
         one_time_bonus_points + daily_stats.iter().map(|i| i.points).sum::<f64>() as u64,
-    ) as f64;
+    ) as f64
+        + referral_points;
     commit_txn(write_transaction).await?;
     Ok(DashboardResponse {
         wallet_address: user.wallet_address,
@@ -158,6 +188,8 @@ pub async fn dashboard_data_extractor(
         invite_code: user_invite_code.invite_code,
         connected,
         daily_stats: daily_stats.iter().take(10).rev().cloned().collect(),
+        referral_points,
+        referral_earnings: referral_earnings.into_iter().take(10).rev().collect(),
         perks: perks
             .into_iter()
             .map(|i| PerkUI {
@@ -165,6 +197,7 @@ pub async fn dashboard_data_extractor(
                 name: i.name.to_string(),
                 multiplier: i.multiplier,
                 one_time_bonus: i.one_time_bonus,
+                expires_at: i.expires_at,
             })
             .collect(),
     })
@@ -32,6 +32,7 @@ pub async fn handler(
             user_id: user.id,
             value: serde_json::Value::from(1),
             name: AggregateName::Uptime.to_string(),
+            idempotency_key: None,
         })],
     )
     .await;
@@ -0,0 +1,50 @@
+use crate::database::api_token::create_named_api_token::create_named_api_token;
+use crate::database::device_authorization::approve_device_authorization::approve_device_authorization;
+use crate::database::device_authorization::deny_device_authorization::deny_device_authorization;
+use crate::database::device_authorization::get_pending_device_authorization::get_pending_device_authorization;
+use crate::domain::device_authorization::normalize_user_code;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::{DeviceApprovalRequest, TokenScope};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::StatusCode;
+use sqlx::PgPool;
+use std::str::FromStr;
+
+/// Issues a named token for the device on approval, the device collects it on its next poll.
+#[tracing::instrument(name = "approve_device", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+    Json(body): Json<DeviceApprovalRequest>,
+) -> Result<StatusCode, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let mut transaction = create_txn(&pool).await?;
+    let authorization =
+        get_pending_device_authorization(&mut transaction, &normalize_user_code(&body.user_code))
+            .await?
+            .ok_or_else(|| Error::BadRequest("Code not found or expired".to_string()))?;
+    if body.approve {
+        let scopes: Vec<TokenScope> = authorization
+            .scopes
+            .iter()
+            .filter_map(|scope| TokenScope::from_str(scope).ok())
+            .collect();
+        let (token, _) = create_named_api_token(
+            &mut transaction,
+            &user.id,
+            &authorization.device_name,
+            &scopes,
+            None,
+        )
+        .await?;
+        approve_device_authorization(&mut transaction, &authorization.id, &user.id, &token.id)
+            .await?;
+    } else {
+        deny_device_authorization(&mut transaction, &authorization.id, &user.id).await?;
+    }
+    commit_txn(transaction).await?;
+    Ok(StatusCode::OK)
+}
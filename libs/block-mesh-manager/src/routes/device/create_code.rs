@@ -0,0 +1,54 @@
+use crate::database::device_authorization::create_device_authorization::create_device_authorization;
+use crate::domain::device_authorization::generate_user_code;
+use crate::errors::error::Error;
+use crate::routes::device::{DEVICE_CODE_EXPIRY_SECS, DEVICE_POLL_INTERVAL_SECS};
+use crate::startup::application::{AppState, ApplicationBaseUrl};
+use axum::extract::State;
+use axum::{Extension, Json};
+use block_mesh_common::interfaces::server_api::{
+    DeviceCodeRequest, DeviceCodeResponse, TokenScope,
+};
+use block_mesh_common::routes_enum::RoutesEnum;
+use chrono::{Duration, Utc};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Devices get a token for reporting and tasks only, never one that reads the dashboard.
+const DEVICE_SCOPES: [TokenScope; 2] = [TokenScope::NodeReporting, TokenScope::TaskSubmission];
+
+#[tracing::instrument(name = "create_device_code", skip_all)]
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Extension(base_url): Extension<ApplicationBaseUrl>,
+    Json(body): Json<DeviceCodeRequest>,
+) -> Result<Json<DeviceCodeResponse>, Error> {
+    let device_name = body.device_name.trim();
+    if device_name.is_empty() || device_name.len() > 64 {
+        return Err(Error::BadRequest(
+            "Device name must be 1 to 64 characters".to_string(),
+        ));
+    }
+    let device_code = Uuid::new_v4();
+    let user_code = generate_user_code();
+    let mut transaction = create_txn(&state.pool).await?;
+    create_device_authorization(
+        &mut transaction,
+        &device_code,
+        &user_code,
+        device_name,
+        &DEVICE_SCOPES,
+        Utc::now() + Duration::seconds(DEVICE_CODE_EXPIRY_SECS),
+    )
+    .await?;
+    commit_txn(transaction).await?;
+    let verification_uri = format!("{}/ui{}", base_url.as_str(), RoutesEnum::Static_Auth_Device);
+    Ok(Json(DeviceCodeResponse {
+        device_code,
+        verification_uri_complete: format!("{}?code={}", verification_uri, user_code),
+        verification_uri,
+        user_code,
+        expires_in: DEVICE_CODE_EXPIRY_SECS,
+        interval: DEVICE_POLL_INTERVAL_SECS,
+    }))
+}
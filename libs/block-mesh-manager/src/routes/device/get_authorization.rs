@@ -0,0 +1,44 @@
+use crate::database::device_authorization::get_pending_device_authorization::get_pending_device_authorization;
+use crate::domain::device_authorization::normalize_user_code;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::startup::application::AppState;
+use axum::extract::{Query, State};
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::DeviceAuthorizationInfo;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use serde::Deserialize;
+use std::str::FromStr;
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize)]
+pub struct DeviceAuthorizationParams {
+    pub code: String,
+}
+
+#[tracing::instrument(name = "get_device_authorization", skip_all)]
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+    Query(params): Query<DeviceAuthorizationParams>,
+) -> Result<Json<DeviceAuthorizationInfo>, Error> {
+    auth.user.ok_or(Error::UserNotFound)?;
+    let mut transaction = create_txn(&state.pool).await?;
+    let authorization =
+        get_pending_device_authorization(&mut transaction, &normalize_user_code(&params.code))
+            .await?;
+    commit_txn(transaction).await?;
+    let authorization =
+        authorization.ok_or_else(|| Error::BadRequest("Code not found or expired".to_string()))?;
+    Ok(Json(DeviceAuthorizationInfo {
+        user_code: authorization.user_code,
+        device_name: authorization.device_name,
+        scopes: authorization
+            .scopes
+            .iter()
+            .filter_map(|scope| FromStr::from_str(scope).ok())
+            .collect(),
+        expires_at: authorization.expires_at,
+    }))
+}
@@ -0,0 +1,10 @@
+pub mod approve;
+pub mod create_code;
+pub mod get_authorization;
+pub mod poll_token;
+
+/// How long a code can wait for approval, and how often a device may poll for it.
+pub const DEVICE_CODE_EXPIRY_SECS: i64 = 600;
+pub const DEVICE_POLL_INTERVAL_SECS: u64 = 5;
+/// A device that lost the response to its token poll may repeat it for this long.
+pub const DEVICE_TOKEN_REPOLL_SECS: i64 = 60;
@@ -0,0 +1,174 @@
+use crate::database::device_authorization::get_device_authorization_by_device_code::{
+    get_device_authorization_by_device_code, DeviceAuthorization,
+};
+use crate::database::device_authorization::update_device_authorization_status::update_device_authorization_status;
+use crate::domain::device_authorization::DeviceAuthorizationStatus;
+use crate::errors::error::Error;
+use crate::routes::device::{DEVICE_POLL_INTERVAL_SECS, DEVICE_TOKEN_REPOLL_SECS};
+use crate::startup::application::AppState;
+use axum::extract::State;
+use axum::Json;
+use block_mesh_common::interfaces::server_api::{
+    DeviceTokenError, DeviceTokenRequest, DeviceTokenResponse,
+};
+use chrono::{DateTime, Duration, Utc};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use std::sync::Arc;
+
+#[tracing::instrument(name = "poll_device_token", skip_all)]
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<DeviceTokenRequest>,
+) -> Result<Json<DeviceTokenResponse>, Error> {
+    let mut transaction = create_txn(&state.pool).await?;
+    let Some(authorization) =
+        get_device_authorization_by_device_code(&mut transaction, &body.device_code).await?
+    else {
+        commit_txn(transaction).await?;
+        return Ok(Json(device_token_error(DeviceTokenError::ExpiredToken)));
+    };
+    let id = authorization.id;
+    let (status, response) = next_poll(authorization, Utc::now());
+    update_device_authorization_status(&mut transaction, &id, status).await?;
+    commit_txn(transaction).await?;
+    Ok(Json(response))
+}
+
+/// `Consumed` still answers with the token for a short while, so a device whose response got
+/// lost can poll again instead of starting over.
+fn next_poll(
+    authorization: DeviceAuthorization,
+    now: DateTime<Utc>,
+) -> (DeviceAuthorizationStatus, DeviceTokenResponse) {
+    let polled_too_soon = authorization.last_polled_at.is_some_and(|last_polled_at| {
+        now - last_polled_at < Duration::seconds(DEVICE_POLL_INTERVAL_SECS as i64)
+    });
+    let repoll = authorization
+        .consumed_at
+        .is_some_and(|consumed_at| now - consumed_at < Duration::seconds(DEVICE_TOKEN_REPOLL_SECS));
+    match authorization.status {
+        DeviceAuthorizationStatus::Approved => (
+            DeviceAuthorizationStatus::Consumed,
+            device_token(authorization),
+        ),
+        DeviceAuthorizationStatus::Consumed if repoll => (
+            DeviceAuthorizationStatus::Consumed,
+            device_token(authorization),
+        ),
+        DeviceAuthorizationStatus::Pending if authorization.expires_at <= now => (
+            DeviceAuthorizationStatus::Pending,
+            device_token_error(DeviceTokenError::ExpiredToken),
+        ),
+        DeviceAuthorizationStatus::Pending if polled_too_soon => (
+            DeviceAuthorizationStatus::Pending,
+            device_token_error(DeviceTokenError::SlowDown),
+        ),
+        DeviceAuthorizationStatus::Pending => (
+            DeviceAuthorizationStatus::Pending,
+            device_token_error(DeviceTokenError::AuthorizationPending),
+        ),
+        DeviceAuthorizationStatus::Denied => (
+            DeviceAuthorizationStatus::Denied,
+            device_token_error(DeviceTokenError::AccessDenied),
+        ),
+        DeviceAuthorizationStatus::Consumed => (
+            DeviceAuthorizationStatus::Consumed,
+            device_token_error(DeviceTokenError::ExpiredToken),
+        ),
+    }
+}
+
+fn device_token(authorization: DeviceAuthorization) -> DeviceTokenResponse {
+    DeviceTokenResponse {
+        email: authorization.email,
+        api_token: authorization.token.map(|token| *token.as_ref()),
+        error: None,
+    }
+}
+
+fn device_token_error(error: DeviceTokenError) -> DeviceTokenResponse {
+    DeviceTokenResponse {
+        email: None,
+        api_token: None,
+        error: Some(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn authorization(status: DeviceAuthorizationStatus, now: DateTime<Utc>) -> DeviceAuthorization {
+        DeviceAuthorization {
+            id: Uuid::new_v4(),
+            status,
+            expires_at: now + Duration::minutes(10),
+            last_polled_at: None,
+            consumed_at: None,
+            email: Some("test@blockmesh.xyz".to_string()),
+            token: Some(Uuid::new_v4().into()),
+        }
+    }
+
+    fn error(authorization: DeviceAuthorization, now: DateTime<Utc>) -> Option<DeviceTokenError> {
+        next_poll(authorization, now).1.error
+    }
+
+    #[test]
+    fn pending_asks_the_device_to_wait_then_to_slow_down() {
+        let now = Utc::now();
+        let mut pending = authorization(DeviceAuthorizationStatus::Pending, now);
+        let (status, response) = next_poll(pending.clone(), now);
+        assert_eq!(status, DeviceAuthorizationStatus::Pending);
+        assert_eq!(response.error, Some(DeviceTokenError::AuthorizationPending));
+        pending.last_polled_at = Some(now - Duration::seconds(1));
+        assert_eq!(
+            error(pending.clone(), now),
+            Some(DeviceTokenError::SlowDown)
+        );
+        pending.expires_at = now;
+        assert_eq!(error(pending, now), Some(DeviceTokenError::ExpiredToken));
+    }
+
+    #[test]
+    fn denied_stays_denied() {
+        let now = Utc::now();
+        let (status, response) =
+            next_poll(authorization(DeviceAuthorizationStatus::Denied, now), now);
+        assert_eq!(status, DeviceAuthorizationStatus::Denied);
+        assert_eq!(response.error, Some(DeviceTokenError::AccessDenied));
+        assert_eq!(response.api_token, None);
+    }
+
+    #[test]
+    fn approved_hands_out_the_token_once_consumed() {
+        let now = Utc::now();
+        let approved = authorization(DeviceAuthorizationStatus::Approved, now);
+        let token = *approved.token.clone().unwrap().as_ref();
+        let (status, response) = next_poll(approved, now);
+        assert_eq!(status, DeviceAuthorizationStatus::Consumed);
+        assert_eq!(response.api_token, Some(token));
+        assert_eq!(response.error, None);
+    }
+
+    #[test]
+    fn consumed_repeats_the_token_only_within_the_repoll_window() {
+        let now = Utc::now();
+        let mut consumed = authorization(DeviceAuthorizationStatus::Consumed, now);
+        let token = *consumed.token.clone().unwrap().as_ref();
+        consumed.consumed_at = Some(now - Duration::seconds(1));
+        consumed.last_polled_at = consumed.consumed_at;
+        let (status, response) = next_poll(consumed.clone(), now);
+        assert_eq!(status, DeviceAuthorizationStatus::Consumed);
+        assert_eq!(response.api_token, Some(token));
+
+        consumed.consumed_at = Some(now - Duration::seconds(DEVICE_TOKEN_REPOLL_SECS));
+        assert_eq!(
+            error(consumed.clone(), now),
+            Some(DeviceTokenError::ExpiredToken)
+        );
+        consumed.consumed_at = None;
+        assert_eq!(error(consumed, now), Some(DeviceTokenError::ExpiredToken));
+    }
+}
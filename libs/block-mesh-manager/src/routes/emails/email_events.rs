@@ -0,0 +1,114 @@
+use crate::database::email::create_email_suppression::create_email_suppression;
+use crate::errors::error::Error;
+use crate::utils::sns::SnsMessage;
+use axum::extract::Query;
+use axum::Extension;
+use block_mesh_common::constants::DeviceType;
+use block_mesh_common::reqwest::http_client;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::StatusCode;
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::env;
+use url::Url;
+
+#[derive(Debug, Deserialize)]
+pub struct EmailEventsQuery {
+    pub code: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SesEvent {
+    #[serde(alias = "eventType")]
+    notification_type: String,
+    bounce: Option<SesBounce>,
+    complaint: Option<SesComplaint>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SesBounce {
+    bounce_type: String,
+    bounced_recipients: Vec<SesRecipient>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SesComplaint {
+    complained_recipients: Vec<SesRecipient>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SesRecipient {
+    email_address: String,
+}
+
+/// Bounce and complaint notifications from SES, delivered through an SNS topic. Permanent
+/// bounces and complaints suppress the address, transient bounces are left to the retries.
+#[tracing::instrument(name = "email_events", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Query(query): Query<EmailEventsQuery>,
+    body: String,
+) -> Result<StatusCode, Error> {
+    let code = env::var("EMAIL_EVENTS_CODE").unwrap_or_default();
+    if code.is_empty() || query.code != code {
+        return Err(Error::Unauthorized);
+    }
+    let message: SnsMessage =
+        serde_json::from_str(&body).map_err(|e| Error::BadRequest(e.to_string()))?;
+    message
+        .verify(&env::var("EMAIL_EVENTS_TOPIC_ARN").unwrap_or_default())
+        .await?;
+    match (message.kind.as_str(), message.subscribe_url) {
+        ("SubscriptionConfirmation", Some(subscribe_url)) => {
+            let url = Url::parse(&subscribe_url).map_err(|e| Error::BadRequest(e.to_string()))?;
+            if url.scheme() != "https"
+                || !url
+                    .host_str()
+                    .is_some_and(|host| host.ends_with(".amazonaws.com"))
+            {
+                return Err(Error::BadRequest("Unexpected subscribe URL".to_string()));
+            }
+            http_client(DeviceType::AppServer)
+                .get(url)
+                .send()
+                .await?
+                .error_for_status()?;
+        }
+        ("Notification", _) => {
+            let event: SesEvent = serde_json::from_str(&message.message)
+                .map_err(|e| Error::BadRequest(e.to_string()))?;
+            let recipients = match (
+                event.notification_type.as_str(),
+                event.bounce,
+                event.complaint,
+            ) {
+                ("Bounce", Some(bounce), _) if bounce.bounce_type == "Permanent" => {
+                    bounce.bounced_recipients
+                }
+                ("Complaint", _, Some(complaint)) => complaint.complained_recipients,
+                _ => Vec::new(),
+            };
+            let mut transaction = create_txn(&pool).await?;
+            for recipient in recipients {
+                tracing::info!(
+                    "Suppressing {} after {}",
+                    recipient.email_address,
+                    event.notification_type
+                );
+                create_email_suppression(
+                    &mut transaction,
+                    &recipient.email_address,
+                    &event.notification_type,
+                )
+                .await?;
+            }
+            commit_txn(transaction).await?;
+        }
+        _ => {}
+    }
+    Ok(StatusCode::OK)
+}
@@ -1,3 +1,7 @@
 pub mod email_confirm;
+pub mod email_events;
+pub mod notification_preferences;
 pub mod resend_confirm_email_form;
 pub mod resend_confirm_email_post;
+pub mod unsubscribe;
+pub mod update_notification_preferences;
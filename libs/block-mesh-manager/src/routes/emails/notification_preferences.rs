@@ -0,0 +1,29 @@
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::email_client::locale::EmailLocale;
+use block_mesh_common::interfaces::server_api::NotificationPreferencesRequest;
+use block_mesh_manager_database_domain::domain::notification_preferences::get_or_create_notification_preferences;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::PgPool;
+
+#[tracing::instrument(name = "notification_preferences", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+) -> Result<Json<NotificationPreferencesRequest>, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let mut transaction = create_txn(&pool).await?;
+    let preferences = get_or_create_notification_preferences(
+        &mut transaction,
+        &user.id,
+        &EmailLocale::default().to_string(),
+    )
+    .await?;
+    commit_txn(transaction).await?;
+    Ok(Json(NotificationPreferencesRequest {
+        probe_alerts: preferences.probe_alerts,
+        locale: preferences.locale.parse().unwrap_or_default(),
+    }))
+}
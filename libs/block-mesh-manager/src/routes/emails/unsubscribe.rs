@@ -0,0 +1,59 @@
+use crate::database::email::unsubscribe_by_token::unsubscribe_by_token;
+use crate::errors::error::Error;
+use crate::notification::notification_redirect::NotificationRedirect;
+use axum::extract::Query;
+use axum::response::Redirect;
+use axum::Extension;
+use block_mesh_common::email_client::template::EmailCategory;
+use block_mesh_common::routes_enum::RoutesEnum;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::StatusCode;
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct UnsubscribeRequest {
+    pub token: Uuid,
+    pub category: EmailCategory,
+}
+
+/// The link in the footer of every optional email.
+#[tracing::instrument(name = "unsubscribe", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Query(query): Query<UnsubscribeRequest>,
+) -> Result<Redirect, Error> {
+    let mut transaction = create_txn(&pool).await?;
+    let found = unsubscribe_by_token(&mut transaction, &query.token, query.category).await?;
+    commit_txn(transaction).await?;
+    if !found {
+        return Ok(Error::redirect(
+            400,
+            "Invalid link",
+            "This unsubscribe link is not valid",
+            RoutesEnum::Static_UnAuth_Root.to_string().as_str(),
+        ));
+    }
+    Ok(NotificationRedirect::redirect(
+        "Unsubscribed",
+        "You will no longer receive these emails, you can change this under Notifications",
+        RoutesEnum::Static_UnAuth_Root.to_string().as_str(),
+    ))
+}
+
+/// One click unsubscribe (RFC 8058) posted by mail clients, which do not follow redirects.
+#[tracing::instrument(name = "unsubscribe_one_click", skip_all)]
+pub async fn one_click_handler(
+    Extension(pool): Extension<PgPool>,
+    Query(query): Query<UnsubscribeRequest>,
+) -> Result<StatusCode, Error> {
+    let mut transaction = create_txn(&pool).await?;
+    let found = unsubscribe_by_token(&mut transaction, &query.token, query.category).await?;
+    commit_txn(transaction).await?;
+    if found {
+        Ok(StatusCode::OK)
+    } else {
+        Err(Error::BadRequest("Invalid unsubscribe token".to_string()))
+    }
+}
@@ -0,0 +1,25 @@
+use crate::database::email::update_notification_preferences::update_notification_preferences;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::NotificationPreferencesRequest;
+use block_mesh_manager_database_domain::domain::notification_preferences::get_or_create_notification_preferences;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::StatusCode;
+use sqlx::PgPool;
+
+#[tracing::instrument(name = "update_notification_preferences", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+    Json(body): Json<NotificationPreferencesRequest>,
+) -> Result<StatusCode, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let locale = body.locale.to_string();
+    let mut transaction = create_txn(&pool).await?;
+    get_or_create_notification_preferences(&mut transaction, &user.id, &locale).await?;
+    update_notification_preferences(&mut transaction, &user.id, body.probe_alerts, &locale).await?;
+    commit_txn(transaction).await?;
+    Ok(StatusCode::OK)
+}
@@ -0,0 +1,58 @@
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::startup::application::AppState;
+use axum::extract::{Query, State};
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::constants::BLOCK_MESH_DATA_SINK;
+use block_mesh_common::interfaces::server_api::{
+    FeedAnalyticsRequest, FeedAnalyticsResponse, FeedDimension,
+};
+use block_mesh_manager_database_domain::domain::get_user_and_api_token::get_user_and_api_token_by_email;
+use chrono::NaiveDate;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use serde::Deserialize;
+use std::env;
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize)]
+pub struct FeedAnalyticsParams {
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+    pub timeline: Option<FeedDimension>,
+}
+
+/// The analytics live in data-sink, which authenticates the user's primary token for
+/// `dashboard_read` like any other client.
+#[tracing::instrument(name = "feed_analytics", skip_all)]
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+    Query(params): Query<FeedAnalyticsParams>,
+) -> Result<Json<FeedAnalyticsResponse>, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let mut follower_transaction = create_txn(&state.follower_pool).await?;
+    let user = get_user_and_api_token_by_email(&mut follower_transaction, &user.email)
+        .await?
+        .ok_or_else(|| Error::UserNotFound)?;
+    commit_txn(follower_transaction).await?;
+    let data_sink_url =
+        env::var("DATA_SINK_URL").unwrap_or_else(|_| BLOCK_MESH_DATA_SINK.to_string());
+    let response = state
+        .client
+        .post(format!("{}/analytics", data_sink_url))
+        .bearer_auth(user.token.as_ref())
+        .json(&FeedAnalyticsRequest {
+            email: user.email,
+            api_token: *user.token.as_ref(),
+            since: params.since,
+            until: params.until,
+            timeline: params.timeline,
+        })
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(Json(response))
+}
@@ -1,4 +1,3 @@
 pub mod auth_status;
 pub mod health;
-pub mod unsubscribe;
 pub mod version;
@@ -1,10 +1,13 @@
 use crate::database::invite_code::create_invite_code::create_invite_code;
+use crate::domain::invite_code::is_valid_vanity_code;
 use crate::errors::error::Error;
 use crate::middlewares::authentication::Backend;
+use crate::routes::two_factor::guard::require_two_factor;
 use crate::startup::application::AppState;
 use axum::extract::State;
 use axum::response::Redirect;
 use axum::{Extension, Form};
+use axum_login::tower_sessions::Session;
 use axum_login::AuthSession;
 use block_mesh_common::interfaces::server_api::EditInviteCodeForm;
 use sqlx::PgPool;
@@ -15,11 +18,24 @@ pub async fn handler(
     State(state): State<Arc<AppState>>,
     Extension(pool): Extension<PgPool>,
     Extension(auth): Extension<AuthSession<Backend>>,
+    session: Session,
     Form(form): Form<EditInviteCodeForm>,
 ) -> Result<Redirect, Error> {
     let mut transaction = pool.begin().await.map_err(Error::from)?;
     let user = auth.user.ok_or(Error::UserNotFound)?;
-    if !form.new_invite_code.chars().all(char::is_alphanumeric) {
+    if let Err(e) = require_two_factor(
+        &mut transaction,
+        &session,
+        &user.id,
+        &user.email,
+        form.totp_code.as_deref(),
+    )
+    .await
+    {
+        transaction.commit().await.map_err(Error::from)?;
+        return Err(e);
+    }
+    if !is_valid_vanity_code(&form.new_invite_code) {
         return Err(Error::InternalServer);
     }
     if create_invite_code(&mut transaction, user.id, &form.new_invite_code)
@@ -4,29 +4,31 @@ use crate::startup::application::AppState;
 use axum::extract::State;
 use axum::Json;
 use block_mesh_common::interfaces::server_api::{
-    GetLatestInviteCodeRequest, GetLatestInviteCodeResponse,
+    GetLatestInviteCodeRequest, GetLatestInviteCodeResponse, TokenScope,
+};
+use block_mesh_manager_database_domain::domain::authenticate_token::{
+    authenticate_token, Credentials,
 };
-use block_mesh_manager_database_domain::domain::get_user_and_api_token::get_user_and_api_token_by_email;
 use dashmap::try_result::TryResult::Present;
 use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::HeaderMap;
 use std::sync::Arc;
 
 #[tracing::instrument(name = "get_latest_invite_code", skip_all)]
 pub async fn handler(
+    headers: HeaderMap,
     State(state): State<Arc<AppState>>,
     Json(body): Json<GetLatestInviteCodeRequest>,
 ) -> Result<Json<GetLatestInviteCodeResponse>, Error> {
-    if let Present(invite_code) = state.invite_codes.try_get(&body.email) {
-        let code = invite_code.value().clone();
-        return Ok(Json(GetLatestInviteCodeResponse { invite_code: code }));
-    }
     let mut transaction = create_txn(&state.follower_pool).await?;
-    let user = get_user_and_api_token_by_email(&mut transaction, &body.email)
+    let credentials = Credentials::new(&headers, &body.email, &body.api_token);
+    let user = authenticate_token(&mut transaction, &credentials, TokenScope::DashboardRead)
         .await?
-        .ok_or_else(|| Error::UserNotFound)?;
-    if user.token.as_ref() != &body.api_token {
+        .ok_or_else(|| Error::ApiTokenNotFound)?;
+    if let Present(invite_code) = state.invite_codes.try_get(&user.email) {
+        let code = invite_code.value().clone();
         commit_txn(transaction).await?;
-        return Err(Error::ApiTokenNotFound);
+        return Ok(Json(GetLatestInviteCodeResponse { invite_code: code }));
     }
     let user_invite_code = get_user_latest_invite_code(&mut transaction, &user.user_id)
         .await
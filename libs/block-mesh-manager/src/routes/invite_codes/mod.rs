@@ -1,3 +1,4 @@
 pub mod edit_invite_code;
 pub mod edit_invite_code_post;
 pub mod get_latest_invite_code;
+pub mod redeem_invite_code;
@@ -0,0 +1,69 @@
+use crate::database::invite_campaign::claim_invite_campaign_use::claim_invite_campaign_use;
+use crate::database::invite_campaign::create_invite_code_signup::create_invite_code_signup;
+use crate::database::invite_code::get_user_opt_by_invited_code::get_user_opt_by_invited_code;
+use crate::database::perks::add_expiring_perk_to_user::add_expiring_perk_to_user;
+use crate::database::user::update_user_invited_by::update_user_invited_by;
+use crate::domain::perk::PerkName;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+pub enum InviteCodeRejection {
+    NotFound,
+    /// The code's campaign has not started, has ended or ran out of uses.
+    Unavailable,
+}
+
+impl InviteCodeRejection {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::NotFound => "Invite Code Not Found",
+            Self::Unavailable => "Invite Code Unavailable",
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::NotFound => "Please check if the invite you insert is correct",
+            Self::Unavailable => "This invite code is no longer accepting signups",
+        }
+    }
+}
+
+/// Attributes a new user to the code's owner and campaign, granting the campaign perk if it has one.
+#[tracing::instrument(name = "redeem_invite_code", skip(transaction))]
+pub async fn redeem_invite_code(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    invite_code: String,
+) -> anyhow::Result<Result<(), InviteCodeRejection>> {
+    let Some(invite_code) = get_user_opt_by_invited_code(transaction, invite_code).await? else {
+        return Ok(Err(InviteCodeRejection::NotFound));
+    };
+    if let Some(campaign_id) = invite_code.campaign_id {
+        let Some(campaign) = claim_invite_campaign_use(transaction, &campaign_id).await? else {
+            return Ok(Err(InviteCodeRejection::Unavailable));
+        };
+        if let (Some(multiplier), Some(days)) = (campaign.perk_multiplier, campaign.perk_days) {
+            add_expiring_perk_to_user(
+                transaction,
+                user_id,
+                PerkName::Campaign,
+                multiplier,
+                json!({ "campaign": campaign.name }),
+                Utc::now() + Duration::days(days.into()),
+            )
+            .await?;
+        }
+    }
+    update_user_invited_by(transaction, *user_id, invite_code.user_id).await?;
+    create_invite_code_signup(
+        transaction,
+        user_id,
+        &invite_code.id,
+        invite_code.campaign_id,
+    )
+    .await?;
+    Ok(Ok(()))
+}
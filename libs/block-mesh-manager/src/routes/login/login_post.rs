@@ -2,8 +2,11 @@ use crate::database::nonce::get_nonce_by_user_id::get_nonce_by_user_id;
 use crate::database::user::get_user_by_email::get_user_opt_by_email;
 use crate::errors::error::Error;
 use crate::middlewares::authentication::{Backend, Credentials};
+use crate::routes::two_factor::guard::start_two_factor_login;
 use axum::response::Redirect;
 use axum::{Extension, Form};
+use axum_extra::extract::CookieJar;
+use axum_login::tower_sessions::Session;
 use axum_login::AuthSession;
 use block_mesh_common::interfaces::server_api::LoginForm;
 use block_mesh_common::routes_enum::RoutesEnum;
@@ -16,6 +19,8 @@ use sqlx::PgPool;
 pub async fn handler(
     Extension(pool): Extension<PgPool>,
     Extension(mut auth): Extension<AuthSession<Backend>>,
+    session: Session,
+    jar: CookieJar,
     Form(form): Form<LoginForm>,
 ) -> Result<Redirect, Error> {
     let mut transaction = create_txn(&pool).await?;
@@ -30,7 +35,7 @@ pub async fn handler(
         password: Secret::from(form.password),
         nonce: nonce.nonce.as_ref().to_string(),
     };
-    let session = match auth.authenticate(creds).await {
+    let user_session = match auth.authenticate(creds).await {
         Ok(Some(user)) => user,
         _ => {
             commit_txn(transaction).await?;
@@ -42,7 +47,13 @@ pub async fn handler(
             ));
         }
     };
-    match auth.login(&session).await {
+    if start_two_factor_login(&mut transaction, &session, &jar, &user_session).await? {
+        commit_txn(transaction).await?;
+        return Ok(Redirect::to(
+            &RoutesEnum::Static_UnAuth_Login_Two_Factor.to_string(),
+        ));
+    }
+    match auth.login(&user_session).await {
         Ok(_) => {}
         Err(e) => {
             commit_txn(transaction).await?;
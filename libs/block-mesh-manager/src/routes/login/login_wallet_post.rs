@@ -1,21 +1,22 @@
 use crate::database::nonce::get_nonce_by_user_id::get_nonce_by_user_id;
-use crate::database::user::get_user_by_email::get_user_opt_by_email;
+use crate::database::wallet::get_wallet_owner::get_wallet_owner;
 use crate::errors::error::Error;
-use crate::middlewares::authentication::{Backend, Credentials};
-use crate::startup::application::AppState;
+use crate::middlewares::authentication::{Backend, SessionUser};
+use crate::routes::two_factor::guard::start_two_factor_login;
+use crate::startup::application::{AppState, ApplicationBaseUrl};
+use crate::utils::siws::verify_siws_message;
 use axum::extract::State;
 use axum::response::Redirect;
 use axum::{Extension, Form};
+use axum_extra::extract::CookieJar;
+use axum_login::tower_sessions::Session;
 use axum_login::AuthSession;
 use block_mesh_common::interfaces::server_api::{LoginWalletForm, SigArray};
 use block_mesh_common::routes_enum::RoutesEnum;
+use block_mesh_common::siws::SiwsPurpose;
+use block_mesh_manager_database_domain::domain::get_user_opt_by_id::get_user_opt_by_id;
 use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
-use redis::{AsyncCommands, RedisResult};
-use secret::Secret;
-use solana_sdk::pubkey::Pubkey;
-use solana_sdk::signature::Signature;
 use sqlx::PgPool;
-use std::str::FromStr;
 use std::sync::Arc;
 
 #[tracing::instrument(name = "login_wallet_post", skip_all)]
@@ -23,26 +24,12 @@ pub async fn handler(
     State(state): State<Arc<AppState>>,
     Extension(pool): Extension<PgPool>,
     Extension(mut auth): Extension<AuthSession<Backend>>,
+    Extension(base_url): Extension<ApplicationBaseUrl>,
+    session: Session,
+    jar: CookieJar,
     Form(form): Form<LoginWalletForm>,
 ) -> Result<Redirect, Error> {
     let mut redis = state.redis.clone();
-    let mut transaction = create_txn(&pool).await?;
-    let pubkey = Pubkey::from_str(form.pubkey.as_str()).unwrap_or_default();
-    let email = format!("wallet_{pubkey}@blockmesh.xyz").to_ascii_lowercase();
-    tracing::info!("form = {:#?}", form);
-    tracing::info!("email = {:#?}", email);
-
-    let user = get_user_opt_by_email(&mut transaction, &email)
-        .await?
-        .ok_or_else(|| Error::UserNotFound)?;
-    let nonce = get_nonce_by_user_id(&mut transaction, &user.id)
-        .await?
-        .ok_or_else(|| Error::NonceNotFound)?;
-    let creds: Credentials = Credentials {
-        email,
-        password: Secret::from(form.password),
-        nonce: nonce.nonce.as_ref().to_string(),
-    };
     let sig_array: SigArray = match serde_json::from_str(&form.signature) {
         Ok(sig_array) => sig_array,
         Err(_) => {
@@ -54,51 +41,62 @@ pub async fn handler(
             ));
         }
     };
-    let signature =
-        Signature::try_from(sig_array.0.as_slice()).map_err(|_| Error::InternalServer)?;
-
-    let form_nonce = form.nonce.clone();
-    let message = form.nonce.as_bytes();
-    let redis_nonce: RedisResult<String> = redis.get(form_nonce.clone()).await;
-    match redis_nonce {
-        Ok(redis_nonce) => {
-            if redis_nonce != form_nonce {
-                return Ok(Error::redirect(
-                    400,
-                    "Retry please",
-                    "Invalid nonce",
-                    RoutesEnum::Static_UnAuth_Login_Wallet.to_string().as_str(),
-                ));
-            }
-        }
-        Err(_) => {
+    let siws = match verify_siws_message(
+        &mut redis,
+        &base_url,
+        &form.message,
+        &form.pubkey,
+        &sig_array.0,
+        SiwsPurpose::Login,
+    )
+    .await
+    {
+        Ok(siws) => siws,
+        Err(e) => {
+            tracing::error!("Wallet login failed: {}", e);
             return Ok(Error::redirect(
                 400,
                 "Retry please",
-                "Missing nonce",
+                &e.to_string(),
                 RoutesEnum::Static_UnAuth_Login_Wallet.to_string().as_str(),
             ));
         }
-    }
+    };
 
-    if !signature.verify(pubkey.as_ref(), message) {
-        tracing::error!("Signature verification failed.");
-        return Err(Error::SignatureMismatch);
-    }
-    let session = match auth.authenticate(creds).await {
-        Ok(Some(user)) => user,
-        _ => {
+    let mut transaction = create_txn(&pool).await?;
+    let user = match get_wallet_owner(&mut transaction, &siws.address).await? {
+        Some(user_id) => get_user_opt_by_id(&mut transaction, &user_id)
+            .await?
+            .ok_or(Error::UserNotFound)?,
+        None => {
             commit_txn(transaction).await?;
             return Ok(Error::redirect(
                 400,
-                "Authentication failed",
-                "Authentication failed. Please try again.",
-                RoutesEnum::Static_UnAuth_Login_Wallet.to_string().as_str(),
+                "Wallet Not Linked",
+                "No account uses this wallet, register or link it from the perks page first",
+                RoutesEnum::Static_UnAuth_Register_Wallet
+                    .to_string()
+                    .as_str(),
             ));
         }
     };
+    let nonce = get_nonce_by_user_id(&mut transaction, &user.id)
+        .await?
+        .ok_or_else(|| Error::NonceNotFound)?;
+    let user_session = SessionUser {
+        id: user.id,
+        email: user.email,
+        nonce: nonce.nonce.as_ref().to_string(),
+    };
+
+    if start_two_factor_login(&mut transaction, &session, &jar, &user_session).await? {
+        commit_txn(transaction).await?;
+        return Ok(Redirect::to(
+            &RoutesEnum::Static_UnAuth_Login_Two_Factor.to_string(),
+        ));
+    }
 
-    match auth.login(&session).await {
+    match auth.login(&user_session).await {
         Ok(_) => {}
         Err(e) => {
             commit_txn(transaction).await?;
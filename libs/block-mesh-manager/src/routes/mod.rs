@@ -1,24 +1,31 @@
+pub mod account_data;
 pub mod api_token;
 pub mod bandwidth;
 pub mod basic_response;
 pub mod call_to_action;
 pub mod dashboard;
+pub mod device;
 pub mod emails;
 pub mod error;
+pub mod feed_analytics;
 pub mod health_check;
 pub mod invite_codes;
 pub mod leaderboard;
 pub mod login;
 pub mod logout;
 pub mod map;
+pub mod node_keys;
 pub mod notification;
 pub mod password;
 pub mod perks;
+pub mod probes;
 pub mod register;
 pub mod rpc;
 pub mod tasks;
 pub mod twitter;
+pub mod two_factor;
 pub mod uptime_report;
+pub mod wallets;
 
 pub mod admin;
 pub mod version;
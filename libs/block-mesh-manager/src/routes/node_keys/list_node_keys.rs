@@ -0,0 +1,45 @@
+use crate::database::node_key::get_node_keys::get_node_keys;
+use crate::errors::error::Error;
+use crate::startup::application::AppState;
+use crate::utils::cache_envar::get_envar;
+use axum::extract::State;
+use axum::Json;
+use block_mesh_common::interfaces::server_api::{NodeKey, NodeKeysRequest, TokenScope};
+use block_mesh_manager_database_domain::domain::authenticate_token::{
+    authenticate_token, Credentials,
+};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::HeaderMap;
+use std::sync::Arc;
+
+/// Lists the caller's own live node keys.
+#[tracing::instrument(name = "list_node_keys", skip_all)]
+pub async fn handler(
+    headers: HeaderMap,
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<NodeKeysRequest>,
+) -> Result<Json<Vec<NodeKey>>, Error> {
+    let credentials = Credentials::new(&headers, &body.email, &body.api_token);
+    let mut follower_transaction = create_txn(&state.follower_pool).await?;
+    let user = authenticate_token(
+        &mut follower_transaction,
+        &credentials,
+        TokenScope::TaskSubmission,
+    )
+    .await?
+    .ok_or_else(|| Error::ApiTokenNotFound)?;
+    let max_age_secs = get_envar("NODE_KEY_MAX_AGE")
+        .await
+        .parse()
+        .unwrap_or(86_400);
+    let limit = body.limit.unwrap_or(20).clamp(1, 100);
+    let keys = get_node_keys(
+        &mut follower_transaction,
+        &user.user_id,
+        max_age_secs,
+        limit,
+    )
+    .await?;
+    commit_txn(follower_transaction).await?;
+    Ok(Json(keys))
+}
@@ -0,0 +1,2 @@
+pub mod list_node_keys;
+pub mod register_node_key;
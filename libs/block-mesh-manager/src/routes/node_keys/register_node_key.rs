@@ -0,0 +1,39 @@
+use crate::database::node_key::upsert_node_key::upsert_node_key;
+use crate::errors::error::Error;
+use crate::startup::application::AppState;
+use axum::extract::State;
+use axum::Json;
+use block_mesh_common::interfaces::server_api::{NodeKey, RegisterNodeKeyRequest, TokenScope};
+use block_mesh_common::task_crypto::parse_public_key;
+use block_mesh_manager_database_domain::domain::authenticate_token::{
+    authenticate_token, Credentials,
+};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::HeaderMap;
+use std::sync::Arc;
+
+#[tracing::instrument(name = "register_node_key", skip_all)]
+pub async fn handler(
+    headers: HeaderMap,
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<RegisterNodeKeyRequest>,
+) -> Result<Json<NodeKey>, Error> {
+    let credentials = Credentials::new(&headers, &body.email, &body.api_token);
+    let mut follower_transaction = create_txn(&state.follower_pool).await?;
+    let user = authenticate_token(
+        &mut follower_transaction,
+        &credentials,
+        TokenScope::TaskSubmission,
+    )
+    .await?
+    .ok_or_else(|| Error::ApiTokenNotFound)?;
+    commit_txn(follower_transaction).await?;
+    parse_public_key(&body.public_key)?;
+    let mut transaction = create_txn(&state.pool).await?;
+    let id = upsert_node_key(&mut transaction, &user.user_id, &body.public_key).await?;
+    commit_txn(transaction).await?;
+    Ok(Json(NodeKey {
+        id,
+        public_key: body.public_key,
+    }))
+}
@@ -6,10 +6,12 @@ use crate::domain::password::Password;
 use crate::errors::error::Error;
 use crate::middlewares::authentication::del_from_redis_with_pattern;
 use crate::notification::notification_redirect::NotificationRedirect;
+use crate::routes::two_factor::guard::require_two_factor;
 use crate::startup::application::AppState;
 use axum::extract::State;
 use axum::response::Redirect;
 use axum::{Extension, Form};
+use axum_login::tower_sessions::Session;
 use bcrypt::{hash, DEFAULT_COST};
 use block_mesh_common::interfaces::db_messages::InvalidateApiCache;
 use block_mesh_common::interfaces::server_api::NewPasswordForm;
@@ -23,6 +25,7 @@ use uuid::Uuid;
 pub async fn handler(
     State(state): State<Arc<AppState>>,
     Extension(pool): Extension<PgPool>,
+    session: Session,
     Form(form): Form<NewPasswordForm>,
 ) -> Result<Redirect, Error> {
     let mut redis = state.redis.clone();
@@ -63,6 +66,35 @@ pub async fn handler(
     if *nonce.nonce.expose_secret() != form.token {
         return Err(Error::TokenMismatch);
     }
+    match require_two_factor(
+        &mut transaction,
+        &session,
+        &user.id,
+        &user.email,
+        form.totp_code.as_deref(),
+    )
+    .await
+    {
+        Ok(()) => {}
+        Err(Error::TwoFactorRequired) => {
+            commit_txn(transaction).await?;
+            return Ok(Error::redirect(
+                403,
+                "Two Factor Code Required",
+                "Please enter a code from your authenticator app or a recovery code",
+                RoutesEnum::Static_UnAuth_ResetPassword.to_string().as_str(),
+            ));
+        }
+        Err(Error::TwoFactorLocked) => {
+            return Ok(Error::redirect(
+                429,
+                "Too Many Attempts",
+                "Too many two factor attempts, try again later",
+                RoutesEnum::Static_UnAuth_ResetPassword.to_string().as_str(),
+            ));
+        }
+        Err(e) => return Err(e),
+    }
     let hashed_password = hash(form.password.clone(), DEFAULT_COST)?;
     update_user_password(&mut transaction, user.id, hashed_password).await?;
     update_api_token(&mut transaction, user.id).await?;
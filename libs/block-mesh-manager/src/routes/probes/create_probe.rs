@@ -0,0 +1,100 @@
+use crate::database::probe::count_user_probes::count_user_probes;
+use crate::database::probe::create_probe::create_probe;
+use crate::errors::error::Error;
+use crate::startup::application::AppState;
+use crate::utils::cache_envar::get_envar;
+use anyhow::anyhow;
+use axum::extract::State;
+use axum::Json;
+use block_mesh_common::interfaces::server_api::{
+    CreateProbeRequest, CreateProbeResponse, TokenScope,
+};
+use block_mesh_common::public_address::resolve_public_host;
+use block_mesh_manager_database_domain::domain::authenticate_token::{
+    authenticate_token, Credentials,
+};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::HeaderMap;
+use std::sync::Arc;
+use url::Url;
+
+/// Returns the host and port the probe points at, which still have to resolve to public
+/// addresses.
+fn validate_probe(body: &CreateProbeRequest, min_interval: i32) -> anyhow::Result<(String, u16)> {
+    let url = Url::parse(&body.url)?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(anyhow!("Probe URL must be http or https"));
+    }
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow!("Probe URL must have a host"))?
+        .to_string();
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| anyhow!("Probe URL must have a port"))?;
+    if !matches!(body.method.as_str(), "GET" | "POST") {
+        return Err(anyhow!("Probe method must be GET or POST"));
+    }
+    if body.interval_secs < min_interval {
+        return Err(anyhow!("Probe interval must be at least {}s", min_interval));
+    }
+    if body
+        .regions
+        .iter()
+        .any(|r| r.len() != 2 || !r.chars().all(|c| c.is_ascii_uppercase()))
+    {
+        return Err(anyhow!("Probe regions must be ISO country codes"));
+    }
+    if body
+        .availability_threshold
+        .is_some_and(|a| !(0.0..=1.0).contains(&a))
+    {
+        return Err(anyhow!("Availability threshold must be between 0 and 1"));
+    }
+    if body
+        .alert_email
+        .as_ref()
+        .is_some_and(|e| !e.eq_ignore_ascii_case(&body.email))
+    {
+        return Err(anyhow!(
+            "Probe alerts can only be sent to the account email"
+        ));
+    }
+    Ok((host, port))
+}
+
+#[tracing::instrument(name = "create_probe", skip_all)]
+pub async fn handler(
+    headers: HeaderMap,
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<CreateProbeRequest>,
+) -> Result<Json<CreateProbeResponse>, Error> {
+    let min_interval = get_envar("PROBE_MIN_INTERVAL").await.parse().unwrap_or(30);
+    let max_probes = get_envar("PROBE_MAX_PER_USER").await.parse().unwrap_or(20);
+    let (host, port) =
+        validate_probe(&body, min_interval).map_err(|e| Error::BadRequest(e.to_string()))?;
+    tokio::task::spawn_blocking(move || resolve_public_host(&host, port))
+        .await
+        .map_err(|e| anyhow!(e))?
+        .map_err(|e| Error::BadRequest(e.to_string()))?;
+    let mut follower_transaction = create_txn(&state.follower_pool).await?;
+    let credentials = Credentials::new(&headers, &body.email, &body.api_token);
+    let user = authenticate_token(
+        &mut follower_transaction,
+        &credentials,
+        TokenScope::TaskSubmission,
+    )
+    .await?
+    .ok_or_else(|| Error::ApiTokenNotFound)?;
+    commit_txn(follower_transaction).await?;
+    let mut transaction = create_txn(&state.pool).await?;
+    if count_user_probes(&mut transaction, &user.user_id).await? >= max_probes {
+        return Err(Error::BadRequest(format!(
+            "A user can have at most {} probes",
+            max_probes
+        )));
+    }
+    let id = create_probe(&mut transaction, &user.user_id, &body).await?;
+    commit_txn(transaction).await?;
+    Ok(Json(CreateProbeResponse { id }))
+}
@@ -0,0 +1,39 @@
+use crate::database::probe::delete_probe::delete_probe;
+use crate::errors::error::Error;
+use crate::startup::application::AppState;
+use anyhow::anyhow;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use block_mesh_common::interfaces::server_api::{ProbeRequest, TokenScope};
+use block_mesh_manager_database_domain::domain::authenticate_token::{
+    authenticate_token, Credentials,
+};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::{HeaderMap, StatusCode};
+use std::sync::Arc;
+
+#[tracing::instrument(name = "delete_probe", skip_all)]
+pub async fn handler(
+    headers: HeaderMap,
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<ProbeRequest>,
+) -> Result<impl IntoResponse, Error> {
+    let mut follower_transaction = create_txn(&state.follower_pool).await?;
+    let credentials = Credentials::new(&headers, &body.email, &body.api_token);
+    let user = authenticate_token(
+        &mut follower_transaction,
+        &credentials,
+        TokenScope::TaskSubmission,
+    )
+    .await?
+    .ok_or_else(|| Error::ApiTokenNotFound)?;
+    commit_txn(follower_transaction).await?;
+    let mut transaction = create_txn(&state.pool).await?;
+    let rows_affected = delete_probe(&mut transaction, &user.user_id, &body.probe_id).await?;
+    if rows_affected == 0 {
+        return Err(Error::from(anyhow!("Probe {} not found", body.probe_id)));
+    }
+    commit_txn(transaction).await?;
+    Ok(StatusCode::OK.into_response())
+}
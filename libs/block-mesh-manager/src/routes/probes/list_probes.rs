@@ -0,0 +1,33 @@
+use crate::database::probe::get_user_probes::get_user_probes;
+use crate::errors::error::Error;
+use crate::startup::application::AppState;
+use axum::extract::State;
+use axum::Json;
+use block_mesh_common::interfaces::server_api::{DashboardRequest, TokenScope};
+use block_mesh_manager_database_domain::domain::authenticate_token::{
+    authenticate_token, Credentials,
+};
+use block_mesh_manager_database_domain::domain::probe::Probe;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::HeaderMap;
+use std::sync::Arc;
+
+#[tracing::instrument(name = "list_probes", skip_all)]
+pub async fn handler(
+    headers: HeaderMap,
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<DashboardRequest>,
+) -> Result<Json<Vec<Probe>>, Error> {
+    let mut follower_transaction = create_txn(&state.follower_pool).await?;
+    let credentials = Credentials::new(&headers, &body.email, &body.api_token);
+    let user = authenticate_token(
+        &mut follower_transaction,
+        &credentials,
+        TokenScope::DashboardRead,
+    )
+    .await?
+    .ok_or_else(|| Error::ApiTokenNotFound)?;
+    let probes = get_user_probes(&mut follower_transaction, &user.user_id).await?;
+    commit_txn(follower_transaction).await?;
+    Ok(Json(probes))
+}
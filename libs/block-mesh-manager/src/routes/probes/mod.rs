@@ -0,0 +1,4 @@
+pub mod create_probe;
+pub mod delete_probe;
+pub mod list_probes;
+pub mod probe_slo;
@@ -0,0 +1,48 @@
+use crate::database::probe::get_probe_slo::{get_probe_slo_by_asn, get_probe_slo_by_region};
+use crate::database::probe::get_user_probes::get_user_probes;
+use crate::errors::error::Error;
+use crate::startup::application::AppState;
+use anyhow::anyhow;
+use axum::extract::State;
+use axum::Json;
+use block_mesh_common::interfaces::server_api::{ProbeRequest, ProbeSloResponse, TokenScope};
+use block_mesh_manager_database_domain::domain::authenticate_token::{
+    authenticate_token, Credentials,
+};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::HeaderMap;
+use std::sync::Arc;
+
+#[tracing::instrument(name = "probe_slo", skip_all)]
+pub async fn handler(
+    headers: HeaderMap,
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<ProbeRequest>,
+) -> Result<Json<ProbeSloResponse>, Error> {
+    let window_secs = body.window_secs.unwrap_or(3_600).min(7 * 86_400);
+    let mut follower_transaction = create_txn(&state.follower_pool).await?;
+    let credentials = Credentials::new(&headers, &body.email, &body.api_token);
+    let user = authenticate_token(
+        &mut follower_transaction,
+        &credentials,
+        TokenScope::DashboardRead,
+    )
+    .await?
+    .ok_or_else(|| Error::ApiTokenNotFound)?;
+    let probes = get_user_probes(&mut follower_transaction, &user.user_id).await?;
+    if !probes.iter().any(|p| p.id == body.probe_id) {
+        commit_txn(follower_transaction).await?;
+        return Err(Error::from(anyhow!("Probe {} not found", body.probe_id)));
+    }
+    let by_region =
+        get_probe_slo_by_region(&mut follower_transaction, &body.probe_id, window_secs).await?;
+    let by_asn =
+        get_probe_slo_by_asn(&mut follower_transaction, &body.probe_id, window_secs).await?;
+    commit_txn(follower_transaction).await?;
+    Ok(Json(ProbeSloResponse {
+        probe_id: body.probe_id,
+        window_secs,
+        by_region,
+        by_asn,
+    }))
+}
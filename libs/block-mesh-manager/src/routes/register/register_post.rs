@@ -1,74 +1,54 @@
-use std::env;
 use std::sync::Arc;
 
-use anyhow::{anyhow, Context};
+use anyhow::anyhow;
 use axum::extract::State;
 use axum::response::Redirect;
 use axum::{Extension, Form};
 use axum_login::AuthSession;
 use bcrypt::{hash, DEFAULT_COST};
-use block_mesh_common::constants::{DeviceType, BLOCK_MESH_EMAILS};
-use block_mesh_common::interfaces::server_api::{RegisterForm, SendEmail};
-use block_mesh_common::reqwest::http_client;
+use block_mesh_common::email_client::locale::EmailLocale;
+use block_mesh_common::email_client::template::EmailTemplate;
+use block_mesh_common::interfaces::server_api::RegisterForm;
 use block_mesh_common::routes_enum::RoutesEnum;
 use block_mesh_manager_database_domain::domain::nonce::Nonce;
+use block_mesh_manager_database_domain::domain::notification_preferences::get_or_create_notification_preferences;
 use block_mesh_manager_database_domain::domain::prep_user::prep_user;
-use chrono::{Duration, Utc};
-use dash_with_expiry::dash_set_with_expiry::DashSetWithExpiry;
 use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::header::ACCEPT_LANGUAGE;
 use http::HeaderMap;
 use secret::Secret;
 use sqlx::PgPool;
-use tokio::sync::OnceCell;
 use uuid::Uuid;
 use validator::validate_email;
 
+use crate::configuration::rate_limit_settings::RateLimitKey;
 use crate::database::api_token::create_api_token::create_api_token;
+use crate::database::email::enqueue_email::enqueue_email;
 use crate::database::invite_code::create_invite_code::create_invite_code;
-use crate::database::invite_code::get_user_opt_by_invited_code::get_user_opt_by_invited_code;
 use crate::database::nonce::create_nonce::create_nonce;
 use crate::database::uptime_report::create_uptime_report::create_uptime_report;
 use crate::database::user::create_user::create_user;
 use crate::database::user::get_user_by_email::get_user_opt_by_email;
-use crate::database::user::update_user_invited_by::update_user_invited_by;
 use crate::errors::error::Error;
 use crate::middlewares::authentication::{Backend, Credentials};
+use crate::middlewares::rate_limit::{RateLimiter, REGISTER_POLICY};
+use crate::routes::invite_codes::redeem_invite_code::redeem_invite_code;
 use crate::startup::application::AppState;
-use crate::utils::cache_envar::get_envar;
 use crate::utils::cftoken::check_cf_token;
 
-static RATE_LIMIT_IP: OnceCell<DashSetWithExpiry<String>> = OnceCell::const_new();
-
 #[tracing::instrument(name = "register_post", skip_all)]
 pub async fn handler(
-    headers: HeaderMap,
     Extension(pool): Extension<PgPool>,
     Extension(mut auth): Extension<AuthSession<Backend>>,
+    Extension(rate_limiter): Extension<Arc<RateLimiter>>,
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Form(form): Form<RegisterForm>,
 ) -> Result<Redirect, Error> {
     let email = form.email.clone().to_ascii_lowercase();
-    let app_env = get_envar("APP_ENVIRONMENT").await;
-    let header_ip = if app_env != "local" {
-        headers
-            .get("cf-connecting-ip")
-            .context("Missing CF-CONNECTING-IP")?
-            .to_str()
-            .context("Unable to STR CF-CONNECTING-IP")?
-    } else {
-        "127.0.0.1"
-    }
-    .to_string();
-
-    let cache = RATE_LIMIT_IP
-        .get_or_init(|| async { DashSetWithExpiry::new() })
-        .await;
-    if cache.get(&header_ip).is_some() || cache.get(&email).is_some() {
-        return Err(Error::NotAllowedRateLimit);
-    }
-    let date = Utc::now() + Duration::milliseconds(60_000);
-    cache.insert(header_ip, Some(date));
-    cache.insert(email.clone(), Some(date));
+    rate_limiter
+        .enforce(REGISTER_POLICY, RateLimitKey::Email, &email)
+        .await?;
 
     if state.cf_enforce {
         if let Err(e) = check_cf_token(form.cftoken.unwrap_or_default(), &state.cf_secret_key).await
@@ -146,19 +126,15 @@ pub async fn handler(
     create_uptime_report(&mut transaction, &user_id, &None).await?;
     prep_user(&mut transaction, &user_id).await?;
     if !form.invite_code.is_empty() {
-        match get_user_opt_by_invited_code(&mut transaction, form.invite_code).await? {
-            Some(invited_by_user) => {
-                let invited_by_user_id = invited_by_user.user_id;
-                update_user_invited_by(&mut transaction, user_id, invited_by_user_id).await?;
-            }
-            None => {
-                return Ok(Error::redirect(
-                    400,
-                    "Invite Code Not Found",
-                    "Please check if the invite you insert is correct",
-                    RoutesEnum::Static_UnAuth_Register.to_string().as_str(),
-                ))
-            }
+        if let Err(rejection) =
+            redeem_invite_code(&mut transaction, &user_id, form.invite_code).await?
+        {
+            return Ok(Error::redirect(
+                400,
+                rejection.title(),
+                rejection.message(),
+                RoutesEnum::Static_UnAuth_Register.to_string().as_str(),
+            ));
         }
     } else {
         return Ok(Error::redirect(
@@ -168,6 +144,21 @@ pub async fn handler(
             RoutesEnum::Static_UnAuth_Register.to_string().as_str(),
         ));
     }
+    let locale = headers
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|header| header.to_str().ok())
+        .and_then(EmailLocale::from_accept_language)
+        .unwrap_or_default();
+    get_or_create_notification_preferences(&mut transaction, &user_id, &locale.to_string()).await?;
+    enqueue_email(
+        &mut transaction,
+        &user_id,
+        &email,
+        &EmailTemplate::ConfirmEmail {
+            token: nonce.clone(),
+        },
+    )
+    .await?;
     commit_txn(transaction).await?;
 
     let creds: Credentials = Credentials {
@@ -184,31 +175,5 @@ pub async fn handler(
     auth.login(&session)
         .await
         .map_err(|_| Error::Auth(anyhow!("Login failed").to_string()))?;
-    let email_mode = get_envar("EMAIL_MODE").await;
-    if email_mode == "AWS" {
-        state
-            .email_client
-            .send_confirmation_email_aws(&email, nonce_secret.expose_secret())
-            .await?;
-    } else if email_mode == "SERVICE" {
-        let client = http_client(DeviceType::AppServer);
-        client
-            .get(format!("{}/send_email", BLOCK_MESH_EMAILS))
-            .query(&SendEmail {
-                code: env::var("EMAILS_CODE").unwrap_or_default(),
-                user_id: session.id,
-                email_type: "confirm_email".to_string(),
-                email_address: session.email,
-                nonce: session.nonce,
-            })
-            .send()
-            .await
-            .map_err(Error::from)?;
-    } else {
-        state
-            .email_client
-            .send_confirmation_email_smtp(&email, nonce_secret.expose_secret())
-            .await?;
-    }
     Ok(Redirect::to("/ui/dashboard"))
 }
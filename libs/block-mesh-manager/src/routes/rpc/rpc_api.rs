@@ -1,13 +1,23 @@
+use anyhow::Context;
 use axum::{Extension, Json};
+use block_mesh_common::constants::BLOCKMESH_SERVER_UUID_ENVAR;
 use sqlx::PgPool;
+use std::env;
+use uuid::Uuid;
 
 use crate::database::task::get_tasks_rpc_results::{get_tasks_rpc_results, RpcResults};
 use crate::errors::error::Error;
 
 #[tracing::instrument(name = "rpc_api", skip_all)]
 pub async fn handler(Extension(pool): Extension<PgPool>) -> Result<Json<Vec<RpcResults>>, Error> {
+    let user_id = Uuid::parse_str(
+        env::var(BLOCKMESH_SERVER_UUID_ENVAR)
+            .context("Could not find SERVER_UUID env var")?
+            .as_str(),
+    )
+    .context("SERVER_UUID env var contains invalid UUID value")?;
     let mut transaction = pool.begin().await.map_err(Error::from)?;
-    let results = get_tasks_rpc_results(&mut transaction, 600).await?;
+    let results = get_tasks_rpc_results(&mut transaction, &user_id, 600).await?;
     transaction.commit().await.map_err(Error::from)?;
     Ok(Json(results))
 }
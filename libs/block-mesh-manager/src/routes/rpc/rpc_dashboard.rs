@@ -1,17 +1,20 @@
 use crate::database::task::get_tasks_rpc_results::{get_tasks_rpc_results, RpcResults};
 use crate::errors::error::Error;
 use crate::startup::application::AppState;
+use anyhow::Context;
 use askama::Template;
 use askama_axum::IntoResponse;
 use axum::extract::State;
 use axum::Extension;
 use block_mesh_common::constants::{
-    BLOCK_MESH_APP_SERVER, BLOCK_MESH_CHROME_EXTENSION_LINK, BLOCK_MESH_GITBOOK, BLOCK_MESH_GITHUB,
-    BLOCK_MESH_LANDING_PAGE_IMAGE, BLOCK_MESH_LOGO, BLOCK_MESH_SUPPORT_CHAT,
-    BLOCK_MESH_SUPPORT_EMAIL, BLOCK_MESH_TWITTER,
+    BLOCKMESH_SERVER_UUID_ENVAR, BLOCK_MESH_APP_SERVER, BLOCK_MESH_CHROME_EXTENSION_LINK,
+    BLOCK_MESH_GITBOOK, BLOCK_MESH_GITHUB, BLOCK_MESH_LANDING_PAGE_IMAGE, BLOCK_MESH_LOGO,
+    BLOCK_MESH_SUPPORT_CHAT, BLOCK_MESH_SUPPORT_EMAIL, BLOCK_MESH_TWITTER,
 };
 use sqlx::PgPool;
+use std::env;
 use std::sync::Arc;
+use uuid::Uuid;
 
 #[allow(dead_code)]
 #[derive(Template)]
@@ -35,8 +38,14 @@ pub async fn handler(
     State(state): State<Arc<AppState>>,
     Extension(pool): Extension<PgPool>,
 ) -> Result<impl IntoResponse, Error> {
+    let user_id = Uuid::parse_str(
+        env::var(BLOCKMESH_SERVER_UUID_ENVAR)
+            .context("Could not find SERVER_UUID env var")?
+            .as_str(),
+    )
+    .context("SERVER_UUID env var contains invalid UUID value")?;
     let mut transaction = pool.begin().await.map_err(Error::from)?;
-    let results = get_tasks_rpc_results(&mut transaction, 600).await?;
+    let results = get_tasks_rpc_results(&mut transaction, &user_id, 600).await?;
     transaction.commit().await.map_err(Error::from)?;
     let template = RpcDashboardTemplate {
         cf_site_key: state.cf_site_key.to_string(),
@@ -0,0 +1,67 @@
+use crate::database::node_key::get_node_key_by_id::get_node_key_by_id;
+use crate::database::task::count_user_tasks_in_period::count_user_tasks_in_period;
+use crate::database::task::create_encrypted_task::create_encrypted_task;
+use crate::errors::error::Error;
+use crate::startup::application::AppState;
+use crate::utils::cache_envar::get_envar;
+use anyhow::anyhow;
+use axum::extract::State;
+use axum::Json;
+use block_mesh_common::interfaces::server_api::{
+    CreateEncryptedTaskRequest, CreateEncryptedTaskResponse, TokenScope,
+};
+use block_mesh_common::task_crypto::{is_envelope, parse_public_key};
+use block_mesh_manager_database_domain::domain::authenticate_token::{
+    authenticate_token, Credentials,
+};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::HeaderMap;
+use std::sync::Arc;
+
+#[tracing::instrument(name = "create_encrypted_task", skip_all)]
+pub async fn handler(
+    headers: HeaderMap,
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<CreateEncryptedTaskRequest>,
+) -> Result<Json<CreateEncryptedTaskResponse>, Error> {
+    let mut transaction = create_txn(&state.pool).await?;
+    let credentials = Credentials::new(&headers, &body.email, &body.api_token);
+    let user = authenticate_token(&mut transaction, &credentials, TokenScope::TaskSubmission)
+        .await?
+        .ok_or_else(|| Error::ApiTokenNotFound)?;
+    parse_public_key(&body.reply_key)?;
+    if !is_envelope(&body.encrypted_payload) {
+        commit_txn(transaction).await?;
+        return Err(Error::from(anyhow!(
+            "encrypted_payload is not a sealed envelope"
+        )));
+    }
+    let max_age_secs = get_envar("NODE_KEY_MAX_AGE")
+        .await
+        .parse()
+        .unwrap_or(86_400);
+    let node_key = get_node_key_by_id(
+        &mut transaction,
+        &body.node_key_id,
+        &user.user_id,
+        max_age_secs,
+    )
+    .await?
+    .ok_or_else(|| anyhow!("Node key {} not found or expired", body.node_key_id))?;
+    let users_tasks_count = count_user_tasks_in_period(&mut transaction, &user.user_id, 60).await?;
+    if users_tasks_count > 50 {
+        commit_txn(transaction).await?;
+        return Err(Error::TooManyTasks);
+    }
+    let task_id = create_encrypted_task(
+        &mut transaction,
+        &user.user_id,
+        &node_key.user_id,
+        &body.node_key_id,
+        body.encrypted_payload,
+        &body.reply_key,
+    )
+    .await?;
+    commit_txn(transaction).await?;
+    Ok(Json(CreateEncryptedTaskResponse { task_id }))
+}
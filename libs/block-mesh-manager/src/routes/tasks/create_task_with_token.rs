@@ -2,9 +2,12 @@ use crate::database::task::count_user_tasks_in_period::count_user_tasks_in_perio
 use crate::database::task::create_task::create_task;
 use crate::errors::error::Error;
 use axum::{Extension, Json};
-use block_mesh_manager_database_domain::domain::get_user_and_api_token::get_user_and_api_token_by_email;
+use block_mesh_common::interfaces::server_api::TokenScope;
+use block_mesh_manager_database_domain::domain::authenticate_token::{
+    authenticate_token, Credentials,
+};
 use block_mesh_manager_database_domain::domain::task::TaskMethod;
-use database_utils::utils::instrument_wrapper::commit_txn;
+use http::HeaderMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sqlx::PgPool;
@@ -27,17 +30,15 @@ pub struct CreateTaskResponse {
 
 #[tracing::instrument(name = "create_task_with_token", skip_all)]
 pub async fn handler(
+    headers: HeaderMap,
     Extension(pool): Extension<PgPool>,
     Json(body): Json<CreateTaskRequest>,
 ) -> Result<Json<CreateTaskResponse>, Error> {
     let mut transaction = pool.begin().await.map_err(Error::from)?;
-    let user = get_user_and_api_token_by_email(&mut transaction, &body.email)
+    let credentials = Credentials::new(&headers, &body.email, &body.api_token);
+    let user = authenticate_token(&mut transaction, &credentials, TokenScope::TaskSubmission)
         .await?
-        .ok_or_else(|| Error::UserNotFound)?;
-    if user.token.as_ref() != &body.api_token {
-        commit_txn(transaction).await?;
-        return Err(Error::ApiTokenNotFound);
-    }
+        .ok_or_else(|| Error::ApiTokenNotFound)?;
     let users_tasks_count = count_user_tasks_in_period(&mut transaction, &user.user_id, 60).await?;
     if users_tasks_count > 50 {
         return Err(Error::TooManyTasks);
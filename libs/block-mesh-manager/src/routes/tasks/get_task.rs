@@ -2,16 +2,19 @@ use crate::database::task::find_task_assigned_to_user::find_task_assigned_to_use
 use crate::database::task::find_task_by_status::find_task_by_status;
 use crate::errors::error::Error;
 use crate::middlewares::authentication::Backend;
-use crate::middlewares::rate_limit::filter_request;
 use crate::startup::application::AppState;
 use crate::utils::cache_envar::get_envar;
 use anyhow::Context;
 use axum::extract::State;
+use axum::response::{IntoResponse, Response};
 use axum::{Extension, Json};
-use block_mesh_common::interfaces::server_api::{GetTaskRequest, GetTaskResponse};
+use block_mesh_common::interfaces::server_api::{GetTaskRequest, GetTaskResponse, TokenScope};
+use block_mesh_manager_database_domain::domain::authenticate_token::{
+    authenticate_token, touch_api_token, Credentials,
+};
 use block_mesh_manager_database_domain::domain::create_daily_stat::get_or_create_daily_stat;
-use block_mesh_manager_database_domain::domain::get_user_and_api_token::get_user_and_api_token_by_email;
-use block_mesh_manager_database_domain::domain::task::TaskStatus;
+use block_mesh_manager_database_domain::domain::task::{GetTask, TaskStatus};
+use block_mesh_manager_database_domain::domain::task_blob::resolve_task_body;
 use block_mesh_manager_database_domain::domain::task_limit::TaskLimit;
 use block_mesh_manager_database_domain::domain::update_task_assigned::update_task_assigned;
 use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
@@ -19,6 +22,11 @@ use http::HeaderMap;
 use sqlx::PgPool;
 use std::sync::Arc;
 
+/// Nodes poll again after an empty answer, which is what they got before limits were 429s.
+pub fn rate_limited() -> Response {
+    Json(None::<GetTaskResponse>).into_response()
+}
+
 #[tracing::instrument(name = "get_task", skip_all)]
 pub async fn handler(
     headers: HeaderMap,
@@ -26,14 +34,9 @@ pub async fn handler(
     State(state): State<Arc<AppState>>,
     Json(body): Json<GetTaskRequest>,
 ) -> Result<Json<Option<GetTaskResponse>>, Error> {
+    let credentials = Credentials::new(&headers, &body.email, &body.api_token);
     let limit = get_envar("TASK_LIMIT").await.parse().unwrap_or(10);
     let mut redis = state.redis.clone();
-    if state.task_limit {
-        let _ = match TaskLimit::get_task_limit(&body.api_token, &mut redis, limit).await {
-            Ok(r) => r,
-            Err(_) => return Ok(Json(None)),
-        };
-    }
     let app_env = get_envar("APP_ENVIRONMENT").await;
     let header_ip = if app_env != "local" {
         headers
@@ -44,38 +47,40 @@ pub async fn handler(
     } else {
         "127.0.0.1"
     };
-
-    if state.rate_limit {
-        let filter = filter_request(&mut redis, &body.api_token, header_ip, "get_task").await;
-        if filter.is_err() || !filter? {
-            return Ok(Json(None));
-        }
-    }
     let mut follower_transaction = create_txn(&state.follower_pool).await?;
-    let user = get_user_and_api_token_by_email(&mut follower_transaction, &body.email)
-        .await?
-        .ok_or_else(|| Error::UserNotFound)?;
-    if user.token.as_ref() != &body.api_token {
-        commit_txn(follower_transaction).await?;
-        return Err(Error::ApiTokenNotFound);
+    let user = authenticate_token(
+        &mut follower_transaction,
+        &credentials,
+        TokenScope::TaskSubmission,
+    )
+    .await?
+    .ok_or_else(|| Error::ApiTokenNotFound)?;
+    // Keyed by user, so minting more tokens does not buy more tasks.
+    if state.task_limit
+        && TaskLimit::get_task_limit(&user.user_id, &mut redis, limit)
+            .await
+            .is_err()
+    {
+        return Ok(Json(None));
     }
-    let task = find_task_assigned_to_user(&mut follower_transaction, &user.user_id).await?;
+    let task =
+        find_task_assigned_to_user(&mut follower_transaction, &user.user_id, body.node_key_id)
+            .await?;
     if let Some(task) = task {
-        return Ok(Json(Some(GetTaskResponse {
-            id: task.id,
-            url: task.url,
-            method: task.method.to_string(),
-            headers: task.headers,
-            body: task.body,
-        })));
+        return Ok(Json(Some(task_response(task).await?)));
     }
-    let task = find_task_by_status(&mut follower_transaction, TaskStatus::Pending).await?;
+    let country = headers
+        .get("cf-ipcountry")
+        .and_then(|c| c.to_str().ok())
+        .unwrap_or_default();
+    let task = find_task_by_status(&mut follower_transaction, TaskStatus::Pending, country).await?;
     let task = match task {
         Some(v) => v,
         None => return Ok(Json(None)),
     };
     commit_txn(follower_transaction).await?;
     let mut transaction = create_txn(&pool).await?;
+    touch_api_token(&mut transaction, &user.token_id, Some(header_ip)).await?;
     let _ = get_or_create_daily_stat(&mut transaction, &user.user_id, None).await?;
     update_task_assigned(
         &mut transaction,
@@ -88,19 +93,37 @@ pub async fn handler(
     if state.task_limit {
         let task_bonus = get_envar("TASK_BONUS").await.parse().unwrap_or(0);
         let expire = 10u64 * Backend::get_expire().await as u64;
-        let mut redis_user =
-            match TaskLimit::get_task_limit(&body.api_token, &mut redis, limit).await {
-                Ok(r) => r,
-                Err(_) => return Err(Error::TaskLimit),
-            };
+        let mut redis_user = match TaskLimit::get_task_limit(&user.user_id, &mut redis, limit).await
+        {
+            Ok(r) => r,
+            Err(_) => return Err(Error::TaskLimit),
+        };
         redis_user.tasks += 1 + task_bonus;
         TaskLimit::save_user(&mut redis, &redis_user, expire).await;
     }
-    Ok(Json(Some(GetTaskResponse {
+    Ok(Json(Some(task_response(task).await?)))
+}
+
+async fn task_response(task: GetTask) -> anyhow::Result<GetTaskResponse> {
+    let body = resolve_task_body(task.body, task.body_ref).await?;
+    if task.encrypted {
+        return Ok(GetTaskResponse {
+            id: task.id,
+            url: String::default(),
+            method: String::default(),
+            headers: None,
+            body: None,
+            encrypted_payload: body.and_then(|body| body.as_str().map(String::from)),
+            reply_key: task.reply_key,
+        });
+    }
+    Ok(GetTaskResponse {
         id: task.id,
         url: task.url,
         method: task.method.to_string(),
         headers: task.headers,
-        body: task.body,
-    })))
+        body,
+        encrypted_payload: None,
+        reply_key: None,
+    })
 }
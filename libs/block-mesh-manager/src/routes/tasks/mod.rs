@@ -1,7 +1,10 @@
+pub mod create_encrypted_task;
 pub mod create_task;
 pub mod create_task_post;
 pub mod create_task_with_token;
 pub mod get_task;
 pub mod submit_task;
+pub mod task_result;
+pub mod task_retention;
 pub mod tasks_table;
 pub mod view_task;
@@ -5,6 +5,7 @@ use axum::Json;
 use block_mesh_common::interfaces::server_api::{
     HandlerMode, SubmitTaskRequest, SubmitTaskResponse,
 };
+use block_mesh_manager_database_domain::domain::authenticate_token::Credentials;
 use block_mesh_manager_database_domain::domain::submit_task_content::submit_task_content;
 use std::sync::Arc;
 
@@ -14,10 +15,18 @@ pub async fn handler(
     Query(query): Query<SubmitTaskRequest>,
     request: Request,
 ) -> Result<Json<SubmitTaskResponse>, Error> {
+    let credentials = Credentials::new(request.headers(), &query.email, &query.api_token);
+    let header_ip = request
+        .headers()
+        .get("cf-connecting-ip")
+        .and_then(|ip| ip.to_str().ok())
+        .map(String::from);
     submit_task_content(
         &state.pool,
         &state.follower_pool,
         &state.channel_pool,
+        header_ip.as_deref(),
+        &credentials,
         query,
         Some(request),
         HandlerMode::Http,
@@ -0,0 +1,43 @@
+use crate::database::task::get_task_result::get_task_result;
+use crate::errors::error::Error;
+use crate::startup::application::AppState;
+use axum::extract::State;
+use axum::Json;
+use block_mesh_common::interfaces::server_api::{
+    TaskResultRequest, TaskResultResponse, TokenScope,
+};
+use block_mesh_manager_database_domain::domain::authenticate_token::{
+    authenticate_token, Credentials,
+};
+use block_mesh_manager_database_domain::domain::task_blob::resolve_task_response;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::HeaderMap;
+use std::sync::Arc;
+
+#[tracing::instrument(name = "task_result", skip_all)]
+pub async fn handler(
+    headers: HeaderMap,
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<TaskResultRequest>,
+) -> Result<Json<TaskResultResponse>, Error> {
+    let mut follower_transaction = create_txn(&state.follower_pool).await?;
+    let credentials = Credentials::new(&headers, &body.email, &body.api_token);
+    let user = authenticate_token(
+        &mut follower_transaction,
+        &credentials,
+        TokenScope::TaskSubmission,
+    )
+    .await?
+    .ok_or_else(|| Error::ApiTokenNotFound)?;
+    let task = get_task_result(&mut follower_transaction, &user.user_id, &body.task_id)
+        .await?
+        .ok_or(Error::TaskNotFound)?;
+    commit_txn(follower_transaction).await?;
+    Ok(Json(TaskResultResponse {
+        task_id: task.id,
+        status: task.status,
+        response_code: task.response_code,
+        response_body: resolve_task_response(task.response_raw, task.response_ref).await?,
+        encrypted: task.encrypted,
+    }))
+}
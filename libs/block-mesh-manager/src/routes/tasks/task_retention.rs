@@ -0,0 +1,58 @@
+use crate::database::task::get_task_retention::get_task_retention;
+use crate::database::task::upsert_task_retention::upsert_task_retention;
+use crate::errors::error::Error;
+use crate::startup::application::AppState;
+use crate::utils::cache_envar::get_envar;
+use anyhow::anyhow;
+use axum::extract::State;
+use axum::Json;
+use block_mesh_common::interfaces::server_api::{
+    TaskRetentionRequest, TaskRetentionResponse, TokenScope,
+};
+use block_mesh_manager_database_domain::domain::authenticate_token::{
+    authenticate_token, Credentials,
+};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::HeaderMap;
+use std::sync::Arc;
+
+#[tracing::instrument(name = "task_retention", skip_all)]
+pub async fn handler(
+    headers: HeaderMap,
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<TaskRetentionRequest>,
+) -> Result<Json<TaskRetentionResponse>, Error> {
+    let mut follower_transaction = create_txn(&state.follower_pool).await?;
+    let credentials = Credentials::new(&headers, &body.email, &body.api_token);
+    let user = authenticate_token(
+        &mut follower_transaction,
+        &credentials,
+        TokenScope::TaskSubmission,
+    )
+    .await?
+    .ok_or_else(|| Error::ApiTokenNotFound)?;
+    commit_txn(follower_transaction).await?;
+    let mut transaction = create_txn(&state.pool).await?;
+    let retention_days = match body.retention_days {
+        Some(retention_days) => {
+            let max_retention_days = get_envar("MAX_TASK_RETENTION_DAYS")
+                .await
+                .parse()
+                .unwrap_or(30);
+            if !(1..=max_retention_days).contains(&retention_days) {
+                return Err(Error::from(anyhow!(
+                    "retention_days must be between 1 and {}",
+                    max_retention_days
+                )));
+            }
+            upsert_task_retention(&mut transaction, &user.user_id, retention_days).await?;
+            retention_days
+        }
+        None => match get_task_retention(&mut transaction, &user.user_id).await? {
+            Some(retention_days) => retention_days,
+            None => get_envar("TASK_RETENTION_DAYS").await.parse().unwrap_or(1),
+        },
+    };
+    commit_txn(transaction).await?;
+    Ok(Json(TaskRetentionResponse { retention_days }))
+}
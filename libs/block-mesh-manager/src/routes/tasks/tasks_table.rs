@@ -63,7 +63,7 @@ struct TaskForTemplate {
     pub assigned_user_id: OptionWrapper<Uuid>,
     pub status: TaskStatus,
     pub response_code: OptionWrapper<i32>,
-    pub response_raw: OptionWrapper<String>,
+    pub has_response: bool,
     pub created_at: DateTime<Utc>,
 }
 
@@ -75,11 +75,15 @@ impl From<Task> for TaskForTemplate {
             url: task.url,
             method: task.method,
             headers: OptionWrapper(task.headers.unwrap_or(Value::Null)),
-            body: OptionWrapper(task.body.unwrap_or(Value::Null)),
+            body: OptionWrapper(match task.body_ref {
+                Some(key) => serde_json::json!({ "blob": key }),
+                None => task.body.unwrap_or(Value::Null),
+            }),
             assigned_user_id: OptionWrapper(task.assigned_user_id.unwrap_or(Uuid::nil())),
             status: task.status,
             response_code: OptionWrapper(task.response_code.unwrap_or(0)),
-            response_raw: OptionWrapper(task.response_raw.unwrap_or_default()),
+            has_response: task.response_ref.is_some()
+                || !task.response_raw.unwrap_or_default().is_empty(),
             created_at: task.created_at,
         }
     }
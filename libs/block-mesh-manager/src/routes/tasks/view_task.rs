@@ -12,6 +12,7 @@ use block_mesh_common::constants::{
     BLOCK_MESH_LANDING_PAGE_IMAGE, BLOCK_MESH_LOGO, BLOCK_MESH_SUPPORT_CHAT,
     BLOCK_MESH_SUPPORT_EMAIL, BLOCK_MESH_TWITTER,
 };
+use block_mesh_manager_database_domain::domain::task_blob::resolve_task_response;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use std::sync::Arc;
@@ -59,12 +60,12 @@ pub async fn handler(
     if task.user_id != user.id {
         return Err(Error::NotYourTask);
     }
-    if task.response_raw.is_none() {
-        return Err(Error::TaskResponseNotFound);
-    }
+    let response_raw = resolve_task_response(task.response_raw, task.response_ref)
+        .await?
+        .ok_or(Error::TaskResponseNotFound)?;
     Ok(ViewTaskTemplate {
         cf_site_key: state.cf_site_key.to_string(),
-        raw_html: task.response_raw.unwrap(),
+        raw_html: response_raw,
         chrome_extension_link: BLOCK_MESH_CHROME_EXTENSION_LINK.to_string(),
         app_server: BLOCK_MESH_APP_SERVER.to_string(),
         github: BLOCK_MESH_GITHUB.to_string(),
@@ -0,0 +1,27 @@
+use crate::database::two_factor::delete_two_factor::delete_two_factor;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::routes::two_factor::guard::check_two_factor_code;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::TwoFactorCodeRequest;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::StatusCode;
+use sqlx::PgPool;
+
+#[tracing::instrument(name = "two_factor_disable", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+    Json(body): Json<TwoFactorCodeRequest>,
+) -> Result<StatusCode, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let mut transaction = create_txn(&pool).await?;
+    if !check_two_factor_code(&mut transaction, &user.id, &user.email, &body.code).await? {
+        commit_txn(transaction).await?;
+        return Err(Error::TwoFactorRequired);
+    }
+    delete_two_factor(&mut transaction, &user.id).await?;
+    commit_txn(transaction).await?;
+    Ok(StatusCode::OK)
+}
@@ -0,0 +1,50 @@
+use crate::database::two_factor::enable_two_factor::enable_two_factor;
+use crate::database::two_factor::get_two_factor_secret::get_two_factor_secret;
+use crate::database::two_factor::replace_recovery_codes::replace_recovery_codes;
+use crate::domain::two_factor::{generate_recovery_codes, hash_recovery_code, totp, verify_totp};
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::routes::api_token::invalidate_token_caches::invalidate_token_caches;
+use crate::routes::two_factor::guard::mark_two_factor_verified;
+use crate::startup::application::AppState;
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_login::tower_sessions::Session;
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::{
+    TwoFactorCodeRequest, TwoFactorRecoveryCodesResponse,
+};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::PgPool;
+use std::sync::Arc;
+
+/// Confirms enrolment with a first code and hands out the recovery codes, they are not shown again.
+#[tracing::instrument(name = "two_factor_enable", skip_all)]
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Extension(pool): Extension<PgPool>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+    session: Session,
+    Json(body): Json<TwoFactorCodeRequest>,
+) -> Result<Json<TwoFactorRecoveryCodesResponse>, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let mut transaction = create_txn(&pool).await?;
+    let secret = get_two_factor_secret(&mut transaction, &user.id)
+        .await?
+        .filter(|secret| secret.enabled_at.is_none())
+        .ok_or_else(|| Error::BadRequest("Please start the setup again".to_string()))?;
+    let step = verify_totp(&totp(&secret.secret, &user.email)?, &body.code, None)
+        .ok_or_else(|| Error::BadRequest("Invalid code".to_string()))?;
+    enable_two_factor(&mut transaction, &user.id, step).await?;
+    let recovery_codes = generate_recovery_codes();
+    let hashes: Vec<String> = recovery_codes
+        .iter()
+        .map(|code| hash_recovery_code(code))
+        .collect();
+    replace_recovery_codes(&mut transaction, &user.id, &hashes).await?;
+    commit_txn(transaction).await?;
+    // Cached password logins would otherwise keep handing out the token without a code.
+    invalidate_token_caches(&state, &user.email).await;
+    mark_two_factor_verified(&session, &user.id).await?;
+    Ok(Json(TwoFactorRecoveryCodesResponse { recovery_codes }))
+}
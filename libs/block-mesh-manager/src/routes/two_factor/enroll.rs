@@ -0,0 +1,33 @@
+use crate::database::two_factor::upsert_two_factor_secret::upsert_two_factor_secret;
+use crate::domain::two_factor::{generate_totp_secret, qr_svg, totp};
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::TwoFactorEnrollResponse;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::PgPool;
+
+/// Stores a fresh secret that only takes effect once a code from it is confirmed.
+#[tracing::instrument(name = "two_factor_enroll", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+) -> Result<Json<TwoFactorEnrollResponse>, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let secret = generate_totp_secret();
+    let otpauth_url = totp(&secret, &user.email)?.get_url();
+    let mut transaction = create_txn(&pool).await?;
+    if !upsert_two_factor_secret(&mut transaction, &user.id, &secret).await? {
+        commit_txn(transaction).await?;
+        return Err(Error::BadRequest(
+            "Two factor authentication is already enabled".to_string(),
+        ));
+    }
+    commit_txn(transaction).await?;
+    Ok(Json(TwoFactorEnrollResponse {
+        qr_svg: qr_svg(&otpauth_url)?,
+        otpauth_url,
+        secret,
+    }))
+}
@@ -0,0 +1,20 @@
+use crate::database::two_factor::delete_remembered_devices::delete_remembered_devices;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use axum::Extension;
+use axum_login::AuthSession;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::StatusCode;
+use sqlx::PgPool;
+
+#[tracing::instrument(name = "two_factor_forget_devices", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+) -> Result<StatusCode, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let mut transaction = create_txn(&pool).await?;
+    delete_remembered_devices(&mut transaction, &user.id).await?;
+    commit_txn(transaction).await?;
+    Ok(StatusCode::OK)
+}
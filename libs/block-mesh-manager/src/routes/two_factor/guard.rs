@@ -0,0 +1,187 @@
+use crate::database::two_factor::get_two_factor_secret::get_two_factor_secret;
+use crate::database::two_factor::is_remembered_device::is_remembered_device;
+use crate::database::two_factor::record_two_factor_failure::record_two_factor_failure;
+use crate::database::two_factor::reset_two_factor_failures::reset_two_factor_failures;
+use crate::database::two_factor::update_two_factor_last_used_step::update_two_factor_last_used_step;
+use crate::database::two_factor::use_recovery_code::use_recovery_code;
+use crate::domain::two_factor::{
+    hash_recovery_code, hash_token, totp, verify_totp, REMEMBER_DEVICE_COOKIE,
+    TWO_FACTOR_FRESH_SECS,
+};
+use crate::errors::error::Error;
+use crate::middlewares::authentication::SessionUser;
+use axum_extra::extract::CookieJar;
+use axum_login::tower_sessions::Session;
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+const PENDING_LOGIN_KEY: &str = "two_factor_pending_login";
+const VERIFIED_KEY: &str = "two_factor_verified";
+pub(crate) const PENDING_LOGIN_SECS: i64 = 300;
+pub(crate) const MAX_LOGIN_ATTEMPTS: u8 = 5;
+/// Wrong codes per user, across sessions, before the second step is locked.
+const MAX_TWO_FACTOR_FAILURES: i32 = 5;
+const TWO_FACTOR_LOCKOUT_SECS: i64 = 900;
+
+/// A login that passed the password check and waits for the second step.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct PendingTwoFactorLogin {
+    pub user: SessionUser,
+    pub attempts: u8,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TwoFactorVerified {
+    user_id: Uuid,
+    at: i64,
+}
+
+/// Accepts a code from the authenticator app or an unused recovery code, `false` if 2FA is off.
+/// Wrong codes are counted on the user, so the caller has to commit on `false` as well.
+pub(crate) async fn check_two_factor_code(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    email: &str,
+    code: &str,
+) -> Result<bool, Error> {
+    let Some(secret) = get_two_factor_secret(transaction, user_id).await? else {
+        return Ok(false);
+    };
+    if secret.enabled_at.is_none() {
+        return Ok(false);
+    }
+    if secret.locked_until.is_some_and(|until| until > Utc::now()) {
+        return Err(Error::TwoFactorLocked);
+    }
+    let totp = totp(&secret.secret, email)?;
+    if let Some(step) = verify_totp(&totp, code, secret.last_used_step) {
+        if update_two_factor_last_used_step(transaction, user_id, step).await? {
+            return Ok(true);
+        }
+    } else if use_recovery_code(transaction, user_id, &hash_recovery_code(code)).await? {
+        reset_two_factor_failures(transaction, user_id).await?;
+        return Ok(true);
+    }
+    record_two_factor_failure(
+        transaction,
+        user_id,
+        MAX_TWO_FACTOR_FAILURES,
+        Utc::now() + Duration::seconds(TWO_FACTOR_LOCKOUT_SECS),
+    )
+    .await?;
+    Ok(false)
+}
+
+pub(crate) async fn is_two_factor_enabled(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+) -> anyhow::Result<bool> {
+    Ok(get_two_factor_secret(transaction, user_id)
+        .await?
+        .is_some_and(|secret| secret.enabled_at.is_some()))
+}
+
+pub(crate) async fn mark_two_factor_verified(
+    session: &Session,
+    user_id: &Uuid,
+) -> anyhow::Result<()> {
+    session
+        .insert(
+            VERIFIED_KEY,
+            TwoFactorVerified {
+                user_id: *user_id,
+                at: Utc::now().timestamp(),
+            },
+        )
+        .await?;
+    Ok(())
+}
+
+/// Sensitive actions need a code in the request, or one verified in this session a few minutes ago.
+/// A wrong code is counted in `transaction`, so the caller has to commit it on an error as well.
+pub(crate) async fn require_two_factor(
+    transaction: &mut Transaction<'_, Postgres>,
+    session: &Session,
+    user_id: &Uuid,
+    email: &str,
+    code: Option<&str>,
+) -> Result<(), Error> {
+    if !is_two_factor_enabled(transaction, user_id).await? {
+        return Ok(());
+    }
+    let verified = session
+        .get::<TwoFactorVerified>(VERIFIED_KEY)
+        .await
+        .map_err(anyhow::Error::from)?;
+    if verified.is_some_and(|verified| {
+        verified.user_id == *user_id && Utc::now().timestamp() - verified.at < TWO_FACTOR_FRESH_SECS
+    }) {
+        return Ok(());
+    }
+    let Some(code) = code.filter(|code| !code.trim().is_empty()) else {
+        return Err(Error::TwoFactorRequired);
+    };
+    if !check_two_factor_code(transaction, user_id, email, code).await? {
+        return Err(Error::TwoFactorRequired);
+    }
+    mark_two_factor_verified(session, user_id).await?;
+    Ok(())
+}
+
+/// Returns `true` when the login has to continue on the second step page instead of logging in.
+pub(crate) async fn start_two_factor_login(
+    transaction: &mut Transaction<'_, Postgres>,
+    session: &Session,
+    jar: &CookieJar,
+    user: &SessionUser,
+) -> anyhow::Result<bool> {
+    if !is_two_factor_enabled(transaction, &user.id).await? {
+        return Ok(false);
+    }
+    if let Some(cookie) = jar.get(REMEMBER_DEVICE_COOKIE) {
+        if is_remembered_device(transaction, &user.id, &hash_token(cookie.value())).await? {
+            return Ok(false);
+        }
+    }
+    // Repeating the password step keeps the attempts of the pending login for the same user.
+    let attempts = get_pending_login(session)
+        .await?
+        .filter(|pending| pending.user.id == user.id)
+        .map_or(0, |pending| pending.attempts);
+    session
+        .insert(
+            PENDING_LOGIN_KEY,
+            PendingTwoFactorLogin {
+                user: user.clone(),
+                attempts,
+                created_at: Utc::now().timestamp(),
+            },
+        )
+        .await?;
+    Ok(true)
+}
+
+pub(crate) async fn get_pending_login(
+    session: &Session,
+) -> anyhow::Result<Option<PendingTwoFactorLogin>> {
+    Ok(session
+        .get::<PendingTwoFactorLogin>(PENDING_LOGIN_KEY)
+        .await?
+        .filter(|pending| Utc::now().timestamp() - pending.created_at < PENDING_LOGIN_SECS))
+}
+
+pub(crate) async fn set_pending_login(
+    session: &Session,
+    pending: Option<PendingTwoFactorLogin>,
+) -> anyhow::Result<()> {
+    match pending {
+        Some(pending) => session.insert(PENDING_LOGIN_KEY, pending).await?,
+        None => {
+            session.remove_value(PENDING_LOGIN_KEY).await?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use crate::database::two_factor::create_remembered_device::create_remembered_device;
+use crate::domain::two_factor::{
+    generate_remember_token, hash_token, REMEMBER_DEVICE_COOKIE, REMEMBER_DEVICE_DAYS,
+};
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::routes::two_factor::guard::{
+    check_two_factor_code, get_pending_login, mark_two_factor_verified, set_pending_login,
+    MAX_LOGIN_ATTEMPTS,
+};
+use crate::utils::cache_envar::get_envar;
+use axum::response::Redirect;
+use axum::{Extension, Form};
+use axum_extra::extract::cookie::{Cookie, SameSite};
+use axum_extra::extract::CookieJar;
+use axum_login::tower_sessions::cookie::time::Duration;
+use axum_login::tower_sessions::Session;
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::TwoFactorLoginForm;
+use block_mesh_common::routes_enum::RoutesEnum;
+use block_mesh_manager_database_domain::domain::create_daily_stat::get_or_create_daily_stat;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::PgPool;
+
+#[tracing::instrument(name = "two_factor_login_post", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Extension(mut auth): Extension<AuthSession<Backend>>,
+    session: Session,
+    jar: CookieJar,
+    Form(form): Form<TwoFactorLoginForm>,
+) -> Result<(CookieJar, Redirect), Error> {
+    let Some(mut pending) = get_pending_login(&session).await? else {
+        return Ok((
+            jar,
+            Error::redirect(
+                400,
+                "Login expired",
+                "Please login again",
+                RoutesEnum::Static_UnAuth_Login.to_string().as_str(),
+            ),
+        ));
+    };
+    let user = pending.user.clone();
+    let mut transaction = create_txn(&pool).await?;
+    let valid =
+        match check_two_factor_code(&mut transaction, &user.id, &user.email, &form.code).await {
+            Err(Error::TwoFactorLocked) => {
+                set_pending_login(&session, None).await?;
+                return Ok((
+                    jar,
+                    Error::redirect(
+                        429,
+                        "Too many attempts",
+                        "Two factor is locked for a few minutes, please login again later",
+                        RoutesEnum::Static_UnAuth_Login.to_string().as_str(),
+                    ),
+                ));
+            }
+            result => result?,
+        };
+    if !valid {
+        commit_txn(transaction).await?;
+        pending.attempts += 1;
+        if pending.attempts >= MAX_LOGIN_ATTEMPTS {
+            set_pending_login(&session, None).await?;
+            return Ok((
+                jar,
+                Error::redirect(
+                    400,
+                    "Too many attempts",
+                    "Please login again",
+                    RoutesEnum::Static_UnAuth_Login.to_string().as_str(),
+                ),
+            ));
+        }
+        set_pending_login(&session, Some(pending)).await?;
+        return Ok((
+            jar,
+            Error::redirect(
+                400,
+                "Invalid code",
+                "Please try again with a new code or a recovery code",
+                RoutesEnum::Static_UnAuth_Login_Two_Factor
+                    .to_string()
+                    .as_str(),
+            ),
+        ));
+    }
+    set_pending_login(&session, None).await?;
+    if let Err(e) = auth.login(&user).await {
+        commit_txn(transaction).await?;
+        tracing::error!("Login failed: {:?} for user {}", e, user.id);
+        return Ok((
+            jar,
+            Error::redirect(
+                400,
+                "Login Failed",
+                "Login failed. Please try again.",
+                RoutesEnum::Static_UnAuth_Login.to_string().as_str(),
+            ),
+        ));
+    }
+    mark_two_factor_verified(&session, &user.id).await?;
+    let jar = if form.remember_device.is_some() {
+        let token = generate_remember_token();
+        create_remembered_device(
+            &mut transaction,
+            &user.id,
+            &hash_token(&token),
+            chrono::Utc::now() + chrono::Duration::days(REMEMBER_DEVICE_DAYS),
+        )
+        .await?;
+        let cookie = Cookie::build((REMEMBER_DEVICE_COOKIE, token))
+            .path("/")
+            .http_only(true)
+            .secure(get_envar("APP_ENVIRONMENT").await != "local")
+            .same_site(SameSite::Lax)
+            .max_age(Duration::days(REMEMBER_DEVICE_DAYS));
+        jar.add(cookie)
+    } else {
+        jar
+    };
+    let _ = get_or_create_daily_stat(&mut transaction, &user.id, None).await?;
+    commit_txn(transaction).await?;
+    Ok((jar, Redirect::to("/ui/dashboard")))
+}
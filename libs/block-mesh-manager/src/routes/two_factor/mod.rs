@@ -0,0 +1,10 @@
+pub mod disable;
+pub mod enable;
+pub mod enroll;
+pub mod forget_devices;
+pub mod guard;
+pub mod login_form;
+pub mod login_post;
+pub mod regenerate_recovery_codes;
+pub mod status;
+pub mod verify;
@@ -0,0 +1,35 @@
+use crate::database::two_factor::replace_recovery_codes::replace_recovery_codes;
+use crate::domain::two_factor::{generate_recovery_codes, hash_recovery_code};
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::routes::two_factor::guard::check_two_factor_code;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::{
+    TwoFactorCodeRequest, TwoFactorRecoveryCodesResponse,
+};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::PgPool;
+
+/// Replaces all recovery codes, the old ones stop working.
+#[tracing::instrument(name = "two_factor_regenerate_recovery_codes", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+    Json(body): Json<TwoFactorCodeRequest>,
+) -> Result<Json<TwoFactorRecoveryCodesResponse>, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let mut transaction = create_txn(&pool).await?;
+    if !check_two_factor_code(&mut transaction, &user.id, &user.email, &body.code).await? {
+        commit_txn(transaction).await?;
+        return Err(Error::TwoFactorRequired);
+    }
+    let recovery_codes = generate_recovery_codes();
+    let hashes: Vec<String> = recovery_codes
+        .iter()
+        .map(|code| hash_recovery_code(code))
+        .collect();
+    replace_recovery_codes(&mut transaction, &user.id, &hashes).await?;
+    commit_txn(transaction).await?;
+    Ok(Json(TwoFactorRecoveryCodesResponse { recovery_codes }))
+}
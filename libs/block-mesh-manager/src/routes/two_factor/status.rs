@@ -0,0 +1,20 @@
+use crate::database::two_factor::get_two_factor_status::get_two_factor_status;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::TwoFactorStatus;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::PgPool;
+
+#[tracing::instrument(name = "two_factor_status", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+) -> Result<Json<TwoFactorStatus>, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let mut transaction = create_txn(&pool).await?;
+    let status = get_two_factor_status(&mut transaction, &user.id).await?;
+    commit_txn(transaction).await?;
+    Ok(Json(status))
+}
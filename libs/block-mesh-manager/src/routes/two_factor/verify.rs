@@ -0,0 +1,29 @@
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::routes::two_factor::guard::{check_two_factor_code, mark_two_factor_verified};
+use axum::{Extension, Json};
+use axum_login::tower_sessions::Session;
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::TwoFactorCodeRequest;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::StatusCode;
+use sqlx::PgPool;
+
+/// Unlocks sensitive actions for a few minutes, for pages that cannot ask for a code inline.
+#[tracing::instrument(name = "two_factor_verify", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+    session: Session,
+    Json(body): Json<TwoFactorCodeRequest>,
+) -> Result<StatusCode, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let mut transaction = create_txn(&pool).await?;
+    if !check_two_factor_code(&mut transaction, &user.id, &user.email, &body.code).await? {
+        commit_txn(transaction).await?;
+        return Err(Error::TwoFactorRequired);
+    }
+    commit_txn(transaction).await?;
+    mark_two_factor_verified(&session, &user.id).await?;
+    Ok(StatusCode::OK)
+}
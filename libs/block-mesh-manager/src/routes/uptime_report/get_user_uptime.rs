@@ -2,25 +2,27 @@ use crate::database::uptime_report::get_user_latest_uptime::get_user_latest_upti
 use crate::errors::error::Error;
 use axum::extract::Query;
 use axum::{Extension, Json};
-use block_mesh_common::interfaces::server_api::{GetUserUptimeRequest, GetUserUptimeResponse};
-use block_mesh_manager_database_domain::domain::get_user_and_api_token::get_user_and_api_token_by_email;
-use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
-use http::StatusCode;
+use block_mesh_common::interfaces::server_api::{
+    GetUserUptimeRequest, GetUserUptimeResponse, TokenScope,
+};
+use block_mesh_manager_database_domain::domain::authenticate_token::{
+    authenticate_token, Credentials,
+};
+use database_utils::utils::instrument_wrapper::create_txn;
+use http::{HeaderMap, StatusCode};
 use sqlx::PgPool;
 
 #[tracing::instrument(name = "get_user_uptime", skip_all)]
 pub async fn handler(
+    headers: HeaderMap,
     Extension(pool): Extension<PgPool>,
     Query(query): Query<GetUserUptimeRequest>,
 ) -> Result<Json<GetUserUptimeResponse>, Error> {
     let mut transaction = create_txn(&pool).await?;
-    let user = get_user_and_api_token_by_email(&mut transaction, &query.email)
+    let credentials = Credentials::new(&headers, &query.email, &query.api_token);
+    let user = authenticate_token(&mut transaction, &credentials, TokenScope::DashboardRead)
         .await?
-        .ok_or_else(|| Error::UserNotFound)?;
-    if user.token.as_ref() != &query.api_token {
-        commit_txn(transaction).await?;
-        return Err(Error::ApiTokenNotFound);
-    }
+        .ok_or_else(|| Error::ApiTokenNotFound)?;
     let user_latest_uptime = get_user_latest_uptime(&mut transaction, user.user_id).await?;
     transaction.commit().await.map_err(Error::from)?;
     match user_latest_uptime {
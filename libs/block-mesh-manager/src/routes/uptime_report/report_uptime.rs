@@ -1,18 +1,24 @@
 use crate::errors::error::Error;
-use crate::middlewares::rate_limit::filter_request;
 use crate::startup::application::AppState;
 use crate::utils::cache_envar::get_envar;
 use anyhow::Context;
 use axum::extract::{Query, Request, State};
+use axum::response::{IntoResponse, Response};
 use axum::Json;
 use block_mesh_common::feature_flag_client::{get_flag_value_from_map, FlagValue};
 use block_mesh_common::interfaces::server_api::{
     HandlerMode, ReportUptimeRequest, ReportUptimeResponse,
 };
+use block_mesh_manager_database_domain::domain::authenticate_token::Credentials;
 use block_mesh_manager_database_domain::domain::report_uptime_content::report_uptime_content;
 use http::HeaderMap;
 use std::sync::Arc;
 
+/// Nodes read the limit from the body and treat any other status as a failed report.
+pub fn rate_limited() -> Response {
+    Json(ReportUptimeResponse { status_code: 429 }).into_response()
+}
+
 #[tracing::instrument(name = "report_uptime", skip_all)]
 pub async fn handler(
     headers: HeaderMap,
@@ -31,14 +37,7 @@ pub async fn handler(
         "127.0.0.1"
     }
     .to_string();
-    let mut redis = state.redis.clone();
-    if state.rate_limit {
-        let filter =
-            filter_request(&mut redis, &query.api_token, &header_ip, "report_uptime").await;
-        if filter.is_err() || !filter? {
-            return Ok(Json(ReportUptimeResponse { status_code: 429 }));
-        }
-    }
+    let credentials = Credentials::new(&headers, &query.email, &query.api_token);
 
     let polling_interval = get_flag_value_from_map(
         &state.flags,
@@ -53,7 +52,8 @@ pub async fn handler(
         &state.follower_pool,
         &state.channel_pool,
         header_ip,
-        query,
+        &credentials,
+        query.report_id,
         Some(request),
         HandlerMode::Http,
         polling_interval,
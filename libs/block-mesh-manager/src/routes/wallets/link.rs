@@ -0,0 +1,53 @@
+use crate::database::perks::add_perk_to_user::add_perk_to_user;
+use crate::database::user::update_user_wallet::update_user_wallet;
+use crate::database::wallet::claim_wallet_perk::claim_wallet_perk;
+use crate::database::wallet::create_user_wallet::create_user_wallet;
+use crate::database::wallet::create_wallet_change::create_wallet_change;
+use crate::database::wallet::get_wallet_owner::get_wallet_owner;
+use crate::domain::perk::PerkName;
+use crate::domain::wallet::WalletChange;
+use crate::errors::error::Error;
+use crate::startup::application::AppState;
+use block_mesh_common::interfaces::server_api::WalletChangeAction;
+use serde_json::json;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Links a verified wallet and makes it the primary one, each address earns the perk for one account only.
+pub(crate) async fn link_wallet(
+    transaction: &mut Transaction<'_, Postgres>,
+    state: &AppState,
+    user_id: &Uuid,
+    email: &str,
+    address: &str,
+) -> Result<(), Error> {
+    match get_wallet_owner(transaction, address).await? {
+        Some(owner) if owner != *user_id => {
+            return Err(Error::BadRequest(
+                "Wallet is already linked to another account".to_string(),
+            ));
+        }
+        Some(_) => {}
+        None => {
+            if !create_user_wallet(transaction, user_id, address).await? {
+                return Err(Error::BadRequest(
+                    "Wallet is already linked to another account".to_string(),
+                ));
+            }
+            create_wallet_change(
+                transaction,
+                user_id,
+                &WalletChange::new(WalletChangeAction::Linked, address).to_value(),
+            )
+            .await?;
+        }
+    }
+    if claim_wallet_perk(transaction, user_id, address).await? {
+        add_perk_to_user(transaction, *user_id, PerkName::Wallet, 1.1, 0.0, json!({})).await?;
+    }
+    update_user_wallet(transaction, *user_id, Some(address)).await?;
+    state
+        .wallet_addresses
+        .insert(email.to_string(), Some(address.to_string()));
+    Ok(())
+}
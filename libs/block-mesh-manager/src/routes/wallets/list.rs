@@ -0,0 +1,36 @@
+use crate::database::wallet::get_user_wallets::get_user_wallets;
+use crate::database::wallet::get_wallet_changes::get_wallet_changes;
+use crate::domain::wallet::WalletChange;
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::routes::wallets::WALLET_HISTORY_LIMIT;
+use axum::{Extension, Json};
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::UserWalletsResponse;
+use block_mesh_manager_database_domain::domain::get_user_opt_by_id::get_user_opt_by_id;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::PgPool;
+
+#[tracing::instrument(name = "list_wallets", skip_all)]
+pub async fn handler(
+    Extension(pool): Extension<PgPool>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+) -> Result<Json<UserWalletsResponse>, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let mut transaction = create_txn(&pool).await?;
+    let db_user = get_user_opt_by_id(&mut transaction, &user.id)
+        .await?
+        .ok_or(Error::UserNotFound)?;
+    let wallets = get_user_wallets(&mut transaction, &user.id).await?;
+    let history = get_wallet_changes(&mut transaction, &user.id, WALLET_HISTORY_LIMIT)
+        .await?
+        .into_iter()
+        .filter_map(|row| WalletChange::entry(row.value, row.created_at))
+        .collect();
+    commit_txn(transaction).await?;
+    Ok(Json(UserWalletsResponse {
+        primary: db_user.wallet_address,
+        wallets,
+        history,
+    }))
+}
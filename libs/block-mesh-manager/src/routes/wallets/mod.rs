@@ -0,0 +1,7 @@
+pub mod link;
+pub mod list;
+pub mod siws_message;
+pub mod unlink;
+
+/// How many wallet changes the wallets page shows.
+pub const WALLET_HISTORY_LIMIT: i64 = 50;
@@ -0,0 +1,20 @@
+use crate::errors::error::Error;
+use crate::startup::application::{AppState, ApplicationBaseUrl};
+use crate::utils::siws::issue_siws_message;
+use axum::extract::{Query, State};
+use axum::{Extension, Json};
+use block_mesh_common::interfaces::server_api::{SiwsMessageQuery, SiwsMessageResponse};
+use std::sync::Arc;
+
+#[tracing::instrument(name = "siws_message", skip_all)]
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Extension(base_url): Extension<ApplicationBaseUrl>,
+    Query(query): Query<SiwsMessageQuery>,
+) -> Result<Json<SiwsMessageResponse>, Error> {
+    let mut redis = state.redis.clone();
+    let message = issue_siws_message(&mut redis, &base_url, &query.address, query.purpose).await?;
+    Ok(Json(SiwsMessageResponse {
+        message: message.to_string(),
+    }))
+}
@@ -0,0 +1,79 @@
+use crate::database::perks::delete_perk_from_user::delete_perk_from_user;
+use crate::database::user::update_user_wallet::update_user_wallet;
+use crate::database::wallet::create_wallet_change::create_wallet_change;
+use crate::database::wallet::delete_user_wallet::delete_user_wallet;
+use crate::database::wallet::get_user_wallets::get_user_wallets;
+use crate::domain::perk::PerkName;
+use crate::domain::wallet::{is_wallet_account, WalletChange};
+use crate::errors::error::Error;
+use crate::middlewares::authentication::Backend;
+use crate::routes::two_factor::guard::require_two_factor;
+use crate::startup::application::AppState;
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_login::tower_sessions::Session;
+use axum_login::AuthSession;
+use block_mesh_common::interfaces::server_api::{UnlinkWalletRequest, WalletChangeAction};
+use block_mesh_manager_database_domain::domain::get_user_opt_by_id::get_user_opt_by_id;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::StatusCode;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+#[tracing::instrument(name = "unlink_wallet", skip_all)]
+pub async fn handler(
+    State(state): State<Arc<AppState>>,
+    Extension(pool): Extension<PgPool>,
+    Extension(auth): Extension<AuthSession<Backend>>,
+    session: Session,
+    Json(body): Json<UnlinkWalletRequest>,
+) -> Result<StatusCode, Error> {
+    let user = auth.user.ok_or(Error::UserNotFound)?;
+    let mut transaction = create_txn(&pool).await?;
+    if let Err(e) = require_two_factor(
+        &mut transaction,
+        &session,
+        &user.id,
+        &user.email,
+        body.totp_code.as_deref(),
+    )
+    .await
+    {
+        commit_txn(transaction).await?;
+        return Err(e);
+    }
+    let db_user = get_user_opt_by_id(&mut transaction, &user.id)
+        .await?
+        .ok_or(Error::UserNotFound)?;
+    let wallets = get_user_wallets(&mut transaction, &user.id).await?;
+    if !wallets.iter().any(|wallet| wallet.address == body.address) {
+        return Err(Error::BadRequest("Wallet is not linked".to_string()));
+    }
+    if wallets.len() == 1 && is_wallet_account(&db_user.email) {
+        return Err(Error::BadRequest(
+            "This account signs in with this wallet, link another one first".to_string(),
+        ));
+    }
+    delete_user_wallet(&mut transaction, &user.id, &body.address).await?;
+    create_wallet_change(
+        &mut transaction,
+        &user.id,
+        &WalletChange::new(WalletChangeAction::Unlinked, &body.address).to_value(),
+    )
+    .await?;
+    if wallets.len() == 1 {
+        delete_perk_from_user(&mut transaction, &user.id, PerkName::Wallet).await?;
+    }
+    if db_user.wallet_address.as_deref() == Some(body.address.as_str()) {
+        let primary = wallets
+            .iter()
+            .map(|wallet| wallet.address.as_str())
+            .find(|address| *address != body.address);
+        update_user_wallet(&mut transaction, user.id, primary).await?;
+        state
+            .wallet_addresses
+            .insert(user.email.clone(), primary.map(str::to_string));
+    }
+    commit_txn(transaction).await?;
+    Ok(StatusCode::OK)
+}
@@ -1,5 +1,6 @@
 use crate::configuration::settings::Settings;
 use crate::middlewares::authentication::{authentication_layer, Backend};
+use crate::middlewares::rate_limit::RateLimiter;
 use crate::routes::twitter::context::Oauth2Ctx;
 use crate::startup::routers::api_router::get_api_router;
 use crate::startup::routers::leptos_router::get_leptos_router;
@@ -23,7 +24,6 @@ use std::time::Duration;
 use tokio::net::TcpListener;
 
 use block_mesh_common::constants::DeviceType;
-use block_mesh_common::email_client::client::EmailClient;
 use block_mesh_common::env::app_env_var::AppEnvVar;
 use block_mesh_common::env::env_var;
 use block_mesh_common::env::get_env_var_or_panic::get_env_var_or_panic;
@@ -45,8 +45,6 @@ pub struct AppState {
     pub cf_secret_key: String,
     pub cf_site_key: String,
     pub task_limit: bool,
-    pub rate_limit: bool,
-    pub submit_bandwidth_limit: bool,
     pub get_token_map: GetTokenResponseMap,
     pub check_token_map: CheckTokenResponseMap,
     pub invite_codes: Arc<DashMap<String, String>>,
@@ -54,7 +52,6 @@ pub struct AppState {
     pub pool: PgPool,
     pub follower_pool: PgPool,
     pub channel_pool: PgPool,
-    pub email_client: Arc<EmailClient>,
     pub client: Client,
     pub flags: Arc<DashMap<String, FlagValue>>,
     pub redis: MultiplexedConnection,
@@ -108,6 +105,10 @@ impl Application {
         };
 
         let application_base_url = ApplicationBaseUrl(settings.application.base_url.clone());
+        let rate_limiter = Arc::new(RateLimiter::new(
+            settings.rate_limits.clone(),
+            app_state.redis.clone(),
+        ));
 
         let sentry_layer = env::var("SENTRY_LAYER")
             .unwrap_or("false".to_string())
@@ -133,6 +134,7 @@ impl Application {
 
         let backend = backend
             .layer(Extension(application_base_url))
+            .layer(Extension(rate_limiter))
             .layer(Extension(db_pool.clone()))
             .layer(cors)
             .layer(auth_layer.clone())
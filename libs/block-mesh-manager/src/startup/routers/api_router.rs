@@ -1,5 +1,10 @@
+use crate::middlewares::rate_limit::{
+    GET_TASK_POLICY, REPORT_UPTIME_POLICY, SUBMIT_BANDWIDTH_POLICY,
+};
+use crate::middlewares::rate_limit_layer::RateLimitLayer;
 use crate::routes;
 use crate::startup::application::AppState;
+use axum::handler::Handler;
 use axum::routing::{get, post};
 use axum::Router;
 use block_mesh_common::routes_enum::RoutesEnum;
@@ -13,19 +18,42 @@ pub fn get_api_router() -> Router<Arc<AppState>> {
         )
         .route(
             RoutesEnum::Api_ReportUptime.to_string().as_str(),
-            post(routes::uptime_report::report_uptime::handler),
+            post(
+                routes::uptime_report::report_uptime::handler.layer(
+                    RateLimitLayer::new(REPORT_UPTIME_POLICY)
+                        .with_limited_response(routes::uptime_report::report_uptime::rate_limited),
+                ),
+            ),
         )
         .route(
             RoutesEnum::Api_SubmitBandwidth.to_string().as_str(),
-            post(routes::bandwidth::submit_bandwidth::handler),
+            post(
+                routes::bandwidth::submit_bandwidth::handler.layer(
+                    RateLimitLayer::new(SUBMIT_BANDWIDTH_POLICY)
+                        .with_limited_response(routes::bandwidth::submit_bandwidth::rate_limited),
+                ),
+            ),
         )
         .route(
             RoutesEnum::Api_GetToken.to_string().as_str(),
             post(routes::api_token::get_token::handler),
         )
+        .route(
+            RoutesEnum::Api_DeviceCode.to_string().as_str(),
+            post(routes::device::create_code::handler),
+        )
+        .route(
+            RoutesEnum::Api_DeviceToken.to_string().as_str(),
+            post(routes::device::poll_token::handler),
+        )
         .route(
             RoutesEnum::Api_GetTask.to_string().as_str(),
-            post(routes::tasks::get_task::handler),
+            post(
+                routes::tasks::get_task::handler.layer(
+                    RateLimitLayer::new(GET_TASK_POLICY)
+                        .with_limited_response(routes::tasks::get_task::rate_limited),
+                ),
+            ),
         )
         .route(
             RoutesEnum::Api_SubmitTask.to_string().as_str(),
@@ -55,6 +83,65 @@ pub fn get_api_router() -> Router<Arc<AppState>> {
             RoutesEnum::Api_ReportsQueue.to_string().as_str(),
             get(routes::admin::reports_queue::get_stats::handler)
                 .post(routes::admin::reports_queue::change_settings::handler),
+        )
+        .route(
+            RoutesEnum::Api_RebuildAggregates.to_string().as_str(),
+            post(routes::admin::rebuild_aggregates::handler),
+        )
+        .route(
+            RoutesEnum::Api_CronJobs.to_string().as_str(),
+            get(routes::admin::cron_jobs::get_cron_jobs::handler)
+                .post(routes::admin::cron_jobs::update_cron_job::handler),
+        )
+        .route(
+            RoutesEnum::Api_InviteCampaigns.to_string().as_str(),
+            get(routes::admin::invite_campaigns::get_invite_campaigns::handler)
+                .post(routes::admin::invite_campaigns::create_invite_campaign::handler),
+        )
+        .route(
+            RoutesEnum::Api_InviteCampaignCodes.to_string().as_str(),
+            post(routes::admin::invite_campaigns::create_partner_invite_code::handler),
+        )
+        .route(
+            RoutesEnum::Api_PerkDefinitions.to_string().as_str(),
+            get(routes::admin::perk_definitions::get_perk_definitions::handler)
+                .post(routes::admin::perk_definitions::upsert_perk_definition::handler),
+        )
+        .route(
+            RoutesEnum::Api_Probes.to_string().as_str(),
+            post(routes::probes::list_probes::handler),
+        )
+        .route(
+            RoutesEnum::Api_CreateProbe.to_string().as_str(),
+            post(routes::probes::create_probe::handler),
+        )
+        .route(
+            RoutesEnum::Api_DeleteProbe.to_string().as_str(),
+            post(routes::probes::delete_probe::handler),
+        )
+        .route(
+            RoutesEnum::Api_ProbeSlo.to_string().as_str(),
+            post(routes::probes::probe_slo::handler),
+        )
+        .route(
+            RoutesEnum::Api_TaskRetention.to_string().as_str(),
+            post(routes::tasks::task_retention::handler),
+        )
+        .route(
+            RoutesEnum::Api_RegisterNodeKey.to_string().as_str(),
+            post(routes::node_keys::register_node_key::handler),
+        )
+        .route(
+            RoutesEnum::Api_NodeKeys.to_string().as_str(),
+            post(routes::node_keys::list_node_keys::handler),
+        )
+        .route(
+            RoutesEnum::Api_CreateEncryptedTask.to_string().as_str(),
+            post(routes::tasks::create_encrypted_task::handler),
+        )
+        .route(
+            RoutesEnum::Api_TaskResult.to_string().as_str(),
+            post(routes::tasks::task_result::handler),
         );
     api_router
 }
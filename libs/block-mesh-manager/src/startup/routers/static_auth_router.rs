@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
+use crate::middlewares::rate_limit::RESEND_CONFIRM_EMAIL_POLICY;
+use crate::middlewares::rate_limit_layer::RateLimitLayer;
 use crate::routes;
 use crate::startup::application::AppState;
+use axum::handler::Handler;
 use axum::routing::{get, post};
 use axum::Router;
 use block_mesh_common::routes_enum::RoutesEnum;
@@ -24,8 +27,10 @@ pub fn get_static_auth_router() -> Router<Arc<AppState>> {
             RoutesEnum::Static_Auth_ResendConfirmationEmail
                 .to_string()
                 .as_str(),
-            get(routes::emails::resend_confirm_email_form::handler)
-                .post(routes::emails::resend_confirm_email_post::handler),
+            get(routes::emails::resend_confirm_email_form::handler).post(
+                routes::emails::resend_confirm_email_post::handler
+                    .layer(RateLimitLayer::new(RESEND_CONFIRM_EMAIL_POLICY)),
+            ),
         )
         .route(
             RoutesEnum::Static_Auth_Call_To_Action.to_string().as_str(),
@@ -40,6 +45,112 @@ pub fn get_static_auth_router() -> Router<Arc<AppState>> {
         .route(
             RoutesEnum::Static_Auth_Dashboard.to_string().as_str(),
             post(routes::dashboard::post::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Feed_Analytics.to_string().as_str(),
+            post(routes::feed_analytics::post::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Api_Tokens.to_string().as_str(),
+            get(routes::api_token::list_tokens::handler)
+                .post(routes::api_token::create_token::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Api_Tokens_Revoke
+                .to_string()
+                .as_str(),
+            post(routes::api_token::revoke_token::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Api_Tokens_Rotate
+                .to_string()
+                .as_str(),
+            post(routes::api_token::rotate_token::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Device.to_string().as_str(),
+            get(routes::device::get_authorization::handler).post(routes::device::approve::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Two_Factor.to_string().as_str(),
+            get(routes::two_factor::status::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Two_Factor_Enroll
+                .to_string()
+                .as_str(),
+            post(routes::two_factor::enroll::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Two_Factor_Enable
+                .to_string()
+                .as_str(),
+            post(routes::two_factor::enable::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Two_Factor_Disable
+                .to_string()
+                .as_str(),
+            post(routes::two_factor::disable::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Two_Factor_Recovery_Codes
+                .to_string()
+                .as_str(),
+            post(routes::two_factor::regenerate_recovery_codes::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Two_Factor_Forget_Devices
+                .to_string()
+                .as_str(),
+            post(routes::two_factor::forget_devices::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Two_Factor_Verify
+                .to_string()
+                .as_str(),
+            post(routes::two_factor::verify::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Wallets.to_string().as_str(),
+            get(routes::wallets::list::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Wallets_Unlink.to_string().as_str(),
+            post(routes::wallets::unlink::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Notification_Preferences
+                .to_string()
+                .as_str(),
+            get(routes::emails::notification_preferences::handler)
+                .post(routes::emails::update_notification_preferences::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Account_Data.to_string().as_str(),
+            get(routes::account_data::get::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Account_Export.to_string().as_str(),
+            post(routes::account_data::request_export::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Account_Export_Download
+                .to_string()
+                .as_str(),
+            get(routes::account_data::download_export::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Account_Deletion
+                .to_string()
+                .as_str(),
+            post(routes::account_data::request_deletion::handler),
+        )
+        .route(
+            RoutesEnum::Static_Auth_Account_Deletion_Cancel
+                .to_string()
+                .as_str(),
+            post(routes::account_data::cancel_deletion::handler),
         );
     auth_router
 }
@@ -1,5 +1,8 @@
+use crate::middlewares::rate_limit::{REGISTER_POLICY, RESET_PASSWORD_POLICY, SIWS_MESSAGE_POLICY};
+use crate::middlewares::rate_limit_layer::RateLimitLayer;
 use crate::routes;
 use crate::startup::application::AppState;
+use axum::handler::Handler;
 use axum::routing::{get, post};
 use axum::Router;
 use block_mesh_common::routes_enum::RoutesEnum;
@@ -37,8 +40,10 @@ pub fn get_static_un_auth_router() -> Router<Arc<AppState>> {
         )
         .route(
             RoutesEnum::Static_UnAuth_ResetPassword.to_string().as_str(),
-            get(routes::password::reset_password_form::handler)
-                .post(routes::password::reset_password_post::handler),
+            get(routes::password::reset_password_form::handler).post(
+                routes::password::reset_password_post::handler
+                    .layer(RateLimitLayer::new(RESET_PASSWORD_POLICY)),
+            ),
         )
         .route(
             RoutesEnum::Static_UnAuth_NewPassword.to_string().as_str(),
@@ -62,14 +67,30 @@ pub fn get_static_un_auth_router() -> Router<Arc<AppState>> {
             get(routes::login::login_wallet::handler)
                 .post(routes::login::login_wallet_post::handler),
         )
+        .route(
+            RoutesEnum::Static_UnAuth_Login_Two_Factor
+                .to_string()
+                .as_str(),
+            get(routes::two_factor::login_form::handler)
+                .post(routes::two_factor::login_post::handler),
+        )
+        .route(
+            RoutesEnum::Static_UnAuth_Siws_Message.to_string().as_str(),
+            get(routes::wallets::siws_message::handler
+                .layer(RateLimitLayer::new(SIWS_MESSAGE_POLICY))),
+        )
         .route(
             RoutesEnum::Static_UnAuth_RegisterApi.to_string().as_str(),
-            post(routes::register::register_api::handler),
+            post(
+                routes::register::register_api::handler.layer(RateLimitLayer::new(REGISTER_POLICY)),
+            ),
         )
         .route(
             RoutesEnum::Static_UnAuth_Register.to_string().as_str(),
-            get(routes::register::register_form::handler)
-                .post(routes::register::register_post::handler),
+            get(routes::register::register_form::handler).post(
+                routes::register::register_post::handler
+                    .layer(RateLimitLayer::new(REGISTER_POLICY)),
+            ),
         )
         .route(
             RoutesEnum::Static_UnAuth_Register_Wallet
@@ -90,7 +111,12 @@ pub fn get_static_un_auth_router() -> Router<Arc<AppState>> {
         )
         .route(
             RoutesEnum::Static_UnAuth_Unsubscribe.to_string().as_str(),
-            get(routes::health_check::unsubscribe::unsubscribe),
+            get(routes::emails::unsubscribe::handler)
+                .post(routes::emails::unsubscribe::one_click_handler),
+        )
+        .route(
+            RoutesEnum::Static_UnAuth_Email_Events.to_string().as_str(),
+            post(routes::emails::email_events::handler),
         );
     un_auth_router
 }
@@ -1,4 +1,6 @@
 pub mod cache_envar;
 pub mod cftoken;
 pub mod points;
+pub mod siws;
+pub mod sns;
 pub mod verify_cache;
@@ -0,0 +1,98 @@
+use crate::errors::error::Error;
+use crate::startup::application::ApplicationBaseUrl;
+use block_mesh_common::siws::{SiwsMessage, SiwsPurpose, SIWS_TTL_SECS};
+use block_mesh_manager_database_domain::domain::nonce::Nonce;
+use chrono::Utc;
+use redis::aio::MultiplexedConnection;
+use redis::AsyncCommands;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::str::FromStr;
+use url::Url;
+
+fn siws_key(nonce: &str) -> String {
+    format!("siws_{nonce}")
+}
+
+fn siws_binding(purpose: SiwsPurpose, address: &str) -> String {
+    format!("{purpose}:{address}")
+}
+
+/// The host the user sees in the wallet prompt, with the port when it is not the default.
+fn siws_domain(base_url: &ApplicationBaseUrl) -> String {
+    Url::parse(base_url.as_str())
+        .ok()
+        .and_then(|url| {
+            let host = url.host_str()?.to_string();
+            Some(match url.port() {
+                Some(port) => format!("{host}:{port}"),
+                None => host,
+            })
+        })
+        .unwrap_or_else(|| base_url.as_str().to_string())
+}
+
+#[tracing::instrument(name = "issue_siws_message", skip_all)]
+pub async fn issue_siws_message(
+    redis: &mut MultiplexedConnection,
+    base_url: &ApplicationBaseUrl,
+    address: &str,
+    purpose: SiwsPurpose,
+) -> Result<SiwsMessage, Error> {
+    let address = Pubkey::from_str(address)
+        .map_err(|_| Error::BadRequest("Invalid wallet address".to_string()))?
+        .to_string();
+    let nonce = Nonce::generate_nonce(16);
+    let message = SiwsMessage::new(
+        &siws_domain(base_url),
+        base_url.as_str(),
+        &address,
+        purpose,
+        &nonce,
+        Utc::now(),
+    );
+    let _: () = redis
+        .set_ex(
+            siws_key(&nonce),
+            siws_binding(purpose, &address),
+            SIWS_TTL_SECS as u64,
+        )
+        .await?;
+    Ok(message)
+}
+
+/// Checks the message and its signature, then burns the nonce so the message works once.
+#[tracing::instrument(name = "verify_siws_message", skip_all)]
+pub async fn verify_siws_message(
+    redis: &mut MultiplexedConnection,
+    base_url: &ApplicationBaseUrl,
+    message: &str,
+    address: &str,
+    signature: &[u8],
+    purpose: SiwsPurpose,
+) -> Result<SiwsMessage, Error> {
+    let pubkey = Pubkey::from_str(address)
+        .map_err(|_| Error::BadRequest("Invalid wallet address".to_string()))?;
+    // Forms post textarea line breaks as CRLF, the wallet signed plain LF.
+    let message = message.replace("\r\n", "\n");
+    let siws = SiwsMessage::from_str(&message).map_err(|e| Error::Auth(e.to_string()))?;
+    siws.validate(
+        &siws_domain(base_url),
+        base_url.as_str(),
+        &pubkey.to_string(),
+        purpose,
+        Utc::now(),
+    )
+    .map_err(|e| Error::Auth(e.to_string()))?;
+    let signature = Signature::try_from(signature).map_err(|_| Error::SignatureMismatch)?;
+    if !signature.verify(pubkey.as_ref(), message.as_bytes()) {
+        return Err(Error::SignatureMismatch);
+    }
+    let binding: Option<String> = redis.get_del(siws_key(&siws.nonce)).await?;
+    if binding.as_deref() != Some(siws_binding(purpose, &siws.address).as_str()) {
+        return Err(Error::Auth(
+            "Message was already used or expired".to_string(),
+        ));
+    }
+    Ok(siws)
+}
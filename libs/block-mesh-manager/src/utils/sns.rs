@@ -0,0 +1,200 @@
+use crate::errors::error::Error;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use block_mesh_common::constants::DeviceType;
+use block_mesh_common::reqwest::http_client;
+use dashmap::try_result::TryResult::Present;
+use dashmap::DashMap;
+use ring::signature::{
+    UnparsedPublicKey, VerificationAlgorithm, RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY,
+    RSA_PKCS1_2048_8192_SHA256,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+use url::Url;
+use x509_parser::pem::parse_x509_pem;
+
+type CertMap = Arc<DashMap<String, Vec<u8>>>;
+
+static CACHE: OnceCell<CertMap> = OnceCell::const_new();
+
+#[tracing::instrument(name = "get_cache", skip_all)]
+pub async fn get_cache<'a>() -> &'a CertMap {
+    CACHE
+        .get_or_init(|| async { Arc::new(DashMap::new()) })
+        .await
+}
+
+/// The envelope SNS posts to HTTP subscribers.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct SnsMessage {
+    #[serde(rename = "Type")]
+    pub kind: String,
+    pub message_id: String,
+    pub topic_arn: String,
+    pub subject: Option<String>,
+    pub message: String,
+    pub timestamp: String,
+    pub token: Option<String>,
+    #[serde(rename = "SubscribeURL")]
+    pub subscribe_url: Option<String>,
+    pub signature_version: String,
+    pub signature: String,
+    #[serde(rename = "SigningCertURL")]
+    pub signing_cert_url: String,
+}
+
+impl SnsMessage {
+    /// The fields SNS signs, in its order, `None` for a type it does not document.
+    pub fn string_to_sign(&self) -> Option<String> {
+        let fields: Vec<(&str, Option<&str>)> = match self.kind.as_str() {
+            "Notification" => vec![
+                ("Message", Some(self.message.as_str())),
+                ("MessageId", Some(self.message_id.as_str())),
+                ("Subject", self.subject.as_deref()),
+                ("Timestamp", Some(self.timestamp.as_str())),
+                ("TopicArn", Some(self.topic_arn.as_str())),
+                ("Type", Some(self.kind.as_str())),
+            ],
+            "SubscriptionConfirmation" | "UnsubscribeConfirmation" => vec![
+                ("Message", Some(self.message.as_str())),
+                ("MessageId", Some(self.message_id.as_str())),
+                ("SubscribeURL", Some(self.subscribe_url.as_deref()?)),
+                ("Timestamp", Some(self.timestamp.as_str())),
+                ("Token", Some(self.token.as_deref()?)),
+                ("TopicArn", Some(self.topic_arn.as_str())),
+                ("Type", Some(self.kind.as_str())),
+            ],
+            _ => return None,
+        };
+        Some(
+            fields
+                .into_iter()
+                .filter_map(|(name, value)| value.map(|value| format!("{}\n{}\n", name, value)))
+                .collect(),
+        )
+    }
+
+    /// Checks the signature against the certificate SNS points at, which has to be served by
+    /// SNS itself. `topic_arn` pins the topic when set.
+    #[tracing::instrument(name = "sns_verify", skip_all)]
+    pub async fn verify(&self, topic_arn: &str) -> Result<(), Error> {
+        if !topic_arn.is_empty() && self.topic_arn != topic_arn {
+            return Err(Error::Unauthorized);
+        }
+        let algorithm: &'static dyn VerificationAlgorithm = match self.signature_version.as_str() {
+            "1" => &RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY,
+            "2" => &RSA_PKCS1_2048_8192_SHA256,
+            _ => return Err(Error::Unauthorized),
+        };
+        let string_to_sign = self.string_to_sign().ok_or(Error::Unauthorized)?;
+        let signature = STANDARD
+            .decode(&self.signature)
+            .map_err(|_| Error::Unauthorized)?;
+        let url = Url::parse(&self.signing_cert_url).map_err(|_| Error::Unauthorized)?;
+        if !is_signing_cert_url(&url) {
+            return Err(Error::Unauthorized);
+        }
+        let pem = signing_cert(url).await?;
+        let (_, pem) = parse_x509_pem(&pem).map_err(|_| Error::Unauthorized)?;
+        let cert = pem.parse_x509().map_err(|_| Error::Unauthorized)?;
+        if !cert.validity().is_valid() {
+            return Err(Error::Unauthorized);
+        }
+        UnparsedPublicKey::new(algorithm, &cert.public_key().subject_public_key.data)
+            .verify(string_to_sign.as_bytes(), &signature)
+            .map_err(|_| Error::Unauthorized)
+    }
+}
+
+/// SNS serves its certificates from `https://sns.<region>.amazonaws.com/*.pem`.
+pub fn is_signing_cert_url(url: &Url) -> bool {
+    url.scheme() == "https"
+        && url.port().is_none()
+        && url.host_str().is_some_and(|host| {
+            host.strip_prefix("sns.")
+                .and_then(|host| host.strip_suffix(".amazonaws.com"))
+                .is_some_and(|region| {
+                    !region.is_empty()
+                        && region
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+                })
+        })
+        && url.path().ends_with(".pem")
+}
+
+async fn signing_cert(url: Url) -> Result<Vec<u8>, Error> {
+    let cache = get_cache().await;
+    if let Present(entry) = cache.try_get(url.as_str()) {
+        return Ok(entry.value().clone());
+    }
+    let pem = http_client(DeviceType::AppServer)
+        .get(url.clone())
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?
+        .to_vec();
+    cache.insert(url.to_string(), pem.clone());
+    Ok(pem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(kind: &str, subject: Option<&str>) -> SnsMessage {
+        SnsMessage {
+            kind: kind.to_string(),
+            message_id: "id".to_string(),
+            topic_arn: "arn".to_string(),
+            subject: subject.map(str::to_string),
+            message: "body".to_string(),
+            timestamp: "ts".to_string(),
+            token: Some("token".to_string()),
+            subscribe_url: Some("https://sns".to_string()),
+            signature_version: "2".to_string(),
+            signature: String::new(),
+            signing_cert_url: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_string_to_sign() {
+        assert_eq!(
+            message("Notification", None).string_to_sign().unwrap(),
+            "Message\nbody\nMessageId\nid\nTimestamp\nts\nTopicArn\narn\nType\nNotification\n"
+        );
+        assert_eq!(
+            message("Notification", Some("s")).string_to_sign().unwrap(),
+            "Message\nbody\nMessageId\nid\nSubject\ns\nTimestamp\nts\nTopicArn\narn\nType\nNotification\n"
+        );
+        assert_eq!(
+            message("SubscriptionConfirmation", Some("s"))
+                .string_to_sign()
+                .unwrap(),
+            "Message\nbody\nMessageId\nid\nSubscribeURL\nhttps://sns\nTimestamp\nts\nToken\ntoken\nTopicArn\narn\nType\nSubscriptionConfirmation\n"
+        );
+        assert!(message("Other", None).string_to_sign().is_none());
+    }
+
+    #[test]
+    fn test_is_signing_cert_url() {
+        let check = |url: &str| is_signing_cert_url(&Url::parse(url).unwrap());
+        assert!(check(
+            "https://sns.us-east-1.amazonaws.com/SimpleNotificationService-abc.pem"
+        ));
+        assert!(!check(
+            "http://sns.us-east-1.amazonaws.com/SimpleNotificationService-abc.pem"
+        ));
+        assert!(!check("https://sns.us-east-1.amazonaws.com/cert.txt"));
+        assert!(!check("https://evil.s3.amazonaws.com/cert.pem"));
+        assert!(!check("https://sns.evil.com/.amazonaws.com/cert.pem"));
+        assert!(!check("https://sns.a.b.amazonaws.com.evil.com/cert.pem"));
+        assert!(!check("https://sns.x.amazonaws.com:8443/cert.pem"));
+    }
+}
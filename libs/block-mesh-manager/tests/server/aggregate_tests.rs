@@ -0,0 +1,137 @@
+use crate::server::test_app::{spawn_app, TestApp};
+use block_mesh_common::interfaces::server_api::{
+    RebuildAggregatesRequest, RebuildAggregatesResponse,
+};
+use block_mesh_common::routes_enum::RoutesEnum;
+use block_mesh_manager_database_domain::domain::bulk_get_or_create_aggregate_by_user_and_name::bulk_get_or_create_aggregate_by_user_and_name;
+use block_mesh_manager_database_domain::domain::rebuild_aggregates::rebuild_aggregates;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+async fn insert_event(app: &TestApp, user_id: &Uuid, kind: &str, value: f64, minutes_ago: i64) {
+    sqlx::query(
+        r#"
+        INSERT INTO aggregate_events (user_id, name, kind, value, idempotency_key, created_at)
+        VALUES ($1, 'Uptime', $2, $3, $4, $5)
+        "#,
+    )
+    .bind(user_id)
+    .bind(kind)
+    .bind(value)
+    .bind(Uuid::new_v4().to_string())
+    .bind(Utc::now() - Duration::minutes(minutes_ago))
+    .execute(&app.db_pool)
+    .await
+    .unwrap();
+}
+
+async fn uptime(app: &TestApp, user_id: &Uuid) -> f64 {
+    let value: serde_json::Value =
+        sqlx::query_scalar("SELECT value FROM aggregates WHERE user_id = $1 AND name = 'Uptime'")
+            .bind(user_id)
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+    value.as_f64().unwrap()
+}
+
+async fn user_with_aggregates(app: &TestApp) -> (String, String, Uuid) {
+    let (email, password) = app.create_user().await;
+    let user_id = app.user_id(&email).await;
+    let mut transaction = app.db_pool.begin().await.unwrap();
+    bulk_get_or_create_aggregate_by_user_and_name(&mut transaction, &user_id)
+        .await
+        .unwrap();
+    transaction.commit().await.unwrap();
+    (email, password, user_id)
+}
+
+#[tokio::test]
+async fn test_rebuild_folds_adds_after_last_set() {
+    let app = spawn_app().await;
+    let (_, _, user_id) = user_with_aggregates(&app).await;
+    insert_event(&app, &user_id, "Add", 100.0, 30).await;
+    insert_event(&app, &user_id, "Set", 10.0, 20).await;
+    insert_event(&app, &user_id, "Add", 2.0, 10).await;
+    insert_event(&app, &user_id, "Add", 3.0, 5).await;
+    let mut transaction = app.db_pool.begin().await.unwrap();
+    let rows = rebuild_aggregates(&mut transaction, Some(user_id), None)
+        .await
+        .unwrap();
+    transaction.commit().await.unwrap();
+    assert_eq!(rows, 1);
+    assert_eq!(uptime(&app, &user_id).await, 15.0);
+}
+
+#[tokio::test]
+async fn test_rebuild_without_set_sums_adds() {
+    let app = spawn_app().await;
+    let (_, _, user_id) = user_with_aggregates(&app).await;
+    insert_event(&app, &user_id, "Add", 4.0, 10).await;
+    insert_event(&app, &user_id, "Add", 6.0, 5).await;
+    let mut transaction = app.db_pool.begin().await.unwrap();
+    rebuild_aggregates(&mut transaction, Some(user_id), None)
+        .await
+        .unwrap();
+    transaction.commit().await.unwrap();
+    assert_eq!(uptime(&app, &user_id).await, 10.0);
+}
+
+#[tokio::test]
+async fn test_rebuild_averages_samples_after_last_set() {
+    let app = spawn_app().await;
+    let (_, _, user_id) = user_with_aggregates(&app).await;
+    insert_event(&app, &user_id, "Sample", 100.0, 30).await;
+    insert_event(&app, &user_id, "Set", 10.0, 20).await;
+    insert_event(&app, &user_id, "Sample", 20.0, 10).await;
+    insert_event(&app, &user_id, "Sample", 40.0, 5).await;
+    let mut transaction = app.db_pool.begin().await.unwrap();
+    rebuild_aggregates(&mut transaction, Some(user_id), None)
+        .await
+        .unwrap();
+    transaction.commit().await.unwrap();
+    // ((10 + 20) / 2 + 40) / 2
+    assert_eq!(uptime(&app, &user_id).await, 27.5);
+}
+
+#[tokio::test]
+async fn test_idempotency_key_is_unique() {
+    let app = spawn_app().await;
+    let (_, _, user_id) = user_with_aggregates(&app).await;
+    let query = r#"
+        INSERT INTO aggregate_events (user_id, name, kind, value, idempotency_key)
+        VALUES ($1, 'Uptime', 'Add', 1, 'same-key')
+        ON CONFLICT (idempotency_key) DO NOTHING
+        "#;
+    for expected in [1, 0] {
+        let r = sqlx::query(query)
+            .bind(user_id)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        assert_eq!(r.rows_affected(), expected);
+    }
+}
+
+#[tokio::test]
+async fn test_rebuild_aggregates_route() {
+    let app = spawn_app().await;
+    let (email, password, user_id) = user_with_aggregates(&app).await;
+    insert_event(&app, &user_id, "Set", 7.0, 5).await;
+    let url = format!("{}/api{}", app.address, RoutesEnum::Api_RebuildAggregates);
+    let body = RebuildAggregatesRequest {
+        user_id: Some(user_id),
+        name: Some("Uptime".to_string()),
+    };
+
+    let client = app.login_client(&email, &password).await;
+    let response = client.post(&url).json(&body).send().await.unwrap();
+    assert_eq!(401, response.status());
+
+    app.make_admin(&email).await;
+    let response = client.post(&url).json(&body).send().await.unwrap();
+    assert_eq!(200, response.status());
+    let response: RebuildAggregatesResponse = response.json().await.unwrap();
+    assert_eq!(response.rows_affected, 1);
+    assert_eq!(uptime(&app, &user_id).await, 7.0);
+}
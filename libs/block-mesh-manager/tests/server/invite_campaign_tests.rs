@@ -0,0 +1,151 @@
+use crate::server::test_app::{spawn_app, TestApp};
+use block_mesh_manager::database::invite_campaign::claim_invite_campaign_use::claim_invite_campaign_use;
+use block_mesh_manager::database::invite_code::create_campaign_invite_code::create_campaign_invite_code;
+use block_mesh_manager::database::invite_code::get_user_latest_invite_code::get_user_latest_invite_code;
+use block_mesh_manager::routes::invite_codes::redeem_invite_code::{
+    redeem_invite_code, InviteCodeRejection,
+};
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+async fn create_campaign(
+    app: &TestApp,
+    created_by: &Uuid,
+    starts_at: Option<DateTime<Utc>>,
+    ends_at: Option<DateTime<Utc>>,
+    max_uses: Option<i32>,
+) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO invite_campaigns (name, starts_at, ends_at, max_uses, perk_multiplier, perk_days, created_by)
+        VALUES ($1, $2, $3, $4, 1.5, 7, $5)
+        RETURNING id
+        "#,
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(starts_at)
+    .bind(ends_at)
+    .bind(max_uses)
+    .bind(created_by)
+    .fetch_one(&app.db_pool)
+    .await
+    .unwrap()
+}
+
+async fn user(app: &TestApp) -> Uuid {
+    let (email, _) = app.create_user().await;
+    app.user_id(&email).await
+}
+
+async fn claim(app: &TestApp, campaign_id: &Uuid) -> Option<i32> {
+    let mut transaction = app.db_pool.begin().await.unwrap();
+    let campaign = claim_invite_campaign_use(&mut transaction, campaign_id)
+        .await
+        .unwrap();
+    transaction.commit().await.unwrap();
+    campaign.map(|campaign| campaign.uses)
+}
+
+#[tokio::test]
+async fn test_campaign_use_stops_at_max_uses() {
+    let app = spawn_app().await;
+    let owner = user(&app).await;
+    let campaign_id = create_campaign(&app, &owner, None, None, Some(2)).await;
+    assert_eq!(claim(&app, &campaign_id).await, Some(1));
+    assert_eq!(claim(&app, &campaign_id).await, Some(2));
+    assert_eq!(claim(&app, &campaign_id).await, None);
+}
+
+#[tokio::test]
+async fn test_campaign_use_only_between_its_dates() {
+    let app = spawn_app().await;
+    let owner = user(&app).await;
+    let now = Utc::now();
+    let upcoming = create_campaign(&app, &owner, Some(now + Duration::days(1)), None, None).await;
+    let ended = create_campaign(&app, &owner, None, Some(now - Duration::days(1)), None).await;
+    let running = create_campaign(
+        &app,
+        &owner,
+        Some(now - Duration::days(1)),
+        Some(now + Duration::days(1)),
+        None,
+    )
+    .await;
+    assert_eq!(claim(&app, &upcoming).await, None);
+    assert_eq!(claim(&app, &ended).await, None);
+    assert_eq!(claim(&app, &running).await, Some(1));
+}
+
+#[tokio::test]
+async fn test_redeem_invite_code_rejections() {
+    let app = spawn_app().await;
+    let owner = user(&app).await;
+    let referee = user(&app).await;
+    let late = user(&app).await;
+    let campaign_id = create_campaign(&app, &owner, None, None, Some(1)).await;
+    let code = Uuid::new_v4().simple().to_string();
+    let mut transaction = app.db_pool.begin().await.unwrap();
+    create_campaign_invite_code(&mut transaction, &owner, &code, &campaign_id)
+        .await
+        .unwrap();
+
+    let missing = redeem_invite_code(&mut transaction, &referee, Uuid::new_v4().to_string())
+        .await
+        .unwrap();
+    assert!(matches!(missing, Err(InviteCodeRejection::NotFound)));
+
+    let redeemed = redeem_invite_code(&mut transaction, &referee, code.clone())
+        .await
+        .unwrap();
+    assert!(redeemed.is_ok());
+    let exhausted = redeem_invite_code(&mut transaction, &late, code)
+        .await
+        .unwrap();
+    assert!(matches!(exhausted, Err(InviteCodeRejection::Unavailable)));
+    transaction.commit().await.unwrap();
+
+    let invited_by: Option<Uuid> = sqlx::query_scalar("SELECT invited_by FROM users WHERE id = $1")
+        .bind(referee)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(invited_by, Some(owner));
+    let invited_by: Option<Uuid> = sqlx::query_scalar("SELECT invited_by FROM users WHERE id = $1")
+        .bind(late)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(invited_by, None);
+    let campaign_perks: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM perks WHERE user_id = $1 AND name = 'campaign'")
+            .bind(referee)
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap();
+    assert_eq!(campaign_perks, 1);
+}
+
+#[tokio::test]
+async fn test_campaign_code_is_not_the_latest_invite_code() {
+    let app = spawn_app().await;
+    let owner = user(&app).await;
+    let campaign_id = create_campaign(&app, &owner, None, None, None).await;
+    let mut transaction = app.db_pool.begin().await.unwrap();
+    let personal = get_user_latest_invite_code(&mut transaction, &owner)
+        .await
+        .unwrap();
+    create_campaign_invite_code(
+        &mut transaction,
+        &owner,
+        &Uuid::new_v4().simple().to_string(),
+        &campaign_id,
+    )
+    .await
+    .unwrap();
+    let latest = get_user_latest_invite_code(&mut transaction, &owner)
+        .await
+        .unwrap();
+    transaction.commit().await.unwrap();
+    assert_eq!(latest.invite_code, personal.invite_code);
+    assert_eq!(latest.campaign_id, None);
+}
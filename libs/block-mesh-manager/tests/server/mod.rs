@@ -1,4 +1,10 @@
+mod aggregate_tests;
 pub mod auth_tests;
+mod invite_campaign_tests;
+mod node_key_tests;
+mod rate_limit_tests;
 pub mod test_app;
 mod test_helpers;
+mod token_scope_tests;
+mod two_factor_tests;
 mod ws_tests;
@@ -0,0 +1,187 @@
+use crate::server::test_app::{spawn_app, TestApp};
+use block_mesh_common::interfaces::server_api::{
+    CreateEncryptedTaskRequest, CreateEncryptedTaskResponse, EncryptedTaskPayload, GetTaskRequest,
+    GetTaskResponse, GetTokenRequest, NodeKey, NodeKeysRequest, RegisterNodeKeyRequest,
+};
+use block_mesh_common::routes_enum::RoutesEnum;
+use block_mesh_common::task_crypto::{seal, TaskKeypair};
+use uuid::Uuid;
+
+async fn node(app: &TestApp) -> (String, Uuid) {
+    let (email, password) = app.create_user().await;
+    let api_token = app
+        .get_api_token(&GetTokenRequest {
+            email: email.clone(),
+            password,
+            totp_code: None,
+        })
+        .await
+        .unwrap()
+        .api_token
+        .unwrap();
+    (email, api_token)
+}
+
+async fn register_key(app: &TestApp, email: &str, api_token: &Uuid, keypair: &TaskKeypair) -> Uuid {
+    let response = app
+        .client
+        .post(format!(
+            "{}/api{}",
+            app.address,
+            RoutesEnum::Api_RegisterNodeKey
+        ))
+        .json(&RegisterNodeKeyRequest {
+            email: email.to_string(),
+            api_token: *api_token,
+            public_key: keypair.public_key(),
+        })
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(200, response.status());
+    response.json::<NodeKey>().await.unwrap().id
+}
+
+async fn create_task(
+    app: &TestApp,
+    email: &str,
+    api_token: &Uuid,
+    node_key_id: &Uuid,
+    node_public_key: &str,
+) -> reqwest::Response {
+    let payload = EncryptedTaskPayload {
+        url: "https://example.com".to_string(),
+        method: "GET".to_string(),
+        headers: None,
+        body: None,
+    };
+    let encrypted_payload = seal(node_public_key, &serde_json::to_vec(&payload).unwrap()).unwrap();
+    app.client
+        .post(format!(
+            "{}/api{}",
+            app.address,
+            RoutesEnum::Api_CreateEncryptedTask
+        ))
+        .json(&CreateEncryptedTaskRequest {
+            email: email.to_string(),
+            api_token: *api_token,
+            node_key_id: *node_key_id,
+            encrypted_payload,
+            reply_key: TaskKeypair::generate().public_key(),
+        })
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_node_keys_are_listed_for_their_owner_only() {
+    let app = spawn_app().await;
+    let (email, api_token) = node(&app).await;
+    let (other_email, other_api_token) = node(&app).await;
+    let key_id = register_key(&app, &email, &api_token, &TaskKeypair::generate()).await;
+    register_key(
+        &app,
+        &other_email,
+        &other_api_token,
+        &TaskKeypair::generate(),
+    )
+    .await;
+    let response = app
+        .client
+        .post(format!("{}/api{}", app.address, RoutesEnum::Api_NodeKeys))
+        .json(&NodeKeysRequest {
+            email,
+            api_token,
+            limit: None,
+        })
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(200, response.status());
+    let keys: Vec<NodeKey> = response.json().await.unwrap();
+    assert_eq!(keys.iter().map(|k| k.id).collect::<Vec<_>>(), vec![key_id]);
+}
+
+#[tokio::test]
+async fn test_encrypted_task_reaches_the_pinned_node() {
+    let app = spawn_app().await;
+    let (node_email, node_api_token) = node(&app).await;
+    let keypair = TaskKeypair::generate();
+    let key_id = register_key(&app, &node_email, &node_api_token, &keypair).await;
+    let response = create_task(
+        &app,
+        &node_email,
+        &node_api_token,
+        &key_id,
+        &keypair.public_key(),
+    )
+    .await;
+    assert_eq!(200, response.status());
+    let task_id = response
+        .json::<CreateEncryptedTaskResponse>()
+        .await
+        .unwrap()
+        .task_id;
+
+    let response = app
+        .client
+        .post(format!("{}/api{}", app.address, RoutesEnum::Api_GetTask))
+        .json(&GetTaskRequest {
+            email: node_email,
+            api_token: node_api_token,
+            node_key_id: Some(key_id),
+        })
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(200, response.status());
+    let task: GetTaskResponse = response
+        .json::<Option<GetTaskResponse>>()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(task.id, task_id);
+    let payload = keypair.open(&task.encrypted_payload.unwrap()).unwrap();
+    let payload: EncryptedTaskPayload = serde_json::from_slice(&payload).unwrap();
+    assert_eq!(payload.url, "https://example.com");
+}
+
+#[tokio::test]
+async fn test_encrypted_task_is_not_pinned_to_a_stale_key() {
+    let app = spawn_app().await;
+    let (node_email, node_api_token) = node(&app).await;
+    let keypair = TaskKeypair::generate();
+    let key_id = register_key(&app, &node_email, &node_api_token, &keypair).await;
+    sqlx::query("UPDATE node_keys SET updated_at = now() - interval '30 days' WHERE id = $1")
+        .bind(key_id)
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+    let response = create_task(
+        &app,
+        &node_email,
+        &node_api_token,
+        &key_id,
+        &keypair.public_key(),
+    )
+    .await;
+    assert!(!response.status().is_success());
+}
+
+#[tokio::test]
+async fn test_encrypted_task_is_not_pinned_to_another_users_node() {
+    let app = spawn_app().await;
+    let (node_email, node_api_token) = node(&app).await;
+    let (email, api_token) = node(&app).await;
+    let keypair = TaskKeypair::generate();
+    let key_id = register_key(&app, &node_email, &node_api_token, &keypair).await;
+    let response = create_task(&app, &email, &api_token, &key_id, &keypair.public_key()).await;
+    assert!(!response.status().is_success());
+    let tasks: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks WHERE node_key_id = $1")
+        .bind(key_id)
+        .fetch_one(&app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(tasks, 0);
+}
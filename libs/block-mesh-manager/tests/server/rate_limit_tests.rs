@@ -0,0 +1,100 @@
+use crate::server::test_app::spawn_app;
+use block_mesh_manager::configuration::rate_limit_settings::{
+    RateLimitAlgorithm, RateLimitKey, RateLimitPolicy, RateLimitSettings,
+};
+use block_mesh_manager::middlewares::rate_limit::RateLimiter;
+use redis::aio::MultiplexedConnection;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const POLICY: &str = "test";
+
+/// Two limiters over the same Redis, a decision only carries over through the Lua scripts.
+fn limiters(
+    redis: &MultiplexedConnection,
+    algorithm: RateLimitAlgorithm,
+) -> (RateLimiter, RateLimiter) {
+    let settings = RateLimitSettings {
+        redis_timeout_ms: 5_000,
+        trust_cloudflare_ip: false,
+        policies: HashMap::from([(
+            POLICY.to_string(),
+            RateLimitPolicy {
+                enabled: true,
+                keys: vec![RateLimitKey::Ip],
+                algorithm,
+            },
+        )]),
+    };
+    (
+        RateLimiter::new(settings.clone(), redis.clone()),
+        RateLimiter::new(settings, redis.clone()),
+    )
+}
+
+#[tokio::test]
+async fn test_token_bucket_script() {
+    let app = spawn_app().await;
+    let (first, second) = limiters(
+        &app.redis,
+        RateLimitAlgorithm::TokenBucket {
+            capacity: 2,
+            refill_per_sec: 0.01,
+        },
+    );
+    let ip = Uuid::new_v4().to_string();
+    let decision = first.check(POLICY, RateLimitKey::Ip, &ip).await.unwrap();
+    assert!(decision.allowed);
+    assert_eq!(decision.remaining, 1);
+    assert_eq!(decision.limit, 2);
+    assert!(decision.reset_secs >= 100);
+    assert!(
+        second
+            .check(POLICY, RateLimitKey::Ip, &ip)
+            .await
+            .unwrap()
+            .allowed
+    );
+    let decision = first.check(POLICY, RateLimitKey::Ip, &ip).await.unwrap();
+    assert!(!decision.allowed);
+    assert_eq!(decision.remaining, 0);
+    assert!(decision.retry_after_secs >= 1);
+    let other = Uuid::new_v4().to_string();
+    assert!(
+        second
+            .check(POLICY, RateLimitKey::Ip, &other)
+            .await
+            .unwrap()
+            .allowed
+    );
+    assert!(first.check(POLICY, RateLimitKey::User, &ip).await.is_none());
+}
+
+#[tokio::test]
+async fn test_sliding_window_script() {
+    let app = spawn_app().await;
+    let (first, second) = limiters(
+        &app.redis,
+        RateLimitAlgorithm::SlidingWindow {
+            limit: 2,
+            window_secs: 60,
+        },
+    );
+    let ip = Uuid::new_v4().to_string();
+    let decision = first.check(POLICY, RateLimitKey::Ip, &ip).await.unwrap();
+    assert!(decision.allowed);
+    assert_eq!(decision.remaining, 1);
+    assert_eq!(decision.window_secs, 60);
+    assert!(
+        second
+            .check(POLICY, RateLimitKey::Ip, &ip)
+            .await
+            .unwrap()
+            .allowed
+    );
+    let decision = second.check(POLICY, RateLimitKey::Ip, &ip).await.unwrap();
+    assert!(!decision.allowed);
+    assert_eq!(decision.remaining, 0);
+    assert!((1..=60).contains(&decision.retry_after_secs));
+    assert_eq!(decision.retry_after_secs, decision.reset_secs);
+}
@@ -1,12 +1,13 @@
+use crate::server::test_helpers::create_random_password;
 use block_mesh_common::constants::DeviceType;
-use block_mesh_common::email_client::client::EmailClient;
 use block_mesh_common::env::app_env_var::AppEnvVar;
 use block_mesh_common::env::env_var::EnvVar;
 use block_mesh_common::env::get_env_var_or_panic::get_env_var_or_panic;
 use block_mesh_common::env::load_dotenv::load_dotenv;
 use block_mesh_common::feature_flag_client::get_all_flags;
 use block_mesh_common::interfaces::server_api::{
-    CheckTokenResponseMap, GetTokenRequest, GetTokenResponse, GetTokenResponseMap, RegisterForm,
+    CheckTokenResponseMap, GetTokenRequest, GetTokenResponse, GetTokenResponseMap, LoginForm,
+    RegisterForm,
 };
 use block_mesh_common::routes_enum::RoutesEnum;
 use block_mesh_manager::configuration::get_configuration::get_configuration;
@@ -16,6 +17,9 @@ use block_mesh_manager::startup::get_connection_pool::get_connection_pool;
 use dashmap::DashMap;
 use database_utils::utils::connection::channel_pool::channel_pool;
 use database_utils::utils::migrate::migrate;
+use fake::faker::internet::raw::SafeEmail;
+use fake::locales::EN;
+use fake::Fake;
 use logger_general::tracing::setup_tracing_stdout_only;
 use redis;
 use redis::aio::MultiplexedConnection;
@@ -61,7 +65,6 @@ pub async fn spawn_app() -> TestApp {
     migrate(&db_pool, "test".to_string())
         .await
         .expect("Failed to migrate database");
-    let email_client = Arc::new(EmailClient::new(configuration.application.base_url.clone()).await);
     let client = ClientBuilder::new()
         .timeout(Duration::from_secs(3))
         .build()
@@ -85,11 +88,8 @@ pub async fn spawn_app() -> TestApp {
         cf_secret_key: "2".to_string(),
         invite_codes,
         wallet_addresses,
-        rate_limit: true,
         task_limit: true,
-        submit_bandwidth_limit: true,
         get_token_map,
-        email_client,
         pool: db_pool.clone(),
         follower_pool: db_pool.clone(),
         channel_pool,
@@ -157,4 +157,59 @@ impl TestApp {
         let response: GetTokenResponse = response.json::<GetTokenResponse>().await?;
         Ok(response)
     }
+
+    /// Registers a fresh user and returns its email and password.
+    pub async fn create_user(&self) -> (String, String) {
+        let email: String = SafeEmail(EN).fake();
+        let password = create_random_password();
+        self.register_post(&RegisterForm {
+            email: email.clone(),
+            password: password.clone(),
+            password_confirm: password.clone(),
+            invite_code: "123".to_string(),
+            cftoken: Option::from("test".to_string()),
+        })
+        .await
+        .unwrap();
+        (email.to_ascii_lowercase(), password)
+    }
+
+    pub async fn user_id(&self, email: &str) -> Uuid {
+        sqlx::query_scalar("SELECT id FROM users WHERE email = $1")
+            .bind(email)
+            .fetch_one(&self.db_pool)
+            .await
+            .unwrap()
+    }
+
+    pub async fn make_admin(&self, email: &str) {
+        sqlx::query("UPDATE users SET role = 'admin' WHERE email = $1")
+            .bind(email)
+            .execute(&self.db_pool)
+            .await
+            .unwrap();
+    }
+
+    /// A client holding the session cookie of the user, for the routes behind the web login.
+    pub async fn login_client(&self, email: &str, password: &str) -> reqwest::Client {
+        let client = ClientBuilder::new()
+            .timeout(Duration::from_secs(3))
+            .cookie_store(true)
+            .build()
+            .unwrap();
+        client
+            .post(format!(
+                "{}{}",
+                &self.address,
+                RoutesEnum::Static_UnAuth_Login
+            ))
+            .form(&LoginForm {
+                email: email.to_string(),
+                password: password.to_string(),
+            })
+            .send()
+            .await
+            .unwrap();
+        client
+    }
 }
@@ -0,0 +1,52 @@
+use crate::server::test_app::{spawn_app, TestApp};
+use block_mesh_common::interfaces::server_api::{TaskRetentionRequest, TokenScope};
+use block_mesh_common::routes_enum::RoutesEnum;
+use block_mesh_manager::database::api_token::create_named_api_token::create_named_api_token;
+use uuid::Uuid;
+
+async fn scoped_token(app: &TestApp, user_id: &Uuid, scope: TokenScope) -> Uuid {
+    let mut transaction = app.db_pool.begin().await.unwrap();
+    let (_, token) = create_named_api_token(
+        &mut transaction,
+        user_id,
+        &scope.to_string(),
+        &[scope],
+        None,
+    )
+    .await
+    .unwrap();
+    transaction.commit().await.unwrap();
+    token
+}
+
+async fn task_retention(app: &TestApp, email: &str, token: &Uuid) -> reqwest::Response {
+    app.client
+        .post(format!(
+            "{}/api{}",
+            app.address,
+            RoutesEnum::Api_TaskRetention
+        ))
+        .bearer_auth(token)
+        .json(&TaskRetentionRequest {
+            email: email.to_string(),
+            api_token: Uuid::nil(),
+            retention_days: None,
+        })
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_task_routes_require_task_submission_scope() {
+    let app = spawn_app().await;
+    let (email, _) = app.create_user().await;
+    let user_id = app.user_id(&email).await;
+    let dashboard = scoped_token(&app, &user_id, TokenScope::DashboardRead).await;
+    let tasks = scoped_token(&app, &user_id, TokenScope::TaskSubmission).await;
+    assert!(!task_retention(&app, &email, &dashboard)
+        .await
+        .status()
+        .is_success());
+    assert_eq!(200, task_retention(&app, &email, &tasks).await.status());
+}
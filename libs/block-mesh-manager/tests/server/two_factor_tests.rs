@@ -0,0 +1,82 @@
+use crate::server::test_app::{spawn_app, TestApp};
+use block_mesh_common::interfaces::server_api::{
+    GetTokenRequest, TwoFactorCodeRequest, TwoFactorEnrollResponse, TwoFactorRecoveryCodesResponse,
+};
+use block_mesh_common::routes_enum::RoutesEnum;
+use block_mesh_manager::domain::two_factor::totp;
+
+async fn get_token(
+    app: &TestApp,
+    email: &str,
+    password: &str,
+    totp_code: Option<&str>,
+) -> reqwest::Response {
+    app.client
+        .post(format!("{}/api{}", app.address, RoutesEnum::Api_GetToken))
+        .json(&GetTokenRequest {
+            email: email.to_string(),
+            password: password.to_string(),
+            totp_code: totp_code.map(str::to_string),
+        })
+        .send()
+        .await
+        .unwrap()
+}
+
+/// Goes through enrolment like the security page does and returns the recovery codes.
+async fn enable_two_factor(app: &TestApp, email: &str, password: &str) -> Vec<String> {
+    let client = app.login_client(email, password).await;
+    let enroll: TwoFactorEnrollResponse = client
+        .post(format!(
+            "{}{}",
+            app.address,
+            RoutesEnum::Static_Auth_Two_Factor_Enroll
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let code = totp(&enroll.secret, email)
+        .unwrap()
+        .generate_current()
+        .unwrap();
+    let response = client
+        .post(format!(
+            "{}{}",
+            app.address,
+            RoutesEnum::Static_Auth_Two_Factor_Enable
+        ))
+        .json(&TwoFactorCodeRequest { code })
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(200, response.status());
+    response
+        .json::<TwoFactorRecoveryCodesResponse>()
+        .await
+        .unwrap()
+        .recovery_codes
+}
+
+#[tokio::test]
+async fn test_get_token_requires_a_two_factor_code() {
+    let app = spawn_app().await;
+    let (email, password) = app.create_user().await;
+    // Cached before two factor is enabled, enabling it has to drop this answer.
+    let response = get_token(&app, &email, &password, None).await;
+    assert_eq!(200, response.status());
+
+    let recovery_codes = enable_two_factor(&app, &email, &password).await;
+    let response = get_token(&app, &email, &password, None).await;
+    assert_eq!(403, response.status());
+    let response = get_token(&app, &email, &password, Some("not-a-code")).await;
+    assert_eq!(403, response.status());
+
+    let response = get_token(&app, &email, &password, Some(&recovery_codes[0])).await;
+    assert_eq!(200, response.status());
+    // A recovery code is used up and the answer is not cached for the next caller.
+    let response = get_token(&app, &email, &password, Some(&recovery_codes[0])).await;
+    assert_eq!(403, response.status());
+}
@@ -26,6 +26,7 @@ async fn test_ws() -> (TestApp, reqwest_websocket::WebSocket) {
         .get_api_token(&GetTokenRequest {
             email: email.clone(),
             password: password.clone(),
+            totp_code: None,
         })
         .await
         .unwrap();
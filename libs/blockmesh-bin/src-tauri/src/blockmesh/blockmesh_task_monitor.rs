@@ -4,9 +4,11 @@ use block_mesh_common::interfaces::server_api::{
     GetTaskRequest, GetTaskResponse, ReportUptimeRequest, ReportUptimeResponse, RunTaskResponse,
     SubmitTaskRequest, SubmitTaskResponse,
 };
+use block_mesh_common::public_address::resolve_public_host;
 use chrono::Utc;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
-use reqwest::ClientBuilder;
+use reqwest::redirect::Policy;
+use reqwest::{ClientBuilder, Url};
 use serde_json::Value;
 use speed_test::metadata::fetch_metadata;
 use speed_test::Metadata;
@@ -28,6 +30,7 @@ pub async fn report_uptime(email: String, api_token: String) -> anyhow::Result<(
         } else {
             Some(metadata.ip)
         },
+        report_id: Some(Uuid::new_v4()),
     };
 
     if let Ok(response) = ClientBuilder::new()
@@ -53,6 +56,7 @@ pub async fn get_task(
     let body: GetTaskRequest = GetTaskRequest {
         email: email.to_string(),
         api_token: *api_token,
+        node_key_id: None,
     };
 
     let response: Option<GetTaskResponse> = ClientBuilder::new()
@@ -75,10 +79,21 @@ pub async fn run_task(
     headers: Option<Value>,
     body: Option<Value>,
 ) -> anyhow::Result<RunTaskResponse> {
+    let target = Url::parse(url)?;
+    let host = target
+        .host_str()
+        .ok_or_else(|| anyhow!("URL has no host"))?
+        .to_string();
+    let port = target
+        .port_or_known_default()
+        .ok_or_else(|| anyhow!("URL has no port"))?;
+    let resolved = host.clone();
+    let addrs = tokio::task::spawn_blocking(move || resolve_public_host(&resolved, port)).await??;
     let client = ClientBuilder::new()
         .timeout(Duration::from_secs(3))
-        .build()
-        .unwrap_or_default();
+        .resolve_to_addrs(&host, &addrs)
+        .redirect(Policy::none())
+        .build()?;
     let mut client = match method {
         "GET" => client.get(url),
         "POST" => match body {
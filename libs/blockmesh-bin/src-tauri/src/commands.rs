@@ -3,11 +3,14 @@ use crate::system_tray::set_dock_visible;
 use crate::tauri_state::{AppState, ChannelMessage};
 use crate::tauri_storage::set_config_with_path;
 use crate::CHANNEL_MSG_TX;
+use anyhow::anyhow;
 use block_mesh_common::app_config::{AppConfig, TaskStatus};
 use block_mesh_common::constants::{env_url, BLOCK_MESH_APP_SERVER};
 use block_mesh_common::interfaces::server_api::{
-    CheckTokenRequest, GetTokenResponse, LoginForm, RegisterForm, RegisterResponse,
+    CheckTokenRequest, DeviceCodeRequest, DeviceCodeResponse, DeviceTokenError, DeviceTokenRequest,
+    DeviceTokenResponse, GetTokenResponse, RegisterForm, RegisterResponse,
 };
+use block_mesh_common::routes_enum::RoutesEnum;
 use reqwest::ClientBuilder;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -130,44 +133,104 @@ pub async fn logout(state: State<'_, Arc<Mutex<AppState>>>) -> Result<(), Invoke
 }
 
 #[tauri::command]
-#[tracing::instrument(name = "login", skip(state, login_form), ret)]
-pub async fn login(
-    state: State<'_, Arc<Mutex<AppState>>>,
-    login_form: LoginForm,
-) -> Result<GetTokenResponse, InvokeError> {
-    let url = format!("{}/api/get_token", BLOCK_MESH_APP_SERVER);
+#[tracing::instrument(name = "request_device_code", ret)]
+pub async fn request_device_code() -> Result<DeviceCodeResponse, InvokeError> {
+    let url = format!(
+        "{}/api{}",
+        BLOCK_MESH_APP_SERVER,
+        RoutesEnum::Api_DeviceCode
+    );
     let client = ClientBuilder::new()
         .timeout(Duration::from_secs(3))
         .build()
         .unwrap_or_default();
-    let response: GetTokenResponse = client
+    let response: DeviceCodeResponse = client
         .post(&url)
-        .header("Content-Type", "application/json")
-        .json(&login_form)
+        .json(&DeviceCodeRequest {
+            device_name: whoami::devicename(),
+        })
         .send()
         .await
+        .and_then(|response| response.error_for_status())
         .map_err(|e| InvokeError::from(e.to_string()))?
         .json()
         .await
         .map_err(|e| InvokeError::from(e.to_string()))?;
+    Ok(response)
+}
+
+/// Waits until the code from `request_device_code` is approved on the dashboard.
+#[tauri::command]
+#[tracing::instrument(name = "login", skip(state, device_code), ret)]
+pub async fn login(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    device_code: DeviceCodeResponse,
+) -> Result<DeviceTokenResponse, InvokeError> {
+    let (email, api_token) = poll_device_token(&device_code)
+        .await
+        .map_err(|e| InvokeError::from(e.to_string()))?;
     let mut state = state.lock().await;
-    if let Some(api_token) = response.api_token {
-        state.config.email = Some(login_form.email.clone());
-        state.config.api_token = Some(api_token.to_string());
-        let config = state.config.clone();
-        set_config_with_path(config)
+    state.config.email = Some(email.clone());
+    state.config.api_token = Some(api_token.to_string());
+    let config = state.config.clone();
+    set_config_with_path(config)
+        .await
+        .ok_or(InvokeError::from("Error setting config"))?;
+    let _ = CHANNEL_MSG_TX
+        .get()
+        .unwrap()
+        .send(ChannelMessage::StartUptime);
+    let _ = CHANNEL_MSG_TX
+        .get()
+        .unwrap()
+        .send(ChannelMessage::StartTaskPull);
+    Ok(DeviceTokenResponse {
+        email: Some(email),
+        api_token: Some(api_token),
+        error: None,
+    })
+}
+
+async fn poll_device_token(device_code: &DeviceCodeResponse) -> anyhow::Result<(String, Uuid)> {
+    let url = format!(
+        "{}/api{}",
+        BLOCK_MESH_APP_SERVER,
+        RoutesEnum::Api_DeviceToken
+    );
+    let client = ClientBuilder::new()
+        .timeout(Duration::from_secs(3))
+        .build()
+        .unwrap_or_default();
+    let mut interval = device_code.interval;
+    loop {
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+        let response: DeviceTokenResponse = match client
+            .post(&url)
+            .json(&DeviceTokenRequest {
+                device_code: device_code.device_code,
+            })
+            .send()
             .await
-            .ok_or(InvokeError::from("Error setting config"))?;
-        let _ = CHANNEL_MSG_TX
-            .get()
-            .unwrap()
-            .send(ChannelMessage::StartUptime);
-        let _ = CHANNEL_MSG_TX
-            .get()
-            .unwrap()
-            .send(ChannelMessage::StartTaskPull);
+            .and_then(|response| response.error_for_status())
+        {
+            Ok(response) => response.json().await?,
+            Err(e) => {
+                tracing::debug!("Polling for the device token failed: {e}");
+                continue;
+            }
+        };
+        match (response.email, response.api_token, response.error) {
+            (Some(email), Some(api_token), _) => return Ok((email, api_token)),
+            (_, _, Some(DeviceTokenError::SlowDown)) => interval += 5,
+            (_, _, Some(DeviceTokenError::AccessDenied)) => {
+                return Err(anyhow!("Device login was denied"))
+            }
+            (_, _, Some(DeviceTokenError::ExpiredToken)) => {
+                return Err(anyhow!("Device code expired, please log in again"))
+            }
+            _ => {}
+        }
     }
-    Ok(response)
 }
 
 #[tauri::command]
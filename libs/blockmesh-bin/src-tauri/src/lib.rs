@@ -21,7 +21,7 @@ use crate::background::channel_receiver;
 use crate::commands::open_main_window;
 use crate::commands::{
     check_token, get_app_config, get_home_url, get_ore_status, get_task_status, login, logout,
-    register, set_app_config, toggle_miner,
+    register, request_device_code, set_app_config, toggle_miner,
 };
 use crate::run_events::on_run_events;
 use crate::system_tray::set_dock_visible;
@@ -120,6 +120,7 @@ pub fn run() -> anyhow::Result<ExitCode> {
         get_task_status,
         toggle_miner,
         get_ore_status,
+        request_device_code,
         login,
         register,
         check_token,
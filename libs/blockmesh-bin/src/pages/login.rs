@@ -1,47 +1,58 @@
 use crate::leptos_state::LeptosTauriAppState;
 use crate::tauri_connector::connector::{invoke_tauri, LoginArgs};
 use block_mesh_common::app_config::AppConfig;
-use block_mesh_common::interfaces::server_api::{GetTokenResponse, LoginForm};
+use block_mesh_common::interfaces::server_api::{DeviceCodeResponse, DeviceTokenResponse};
 use leptos::*;
 use leptos_router::A;
+use wasm_bindgen::JsValue;
 
 #[component]
 pub fn Login() -> impl IntoView {
     let state = expect_context::<LeptosTauriAppState>();
-    let (email, set_email) = create_signal(String::new());
-    let (password, set_password) = create_signal(String::new());
+    let (device_code, set_device_code) = create_signal(None::<DeviceCodeResponse>);
 
     let submit_action = create_action(move |_| async move {
-        let e = email.get_untracked();
-        let p = password.get_untracked();
-        let args: LoginArgs = LoginArgs {
-            login_form: LoginForm {
-                email: e.clone(),
-                password: p,
-            },
+        let code = match invoke_tauri("request_device_code", JsValue::NULL)
+            .await
+            .map_err(|e| e.message)
+            .and_then(|result| {
+                serde_wasm_bindgen::from_value::<DeviceCodeResponse>(result)
+                    .map_err(|e| e.to_string())
+            }) {
+            Ok(code) => code,
+            Err(e) => {
+                LeptosTauriAppState::set_error(e, state.error);
+                return;
+            }
         };
+        set_device_code.update(|v| *v = Some(code.clone()));
+        let args = LoginArgs { device_code: code };
         if let Ok(js_args) = serde_wasm_bindgen::to_value(&args) {
-            if let Ok(result) = invoke_tauri("login", js_args).await {
-                if let Ok(value) = serde_wasm_bindgen::from_value::<GetTokenResponse>(result) {
-                    let conf = state.app_config.get_untracked();
-                    state.app_config.update(|v| {
-                        *v = AppConfig {
-                            api_token: Some(value.api_token.unwrap_or_default().to_string()),
-                            email: Some(e),
-                            ..conf
-                        }
-                    });
-                    state.logged_in.update(|v| *v = true);
-                    LeptosTauriAppState::set_success("Successful login".to_string(), state.success);
+            match invoke_tauri("login", js_args).await {
+                Ok(result) => {
+                    if let Ok(value) = serde_wasm_bindgen::from_value::<DeviceTokenResponse>(result)
+                    {
+                        let conf = state.app_config.get_untracked();
+                        state.app_config.update(|v| {
+                            *v = AppConfig {
+                                api_token: Some(value.api_token.unwrap_or_default().to_string()),
+                                email: value.email,
+                                ..conf
+                            }
+                        });
+                        state.logged_in.update(|v| *v = true);
+                        LeptosTauriAppState::set_success(
+                            "Successful login".to_string(),
+                            state.success,
+                        );
+                    }
                 }
+                Err(e) => LeptosTauriAppState::set_error(e.message, state.error),
             }
         }
+        set_device_code.update(|v| *v = None);
     });
 
-    let on_submit_action = move || {
-        submit_action.dispatch(());
-    };
-
     view! {
         <div>
             <div class="bg-gray-700 flex justify-center items-center h-screen">
@@ -55,66 +66,46 @@ pub fn Login() -> impl IntoView {
                             Register
                         </A>
                     </div>
-                    <div class="mb-4">
-                        <label class="block text-white text-sm font-bold mb-2" for="email">
-                            Email
-                        </label>
-                        <input
-                            class="shadow appearance-none border rounded w-full py-2 px-3 text-gray-700 leading-tight focus:outline-none focus:shadow-outline"
-                            type="text"
-                            id="email"
-                            placeholder="Email"
-                            name="email"
-                            on:keyup=move |ev: ev::KeyboardEvent| {
-                                let val = event_target_value(&ev);
-                                set_email.update(|v| *v = val);
+                    <Show
+                        when=move || device_code.get().is_some()
+                        fallback=move || {
+                            view! {
+                                <div class="flex items-center justify-between">
+                                    <button
+                                        class="bg-blue-500 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded focus:outline-none focus:shadow-outline"
+                                        type="submit"
+                                        on:click=move |_| { submit_action.dispatch(()) }
+                                    >
+                                        Connect this device
+                                    </button>
+                                </div>
                             }
+                        }
+                    >
 
-                            on:change=move |ev| {
-                                let val = event_target_value(&ev);
-                                set_email.update(|v| *v = val);
-                            }
-                        />
-
-                    </div>
-                    <div class="mb-4">
-                        <label class="block text-white text-sm font-bold mb-2" for="password">
-                            Password
-                        </label>
-                        <input
-                            class="shadow appearance-none border rounded w-full py-2 px-3 text-gray-700 mb-3 leading-tight focus:outline-none focus:shadow-outline"
-                            type="password"
-                            id="password"
-                            name="password"
-                            placeholder="******************"
-                            on:keyup=move |ev: ev::KeyboardEvent| {
-                                match &*ev.key() {
-                                    "Enter" => {
-                                        on_submit_action();
+                        {move || {
+                            device_code
+                                .get()
+                                .map(|code| {
+                                    view! {
+                                        <p class="text-white text-sm mb-2">
+                                            Open the link below and enter the code
+                                        </p>
+                                        <p class="text-white text-2xl font-bold text-center mb-2">
+                                            {code.user_code}
+                                        </p>
+                                        <a
+                                            class="text-blue-500 hover:text-blue-800 text-sm break-all"
+                                            href=code.verification_uri_complete
+                                            target="_blank"
+                                        >
+                                            {code.verification_uri}
+                                        </a>
                                     }
-                                    _ => {
-                                        let val = event_target_value(&ev);
-                                        set_password.update(|p| *p = val);
-                                    }
-                                }
-                            }
-
-                            on:change=move |ev| {
-                                let val = event_target_value(&ev);
-                                set_password.update(|p| *p = val);
-                            }
-                        />
+                                })
+                        }}
 
-                    </div>
-                    <div class="flex items-center justify-between">
-                        <button
-                            class="bg-blue-500 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded focus:outline-none focus:shadow-outline"
-                            type="submit"
-                            on:click=move |_| { on_submit_action() }
-                        >
-                            Submit
-                        </button>
-                    </div>
+                    </Show>
                 </div>
             </div>
         </div>
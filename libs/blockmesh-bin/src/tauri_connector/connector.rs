@@ -1,6 +1,6 @@
 use block_mesh_common::app_config::{AppConfig, TaskStatus};
 use block_mesh_common::chrome_storage::{MessageKey, MessageType, MessageValue, PostMessage};
-use block_mesh_common::interfaces::server_api::{LoginForm, RegisterForm};
+use block_mesh_common::interfaces::server_api::{DeviceCodeResponse, RegisterForm};
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 use wasm_bindgen::closure::Closure;
@@ -112,7 +112,7 @@ pub struct ToggleMinerArgs {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct LoginArgs {
-    pub login_form: LoginForm,
+    pub device_code: DeviceCodeResponse,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
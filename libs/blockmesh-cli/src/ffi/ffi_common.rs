@@ -126,7 +126,7 @@ pub fn run_login_mode(url: &str, email: &str, password: &str) {
         let url_s = url.to_string();
         let task = tokio::spawn(async move {
             set_status(FFIStatus::RUNNING);
-            login_mode(&url, &email, &password, Some("Mobile".to_string())).await
+            login_mode(&url, &email, &password, None, Some("Mobile".to_string())).await
         });
         let cancel_future = tokio::spawn(async move {
             notify.notified().await;
@@ -2,15 +2,20 @@ use anyhow::{anyhow, Context};
 use block_mesh_common::constants::{DeviceType, BLOCKMESH_VPS};
 use block_mesh_common::feature_flag_client::get_flag_value;
 use block_mesh_common::interfaces::server_api::{
-    ClientsMetadata, DashboardRequest, DashboardResponse, GetTaskRequest, GetTaskResponse,
-    OptCreds, RegisterForm, RegisterResponse, ReportBandwidthRequest, ReportBandwidthResponse,
+    CheckTokenRequest, ClientsMetadata, DashboardRequest, DashboardResponse, DeviceCodeRequest,
+    DeviceCodeResponse, DeviceTokenError, DeviceTokenRequest, DeviceTokenResponse,
+    EncryptedTaskPayload, GetTaskRequest, GetTaskResponse, NodeKey, OptCreds, RegisterForm,
+    RegisterNodeKeyRequest, RegisterResponse, ReportBandwidthRequest, ReportBandwidthResponse,
     ReportUptimeRequest, ReportUptimeResponse, RunTaskResponse, SubmitTaskRequest,
     SubmitTaskResponse, VpsResp,
 };
-use block_mesh_common::interfaces::server_api::{GetTokenResponse, LoginForm};
-use block_mesh_common::reqwest::http_client;
+use block_mesh_common::interfaces::server_api::{GetTokenRequest, GetTokenResponse};
+use block_mesh_common::reqwest::{http_client, public_http_client};
 use block_mesh_common::routes_enum::RoutesEnum;
+use block_mesh_common::task_crypto::{seal, TaskKeypair};
+use once_cell::sync::OnceCell;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use reqwest::{StatusCode, Url};
 use serde_json::Value;
 use speed_test::download::test_download;
 use speed_test::latency::test_latency;
@@ -18,10 +23,74 @@ use speed_test::metadata::fetch_metadata;
 use speed_test::upload::test_upload;
 use speed_test::Metadata;
 use std::cmp;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 use tracing::Level;
 use uuid::Uuid;
 
+const DEFAULT_NODE_KEY_FILE: &str = "blockmesh-node.key";
+static NODE_KEY_FILE: OnceCell<PathBuf> = OnceCell::new();
+static NODE_KEYPAIR: OnceCell<TaskKeypair> = OnceCell::new();
+static NODE_KEY_ID: OnceCell<Uuid> = OnceCell::new();
+
+/// Must be called before the keypair is first used, later calls are ignored.
+pub fn set_node_key_file(path: PathBuf) {
+    let _ = NODE_KEY_FILE.set(path);
+}
+
+/// The keypair is kept on disk so tasks sealed to it survive restarts.
+pub fn node_keypair() -> &'static TaskKeypair {
+    NODE_KEYPAIR.get_or_init(|| {
+        let path = NODE_KEY_FILE.get_or_init(default_node_key_file);
+        load_or_create_keypair(path).unwrap_or_else(|e| {
+            warn!(
+                "Failed to persist node key to {}, using a temporary key: {e}",
+                path.display()
+            );
+            TaskKeypair::generate()
+        })
+    })
+}
+
+/// Falls back to the current directory on platforms without a config dir.
+fn default_node_key_file() -> PathBuf {
+    dirs::config_dir()
+        .map(|dir| dir.join("blockmesh"))
+        .unwrap_or_default()
+        .join(DEFAULT_NODE_KEY_FILE)
+}
+
+fn load_or_create_keypair(path: &Path) -> anyhow::Result<TaskKeypair> {
+    if let Ok(secret_key) = fs::read_to_string(path) {
+        return TaskKeypair::from_secret_key(secret_key.trim());
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let keypair = TaskKeypair::generate();
+    write_private_file(path, &keypair.secret_key())?;
+    Ok(keypair)
+}
+
+/// Writes a file only the current user can read.
+pub fn write_private_file(path: &Path, content: &str) -> anyhow::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(path)?;
+    // `mode` only applies when the file is created, older files may still be world-readable.
+    #[cfg(unix)]
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
 #[allow(dead_code)]
 pub async fn is_vps() -> anyhow::Result<VpsResp> {
     let client = http_client(DeviceType::Cli);
@@ -87,8 +156,9 @@ pub async fn register(url: &str, credentials: &RegisterForm) -> anyhow::Result<(
 }
 
 #[allow(dead_code)]
-pub async fn login_to_network(url: &str, login_form: LoginForm) -> anyhow::Result<Uuid> {
-    let url = if url.contains("app") {
+pub async fn login_to_network(url: &str, login_form: GetTokenRequest) -> anyhow::Result<Uuid> {
+    // Only the app server checks two factor codes, the api service reads from a follower.
+    let url = if url.contains("app") && login_form.totp_code.is_none() {
         url.replace("app", "api")
     } else {
         url.to_string()
@@ -104,15 +174,19 @@ pub async fn login_to_network(url: &str, login_form: LoginForm) -> anyhow::Resul
         api_token: None,
     };
     let client = http_client(DeviceType::Cli);
-    let response: GetTokenResponse = client
+    let response = client
         .post(&url)
         .query(&query)
         .header(CONTENT_TYPE, "application/json")
         .json(&login_form)
         .send()
-        .await?
-        .json()
         .await?;
+    if response.status() == StatusCode::FORBIDDEN {
+        return Err(anyhow!(
+            "Two factor is enabled, pass --totp-code or log in with a device code"
+        ));
+    }
+    let response: GetTokenResponse = response.json().await?;
     match response.api_token {
         Some(api_token) => {
             info!("Login successful");
@@ -125,6 +199,124 @@ pub async fn login_to_network(url: &str, login_form: LoginForm) -> anyhow::Resul
     }
 }
 
+#[tracing::instrument(name = "check_token", skip(api_token), err)]
+pub async fn check_token(
+    url: &str,
+    email: &str,
+    api_token: &Uuid,
+) -> anyhow::Result<GetTokenResponse> {
+    let body = CheckTokenRequest {
+        email: email.to_string(),
+        api_token: *api_token,
+    };
+    let response = http_client(DeviceType::Cli)
+        .post(format!(
+            "{}/{}/api{}",
+            url,
+            DeviceType::Cli,
+            RoutesEnum::Api_CheckToken
+        ))
+        .json(&body)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(response)
+}
+
+/// Registers this node's key and remembers its id, so sealed tasks are only fetched by this node.
+#[tracing::instrument(name = "register_node_key", skip(api_token), err)]
+pub async fn register_node_key(url: &str, email: &str, api_token: &Uuid) -> anyhow::Result<Uuid> {
+    let body = RegisterNodeKeyRequest {
+        email: email.to_string(),
+        api_token: *api_token,
+        public_key: node_keypair().public_key(),
+    };
+    let response: NodeKey = http_client(DeviceType::Cli)
+        .post(format!(
+            "{}/{}/api{}",
+            url,
+            DeviceType::Cli,
+            RoutesEnum::Api_RegisterNodeKey
+        ))
+        .bearer_auth(api_token)
+        .json(&body)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let _ = NODE_KEY_ID.set(response.id);
+    Ok(response.id)
+}
+
+#[tracing::instrument(name = "request_device_code", err)]
+pub async fn request_device_code(
+    url: &str,
+    device_name: &str,
+) -> anyhow::Result<DeviceCodeResponse> {
+    let response: DeviceCodeResponse = http_client(DeviceType::Cli)
+        .post(format!(
+            "{}/{}/api{}",
+            url,
+            DeviceType::Cli,
+            RoutesEnum::Api_DeviceCode
+        ))
+        .json(&DeviceCodeRequest {
+            device_name: device_name.to_string(),
+        })
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(response)
+}
+
+/// Polls until the code is approved, returning the email and the device token.
+#[tracing::instrument(name = "poll_device_token", skip_all, err)]
+pub async fn poll_device_token(
+    url: &str,
+    device_code: &DeviceCodeResponse,
+) -> anyhow::Result<(String, Uuid)> {
+    let mut interval = device_code.interval;
+    loop {
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+        let response: DeviceTokenResponse = match http_client(DeviceType::Cli)
+            .post(format!(
+                "{}/{}/api{}",
+                url,
+                DeviceType::Cli,
+                RoutesEnum::Api_DeviceToken
+            ))
+            .json(&DeviceTokenRequest {
+                device_code: device_code.device_code,
+            })
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+        {
+            Ok(response) => response.json().await?,
+            Err(e) => {
+                debug!("Polling for the device token failed: {e}");
+                continue;
+            }
+        };
+        match (response.email, response.api_token, response.error) {
+            (Some(email), Some(api_token), _) => return Ok((email, api_token)),
+            (_, _, Some(DeviceTokenError::SlowDown)) => interval += 5,
+            (_, _, Some(DeviceTokenError::AccessDenied)) => {
+                return Err(anyhow!("Device login was denied"))
+            }
+            (_, _, Some(DeviceTokenError::ExpiredToken)) => {
+                return Err(anyhow!("Device code expired, please run the login again"))
+            }
+            _ => {}
+        }
+    }
+}
+
 #[tracing::instrument(name = "report_uptime", skip(api_token), err(level = Level::TRACE))]
 pub async fn report_uptime(
     url: &str,
@@ -139,6 +331,7 @@ pub async fn report_uptime(
         email: email.to_string(),
         api_token,
         ip: Some(cloudflare_metadata.ip).filter(|ip| !ip.is_empty()),
+        report_id: Some(Uuid::new_v4()),
     };
 
     let url = format!(
@@ -150,6 +343,7 @@ pub async fn report_uptime(
     info!("Reporting uptime on {}", &url);
     if let Ok(response) = http_client(DeviceType::Cli)
         .post(url)
+        .bearer_auth(api_token)
         .query(&query)
         .json(&session_metadata)
         .send()
@@ -175,6 +369,7 @@ pub async fn get_task(
     let body: GetTaskRequest = GetTaskRequest {
         email: email.to_string(),
         api_token: *api_token,
+        node_key_id: NODE_KEY_ID.get().copied(),
     };
 
     let response: Option<GetTaskResponse> = http_client(DeviceType::Cli)
@@ -182,8 +377,9 @@ pub async fn get_task(
             "{}/{}/api{}",
             base_url,
             DeviceType::Cli,
-            RoutesEnum::Api_GetToken
+            RoutesEnum::Api_GetTask
         ))
+        .bearer_auth(api_token)
         .json(&body)
         .send()
         .await?
@@ -199,7 +395,9 @@ pub async fn run_task(
     headers: Option<Value>,
     body: Option<Value>,
 ) -> anyhow::Result<RunTaskResponse> {
-    let client = http_client(DeviceType::Cli);
+    let target = Url::parse(url)?;
+    let client =
+        tokio::task::spawn_blocking(move || public_http_client(DeviceType::Cli, &target)).await??;
     let mut client = match method {
         "GET" => client.get(url),
         "POST" => match body {
@@ -238,6 +436,33 @@ pub async fn run_task(
     }
 }
 
+#[tracing::instrument(name = "run_assigned_task", skip_all)]
+pub async fn run_assigned_task(task: &GetTaskResponse) -> anyhow::Result<RunTaskResponse> {
+    match &task.encrypted_payload {
+        Some(envelope) => {
+            let payload: EncryptedTaskPayload =
+                serde_json::from_slice(&node_keypair().open(envelope)?)?;
+            run_task(&payload.url, &payload.method, payload.headers, payload.body).await
+        }
+        None => {
+            run_task(
+                &task.url,
+                &task.method,
+                task.headers.clone(),
+                task.body.clone(),
+            )
+            .await
+        }
+    }
+}
+
+pub fn seal_task_response(task: &GetTaskResponse, raw: String) -> anyhow::Result<String> {
+    match &task.reply_key {
+        Some(reply_key) => seal(reply_key, raw.as_bytes()),
+        None => Ok(raw),
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 #[tracing::instrument(name = "submit_task", skip(api_token, response_raw), err(level = Level::TRACE))]
 pub async fn submit_task(
@@ -276,6 +501,7 @@ pub async fn submit_task(
             DeviceType::Cli,
             RoutesEnum::Api_SubmitTask
         ))
+        .bearer_auth(api_token)
         .query(&query)
         .body(response_raw)
         .send()
@@ -293,17 +519,18 @@ pub async fn task_poller(url: &str, email: &str, api_token: &str) -> anyhow::Res
     let task = task.context("Task not found")?;
 
     let task_start = std::time::Instant::now();
-    let finished_task = match run_task(&task.url, &task.method, task.headers, task.body).await {
+    let finished_task = match run_assigned_task(&task).await {
         Ok(v) => v,
         Err(e) => {
             let response_time = cmp::max(task_start.elapsed().as_millis(), 1) as f64;
+            let response_raw = seal_task_response(&task, e.to_string())?;
             match submit_task(
                 url,
                 email,
                 &api_token,
                 &task.id,
                 520,
-                e.to_string(),
+                response_raw,
                 metadata.clone(),
                 response_time,
             )
@@ -320,6 +547,7 @@ pub async fn task_poller(url: &str, email: &str, api_token: &str) -> anyhow::Res
         }
     };
     let response_time = cmp::max(task_start.elapsed().as_millis(), 1) as f64;
+    let response_raw = seal_task_response(&task, finished_task.raw)?;
 
     match submit_task(
         url,
@@ -327,7 +555,7 @@ pub async fn task_poller(url: &str, email: &str, api_token: &str) -> anyhow::Res
         &api_token,
         &task.id,
         finished_task.status,
-        finished_task.raw,
+        response_raw,
         metadata,
         response_time,
     )
@@ -379,6 +607,7 @@ pub async fn submit_bandwidth(
             DeviceType::Cli,
             RoutesEnum::Api_SubmitBandwidth
         ))
+        .bearer_auth(api_token)
         .query(&query)
         .json(&body)
         .send()
@@ -1,23 +1,29 @@
 use crate::helpers::{
-    get_polling_interval, login_to_network, report_uptime, run_task, submit_bandwidth, task_poller,
+    check_token, get_polling_interval, login_to_network, poll_device_token, register_node_key,
+    report_uptime, request_device_code, run_assigned_task, seal_task_response, submit_bandwidth,
+    task_poller, write_private_file,
 };
-use anyhow::anyhow;
 use block_mesh_common::constants::DeviceType;
-use block_mesh_common::feature_flag_client::get_flag_value;
+use block_mesh_common::feature_flag_client::FlagsClient;
+use block_mesh_common::feature_flags::FlagContext;
 use block_mesh_common::interfaces::server_api::{
-    ClientsMetadata, LoginForm, ReportBandwidthRequest, ReportUptimeRequest, SubmitTaskRequest,
+    ClientsMetadata, GetTokenRequest, ReportBandwidthRequest, ReportUptimeRequest, RunTaskResponse,
+    SubmitTaskRequest,
 };
 use block_mesh_common::interfaces::ws_api::{WsClientMessage, WsServerMessage};
 use block_mesh_common::reqwest::http_client;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use logger_general::tracing::setup_tracing;
-use rand::{thread_rng, Rng};
+use reqwest::StatusCode;
 use reqwest_websocket::{Message, RequestBuilderExt};
+use serde::{Deserialize, Serialize};
 use speed_test::download::test_download;
 use speed_test::latency::test_latency;
 use speed_test::metadata::fetch_metadata;
 use speed_test::upload::test_upload;
 use speed_test::Metadata;
+use std::fs;
+use std::path::Path;
 use std::process::ExitCode;
 use std::sync::Arc;
 use std::time::Duration;
@@ -30,6 +36,7 @@ pub async fn login_mode(
     url: &str,
     email: &str,
     password: &str,
+    totp_code: Option<String>,
     depin_aggregator: Option<String>,
 ) -> anyhow::Result<ExitCode> {
     let url = url.to_string();
@@ -37,33 +44,162 @@ pub async fn login_mode(
     info!("CLI running with url {}", url);
     let api_token = match login_to_network(
         &url,
-        LoginForm {
+        GetTokenRequest {
             email: email.to_string(),
             password: password.to_string(),
+            totp_code,
         },
     )
     .await
     {
         Ok(api_token) => api_token,
-        Err(_) => {
+        Err(e) => {
             setup_tracing(Uuid::default(), DeviceType::Cli);
-            tracing::error!("Failed to login, did you register on {}/register ?", url);
+            tracing::error!(
+                "Failed to login, did you register on {}/register ? {e}",
+                url
+            );
             return Ok(ExitCode::FAILURE);
         }
     };
     setup_tracing(api_token, DeviceType::Cli);
 
     info!("Login successful");
+    run_node(&url, &email, api_token, depin_aggregator).await
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedCredentials {
+    email: String,
+    api_token: Uuid,
+}
+
+/// Logs in by approving a code in the dashboard, delete `credentials_file` to approve again.
+pub async fn device_login_mode(
+    url: &str,
+    device_name: &str,
+    credentials_file: Option<&Path>,
+    depin_aggregator: Option<String>,
+) -> anyhow::Result<ExitCode> {
+    let saved = credentials_file
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str::<SavedCredentials>(&content).ok());
+    // A revoked or rotated token would otherwise fail every request until the file is deleted.
+    let saved = match saved {
+        Some(credentials) => {
+            match check_token(url, &credentials.email, &credentials.api_token).await {
+                Err(e) if is_rejected(&e) => {
+                    println!("Saved credentials were rejected, approving this device again");
+                    None
+                }
+                _ => Some(credentials),
+            }
+        }
+        None => None,
+    };
+    let credentials = match saved {
+        Some(credentials) => credentials,
+        None => {
+            let (email, api_token) = match device_login(url, device_name).await {
+                Ok(credentials) => credentials,
+                Err(e) => {
+                    setup_tracing(Uuid::default(), DeviceType::Cli);
+                    tracing::error!("Device login failed: {e}");
+                    return Ok(ExitCode::FAILURE);
+                }
+            };
+            SavedCredentials { email, api_token }
+        }
+    };
+    setup_tracing(credentials.api_token, DeviceType::Cli);
+
+    info!("Login successful");
+    if let Some(path) = credentials_file {
+        if let Err(e) = write_private_file(path, &serde_json::to_string(&credentials)?) {
+            warn!("Failed to save credentials to {}: {e}", path.display());
+        }
+    }
+    run_node(
+        url,
+        &credentials.email,
+        credentials.api_token,
+        depin_aggregator,
+    )
+    .await
+}
+
+/// A bad token is answered with 400 or 500, a server that is down or overloaded keeps it.
+fn is_rejected(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<reqwest::Error>()
+        .and_then(|e| e.status())
+        .is_some_and(|status| {
+            !matches!(
+                status,
+                StatusCode::TOO_MANY_REQUESTS
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            )
+        })
+}
+
+async fn device_login(url: &str, device_name: &str) -> anyhow::Result<(String, Uuid)> {
+    let device_code = request_device_code(url, device_name).await?;
+    println!(
+        "To connect {}, open {} and enter the code {}",
+        device_name, device_code.verification_uri, device_code.user_code
+    );
+    println!("Or open {}", device_code.verification_uri_complete);
+    poll_device_token(url, &device_code).await
+}
+
+async fn run_node(
+    url: &str,
+    email: &str,
+    api_token: Uuid,
+    depin_aggregator: Option<String>,
+) -> anyhow::Result<ExitCode> {
+    let url = url.to_string();
+    let email = email.to_string();
+    if let Err(e) = register_node_key(&url, &email, &api_token).await {
+        warn!("Failed to register node key, encrypted tasks are disabled: {e}");
+    }
+    tokio::spawn(refresh_node_key(url.clone(), email.clone(), api_token));
     info!("CLI starting");
     let session_metadata = ClientsMetadata {
         depin_aggregator,
         device_type: DeviceType::Cli,
         version: Some(env!("CARGO_PKG_VERSION").to_string()),
     };
+    let flags = FlagsClient::new(http_client(DeviceType::Cli));
+    let _ = flags.refresh().await;
+    tokio::spawn(sync_flags(flags.clone()));
+    let user_id = match check_token(&url, &email, &api_token).await {
+        Ok(response) => response.user_id,
+        Err(e) => {
+            warn!("Failed to look up the user id, flag rollouts will not apply: {e}");
+            None
+        }
+    };
+    let country = fetch_metadata()
+        .await
+        .ok()
+        .map(|metadata| metadata.country)
+        .filter(|country| !country.is_empty());
+    let flag_context = FlagContext {
+        version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        user_id,
+        country,
+        ..FlagContext::new(DeviceType::Cli)
+    };
     let mut prev_is_ws_feature: Option<bool> = None;
     let stop_notifier = Arc::new(Notify::new());
     loop {
-        let is_ws_feature = is_ws_feature_connection().await.unwrap_or_default();
+        let is_ws_feature = flags
+            .evaluate("websocket_mode", &flag_context)
+            .and_then(|v| v.as_bool())
+            .unwrap_or_default();
         if prev_is_ws_feature.is_none()
             || (prev_is_ws_feature.is_some() && is_ws_feature != prev_is_ws_feature.unwrap())
         {
@@ -166,10 +302,21 @@ async fn handle_ws_message(
                     ..
                 } = fetch_metadata().await.unwrap_or_default();
                 let task_start = Instant::now();
-                let completed_task = run_task(&task.url, &task.method, task.headers, task.body)
-                    .await
-                    .unwrap();
+                let completed_task =
+                    run_assigned_task(&task)
+                        .await
+                        .unwrap_or_else(|e| RunTaskResponse {
+                            status: 520,
+                            raw: e.to_string(),
+                        });
                 let response_time = Some(std::cmp::max(task_start.elapsed().as_millis(), 1) as f64);
+                let response_body = match seal_task_response(&task, completed_task.raw) {
+                    Ok(response_body) => response_body,
+                    Err(e) => {
+                        warn!("Failed to seal task {} response: {e}", task.id);
+                        return;
+                    }
+                };
                 let report = SubmitTaskRequest {
                     email: email.clone(),
                     api_token,
@@ -180,7 +327,7 @@ async fn handle_ws_message(
                     asn: Some(asn),
                     colo: Some(colo),
                     response_time,
-                    response_body: Some(completed_task.raw),
+                    response_body: Some(response_body),
                 };
                 let _ = tx.send(WsClientMessage::CompleteTask(report)).await;
             }
@@ -210,6 +357,7 @@ async fn handle_ws_message(
                     email: email.clone(),
                     api_token,
                     ip: Some(cf_meta.ip).filter(|ip| !ip.is_empty()),
+                    report_id: Some(Uuid::new_v4()),
                 };
                 let _ = tx.send(WsClientMessage::ReportUptime(report)).await;
             }
@@ -220,32 +368,26 @@ async fn handle_ws_message(
         }
     });
 }
-async fn is_ws_feature_connection() -> anyhow::Result<bool> {
-    let client = http_client(DeviceType::Cli);
-    let response = get_flag_value("cli_use_websocket", &client, DeviceType::Cli).await?;
-    if let Some(res) = response {
-        let is_enabled = res.as_bool().unwrap_or_default();
-        if !is_enabled {
-            return Ok(false);
+/// The server stops pinning tasks to keys that weren't registered within NODE_KEY_MAX_AGE.
+async fn refresh_node_key(url: String, email: String, api_token: Uuid) {
+    loop {
+        tokio::time::sleep(Duration::from_secs(3600)).await;
+        if let Err(e) = register_node_key(&url, &email, &api_token).await {
+            warn!("Failed to refresh node key: {e}");
         }
-    } else {
-        return Err(anyhow!(
-            "Failed to fetch 'use_websocket' feature flag: {response:#?}"
-        ));
     }
+}
 
-    let response = get_flag_value("cli_use_websocket_percent", &client, DeviceType::Cli).await?;
-    if let Some(res) = response {
-        let percentage: u64 = res.as_u64().unwrap_or_default();
-        let mut rng = thread_rng();
-        let probe: u64 = rng.gen_range(0..100);
-        Ok(probe < percentage)
-    } else {
-        Err(anyhow!(
-            "Failed to fetch 'use_websocket_percent' feature flag: {response:#?}"
-        ))
+async fn sync_flags(flags: FlagsClient) {
+    loop {
+        if let Err(e) = flags.refresh().await {
+            warn!("Failed to refresh feature flags: {e}");
+        }
+        let _ = flags.stream().await;
+        tokio::time::sleep(Duration::from_secs(30)).await;
     }
 }
+
 async fn poll(
     url: String,
     email: String,
@@ -2,21 +2,20 @@ use std::process::ExitCode;
 
 use block_mesh_common::constants::DeviceType;
 use block_mesh_common::interfaces::cli::{CliOptMod, CliOpts};
-use block_mesh_common::interfaces::server_api::{DashboardRequest, LoginForm};
-use blockmesh_cli::helpers::{dashboard, is_vps, login_to_network};
-use blockmesh_cli::login_mode::login_mode;
+use block_mesh_common::interfaces::server_api::{DashboardRequest, GetTokenRequest};
+use blockmesh_cli::helpers::{dashboard, is_vps, login_to_network, set_node_key_file};
+use blockmesh_cli::login_mode::{device_login_mode, login_mode};
 use clap::Parser;
 use logger_general::tracing::setup_tracing;
+use std::env;
 use uuid::Uuid;
 
-#[macro_use]
-pub extern crate tracing;
-
-mod helpers;
-
 #[tokio::main]
 pub async fn main() -> anyhow::Result<ExitCode> {
     let args = CliOpts::parse();
+    if let Some(node_key_file) = args.node_key_file.clone() {
+        set_node_key_file(node_key_file);
+    }
     let vps_resp = is_vps().await?;
     if let Some(vps) = &vps_resp.is_vps {
         if *vps {
@@ -37,15 +36,31 @@ pub async fn main() -> anyhow::Result<ExitCode> {
         }
     }
     match args.mode {
-        CliOptMod::Login => {
-            login_mode(
-                &args.url.clone(),
-                &args.email,
-                &args.password,
-                args.depin_aggregator,
-            )
-            .await?;
-        }
+        CliOptMod::Login => match (&args.email, &args.password) {
+            (Some(email), Some(password)) => {
+                login_mode(
+                    &args.url,
+                    email,
+                    password,
+                    args.totp_code.clone(),
+                    args.depin_aggregator,
+                )
+                .await?;
+            }
+            _ => {
+                let device_name = args
+                    .device_name
+                    .or_else(|| env::var("HOSTNAME").ok())
+                    .unwrap_or_else(|| "blockmesh-cli".to_string());
+                device_login_mode(
+                    &args.url,
+                    &device_name,
+                    args.credentials_file.as_deref(),
+                    args.depin_aggregator,
+                )
+                .await?;
+            }
+        },
         CliOptMod::Register => {
             setup_tracing(Uuid::default(), DeviceType::Cli);
             tracing::info!("Please register via https://app.blockmesh.xyz/register");
@@ -62,22 +77,20 @@ pub async fn main() -> anyhow::Result<ExitCode> {
         }
         CliOptMod::Dashboard => {
             setup_tracing(Uuid::default(), DeviceType::Cli);
+            let (Some(email), Some(password)) = (args.email, args.password) else {
+                tracing::error!("Dashboard needs --email and --password");
+                return Ok(ExitCode::FAILURE);
+            };
             let api_token = login_to_network(
                 &args.url,
-                LoginForm {
-                    email: args.email.clone(),
-                    password: args.password.clone(),
-                },
-            )
-            .await?;
-            dashboard(
-                &args.url,
-                &DashboardRequest {
-                    email: args.email.clone(),
-                    api_token,
+                GetTokenRequest {
+                    email: email.clone(),
+                    password,
+                    totp_code: args.totp_code,
                 },
             )
             .await?;
+            dashboard(&args.url, &DashboardRequest { email, api_token }).await?;
         }
     }
     Ok(ExitCode::SUCCESS)
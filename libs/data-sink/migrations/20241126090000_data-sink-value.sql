@@ -0,0 +1,7 @@
+ALTER TABLE data_sinks ADD COLUMN value jsonb NOT NULL DEFAULT '{}'::jsonb;
+ALTER TABLE data_sinks ADD COLUMN parsed_at timestamptz;
+
+CREATE INDEX data_sinks_unparsed ON data_sinks (created_at) WHERE parsed_at IS NULL;
+CREATE INDEX data_sinks_created_at_id ON data_sinks (created_at, id);
+CREATE INDEX data_sinks_origin_created_at_id ON data_sinks (origin, created_at, id);
+CREATE INDEX data_sinks_user_id_created_at_id ON data_sinks (user_id, created_at, id);
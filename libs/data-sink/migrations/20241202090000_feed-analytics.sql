@@ -0,0 +1,17 @@
+ALTER TABLE data_sinks ADD COLUMN topic TEXT;
+ALTER TABLE data_sinks ADD COLUMN analyzed_at timestamptz;
+
+CREATE INDEX data_sinks_unanalyzed ON data_sinks (created_at) WHERE analyzed_at IS NULL AND parsed_at IS NOT NULL;
+
+CREATE TABLE feed_analytics
+(
+    user_id    uuid        NOT NULL,
+    day        DATE        NOT NULL,
+    dimension  TEXT        NOT NULL,
+    key        TEXT        NOT NULL,
+    count      BIGINT      NOT NULL DEFAULT 0,
+    updated_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (user_id, day, dimension, key)
+);
+
+CREATE INDEX feed_analytics_day_dimension ON feed_analytics (day, dimension);
@@ -0,0 +1 @@
+ALTER TABLE data_sinks ADD COLUMN analysis_leased_until timestamptz;
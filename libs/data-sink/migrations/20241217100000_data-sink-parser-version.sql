@@ -0,0 +1,5 @@
+ALTER TABLE data_sinks
+    ADD COLUMN parser_version integer GENERATED ALWAYS AS (COALESCE((value ->> 'parser_version')::integer, 0)) STORED;
+
+DROP INDEX IF EXISTS data_sinks_unparsed;
+CREATE INDEX data_sinks_parser_version_created_at ON data_sinks (parser_version, created_at);
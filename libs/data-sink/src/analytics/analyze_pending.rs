@@ -0,0 +1,84 @@
+use crate::analytics::classify::TopicClassifier;
+use crate::analytics::feed_analytics::{FeedAnalytics, FeedCounts};
+use crate::analytics::feed_keys;
+use crate::parser::FeedValue;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::PgPool;
+use std::collections::HashSet;
+use std::env;
+use std::time::Duration;
+use uuid::Uuid;
+
+// Each row is counted exactly once, in the same transaction that marks it analyzed.
+// Rows are leased first so no transaction stays open while the classifier runs.
+#[tracing::instrument(name = "analyze_pending", skip_all)]
+pub async fn analyze_pending(pool: PgPool) -> anyhow::Result<()> {
+    let batch_size = env::var("ANALYZE_PENDING_BATCH_SIZE")
+        .unwrap_or("200".to_string())
+        .parse()
+        .unwrap_or(200);
+    let interval = env::var("ANALYZE_PENDING_INTERVAL_SECS")
+        .unwrap_or("300".to_string())
+        .parse()
+        .unwrap_or(300);
+    let lease_secs = env::var("ANALYZE_PENDING_LEASE_SECS")
+        .unwrap_or("600".to_string())
+        .parse()
+        .unwrap_or(600.0);
+    let classifier = TopicClassifier::from_env();
+    loop {
+        match analyze_batch(&pool, classifier.as_ref(), batch_size, lease_secs).await {
+            Ok(analyzed) if analyzed as i64 == batch_size => continue,
+            Ok(_) => {}
+            Err(e) => tracing::error!("analyze_pending failed: {:?}", e),
+        }
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+    }
+}
+
+async fn analyze_batch(
+    pool: &PgPool,
+    classifier: Option<&TopicClassifier>,
+    batch_size: i64,
+    lease_secs: f64,
+) -> anyhow::Result<usize> {
+    let mut transaction = create_txn(pool).await?;
+    let rows = FeedAnalytics::claim_unanalyzed(&mut transaction, batch_size, lease_secs).await?;
+    commit_txn(transaction).await?;
+    if rows.is_empty() {
+        return Ok(0);
+    }
+    let values: Vec<FeedValue> = rows
+        .iter()
+        .map(|row| serde_json::from_value(row.value.clone()).unwrap_or_default())
+        .collect();
+    let topics = match classifier {
+        Some(classifier) => {
+            let texts: Vec<Option<String>> =
+                values.iter().map(|value| value.text.clone()).collect();
+            classifier.classify(&texts).await
+        }
+        None => vec![None; rows.len()],
+    };
+    let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+    let mut transaction = create_txn(pool).await?;
+    let marked: HashSet<Uuid> = FeedAnalytics::mark_analyzed(&mut transaction, &ids, &topics)
+        .await?
+        .into_iter()
+        .collect();
+    let mut counts = FeedCounts::new();
+    for ((row, value), topic) in rows.iter().zip(&values).zip(&topics) {
+        if !marked.contains(&row.id) {
+            continue;
+        }
+        let day = row.created_at.date_naive();
+        for (dimension, key) in feed_keys(&row.origin, &row.user_name, value, topic.as_deref()) {
+            *counts
+                .entry((row.user_id, day, dimension.as_str(), key))
+                .or_default() += 1;
+        }
+    }
+    FeedAnalytics::increment(&mut transaction, &counts).await?;
+    commit_txn(transaction).await?;
+    Ok(rows.len())
+}
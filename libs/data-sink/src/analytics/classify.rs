@@ -0,0 +1,141 @@
+use ai_interfaces::clients::bulk::{AIClient, CompletionOptions, Message};
+use ai_interfaces::models::base::ModelName;
+use std::env;
+use std::time::Duration;
+
+pub const TOPICS: [&str; 12] = [
+    "politics",
+    "news",
+    "technology",
+    "crypto",
+    "finance",
+    "science",
+    "health",
+    "sports",
+    "entertainment",
+    "lifestyle",
+    "humor",
+    "other",
+];
+// Posts are cut to this many characters so one prompt stays well inside small context windows.
+const MAX_POST_CHARS: usize = 500;
+
+pub struct TopicClassifier {
+    client: AIClient,
+    model: ModelName,
+    chunk_size: usize,
+    timeout: Duration,
+}
+
+impl TopicClassifier {
+    /// Classification is off unless `FEED_TOPIC_MODEL` names a model.
+    pub fn from_env() -> Option<Self> {
+        let model = env::var("FEED_TOPIC_MODEL")
+            .ok()
+            .filter(|m| !m.is_empty())?;
+        let chunk_size = env::var("FEED_TOPIC_CHUNK_SIZE")
+            .unwrap_or("20".to_string())
+            .parse()
+            .unwrap_or(20);
+        let timeout = env::var("FEED_TOPIC_TIMEOUT_SECS")
+            .unwrap_or("60".to_string())
+            .parse()
+            .unwrap_or(60);
+        Some(Self {
+            client: AIClient::new(),
+            model: ModelName::from(model),
+            chunk_size: usize::max(chunk_size, 1),
+            timeout: Duration::from_secs(timeout),
+        })
+    }
+
+    /// Returns one topic per text, posts without text or failed chunks stay unclassified.
+    pub async fn classify(&self, texts: &[Option<String>]) -> Vec<Option<String>> {
+        let mut topics = vec![None; texts.len()];
+        let posts: Vec<(usize, &str)> = texts
+            .iter()
+            .enumerate()
+            .filter_map(|(index, text)| Some((index, text.as_deref()?)))
+            .filter(|(_, text)| !text.trim().is_empty())
+            .collect();
+        for chunk in posts.chunks(self.chunk_size) {
+            let texts: Vec<&str> = chunk.iter().map(|(_, text)| *text).collect();
+            match self.classify_chunk(&texts).await {
+                Ok(chunk_topics) => {
+                    for ((index, _), topic) in chunk.iter().zip(chunk_topics) {
+                        topics[*index] = topic;
+                    }
+                }
+                Err(e) => tracing::warn!("topic classification failed: {:?}", e),
+            }
+        }
+        topics
+    }
+
+    async fn classify_chunk(&self, texts: &[&str]) -> anyhow::Result<Vec<Option<String>>> {
+        let options = CompletionOptions {
+            temperature: Some(0.0),
+            ..CompletionOptions::default()
+        };
+        let completion = tokio::time::timeout(
+            self.timeout,
+            self.client
+                .completion(self.model.clone(), prompt(texts), &options),
+        )
+        .await??;
+        Ok(parse_topics(&completion.message.content, texts.len()))
+    }
+}
+
+fn prompt(texts: &[&str]) -> Vec<Message> {
+    let posts = texts
+        .iter()
+        .enumerate()
+        .map(|(index, text)| {
+            let text: String = text.chars().take(MAX_POST_CHARS).collect();
+            format!("{}. {}", index + 1, text.replace('\n', " "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    vec![
+        Message::system(format!(
+            "You label social media posts by topic. Allowed topics: {}. \
+             Reply with a JSON array of strings only, one topic per post, in the order given.",
+            TOPICS.join(", ")
+        )),
+        Message::user(posts),
+    ]
+}
+
+/// Models wrap the array in prose or code fences often enough that only the brackets are trusted.
+fn parse_topics(content: &str, expected: usize) -> Vec<Option<String>> {
+    let array = match (content.find('['), content.rfind(']')) {
+        (Some(start), Some(end)) if start < end => &content[start..=end],
+        _ => return vec![None; expected],
+    };
+    let labels: Vec<String> = serde_json::from_str(array).unwrap_or_default();
+    if labels.len() != expected {
+        return vec![None; expected];
+    }
+    labels
+        .into_iter()
+        .map(|label| {
+            let label = label.trim().to_lowercase();
+            Some(if TOPICS.contains(&label.as_str()) {
+                label
+            } else {
+                "other".to_string()
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_parse_topics() {
+    assert_eq!(
+        parse_topics("```json\n[\"Crypto\", \"cooking\"]\n```", 2),
+        vec![Some("crypto".to_string()), Some("other".to_string())]
+    );
+    assert_eq!(parse_topics("[\"crypto\"]", 2), vec![None, None]);
+    assert_eq!(parse_topics("no idea", 1), vec![None]);
+}
@@ -0,0 +1,225 @@
+use block_mesh_common::interfaces::server_api::{
+    FeedComposition, FeedDimension, FeedShare, FeedTimelinePoint,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{Postgres, Transaction};
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// `(user_id, day, dimension, key)`, ordered so concurrent upserts lock rows in the same order.
+pub type FeedCounts = BTreeMap<(Uuid, NaiveDate, &'static str, String), i64>;
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct UnanalyzedDataSink {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub origin: String,
+    pub user_name: String,
+    pub value: serde_json::Value,
+}
+
+pub struct FeedAnalytics;
+
+impl FeedAnalytics {
+    /// Leases a batch so it can be classified outside of a transaction, a worker that dies
+    /// mid-batch leaves rows that are picked up again once the lease runs out.
+    pub async fn claim_unanalyzed(
+        transaction: &mut Transaction<'_, Postgres>,
+        limit: i64,
+        lease_secs: f64,
+    ) -> anyhow::Result<Vec<UnanalyzedDataSink>> {
+        let rows = sqlx::query_as!(
+            UnanalyzedDataSink,
+            r#"
+            UPDATE data_sinks
+            SET analysis_leased_until = now() + make_interval(secs => $2)
+            WHERE id IN (
+                SELECT id
+                FROM data_sinks
+                WHERE analyzed_at IS NULL AND parsed_at IS NOT NULL
+                AND (analysis_leased_until IS NULL OR analysis_leased_until < now())
+                ORDER BY created_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, user_id, created_at, origin, user_name, value
+            "#,
+            limit,
+            lease_secs
+        )
+        .fetch_all(&mut **transaction)
+        .await?;
+        Ok(rows)
+    }
+
+    /// Returns the rows this call marked, rows another worker already analyzed are skipped.
+    pub async fn mark_analyzed(
+        transaction: &mut Transaction<'_, Postgres>,
+        ids: &[Uuid],
+        topics: &[Option<String>],
+    ) -> anyhow::Result<Vec<Uuid>> {
+        let marked = sqlx::query_scalar!(
+            r#"
+            UPDATE data_sinks
+            SET topic = t.topic, analyzed_at = now(), analysis_leased_until = NULL
+            FROM UNNEST($1::uuid[], $2::text[]) AS t(id, topic)
+            WHERE data_sinks.id = t.id AND data_sinks.analyzed_at IS NULL
+            RETURNING data_sinks.id
+            "#,
+            ids,
+            topics as &[Option<String>]
+        )
+        .fetch_all(&mut **transaction)
+        .await?;
+        Ok(marked)
+    }
+
+    pub async fn increment(
+        transaction: &mut Transaction<'_, Postgres>,
+        counts: &FeedCounts,
+    ) -> anyhow::Result<()> {
+        if counts.is_empty() {
+            return Ok(());
+        }
+        let mut user_ids = Vec::with_capacity(counts.len());
+        let mut days = Vec::with_capacity(counts.len());
+        let mut dimensions = Vec::with_capacity(counts.len());
+        let mut keys = Vec::with_capacity(counts.len());
+        let mut values = Vec::with_capacity(counts.len());
+        for ((user_id, day, dimension, key), count) in counts {
+            user_ids.push(*user_id);
+            days.push(*day);
+            dimensions.push(dimension.to_string());
+            keys.push(key.clone());
+            values.push(*count);
+        }
+        sqlx::query!(
+            r#"
+            INSERT INTO feed_analytics (user_id, day, dimension, key, count)
+            SELECT * FROM UNNEST($1::uuid[], $2::date[], $3::text[], $4::text[], $5::bigint[])
+            ON CONFLICT (user_id, day, dimension, key)
+            DO UPDATE SET count = feed_analytics.count + EXCLUDED.count, updated_at = now()
+            "#,
+            &user_ids,
+            &days,
+            &dimensions,
+            &keys,
+            &values
+        )
+        .execute(&mut **transaction)
+        .await?;
+        Ok(())
+    }
+
+    /// Top keys per dimension for one user, or for everyone when `user_id` is `None`.
+    pub async fn composition(
+        transaction: &mut Transaction<'_, Postgres>,
+        user_id: Option<&Uuid>,
+        since: NaiveDate,
+        until: NaiveDate,
+        limit: i64,
+    ) -> anyhow::Result<Vec<FeedComposition>> {
+        let totals = sqlx::query!(
+            r#"
+            SELECT dimension AS "dimension!", SUM(count)::BIGINT AS "total!"
+            FROM feed_analytics
+            WHERE ($1::uuid IS NULL OR user_id = $1)
+            AND day >= $2 AND day <= $3
+            GROUP BY dimension
+            "#,
+            user_id,
+            since,
+            until
+        )
+        .fetch_all(&mut **transaction)
+        .await?;
+        let top = sqlx::query!(
+            r#"
+            SELECT dimension AS "dimension!", key AS "key!", total AS "total!"
+            FROM (
+                SELECT dimension, key, SUM(count)::BIGINT AS total,
+                ROW_NUMBER() OVER (PARTITION BY dimension ORDER BY SUM(count) DESC, key) AS rank
+                FROM feed_analytics
+                WHERE ($1::uuid IS NULL OR user_id = $1)
+                AND day >= $2 AND day <= $3
+                GROUP BY dimension, key
+            ) ranked
+            WHERE rank <= $4
+            ORDER BY dimension, total DESC, key
+            "#,
+            user_id,
+            since,
+            until,
+            limit
+        )
+        .fetch_all(&mut **transaction)
+        .await?;
+        let totals: HashMap<String, i64> = totals
+            .into_iter()
+            .map(|row| (row.dimension, row.total))
+            .collect();
+        let mut compositions: Vec<FeedComposition> = FeedDimension::ALL
+            .into_iter()
+            .map(|dimension| FeedComposition {
+                dimension,
+                total: totals.get(dimension.as_str()).copied().unwrap_or_default(),
+                top: Vec::new(),
+            })
+            .collect();
+        for row in top {
+            let Ok(dimension) = FeedDimension::from_str(&row.dimension) else {
+                continue;
+            };
+            if let Some(composition) = compositions.iter_mut().find(|c| c.dimension == dimension) {
+                composition.top.push(FeedShare {
+                    share: row.total as f64 / composition.total.max(1) as f64,
+                    key: row.key,
+                    count: row.total,
+                });
+            }
+        }
+        Ok(compositions)
+    }
+
+    pub async fn timeline(
+        transaction: &mut Transaction<'_, Postgres>,
+        user_id: &Uuid,
+        dimension: FeedDimension,
+        since: NaiveDate,
+        until: NaiveDate,
+        keys: &[String],
+    ) -> anyhow::Result<Vec<FeedTimelinePoint>> {
+        let points = sqlx::query_as!(
+            FeedTimelinePoint,
+            r#"
+            SELECT day AS "day!", key AS "key!", SUM(count)::BIGINT AS "count!"
+            FROM feed_analytics
+            WHERE user_id = $1 AND dimension = $2
+            AND day >= $3 AND day <= $4
+            AND key = ANY($5)
+            GROUP BY day, key
+            ORDER BY day, key
+            "#,
+            user_id,
+            dimension.as_str(),
+            since,
+            until,
+            keys
+        )
+        .fetch_all(&mut **transaction)
+        .await?;
+        Ok(points)
+    }
+
+    pub async fn delete_by_user(
+        transaction: &mut Transaction<'_, Postgres>,
+        user_id: &Uuid,
+    ) -> anyhow::Result<u64> {
+        let result = sqlx::query!("DELETE FROM feed_analytics WHERE user_id = $1", user_id)
+            .execute(&mut **transaction)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
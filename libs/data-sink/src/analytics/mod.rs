@@ -0,0 +1,120 @@
+pub mod analyze_pending;
+pub mod classify;
+pub mod feed_analytics;
+
+use crate::parser::{host, FeedValue};
+use block_mesh_common::interfaces::server_api::FeedDimension;
+
+/// Every key a post counts towards, at most once per dimension and key.
+pub fn feed_keys(
+    origin: &str,
+    user_name: &str,
+    value: &FeedValue,
+    topic: Option<&str>,
+) -> Vec<(FeedDimension, String)> {
+    let mut keys = Vec::new();
+    let platform = host(origin).unwrap_or_else(|| origin.to_lowercase());
+
+    // Rows parsed before domains were extracted only have the raw links.
+    let mut sources: Vec<String> = if value.domains.is_empty() {
+        value.links.iter().filter_map(|link| host(link)).collect()
+    } else {
+        value.domains.clone()
+    };
+    sources.retain(|source| source != &platform);
+    sources.sort();
+    sources.dedup();
+    if sources.is_empty() {
+        sources.push(platform);
+    }
+    keys.extend(
+        sources
+            .into_iter()
+            .map(|source| (FeedDimension::Source, source)),
+    );
+
+    let author = value
+        .author
+        .as_ref()
+        .and_then(|author| author.handle.as_deref())
+        .unwrap_or(user_name)
+        .trim()
+        .trim_start_matches('@')
+        .to_lowercase();
+    if !author.is_empty() {
+        keys.push((FeedDimension::Author, author));
+    }
+
+    let mut hashtags = value.text.as_deref().map(hashtags).unwrap_or_default();
+    hashtags.sort();
+    hashtags.dedup();
+    keys.extend(
+        hashtags
+            .into_iter()
+            .map(|hashtag| (FeedDimension::Hashtag, hashtag)),
+    );
+
+    if let Some(topic) = topic {
+        keys.push((FeedDimension::Topic, topic.to_string()));
+    }
+    keys
+}
+
+fn hashtags(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .filter_map(|word| word.strip_prefix('#'))
+        .map(|tag| {
+            tag.chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect::<String>()
+                .to_lowercase()
+        })
+        .filter(|tag| !tag.is_empty() && !tag.chars().all(|c| c.is_ascii_digit()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Author;
+
+    #[test]
+    fn feed_keys_cover_every_dimension() {
+        let value = FeedValue {
+            author: Some(Author {
+                name: Some("BlockMesh Network".to_string()),
+                handle: Some("@BlockMesh_xyz".to_string()),
+            }),
+            text: Some("Nodes #DePIN #depin, #2024 and #Solana!".to_string()),
+            domains: vec!["blockmesh.xyz".to_string(), "x.com".to_string()],
+            ..FeedValue::default()
+        };
+        let keys = feed_keys("https://x.com", "someone_else", &value, Some("crypto"));
+        assert_eq!(
+            keys,
+            vec![
+                (FeedDimension::Source, "blockmesh.xyz".to_string()),
+                (FeedDimension::Author, "blockmesh_xyz".to_string()),
+                (FeedDimension::Hashtag, "depin".to_string()),
+                (FeedDimension::Hashtag, "solana".to_string()),
+                (FeedDimension::Topic, "crypto".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn posts_without_outbound_links_count_as_the_platform() {
+        let value = FeedValue {
+            links: vec!["https://www.x.com/blockmesh_xyz".to_string()],
+            ..FeedValue::default()
+        };
+        let keys = feed_keys("https://x.com", "blockmesh_xyz", &value, None);
+        assert_eq!(
+            keys,
+            vec![
+                (FeedDimension::Source, "x.com".to_string()),
+                (FeedDimension::Author, "blockmesh_xyz".to_string()),
+            ]
+        );
+    }
+}
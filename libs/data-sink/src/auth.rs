@@ -0,0 +1,108 @@
+use crate::errors::Error;
+use crate::AppState;
+use axum::http::HeaderMap;
+use block_mesh_common::constants::BLOCKMESH_PG_NOTIFY_API;
+use block_mesh_common::interfaces::db_messages::InvalidateApiCache;
+use block_mesh_common::interfaces::server_api::TokenScope;
+use block_mesh_manager_database_domain::domain::authenticate_token::{
+    authenticate_token, Credentials,
+};
+use dashmap::DashMap;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use std::cmp;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+use validator::validate_email;
+
+// Extensions upload every few seconds, so a short cache saves most follower lookups.
+const AUTH_CACHE_TTL: Duration = Duration::from_secs(300);
+const MIN_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+pub type AuthCacheKey = (Credentials, TokenScope);
+
+#[derive(Debug, Clone)]
+pub struct CachedUser {
+    pub user_id: Uuid,
+    pub email: String,
+    pub cached_at: Instant,
+}
+
+/// Accepts a bearer token or the legacy email and api token pair, as long as the token carries
+/// `scope`.
+#[tracing::instrument(name = "authenticate", skip_all, fields(scope = %scope))]
+pub async fn authenticate(
+    state: &AppState,
+    headers: &HeaderMap,
+    email: &str,
+    api_token: &Uuid,
+    scope: TokenScope,
+) -> Result<Uuid, Error> {
+    let credentials = Credentials::new(headers, email, api_token);
+    let key = (credentials, scope);
+    if let Some(cached) = state.auth_cache.get(&key) {
+        if cached.cached_at.elapsed() < AUTH_CACHE_TTL {
+            return Ok(cached.user_id);
+        }
+    }
+    if matches!(key.0, Credentials::Legacy { .. }) && !validate_email(email) {
+        return Err(Error::BadRequest("BadEmail".to_string()));
+    }
+    let mut transaction = create_txn(&state.follower_db_pool).await?;
+    let user = authenticate_token(&mut transaction, &key.0, scope).await?;
+    commit_txn(transaction).await?;
+    let user = match user {
+        Some(user) => user,
+        None => {
+            state.auth_cache.remove(&key);
+            return Err(Error::Unauthorized("ApiTokenNotFound".to_string()));
+        }
+    };
+    state.auth_cache.insert(
+        key,
+        CachedUser {
+            user_id: user.user_id,
+            email: user.email,
+            cached_at: Instant::now(),
+        },
+    );
+    Ok(user.user_id)
+}
+
+/// Drops cached tokens of a user whose tokens were rotated or revoked, or whose account was deleted.
+#[tracing::instrument(name = "auth_cache_listener", skip_all, err)]
+pub async fn auth_cache_listener(
+    channel_pool: PgPool,
+    auth_cache: Arc<DashMap<AuthCacheKey, CachedUser>>,
+) -> anyhow::Result<()> {
+    let mut listener = PgListener::connect_with(&channel_pool).await?;
+    listener.listen(BLOCKMESH_PG_NOTIFY_API).await?;
+    let mut backoff = MIN_BACKOFF;
+    loop {
+        let notification = match listener.recv().await {
+            Ok(notification) => {
+                backoff = MIN_BACKOFF;
+                notification
+            }
+            Err(e) => {
+                tracing::error!(
+                    "auth_cache_listener failed to receive, retrying in {backoff:?}: {e}"
+                );
+                // Anything published while disconnected is lost, start from an empty cache.
+                auth_cache.clear();
+                tokio::time::sleep(backoff).await;
+                backoff = cmp::min(backoff * 2, MAX_BACKOFF);
+                continue;
+            }
+        };
+        match serde_json::from_str::<InvalidateApiCache>(notification.payload()) {
+            Ok(payload) => {
+                auth_cache.retain(|_, cached| cached.email != payload.email);
+            }
+            Err(_) => tracing::error!("Failed to deserialize {:?}", notification.payload()),
+        }
+    }
+}
@@ -1,9 +1,13 @@
-use block_mesh_common::interfaces::server_api::FeedElement;
+use crate::query::{Cursor, DataSinkQuery};
+use block_mesh_common::interfaces::server_api::{DigestDataResult, DigestDataStatus, FeedElement};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{Postgres, Transaction};
+use std::collections::HashSet;
 use uuid::Uuid;
 
+pub const MAX_RAW_BYTES: usize = 512 * 1024;
+
 #[derive(sqlx::FromRow, Debug, Serialize, Deserialize, Clone)]
 pub struct DataSink {
     pub id: Uuid,
@@ -16,30 +20,248 @@ pub struct DataSink {
     pub origin_id: String,
     pub user_name: String,
     pub link: String,
+    pub parsed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, Clone)]
+pub struct DataSinkRecord {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub origin: String,
+    pub origin_id: String,
+    pub user_name: String,
+    pub link: String,
+    pub value: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw: Option<String>,
+}
+
+impl DataSinkRecord {
+    pub fn cursor(&self) -> Cursor {
+        Cursor {
+            created_at: self.created_at,
+            id: self.id,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct UnparsedDataSink {
+    pub id: Uuid,
+    pub origin: String,
+    pub raw: String,
+}
+
+pub fn origin_id(data: &FeedElement) -> String {
+    format!("{}_{}", data.origin, data.id)
+}
+
+pub fn validate_feed_element(data: &FeedElement) -> Result<(), String> {
+    if data.origin.is_empty() || data.id.is_empty() || data.link.is_empty() {
+        return Err("Missing origin, id or link".to_string());
+    }
+    if data.raw.len() > MAX_RAW_BYTES {
+        return Err(format!("Raw is larger than {} bytes", MAX_RAW_BYTES));
+    }
+    Ok(())
+}
+
+/// Lines results up with the request, a repeated element only counts as inserted once.
+pub fn batch_results(data: &[FeedElement], mut inserted: HashSet<String>) -> Vec<DigestDataResult> {
+    data.iter()
+        .map(|element| {
+            let id = origin_id(element);
+            let (status, error) = match validate_feed_element(element) {
+                Err(error) => (DigestDataStatus::Invalid, Some(error)),
+                Ok(()) if inserted.remove(&id) => (DigestDataStatus::Inserted, None),
+                Ok(()) => (DigestDataStatus::Duplicate, None),
+            };
+            DigestDataResult { id, status, error }
+        })
+        .collect()
 }
 
 impl DataSink {
+    /// Returns false when the element was already stored.
     pub async fn create_data_sink(
         transaction: &mut Transaction<'_, Postgres>,
         user_id: &Uuid,
         data: FeedElement,
-    ) -> anyhow::Result<()> {
-        sqlx::query!(
+        value: serde_json::Value,
+    ) -> anyhow::Result<bool> {
+        let result = sqlx::query!(
             r#"
             INSERT INTO data_sinks
-            (user_id, origin, origin_id, user_name, link, raw)
+            (user_id, origin, origin_id, user_name, link, raw, value, parsed_at)
             VALUES
-            ($1, $2, $3, $4, $5, $6)
+            ($1, $2, $3, $4, $5, $6, $7, now())
+            ON CONFLICT (origin, origin_id) DO NOTHING
             "#,
             user_id,
             data.origin,
-            format!("{}_{}", data.origin, data.id),
+            origin_id(&data),
             data.user_name,
             data.link,
-            data.raw
+            data.raw,
+            value
+        )
+        .execute(&mut **transaction)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Returns the origin ids that were new.
+    pub async fn create_data_sinks(
+        transaction: &mut Transaction<'_, Postgres>,
+        user_id: &Uuid,
+        data: &[(FeedElement, serde_json::Value)],
+    ) -> anyhow::Result<HashSet<String>> {
+        let origins: Vec<String> = data.iter().map(|(e, _)| e.origin.clone()).collect();
+        let origin_ids: Vec<String> = data.iter().map(|(e, _)| origin_id(e)).collect();
+        let user_names: Vec<String> = data.iter().map(|(e, _)| e.user_name.clone()).collect();
+        let links: Vec<String> = data.iter().map(|(e, _)| e.link.clone()).collect();
+        let raws: Vec<String> = data.iter().map(|(e, _)| e.raw.clone()).collect();
+        let values: Vec<serde_json::Value> = data.iter().map(|(_, v)| v.clone()).collect();
+        let inserted = sqlx::query_scalar!(
+            r#"
+            INSERT INTO data_sinks
+            (user_id, origin, origin_id, user_name, link, raw, value, parsed_at)
+            SELECT $1, origin, origin_id, user_name, link, raw, value, now()
+            FROM UNNEST($2::text[], $3::text[], $4::text[], $5::text[], $6::text[], $7::jsonb[])
+            AS t(origin, origin_id, user_name, link, raw, value)
+            ON CONFLICT (origin, origin_id) DO NOTHING
+            RETURNING origin_id
+            "#,
+            user_id,
+            &origins,
+            &origin_ids,
+            &user_names,
+            &links,
+            &raws,
+            &values
+        )
+        .fetch_all(&mut **transaction)
+        .await?;
+        Ok(inserted.into_iter().collect())
+    }
+
+    /// Rows never parsed or parsed with an older `PARSER_VERSION`, oldest versions first.
+    pub async fn get_unparsed(
+        transaction: &mut Transaction<'_, Postgres>,
+        parser_version: u32,
+        limit: i64,
+    ) -> anyhow::Result<Vec<UnparsedDataSink>> {
+        let rows = sqlx::query_as!(
+            UnparsedDataSink,
+            r#"
+            SELECT id, origin, raw
+            FROM data_sinks
+            WHERE parser_version < $1
+            ORDER BY parser_version, created_at
+            LIMIT $2
+            FOR UPDATE SKIP LOCKED
+            "#,
+            parser_version as i32,
+            limit
+        )
+        .fetch_all(&mut **transaction)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn update_value(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+        value: serde_json::Value,
+    ) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE data_sinks
+            SET value = $2, parsed_at = now(), updated_at = now()
+            WHERE id = $1
+            "#,
+            id,
+            value
         )
         .execute(&mut **transaction)
         .await?;
         Ok(())
     }
+
+    pub async fn query(
+        transaction: &mut Transaction<'_, Postgres>,
+        query: &DataSinkQuery,
+        cursor: Option<&Cursor>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<DataSinkRecord>> {
+        let rows = sqlx::query_as!(
+            DataSinkRecord,
+            r#"
+            SELECT
+            id, user_id, created_at, origin, origin_id, user_name, link, value,
+            CASE WHEN $7 THEN raw END AS raw
+            FROM data_sinks
+            WHERE ($1::text IS NULL OR origin = $1)
+            AND ($2::uuid IS NULL OR user_id = $2)
+            AND ($3::timestamptz IS NULL OR created_at >= $3)
+            AND ($4::timestamptz IS NULL OR created_at < $4)
+            AND ($5::timestamptz IS NULL OR (created_at, id) > ($5, $6::uuid))
+            ORDER BY created_at, id
+            LIMIT $8
+            "#,
+            query.origin,
+            query.user_id,
+            query.since,
+            query.until,
+            cursor.map(|cursor| cursor.created_at),
+            cursor.map(|cursor| cursor.id),
+            query.include_raw.unwrap_or(false),
+            limit
+        )
+        .fetch_all(&mut **transaction)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn delete_by_user(
+        transaction: &mut Transaction<'_, Postgres>,
+        user_id: &Uuid,
+    ) -> anyhow::Result<u64> {
+        let result = sqlx::query!("DELETE FROM data_sinks WHERE user_id = $1", user_id)
+            .execute(&mut **transaction)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[test]
+fn test_batch_results() {
+    let element = |id: &str, link: &str| FeedElement {
+        origin: "https://x.com".to_string(),
+        user_name: "blockmesh_xyz".to_string(),
+        link: link.to_string(),
+        id: id.to_string(),
+        raw: "<article></article>".to_string(),
+    };
+    let data = vec![
+        element("1", "/blockmesh_xyz/status/1"),
+        element("1", "/blockmesh_xyz/status/1"),
+        element("2", "/blockmesh_xyz/status/2"),
+        element("3", ""),
+    ];
+    let inserted = HashSet::from(["https://x.com_1".to_string()]);
+    let statuses: Vec<DigestDataStatus> = batch_results(&data, inserted)
+        .into_iter()
+        .map(|result| result.status)
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            DigestDataStatus::Inserted,
+            DigestDataStatus::Duplicate,
+            DigestDataStatus::Duplicate,
+            DigestDataStatus::Invalid,
+        ]
+    );
 }
@@ -9,6 +9,10 @@ pub enum Error {
     Sql(#[from] sqlx::Error),
     #[error(transparent)]
     Anyhow(#[from] AnyhowError),
+    #[error("{0}")]
+    Unauthorized(String),
+    #[error("{0}")]
+    BadRequest(String),
 }
 
 impl Error {
@@ -35,6 +39,8 @@ impl IntoResponse for Error {
             Error::Anyhow(_) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error.").into_response()
             }
+            Error::Unauthorized(message) => (StatusCode::UNAUTHORIZED, message).into_response(),
+            Error::BadRequest(message) => (StatusCode::BAD_REQUEST, message).into_response(),
         }
     }
 }
@@ -45,6 +51,8 @@ impl From<Error> for StatusCode {
         match error {
             Error::Sql(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Error::Anyhow(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Error::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Error::BadRequest(_) => StatusCode::BAD_REQUEST,
         }
     }
 }
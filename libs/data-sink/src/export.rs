@@ -0,0 +1,251 @@
+use crate::data_sink::{DataSink, DataSinkRecord};
+use crate::errors::Error;
+use crate::parser::FeedValue;
+use crate::query::{Cursor, DataSinkQuery};
+use arrow_array::{ArrayRef, RecordBatch, StringArray, TimestampMicrosecondArray, UInt64Array};
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use futures::stream::{self, Stream};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use sqlx::PgPool;
+use std::env;
+use std::sync::Arc;
+
+const EXPORT_BATCH_SIZE: i64 = 1_000;
+
+async fn next_batch(
+    pool: &PgPool,
+    query: &DataSinkQuery,
+    cursor: Option<&Cursor>,
+) -> anyhow::Result<Vec<DataSinkRecord>> {
+    let mut transaction = create_txn(pool).await?;
+    let records = DataSink::query(&mut transaction, query, cursor, EXPORT_BATCH_SIZE).await?;
+    commit_txn(transaction).await?;
+    Ok(records)
+}
+
+struct ExportState {
+    pool: PgPool,
+    query: DataSinkQuery,
+    cursor: Option<Cursor>,
+    done: bool,
+}
+
+/// Walks the whole filter range batch by batch so large exports never sit in memory.
+pub fn ndjson_stream(
+    pool: PgPool,
+    query: DataSinkQuery,
+) -> impl Stream<Item = anyhow::Result<String>> {
+    let state = ExportState {
+        pool,
+        cursor: query.cursor().ok().flatten(),
+        query,
+        done: false,
+    };
+    stream::unfold(state, |mut state| async move {
+        if state.done {
+            return None;
+        }
+        let records = match next_batch(&state.pool, &state.query, state.cursor.as_ref()).await {
+            Ok(records) => records,
+            Err(e) => {
+                state.done = true;
+                return Some((Err(e), state));
+            }
+        };
+        state.done = (records.len() as i64) < EXPORT_BATCH_SIZE;
+        state.cursor = records.last().map(DataSinkRecord::cursor);
+        let mut lines = String::new();
+        for record in &records {
+            match serde_json::to_string(record) {
+                Ok(line) => {
+                    lines.push_str(&line);
+                    lines.push('\n');
+                }
+                Err(e) => return Some((Err(e.into()), state)),
+            }
+        }
+        Some((Ok(lines), state))
+    })
+}
+
+fn parquet_export_max_rows() -> usize {
+    env::var("PARQUET_EXPORT_MAX_ROWS")
+        .unwrap_or("100000".to_string())
+        .parse()
+        .unwrap_or(100_000)
+}
+
+/// Parquet writes its footer last so the file is built in memory, the row cap bounds it.
+pub async fn parquet_export(pool: &PgPool, query: &DataSinkQuery) -> Result<Vec<u8>, Error> {
+    let max_rows = parquet_export_max_rows();
+    let mut rows = 0;
+    let schema = parquet_schema();
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut buffer = Vec::new();
+    let mut writer = ArrowWriter::try_new(&mut buffer, schema.clone(), Some(properties))
+        .map_err(anyhow::Error::from)?;
+    let mut cursor = query.cursor().ok().flatten();
+    loop {
+        let records = next_batch(pool, query, cursor.as_ref()).await?;
+        rows += records.len();
+        if rows > max_rows {
+            return Err(Error::BadRequest(format!(
+                "Parquet exports are limited to {} rows, narrow the range or use ndjson",
+                max_rows
+            )));
+        }
+        if !records.is_empty() {
+            writer
+                .write(&record_batch(schema.clone(), &records)?)
+                .map_err(anyhow::Error::from)?;
+        }
+        if (records.len() as i64) < EXPORT_BATCH_SIZE {
+            break;
+        }
+        cursor = records.last().map(DataSinkRecord::cursor);
+    }
+    writer.close().map_err(anyhow::Error::from)?;
+    Ok(buffer)
+}
+
+fn parquet_schema() -> SchemaRef {
+    let utf8 = |name: &str, nullable: bool| Field::new(name, DataType::Utf8, nullable);
+    let timestamp = |name: &str, nullable: bool| {
+        Field::new(
+            name,
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            nullable,
+        )
+    };
+    let count = |name: &str| Field::new(name, DataType::UInt64, true);
+    Arc::new(Schema::new(vec![
+        utf8("id", false),
+        utf8("user_id", false),
+        timestamp("created_at", false),
+        utf8("origin", false),
+        utf8("origin_id", false),
+        utf8("user_name", false),
+        utf8("link", false),
+        utf8("author_name", true),
+        utf8("author_handle", true),
+        utf8("text", true),
+        timestamp("published_at", true),
+        count("replies"),
+        count("reposts"),
+        count("likes"),
+        count("views"),
+        utf8("media", false),
+        utf8("value", false),
+        utf8("raw", true),
+    ]))
+}
+
+fn record_batch(schema: SchemaRef, records: &[DataSinkRecord]) -> anyhow::Result<RecordBatch> {
+    let values: Vec<FeedValue> = records
+        .iter()
+        .map(|record| serde_json::from_value(record.value.clone()).unwrap_or_default())
+        .collect();
+    let strings = |f: &dyn Fn(&DataSinkRecord) -> String| -> ArrayRef {
+        Arc::new(StringArray::from(records.iter().map(f).collect::<Vec<_>>()))
+    };
+    let optional_strings = |f: &dyn Fn(&FeedValue) -> Option<String>| -> ArrayRef {
+        Arc::new(StringArray::from(values.iter().map(f).collect::<Vec<_>>()))
+    };
+    let counts = |f: &dyn Fn(&FeedValue) -> Option<u64>| -> ArrayRef {
+        Arc::new(UInt64Array::from(values.iter().map(f).collect::<Vec<_>>()))
+    };
+    let columns: Vec<ArrayRef> = vec![
+        strings(&|record| record.id.to_string()),
+        strings(&|record| record.user_id.to_string()),
+        Arc::new(
+            TimestampMicrosecondArray::from(
+                records
+                    .iter()
+                    .map(|record| record.created_at.timestamp_micros())
+                    .collect::<Vec<_>>(),
+            )
+            .with_timezone("UTC"),
+        ),
+        strings(&|record| record.origin.clone()),
+        strings(&|record| record.origin_id.clone()),
+        strings(&|record| record.user_name.clone()),
+        strings(&|record| record.link.clone()),
+        optional_strings(&|value| value.author.as_ref()?.name.clone()),
+        optional_strings(&|value| value.author.as_ref()?.handle.clone()),
+        optional_strings(&|value| value.text.clone()),
+        Arc::new(
+            TimestampMicrosecondArray::from(
+                values
+                    .iter()
+                    .map(|value| value.published_at.map(|at| at.timestamp_micros()))
+                    .collect::<Vec<_>>(),
+            )
+            .with_timezone("UTC"),
+        ),
+        counts(&|value| value.engagement.replies),
+        counts(&|value| value.engagement.reposts),
+        counts(&|value| value.engagement.likes),
+        counts(&|value| value.engagement.views),
+        Arc::new(StringArray::from(
+            values
+                .iter()
+                .map(|value| serde_json::to_string(&value.media).unwrap_or_default())
+                .collect::<Vec<_>>(),
+        )),
+        strings(&|record| record.value.to_string()),
+        Arc::new(StringArray::from(
+            records
+                .iter()
+                .map(|record| record.raw.clone())
+                .collect::<Vec<_>>(),
+        )),
+    ];
+    Ok(RecordBatch::try_new(schema, columns)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_feed_element;
+    use chrono::Utc;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use uuid::Uuid;
+
+    #[test]
+    fn parquet_batches_flatten_values() {
+        let value = parse_feed_element("https://x.com", include_str!("../fixtures/x_article.html"));
+        let record = DataSinkRecord {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            origin: "https://x.com".to_string(),
+            origin_id: "https://x.com_1859214123471239612".to_string(),
+            user_name: "blockmesh_xyz".to_string(),
+            link: "/blockmesh_xyz/status/1859214123471239612".to_string(),
+            value: serde_json::to_value(&value).unwrap(),
+            raw: None,
+        };
+        let schema = parquet_schema();
+        let batch = record_batch(schema.clone(), &[record.clone(), record]).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        let likes = batch
+            .column_by_name("likes")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert_eq!(likes.value(0), 1404);
+
+        let mut buffer = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut buffer, schema, None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let reader = SerializedFileReader::new(bytes::Bytes::from(buffer)).unwrap();
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 2);
+    }
+}
@@ -1,12 +1,24 @@
+mod analytics;
+mod auth;
 mod data_sink;
-mod database;
 mod errors;
+mod export;
+mod parse_pending;
+mod parser;
+mod query;
 mod routes;
 
+use crate::analytics::analyze_pending::analyze_pending;
+use crate::auth::{auth_cache_listener, AuthCacheKey, CachedUser};
+use crate::parse_pending::parse_pending;
 use crate::routes::get_router;
 use axum::Router;
 use block_mesh_common::env::environment::Environment;
 use block_mesh_common::env::load_dotenv::load_dotenv;
+use block_mesh_common::interfaces::server_api::FeedComposition;
+use chrono::NaiveDate;
+use dashmap::DashMap;
+use database_utils::utils::connection::channel_pool::channel_pool;
 use database_utils::utils::connection::follower_pool::follower_pool;
 use database_utils::utils::connection::write_pool::write_pool;
 use database_utils::utils::migrate::migrate;
@@ -14,6 +26,8 @@ use logger_general::tracing::setup_tracing_stdout_only_with_sentry;
 use sqlx::PgPool;
 use std::net::SocketAddr;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Instant;
 use std::{env, mem, process};
 use tokio::net::TcpListener;
 use tower_http::cors::CorsLayer;
@@ -59,23 +73,36 @@ fn main() {
     process::exit(1);
 }
 
+#[derive(Debug, Clone)]
+pub struct CachedComposition {
+    pub compositions: Vec<FeedComposition>,
+    pub cached_at: Instant,
+}
+
 #[derive(Clone)]
 pub struct AppState {
     pub data_sink_db_pool: PgPool,
     pub follower_db_pool: PgPool,
+    pub channel_pool: PgPool,
     pub environment: Environment,
+    pub auth_cache: Arc<DashMap<AuthCacheKey, CachedUser>>,
+    pub analytics_cache: Arc<DashMap<(NaiveDate, NaiveDate), CachedComposition>>,
 }
 
 impl AppState {
     pub async fn new() -> Self {
         let data_sink_db_pool = write_pool(None).await;
         let follower_db_pool = follower_pool(Some("FOLLOWER_DATABASE_URL".to_string())).await;
+        let channel_pool = channel_pool(Some("CHANNEL_DATABASE_URL".to_string())).await;
         let environment = env::var("APP_ENVIRONMENT").unwrap();
         let environment = Environment::from_str(&environment).unwrap();
         Self {
             data_sink_db_pool,
             follower_db_pool,
+            channel_pool,
             environment,
+            auth_cache: Arc::new(DashMap::new()),
+            analytics_cache: Arc::new(DashMap::new()),
         }
     }
 }
@@ -90,6 +117,12 @@ async fn run() -> anyhow::Result<()> {
     migrate(&state.data_sink_db_pool, env)
         .await
         .expect("Failed to migrate database");
+    let parse_pending_task = tokio::spawn(parse_pending(state.data_sink_db_pool.clone()));
+    let analyze_pending_task = tokio::spawn(analyze_pending(state.data_sink_db_pool.clone()));
+    let auth_cache_listener_task = tokio::spawn(auth_cache_listener(
+        state.channel_pool.clone(),
+        state.auth_cache.clone(),
+    ));
     let router = get_router(state);
     let cors = CorsLayer::permissive();
     let app = Router::new().nest("/", router).layer(cors);
@@ -98,6 +131,9 @@ async fn run() -> anyhow::Result<()> {
     tracing::info!("Listening on {}", listener.local_addr()?);
     let server_task = run_server(listener, app);
     tokio::select! {
-        o = server_task => panic!("server task exit {:?}", o)
+        o = server_task => panic!("server task exit {:?}", o),
+        o = parse_pending_task => panic!("parse_pending task exit {:?}", o),
+        o = analyze_pending_task => panic!("analyze_pending task exit {:?}", o),
+        o = auth_cache_listener_task => panic!("auth_cache_listener task exit {:?}", o)
     }
 }
@@ -0,0 +1,39 @@
+use crate::data_sink::DataSink;
+use crate::parser::{parse_feed_element, PARSER_VERSION};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use sqlx::PgPool;
+use std::env;
+use std::time::Duration;
+
+// Rows stored before parsing existed, or parsed before the last `PARSER_VERSION` bump, are picked
+// up here. Feed analytics keep the keys a row was counted with when it was first analyzed.
+#[tracing::instrument(name = "parse_pending", skip_all)]
+pub async fn parse_pending(pool: PgPool) -> anyhow::Result<()> {
+    let batch_size = env::var("PARSE_PENDING_BATCH_SIZE")
+        .unwrap_or("500".to_string())
+        .parse()
+        .unwrap_or(500);
+    let interval = env::var("PARSE_PENDING_INTERVAL_SECS")
+        .unwrap_or("60".to_string())
+        .parse()
+        .unwrap_or(60);
+    loop {
+        match parse_batch(&pool, batch_size).await {
+            Ok(parsed) if parsed as i64 == batch_size => continue,
+            Ok(_) => {}
+            Err(e) => tracing::error!("parse_pending failed: {:?}", e),
+        }
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+    }
+}
+
+async fn parse_batch(pool: &PgPool, batch_size: i64) -> anyhow::Result<usize> {
+    let mut transaction = create_txn(pool).await?;
+    let rows = DataSink::get_unparsed(&mut transaction, PARSER_VERSION, batch_size).await?;
+    for row in &rows {
+        let value = serde_json::to_value(parse_feed_element(&row.origin, &row.raw))?;
+        DataSink::update_value(&mut transaction, &row.id, value).await?;
+    }
+    commit_txn(transaction).await?;
+    Ok(rows.len())
+}
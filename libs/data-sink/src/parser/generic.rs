@@ -0,0 +1,36 @@
+use crate::parser::{element_text, first_datetime, link_domain, push_unique, select, FeedValue};
+use scraper::Html;
+
+pub fn parse(html: &Html) -> FeedValue {
+    // Without knowing the layout, text nodes are treated as separate blocks.
+    let text = html
+        .root_element()
+        .text()
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut media = Vec::new();
+    for element in select(
+        html.root_element(),
+        "img[src], video[src], video source[src]",
+    ) {
+        push_unique(&mut media, element.value().attr("src").unwrap_or_default());
+    }
+    let mut links = Vec::new();
+    let mut domains = Vec::new();
+    for element in select(html.root_element(), "a[href]") {
+        let href = element.value().attr("href").unwrap_or_default();
+        push_unique(&mut links, href);
+        if let Some(domain) = link_domain(href, &element_text(element)) {
+            push_unique(&mut domains, &domain);
+        }
+    }
+    FeedValue {
+        text: (!text.is_empty()).then_some(text),
+        published_at: first_datetime(html),
+        media,
+        links,
+        domains,
+        ..FeedValue::default()
+    }
+}
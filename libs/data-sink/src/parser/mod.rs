@@ -0,0 +1,200 @@
+mod generic;
+mod x;
+
+use chrono::{DateTime, Utc};
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+// Bump when the parsers change, `parse_pending` re-parses the rows stored with an older version.
+pub const PARSER_VERSION: u32 = 2;
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FeedValue {
+    pub parser: String,
+    pub parser_version: u32,
+    pub author: Option<Author>,
+    pub text: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub engagement: Engagement,
+    pub media: Vec<String>,
+    pub links: Vec<String>,
+    #[serde(default)]
+    pub domains: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Author {
+    pub name: Option<String>,
+    pub handle: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Engagement {
+    pub replies: Option<u64>,
+    pub reposts: Option<u64>,
+    pub likes: Option<u64>,
+    pub views: Option<u64>,
+}
+
+pub fn parse_feed_element(origin: &str, raw: &str) -> FeedValue {
+    let html = Html::parse_fragment(raw);
+    let host = host(origin).unwrap_or_default();
+    let (parser, value) = match host.as_str() {
+        "x.com" | "twitter.com" | "mobile.twitter.com" => ("x", x::parse(&html)),
+        _ => ("generic", generic::parse(&html)),
+    };
+    FeedValue {
+        parser: parser.to_string(),
+        parser_version: PARSER_VERSION,
+        ..value
+    }
+}
+
+// Feeds wrap outbound links, the anchor text is the only place the real destination shows up.
+const SHORTENERS: [&str; 6] = [
+    "t.co",
+    "bit.ly",
+    "buff.ly",
+    "lnkd.in",
+    "ow.ly",
+    "tinyurl.com",
+];
+
+pub fn host(url: &str) -> Option<String> {
+    Url::parse(url).ok().and_then(|url| {
+        url.host_str()
+            .map(|host| host.trim_start_matches("www.").to_lowercase())
+    })
+}
+
+/// The domain a link points at, read from the display text for shortened links.
+pub(crate) fn link_domain(href: &str, text: &str) -> Option<String> {
+    let href_host = host(href)?;
+    if !SHORTENERS.contains(&href_host.as_str()) {
+        return Some(href_host);
+    }
+    let display = text
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.")
+        .split(['/', '?', '#'])
+        .next()?
+        .trim_end_matches('…')
+        .to_lowercase();
+    if display.contains('.') && !display.contains(char::is_whitespace) {
+        Some(display)
+    } else {
+        Some(href_host)
+    }
+}
+
+/// Elements under `element` matching `selector`, a selector that fails to parse matches nothing.
+pub(crate) fn select<'a>(
+    element: ElementRef<'a>,
+    selector: &str,
+) -> impl Iterator<Item = ElementRef<'a>> {
+    match Selector::parse(selector) {
+        Ok(parsed) => element.select(&parsed).collect::<Vec<_>>(),
+        Err(e) => {
+            tracing::error!("Invalid selector {}: {:?}", selector, e);
+            Vec::new()
+        }
+    }
+    .into_iter()
+}
+
+pub(crate) fn element_text(element: ElementRef) -> String {
+    element
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub(crate) fn first_datetime(html: &Html) -> Option<DateTime<Utc>> {
+    select(html.root_element(), "time[datetime]")
+        .filter_map(|time| time.value().attr("datetime"))
+        .find_map(|datetime| DateTime::parse_from_rfc3339(datetime).ok())
+        .map(|datetime| datetime.with_timezone(&Utc))
+}
+
+pub(crate) fn push_unique(values: &mut Vec<String>, value: &str) {
+    if !value.is_empty() && !values.iter().any(|v| v == value) {
+        values.push(value.to_string());
+    }
+}
+
+/// Reads counts the way feeds print them: "1,234", "1.2K", "3M" or "12 Likes. Like".
+pub(crate) fn parse_count(text: &str) -> Option<u64> {
+    let token = text
+        .split_whitespace()
+        .find(|token| token.starts_with(|c: char| c.is_ascii_digit()))?;
+    let token = token.replace(',', "");
+    let (number, multiplier) = match token.chars().last()? {
+        'K' | 'k' => (&token[..token.len() - 1], 1_000.0),
+        'M' | 'm' => (&token[..token.len() - 1], 1_000_000.0),
+        'B' | 'b' => (&token[..token.len() - 1], 1_000_000_000.0),
+        _ => (token.as_str(), 1.0),
+    };
+    let number: f64 = number.trim_end_matches('.').parse().ok()?;
+    Some((number * multiplier).round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_selectors_match_nothing() {
+        let html = Html::parse_fragment(r#"<p><a href="https://example.com">x</a></p>"#);
+        assert_eq!(select(html.root_element(), "a[href]").count(), 1);
+        assert_eq!(select(html.root_element(), "a[href").count(), 0);
+    }
+
+    #[test]
+    fn counts_are_normalized() {
+        assert_eq!(parse_count("1,234"), Some(1234));
+        assert_eq!(parse_count("1.2K"), Some(1200));
+        assert_eq!(parse_count("3M"), Some(3_000_000));
+        assert_eq!(parse_count("12 Likes. Like"), Some(12));
+        assert_eq!(parse_count("Like"), None);
+    }
+
+    #[test]
+    fn shortened_links_use_the_display_domain() {
+        assert_eq!(
+            link_domain("https://t.co/abc123", "blockmesh.xyz/stats").as_deref(),
+            Some("blockmesh.xyz")
+        );
+        assert_eq!(
+            link_domain("https://t.co/abc123", "https://www.Example.com/a…").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(
+            link_domain("https://t.co/abc123", "Read more").as_deref(),
+            Some("t.co")
+        );
+        assert_eq!(
+            link_domain("https://www.example.com/post", "click").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(link_domain("/relative", "blockmesh.xyz"), None);
+    }
+
+    #[test]
+    fn unknown_origins_use_the_generic_parser() {
+        let value = parse_feed_element(
+            "https://news.example.com",
+            r#"<div><p>Hello   <b>world</b></p><img src="https://cdn.example.com/a.png"><a href="https://example.com/post">more</a><time datetime="2024-11-20T10:00:00.000Z"></time></div>"#,
+        );
+        assert_eq!(value.parser, "generic");
+        assert_eq!(value.text.as_deref(), Some("Hello world more"));
+        assert_eq!(value.media, vec!["https://cdn.example.com/a.png"]);
+        assert_eq!(value.links, vec!["https://example.com/post"]);
+        assert_eq!(value.domains, vec!["example.com"]);
+        assert!(value.published_at.is_some());
+    }
+}
@@ -0,0 +1,132 @@
+use crate::parser::{
+    element_text, first_datetime, link_domain, parse_count, push_unique, select, Author,
+    Engagement, FeedValue,
+};
+use scraper::Html;
+
+pub fn parse(html: &Html) -> FeedValue {
+    let author = select(html.root_element(), r#"[data-testid="User-Name"]"#)
+        .next()
+        .map(|user_name| {
+            let spans: Vec<String> = select(user_name, "span")
+                .map(element_text)
+                .filter(|text| !text.is_empty() && text != "·")
+                .collect();
+            Author {
+                name: spans.iter().find(|text| !text.starts_with('@')).cloned(),
+                handle: spans
+                    .iter()
+                    .find(|text| text.starts_with('@'))
+                    .map(|handle| handle.trim_start_matches('@').to_string()),
+            }
+        });
+    let text = select(html.root_element(), r#"[data-testid="tweetText"]"#)
+        .next()
+        .map(element_text)
+        .filter(|text| !text.is_empty());
+    // aria-labels carry exact numbers, the visible text is rounded to "1.4K".
+    let count = |test_ids: &[&str]| {
+        test_ids.iter().find_map(|test_id| {
+            let element = select(
+                html.root_element(),
+                &format!(r#"[data-testid="{}"]"#, test_id),
+            )
+            .next()?;
+            element
+                .value()
+                .attr("aria-label")
+                .and_then(parse_count)
+                .or_else(|| parse_count(&element_text(element)))
+        })
+    };
+    let views = select(html.root_element(), r#"a[href$="/analytics"]"#)
+        .next()
+        .and_then(|element| {
+            element
+                .value()
+                .attr("aria-label")
+                .and_then(parse_count)
+                .or_else(|| parse_count(&element_text(element)))
+        });
+    let mut media = Vec::new();
+    for image in select(
+        html.root_element(),
+        r#"[data-testid="tweetPhoto"] img[src]"#,
+    ) {
+        push_unique(&mut media, image.value().attr("src").unwrap_or_default());
+    }
+    for video in select(html.root_element(), "video") {
+        // Video sources are blob: URLs that only live in the page, the poster is what we can keep.
+        let src = video.value().attr("src").unwrap_or_default();
+        if src.starts_with("http") {
+            push_unique(&mut media, src);
+        }
+        push_unique(&mut media, video.value().attr("poster").unwrap_or_default());
+    }
+    let mut links = Vec::new();
+    let mut domains = Vec::new();
+    for link in select(
+        html.root_element(),
+        r#"[data-testid="tweetText"] a[href^="http"], [data-testid="card.wrapper"] a[href^="http"]"#,
+    ) {
+        let href = link.value().attr("href").unwrap_or_default();
+        push_unique(&mut links, href);
+        if let Some(domain) = link_domain(href, &element_text(link)) {
+            push_unique(&mut domains, &domain);
+        }
+    }
+    FeedValue {
+        author,
+        text,
+        published_at: first_datetime(html),
+        engagement: Engagement {
+            replies: count(&["reply"]),
+            reposts: count(&["retweet", "unretweet"]),
+            likes: count(&["like", "unlike"]),
+            views,
+        },
+        media,
+        links,
+        domains,
+        ..FeedValue::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_feed_element;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn parses_x_articles() {
+        let value = parse_feed_element(
+            "https://x.com",
+            include_str!("../../fixtures/x_article.html"),
+        );
+        assert_eq!(value.parser, "x");
+        let author = value.author.unwrap();
+        assert_eq!(author.name.as_deref(), Some("BlockMesh Network"));
+        assert_eq!(author.handle.as_deref(), Some("blockmesh_xyz"));
+        assert_eq!(
+            value.text.as_deref(),
+            Some("Our node count just passed 100K! Thanks to everyone running #DePIN nodes blockmesh.xyz/stats")
+        );
+        assert_eq!(
+            value.published_at,
+            Some(Utc.with_ymd_and_hms(2024, 11, 20, 12, 34, 56).unwrap())
+        );
+        assert_eq!(value.engagement.replies, Some(57));
+        assert_eq!(value.engagement.reposts, Some(212));
+        assert_eq!(value.engagement.likes, Some(1404));
+        assert_eq!(value.engagement.views, Some(98765));
+        assert_eq!(
+            value.media,
+            vec![
+                "https://pbs.twimg.com/media/GcxYz1aXkAA1b2c?format=jpg&name=small",
+                "https://pbs.twimg.com/ext_tw_video_thumb/1859/pu/img/thumb.jpg",
+            ]
+        );
+        assert_eq!(value.links, vec!["https://t.co/abc123"]);
+        assert_eq!(value.domains, vec!["blockmesh.xyz"]);
+    }
+}
@@ -0,0 +1,202 @@
+use crate::data_sink::DataSinkRecord;
+use crate::errors::Error;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use uuid::Uuid;
+
+pub const DEFAULT_PAGE_SIZE: i64 = 100;
+pub const MAX_PAGE_SIZE: i64 = 1_000;
+pub const DEFAULT_ANALYTICS_DAYS: i64 = 30;
+pub const MAX_ANALYTICS_DAYS: i64 = 366;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Ndjson,
+    Parquet,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DataSinkQuery {
+    pub code: String,
+    pub origin: Option<String>,
+    pub user_id: Option<Uuid>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+    pub include_raw: Option<bool>,
+    pub format: Option<ExportFormat>,
+}
+
+/// A user's own rows, the filters of `DataSinkQuery` without `user_id` and the admin `code`.
+#[derive(Debug, Deserialize)]
+pub struct UserDataSinkRequest {
+    pub email: String,
+    pub api_token: Uuid,
+    pub origin: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+    pub include_raw: Option<bool>,
+}
+
+impl UserDataSinkRequest {
+    /// The authenticated user's `user_id` always replaces whatever filter the caller wanted.
+    pub fn query(self, user_id: Uuid) -> DataSinkQuery {
+        DataSinkQuery {
+            code: String::new(),
+            origin: self.origin,
+            user_id: Some(user_id),
+            since: self.since,
+            until: self.until,
+            cursor: self.cursor,
+            limit: self.limit,
+            include_raw: self.include_raw,
+            format: None,
+        }
+    }
+}
+
+pub fn authorize_admin(code: &str) -> Result<(), Error> {
+    if code.is_empty() || code != env::var("ADMIN_PARAM").unwrap_or_default() {
+        return Err(Error::Unauthorized("Bad admin param".to_string()));
+    }
+    Ok(())
+}
+
+/// Inclusive day range, defaults to the last `DEFAULT_ANALYTICS_DAYS` days.
+pub fn analytics_range(
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+) -> Result<(NaiveDate, NaiveDate), Error> {
+    let until = until.unwrap_or_else(|| Utc::now().date_naive());
+    let since = since.unwrap_or(until - Duration::days(DEFAULT_ANALYTICS_DAYS - 1));
+    if since > until {
+        return Err(Error::BadRequest("since is after until".to_string()));
+    }
+    if (until - since).num_days() >= MAX_ANALYTICS_DAYS {
+        return Err(Error::BadRequest(format!(
+            "Ranges are limited to {} days",
+            MAX_ANALYTICS_DAYS
+        )));
+    }
+    Ok((since, until))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AnalyticsQuery {
+    pub code: String,
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserDataQuery {
+    pub code: String,
+    pub user_id: Uuid,
+}
+
+impl DataSinkQuery {
+    pub fn authorize(&self) -> Result<(), Error> {
+        authorize_admin(&self.code)
+    }
+
+    pub fn cursor(&self) -> Result<Option<Cursor>, Error> {
+        self.cursor
+            .as_deref()
+            .map(Cursor::from_str)
+            .transpose()
+            .map_err(|_| Error::BadRequest("Bad cursor".to_string()))
+    }
+
+    pub fn limit(&self) -> i64 {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE)
+    }
+}
+
+/// Keyset position, rows are always ordered by `(created_at, id)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl Display for Cursor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}_{}", self.created_at.timestamp_micros(), self.id)
+    }
+}
+
+impl FromStr for Cursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (micros, id) = s
+            .split_once('_')
+            .ok_or_else(|| anyhow::anyhow!("Missing cursor separator"))?;
+        Ok(Self {
+            created_at: DateTime::from_timestamp_micros(micros.parse()?)
+                .ok_or_else(|| anyhow::anyhow!("Cursor timestamp out of range"))?,
+            id: Uuid::from_str(id)?,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DataSinkPage {
+    pub items: Vec<DataSinkRecord>,
+    pub next_cursor: Option<String>,
+}
+
+impl DataSinkPage {
+    pub fn new(items: Vec<DataSinkRecord>, limit: i64) -> Self {
+        let next_cursor = if items.len() as i64 == limit {
+            items.last().map(|item| item.cursor().to_string())
+        } else {
+            None
+        };
+        Self { items, next_cursor }
+    }
+}
+
+#[test]
+fn test_cursor_round_trip() {
+    let cursor = Cursor {
+        created_at: DateTime::from_timestamp_micros(1_732_100_000_123_456).unwrap(),
+        id: Uuid::new_v4(),
+    };
+    assert_eq!(Cursor::from_str(&cursor.to_string()).unwrap(), cursor);
+    assert!(Cursor::from_str("not-a-cursor").is_err());
+}
+
+#[test]
+fn test_user_query_is_scoped_to_the_user() {
+    let user_id = Uuid::new_v4();
+    let request: UserDataSinkRequest = serde_json::from_value(serde_json::json!({
+        "email": "user@example.com",
+        "api_token": Uuid::new_v4(),
+        "user_id": Uuid::new_v4(),
+        "code": "admin",
+    }))
+    .unwrap();
+    let query = request.query(user_id);
+    assert_eq!(query.user_id, Some(user_id));
+    assert!(query.authorize().is_err());
+}
+
+#[test]
+fn test_analytics_range() {
+    let day = |d| NaiveDate::from_ymd_opt(2024, 12, d).unwrap();
+    let (since, until) = analytics_range(None, Some(day(30))).unwrap();
+    assert_eq!((since, until), (day(1), day(30)));
+    assert!(analytics_range(Some(day(2)), Some(day(1))).is_err());
+    assert!(analytics_range(Some(day(1) - Duration::days(400)), Some(day(1))).is_err());
+}
@@ -1,17 +1,41 @@
-use crate::data_sink::DataSink;
-use crate::database::get_user_and_api_token_by_email;
+use crate::analytics::feed_analytics::FeedAnalytics;
+use crate::auth::authenticate;
+use crate::data_sink::{batch_results, validate_feed_element, DataSink};
 use crate::errors::Error;
-use crate::AppState;
-use anyhow::anyhow;
-use axum::extract::State;
+use crate::export::{ndjson_stream, parquet_export};
+use crate::parser::parse_feed_element;
+use crate::query::{
+    analytics_range, authorize_admin, AnalyticsQuery, DataSinkPage, DataSinkQuery, ExportFormat,
+    UserDataQuery, UserDataSinkRequest,
+};
+use crate::{AppState, CachedComposition};
+use axum::body::Body;
+use axum::extract::{DefaultBodyLimit, Query, State};
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::http::HeaderMap;
 use axum::response::IntoResponse;
-use axum::routing::{get, post};
+use axum::routing::{delete, get, post};
 use axum::{Json, Router};
-use block_mesh_common::interfaces::server_api::DigestDataRequest;
+use block_mesh_common::interfaces::server_api::{
+    DigestDataBatchRequest, DigestDataBatchResponse, DigestDataRequest, FeedAnalyticsRequest,
+    FeedAnalyticsResponse, FeedComposition, TokenScope,
+};
+use chrono::NaiveDate;
 use database_utils::utils::health_check::health_check;
 use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
 use reqwest::StatusCode;
-use validator::validate_email;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+use tower_http::decompression::RequestDecompressionLayer;
+
+const MAX_BATCH_SIZE: usize = 500;
+// Applies after decompression, so a small gzip body can't expand without bound.
+const MAX_BATCH_BODY_BYTES: usize = 32 * 1024 * 1024;
+const ANALYTICS_TOP_KEYS: i64 = 10;
+// The global composition scans every user's rows, dashboards share one result for a while.
+const GLOBAL_ANALYTICS_TTL: Duration = Duration::from_secs(600);
+// Ranges come from the request, so the number of cached ranges has to be bounded.
+const GLOBAL_ANALYTICS_CACHE_ENTRIES: usize = 64;
 
 #[tracing::instrument(name = "db_health", skip_all)]
 pub async fn db_health(State(state): State<AppState>) -> Result<impl IntoResponse, Error> {
@@ -37,37 +61,262 @@ pub async fn server_health() -> Result<impl IntoResponse, Error> {
 }
 
 pub async fn digest_data(
+    headers: HeaderMap,
     State(state): State<AppState>,
     Json(body): Json<DigestDataRequest>,
 ) -> Result<impl IntoResponse, Error> {
-    if !validate_email(&body.email) {
-        return Err(Error::from(anyhow!("BadEmail")));
+    let user_id = authenticate(
+        &state,
+        &headers,
+        &body.email,
+        &body.api_token,
+        TokenScope::NodeReporting,
+    )
+    .await?;
+    validate_feed_element(&body.data).map_err(Error::BadRequest)?;
+    let value = serde_json::to_value(parse_feed_element(&body.data.origin, &body.data.raw))
+        .map_err(anyhow::Error::from)?;
+    let data_sink_db_pool = &state.data_sink_db_pool;
+    let mut transaction = create_txn(data_sink_db_pool).await?;
+    let inserted = DataSink::create_data_sink(&mut transaction, &user_id, body.data, value).await?;
+    commit_txn(transaction).await?;
+    if !inserted {
+        return Ok((StatusCode::ALREADY_REPORTED, "Already reported"));
     }
-    let follower_db_pool = &state.follower_db_pool;
-    let mut transaction = create_txn(follower_db_pool).await?;
-    let user = get_user_and_api_token_by_email(&mut transaction, &body.email)
-        .await?
-        .ok_or_else(|| anyhow!("UserNotFound"))?;
-    if user.token.as_ref() != &body.api_token {
-        commit_txn(transaction).await?;
-        return Err(Error::from(anyhow!("ApiTokenNotFound")));
+    Ok((StatusCode::OK, "OK"))
+}
+
+#[tracing::instrument(name = "digest_data_batch", skip_all)]
+pub async fn digest_data_batch(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Json(body): Json<DigestDataBatchRequest>,
+) -> Result<Json<DigestDataBatchResponse>, Error> {
+    if body.data.len() > MAX_BATCH_SIZE {
+        return Err(Error::BadRequest(format!(
+            "Batches are limited to {} elements",
+            MAX_BATCH_SIZE
+        )));
     }
+    let user_id = authenticate(
+        &state,
+        &headers,
+        &body.email,
+        &body.api_token,
+        TokenScope::NodeReporting,
+    )
+    .await?;
+    let mut valid = Vec::with_capacity(body.data.len());
+    for element in body
+        .data
+        .iter()
+        .filter(|e| validate_feed_element(e).is_ok())
+    {
+        let value = serde_json::to_value(parse_feed_element(&element.origin, &element.raw))
+            .map_err(anyhow::Error::from)?;
+        valid.push((element.clone(), value));
+    }
+    let inserted = if valid.is_empty() {
+        HashSet::new()
+    } else {
+        let mut transaction = create_txn(&state.data_sink_db_pool).await?;
+        let inserted = DataSink::create_data_sinks(&mut transaction, &user_id, &valid).await?;
+        commit_txn(transaction).await?;
+        inserted
+    };
+    Ok(Json(DigestDataBatchResponse {
+        results: batch_results(&body.data, inserted),
+    }))
+}
+
+#[tracing::instrument(name = "get_data_sinks", skip_all)]
+pub async fn get_data_sinks(
+    State(state): State<AppState>,
+    Query(query): Query<DataSinkQuery>,
+) -> Result<Json<DataSinkPage>, Error> {
+    query.authorize()?;
+    let cursor = query.cursor()?;
+    let limit = query.limit();
+    let mut transaction = create_txn(&state.data_sink_db_pool).await?;
+    let items = DataSink::query(&mut transaction, &query, cursor.as_ref(), limit).await?;
     commit_txn(transaction).await?;
-    let data_sink_db_pool = &state.data_sink_db_pool;
-    let mut transaction = create_txn(data_sink_db_pool).await?;
-    let result = DataSink::create_data_sink(&mut transaction, &user.user_id, body.data).await;
-    if let Err(error) = result {
-        if error
-            .to_string()
-            .contains("duplicate key value violates unique constraint")
-        {
-            return Ok((StatusCode::ALREADY_REPORTED, "Already reported"));
+    Ok(Json(DataSinkPage::new(items, limit)))
+}
+
+#[tracing::instrument(name = "get_user_data_sinks", skip_all)]
+pub async fn get_user_data_sinks(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Json(body): Json<UserDataSinkRequest>,
+) -> Result<Json<DataSinkPage>, Error> {
+    let user_id = authenticate(
+        &state,
+        &headers,
+        &body.email,
+        &body.api_token,
+        TokenScope::DashboardRead,
+    )
+    .await?;
+    let query = body.query(user_id);
+    let cursor = query.cursor()?;
+    let limit = query.limit();
+    let mut transaction = create_txn(&state.data_sink_db_pool).await?;
+    let items = DataSink::query(&mut transaction, &query, cursor.as_ref(), limit).await?;
+    commit_txn(transaction).await?;
+    Ok(Json(DataSinkPage::new(items, limit)))
+}
+
+#[tracing::instrument(name = "export_data_sinks", skip_all)]
+pub async fn export_data_sinks(
+    State(state): State<AppState>,
+    Query(query): Query<DataSinkQuery>,
+) -> Result<impl IntoResponse, Error> {
+    query.authorize()?;
+    query.cursor()?;
+    match query.format.unwrap_or_default() {
+        ExportFormat::Ndjson => Ok((
+            [
+                (CONTENT_TYPE, "application/x-ndjson"),
+                (
+                    CONTENT_DISPOSITION,
+                    "attachment; filename=\"data_sinks.ndjson\"",
+                ),
+            ],
+            Body::from_stream(ndjson_stream(state.data_sink_db_pool.clone(), query)),
+        )),
+        ExportFormat::Parquet => {
+            let parquet = parquet_export(&state.data_sink_db_pool, &query).await?;
+            Ok((
+                [
+                    (CONTENT_TYPE, "application/vnd.apache.parquet"),
+                    (
+                        CONTENT_DISPOSITION,
+                        "attachment; filename=\"data_sinks.parquet\"",
+                    ),
+                ],
+                Body::from(parquet),
+            ))
         }
     }
+}
+
+/// Removes everything stored for a user, called by the manager worker when an account is deleted.
+#[tracing::instrument(name = "delete_user_data", skip_all)]
+pub async fn delete_user_data(
+    State(state): State<AppState>,
+    Query(query): Query<UserDataQuery>,
+) -> Result<impl IntoResponse, Error> {
+    authorize_admin(&query.code)?;
+    let mut transaction = create_txn(&state.data_sink_db_pool).await?;
+    let data_sinks = DataSink::delete_by_user(&mut transaction, &query.user_id).await?;
+    let feed_analytics = FeedAnalytics::delete_by_user(&mut transaction, &query.user_id).await?;
     commit_txn(transaction).await?;
+    // The global composition was aggregated over the deleted rows too.
+    state
+        .auth_cache
+        .retain(|_, cached| cached.user_id != query.user_id);
+    state.analytics_cache.clear();
+    tracing::info!(
+        "Deleted {} data sinks and {} feed analytics rows of {}",
+        data_sinks,
+        feed_analytics,
+        query.user_id
+    );
     Ok((StatusCode::OK, "OK"))
 }
 
+async fn global_composition(
+    state: &AppState,
+    since: NaiveDate,
+    until: NaiveDate,
+) -> Result<Vec<FeedComposition>, Error> {
+    if let Some(cached) = state.analytics_cache.get(&(since, until)) {
+        if cached.cached_at.elapsed() < GLOBAL_ANALYTICS_TTL {
+            return Ok(cached.compositions.clone());
+        }
+    }
+    let mut transaction = create_txn(&state.data_sink_db_pool).await?;
+    let compositions =
+        FeedAnalytics::composition(&mut transaction, None, since, until, ANALYTICS_TOP_KEYS)
+            .await?;
+    commit_txn(transaction).await?;
+    state
+        .analytics_cache
+        .retain(|_, cached| cached.cached_at.elapsed() < GLOBAL_ANALYTICS_TTL);
+    if state.analytics_cache.len() >= GLOBAL_ANALYTICS_CACHE_ENTRIES {
+        return Ok(compositions);
+    }
+    state.analytics_cache.insert(
+        (since, until),
+        CachedComposition {
+            compositions: compositions.clone(),
+            cached_at: Instant::now(),
+        },
+    );
+    Ok(compositions)
+}
+
+#[tracing::instrument(name = "feed_analytics", skip_all)]
+pub async fn feed_analytics(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Json(body): Json<FeedAnalyticsRequest>,
+) -> Result<Json<FeedAnalyticsResponse>, Error> {
+    let user_id = authenticate(
+        &state,
+        &headers,
+        &body.email,
+        &body.api_token,
+        TokenScope::DashboardRead,
+    )
+    .await?;
+    let (since, until) = analytics_range(body.since, body.until)?;
+    let timeline_dimension = body.timeline.unwrap_or_default();
+    let mut transaction = create_txn(&state.data_sink_db_pool).await?;
+    let user = FeedAnalytics::composition(
+        &mut transaction,
+        Some(&user_id),
+        since,
+        until,
+        ANALYTICS_TOP_KEYS,
+    )
+    .await?;
+    let keys: Vec<String> = user
+        .iter()
+        .filter(|composition| composition.dimension == timeline_dimension)
+        .flat_map(|composition| composition.top.iter().map(|share| share.key.clone()))
+        .collect();
+    let timeline = FeedAnalytics::timeline(
+        &mut transaction,
+        &user_id,
+        timeline_dimension,
+        since,
+        until,
+        &keys,
+    )
+    .await?;
+    commit_txn(transaction).await?;
+    let global = global_composition(&state, since, until).await?;
+    Ok(Json(FeedAnalyticsResponse {
+        since,
+        until,
+        user,
+        global,
+        timeline_dimension,
+        timeline,
+    }))
+}
+
+#[tracing::instrument(name = "aggregate_feed_analytics", skip_all)]
+pub async fn aggregate_feed_analytics(
+    State(state): State<AppState>,
+    Query(query): Query<AnalyticsQuery>,
+) -> Result<Json<Vec<FeedComposition>>, Error> {
+    authorize_admin(&query.code)?;
+    let (since, until) = analytics_range(query.since, query.until)?;
+    Ok(Json(global_composition(&state, since, until).await?))
+}
+
 #[tracing::instrument(name = "version", skip_all)]
 pub async fn version() -> impl IntoResponse {
     (StatusCode::OK, env!("CARGO_PKG_VERSION"))
@@ -80,5 +329,17 @@ pub fn get_router(state: AppState) -> Router {
         .route("/follower_health", get(follower_health))
         .route("/version", get(version))
         .route("/digest_data", post(digest_data))
+        .merge(
+            Router::new()
+                .route("/digest_data_batch", post(digest_data_batch))
+                .layer(RequestDecompressionLayer::new())
+                .layer(DefaultBodyLimit::max(MAX_BATCH_BODY_BYTES)),
+        )
+        .route("/data_sinks", get(get_data_sinks))
+        .route("/data_sinks/export", get(export_data_sinks))
+        .route("/data_sinks/mine", post(get_user_data_sinks))
+        .route("/data_sinks/user", delete(delete_user_data))
+        .route("/analytics", post(feed_analytics))
+        .route("/analytics/aggregate", get(aggregate_feed_analytics))
         .with_state(state)
 }
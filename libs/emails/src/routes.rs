@@ -5,6 +5,8 @@ use axum::extract::{Query, State};
 use axum::response::IntoResponse;
 use axum::routing::get;
 use axum::Router;
+use block_mesh_common::email_client::locale::EmailLocale;
+use block_mesh_common::email_client::template::EmailTemplate;
 use block_mesh_common::interfaces::server_api::SendEmail;
 use database_utils::utils::health_check::health_check;
 use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
@@ -36,43 +38,33 @@ pub async fn send_email(
         Err(Error::InternalServer("Bad admin param".to_string()))
     } else {
         let email_type = EmailType::from(send_params.email_type);
-        match email_type {
-            EmailType::ConfirmEmail => {
-                let mut transaction = create_txn(&state.emails_db_pool).await?;
-                let result = state
-                    .email_client
-                    .send_confirmation_email_aws(&send_params.email_address, &send_params.nonce)
-                    .await?;
-                Email::create_email(
-                    &mut transaction,
-                    &send_params.user_id,
-                    &email_type,
-                    &send_params.email_address,
-                    &result.message_id.unwrap_or_default(),
-                )
-                .await?;
-                commit_txn(transaction).await?;
-                Ok(StatusCode::OK)
+        let template = match email_type {
+            EmailType::ConfirmEmail => EmailTemplate::ConfirmEmail {
+                token: send_params.nonce,
+            },
+            EmailType::ResetPassword => EmailTemplate::ResetPassword {
+                token: send_params.nonce,
+            },
+            EmailType::Unknown => {
+                return Err(Error::InternalServer("Unknown email type".to_string()))
             }
-            EmailType::ResetPassword => {
-                let mut transaction = create_txn(&state.emails_db_pool).await?;
-                let result = state
-                    .email_client
-                    .send_reset_password_email_aws(&send_params.email_address, &send_params.nonce)
-                    .await?;
-                Email::create_email(
-                    &mut transaction,
-                    &send_params.user_id,
-                    &email_type,
-                    &send_params.email_address,
-                    &result.message_id.unwrap_or_default(),
-                )
-                .await?;
-                commit_txn(transaction).await?;
-                Ok(StatusCode::OK)
-            }
-            EmailType::Unknown => Err(Error::InternalServer("Unknown email type".to_string())),
-        }
+        };
+        let email = template.render(EmailLocale::default(), &state.email_client.base_url, None)?;
+        let message_id = state
+            .email_client
+            .send_aws(&send_params.email_address, &email)
+            .await?;
+        let mut transaction = create_txn(&state.emails_db_pool).await?;
+        Email::create_email(
+            &mut transaction,
+            &send_params.user_id,
+            &email_type,
+            &send_params.email_address,
+            &message_id,
+        )
+        .await?;
+        commit_txn(transaction).await?;
+        Ok(StatusCode::OK)
     }
 }
 #[tracing::instrument(name = "version", skip_all)]
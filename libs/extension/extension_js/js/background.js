@@ -7,12 +7,15 @@ import initWasmModule, {
   measure_bandwidth,
   stop_websocket,
   read_dom,
-  feed_setup
+  feed_setup,
+  flush_feed_queue,
+  read_flag
 } from './wasm/blockmesh_ext.js'
 
 console.log('Background script started')
 
 const PING_INTERVAL = 3 * 1000
+const FEED_FLUSH_INTERVAL = 30 * 1000
 
 // This keeps the service worker alive
 function stayAlive() {
@@ -63,18 +66,18 @@ let polling_interval = 120000
 let intervals = []
 
 
+// Flags are evaluated against the ruleset in wasm, `undefined` when the flag can't be read
+async function get_flag(name) {
+  const value = await read_flag(name)
+  return value === undefined ? undefined : JSON.parse(value)
+}
+
 async function is_ws_feature_connection() {
   try {
-    const response1 = await fetch('https://feature-flags.blockmesh.xyz/extension/read-flag/extension_use_websocket')
-    if (response1.ok) {
-      const value = await response1.text()
-      const is_enabled = value === 'true'
-      if (!is_enabled) return false
-    }
-    const response2 = await fetch('https://feature-flags.blockmesh.xyz/extension/read-flag/extension_use_websocket_percent')
-    if (response2.ok) {
-      const value = await response2.text()
-      const percentage = parseInt(value, 10)
+    const is_enabled = await get_flag('extension_use_websocket')
+    if (is_enabled !== undefined && is_enabled !== true) return false
+    const percentage = parseFloat(await get_flag('extension_use_websocket_percent'))
+    if (!isNaN(percentage)) {
       const probe = Math.random() * 100
       return probe < percentage
     }
@@ -86,13 +89,9 @@ async function is_ws_feature_connection() {
 
 async function get_polling_interval() {
   try {
-    const response = await fetch('https://feature-flags.blockmesh.xyz/extension/read-flag/extension_polling_interval')
-    if (response.ok) {
-      const value = await response.text()
-      const num = parseFloat(value)
-      if (!isNaN(num)) {
-        return num
-      }
+    const num = parseFloat(await get_flag('extension_polling_interval'))
+    if (!isNaN(num)) {
+      return num
     }
     return 120000
   } catch (_) {
@@ -145,6 +144,9 @@ async function init_background() {
     periodInMinutes: 0.55
   })
   await feed_setup()
+  setInterval(async () => {
+    await flush_feed_queue()
+  }, FEED_FLUSH_INTERVAL)
   await main_interval()
   setInterval(async () => {
     await main_interval()
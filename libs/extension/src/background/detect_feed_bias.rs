@@ -1,15 +1,61 @@
+use crate::utils::connectors::{get_local_storage_value, set_local_storage_value};
 use crate::utils::extension_wrapper_state::ExtensionWrapperState;
 use block_mesh_common::constants::DeviceType;
-use block_mesh_common::interfaces::server_api::{DigestDataRequest, FeedElement};
+use block_mesh_common::interfaces::server_api::{
+    DigestDataBatchRequest, DigestDataBatchResponse, DigestDataStatus, FeedElement,
+};
 use block_mesh_common::reqwest::http_client;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use leptos::logging::log;
 use regex::Regex;
 use scraper::{Html, Selector};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
 use std::str::FromStr;
 use std::string::ToString;
+use std::sync::atomic::{AtomicBool, Ordering};
 use uuid::Uuid;
 use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+const FEED_QUEUE_KEY: &str = "feed_queue";
+const FEED_QUEUE_RETRY_KEY: &str = "feed_queue_retry";
+const MAX_QUEUE_LEN: usize = 1_000;
+// chrome.storage.local holds 10MB without the unlimitedStorage permission, and the queue is
+// stored as a JSON string so quotes are escaped once more on the way in.
+const MAX_QUEUE_BYTES: usize = 4 * 1024 * 1024;
+const BATCH_SIZE: usize = 100;
+const BASE_BACKOFF_MS: i64 = 5_000;
+const MAX_BACKOFF_MS: i64 = 10 * 60 * 1_000;
+
+static FLUSHING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RetryState {
+    attempts: u32,
+    next_attempt_at: i64,
+}
+
+impl RetryState {
+    fn failed(self) -> Self {
+        let attempts = self.attempts.saturating_add(1);
+        let backoff = BASE_BACKOFF_MS
+            .saturating_mul(1 << attempts.min(16))
+            .min(MAX_BACKOFF_MS);
+        Self {
+            attempts,
+            next_attempt_at: chrono::Utc::now().timestamp_millis() + backoff,
+        }
+    }
+}
+
+struct Credentials {
+    data_sink_url: String,
+    email: String,
+    api_token: Uuid,
+}
 
 #[wasm_bindgen]
 pub async fn feed_setup() {
@@ -17,8 +63,7 @@ pub async fn feed_setup() {
     ExtensionWrapperState::store_feed_selector(env!("FEED_SELECTOR").to_string()).await;
 }
 
-#[wasm_bindgen]
-pub async fn read_dom(html: String, origin: String) {
+async fn get_credentials() -> Option<Credentials> {
     let mut blockmesh_data_sink_url = ExtensionWrapperState::get_blockmesh_data_sink_url().await;
     if blockmesh_data_sink_url.is_empty() {
         blockmesh_data_sink_url = "https://data-sink.blockmesh.xyz".to_string();
@@ -33,11 +78,23 @@ pub async fn read_dom(html: String, origin: String) {
         || api_token.is_nil()
     {
         log!(
-            "early return from read_dom => url = {} , email = {} , api_token = {}",
+            "missing data sink credentials => url = {} , email = {} , api_token = {}",
             blockmesh_data_sink_url,
             email,
             api_token
         );
+        return None;
+    }
+    Some(Credentials {
+        data_sink_url: blockmesh_data_sink_url,
+        email,
+        api_token,
+    })
+}
+
+#[wasm_bindgen]
+pub async fn read_dom(html: String, origin: String) {
+    if get_credentials().await.is_none() {
         return;
     }
 
@@ -62,20 +119,147 @@ pub async fn read_dom(html: String, origin: String) {
     }
     match FeedElement::try_from(map) {
         Ok(feed_element) => {
-            let client = http_client(DeviceType::Extension);
-            let body: DigestDataRequest = DigestDataRequest {
-                email,
-                api_token,
-                data: feed_element,
-            };
-            let _ = client
-                .post(format!("{}/digest_data", blockmesh_data_sink_url))
-                .json(&body)
-                .send()
-                .await;
+            if enqueue(feed_element).await >= BATCH_SIZE {
+                flush_feed_queue().await;
+            }
         }
         Err(e) => {
             log!("error = {:?}", e);
         }
     }
 }
+
+/// Sends queued feed elements to the data sink in gzip compressed batches.
+/// Failed uploads stay queued and are retried with exponential backoff.
+#[wasm_bindgen]
+pub async fn flush_feed_queue() {
+    if FLUSHING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    if let Err(e) = flush().await {
+        log!("flush_feed_queue error = {:?}", e);
+    }
+    FLUSHING.store(false, Ordering::SeqCst);
+}
+
+async fn flush() -> anyhow::Result<()> {
+    let retry: RetryState = load(FEED_QUEUE_RETRY_KEY).await.unwrap_or_default();
+    if chrono::Utc::now().timestamp_millis() < retry.next_attempt_at {
+        return Ok(());
+    }
+    let Some(credentials) = get_credentials().await else {
+        return Ok(());
+    };
+    loop {
+        let queue = load_queue().await;
+        if queue.is_empty() {
+            return Ok(());
+        }
+        let batch: Vec<FeedElement> = queue.into_iter().take(BATCH_SIZE).collect();
+        let sent: HashSet<(String, String)> = batch.iter().map(queue_key).collect();
+        if let Err(e) = send_batch(&credentials, batch).await {
+            store(FEED_QUEUE_RETRY_KEY, &retry.failed()).await?;
+            return Err(e);
+        }
+        // Re-read the queue, elements may have been added while the request was in flight
+        let mut queue = load_queue().await;
+        queue.retain(|element| !sent.contains(&queue_key(element)));
+        store(FEED_QUEUE_KEY, &queue).await?;
+        store(FEED_QUEUE_RETRY_KEY, &RetryState::default()).await?;
+    }
+}
+
+async fn send_batch(credentials: &Credentials, data: Vec<FeedElement>) -> anyhow::Result<()> {
+    let body = DigestDataBatchRequest {
+        email: credentials.email.clone(),
+        api_token: credentials.api_token,
+        data,
+    };
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&serde_json::to_vec(&body)?)?;
+    let compressed = encoder.finish()?;
+    let response = http_client(DeviceType::Extension)
+        .post(format!("{}/digest_data_batch", credentials.data_sink_url))
+        .header("Content-Type", "application/json")
+        .header("Content-Encoding", "gzip")
+        .body(compressed)
+        .send()
+        .await?;
+    let status = response.status();
+    if status.is_success() {
+        let response: DigestDataBatchResponse = response.json().await?;
+        for result in response
+            .results
+            .iter()
+            .filter(|result| result.status == DigestDataStatus::Invalid)
+        {
+            log!("data sink rejected {} => {:?}", result.id, result.error);
+        }
+        return Ok(());
+    }
+    // A malformed batch will never be accepted, drop it instead of retrying forever
+    if status.as_u16() == 400 {
+        log!(
+            "data sink rejected batch => {}",
+            response.text().await.unwrap_or_default()
+        );
+        return Ok(());
+    }
+    Err(anyhow::anyhow!("data sink responded with {}", status))
+}
+
+async fn enqueue(element: FeedElement) -> usize {
+    let mut queue = load_queue().await;
+    let key = queue_key(&element);
+    if queue.iter().any(|queued| queue_key(queued) == key) {
+        return queue.len();
+    }
+    queue.push(element);
+    trim_queue(&mut queue);
+    if let Err(e) = store(FEED_QUEUE_KEY, &queue).await {
+        log!("failed to store feed queue => {:?}", e);
+    }
+    queue.len()
+}
+
+/// Keeps the newest elements that fit both the length and the byte budget.
+fn trim_queue(queue: &mut Vec<FeedElement>) {
+    let mut bytes = 0;
+    let keep = queue
+        .iter()
+        .rev()
+        .take(MAX_QUEUE_LEN)
+        .take_while(|element| {
+            bytes += serde_json::to_string(element)
+                .map(|json| json.len())
+                .unwrap_or_default();
+            bytes <= MAX_QUEUE_BYTES
+        })
+        .count();
+    queue.drain(..queue.len() - keep);
+}
+
+fn queue_key(element: &FeedElement) -> (String, String) {
+    (element.origin.clone(), element.id.clone())
+}
+
+async fn load_queue() -> Vec<FeedElement> {
+    load(FEED_QUEUE_KEY).await.unwrap_or_default()
+}
+
+async fn load<T: for<'de> Deserialize<'de>>(key: &str) -> Option<T> {
+    let value = get_local_storage_value(key).await.as_string()?;
+    serde_json::from_str(&value).ok()
+}
+
+async fn store<T: Serialize>(key: &str, value: &T) -> anyhow::Result<()> {
+    let value = serde_json::to_string(value)?;
+    let error = set_local_storage_value(key, JsValue::from_str(&value))
+        .await
+        .as_string()
+        .unwrap_or_default();
+    if !error.is_empty() {
+        return Err(anyhow::anyhow!("failed to store {} => {}", key, error));
+    }
+    Ok(())
+}
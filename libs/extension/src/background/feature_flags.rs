@@ -0,0 +1,51 @@
+use crate::utils::check_token::check_token;
+use crate::utils::extension_wrapper_state::ExtensionWrapperState;
+use block_mesh_common::constants::DeviceType;
+use block_mesh_common::feature_flag_client::get_ruleset;
+use block_mesh_common::feature_flags::FlagContext;
+use block_mesh_common::interfaces::server_api::CheckTokenRequest;
+use leptos::logging::log;
+use speed_test::metadata::fetch_metadata;
+use std::str::FromStr;
+use uuid::Uuid;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Evaluates a flag with the same rules as the CLI, returns its value as JSON.
+#[wasm_bindgen]
+pub async fn read_flag(name: String) -> Option<String> {
+    let ruleset = match get_ruleset(&reqwest::Client::new(), None).await {
+        Ok(ruleset) => ruleset?.0,
+        Err(e) => {
+            log!("read_flag error = {:?}", e);
+            return None;
+        }
+    };
+    let value = ruleset.evaluate(&name, &flag_context().await)?;
+    serde_json::to_string(&value).ok()
+}
+
+async fn flag_context() -> FlagContext {
+    let blockmesh_url = ExtensionWrapperState::get_blockmesh_url().await;
+    let email = ExtensionWrapperState::get_email().await;
+    let api_token = Uuid::from_str(&ExtensionWrapperState::get_api_token().await).ok();
+    let user_id = match api_token {
+        Some(api_token) if !email.is_empty() => {
+            check_token(&blockmesh_url, &CheckTokenRequest { email, api_token })
+                .await
+                .ok()
+                .and_then(|response| response.user_id)
+        }
+        _ => None,
+    };
+    let country = fetch_metadata()
+        .await
+        .ok()
+        .map(|metadata| metadata.country)
+        .filter(|country| !country.is_empty());
+    FlagContext {
+        version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        user_id,
+        country,
+        ..FlagContext::new(DeviceType::Extension)
+    }
+}
@@ -1,5 +1,6 @@
 pub mod bandwidth_measurement;
 pub mod detect_feed_bias;
+pub mod feature_flags;
 pub mod operation_mode;
 pub mod tasks;
 pub mod tasks_manager;
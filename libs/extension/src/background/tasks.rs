@@ -21,6 +21,7 @@ pub async fn get_task(
     let body: GetTaskRequest = GetTaskRequest {
         email: email.to_string(),
         api_token: *api_token,
+        node_key_id: None,
     };
 
     let response: Option<GetTaskResponse> = reqwest::Client::new()
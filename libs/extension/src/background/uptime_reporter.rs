@@ -49,6 +49,7 @@ pub async fn report_uptime_inner(
         email: email.to_string(),
         api_token: *api_token,
         ip: ip.clone(),
+        report_id: Some(Uuid::new_v4()),
     };
 
     match operation_mode {
@@ -72,6 +73,7 @@ pub async fn report_uptime_inner(
             email: email.to_string(),
             api_token: *api_token,
             ip: ip.clone(),
+            report_id: Some(Uuid::new_v4()),
         }),
     }
 }
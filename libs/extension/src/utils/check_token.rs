@@ -1,12 +1,11 @@
 use block_mesh_common::constants::DeviceType;
-use block_mesh_common::interfaces::server_api::CheckTokenRequest;
+use block_mesh_common::interfaces::server_api::{CheckTokenRequest, GetTokenResponse};
 use block_mesh_common::routes_enum::RoutesEnum;
 
-#[allow(dead_code)]
 pub async fn check_token(
     blockmesh_url: &str,
     credentials: &CheckTokenRequest,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<GetTokenResponse> {
     let blockmesh_url = if blockmesh_url.contains("app") {
         blockmesh_url.replace("app", "api")
     } else {
@@ -24,7 +23,7 @@ pub async fn check_token(
         .header("Content-Type", "application/json")
         .json(&credentials)
         .send()
-        .await?;
-    let _: serde_json::Value = r.json().await?;
-    Ok(())
+        .await?
+        .error_for_status()?;
+    Ok(r.json().await?)
 }
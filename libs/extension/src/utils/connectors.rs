@@ -106,6 +106,39 @@ extern "C" {
     pub async fn set_storage_value(key: &str, value: JsValue) -> JsValue;
 }
 
+#[wasm_bindgen(inline_js = r#"
+    export async function get_local_storage_value(key) {
+        try {
+            let result = await chrome.storage.local.get(key);
+            if (result[key]) {
+                return `${result[key]}`;
+            }
+            return "";
+        } catch (e) {
+            return ""
+        }
+    };
+"#)]
+extern "C" {
+    /// chrome.storage.local has no per-item quota, unlike chrome.storage.sync
+    pub async fn get_local_storage_value(key: &str) -> JsValue;
+}
+
+#[wasm_bindgen(inline_js = r#"
+    export async function set_local_storage_value(key, value) {
+        try {
+            await chrome.storage.local.set({ [key]: value });
+            return "";
+        } catch (e) {
+            return `${e}`;
+        }
+    };
+"#)]
+extern "C" {
+    /// Resolves to an empty string on success and to the error message otherwise
+    pub async fn set_local_storage_value(key: &str, value: JsValue) -> JsValue;
+}
+
 #[wasm_bindgen(inline_js = r#"
     export function storageOnChange(callback) {
         chrome.storage.sync.onChanged.addListener((changes, namespace) => {
@@ -0,0 +1,35 @@
+ALTER TABLE flags ADD COLUMN kind TEXT NOT NULL DEFAULT 'json';
+ALTER TABLE flags ADD COLUMN rules JSONB NOT NULL DEFAULT '[]';
+ALTER TABLE flags ADD COLUMN updated_at timestamptz NOT NULL DEFAULT now();
+
+UPDATE flags
+SET kind = CASE jsonb_typeof(value)
+    WHEN 'boolean' THEN 'boolean'
+    WHEN 'number' THEN 'number'
+    WHEN 'string' THEN 'string'
+    ELSE 'json'
+END;
+
+-- websocket_mode replaces the <device>_use_websocket and <device>_use_websocket_percent pairs
+WITH rollouts AS (
+    SELECT d.device, LEAST(GREATEST((percent.value #>> '{}')::numeric, 0), 100)::int AS percent
+    FROM (VALUES ('Cli', 'cli'), ('Extension', 'extension')) AS d(device, prefix)
+    JOIN flags enabled ON enabled.name = d.prefix || '_use_websocket' AND enabled.value = 'true'::jsonb
+    JOIN flags percent ON percent.name = d.prefix || '_use_websocket_percent'
+        AND jsonb_typeof(percent.value) = 'number'
+)
+INSERT INTO flags (id, name, value, kind, rules, created_at)
+SELECT gen_random_uuid(),
+       'websocket_mode',
+       'false'::jsonb,
+       'boolean',
+       COALESCE(
+           (SELECT jsonb_agg(jsonb_build_object(
+               'device_types', jsonb_build_array(device),
+               'rollout_percent', percent,
+               'value', true
+           )) FROM rollouts),
+           '[]'::jsonb
+       ),
+       now()
+ON CONFLICT (name) DO NOTHING;
@@ -0,0 +1,32 @@
+ALTER TABLE flags ADD COLUMN archived_at timestamptz;
+
+CREATE TABLE flag_changes
+(
+    id         uuid PRIMARY KEY,
+    flag_id    uuid        NOT NULL REFERENCES flags (id),
+    action     TEXT        NOT NULL,
+    actor      TEXT        NOT NULL,
+    reason     TEXT        NOT NULL,
+    old_state  JSONB,
+    new_state  JSONB       NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX flag_changes_flag_id_created_at ON flag_changes (flag_id, created_at);
+
+CREATE TABLE flag_schedules
+(
+    id           uuid PRIMARY KEY,
+    flag_id      uuid        NOT NULL REFERENCES flags (id),
+    actor        TEXT        NOT NULL,
+    reason       TEXT        NOT NULL,
+    patch        JSONB       NOT NULL,
+    run_at       timestamptz NOT NULL,
+    applied_at   timestamptz,
+    cancelled_at timestamptz,
+    error        TEXT,
+    created_at   timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX flag_schedules_pending ON flag_schedules (run_at) WHERE applied_at IS NULL AND cancelled_at IS NULL;
+CREATE INDEX flag_schedules_flag_id ON flag_schedules (flag_id, run_at);
@@ -0,0 +1,395 @@
+use crate::changes::{
+    apply_change, diff, get_change, get_changes, FieldChange, FlagAction, FlagChange, FlagPatch,
+    FlagState,
+};
+use crate::database::{get_flag_for_update, Flag};
+use crate::error::Error;
+use crate::schedules::{cancel_schedule, create_schedule, get_schedules, FlagSchedule};
+use axum::extract::{Path, Query};
+use axum::routing::{delete, get, post, put};
+use axum::{Extension, Json, Router};
+use block_mesh_common::feature_flags::{FlagKind, FlagRule};
+use chrono::{DateTime, Utc};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::env;
+use uuid::Uuid;
+
+const DEFAULT_HISTORY_LIMIT: i64 = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct AdminQuery {
+    pub code: String,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateFlag {
+    pub name: String,
+    pub kind: FlagKind,
+    pub default_value: Value,
+    #[serde(default)]
+    pub rules: Vec<FlagRule>,
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateFlag {
+    #[serde(flatten)]
+    pub patch: FlagPatch,
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ArchiveFlag {
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RollbackFlag {
+    pub change_id: Uuid,
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScheduleFlag {
+    #[serde(flatten)]
+    pub patch: FlagPatch,
+    pub run_at: DateTime<Utc>,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HistoryEntry {
+    pub id: Uuid,
+    pub action: String,
+    pub actor: String,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+    pub changes: Vec<FieldChange>,
+}
+
+impl From<FlagChange> for HistoryEntry {
+    fn from(change: FlagChange) -> Self {
+        Self {
+            changes: diff(change.old_state.as_ref(), &change.new_state),
+            id: change.id,
+            action: change.action,
+            actor: change.actor,
+            reason: change.reason,
+            created_at: change.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct FlagChangeResponse {
+    pub name: String,
+    pub state: FlagState,
+    pub change: HistoryEntry,
+}
+
+impl FlagChangeResponse {
+    fn new(flag: Flag, change: FlagChange) -> Self {
+        Self {
+            state: FlagState::of(&flag),
+            name: flag.name,
+            change: change.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct FlagHistoryResponse {
+    pub name: String,
+    pub state: FlagState,
+    pub history: Vec<HistoryEntry>,
+}
+
+/// `FLAG_ADMINS` holds `name:code` pairs, the name of the matching code is recorded as the actor.
+fn authorize_admin(code: &str) -> Result<String, Error> {
+    find_admin(&env::var("FLAG_ADMINS").unwrap_or_default(), code)
+        .ok_or_else(|| Error::Unauthorized("Bad admin param".to_string()))
+}
+
+fn find_admin(admins: &str, code: &str) -> Option<String> {
+    if code.is_empty() {
+        return None;
+    }
+    admins
+        .split(',')
+        .filter_map(|admin| admin.trim().split_once(':'))
+        .find(|(name, admin_code)| !name.is_empty() && *admin_code == code)
+        .map(|(name, _)| name.to_string())
+}
+
+fn require_reason(reason: &str) -> Result<(), Error> {
+    if reason.trim().is_empty() {
+        return Err(Error::BadRequest("reason is required".to_string()));
+    }
+    Ok(())
+}
+
+async fn existing_flag(
+    transaction: &mut Transaction<'_, Postgres>,
+    name: &str,
+) -> Result<Flag, Error> {
+    get_flag_for_update(transaction, name)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("Flag {} not found", name)))
+}
+
+#[tracing::instrument(name = "admin_create_flag", skip_all)]
+pub async fn create_flag(
+    Extension(pool): Extension<PgPool>,
+    Query(query): Query<AdminQuery>,
+    Json(body): Json<CreateFlag>,
+) -> Result<Json<FlagChangeResponse>, Error> {
+    let actor = authorize_admin(&query.code)?;
+    require_reason(&body.reason)?;
+    if body.name.is_empty()
+        || !body
+            .name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        return Err(Error::BadRequest(
+            "name must be lowercase letters, digits and underscores".to_string(),
+        ));
+    }
+    let mut transaction = create_txn(&pool).await?;
+    if get_flag_for_update(&mut transaction, &body.name)
+        .await?
+        .is_some()
+    {
+        return Err(Error::Conflict(format!(
+            "Flag {} already exists",
+            body.name
+        )));
+    }
+    let state = FlagState {
+        kind: body.kind,
+        default_value: body.default_value,
+        rules: body.rules,
+        archived: false,
+    };
+    let (flag, change) = apply_change(
+        &mut transaction,
+        &body.name,
+        None,
+        &state,
+        FlagAction::Create,
+        &actor,
+        &body.reason,
+    )
+    .await?;
+    commit_txn(transaction).await?;
+    Ok(Json(FlagChangeResponse::new(flag, change)))
+}
+
+#[tracing::instrument(name = "admin_update_flag", skip_all)]
+pub async fn update_flag(
+    Extension(pool): Extension<PgPool>,
+    Query(query): Query<AdminQuery>,
+    Path(name): Path<String>,
+    Json(body): Json<UpdateFlag>,
+) -> Result<Json<FlagChangeResponse>, Error> {
+    let actor = authorize_admin(&query.code)?;
+    require_reason(&body.reason)?;
+    if body.patch.is_empty() {
+        return Err(Error::BadRequest("Nothing to change".to_string()));
+    }
+    let mut transaction = create_txn(&pool).await?;
+    let flag = existing_flag(&mut transaction, &name).await?;
+    let state = body.patch.apply(&FlagState::of(&flag));
+    let (flag, change) = apply_change(
+        &mut transaction,
+        &name,
+        Some(&flag),
+        &state,
+        FlagAction::Update,
+        &actor,
+        &body.reason,
+    )
+    .await?;
+    commit_txn(transaction).await?;
+    Ok(Json(FlagChangeResponse::new(flag, change)))
+}
+
+#[tracing::instrument(name = "admin_archive_flag", skip_all)]
+pub async fn archive_flag(
+    Extension(pool): Extension<PgPool>,
+    Query(query): Query<AdminQuery>,
+    Path(name): Path<String>,
+    Json(body): Json<ArchiveFlag>,
+) -> Result<Json<FlagChangeResponse>, Error> {
+    let actor = authorize_admin(&query.code)?;
+    require_reason(&body.reason)?;
+    let mut transaction = create_txn(&pool).await?;
+    let flag = existing_flag(&mut transaction, &name).await?;
+    let state = FlagState {
+        archived: true,
+        ..FlagState::of(&flag)
+    };
+    let (flag, change) = apply_change(
+        &mut transaction,
+        &name,
+        Some(&flag),
+        &state,
+        FlagAction::Archive,
+        &actor,
+        &body.reason,
+    )
+    .await?;
+    commit_txn(transaction).await?;
+    Ok(Json(FlagChangeResponse::new(flag, change)))
+}
+
+/// Restores the state the flag had right before `change_id`, recorded as a change of its own.
+#[tracing::instrument(name = "admin_rollback_flag", skip_all)]
+pub async fn rollback_flag(
+    Extension(pool): Extension<PgPool>,
+    Query(query): Query<AdminQuery>,
+    Path(name): Path<String>,
+    Json(body): Json<RollbackFlag>,
+) -> Result<Json<FlagChangeResponse>, Error> {
+    let actor = authorize_admin(&query.code)?;
+    require_reason(&body.reason)?;
+    let mut transaction = create_txn(&pool).await?;
+    let flag = existing_flag(&mut transaction, &name).await?;
+    let target = get_change(&mut transaction, &body.change_id)
+        .await?
+        .filter(|change| change.flag_id == flag.id)
+        .ok_or_else(|| Error::NotFound(format!("Change {} not found", body.change_id)))?;
+    let old_state = target.old_state.ok_or_else(|| {
+        Error::BadRequest("A create cannot be rolled back, archive the flag instead".to_string())
+    })?;
+    let state: FlagState = serde_json::from_value(old_state)
+        .map_err(|e| Error::BadRequest(format!("Stored state is invalid: {}", e)))?;
+    let (flag, change) = apply_change(
+        &mut transaction,
+        &name,
+        Some(&flag),
+        &state,
+        FlagAction::Rollback,
+        &actor,
+        &body.reason,
+    )
+    .await?;
+    commit_txn(transaction).await?;
+    Ok(Json(FlagChangeResponse::new(flag, change)))
+}
+
+#[tracing::instrument(name = "admin_flag_history", skip_all)]
+pub async fn flag_history(
+    Extension(pool): Extension<PgPool>,
+    Query(query): Query<AdminQuery>,
+    Path(name): Path<String>,
+) -> Result<Json<FlagHistoryResponse>, Error> {
+    authorize_admin(&query.code)?;
+    let limit = query.limit.unwrap_or(DEFAULT_HISTORY_LIMIT).clamp(1, 500);
+    let mut transaction = create_txn(&pool).await?;
+    let flag = existing_flag(&mut transaction, &name).await?;
+    let changes = get_changes(&mut transaction, &flag.id, limit).await?;
+    commit_txn(transaction).await?;
+    Ok(Json(FlagHistoryResponse {
+        state: FlagState::of(&flag),
+        name: flag.name,
+        history: changes.into_iter().map(HistoryEntry::from).collect(),
+    }))
+}
+
+#[tracing::instrument(name = "admin_schedule_flag", skip_all)]
+pub async fn schedule_flag(
+    Extension(pool): Extension<PgPool>,
+    Query(query): Query<AdminQuery>,
+    Path(name): Path<String>,
+    Json(body): Json<ScheduleFlag>,
+) -> Result<Json<FlagSchedule>, Error> {
+    let actor = authorize_admin(&query.code)?;
+    require_reason(&body.reason)?;
+    if body.patch.is_empty() {
+        return Err(Error::BadRequest("Nothing to change".to_string()));
+    }
+    if body.run_at <= Utc::now() {
+        return Err(Error::BadRequest(
+            "run_at must be in the future".to_string(),
+        ));
+    }
+    let mut transaction = create_txn(&pool).await?;
+    let flag = existing_flag(&mut transaction, &name).await?;
+    // Checked against today's state, the cron checks again when it runs
+    body.patch.apply(&FlagState::of(&flag)).validate()?;
+    let schedule = create_schedule(
+        &mut transaction,
+        &flag.id,
+        &actor,
+        &body.reason,
+        &body.patch,
+        body.run_at,
+    )
+    .await?;
+    commit_txn(transaction).await?;
+    Ok(Json(schedule))
+}
+
+#[tracing::instrument(name = "admin_flag_schedules", skip_all)]
+pub async fn flag_schedules(
+    Extension(pool): Extension<PgPool>,
+    Query(query): Query<AdminQuery>,
+    Path(name): Path<String>,
+) -> Result<Json<Vec<FlagSchedule>>, Error> {
+    authorize_admin(&query.code)?;
+    let mut transaction = create_txn(&pool).await?;
+    let flag = existing_flag(&mut transaction, &name).await?;
+    let schedules = get_schedules(&mut transaction, &flag.id).await?;
+    commit_txn(transaction).await?;
+    Ok(Json(schedules))
+}
+
+#[tracing::instrument(name = "admin_cancel_schedule", skip_all)]
+pub async fn cancel_flag_schedule(
+    Extension(pool): Extension<PgPool>,
+    Query(query): Query<AdminQuery>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<FlagSchedule>, Error> {
+    authorize_admin(&query.code)?;
+    let mut transaction = create_txn(&pool).await?;
+    let schedule = cancel_schedule(&mut transaction, &id)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("No pending schedule {}", id)))?;
+    commit_txn(transaction).await?;
+    Ok(Json(schedule))
+}
+
+pub fn get_admin_router() -> Router {
+    Router::new()
+        .route("/admin/flags", post(create_flag))
+        .route("/admin/flags/:name", put(update_flag))
+        .route("/admin/flags/:name/archive", post(archive_flag))
+        .route("/admin/flags/:name/rollback", post(rollback_flag))
+        .route("/admin/flags/:name/history", get(flag_history))
+        .route(
+            "/admin/flags/:name/schedules",
+            get(flag_schedules).post(schedule_flag),
+        )
+        .route("/admin/schedules/:id", delete(cancel_flag_schedule))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn admins_are_found_by_their_own_code() {
+        let admins = "alice:a-code, bob:b-code";
+        assert_eq!(find_admin(admins, "a-code").as_deref(), Some("alice"));
+        assert_eq!(find_admin(admins, "b-code").as_deref(), Some("bob"));
+        assert_eq!(find_admin(admins, "c-code"), None);
+        assert_eq!(find_admin(admins, ""), None);
+        assert_eq!(find_admin(":no-name,", "no-name"), None);
+        assert_eq!(find_admin("", ""), None);
+    }
+}
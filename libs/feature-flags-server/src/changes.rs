@@ -0,0 +1,301 @@
+use crate::database::{insert_flag, update_flag, Flag};
+use crate::error::Error;
+use block_mesh_common::constants::BLOCKMESH_PG_NOTIFY_FLAGS;
+use block_mesh_common::feature_flags::{FlagKind, FlagRule};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Postgres, Transaction};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Everything an admin can change on a flag, stored as the before/after of each change.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FlagState {
+    pub kind: FlagKind,
+    pub default_value: Value,
+    #[serde(default)]
+    pub rules: Vec<FlagRule>,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+impl FlagState {
+    pub fn of(flag: &Flag) -> Self {
+        Self {
+            kind: FlagKind::from_str(&flag.kind).unwrap_or_else(|_| FlagKind::of(&flag.value)),
+            default_value: flag.value.clone(),
+            rules: serde_json::from_value(flag.rules.clone()).unwrap_or_default(),
+            archived: flag.archived_at.is_some(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        if !self.kind.accepts(&self.default_value) {
+            return Err(Error::BadRequest(format!(
+                "default_value is not a {}",
+                self.kind
+            )));
+        }
+        if let Some(index) = self
+            .rules
+            .iter()
+            .position(|rule| !self.kind.accepts(&rule.value))
+        {
+            return Err(Error::BadRequest(format!(
+                "rules[{}].value is not a {}",
+                index, self.kind
+            )));
+        }
+        if let Some(index) = self
+            .rules
+            .iter()
+            .position(|rule| rule.rollout_percent.is_some_and(|p| p > 100))
+        {
+            return Err(Error::BadRequest(format!(
+                "rules[{}].rollout_percent is above 100",
+                index
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Fields left out keep their current value.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct FlagPatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<FlagKind>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rules: Option<Vec<FlagRule>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+}
+
+impl FlagPatch {
+    pub fn is_empty(&self) -> bool {
+        self.kind.is_none()
+            && self.default_value.is_none()
+            && self.rules.is_none()
+            && self.archived.is_none()
+    }
+
+    pub fn apply(&self, state: &FlagState) -> FlagState {
+        FlagState {
+            kind: self.kind.unwrap_or(state.kind),
+            default_value: self
+                .default_value
+                .clone()
+                .unwrap_or_else(|| state.default_value.clone()),
+            rules: self.rules.clone().unwrap_or_else(|| state.rules.clone()),
+            archived: self.archived.unwrap_or(state.archived),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagAction {
+    Create,
+    Update,
+    Archive,
+    Rollback,
+    Scheduled,
+}
+
+impl Display for FlagAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FlagAction::Create => write!(f, "create"),
+            FlagAction::Update => write!(f, "update"),
+            FlagAction::Archive => write!(f, "archive"),
+            FlagAction::Rollback => write!(f, "rollback"),
+            FlagAction::Scheduled => write!(f, "scheduled"),
+        }
+    }
+}
+
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, Clone)]
+pub struct FlagChange {
+    pub id: Uuid,
+    pub flag_id: Uuid,
+    pub action: String,
+    pub actor: String,
+    pub reason: String,
+    pub old_state: Option<Value>,
+    pub new_state: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Option<Value>,
+    pub new: Value,
+}
+
+/// Top level fields of the two states that differ, `old` is `None` for a new flag.
+pub fn diff(old: Option<&Value>, new: &Value) -> Vec<FieldChange> {
+    let Some(new_fields) = new.as_object() else {
+        return Vec::new();
+    };
+    new_fields
+        .iter()
+        .filter_map(|(field, new_value)| {
+            let old_value = old.and_then(|old| old.get(field));
+            if old_value == Some(new_value) {
+                return None;
+            }
+            Some(FieldChange {
+                field: field.clone(),
+                old: old_value.cloned(),
+                new: new_value.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Writes the new state of a flag together with its audit row, then tells every instance to reload.
+#[tracing::instrument(name = "apply_change", skip_all, fields(name = name, action = %action))]
+pub async fn apply_change(
+    transaction: &mut Transaction<'_, Postgres>,
+    name: &str,
+    current: Option<&Flag>,
+    new_state: &FlagState,
+    action: FlagAction,
+    actor: &str,
+    reason: &str,
+) -> Result<(Flag, FlagChange), Error> {
+    new_state.validate()?;
+    let old_state = current.map(FlagState::of);
+    if old_state.as_ref() == Some(new_state) {
+        return Err(Error::BadRequest("Nothing to change".to_string()));
+    }
+    let flag = match current {
+        Some(flag) => update_flag(transaction, &flag.id, new_state).await?,
+        None => insert_flag(transaction, name, new_state).await?,
+    };
+    let old_state = old_state
+        .map(serde_json::to_value)
+        .transpose()
+        .map_err(anyhow::Error::from)?;
+    let new_state = serde_json::to_value(new_state).map_err(anyhow::Error::from)?;
+    let change = sqlx::query_as!(
+        FlagChange,
+        r#"
+        INSERT INTO flag_changes
+        (id, flag_id, action, actor, reason, old_state, new_state, created_at)
+        VALUES
+        ($1, $2, $3, $4, $5, $6, $7, now())
+        RETURNING id, flag_id, action, actor, reason, old_state, new_state, created_at
+        "#,
+        Uuid::new_v4(),
+        flag.id,
+        action.to_string(),
+        actor,
+        reason,
+        old_state,
+        new_state
+    )
+    .fetch_one(&mut **transaction)
+    .await?;
+    notify_flags_changed(transaction, &flag.name).await?;
+    Ok((flag, change))
+}
+
+/// Delivered on commit, so listeners never reload before the change is visible.
+#[tracing::instrument(name = "notify_flags_changed", skip_all)]
+pub async fn notify_flags_changed(
+    transaction: &mut Transaction<'_, Postgres>,
+    name: &str,
+) -> anyhow::Result<()> {
+    sqlx::query!("SELECT pg_notify($1, $2)", BLOCKMESH_PG_NOTIFY_FLAGS, name)
+        .execute(&mut **transaction)
+        .await?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "get_changes", skip_all)]
+pub async fn get_changes(
+    transaction: &mut Transaction<'_, Postgres>,
+    flag_id: &Uuid,
+    limit: i64,
+) -> anyhow::Result<Vec<FlagChange>> {
+    let changes = sqlx::query_as!(
+        FlagChange,
+        r#"
+        SELECT id, flag_id, action, actor, reason, old_state, new_state, created_at
+        FROM flag_changes
+        WHERE flag_id = $1
+        ORDER BY created_at DESC
+        LIMIT $2
+        "#,
+        flag_id,
+        limit
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(changes)
+}
+
+#[tracing::instrument(name = "get_change", skip_all)]
+pub async fn get_change(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: &Uuid,
+) -> anyhow::Result<Option<FlagChange>> {
+    let change = sqlx::query_as!(
+        FlagChange,
+        r#"
+        SELECT id, flag_id, action, actor, reason, old_state, new_state, created_at
+        FROM flag_changes
+        WHERE id = $1
+        "#,
+        id
+    )
+    .fetch_optional(&mut **transaction)
+    .await?;
+    Ok(change)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff() {
+        let old =
+            json!({"kind": "boolean", "default_value": false, "rules": [], "archived": false});
+        let new = json!({"kind": "boolean", "default_value": true, "rules": [], "archived": true});
+        let mut fields: Vec<String> = diff(Some(&old), &new)
+            .into_iter()
+            .map(|c| c.field)
+            .collect();
+        fields.sort();
+        assert_eq!(fields, vec!["archived", "default_value"]);
+        assert_eq!(diff(None, &new).len(), 4);
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut state = FlagState {
+            kind: FlagKind::Number,
+            default_value: json!(300_000),
+            rules: vec![FlagRule {
+                value: json!(60_000),
+                rollout_percent: Some(10),
+                ..FlagRule::default()
+            }],
+            archived: false,
+        };
+        assert!(state.validate().is_ok());
+        state.rules[0].value = json!("fast");
+        assert!(state.validate().is_err());
+        state.rules[0].value = json!(60_000);
+        state.rules[0].rollout_percent = Some(101);
+        assert!(state.validate().is_err());
+    }
+}
@@ -1,4 +1,7 @@
+use crate::changes::FlagState;
 use crate::error::Error;
+use crate::ruleset::RulesetCache;
+use block_mesh_common::feature_flags::{FlagDefinition, FlagKind, FlagRule};
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
@@ -6,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Number, Value};
 use sqlx::Postgres;
 use sqlx::{PgPool, Transaction};
+use std::str::FromStr;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -14,7 +18,29 @@ pub struct Flag {
     pub id: Uuid,
     pub name: String,
     pub value: Value,
+    pub kind: String,
+    pub rules: Value,
     pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+    pub archived_at: Option<DateTime<Utc>>,
+}
+
+impl Flag {
+    /// `value` stays the default so `read-flag` keeps answering old clients.
+    pub fn definition(self) -> FlagDefinition {
+        let kind = FlagKind::from_str(&self.kind).unwrap_or_else(|_| FlagKind::of(&self.value));
+        let rules: Vec<FlagRule> = serde_json::from_value(self.rules).unwrap_or_else(|e| {
+            tracing::error!("Invalid rules for flag {}: {}", self.name, e);
+            Vec::new()
+        });
+        FlagDefinition {
+            name: self.name,
+            kind,
+            default_value: self.value,
+            rules,
+            updated_at: self.updated_at,
+        }
+    }
 }
 
 #[tracing::instrument(name = "get_flag", skip_all)]
@@ -25,9 +51,9 @@ pub async fn get_flag(
     let flag = sqlx::query_as!(
         Flag,
         r#"
-        SELECT id, name, value, created_at
+        SELECT id, name, value, kind, rules, created_at, updated_at, archived_at
         FROM flags
-        WHERE name = $1
+        WHERE name = $1 AND archived_at IS NULL
         LIMIT 1
         "#,
         name
@@ -42,8 +68,9 @@ pub async fn get_flags(transaction: &mut Transaction<'_, Postgres>) -> anyhow::R
     let flags = sqlx::query_as!(
         Flag,
         r#"
-        SELECT id, name, value, created_at
+        SELECT id, name, value, kind, rules, created_at, updated_at, archived_at
         FROM flags
+        WHERE archived_at IS NULL
         "#,
     )
     .fetch_all(&mut **transaction)
@@ -51,28 +78,126 @@ pub async fn get_flags(transaction: &mut Transaction<'_, Postgres>) -> anyhow::R
     Ok(flags)
 }
 
+#[tracing::instrument(name = "get_flag_for_update", skip_all)]
+pub async fn get_flag_for_update(
+    transaction: &mut Transaction<'_, Postgres>,
+    name: &str,
+) -> anyhow::Result<Option<Flag>> {
+    let flag = sqlx::query_as!(
+        Flag,
+        r#"
+        SELECT id, name, value, kind, rules, created_at, updated_at, archived_at
+        FROM flags
+        WHERE name = $1
+        LIMIT 1
+        FOR UPDATE
+        "#,
+        name
+    )
+    .fetch_optional(&mut **transaction)
+    .await?;
+    Ok(flag)
+}
+
+#[tracing::instrument(name = "get_flag_by_id_for_update", skip_all)]
+pub async fn get_flag_by_id_for_update(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: &Uuid,
+) -> anyhow::Result<Flag> {
+    let flag = sqlx::query_as!(
+        Flag,
+        r#"
+        SELECT id, name, value, kind, rules, created_at, updated_at, archived_at
+        FROM flags
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+        id
+    )
+    .fetch_one(&mut **transaction)
+    .await?;
+    Ok(flag)
+}
+
+#[tracing::instrument(name = "insert_flag", skip_all)]
+pub async fn insert_flag(
+    transaction: &mut Transaction<'_, Postgres>,
+    name: &str,
+    state: &FlagState,
+) -> anyhow::Result<Flag> {
+    let flag = sqlx::query_as!(
+        Flag,
+        r#"
+        INSERT INTO flags
+        (id, name, value, kind, rules, created_at, updated_at, archived_at)
+        VALUES
+        ($1, $2, $3, $4, $5, now(), now(), CASE WHEN $6 THEN now() END)
+        RETURNING id, name, value, kind, rules, created_at, updated_at, archived_at
+        "#,
+        Uuid::new_v4(),
+        name,
+        state.default_value,
+        state.kind.to_string(),
+        serde_json::to_value(&state.rules)?,
+        state.archived
+    )
+    .fetch_one(&mut **transaction)
+    .await?;
+    Ok(flag)
+}
+
+#[tracing::instrument(name = "update_flag", skip_all)]
+pub async fn update_flag(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: &Uuid,
+    state: &FlagState,
+) -> anyhow::Result<Flag> {
+    let flag = sqlx::query_as!(
+        Flag,
+        r#"
+        UPDATE flags
+        SET value = $2, kind = $3, rules = $4, updated_at = now(),
+            archived_at = CASE WHEN $5 THEN COALESCE(archived_at, now()) END
+        WHERE id = $1
+        RETURNING id, name, value, kind, rules, created_at, updated_at, archived_at
+        "#,
+        id,
+        state.default_value,
+        state.kind.to_string(),
+        serde_json::to_value(&state.rules)?,
+        state.archived
+    )
+    .fetch_one(&mut **transaction)
+    .await?;
+    Ok(flag)
+}
+
 #[tracing::instrument(name = "load_flags", skip_all)]
 pub async fn load_flags(
     flags_cache: Arc<DashMap<String, Value>>,
+    ruleset_cache: Arc<RulesetCache>,
     pool: &PgPool,
 ) -> anyhow::Result<()> {
     let mut transaction = create_txn(pool).await.map_err(Error::from)?;
     let flags = get_flags(&mut transaction).await?;
-    for flag in flags {
-        flags_cache.insert(flag.name, flag.value.clone());
-    }
     commit_txn(transaction).await?;
+    flags_cache.retain(|name, _| flags.iter().any(|flag| &flag.name == name));
+    for flag in &flags {
+        flags_cache.insert(flag.name.clone(), flag.value.clone());
+    }
+    ruleset_cache.update(flags.into_iter().map(Flag::definition).collect());
     Ok(())
 }
 
 #[tracing::instrument(name = "load_flags_cron", skip_all)]
 pub async fn load_flags_cron(
     flags_cache: Arc<DashMap<String, Value>>,
+    ruleset_cache: Arc<RulesetCache>,
     pool: PgPool,
 ) -> anyhow::Result<()> {
     let sleep = std::time::Duration::from_millis(60_000);
     loop {
-        load_flags(flags_cache.clone(), &pool).await?;
+        load_flags(flags_cache.clone(), ruleset_cache.clone(), &pool).await?;
         tokio::time::sleep(sleep).await;
     }
 }
@@ -83,13 +208,15 @@ pub async fn create_flag(pool: &PgPool, name: &str, value: Value) -> anyhow::Res
     sqlx::query!(
         r#"
         INSERT INTO flags
-        (id, name, value, created_at)
+        (id, name, value, kind, created_at)
         VALUES
-        ($1, $2, $3, $4)
+        ($1, $2, $3, $4, $5)
+        ON CONFLICT (name) DO NOTHING
         "#,
         id,
         name,
         value,
+        FlagKind::of(&value).to_string(),
         now
     )
     .execute(pool)
@@ -130,5 +257,6 @@ pub async fn pre_populate_db(pool: &PgPool) -> anyhow::Result<()> {
         Value::Number(Number::from(50)),
     )
     .await?;
+    create_flag(pool, "websocket_mode", Value::Bool(false)).await?;
     Ok(())
 }
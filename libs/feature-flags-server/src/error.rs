@@ -8,6 +8,14 @@ pub enum Error {
     Sql(#[from] sqlx::Error),
     #[error(transparent)]
     Anyhow(#[from] AnyhowError),
+    #[error("{0}")]
+    Unauthorized(String),
+    #[error("{0}")]
+    BadRequest(String),
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    Conflict(String),
 }
 
 impl IntoResponse for Error {
@@ -20,6 +28,10 @@ impl IntoResponse for Error {
             Error::Sql(_) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error.").into_response()
             }
+            Error::Unauthorized(message) => (StatusCode::UNAUTHORIZED, message).into_response(),
+            Error::BadRequest(message) => (StatusCode::BAD_REQUEST, message).into_response(),
+            Error::NotFound(message) => (StatusCode::NOT_FOUND, message).into_response(),
+            Error::Conflict(message) => (StatusCode::CONFLICT, message).into_response(),
         }
     }
 }
@@ -30,6 +42,10 @@ impl From<Error> for StatusCode {
         match error {
             Error::Sql(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Error::Anyhow(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Error::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Error::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Error::NotFound(_) => StatusCode::NOT_FOUND,
+            Error::Conflict(_) => StatusCode::CONFLICT,
         }
     }
 }
@@ -0,0 +1,37 @@
+use crate::database::load_flags;
+use crate::ruleset::RulesetCache;
+use block_mesh_common::constants::BLOCKMESH_PG_NOTIFY_FLAGS;
+use dashmap::DashMap;
+use serde_json::Value;
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Reloads every flag whenever any instance commits a change, `load_flags_cron` stays as a fallback.
+#[tracing::instrument(name = "flags_listener", skip_all, err)]
+pub async fn flags_listener(
+    flags_cache: Arc<DashMap<String, Value>>,
+    ruleset_cache: Arc<RulesetCache>,
+    pool: PgPool,
+) -> anyhow::Result<()> {
+    let mut listener = PgListener::connect_with(&pool).await?;
+    listener.listen(BLOCKMESH_PG_NOTIFY_FLAGS).await?;
+    loop {
+        match listener.try_recv().await {
+            Ok(Some(notification)) => {
+                tracing::info!("Flag {} changed", notification.payload());
+            }
+            // The connection was lost and notifications sent meanwhile are gone, so reload anyway
+            Ok(None) => tracing::warn!("Flags listener reconnected"),
+            Err(e) => {
+                tracing::error!("Flags listener error: {}", e);
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+        }
+        if let Err(e) = load_flags(flags_cache.clone(), ruleset_cache.clone(), &pool).await {
+            tracing::error!("Failed to reload flags: {}", e);
+        }
+    }
+}
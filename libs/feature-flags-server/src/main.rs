@@ -1,5 +1,8 @@
 use crate::database::{load_flags_cron, pre_populate_db};
+use crate::listener::flags_listener;
 use crate::routes::get_router;
+use crate::ruleset::RulesetCache;
+use crate::schedules::schedules_cron;
 use axum::{Extension, Router};
 use block_mesh_common::env::load_dotenv::load_dotenv;
 use dashmap::DashMap;
@@ -15,9 +18,14 @@ use tokio::net::TcpListener;
 use tokio::select;
 use tower_http::cors::CorsLayer;
 
+mod admin;
+mod changes;
 mod database;
 mod error;
+mod listener;
 mod routes;
+mod ruleset;
+mod schedules;
 
 #[tracing::instrument(name = "run_server", skip_all)]
 pub async fn run_server(listener: TcpListener, app: Router<()>) -> std::io::Result<()> {
@@ -43,11 +51,23 @@ async fn main() -> anyhow::Result<()> {
     let cors = CorsLayer::permissive();
     let flags_cache: DashMap<String, Value> = DashMap::new();
     let flags_cache = Arc::new(flags_cache);
-    let load_flags_cron_task = tokio::spawn(load_flags_cron(flags_cache.clone(), db_pool.clone()));
+    let ruleset_cache = Arc::new(RulesetCache::new());
+    let load_flags_cron_task = tokio::spawn(load_flags_cron(
+        flags_cache.clone(),
+        ruleset_cache.clone(),
+        db_pool.clone(),
+    ));
+    let flags_listener_task = tokio::spawn(flags_listener(
+        flags_cache.clone(),
+        ruleset_cache.clone(),
+        db_pool.clone(),
+    ));
+    let schedules_cron_task = tokio::spawn(schedules_cron(db_pool.clone()));
     let app = Router::new()
         .nest("/", router)
         .layer(cors)
         .layer(Extension(flags_cache))
+        .layer(Extension(ruleset_cache))
         .layer(Extension(db_pool.clone()));
     let port = env::var("PORT").unwrap_or("8001".to_string());
     let listener = TcpListener::bind(format!("0.0.0.0:{}", port)).await?;
@@ -56,6 +76,8 @@ async fn main() -> anyhow::Result<()> {
 
     select! {
        o = load_flags_cron_task => panic!("load_flags_cron_task {:?}", o),
+       o = flags_listener_task => panic!("flags_listener_task {:?}", o),
+       o = schedules_cron_task => panic!("schedules_cron_task {:?}", o),
        o = server_task => panic!("server_task {:?}", o),
     }
 }
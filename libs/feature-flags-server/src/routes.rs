@@ -1,7 +1,11 @@
+use crate::admin::get_admin_router;
 use crate::database::get_flag;
 use crate::error::Error;
+use crate::ruleset::RulesetCache;
 use axum::extract::Path;
-use axum::http::StatusCode;
+use axum::http::header::{CACHE_CONTROL, ETAG, IF_NONE_MATCH};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::response::IntoResponse;
 use axum::routing::get;
 use axum::{Extension, Json, Router};
@@ -13,6 +17,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sqlx::PgPool;
 use std::sync::Arc;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
 
 #[tracing::instrument(name = "db_health", skip_all)]
 pub async fn db_health(Extension(pool): Extension<PgPool>) -> Result<impl IntoResponse, Error> {
@@ -45,6 +51,40 @@ pub async fn read_flag(
     Ok(Json(db_flag.value))
 }
 
+#[tracing::instrument(name = "read_ruleset", skip_all, level = "trace")]
+pub async fn read_ruleset(
+    Extension(ruleset_cache): Extension<Arc<RulesetCache>>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let ruleset = ruleset_cache.current();
+    let etag = format!("\"{}\"", ruleset.revision);
+    let not_modified = headers
+        .get(IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|tag| tag.trim() == etag));
+    let headers = [(ETAG, etag), (CACHE_CONTROL, "no-cache".to_string())];
+    if not_modified {
+        return (StatusCode::NOT_MODIFIED, headers).into_response();
+    }
+    (headers, Json(ruleset.as_ref())).into_response()
+}
+
+/// Sends the current ruleset first, then every new revision.
+#[tracing::instrument(name = "stream_ruleset", skip_all)]
+pub async fn stream_ruleset(
+    Extension(ruleset_cache): Extension<Arc<RulesetCache>>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let updates = BroadcastStream::new(ruleset_cache.subscribe()).filter_map(|r| r.ok());
+    let stream = tokio_stream::once(ruleset_cache.current())
+        .chain(updates)
+        .map(|ruleset| {
+            Event::default()
+                .event("ruleset")
+                .json_data(ruleset.as_ref())
+        });
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct FlagOut {
     name: String,
@@ -56,6 +96,8 @@ pub fn get_router() -> Router {
         .route("/", get(server_health))
         .route("/server_health", get(server_health))
         .route("/db_health", get(db_health))
+        .route("/flags", get(read_ruleset))
+        .route("/flags/stream", get(stream_ruleset))
         .route("/read-flag/:flag", get(read_flag))
         .route(
             &format!("/{}/read-flag/:flag", DeviceType::Extension),
@@ -77,4 +119,5 @@ pub fn get_router() -> Router {
             &format!("/{}/read-flag/:flag", DeviceType::Worker),
             get(read_flag),
         )
+        .merge(get_admin_router())
 }
@@ -0,0 +1,50 @@
+use block_mesh_common::feature_flags::{fingerprint, FlagDefinition, FlagRuleset};
+use std::sync::{Arc, RwLock};
+use tokio::sync::broadcast;
+
+pub struct RulesetCache {
+    current: RwLock<Arc<FlagRuleset>>,
+    updates: broadcast::Sender<Arc<FlagRuleset>>,
+}
+
+impl Default for RulesetCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RulesetCache {
+    pub fn new() -> Self {
+        let (updates, _) = broadcast::channel(16);
+        Self {
+            current: RwLock::new(Arc::new(FlagRuleset::default())),
+            updates,
+        }
+    }
+
+    pub fn current(&self) -> Arc<FlagRuleset> {
+        self.current.read().unwrap().clone()
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<FlagRuleset>> {
+        self.updates.subscribe()
+    }
+
+    /// The revision is a hash of the content, so every instance hands out the same ETag.
+    pub fn update(&self, mut flags: Vec<FlagDefinition>) {
+        flags.sort_by(|a, b| a.name.cmp(&b.name));
+        let revision = match serde_json::to_vec(&flags) {
+            Ok(bytes) => format!("{:016x}", fingerprint(&bytes)),
+            Err(e) => {
+                tracing::error!("Failed to serialize flags: {}", e);
+                return;
+            }
+        };
+        if self.current().revision == revision {
+            return;
+        }
+        let ruleset = Arc::new(FlagRuleset { revision, flags });
+        *self.current.write().unwrap() = ruleset.clone();
+        let _ = self.updates.send(ruleset);
+    }
+}
@@ -0,0 +1,199 @@
+use crate::changes::{apply_change, FlagAction, FlagPatch, FlagState};
+use crate::database::get_flag_by_id_for_update;
+use chrono::{DateTime, Utc};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Acquire, PgPool, Postgres, Transaction};
+use std::env;
+use std::time::Duration;
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, Clone)]
+pub struct FlagSchedule {
+    pub id: Uuid,
+    pub flag_id: Uuid,
+    pub actor: String,
+    pub reason: String,
+    pub patch: Value,
+    pub run_at: DateTime<Utc>,
+    pub applied_at: Option<DateTime<Utc>>,
+    pub cancelled_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[tracing::instrument(name = "create_schedule", skip_all)]
+pub async fn create_schedule(
+    transaction: &mut Transaction<'_, Postgres>,
+    flag_id: &Uuid,
+    actor: &str,
+    reason: &str,
+    patch: &FlagPatch,
+    run_at: DateTime<Utc>,
+) -> anyhow::Result<FlagSchedule> {
+    let schedule = sqlx::query_as!(
+        FlagSchedule,
+        r#"
+        INSERT INTO flag_schedules
+        (id, flag_id, actor, reason, patch, run_at, created_at)
+        VALUES
+        ($1, $2, $3, $4, $5, $6, now())
+        RETURNING id, flag_id, actor, reason, patch, run_at, applied_at, cancelled_at, error, created_at
+        "#,
+        Uuid::new_v4(),
+        flag_id,
+        actor,
+        reason,
+        serde_json::to_value(patch)?,
+        run_at
+    )
+    .fetch_one(&mut **transaction)
+    .await?;
+    Ok(schedule)
+}
+
+#[tracing::instrument(name = "get_schedules", skip_all)]
+pub async fn get_schedules(
+    transaction: &mut Transaction<'_, Postgres>,
+    flag_id: &Uuid,
+) -> anyhow::Result<Vec<FlagSchedule>> {
+    let schedules = sqlx::query_as!(
+        FlagSchedule,
+        r#"
+        SELECT id, flag_id, actor, reason, patch, run_at, applied_at, cancelled_at, error, created_at
+        FROM flag_schedules
+        WHERE flag_id = $1
+        ORDER BY run_at DESC
+        "#,
+        flag_id
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(schedules)
+}
+
+/// Only pending schedules can be cancelled, returns `None` otherwise.
+#[tracing::instrument(name = "cancel_schedule", skip_all)]
+pub async fn cancel_schedule(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: &Uuid,
+) -> anyhow::Result<Option<FlagSchedule>> {
+    let schedule = sqlx::query_as!(
+        FlagSchedule,
+        r#"
+        UPDATE flag_schedules
+        SET cancelled_at = now()
+        WHERE id = $1 AND applied_at IS NULL AND cancelled_at IS NULL
+        RETURNING id, flag_id, actor, reason, patch, run_at, applied_at, cancelled_at, error, created_at
+        "#,
+        id
+    )
+    .fetch_optional(&mut **transaction)
+    .await?;
+    Ok(schedule)
+}
+
+#[tracing::instrument(name = "get_due_schedules", skip_all)]
+async fn get_due_schedules(
+    transaction: &mut Transaction<'_, Postgres>,
+    limit: i64,
+) -> anyhow::Result<Vec<FlagSchedule>> {
+    let schedules = sqlx::query_as!(
+        FlagSchedule,
+        r#"
+        SELECT id, flag_id, actor, reason, patch, run_at, applied_at, cancelled_at, error, created_at
+        FROM flag_schedules
+        WHERE applied_at IS NULL AND cancelled_at IS NULL AND run_at <= now()
+        ORDER BY run_at
+        LIMIT $1
+        FOR UPDATE SKIP LOCKED
+        "#,
+        limit
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(schedules)
+}
+
+#[tracing::instrument(name = "mark_schedule_applied", skip_all)]
+async fn mark_schedule_applied(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: &Uuid,
+    error: Option<String>,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE flag_schedules
+        SET applied_at = now(), error = $2
+        WHERE id = $1
+        "#,
+        id,
+        error
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
+
+/// Each schedule runs in a savepoint, so one that no longer applies is marked failed
+/// instead of blocking the ones behind it.
+#[tracing::instrument(name = "apply_due_schedules", skip_all)]
+pub async fn apply_due_schedules(pool: &PgPool) -> anyhow::Result<usize> {
+    let mut transaction = create_txn(pool).await?;
+    let schedules = get_due_schedules(&mut transaction, 50).await?;
+    for schedule in &schedules {
+        let mut savepoint = transaction.begin().await?;
+        let result = apply_schedule(&mut savepoint, schedule).await;
+        let error = match result {
+            Ok(()) => {
+                savepoint.commit().await?;
+                None
+            }
+            Err(e) => {
+                savepoint.rollback().await?;
+                tracing::warn!("Schedule {} failed: {}", schedule.id, e);
+                Some(e.to_string())
+            }
+        };
+        mark_schedule_applied(&mut transaction, &schedule.id, error).await?;
+    }
+    commit_txn(transaction).await?;
+    Ok(schedules.len())
+}
+
+async fn apply_schedule(
+    transaction: &mut Transaction<'_, Postgres>,
+    schedule: &FlagSchedule,
+) -> anyhow::Result<()> {
+    let patch: FlagPatch = serde_json::from_value(schedule.patch.clone())?;
+    let flag = get_flag_by_id_for_update(transaction, &schedule.flag_id).await?;
+    let new_state = patch.apply(&FlagState::of(&flag));
+    apply_change(
+        transaction,
+        &flag.name,
+        Some(&flag),
+        &new_state,
+        FlagAction::Scheduled,
+        &schedule.actor,
+        &schedule.reason,
+    )
+    .await?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "schedules_cron", skip_all)]
+pub async fn schedules_cron(pool: PgPool) -> anyhow::Result<()> {
+    let sleep = env::var("FLAG_SCHEDULES_INTERVAL_SECS")
+        .ok()
+        .and_then(|var| var.parse().ok())
+        .unwrap_or(10);
+    loop {
+        match apply_due_schedules(&pool).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Applied {} flag schedules", count),
+            Err(e) => tracing::error!("Failed to apply flag schedules: {}", e),
+        }
+        tokio::time::sleep(Duration::from_secs(sleep)).await;
+    }
+}
@@ -8,11 +8,15 @@ export interface GetTaskResponse {
 	method: string;
 	headers: object;
 	body: object;
+	encrypted_payload?: string;
+	reply_key?: string;
 }
 
 export interface GetTaskRequest {
 	email: string;
 	api_token: string;
+	/** Sealed tasks are only handed to the node holding this key. */
+	node_key_id?: string;
 }
 
 export interface SubmitTaskRequest {
@@ -40,6 +44,11 @@ export interface ReportUptimeRequest {
 	email: string;
 	api_token: string;
 	ip?: string;
+	/**
+	 * Generated by the client for every report, so the manager can tell a retried report
+	 * from a new one.
+	 */
+	report_id?: string;
 }
 
 export interface DashboardRequest {
@@ -58,6 +67,8 @@ export interface SubmitTaskResponse {
 export interface GetTokenRequest {
 	email: string;
 	password: string;
+	/** Required when the account has two factor enabled, only the app server checks it. */
+	totp_code?: string;
 }
 
 export interface GetEmailViaTokenRequest {
@@ -76,6 +87,7 @@ export interface CheckTokenRequest {
 export interface GetTokenResponse {
 	api_token: string;
 	message?: string;
+	user_id?: string;
 }
 
 export interface GetStatsRequest {
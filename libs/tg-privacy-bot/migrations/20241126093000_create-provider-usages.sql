@@ -0,0 +1,26 @@
+CREATE TABLE provider_usages
+(
+    id            uuid PRIMARY KEY,
+    usage_id      uuid        NOT NULL,
+    provider      TEXT        NOT NULL,
+    requests      BIGINT      NOT NULL,
+    input_tokens  BIGINT      NOT NULL,
+    output_tokens BIGINT      NOT NULL,
+    created_at    timestamptz NOT NULL,
+    updated_at    timestamptz NOT NULL,
+    CONSTRAINT fk_usage FOREIGN KEY (usage_id) REFERENCES usages (id)
+);
+
+CREATE INDEX provider_usages_created_at ON provider_usages (created_at);
+CREATE INDEX provider_usages_updated_at ON provider_usages (updated_at);
+CREATE INDEX provider_usages_provider ON provider_usages (provider);
+CREATE UNIQUE INDEX provider_usages_usage_id_provider ON provider_usages (usage_id, provider);
+
+-- Every message before this migration was answered by the OpenAI API.
+INSERT INTO provider_usages
+(id, usage_id, provider, requests, input_tokens, output_tokens, created_at, updated_at)
+SELECT gen_random_uuid(), id, 'OpenAi', usage, 0, 0, created_at, updated_at
+FROM usages
+WHERE usage > 0;
+
+ALTER TABLE usages DROP COLUMN usage;
@@ -0,0 +1,14 @@
+CREATE TABLE conversation_messages
+(
+    id         uuid PRIMARY KEY,
+    user_id    uuid        NOT NULL,
+    chat_id    BIGINT      NOT NULL,
+    model_name TEXT        NOT NULL,
+    role       TEXT        NOT NULL,
+    content    TEXT        NOT NULL,
+    created_at timestamptz NOT NULL,
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id)
+);
+
+CREATE INDEX conversation_messages_created_at ON conversation_messages (created_at);
+CREATE INDEX conversation_messages_user_id_chat_id_created_at ON conversation_messages (user_id, chat_id, created_at);
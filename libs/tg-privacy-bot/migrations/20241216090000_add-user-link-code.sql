@@ -0,0 +1,4 @@
+-- The code from a BlockMesh account's /start link, its export and deletion include this user.
+ALTER TABLE users ADD COLUMN link_code TEXT NULL;
+
+CREATE INDEX user_link_code ON users (link_code);
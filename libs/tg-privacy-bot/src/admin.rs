@@ -0,0 +1,50 @@
+use crate::database::calls::delete_linked_users::delete_linked_users;
+use crate::database::calls::export_linked_users::export_linked_users;
+use crate::error::Error;
+use axum::extract::Query;
+use axum::{Extension, Json};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use http::StatusCode;
+use serde::Deserialize;
+use serde_json::Value;
+use sqlx::PgPool;
+use std::env;
+
+/// The manager worker calls these with the link code of the account it exports or deletes.
+#[derive(Debug, Deserialize)]
+pub struct LinkedUsersQuery {
+    pub code: String,
+    pub link_code: String,
+}
+
+fn authorize_admin(code: &str) -> Result<(), Error> {
+    if code.is_empty() || code != env::var("ADMIN_PARAM").unwrap_or_default() {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+#[tracing::instrument(name = "export_linked_users", skip_all)]
+pub async fn export_users(
+    Extension(pool): Extension<PgPool>,
+    Query(query): Query<LinkedUsersQuery>,
+) -> Result<Json<Value>, Error> {
+    authorize_admin(&query.code)?;
+    let mut transaction = create_txn(&pool).await?;
+    let export = export_linked_users(&mut transaction, &query.link_code).await?;
+    commit_txn(transaction).await?;
+    Ok(Json(export))
+}
+
+#[tracing::instrument(name = "delete_linked_users", skip_all)]
+pub async fn delete_users(
+    Extension(pool): Extension<PgPool>,
+    Query(query): Query<LinkedUsersQuery>,
+) -> Result<StatusCode, Error> {
+    authorize_admin(&query.code)?;
+    let mut transaction = create_txn(&pool).await?;
+    let deleted = delete_linked_users(&mut transaction, &query.link_code).await?;
+    commit_txn(transaction).await?;
+    tracing::info!("Deleted {} linked telegram users", deleted);
+    Ok(StatusCode::OK)
+}
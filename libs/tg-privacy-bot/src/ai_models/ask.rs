@@ -0,0 +1,19 @@
+use ai_interfaces::clients::bulk::{AIClient, Completion, CompletionOptions, Message};
+use ai_interfaces::models::base::ModelName;
+use std::sync::OnceLock;
+
+static AI_CLIENT: OnceLock<AIClient> = OnceLock::new();
+
+pub fn get_ai_client() -> &'static AIClient {
+    AI_CLIENT.get_or_init(AIClient::new)
+}
+
+pub async fn ask(messages: Vec<Message>, model_name: ModelName) -> anyhow::Result<Completion> {
+    let options = CompletionOptions {
+        temperature: Some(0_f32),
+        ..CompletionOptions::default()
+    };
+    get_ai_client()
+        .completion(model_name, messages, &options)
+        .await
+}
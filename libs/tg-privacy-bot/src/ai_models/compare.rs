@@ -0,0 +1,31 @@
+use ai_interfaces::clients::bulk::CompletionOptions;
+use ai_interfaces::clients::compare::{CompareOptions, Judge, JudgeMode};
+use ai_interfaces::models::base::ModelName;
+use std::env;
+use std::time::Duration;
+
+pub fn compare_options() -> CompareOptions {
+    let timeout = env::var("COMPARE_TIMEOUT_SECS")
+        .unwrap_or("60".to_string())
+        .parse()
+        .unwrap_or(60);
+    let judge = env::var("COMPARE_JUDGE_MODEL")
+        .ok()
+        .map(|model_name| Judge {
+            model_name: ModelName::from(model_name),
+            mode: JudgeMode::from(
+                env::var("COMPARE_JUDGE_MODE")
+                    .unwrap_or("score".to_string())
+                    .as_str(),
+            ),
+        });
+    CompareOptions {
+        completion: CompletionOptions {
+            temperature: Some(0_f32),
+            ..CompletionOptions::default()
+        },
+        timeout: Duration::from_secs(timeout),
+        judge,
+        ..CompareOptions::default()
+    }
+}
@@ -0,0 +1,128 @@
+use crate::database::models::conversation_message::ConversationMessage;
+use ai_interfaces::clients::bulk::{Message, Role};
+use chrono::Duration;
+use std::env;
+
+pub fn history_ttl() -> Duration {
+    let hours = env::var("HISTORY_TTL_HOURS")
+        .unwrap_or("24".to_string())
+        .parse()
+        .unwrap_or(24);
+    Duration::hours(hours)
+}
+
+pub fn history_token_budget() -> usize {
+    env::var("HISTORY_TOKEN_BUDGET")
+        .unwrap_or("4000".to_string())
+        .parse()
+        .unwrap_or(4000)
+}
+
+pub fn history_max_messages() -> i64 {
+    env::var("HISTORY_MAX_MESSAGES")
+        .unwrap_or("50".to_string())
+        .parse()
+        .unwrap_or(50)
+}
+
+// A rough count that works for every provider without shipping their tokenizers.
+pub fn estimate_tokens(content: &str) -> usize {
+    content.chars().count().div_ceil(4)
+}
+
+pub fn build_conversation(
+    newest_first: Vec<ConversationMessage>,
+    question: String,
+    token_budget: usize,
+) -> Vec<Message> {
+    let mut used = estimate_tokens(&question);
+    let mut messages = Vec::new();
+    for message in newest_first {
+        used += estimate_tokens(&message.content);
+        if used > token_budget {
+            break;
+        }
+        messages.push(Message::from(message));
+    }
+    messages.reverse();
+    // Trimming can cut a turn in half, providers expect the conversation to open with the user.
+    while messages
+        .first()
+        .is_some_and(|message| message.role != Role::User)
+    {
+        messages.remove(0);
+    }
+    messages.push(Message::user(question));
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn stored(role: &str, content: &str) -> ConversationMessage {
+        ConversationMessage {
+            id: Uuid::new_v4(),
+            user_id: Uuid::nil(),
+            chat_id: 1,
+            model_name: "gpt-4o".to_string(),
+            role: role.to_string(),
+            content: content.to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn contents(messages: &[Message]) -> Vec<&str> {
+        messages.iter().map(|m| m.content.as_str()).collect()
+    }
+
+    #[test]
+    fn test_estimate_tokens_rounds_up() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abc"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+
+    #[test]
+    fn test_history_is_returned_oldest_first_with_question_last() {
+        let history = vec![stored("assistant", "a1"), stored("user", "q1")];
+        let messages = build_conversation(history, "q2".to_string(), 100);
+        assert_eq!(contents(&messages), vec!["q1", "a1", "q2"]);
+        assert_eq!(messages[1].role, Role::Assistant);
+        assert_eq!(messages[2].role, Role::User);
+    }
+
+    #[test]
+    fn test_oldest_messages_are_dropped_over_budget() {
+        let history = vec![
+            stored("assistant", "aaaa"),
+            stored("user", "qqqq"),
+            stored("assistant", "aaaa"),
+            stored("user", "qqqq"),
+        ];
+        let messages = build_conversation(history, "qqqq".to_string(), 3);
+        assert_eq!(contents(&messages), vec!["qqqq", "aaaa", "qqqq"]);
+    }
+
+    #[test]
+    fn test_leading_assistant_message_is_trimmed() {
+        let history = vec![
+            stored("assistant", "aaaa"),
+            stored("user", "qqqq"),
+            stored("assistant", "aaaa"),
+        ];
+        let messages = build_conversation(history, "qqqq".to_string(), 4);
+        assert_eq!(contents(&messages), vec!["qqqq", "aaaa", "qqqq"]);
+        assert_eq!(messages[0].role, Role::User);
+    }
+
+    #[test]
+    fn test_question_is_kept_when_it_exceeds_the_budget() {
+        let history = vec![stored("assistant", "a1"), stored("user", "q1")];
+        let messages = build_conversation(history, "x".repeat(40), 5);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].role, Role::User);
+    }
+}
@@ -1 +1,4 @@
-pub mod open_ai;
+pub mod ask;
+pub mod compare;
+pub mod history;
+pub mod user_error;
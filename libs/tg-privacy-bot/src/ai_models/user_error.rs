@@ -0,0 +1,38 @@
+use ai_interfaces::error::AiInterfaceError;
+
+pub fn user_facing_error(error: &anyhow::Error) -> String {
+    match error.downcast_ref::<AiInterfaceError>() {
+        Some(AiInterfaceError::NotConfigured(client)) => format!(
+            "{} models are not available right now, please /selectmodel another one",
+            client
+        ),
+        Some(AiInterfaceError::Timeout(client)) => {
+            format!("{} took too long to answer, please try again", client)
+        }
+        Some(AiInterfaceError::Provider { client, status, .. }) => match status {
+            400 | 413 | 422 => {
+                "The model could not process this message, try rephrasing or shortening it"
+                    .to_string()
+            }
+            401 | 403 => format!(
+                "{} rejected the request, please /selectmodel another one",
+                client
+            ),
+            429 => format!(
+                "{} is receiving too many requests, please try again in a minute",
+                client
+            ),
+            _ => format!(
+                "{} is unavailable right now, please try again later",
+                client
+            ),
+        },
+        _ if error
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(|e| e.is_timeout()) =>
+        {
+            "The model took too long to answer, please try again".to_string()
+        }
+        _ => "Something went wrong while asking the model, please try again".to_string(),
+    }
+}
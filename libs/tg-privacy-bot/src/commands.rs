@@ -1,18 +1,23 @@
 use teloxide::utils::command::BotCommands;
 
-#[derive(BotCommands, Clone, Default)]
+#[derive(BotCommands, Clone)]
 #[command(
     rename_rule = "lowercase",
     description = "These commands are supported:"
 )]
 pub enum Commands {
-    #[default]
     #[command(description = "Start bot")]
-    Start,
-    // #[command(description = "select_mode")]
-    // SelectMode,
+    Start(String),
+    #[command(description = "select_mode")]
+    SelectMode,
     #[command(description = "select_model")]
     SelectModel,
+    #[command(description = "Start a new conversation")]
+    Reset,
+    #[command(description = "Delete all stored conversations")]
+    Forget,
+    #[command(description = "Compare answers from all models")]
+    Compare(String),
     #[command(description = "info")]
     Info,
     #[command(description = "Display Help.")]
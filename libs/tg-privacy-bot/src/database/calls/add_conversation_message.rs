@@ -0,0 +1,35 @@
+use crate::database::models::conversation_message::role_name;
+use ai_interfaces::clients::bulk::Message;
+use ai_interfaces::models::base::ModelName;
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+pub async fn add_conversation_message(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    chat_id: i64,
+    model_name: &ModelName,
+    message: &Message,
+    created_at: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    let id = Uuid::new_v4();
+    sqlx::query!(
+        r#"
+        INSERT INTO conversation_messages
+        (id, user_id, chat_id, model_name, role, content, created_at)
+        VALUES
+        ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+        id,
+        user_id,
+        chat_id,
+        model_name.to_string(),
+        role_name(&message.role),
+        message.content,
+        created_at
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
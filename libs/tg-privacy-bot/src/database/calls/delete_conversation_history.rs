@@ -0,0 +1,20 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+pub async fn delete_conversation_history(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    chat_id: Option<i64>,
+) -> anyhow::Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM conversation_messages
+        WHERE user_id = $1 AND ($2::BIGINT IS NULL OR chat_id = $2)
+        "#,
+        user_id,
+        chat_id
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,18 @@
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+
+pub async fn delete_expired_conversation_messages(
+    pool: &PgPool,
+    ttl: Duration,
+) -> anyhow::Result<u64> {
+    let before = Utc::now() - ttl;
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM conversation_messages WHERE created_at < $1
+        "#,
+        before
+    )
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
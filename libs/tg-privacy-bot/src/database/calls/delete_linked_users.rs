@@ -0,0 +1,41 @@
+use sqlx::{Postgres, Transaction};
+
+/// Tables keyed by `user_id`, cleared before the users themselves.
+const USER_TABLES: &[&str] = &[
+    "conversation_messages",
+    "invite_codes",
+    "user_settings",
+    "usages",
+];
+
+/// Removes the Telegram users that opened the bot with this link code and all their rows.
+pub async fn delete_linked_users(
+    transaction: &mut Transaction<'_, Postgres>,
+    link_code: &str,
+) -> anyhow::Result<u64> {
+    sqlx::query!(
+        r#"
+        DELETE FROM provider_usages
+        USING usages, users
+        WHERE usages.id = provider_usages.usage_id
+        AND users.id = usages.user_id
+        AND users.link_code = $1
+        "#,
+        link_code
+    )
+    .execute(&mut **transaction)
+    .await?;
+    for table in USER_TABLES {
+        sqlx::query(&format!(
+            "DELETE FROM {} WHERE user_id IN (SELECT id FROM users WHERE link_code = $1)",
+            table
+        ))
+        .bind(link_code)
+        .execute(&mut **transaction)
+        .await?;
+    }
+    let result = sqlx::query!(r#"DELETE FROM users WHERE link_code = $1"#, link_code)
+        .execute(&mut **transaction)
+        .await?;
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,61 @@
+use serde_json::{Map, Value};
+use sqlx::{Postgres, Transaction};
+
+/// One entry per table, every query takes the link code as `$1`.
+const EXPORT_SECTIONS: &[(&str, &str)] = &[
+    (
+        "users",
+        "SELECT id, tg_id, username, created_at FROM users WHERE link_code = $1",
+    ),
+    (
+        "user_settings",
+        "SELECT user_settings.* FROM user_settings
+        JOIN users ON users.id = user_settings.user_id
+        WHERE users.link_code = $1",
+    ),
+    (
+        "usages",
+        "SELECT usages.* FROM usages
+        JOIN users ON users.id = usages.user_id
+        WHERE users.link_code = $1",
+    ),
+    (
+        "provider_usages",
+        "SELECT provider_usages.* FROM provider_usages
+        JOIN usages ON usages.id = provider_usages.usage_id
+        JOIN users ON users.id = usages.user_id
+        WHERE users.link_code = $1",
+    ),
+    (
+        "invite_codes",
+        "SELECT invite_codes.* FROM invite_codes
+        JOIN users ON users.id = invite_codes.user_id
+        WHERE users.link_code = $1",
+    ),
+    (
+        "conversation_messages",
+        "SELECT conversation_messages.* FROM conversation_messages
+        JOIN users ON users.id = conversation_messages.user_id
+        WHERE users.link_code = $1",
+    ),
+];
+
+/// Everything stored for the Telegram users that opened the bot with this link code.
+pub async fn export_linked_users(
+    transaction: &mut Transaction<'_, Postgres>,
+    link_code: &str,
+) -> anyhow::Result<Value> {
+    let mut export = Map::new();
+    for (name, query) in EXPORT_SECTIONS {
+        let query = format!(
+            "SELECT COALESCE(json_agg(t), '[]'::json) FROM ({}) t",
+            query
+        );
+        let rows: Value = sqlx::query_scalar(&query)
+            .bind(link_code)
+            .fetch_one(&mut **transaction)
+            .await?;
+        export.insert(name.to_string(), rows);
+    }
+    Ok(Value::Object(export))
+}
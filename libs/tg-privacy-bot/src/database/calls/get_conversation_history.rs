@@ -0,0 +1,31 @@
+use crate::database::models::conversation_message::ConversationMessage;
+use chrono::{Duration, Utc};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+pub async fn get_conversation_history(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    chat_id: i64,
+    ttl: Duration,
+    limit: i64,
+) -> anyhow::Result<Vec<ConversationMessage>> {
+    let since = Utc::now() - ttl;
+    let messages = sqlx::query_as!(
+        ConversationMessage,
+        r#"
+        SELECT id, user_id, chat_id, model_name, role, content, created_at
+        FROM conversation_messages
+        WHERE user_id = $1 AND chat_id = $2 AND created_at > $3
+        ORDER BY created_at DESC
+        LIMIT $4
+        "#,
+        user_id,
+        chat_id,
+        since,
+        limit
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(messages)
+}
@@ -18,16 +18,20 @@ pub async fn get_or_create_usage(
         Usage,
         r#"
         INSERT INTO usages
-        (id, user_id, usage_limit, usage, created_at, updated_at, day)
+        (id, user_id, usage_limit, created_at, updated_at, day)
         VALUES
-        ($1, $2, $3, $4, $5, $6, $7)
-        ON CONFLICT (user_id, day) DO UPDATE SET updated_at = $6
-        RETURNING id, user_id, usage_limit, usage, created_at, updated_at, day
+        ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (user_id, day) DO UPDATE SET updated_at = $5
+        RETURNING id, user_id, usage_limit, created_at, updated_at, day,
+        (
+            SELECT COALESCE(SUM(requests), 0)::BIGINT
+            FROM provider_usages
+            WHERE provider_usages.usage_id = usages.id
+        ) AS "usage!"
         "#,
         id,
         user_id,
         usage_limit,
-        0i64,
         now,
         now.clone(),
         day
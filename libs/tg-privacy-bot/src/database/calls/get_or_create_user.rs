@@ -18,7 +18,7 @@ pub async fn get_or_create_user(
         VALUES
         ($1, $2, $3, $4)
         ON CONFLICT (tg_id) DO UPDATE SET username = $3
-        RETURNING id, tg_id, username, created_at
+        RETURNING id, tg_id, username, created_at, link_code
         "#,
         id,
         tg_id,
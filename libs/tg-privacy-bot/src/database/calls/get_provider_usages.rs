@@ -0,0 +1,22 @@
+use crate::database::models::provider_usage::ProviderUsage;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+pub async fn get_provider_usages(
+    transaction: &mut Transaction<'_, Postgres>,
+    usage_id: &Uuid,
+) -> anyhow::Result<Vec<ProviderUsage>> {
+    let usages = sqlx::query_as!(
+        ProviderUsage,
+        r#"
+        SELECT id, usage_id, provider, requests, input_tokens, output_tokens, created_at, updated_at
+        FROM provider_usages
+        WHERE usage_id = $1
+        ORDER BY provider
+        "#,
+        usage_id
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(usages)
+}
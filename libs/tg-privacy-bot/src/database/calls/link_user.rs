@@ -0,0 +1,17 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+pub async fn link_user(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: &Uuid,
+    link_code: &str,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"UPDATE users SET link_code = $2 WHERE id = $1"#,
+        user_id,
+        link_code
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
@@ -1,6 +1,14 @@
+pub mod add_conversation_message;
+pub mod delete_conversation_history;
+pub mod delete_expired_conversation_messages;
+pub mod delete_linked_users;
+pub mod export_linked_users;
+pub mod get_conversation_history;
 pub mod get_or_create_usage;
 pub mod get_or_create_user;
 pub mod get_or_create_user_settings;
-pub mod increment_usage;
+pub mod get_provider_usages;
+pub mod link_user;
+pub mod record_provider_usage;
 pub mod update_user_settings_message_mode;
 pub mod update_user_settings_model_name;
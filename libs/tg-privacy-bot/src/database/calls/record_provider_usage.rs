@@ -0,0 +1,37 @@
+use ai_interfaces::clients::bulk::{ClientKind, TokenUsage};
+use chrono::Utc;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+pub async fn record_provider_usage(
+    transaction: &mut Transaction<'_, Postgres>,
+    usage_id: &Uuid,
+    provider: &ClientKind,
+    token_usage: &TokenUsage,
+) -> anyhow::Result<()> {
+    let id = Uuid::new_v4();
+    let now = Utc::now();
+    sqlx::query!(
+        r#"
+        INSERT INTO provider_usages
+        (id, usage_id, provider, requests, input_tokens, output_tokens, created_at, updated_at)
+        VALUES
+        ($1, $2, $3, 1, $4, $5, $6, $7)
+        ON CONFLICT (usage_id, provider) DO UPDATE SET
+            requests = provider_usages.requests + 1,
+            input_tokens = provider_usages.input_tokens + $4,
+            output_tokens = provider_usages.output_tokens + $5,
+            updated_at = $7
+        "#,
+        id,
+        usage_id,
+        provider.to_string(),
+        token_usage.input_tokens as i64,
+        token_usage.output_tokens as i64,
+        now,
+        now.clone()
+    )
+    .execute(&mut **transaction)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use ai_interfaces::clients::bulk::{Message, Role};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, Clone)]
+pub struct ConversationMessage {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub chat_id: i64,
+    pub model_name: String,
+    pub role: String,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ConversationMessage> for Message {
+    fn from(message: ConversationMessage) -> Self {
+        match message.role.as_str() {
+            "assistant" => Message::assistant(message.content),
+            _ => Message::user(message.content),
+        }
+    }
+}
+
+pub fn role_name(role: &Role) -> &'static str {
+    match role {
+        Role::System => "system",
+        Role::User => "user",
+        Role::Assistant => "assistant",
+    }
+}
@@ -1,5 +1,7 @@
+pub mod conversation_message;
 pub mod invite_code;
 pub mod message_mode;
+pub mod provider_usage;
 pub mod usage;
 pub mod user;
 pub mod user_settings;
@@ -8,4 +8,5 @@ pub struct User {
     pub tg_id: i64,
     pub username: String,
     pub created_at: DateTime<Utc>,
+    pub link_code: Option<String>,
 }
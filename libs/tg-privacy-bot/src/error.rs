@@ -9,6 +9,8 @@ pub enum Error {
     Sql(#[from] sqlx::Error),
     #[error(transparent)]
     Anyhow(#[from] AnyhowError),
+    #[error("Unauthorized")]
+    Unauthorized,
 }
 
 impl Error {
@@ -35,6 +37,7 @@ impl IntoResponse for Error {
             Error::Anyhow(_) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error.").into_response()
             }
+            Error::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized").into_response(),
         }
     }
 }
@@ -45,6 +48,7 @@ impl From<Error> for StatusCode {
         match error {
             Error::Sql(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Error::Anyhow(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Error::Unauthorized => StatusCode::UNAUTHORIZED,
         }
     }
 }
@@ -1,3 +1,4 @@
+use crate::database::calls::delete_conversation_history::delete_conversation_history;
 use crate::database::calls::get_or_create_usage::get_or_create_usage;
 use crate::database::calls::get_or_create_user::get_or_create_user;
 use crate::database::calls::get_or_create_user_settings::get_or_create_user_settings;
@@ -5,8 +6,8 @@ use crate::database::calls::update_user_settings_message_mode::update_user_setti
 use crate::database::calls::update_user_settings_model_name::update_user_settings_model_name;
 use crate::database::db_utils::get_pool;
 use crate::database::models::message_mode::MessageMode;
+use crate::handlers::select_model::selectable_models;
 use crate::HandlerResult;
-use ai_interfaces::models::base::ModelName;
 use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
 use teloxide::prelude::*;
 use teloxide::Bot;
@@ -36,6 +37,9 @@ pub async fn callback_handler(bot: Bot, query: CallbackQuery) -> HandlerResult {
                 MessageMode::from(data.replace("select_mode_", "").trim().to_string());
             update_user_settings_message_mode(&mut transaction, &user_settings.id, &message_mode)
                 .await?;
+            if message_mode == MessageMode::ResetOnEachMessage {
+                delete_conversation_history(&mut transaction, &user.id, None).await?;
+            }
             if let Some(message) = query.message {
                 bot.send_message(
                     message.chat().id,
@@ -46,9 +50,28 @@ pub async fn callback_handler(bot: Bot, query: CallbackQuery) -> HandlerResult {
             // Acknowledge the callback query
             bot.answer_callback_query(query.id).await?;
         } else if data.starts_with("select_model_") {
-            let model_name = ModelName::from(data.replace("select_model_", "").trim().to_string());
+            let model_name = match data
+                .replace("select_model_", "")
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| selectable_models().get(index).cloned())
+            {
+                Some(model_name) => model_name,
+                None => {
+                    bot.answer_callback_query(query.id)
+                        .text("This model is no longer available, please /selectmodel again")
+                        .await?;
+                    return Ok(());
+                }
+            };
             update_user_settings_model_name(&mut transaction, &user_settings.id, &model_name)
                 .await?;
+            if user_settings.message_mode == MessageMode::ResetOnModelChange
+                && user_settings.model_name != model_name
+            {
+                delete_conversation_history(&mut transaction, &user.id, None).await?;
+            }
             if let Some(message) = query.message {
                 bot.send_message(
                     message.chat().id,
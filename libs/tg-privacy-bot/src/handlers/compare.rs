@@ -0,0 +1,149 @@
+use crate::ai_models::ask::get_ai_client;
+use crate::ai_models::compare::compare_options;
+use crate::database::calls::get_or_create_usage::get_or_create_usage;
+use crate::database::calls::get_or_create_user::get_or_create_user;
+use crate::database::calls::record_provider_usage::record_provider_usage;
+use crate::database::db_utils::get_pool;
+use crate::{HandlerResult, MyDialogue};
+use ai_interfaces::clients::bulk::{Message as ChatMessage, Response};
+use ai_interfaces::clients::compare::Comparison;
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use std::collections::HashSet;
+use teloxide::prelude::*;
+use teloxide::types::InputFile;
+use teloxide::Bot;
+
+const MAX_MESSAGE_CHARS: usize = 4000;
+
+#[tracing::instrument(name = "compare", skip(bot, _dialogue))]
+pub async fn compare(
+    bot: Bot,
+    _dialogue: MyDialogue,
+    msg: Message,
+    question: String,
+) -> HandlerResult {
+    let pool = get_pool().await;
+    let mut transaction = create_txn(pool).await?;
+    match msg.from {
+        Some(ref from) => {
+            let username = from.username.clone().unwrap_or_default();
+            let tg_id = from.id.0;
+            let user = get_or_create_user(&mut transaction, tg_id as i64, &username).await?;
+            let usage = get_or_create_usage(&mut transaction, &user.id).await?;
+            commit_txn(transaction).await?;
+            if usage.over_limit() {
+                bot.send_message(msg.chat.id, "Usage limit exceeded")
+                    .await?;
+                return Ok(());
+            }
+            let question = question.trim().to_string();
+            if question.is_empty() {
+                bot.send_message(msg.chat.id, "Usage: /compare <question>")
+                    .await?;
+                return Ok(());
+            }
+            let client = get_ai_client();
+            let client_kinds: HashSet<_> = client.configured_kinds().into_iter().collect();
+            if client_kinds.is_empty() {
+                bot.send_message(msg.chat.id, "No AI providers are configured")
+                    .await?;
+                return Ok(());
+            }
+            let comparison = client
+                .compare(
+                    client_kinds,
+                    vec![ChatMessage::user(question)],
+                    &compare_options(),
+                )
+                .await;
+            let mut transaction = create_txn(pool).await?;
+            let judge = comparison.verdict.as_ref().map(|verdict| &verdict.judge);
+            for response in comparison.responses.iter().chain(judge) {
+                record_provider_usage(
+                    &mut transaction,
+                    &usage.id,
+                    &response.client,
+                    &response.usage,
+                )
+                .await?;
+            }
+            commit_txn(transaction).await?;
+            for response in &comparison.responses {
+                bot.send_message(msg.chat.id, format_response(&comparison, response))
+                    .await?;
+            }
+            if let Some(verdict) = &comparison.verdict {
+                bot.send_message(msg.chat.id, format_verdict(&comparison))
+                    .await?;
+                if let Some(error) = &verdict.judge.error {
+                    tracing::error!("{} judge failed: {}", verdict.judge.client, error);
+                }
+            }
+            match comparison.to_jsonl() {
+                Ok(jsonl) => {
+                    bot.send_document(
+                        msg.chat.id,
+                        InputFile::memory(jsonl).file_name("compare.jsonl"),
+                    )
+                    .await?;
+                }
+                Err(e) => tracing::error!("Failed to export comparison: {:?}", e),
+            }
+        }
+        None => {
+            bot.send_message(msg.chat.id, "Cannot get user data")
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+fn format_response(comparison: &Comparison, response: &Response) -> String {
+    let mut header = format!(
+        "{} ({}) · {}ms",
+        response.client, response.model, response.latency_ms
+    );
+    if let Some(cost_usd) = response.cost_usd {
+        header.push_str(&format!(" · ${:.4}", cost_usd));
+    }
+    if let Some(score) = comparison.score(response.client) {
+        header.push_str(&format!(" · {}/10", score.score));
+    }
+    let body = match (&response.answer, &response.error) {
+        (Some(answer), _) => answer.as_str(),
+        (None, Some(error)) => error.as_str(),
+        (None, None) => "No answer",
+    };
+    truncate(format!("{}\n\n{}", header, body))
+}
+
+fn format_verdict(comparison: &Comparison) -> String {
+    let Some(verdict) = &comparison.verdict else {
+        return String::new();
+    };
+    if let Some(merged) = verdict.merged() {
+        return truncate(format!("Merged by {}\n\n{}", verdict.judge.model, merged));
+    }
+    if verdict.scores.is_empty() {
+        return format!("{} could not score the answers", verdict.judge.model);
+    }
+    let mut scores = verdict.scores.clone();
+    scores.sort_by(|a, b| b.score.total_cmp(&a.score));
+    let mut text = format!("Scored by {}\n", verdict.judge.model);
+    for score in scores {
+        text.push_str(&format!(
+            "\n{}: {}/10 {}",
+            score.client, score.score, score.reason
+        ));
+    }
+    truncate(text)
+}
+
+fn truncate(text: String) -> String {
+    if text.chars().count() <= MAX_MESSAGE_CHARS {
+        return text;
+    }
+    let mut truncated: String = text.chars().take(MAX_MESSAGE_CHARS - 1).collect();
+    truncated.push('…');
+    truncated
+}
@@ -1,15 +1,12 @@
 use crate::{HandlerResult, MyDialogue};
 use teloxide::prelude::*;
 
-// const HELP_TEXT: &str = r#"
-// /select_mode - Select message context mode
-// /select_model - Select a different model
-// /info - Show current settings
-// /help - This message
-// "#;
-
 const HELP_TEXT: &str = r#"
-/select_model - Select a different model
+/selectmode - Select message context mode
+/selectmodel - Select a different model
+/reset - Start a new conversation in this chat
+/forget - Delete all your stored conversations
+/compare <question> - Ask every model and compare the answers
 /info - Show current settings
 /help - This message
 "#;
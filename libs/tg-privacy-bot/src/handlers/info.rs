@@ -1,6 +1,7 @@
 use crate::database::calls::get_or_create_usage::get_or_create_usage;
 use crate::database::calls::get_or_create_user::get_or_create_user;
 use crate::database::calls::get_or_create_user_settings::get_or_create_user_settings;
+use crate::database::calls::get_provider_usages::get_provider_usages;
 use crate::database::db_utils::get_pool;
 use crate::{HandlerResult, MyDialogue};
 use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
@@ -19,19 +20,26 @@ pub async fn info(bot: Bot, _dialogue: MyDialogue, msg: Message) -> HandlerResul
             let user = get_or_create_user(&mut transaction, tg_id as i64, &username).await?;
             let usage = get_or_create_usage(&mut transaction, &user.id).await?;
             let user_settings = get_or_create_user_settings(&mut transaction, &user.id).await?;
+            let provider_usages = get_provider_usages(&mut transaction, &usage.id).await?;
             commit_txn(transaction).await?;
-            // let response = format!(
-            //     r#"
-            //     Mode: {} | Model Name: {}
-            //     "#,
-            //     user_settings.message_mode, user_settings.model_name
-            // );
-            let response = format!(
+            let mut response = format!(
                 r#"
-                Model Name: {} | Usage {} / {}
+                Mode: {} | Model Name: {} | Usage {} / {}
                 "#,
-                user_settings.model_name, usage.usage, usage.usage_limit
+                user_settings.message_mode,
+                user_settings.model_name,
+                usage.usage,
+                usage.usage_limit
             );
+            for provider_usage in provider_usages {
+                response.push_str(&format!(
+                    "{}: {} requests | {} input / {} output tokens\n",
+                    provider_usage.provider,
+                    provider_usage.requests,
+                    provider_usage.input_tokens,
+                    provider_usage.output_tokens
+                ));
+            }
             let _r = bot.send_message(msg.chat.id, response).await;
         }
         None => {
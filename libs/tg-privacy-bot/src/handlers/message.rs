@@ -1,10 +1,19 @@
-use crate::ai_models::open_ai::ask;
+use crate::ai_models::ask::ask;
+use crate::ai_models::history::{
+    build_conversation, history_max_messages, history_token_budget, history_ttl,
+};
+use crate::ai_models::user_error::user_facing_error;
+use crate::database::calls::add_conversation_message::add_conversation_message;
+use crate::database::calls::get_conversation_history::get_conversation_history;
 use crate::database::calls::get_or_create_usage::get_or_create_usage;
 use crate::database::calls::get_or_create_user::get_or_create_user;
 use crate::database::calls::get_or_create_user_settings::get_or_create_user_settings;
-use crate::database::calls::increment_usage::increment_usage;
+use crate::database::calls::record_provider_usage::record_provider_usage;
 use crate::database::db_utils::get_pool;
+use crate::database::models::message_mode::MessageMode;
 use crate::HandlerResult;
+use ai_interfaces::clients::bulk::{ClientKind, Message as ChatMessage, TokenUsage};
+use chrono::Utc;
 use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
 use teloxide::prelude::*;
 use teloxide::Bot;
@@ -26,10 +35,71 @@ pub async fn message_handler(bot: Bot, msg: Message) -> HandlerResult {
                     .await?;
                 return Ok(());
             }
-            increment_usage(&mut transaction, &usage.id).await?;
+            let keep_history = user_settings.message_mode != MessageMode::ResetOnEachMessage;
+            let history = if keep_history {
+                get_conversation_history(
+                    &mut transaction,
+                    &user.id,
+                    msg.chat.id.0,
+                    history_ttl(),
+                    history_max_messages(),
+                )
+                .await?
+            } else {
+                Vec::new()
+            };
             commit_txn(transaction).await?;
-            let response = ask(message, user_settings.model_name).await?;
-            bot.send_message(msg.chat.id, response).await?;
+            let asked_at = Utc::now();
+            let messages = build_conversation(history, message.clone(), history_token_budget());
+            let provider = ClientKind::from(&user_settings.model_name);
+            match ask(messages, user_settings.model_name.clone()).await {
+                Ok(completion) => {
+                    let mut transaction = create_txn(pool).await?;
+                    record_provider_usage(
+                        &mut transaction,
+                        &usage.id,
+                        &provider,
+                        &completion.usage,
+                    )
+                    .await?;
+                    if keep_history {
+                        add_conversation_message(
+                            &mut transaction,
+                            &user.id,
+                            msg.chat.id.0,
+                            &user_settings.model_name,
+                            &ChatMessage::user(message),
+                            asked_at,
+                        )
+                        .await?;
+                        add_conversation_message(
+                            &mut transaction,
+                            &user.id,
+                            msg.chat.id.0,
+                            &user_settings.model_name,
+                            &ChatMessage::assistant(completion.message.content.clone()),
+                            Utc::now(),
+                        )
+                        .await?;
+                    }
+                    commit_txn(transaction).await?;
+                    bot.send_message(msg.chat.id, completion.message.content)
+                        .await?;
+                }
+                Err(e) => {
+                    tracing::error!("{} completion failed: {:?}", provider, e);
+                    let mut transaction = create_txn(pool).await?;
+                    record_provider_usage(
+                        &mut transaction,
+                        &usage.id,
+                        &provider,
+                        &TokenUsage::default(),
+                    )
+                    .await?;
+                    commit_txn(transaction).await?;
+                    bot.send_message(msg.chat.id, user_facing_error(&e)).await?;
+                }
+            }
         }
         None => {
             bot.send_message(msg.chat.id, "Cannot get user data")
@@ -1,9 +1,12 @@
 pub mod callback;
 pub mod chosen_inline_result;
+pub mod compare;
+pub mod forget;
 pub mod help;
 pub mod info;
 pub mod inline;
 pub mod message;
+pub mod reset;
 pub mod select_mode;
 pub mod select_model;
 pub mod start;
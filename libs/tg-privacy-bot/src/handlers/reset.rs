@@ -0,0 +1,29 @@
+use crate::database::calls::delete_conversation_history::delete_conversation_history;
+use crate::database::calls::get_or_create_user::get_or_create_user;
+use crate::database::db_utils::get_pool;
+use crate::{HandlerResult, MyDialogue};
+use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
+use teloxide::prelude::*;
+use teloxide::Bot;
+
+#[tracing::instrument(name = "reset", skip(bot, _dialogue))]
+pub async fn reset(bot: Bot, _dialogue: MyDialogue, msg: Message) -> HandlerResult {
+    let pool = get_pool().await;
+    let mut transaction = create_txn(pool).await?;
+    match msg.from {
+        Some(ref from) => {
+            let username = from.username.clone().unwrap_or_default();
+            let tg_id = from.id.0;
+            let user = get_or_create_user(&mut transaction, tg_id as i64, &username).await?;
+            delete_conversation_history(&mut transaction, &user.id, Some(msg.chat.id.0)).await?;
+            commit_txn(transaction).await?;
+            bot.send_message(msg.chat.id, "Started a new conversation")
+                .await?;
+        }
+        None => {
+            bot.send_message(msg.chat.id, "Cannot get user data")
+                .await?;
+        }
+    }
+    Ok(())
+}
@@ -9,7 +9,6 @@ use teloxide::prelude::*;
 use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
 use teloxide::Bot;
 
-#[allow(dead_code)]
 #[tracing::instrument(name = "select_mode", skip(bot, _dialogue))]
 pub async fn select_mode(bot: Bot, _dialogue: MyDialogue, msg: Message) -> HandlerResult {
     let pool = get_pool().await;
@@ -47,7 +46,7 @@ pub async fn select_mode(bot: Bot, _dialogue: MyDialogue, msg: Message) -> Handl
     ]);
 
     // Send a message with the inline keyboard
-    bot.send_message(msg.chat.id, "Please operation mode:")
+    bot.send_message(msg.chat.id, "Please select operation mode:")
         .reply_markup(keyboard)
         .await?;
     Ok(())
@@ -1,20 +1,34 @@
+use crate::ai_models::ask::get_ai_client;
 use crate::{HandlerResult, MyDialogue};
+use ai_interfaces::clients::bulk::ClientKind;
 use ai_interfaces::models::base::ModelName;
-use enum_iterator::all;
 use teloxide::prelude::*;
 use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
 use teloxide::Bot;
 
+/// Buttons carry the model's position in this list, Telegram caps callback data at 64 bytes
+/// and openai-compatible model names can be longer than that.
+pub fn selectable_models() -> Vec<ModelName> {
+    let mut model_names = ModelName::known();
+    let client = get_ai_client();
+    if client
+        .configured_kinds()
+        .contains(&ClientKind::OpenAiCompatible)
+    {
+        model_names.push(client.default_model(ClientKind::OpenAiCompatible));
+    }
+    model_names
+}
+
 #[tracing::instrument(name = "select_model", skip(bot, _dialogue))]
 pub async fn select_model(bot: Bot, _dialogue: MyDialogue, msg: Message) -> HandlerResult {
-    let model_names = all::<ModelName>().collect::<Vec<_>>();
-
-    let model_names_keyboard = model_names
+    let model_names_keyboard = selectable_models()
         .iter()
-        .map(|model_name| {
+        .enumerate()
+        .map(|(index, model_name)| {
             vec![InlineKeyboardButton::callback(
                 model_name.to_string(),
-                format!("select_model_{}", model_name),
+                format!("select_model_{}", index),
             )]
         })
         .collect::<Vec<_>>();
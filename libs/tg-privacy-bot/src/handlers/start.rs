@@ -1,12 +1,27 @@
 use crate::database::calls::get_or_create_usage::get_or_create_usage;
 use crate::database::calls::get_or_create_user::get_or_create_user;
 use crate::database::calls::get_or_create_user_settings::get_or_create_user_settings;
+use crate::database::calls::link_user::link_user;
 use crate::database::db_utils::get_pool;
 use crate::{HandlerResult, MyDialogue};
 use database_utils::utils::instrument_wrapper::{commit_txn, create_txn};
 use teloxide::prelude::*;
 
-pub async fn start(bot: Bot, _dialogue: MyDialogue, msg: Message) -> HandlerResult {
+/// Telegram only passes `[A-Za-z0-9_-]` and at most 64 characters as the `/start` payload.
+fn is_link_code(payload: &str) -> bool {
+    !payload.is_empty()
+        && payload.len() <= 64
+        && payload
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+pub async fn start(
+    bot: Bot,
+    _dialogue: MyDialogue,
+    msg: Message,
+    payload: String,
+) -> HandlerResult {
     let pool = get_pool().await;
     let mut transaction = create_txn(pool).await?;
     match msg.from {
@@ -17,11 +32,23 @@ pub async fn start(bot: Bot, _dialogue: MyDialogue, msg: Message) -> HandlerResu
             let user = get_or_create_user(&mut transaction, tg_id as i64, &username).await?;
             let _ = get_or_create_usage(&mut transaction, &user.id).await?;
             let _ = get_or_create_user_settings(&mut transaction, &user.id).await?;
+            let linked = is_link_code(payload.trim());
+            if linked {
+                link_user(&mut transaction, &user.id, payload.trim()).await?;
+            }
             commit_txn(transaction).await?;
             let response = r#"
                 Welcome to BlockMesh Network AI Privacy bot
                 "#;
             let _r = bot.send_message(msg.chat.id, response).await;
+            if linked {
+                let _r = bot
+                    .send_message(
+                        msg.chat.id,
+                        "Linked to your BlockMesh account, its data export includes your chats with this bot and deleting the account deletes them too",
+                    )
+                    .await;
+            }
         }
         None => {
             bot.send_message(msg.chat.id, "Cannot get user data")
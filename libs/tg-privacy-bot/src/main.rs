@@ -1,16 +1,19 @@
+mod admin;
 mod ai_models;
 mod commands;
 mod database;
 mod error;
 mod handlers;
 
+use crate::ai_models::history::history_ttl;
 use crate::commands::Commands;
+use crate::database::calls::delete_expired_conversation_messages::delete_expired_conversation_messages;
 use crate::database::db_utils::get_pool;
 use crate::error::Error;
 use crate::handlers::callback::callback_handler;
 use crate::handlers::inline::inline_query_handler;
 use askama_axum::IntoResponse;
-use axum::routing::get;
+use axum::routing::{delete, get};
 use axum::{Extension, Router};
 use block_mesh_common::env::load_dotenv::load_dotenv;
 use database_utils::utils::connection::unlimited_pool::unlimited_pool;
@@ -21,6 +24,7 @@ use http::StatusCode;
 use logger_general::tracing::setup_tracing_stdout_only_with_sentry;
 use sqlx::PgPool;
 use std::net::SocketAddr;
+use std::time::Duration;
 use std::{env, mem, process};
 use teloxide::dispatching::dialogue::InMemStorage;
 use teloxide::dispatching::{dialogue, UpdateHandler};
@@ -46,10 +50,13 @@ fn bot_schema() -> UpdateHandler<Box<dyn std::error::Error + Send + Sync + 'stat
     let command_handler = teloxide::filter_command::<Commands, _>().branch(
         case![State::Start]
             .branch(case![Commands::Help].endpoint(handlers::help::help))
-            // .branch(case![Commands::SelectMode].endpoint(handlers::select_mode::select_mode))
+            .branch(case![Commands::SelectMode].endpoint(handlers::select_mode::select_mode))
             .branch(case![Commands::SelectModel].endpoint(handlers::select_model::select_model))
+            .branch(case![Commands::Reset].endpoint(handlers::reset::reset))
+            .branch(case![Commands::Forget].endpoint(handlers::forget::forget))
+            .branch(case![Commands::Compare(question)].endpoint(handlers::compare::compare))
             .branch(case![Commands::Info].endpoint(handlers::info::info))
-            .branch(case![Commands::Start].endpoint(handlers::start::start)),
+            .branch(case![Commands::Start(payload)].endpoint(handlers::start::start)),
     );
 
     let message_handler = Update::filter_message().branch(command_handler);
@@ -141,6 +148,20 @@ pub async fn dispatch_bot(bot: Bot) {
         .await
 }
 
+#[tracing::instrument(name = "expire_history", skip_all)]
+pub async fn expire_history(pool: &PgPool) {
+    loop {
+        match delete_expired_conversation_messages(pool, history_ttl()).await {
+            Ok(deleted) if deleted > 0 => {
+                tracing::info!("Deleted {} expired conversation messages", deleted)
+            }
+            Ok(_) => {}
+            Err(e) => tracing::error!("Failed to delete expired conversation messages: {:?}", e),
+        }
+        tokio::time::sleep(Duration::from_secs(600)).await;
+    }
+}
+
 #[tracing::instrument(name = "run", skip_all, ret, err)]
 async fn run() -> anyhow::Result<()> {
     load_dotenv();
@@ -157,7 +178,9 @@ async fn run() -> anyhow::Result<()> {
         .route("/", get(server_health))
         .route("/server_health", get(server_health))
         .route("/db_health", get(db_health))
-        .route("/version", get(version));
+        .route("/version", get(version))
+        .route("/users/export", get(admin::export_users))
+        .route("/users", delete(admin::delete_users));
     let cors = CorsLayer::permissive();
 
     let app = Router::new()
@@ -170,9 +193,11 @@ async fn run() -> anyhow::Result<()> {
     tracing::info!("Listening on {}", listener.local_addr()?);
     let server_task = run_server(listener, app);
     let bot_task = dispatch_bot(bot);
+    let history_task = expire_history(db_pool);
 
     tokio::select! {
         o = bot_task => panic!("bot_task exit {:?}", o),
-        o = server_task => panic!("server_task exit {:?}", o)
+        o = server_task => panic!("server_task exit {:?}", o),
+        o = history_task => panic!("history_task exit {:?}", o)
     }
 }